@@ -97,8 +97,18 @@ pub enum ExprValue {
     DoubleConstant(f64),
     // {}
     EmptyUnion,
+    /// `exists variable. domain`
+    Exists {
+        variable: Expr, // symbol
+        domain: Expr,
+    },
     /// `1.0f`
     FloatConstant(f32),
+    /// `forall variable. domain`
+    Forall {
+        variable: Expr, // symbol
+        domain: Expr,
+    },
     /// `function(arguments)`
     FunctionCall {
         function: Expr,
@@ -211,6 +221,7 @@ pub enum BinaryOperator {
     VectorGt,
     VectorLe,
     VectorLt,
+    WOk,
     Xor,
 }
 
@@ -575,6 +586,20 @@ pub fn empty_union(typ: Type, st: &SymbolTable) -> Self {
         expr!(EmptyUnion, typ)
     }
 
+    /// `exists variable. domain`
+    pub fn exists(variable: Expr, domain: Expr) -> Self {
+        assert!(variable.is_symbol(), "Quantified variable must be a symbol: {variable:?}");
+        assert!(domain.typ.is_bool());
+        expr!(Exists { variable, domain }, Type::bool())
+    }
+
+    /// `forall variable. domain`
+    pub fn forall(variable: Expr, domain: Expr) -> Self {
+        assert!(variable.is_symbol(), "Quantified variable must be a symbol: {variable:?}");
+        assert!(domain.typ.is_bool());
+        expr!(Forall { variable, domain }, Type::bool())
+    }
+
     /// `1.0f`
     pub fn float_constant(c: f32) -> Self {
         expr!(FloatConstant(c), Type::float())
@@ -954,7 +979,7 @@ fn typecheck_binop_args(op: BinaryOperator, lhs: &Expr, rhs: &Expr) -> bool {
                 (lhs.typ == rhs.typ && lhs.typ.is_integer())
                     || (lhs.typ.is_pointer() && rhs.typ.is_integer())
             }
-            ROk => lhs.typ.is_pointer() && rhs.typ.is_c_size_t(),
+            ROk | WOk => lhs.typ.is_pointer() && rhs.typ.is_c_size_t(),
             VectorEqual | VectorNotequal | VectorGe | VectorLe | VectorGt | VectorLt => {
                 unreachable!(
                     "vector comparison operators must be typechecked by `typecheck_vector_cmp_expr`"
@@ -993,7 +1018,7 @@ fn binop_return_type(op: BinaryOperator, lhs: &Expr, rhs: &Expr) -> Type {
                 let struct_type = arithmetic_overflow_result_type(lhs.typ.clone());
                 Type::struct_tag(struct_type.tag().unwrap())
             }
-            ROk => Type::bool(),
+            ROk | WOk => Type::bool(),
             // Vector comparisons
             VectorEqual | VectorNotequal | VectorGe | VectorLe | VectorGt | VectorLt => {
                 unreachable!(
@@ -1193,6 +1218,11 @@ pub fn r_ok(self, e: Expr) -> Expr {
         self.binop(ROk, e)
     }
 
+    /// `__CPROVER_w_ok(self, e)`
+    pub fn w_ok(self, e: Expr) -> Expr {
+        self.binop(WOk, e)
+    }
+
     // Regular comparison operators (e.g., `==` or `<`) don't work over SIMD vectors.
     // Instead, we must use the dedicated `vector-<op>` Irep operators.
 
@@ -1630,3 +1660,312 @@ pub fn struct_field_exprs(&self, symbol_table: &SymbolTable) -> BTreeMap<Interne
         exprs
     }
 }
+
+impl Expr {
+    /// Replace every symbol that has an entry in `values` by its value. This is used to inline
+    /// the body of a function in an expression, where the symbols are the locals of the function.
+    ///
+    /// Statement expressions are left untouched, and the variables bound by quantifiers must
+    /// not have an entry in `values`.
+    pub fn substitute_symbols(self, values: &BTreeMap<InternedString, Expr>) -> Expr {
+        let sub = |e: Expr| e.substitute_symbols(values);
+        let sub_all = |es: Vec<Expr>| es.into_iter().map(sub).collect();
+        let value = match *self.value {
+            Symbol { identifier } => match values.get(&identifier) {
+                Some(value) => return value.clone(),
+                None => Symbol { identifier },
+            },
+            AddressOf(e) => AddressOf(sub(e)),
+            Array { elems } => Array { elems: sub_all(elems) },
+            ArrayOf { elem } => ArrayOf { elem: sub(elem) },
+            Assign { left, right } => Assign { left: sub(left), right: sub(right) },
+            BinOp { op, lhs, rhs } => BinOp { op, lhs: sub(lhs), rhs: sub(rhs) },
+            ByteExtract { e, offset } => ByteExtract { e: sub(e), offset },
+            Dereference(e) => Dereference(sub(e)),
+            Exists { variable, domain } => Exists { variable, domain: sub(domain) },
+            Forall { variable, domain } => Forall { variable, domain: sub(domain) },
+            FunctionCall { function, arguments } => {
+                FunctionCall { function: sub(function), arguments: sub_all(arguments) }
+            }
+            If { c, t, e } => If { c: sub(c), t: sub(t), e: sub(e) },
+            Index { array, index } => Index { array: sub(array), index: sub(index) },
+            Member { lhs, field } => Member { lhs: sub(lhs), field },
+            SelfOp { op, e } => SelfOp { op, e: sub(e) },
+            Struct { values } => Struct { values: sub_all(values) },
+            Typecast(e) => Typecast(sub(e)),
+            Union { value, field } => Union { value: sub(value), field },
+            UnOp { op, e } => UnOp { op, e: sub(e) },
+            Vector { elems } => Vector { elems: sub_all(elems) },
+            value @ (BoolConstant(_)
+            | CBoolConstant(_)
+            | DoubleConstant(_)
+            | EmptyUnion
+            | FloatConstant(_)
+            | IntConstant(_)
+            | Nondet
+            | PointerConstant(_)
+            | StatementExpression { .. }
+            | StringConstant { .. }) => value,
+        };
+        Expr { value: Box::new(value), ..self }
+    }
+}
+
+/// Simplification
+impl Expr {
+    /// Simplify the expression bottom-up, to reduce the size of the formulas that CBMC generates
+    /// for the patterns that codegen emits repeatedly:
+    ///  - Integer arithmetic, bitwise operations and comparisons over constants are folded,
+    ///    using the wrapping semantics of the bit-vector type of the operands.
+    ///  - Boolean operators and conditionals are folded when an operand is a constant.
+    ///  - Typecasts to the type of the operand are removed, casts of integer constants are
+    ///    folded, and a widening cast followed by a cast back to the original type is removed.
+    ///  - Member accesses of struct and union initializers are replaced by the field value.
+    ///
+    /// Operands are only dropped when they have no side effects.
+    pub fn simplify(self, st: &SymbolTable) -> Expr {
+        let simplify = |e: Expr| e.simplify(st);
+        let simplify_all = |es: Vec<Expr>| es.into_iter().map(simplify).collect();
+        let value = match *self.value {
+            AddressOf(e) => AddressOf(simplify(e)),
+            Array { elems } => Array { elems: simplify_all(elems) },
+            ArrayOf { elem } => ArrayOf { elem: simplify(elem) },
+            Assign { left, right } => Assign { left: simplify(left), right: simplify(right) },
+            BinOp { op, lhs, rhs } => BinOp { op, lhs: simplify(lhs), rhs: simplify(rhs) },
+            ByteExtract { e, offset } => ByteExtract { e: simplify(e), offset },
+            Dereference(e) => Dereference(simplify(e)),
+            Exists { variable, domain } => Exists { variable, domain: simplify(domain) },
+            Forall { variable, domain } => Forall { variable, domain: simplify(domain) },
+            FunctionCall { function, arguments } => {
+                FunctionCall { function: simplify(function), arguments: simplify_all(arguments) }
+            }
+            If { c, t, e } => If { c: simplify(c), t: simplify(t), e: simplify(e) },
+            Index { array, index } => Index { array: simplify(array), index: simplify(index) },
+            Member { lhs, field } => Member { lhs: simplify(lhs), field },
+            SelfOp { op, e } => SelfOp { op, e: simplify(e) },
+            StatementExpression { statements } => StatementExpression {
+                statements: statements.into_iter().map(|s| s.simplify(st)).collect(),
+            },
+            Struct { values } => Struct { values: simplify_all(values) },
+            Typecast(e) => Typecast(simplify(e)),
+            Union { value, field } => Union { value: simplify(value), field },
+            UnOp { op, e } => UnOp { op, e: simplify(e) },
+            Vector { elems } => Vector { elems: simplify_all(elems) },
+            value @ (BoolConstant(_)
+            | CBoolConstant(_)
+            | DoubleConstant(_)
+            | EmptyUnion
+            | FloatConstant(_)
+            | IntConstant(_)
+            | Nondet
+            | PointerConstant(_)
+            | Symbol { .. }
+            | StringConstant { .. }) => value,
+        };
+        let expr = Expr { value: Box::new(value), ..self };
+        match expr.simplify_node(st) {
+            Some(simplified) => Expr {
+                location: if simplified.location.is_none() {
+                    expr.location
+                } else {
+                    simplified.location
+                },
+                size_of_annotation: simplified.size_of_annotation.or(expr.size_of_annotation),
+                ..simplified
+            },
+            None => expr,
+        }
+    }
+
+    /// Apply the simplification rules to the root of the expression, assuming that its operands
+    /// are already simplified. Returns `None` if no rule applies.
+    fn simplify_node(&self, st: &SymbolTable) -> Option<Expr> {
+        let mm = st.machine_model();
+        match &*self.value {
+            BinOp { op, lhs, rhs } => match (&*lhs.value, &*rhs.value) {
+                (IntConstant(l), IntConstant(r)) if lhs.typ == rhs.typ => {
+                    let width = lhs.typ.native_width(mm)?;
+                    let signed = lhs.typ.is_signed(mm);
+                    let (l, r) = (wrap_int(l, width, signed), wrap_int(r, width, signed));
+                    let result = match op {
+                        Plus => l + r,
+                        Minus => l - r,
+                        Mult => l * r,
+                        Bitand => l & r,
+                        Bitor => l | r,
+                        Bitxor => l ^ r,
+                        Equal => return Some(Expr::bool_constant(l == r)),
+                        Notequal => return Some(Expr::bool_constant(l != r)),
+                        Ge => return Some(Expr::bool_constant(l >= r)),
+                        Gt => return Some(Expr::bool_constant(l > r)),
+                        Le => return Some(Expr::bool_constant(l <= r)),
+                        Lt => return Some(Expr::bool_constant(l < r)),
+                        _ => return None,
+                    };
+                    Some(Expr::int_constant(wrap_int(&result, width, signed), self.typ.clone()))
+                }
+                (BoolConstant(l), BoolConstant(r)) => {
+                    let result = match op {
+                        And => *l && *r,
+                        Or => *l || *r,
+                        Implies => !*l || *r,
+                        Xor => *l != *r,
+                        Equal => *l == *r,
+                        Notequal => *l != *r,
+                        _ => return None,
+                    };
+                    Some(Expr::bool_constant(result))
+                }
+                (BoolConstant(c), _) | (_, BoolConstant(c)) => {
+                    let (constant, other) =
+                        if matches!(*lhs.value, BoolConstant(_)) { (lhs, rhs) } else { (rhs, lhs) };
+                    match (op, c) {
+                        (And, true) | (Or, false) => Some(other.clone()),
+                        (And, false) | (Or, true) if !other.is_side_effect() => {
+                            Some(constant.clone())
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            },
+            UnOp { op: Not, e } => match &*e.value {
+                BoolConstant(c) => Some(Expr::bool_constant(!c)),
+                UnOp { op: Not, e } if e.typ.is_bool() => Some(e.clone()),
+                _ => None,
+            },
+            If { c, t, e } => match &*c.value {
+                BoolConstant(true) if !e.is_side_effect() => Some(t.clone()),
+                BoolConstant(false) if !t.is_side_effect() => Some(e.clone()),
+                _ => None,
+            },
+            Typecast(e) => {
+                if e.typ == self.typ {
+                    return Some(e.clone());
+                }
+                match &*e.value {
+                    IntConstant(i) if self.typ.is_integer() => {
+                        let width = self.typ.native_width(mm)?;
+                        let value = wrap_int(i, width, self.typ.is_signed(mm));
+                        Some(Expr::int_constant(value, self.typ.clone()))
+                    }
+                    // `(T)(U)x` where `x: T` and `U` is at least as wide as `T`.
+                    Typecast(inner) if inner.typ == self.typ && self.typ.is_integer() => {
+                        let width = self.typ.native_width(mm)?;
+                        let widened = e.typ.native_width(mm)?;
+                        (e.typ.is_integer() && widened >= width).then(|| inner.clone())
+                    }
+                    _ => None,
+                }
+            }
+            Member { lhs, field } => match &*lhs.value {
+                Struct { values } => {
+                    let components = lhs.typ.lookup_components(st)?;
+                    if components.len() != values.len() {
+                        return None;
+                    }
+                    let index =
+                        components.iter().position(|c| !c.is_padding() && c.name() == *field)?;
+                    let droppable = |(i, v): (usize, &Expr)| {
+                        i == index || !v.is_side_effect() || matches!(*v.value, Nondet)
+                    };
+                    let value = &values[index];
+                    (value.typ == self.typ && values.iter().enumerate().all(droppable))
+                        .then(|| value.clone())
+                }
+                Union { value, field: init } if init == field && value.typ == self.typ => {
+                    Some(value.clone())
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// The value of `i` in a bit-vector of the given `width` and signedness, i.e., `i` modulo
+/// `2^width`, in the range of the bit-vector.
+fn wrap_int(i: &BigInt, width: u64, signed: bool) -> BigInt {
+    let modulus = BigInt::from(1) << width;
+    let value = ((i % &modulus) + &modulus) % &modulus;
+    if signed && value >= (&modulus >> 1) { value - modulus } else { value }
+}
+
+#[cfg(test)]
+mod simplify_tests {
+    use super::*;
+    use crate::goto_program::Symbol as GotoSymbol;
+    use crate::machine_model::test_util::machine_model_test_stub;
+
+    fn int_value(e: &Expr) -> i64 {
+        e.int_constant_value().unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn check_fold_arithmetic() {
+        let st = SymbolTable::new(machine_model_test_stub());
+        let u8 = Type::unsigned_int(8);
+        let sum = Expr::int_constant(200, u8.clone()).plus(Expr::int_constant(100, u8.clone()));
+        assert_eq!(int_value(&sum.simplify(&st)), 44);
+        let i8 = Type::signed_int(8);
+        let sub = Expr::int_constant(-100, i8.clone()).sub(Expr::int_constant(100, i8.clone()));
+        assert_eq!(int_value(&sub.simplify(&st)), 56);
+        let cmp = Expr::int_constant(-1, i8.clone()).lt(Expr::int_constant(0, i8));
+        assert!(matches!(*cmp.simplify(&st).value, BoolConstant(true)));
+        let x = Expr::symbol_expression("x", u8.clone());
+        let div = x.clone().div(Expr::int_constant(0, u8));
+        assert!(matches!(*div.simplify(&st).value, BinOp { op: Div, .. }));
+    }
+
+    #[test]
+    fn check_fold_booleans() {
+        let st = SymbolTable::new(machine_model_test_stub());
+        let b = Expr::symbol_expression("b", Type::bool());
+        let and = Expr::bool_true().and(b.clone());
+        assert!(matches!(*and.simplify(&st).value, Symbol { .. }));
+        let or = b.clone().or(Expr::bool_true());
+        assert!(matches!(*or.simplify(&st).value, BoolConstant(true)));
+        let not = Expr::bool_false().not().not().not();
+        assert!(matches!(*not.simplify(&st).value, BoolConstant(true)));
+        let call = Expr::symbol_expression("f", Type::code(vec![], Type::bool())).call(vec![]);
+        let effectful = Expr::bool_false().and(call);
+        assert!(matches!(*effectful.simplify(&st).value, BinOp { op: And, .. }));
+    }
+
+    #[test]
+    fn check_simplify_casts() {
+        let st = SymbolTable::new(machine_model_test_stub());
+        let u8 = Type::unsigned_int(8);
+        let x = Expr::symbol_expression("x", u8.clone());
+        let round_trip = x.cast_to(Type::unsigned_int(32)).cast_to(u8.clone());
+        assert!(matches!(*round_trip.simplify(&st).value, Symbol { .. }));
+        let truncated = Expr::int_constant(300, Type::unsigned_int(32)).cast_to(u8.clone());
+        let truncated = truncated.simplify(&st);
+        assert_eq!(int_value(&truncated), 44);
+        assert_eq!(truncated.typ(), &u8);
+        let y = Expr::symbol_expression("y", Type::unsigned_int(32));
+        let narrowing = y.cast_to(u8).cast_to(Type::unsigned_int(32));
+        assert!(matches!(*narrowing.simplify(&st).value, Typecast(_)));
+    }
+
+    #[test]
+    fn check_forward_struct_fields() {
+        let mut st = SymbolTable::new(machine_model_test_stub());
+        st.insert(GotoSymbol::struct_type(
+            "Pair",
+            "Pair".into(),
+            vec![
+                DatatypeComponent::field("first", Type::unsigned_int(32)),
+                DatatypeComponent::padding("pad", 32),
+                DatatypeComponent::field("second", Type::bool()),
+            ],
+        ));
+        let tag = Type::struct_tag("Pair");
+        let x = Expr::symbol_expression("x", Type::unsigned_int(32));
+        let pair = Expr::struct_expr_from_values(tag, vec![x, Expr::bool_true().not()], &st);
+        let first = pair.clone().member("first", &st).simplify(&st);
+        assert!(matches!(*first.value, Symbol { .. }));
+        let second = pair.member("second", &st).simplify(&st);
+        assert!(matches!(*second.value, BoolConstant(false)));
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 use self::StmtBody::*;
-use super::{BuiltinFn, Expr, Location};
+use super::{BuiltinFn, Expr, Location, SymbolTable};
 use crate::{InternString, InternedString};
 use std::fmt::Debug;
 
@@ -66,6 +66,9 @@ pub enum StmtBody {
     Deinit(Expr),
     /// `e;`
     Expression(Expr),
+    /// `__CPROVER_fence("WWfence", "RRfence", "RWfence", "WRfence");`
+    /// No memory access is reordered across the fence under a weak memory model.
+    Fence,
     // `for (init; cond; update) {body}`
     For {
         init: Stmt,
@@ -80,7 +83,14 @@ pub enum StmtBody {
         arguments: Vec<Expr>,
     },
     /// `goto dest;`
-    Goto(InternedString),
+    Goto {
+        dest: InternedString,
+        /// The invariant of the loop if this is the back edge of an annotated loop.
+        loop_invariants: Option<Expr>,
+        /// The components of the measure that must decrease at every iteration of the loop, in
+        /// lexicographic order, if this is the back edge of an annotated loop.
+        loop_decreases: Option<Vec<Expr>>,
+    },
     /// `if (i) { t } else { e }`
     Ifthenelse {
         i: Expr,
@@ -96,6 +106,9 @@ pub enum StmtBody {
     Return(Option<Expr>),
     /// `;`
     Skip,
+    /// `__CPROVER_ASYNC_1: body;`
+    /// Runs `body` in a new thread, while the current thread continues with the next statement.
+    StartThread(Stmt),
     /// `switch (control) { case1.case: cast1.body; case2.case: case2.body; ... }`
     Switch {
         control: Expr,
@@ -276,7 +289,7 @@ pub fn function_call(
     pub fn goto<T: Into<InternedString>>(dest: T, loc: Location) -> Self {
         let dest = dest.into();
         assert!(!dest.is_empty());
-        stmt!(Goto(dest), loc)
+        stmt!(Goto { dest, loop_invariants: None, loop_decreases: None }, loc)
     }
 
     /// `if (i) { t } else { e }` or `if (i) { t }`
@@ -295,6 +308,16 @@ pub fn skip(loc: Location) -> Self {
         stmt!(Skip, loc)
     }
 
+    /// `__CPROVER_fence("WWfence", "RRfence", "RWfence", "WRfence");`
+    pub fn fence(loc: Location) -> Self {
+        stmt!(Fence, loc)
+    }
+
+    /// `__CPROVER_ASYNC_1: body;`
+    pub fn start_thread(body: Stmt, loc: Location) -> Self {
+        stmt!(StartThread(body), loc)
+    }
+
     /// `switch (control) { case1.case: cast1.body; case2.case: case2.body; ... }`
     pub fn switch(
         control: Expr,
@@ -312,6 +335,22 @@ pub fn while_loop(cond: Expr, body: Stmt, loc: Location) -> Self {
         stmt!(While { cond, body }, loc)
     }
 
+    /// Annotate the back edge of a loop with the loop invariant, and optionally with the
+    /// components of a measure that decreases at every iteration, so CBMC can use them as a loop
+    /// contract instead of unwinding the loop.
+    pub fn with_loop_contracts(self, inv: Expr, decreases: Option<Vec<Expr>>) -> Self {
+        assert!(inv.typ().is_bool());
+        assert!(decreases.iter().flatten().all(|measure| measure.typ().is_integer()));
+        if let Goto { dest, loop_invariants: None, loop_decreases: None } = self.body() {
+            stmt!(
+                Goto { dest: *dest, loop_invariants: Some(inv), loop_decreases: decreases },
+                *self.location()
+            )
+        } else {
+            unreachable!("Loop contracts should be annotated only to goto statements")
+        }
+    }
+
     /// `label: self;`
     pub fn with_label<T: Into<InternedString>>(self, label: T) -> Self {
         let label = label.into();
@@ -330,6 +369,61 @@ pub fn is_expression(&self) -> bool {
     }
 }
 
+/// Simplification
+impl Stmt {
+    /// Simplify every expression in the statement with [Expr::simplify]. The structure of the
+    /// statement is left untouched, so that labels and properties are preserved.
+    pub fn simplify(self, st: &SymbolTable) -> Stmt {
+        let expr = |e: Expr| e.simplify(st);
+        let stmt = |s: Stmt| s.simplify(st);
+        let stmts = |ss: Vec<Stmt>| ss.into_iter().map(stmt).collect();
+        let body = match *self.body {
+            Assign { lhs, rhs } => Assign { lhs: expr(lhs), rhs: expr(rhs) },
+            Assert { cond, property_class, msg } => {
+                Assert { cond: expr(cond), property_class, msg }
+            }
+            Assume { cond } => Assume { cond: expr(cond) },
+            AtomicBlock(ss) => AtomicBlock(stmts(ss)),
+            Block(ss) => Block(stmts(ss)),
+            Decl { lhs, value } => Decl { lhs, value: value.map(expr) },
+            Deinit(e) => Deinit(expr(e)),
+            Expression(e) => Expression(expr(e)),
+            For { init, cond, update, body } => {
+                For { init: stmt(init), cond: expr(cond), update: stmt(update), body: stmt(body) }
+            }
+            FunctionCall { lhs, function, arguments } => FunctionCall {
+                lhs: lhs.map(expr),
+                function: expr(function),
+                arguments: arguments.into_iter().map(expr).collect(),
+            },
+            Goto { dest, loop_invariants, loop_decreases } => Goto {
+                dest,
+                loop_invariants: loop_invariants.map(expr),
+                loop_decreases: loop_decreases
+                    .map(|measures| measures.into_iter().map(expr).collect()),
+            },
+            Ifthenelse { i, t, e } => Ifthenelse { i: expr(i), t: stmt(t), e: e.map(stmt) },
+            Label { label, body } => Label { label, body: stmt(body) },
+            Return(e) => Return(e.map(expr)),
+            StartThread(body) => StartThread(stmt(body)),
+            Switch { control, cases, default } => Switch {
+                control: expr(control),
+                cases: cases
+                    .into_iter()
+                    .map(|SwitchCase { case, body }| SwitchCase {
+                        case: expr(case),
+                        body: stmt(body),
+                    })
+                    .collect(),
+                default: default.map(stmt),
+            },
+            While { cond, body } => While { cond: expr(cond), body: stmt(body) },
+            body @ (Break | Continue | Fence | Skip) => body,
+        };
+        stmt!(body, self.location)
+    }
+}
+
 /// Setters
 impl StmtBody {
     #[deprecated(
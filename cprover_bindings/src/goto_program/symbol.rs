@@ -42,6 +42,45 @@ pub struct Symbol {
     pub is_thread_local: bool,
     pub is_volatile: bool,
     pub is_weak: bool,
+
+    /// Contract of a function symbol, which CBMC keeps in a separate `contract::<name>` symbol.
+    pub contract: Option<Box<FunctionContract>>,
+}
+
+/// The parts of a function contract that Kani hands over to CBMC.
+///
+/// Based off the `code_with_contract_typet` of CBMC:
+/// <https://github.com/diffblue/cbmc/blob/develop/src/ansi-c/c_expr.h>
+/// Only the assigns clause is supported for now.
+#[derive(Clone, Debug)]
+pub struct FunctionContract {
+    /// The memory the function may write to. Each target is a lambda over the
+    /// parameters of the function.
+    pub(crate) assigns: Vec<Lambda>,
+}
+
+impl FunctionContract {
+    pub fn new(assigns: Vec<Lambda>) -> Self {
+        Self { assigns }
+    }
+}
+
+/// A mathematical function, which CBMC uses for the clauses of function
+/// contracts. When a contract is used, the parameters of the lambda are
+/// substituted by the arguments of the call.
+#[derive(Clone, Debug)]
+pub struct Lambda {
+    pub arguments: Vec<Parameter>,
+    pub body: Expr,
+}
+
+impl Lambda {
+    /// Create a lambda whose parameters are the parameters of a function with
+    /// type `fn_typ`, so that `body` can refer to them.
+    pub fn as_contract_for(fn_typ: &Type, body: Expr) -> Self {
+        let arguments = fn_typ.parameters().expect("Expected a function type").clone();
+        Self { arguments, body }
+    }
 }
 
 /// Currently, only C is understood by CBMC.
@@ -104,6 +143,7 @@ fn new<T: Into<InternedString>, U: Into<InternedString>, V: Into<InternedString>
             is_thread_local: false,
             is_volatile: false,
             is_weak: false,
+            contract: None,
         }
     }
 
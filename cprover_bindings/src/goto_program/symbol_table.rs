@@ -1,7 +1,7 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 use super::super::{env, MachineModel};
-use super::{BuiltinFn, Stmt, Symbol};
+use super::{BuiltinFn, FunctionContract, Stmt, Symbol, SymbolValues};
 use crate::InternedString;
 use std::collections::BTreeMap;
 /// This is a typesafe implementation of the CBMC symbol table, based on the CBMC code at:
@@ -71,6 +71,18 @@ pub fn replace_with_completion(&mut self, new_symbol: Symbol) {
         self.replace(|old_symbol| new_symbol.completes(old_symbol), new_symbol.clone())
     }
 
+    /// Attach a contract to the function with the given name. Each function has
+    /// at most one contract.
+    pub fn attach_contract<T: Into<InternedString>>(
+        &mut self,
+        name: T,
+        contract: FunctionContract,
+    ) {
+        let sym = self.symbol_table.get_mut(&name.into()).unwrap();
+        assert!(sym.contract.is_none(), "Expected function without contract, got {sym:?}");
+        sym.contract = Some(Box::new(contract));
+    }
+
     pub fn update_fn_declaration_with_definition<T: Into<InternedString>>(
         &mut self,
         name: T,
@@ -81,6 +93,32 @@ pub fn update_fn_declaration_with_definition<T: Into<InternedString>>(
     }
 }
 
+/// Simplification
+impl SymbolTable {
+    /// Simplify the values of all the symbols with [Expr::simplify](super::Expr::simplify).
+    /// This is meant to be done once codegen is complete, before the table is emitted.
+    pub fn simplify(&mut self) {
+        let names: Vec<_> = self
+            .symbol_table
+            .iter()
+            .filter(|(_, symbol)| !matches!(symbol.value, SymbolValues::None))
+            .map(|(name, _)| *name)
+            .collect();
+        for name in names {
+            let value = std::mem::replace(
+                &mut self.symbol_table.get_mut(&name).unwrap().value,
+                SymbolValues::None,
+            );
+            let value = match value {
+                SymbolValues::Expr(e) => SymbolValues::Expr(e.simplify(self)),
+                SymbolValues::Stmt(s) => SymbolValues::Stmt(s.simplify(self)),
+                SymbolValues::None => SymbolValues::None,
+            };
+            self.symbol_table.get_mut(&name).unwrap().value = value;
+        }
+    }
+}
+
 /// Getters
 impl SymbolTable {
     pub fn contains(&self, name: InternedString) -> bool {
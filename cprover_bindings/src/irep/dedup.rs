@@ -0,0 +1,293 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Removal of the functions that are defined more than once across symbol tables that are
+//! linked together.
+//!
+//! Every crate codegens the instances of the generic functions that it uses, and the symbol name
+//! of an instance that isn't shared with upstream crates depends on the crate that instantiated
+//! it. So the goto binaries of two crates may define the same instance under different names,
+//! and linking them keeps both copies.
+//!
+//! Two functions are considered duplicates if they have the same pretty name, and the same type
+//! and body up to the names of their own parameters and local variables, which are prefixed by
+//! the name of the function. We keep the function with the smallest name, and redirect every
+//! reference to the others to it. Since functions are compared after the references to known
+//! duplicates have been redirected, this is repeated until no new duplicates are found, which
+//! catches functions that only differ in which copy of a duplicate they call.
+
+use super::goto_binary_serde::IrepNumbering;
+use super::{Irep, IrepId, Symbol, SymbolTable};
+use crate::InternedString;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Replaces the name of the function being compared, so that references to itself and to its
+/// local variables compare equal across duplicates.
+const SELF_NAME: &str = "#self";
+
+/// The result of [deduplicate_functions].
+#[derive(Debug, PartialEq)]
+pub struct Deduplication {
+    /// How many functions were removed.
+    pub removed: usize,
+    /// Whether each table was modified. The tables that weren't don't need to be written again.
+    pub modified: Vec<bool>,
+}
+
+/// Removes the duplicated functions from `tables`.
+///
+/// References to a function that was removed from a table may now refer to a function that's
+/// only defined in another table, in which case a declaration of the function is added.
+pub fn deduplicate_functions(tables: &mut [SymbolTable]) -> Deduplication {
+    let renames = find_duplicates(tables);
+    if renames.is_empty() {
+        return Deduplication { removed: 0, modified: vec![false; tables.len()] };
+    }
+    let removed: HashSet<String> = renames.keys().map(|name| name.to_string()).collect();
+    let canonicals: HashSet<InternedString> = renames.values().copied().collect();
+    let declarations: HashMap<InternedString, Symbol> = tables
+        .iter()
+        .flat_map(|table| table.symbol_table.values())
+        .filter(|symbol| canonicals.contains(&symbol.name))
+        .map(|symbol| (symbol.name, Symbol { value: Irep::nil(), ..symbol.clone() }))
+        .collect();
+
+    let modified = tables
+        .iter_mut()
+        .map(|table| {
+            // Remove the duplicates, as well as their parameters and local variables.
+            let size = table.symbol_table.len();
+            table.symbol_table.retain(|name, _| {
+                !name.map(|name| {
+                    removed.contains(name)
+                        || name
+                            .split_once("::")
+                            .is_some_and(|(function, _)| removed.contains(function))
+                })
+            });
+            let mut modified = table.symbol_table.len() != size;
+            let mut referenced = BTreeSet::new();
+            for symbol in table.symbol_table.values_mut() {
+                let mut rename = |name: InternedString| {
+                    let canonical = renames.get(&name).copied();
+                    referenced.extend(canonical);
+                    canonical
+                };
+                rewrite_strings(&mut symbol.typ, &mut rename);
+                rewrite_strings(&mut symbol.value, &mut rename);
+            }
+            modified |= !referenced.is_empty();
+            for name in referenced {
+                if !table.symbol_table.contains_key(&name) {
+                    table.insert(declarations[&name].clone());
+                }
+            }
+            modified
+        })
+        .collect();
+    Deduplication { removed: renames.len(), modified }
+}
+
+/// Map each duplicated function to the function that replaces it.
+fn find_duplicates(tables: &[SymbolTable]) -> HashMap<InternedString, InternedString> {
+    let mut renames = HashMap::new();
+    loop {
+        let mut numbering = IrepNumbering::new();
+        let mut classes: HashMap<(InternedString, usize, usize), Vec<InternedString>> =
+            HashMap::new();
+        for symbol in tables.iter().flat_map(|table| table.symbol_table.values()) {
+            if !is_function_definition(symbol) || renames.contains_key(&symbol.name) {
+                continue;
+            }
+            let typ = numbering.number_irep(&canonicalize(&symbol.typ, symbol.name, &renames));
+            let value = numbering.number_irep(&canonicalize(&symbol.value, symbol.name, &renames));
+            classes
+                .entry((symbol.pretty_name, typ.number, value.number))
+                .or_default()
+                .push(symbol.name);
+        }
+
+        let mut changed = false;
+        for names in classes.into_values() {
+            let canonical = *names.iter().min_by_key(|name| name.to_string()).unwrap();
+            for name in names {
+                // The same function may be defined in several tables.
+                if name != canonical {
+                    renames.insert(name, canonical);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            return renames;
+        }
+    }
+}
+
+/// Whether this symbol is a function with a body. File local functions aren't merged, since
+/// their names aren't visible across tables.
+fn is_function_definition(symbol: &Symbol) -> bool {
+    !symbol.is_type
+        && !symbol.is_file_local
+        && symbol.typ.id.to_string() == IrepId::Code.to_string()
+        && symbol.value.id.to_string() != IrepId::Nil.to_string()
+}
+
+/// A copy of `irep` where the name of `function` is replaced by [SELF_NAME], and where the
+/// references to known duplicates are redirected.
+fn canonicalize(
+    irep: &Irep,
+    function: InternedString,
+    renames: &HashMap<InternedString, InternedString>,
+) -> Irep {
+    let function = function.to_string();
+    let own_prefix = format!("{function}::");
+    let mut canonical = irep.clone();
+    rewrite_strings(&mut canonical, &mut |name| {
+        if let Some(renamed) = renames.get(&name) {
+            return Some(*renamed);
+        }
+        // Strings can't be interned while the interner is borrowed by `map`.
+        let local = name.map(|name| {
+            if name == function {
+                Some(SELF_NAME.to_string())
+            } else {
+                name.strip_prefix(&own_prefix).map(|rest| format!("{SELF_NAME}::{rest}"))
+            }
+        });
+        local.map(InternedString::from)
+    });
+    canonical
+}
+
+/// Replace the string ids of `irep` for which `rename` returns a new name.
+fn rewrite_strings(
+    irep: &mut Irep,
+    rename: &mut impl FnMut(InternedString) -> Option<InternedString>,
+) {
+    if let IrepId::FreeformString(name) = irep.id {
+        if let Some(new_name) = rename(name) {
+            irep.id = IrepId::FreeformString(new_name);
+        }
+    }
+    for sub in irep.sub.iter_mut() {
+        rewrite_strings(sub, rename);
+    }
+    for (_, sub) in irep.named_sub.iter_mut() {
+        rewrite_strings(sub, rename);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_string::InternString;
+    use crate::linear_map;
+
+    fn symbol(name: &str, typ: Irep, value: Irep) -> Symbol {
+        Symbol {
+            typ,
+            value,
+            location: Irep::nil(),
+            name: name.into(),
+            module: "".into(),
+            base_name: name.into(),
+            pretty_name: name.into(),
+            mode: "C".into(),
+            is_type: false,
+            is_macro: false,
+            is_exported: false,
+            is_input: false,
+            is_output: false,
+            is_state_var: false,
+            is_property: false,
+            is_static_lifetime: false,
+            is_thread_local: false,
+            is_lvalue: true,
+            is_file_local: false,
+            is_extern: false,
+            is_volatile: false,
+            is_parameter: false,
+            is_auxiliary: false,
+            is_weak: false,
+        }
+    }
+
+    fn variable(name: &str) -> Symbol {
+        symbol(name, Irep::just_id(IrepId::Bool), Irep::nil())
+    }
+
+    fn symbol_expr(identifier: &str) -> Irep {
+        Irep {
+            id: IrepId::from_string("symbol"),
+            sub: vec![],
+            named_sub: linear_map![(
+                IrepId::from_string("identifier"),
+                Irep::just_string_id(identifier)
+            )],
+        }
+    }
+
+    /// A function whose body returns the sum of a local variable and of a call to `callee`.
+    fn function(name: &str, pretty_name: &str, callee: &str) -> Symbol {
+        let call = Irep {
+            id: IrepId::from_string("side_effect"),
+            sub: vec![symbol_expr(callee)],
+            named_sub: linear_map![],
+        };
+        let body = Irep {
+            id: IrepId::from_string("plus"),
+            sub: vec![symbol_expr(&format!("{name}::1::var_1")), call],
+            named_sub: linear_map![],
+        };
+        let typ = Irep::just_id(IrepId::from_string("code"));
+        Symbol { pretty_name: pretty_name.intern(), ..symbol(name, typ, body) }
+    }
+
+    fn names(table: &SymbolTable) -> Vec<String> {
+        let mut names: Vec<_> = table.symbol_table.keys().map(|name| name.to_string()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_deduplicate_functions() {
+        let mut first = SymbolTable::new();
+        first.insert(function("a_leaf", "leaf", "ext"));
+        first.insert(function("a_caller", "caller", "a_leaf"));
+        first.insert(variable("a_leaf::1::var_1"));
+        let mut second = SymbolTable::new();
+        second.insert(function("b_leaf", "leaf", "ext"));
+        second.insert(function("b_caller", "caller", "b_leaf"));
+        second.insert(function("b_other", "other", "b_leaf"));
+        second.insert(variable("b_leaf::1::var_1"));
+        let mut tables = [first, second];
+
+        assert_eq!(
+            deduplicate_functions(&mut tables),
+            Deduplication { removed: 2, modified: vec![false, true] }
+        );
+        let [first, second] = &tables;
+        assert_eq!(names(first), ["a_caller", "a_leaf", "a_leaf::1::var_1"]);
+        // The functions that were removed are declared instead, since `b_other` calls `a_leaf`.
+        assert_eq!(names(second), ["a_leaf", "b_other"]);
+        assert!(second.symbol_table[&"a_leaf".intern()].value.is_nil());
+        assert_eq!(
+            second.symbol_table[&"b_other".intern()].value.sub[1].sub[0],
+            symbol_expr("a_leaf")
+        );
+    }
+
+    #[test]
+    fn test_keep_distinct_functions() {
+        let mut first = SymbolTable::new();
+        first.insert(function("a_leaf", "leaf", "ext"));
+        let mut second = SymbolTable::new();
+        // Same pretty name, but a different body.
+        second.insert(function("b_leaf", "leaf", "other_ext"));
+        // Same body, but a different pretty name.
+        second.insert(function("c_leaf", "other_leaf", "ext"));
+        let mut tables = [first, second];
+        assert_eq!(deduplicate_functions(&mut tables).removed, 0);
+        assert_eq!(names(&tables[1]).len(), 2);
+    }
+}
@@ -19,11 +19,17 @@
 /// - src/util/irep_hash_container.h
 /// - src/util/irep_hash.h
 pub fn write_goto_binary_file(filename: &Path, source: &crate::goto_program::SymbolTable) {
-    let out_file = File::create(filename).unwrap();
+    write_irep_goto_binary_file(filename, &source.to_irep()).unwrap();
+}
+
+/// Writes a symbol table that is already in its [Irep] form, e.g., one that was read with
+/// [read_goto_binary_file], to a file in goto binary format in version 5.
+pub fn write_irep_goto_binary_file(filename: &Path, source: &SymbolTable) -> io::Result<()> {
+    let out_file = File::create(filename)?;
     let mut writer = BufWriter::new(out_file);
     let mut serializer = GotoBinarySerializer::new(&mut writer);
-    let irep_symbol_table = &source.to_irep();
-    serializer.write_file(irep_symbol_table);
+    serializer.write_file(source);
+    writer.flush()
 }
 
 /// Reads a symbol table from a file expected to be in goto binary format in version 5.
@@ -33,7 +39,11 @@ pub fn write_goto_binary_file(filename: &Path, source: &crate::goto_program::Sym
 /// - src/util/irep_serialization.h
 /// - src/util/irep_hash_container.h
 /// - src/util/irep_hash.h
-pub fn read_goto_binary_file(filename: &Path) -> io::Result<()> {
+///
+/// Since the goto binary format only stores the string representation of [IrepId]s, the ids of
+/// the [Irep]s in the resulting symbol table are all [IrepId::FreeformString]s. They are written
+/// back unchanged by [write_irep_goto_binary_file].
+pub fn read_goto_binary_file(filename: &Path) -> io::Result<SymbolTable> {
     let file = File::open(filename)?;
     let reader = BufReader::new(file);
     let mut deserializer = GotoBinaryDeserializer::new(reader);
@@ -202,7 +212,7 @@ fn numbered_irep_from_number(&self, irep_number: usize) -> Option<NumberedIrep>
 }
 
 /// A numbering of [InternedString], [IrepId] and [Irep] based on their contents.
-struct IrepNumbering {
+pub(super) struct IrepNumbering {
     /// Map from [InternedString] to their unique numbers.
     string_cache: HashMap<InternedString, usize>,
 
@@ -217,7 +227,7 @@ struct IrepNumbering {
 }
 
 impl IrepNumbering {
-    fn new() -> Self {
+    pub(super) fn new() -> Self {
         IrepNumbering {
             string_cache: HashMap::new(),
             inv_string_cache: Vec::new(),
@@ -256,7 +266,7 @@ fn number_irep_id(&mut self, irep_id: &IrepId) -> NumberedString {
     /// Turns an [Irep] into a [NumberedIrep]. The [Irep] is recursively traversed
     /// and numbered in a bottom-up fashion. Structurally identical [Irep]s
     /// result in the same [NumberedIrep].
-    fn number_irep(&mut self, irep: &Irep) -> NumberedIrep {
+    pub(super) fn number_irep(&mut self, irep: &Irep) -> NumberedIrep {
         // build the key
         let id = self.number_irep_id(&irep.id).number;
         let sub: Vec<usize> = irep.sub.iter().map(|sub| self.number_irep(sub).number).collect();
@@ -319,15 +329,32 @@ fn named_sub(
             self.inv_cache.index[self.inv_cache.keys[start_index + 1]],
         )
     }
+
+    /// Rebuilds the [Irep] that was numbered as the given [NumberedIrep].
+    /// Only the string representation of the ids is kept by the numbering, so all ids are
+    /// rebuilt as [IrepId::FreeformString].
+    fn irep(&self, numbered_irep: &NumberedIrep) -> Irep {
+        let id = IrepId::from_string(self.id(numbered_irep).string);
+        let sub = (0..self.nof_sub(numbered_irep))
+            .map(|sub_idx| self.irep(&self.sub(numbered_irep, sub_idx)))
+            .collect();
+        let named_sub = (0..self.nof_named_sub(numbered_irep))
+            .map(|named_sub_idx| {
+                let (key, value) = self.named_sub(numbered_irep, named_sub_idx);
+                (IrepId::from_string(key.string), self.irep(&value))
+            })
+            .collect();
+        Irep { id, sub, named_sub }
+    }
 }
 
 /// A uniquely numbered [Irep].
 /// A NumberedIrep can be viewed as a generational index into an
 /// [IrepNumbering] instance.
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
-struct NumberedIrep {
+pub(super) struct NumberedIrep {
     /// The unique number of this NumberedIrep.
-    number: usize,
+    pub(super) number: usize,
     /// Start index of the [IrepKey] of this [NumberedIrep] in the inverse cache
     /// of the [IrepNumbering] that produced it.
     start_index: usize,
@@ -843,18 +870,18 @@ fn read_numbered_irep_ref(&mut self) -> io::Result<NumberedIrep> {
     }
 
     /// Reads a Symbol from the byte stream.
-    fn read_symbol(&mut self) -> io::Result<()> {
+    fn read_symbol(&mut self) -> io::Result<Symbol> {
         // Read Irep attributes of the symbol
-        let _typ = self.read_numbered_irep_ref()?;
-        let _value = self.read_numbered_irep_ref()?;
-        let _location = self.read_numbered_irep_ref()?;
+        let typ = self.read_numbered_irep_ref()?;
+        let value = self.read_numbered_irep_ref()?;
+        let location = self.read_numbered_irep_ref()?;
 
         // Read string attributes of the symbol
-        let _name = self.read_numbered_string_ref()?;
-        let _module = self.read_numbered_string_ref()?;
-        let _base_name = self.read_numbered_string_ref()?;
-        let _mode = self.read_numbered_string_ref()?;
-        let _pretty_name = self.read_numbered_string_ref()?;
+        let name = self.read_numbered_string_ref()?.string;
+        let module = self.read_numbered_string_ref()?.string;
+        let base_name = self.read_numbered_string_ref()?.string;
+        let mode = self.read_numbered_string_ref()?.string;
+        let pretty_name = self.read_numbered_string_ref()?.string;
 
         // obsolete: symordering
         let symordering = self.read_u8()?;
@@ -863,24 +890,24 @@ fn read_symbol(&mut self) -> io::Result<()> {
         // Decode the bit-packed flags and extract bits one by one
         let flags: usize = self.read_usize_varenc()?;
 
-        let _is_weak = (flags & (1 << 16)) != 0;
-        let _is_type = (flags & (1 << 15)) != 0;
-        let _is_property = (flags & (1 << 14)) != 0;
-        let _is_macro = (flags & (1 << 13)) != 0;
-        let _is_exported = (flags & (1 << 12)) != 0;
-        let _is_input = (flags & (1 << 11)) != 0;
-        let _is_output = (flags & (1 << 10)) != 0;
-        let _is_state_var = (flags & (1 << 9)) != 0;
-        let _is_parameter = (flags & (1 << 8)) != 0;
-        let _is_auxiliary = (flags & (1 << 7)) != 0;
+        let is_weak = (flags & (1 << 16)) != 0;
+        let is_type = (flags & (1 << 15)) != 0;
+        let is_property = (flags & (1 << 14)) != 0;
+        let is_macro = (flags & (1 << 13)) != 0;
+        let is_exported = (flags & (1 << 12)) != 0;
+        let is_input = (flags & (1 << 11)) != 0;
+        let is_output = (flags & (1 << 10)) != 0;
+        let is_state_var = (flags & (1 << 9)) != 0;
+        let is_parameter = (flags & (1 << 8)) != 0;
+        let is_auxiliary = (flags & (1 << 7)) != 0;
         // deprecated sym.binding but remains present for compatibility
         let _is_binding = (flags & (1 << 6)) != 0;
-        let _is_lvalue = (flags & (1 << 5)) != 0;
-        let _is_static_lifetime = (flags & (1 << 4)) != 0;
-        let _is_thread_local = (flags & (1 << 3)) != 0;
-        let _is_file_local = (flags & (1 << 2)) != 0;
-        let _is_extern = (flags & (1 << 1)) != 0;
-        let _is_volatile = (flags & 1) != 0;
+        let is_lvalue = (flags & (1 << 5)) != 0;
+        let is_static_lifetime = (flags & (1 << 4)) != 0;
+        let is_thread_local = (flags & (1 << 3)) != 0;
+        let is_file_local = (flags & (1 << 2)) != 0;
+        let is_extern = (flags & (1 << 1)) != 0;
+        let is_volatile = (flags & 1) != 0;
 
         let shifted_flags = flags >> 16;
 
@@ -890,20 +917,46 @@ fn read_symbol(&mut self) -> io::Result<()> {
                 "incorrect binary format: true bits remain in decoded symbol flags",
             ));
         }
-        Ok(())
+        Ok(Symbol {
+            typ: self.numbering.irep(&typ),
+            value: self.numbering.irep(&value),
+            location: self.numbering.irep(&location),
+            name,
+            module,
+            base_name,
+            pretty_name,
+            mode,
+            is_type,
+            is_macro,
+            is_exported,
+            is_input,
+            is_output,
+            is_state_var,
+            is_property,
+            is_static_lifetime,
+            is_thread_local,
+            is_lvalue,
+            is_file_local,
+            is_extern,
+            is_volatile,
+            is_parameter,
+            is_auxiliary,
+            is_weak,
+        })
     }
 
     /// Reads a whole SymbolTable from the byte stream.
-    fn read_symbol_table(&mut self) -> io::Result<()> {
-        // Write symbol table size
+    fn read_symbol_table(&mut self) -> io::Result<SymbolTable> {
+        // Read symbol table size
         let symbol_table_len = self.read_usize_varenc()?;
 
-        // Write symbols
+        // Read symbols
+        let mut symbol_table = SymbolTable::new();
         for _ in 0..symbol_table_len {
-            self.read_symbol()?;
+            symbol_table.insert(self.read_symbol()?);
         }
 
-        Ok(())
+        Ok(symbol_table)
     }
 
     /// Reads an empty function map from the byte stream.
@@ -936,11 +989,11 @@ fn read_header(&mut self) -> io::Result<()> {
     }
 
     /// Read a GOTO binary file from the byte stream.
-    fn read_file(&mut self) -> io::Result<()> {
+    fn read_file(&mut self) -> io::Result<SymbolTable> {
         self.read_header()?;
-        self.read_symbol_table()?;
+        let symbol_table = self.read_symbol_table()?;
         self.read_function_map()?;
-        Ok(())
+        Ok(symbol_table)
     }
 }
 
@@ -1211,6 +1264,8 @@ mod tests {
     use crate::irep::goto_binary_serde::GotoBinaryDeserializer;
     use crate::irep::Irep;
     use crate::irep::IrepId;
+    use crate::irep::Symbol;
+    use crate::irep::SymbolTable;
     use crate::linear_map;
     use crate::InternedString;
     use linear_map::LinearMap;
@@ -1466,4 +1521,57 @@ fn test_write_read_irep_ref() {
             assert_eq!(irep1, irep6);
         }
     }
+
+    #[test]
+    /// Write a symbol table and read it back.
+    fn test_write_read_symbol_table() {
+        let mut symbol_table = SymbolTable::new();
+        let typ = Irep::just_id(IrepId::from_string("code"));
+        let value = fold_with_op(&vec!["foo", "bar"], IrepId::from_string("and"));
+        symbol_table.insert(Symbol {
+            typ,
+            value,
+            location: Irep::nil(),
+            name: "foo".intern(),
+            module: "".intern(),
+            base_name: "foo".intern(),
+            pretty_name: "crate::foo".intern(),
+            mode: "C".intern(),
+            is_type: false,
+            is_macro: false,
+            is_exported: false,
+            is_input: false,
+            is_output: false,
+            is_state_var: false,
+            is_property: false,
+            is_static_lifetime: true,
+            is_thread_local: false,
+            is_lvalue: true,
+            is_file_local: false,
+            is_extern: false,
+            is_volatile: false,
+            is_parameter: false,
+            is_auxiliary: false,
+            is_weak: false,
+        });
+
+        let write = |symbol_table: &SymbolTable| {
+            let mut vec: Vec<u8> = Vec::new();
+            {
+                let mut writer = BufWriter::new(&mut vec);
+                let mut serializer = GotoBinarySerializer::new(&mut writer);
+                serializer.write_file(symbol_table);
+            }
+            vec
+        };
+        let bytes = write(&symbol_table);
+        let mut deserializer = GotoBinaryDeserializer::new(std::io::Cursor::new(bytes.clone()));
+        let read = deserializer.read_file().unwrap();
+
+        // Ids are read back as strings, so compare what gets written instead.
+        assert_eq!(write(&read), bytes);
+        let symbol = &read.symbol_table[&"foo".intern()];
+        assert_eq!(symbol.pretty_name, "crate::foo".intern());
+        assert!(symbol.is_static_lifetime && symbol.is_lvalue && !symbol.is_type);
+    }
 }
@@ -591,8 +591,10 @@ pub enum IrepId {
     Used,
     IsUsed,
     CSpecLoopInvariant,
+    CSpecAssigns,
     CSpecRequires,
     CSpecEnsures,
+    CSpecDecreases,
     VirtualFunction,
     ElementType,
     WorkingDirectory,
@@ -1460,8 +1462,10 @@ fn to_string(&self) -> String {
             IrepId::Used => "used",
             IrepId::IsUsed => "is_used",
             IrepId::CSpecLoopInvariant => "#spec_loop_invariant",
+            IrepId::CSpecAssigns => "#spec_assigns",
             IrepId::CSpecRequires => "#spec_requires",
             IrepId::CSpecEnsures => "#spec_ensures",
+            IrepId::CSpecDecreases => "#spec_decreases",
             IrepId::VirtualFunction => "virtual_function",
             IrepId::ElementType => "element_type",
             IrepId::WorkingDirectory => "working_directory",
@@ -7,8 +7,9 @@
 use super::{Irep, IrepId};
 use crate::linear_map;
 use goto_program::{
-    BinaryOperator, CIntType, DatatypeComponent, Expr, ExprValue, Location, Parameter,
-    SelfOperator, Stmt, StmtBody, SwitchCase, SymbolValues, Type, UnaryOperator,
+    BinaryOperator, CIntType, DatatypeComponent, Expr, ExprValue, FunctionContract, Lambda,
+    Location, Parameter, SelfOperator, Stmt, StmtBody, SwitchCase, SymbolValues, Type,
+    UnaryOperator,
 };
 
 pub trait ToIrep {
@@ -90,6 +91,7 @@ fn to_irep_id(&self) -> IrepId {
             BinaryOperator::VectorLe => IrepId::VectorLe,
             BinaryOperator::VectorGt => IrepId::VectorGt,
             BinaryOperator::VectorLt => IrepId::VectorLt,
+            BinaryOperator::WOk => IrepId::WOk,
         }
     }
 }
@@ -297,6 +299,30 @@ fn to_irep(&self, mm: &MachineModel) -> Irep {
                 sub: values.iter().map(|x| x.to_irep(mm)).collect(),
                 named_sub: linear_map![],
             },
+            ExprValue::Exists { variable, domain } => Irep {
+                id: IrepId::Exists,
+                sub: vec![
+                    Irep {
+                        id: IrepId::Tuple,
+                        sub: vec![variable.to_irep(mm)],
+                        named_sub: linear_map![],
+                    },
+                    domain.to_irep(mm),
+                ],
+                named_sub: linear_map![],
+            },
+            ExprValue::Forall { variable, domain } => Irep {
+                id: IrepId::Forall,
+                sub: vec![
+                    Irep {
+                        id: IrepId::Tuple,
+                        sub: vec![variable.to_irep(mm)],
+                        named_sub: linear_map![],
+                    },
+                    domain.to_irep(mm),
+                ],
+                named_sub: linear_map![],
+            },
             ExprValue::Symbol { identifier } => Irep {
                 id: IrepId::Symbol,
                 sub: vec![],
@@ -447,6 +473,11 @@ fn to_irep(&self, mm: &MachineModel) -> Irep {
                     .with_comment("deinit")
             }
             StmtBody::Expression(e) => code_irep(IrepId::Expression, vec![e.to_irep(mm)]),
+            StmtBody::Fence => code_irep(IrepId::Fence, vec![])
+                .with_named_sub(IrepId::WWfence, Irep::one())
+                .with_named_sub(IrepId::RRfence, Irep::one())
+                .with_named_sub(IrepId::RWfence, Irep::one())
+                .with_named_sub(IrepId::WRfence, Irep::one()),
             StmtBody::For { init, cond, update, body } => code_irep(
                 IrepId::For,
                 vec![init.to_irep(mm), cond.to_irep(mm), update.to_irep(mm), body.to_irep(mm)],
@@ -459,8 +490,34 @@ fn to_irep(&self, mm: &MachineModel) -> Irep {
                     arguments_irep(arguments, mm),
                 ],
             ),
-            StmtBody::Goto(dest) => code_irep(IrepId::Goto, vec![])
-                .with_named_sub(IrepId::Destination, Irep::just_string_id(dest.to_string())),
+            StmtBody::Goto { dest, loop_invariants, loop_decreases } => {
+                let stmt_goto = code_irep(IrepId::Goto, vec![])
+                    .with_named_sub(IrepId::Destination, Irep::just_string_id(dest.to_string()));
+                let stmt_goto = if let Some(inv) = loop_invariants {
+                    // CBMC expects the invariant to be a conjunction where each operand is a
+                    // clause of the invariant.
+                    stmt_goto.with_named_sub(
+                        IrepId::CSpecLoopInvariant,
+                        inv.clone().and(Expr::bool_true()).to_irep(mm),
+                    )
+                } else {
+                    stmt_goto
+                };
+                if let Some(measures) = loop_decreases {
+                    // CBMC expects the decreases clause to be an expression whose operands are
+                    // the components of the measure.
+                    stmt_goto.with_named_sub(
+                        IrepId::CSpecDecreases,
+                        Irep {
+                            id: IrepId::Tuple,
+                            sub: measures.iter().map(|m| m.to_irep(mm)).collect(),
+                            named_sub: linear_map![],
+                        },
+                    )
+                } else {
+                    stmt_goto
+                }
+            }
             StmtBody::Ifthenelse { i, t, e } => code_irep(
                 IrepId::Ifthenelse,
                 vec![
@@ -475,6 +532,7 @@ fn to_irep(&self, mm: &MachineModel) -> Irep {
                 code_irep(IrepId::Return, vec![e.as_ref().map_or(Irep::nil(), |x| x.to_irep(mm))])
             }
             StmtBody::Skip => code_irep(IrepId::Skip, vec![]),
+            StmtBody::StartThread(body) => code_irep(IrepId::StartThread, vec![body.to_irep(mm)]),
             StmtBody::Switch { control, cases, default } => {
                 let mut switch_arms: Vec<Irep> = cases.iter().map(|x| x.to_irep(mm)).collect();
                 if default.is_some() {
@@ -499,7 +557,48 @@ fn to_irep(&self, mm: &MachineModel) -> Irep {
     }
 }
 
+impl ToIrep for Lambda {
+    fn to_irep(&self, mm: &MachineModel) -> Irep {
+        let (variables, domain): (Vec<_>, Vec<_>) = self
+            .arguments
+            .iter()
+            .map(|param| {
+                let identifier = param.identifier().expect("Expected a named parameter");
+                let variable = Expr::symbol_expression(identifier, param.typ().clone());
+                (variable.to_irep(mm), param.typ().to_irep(mm))
+            })
+            .unzip();
+        let typ = Irep {
+            id: IrepId::MathematicalFunction,
+            sub: vec![Irep::just_sub(domain), self.body.typ().to_irep(mm)],
+            named_sub: linear_map![],
+        };
+        Irep {
+            id: IrepId::Lambda,
+            sub: vec![
+                Irep { id: IrepId::Tuple, sub: variables, named_sub: linear_map![] },
+                self.body.to_irep(mm),
+            ],
+            named_sub: linear_map![(IrepId::Type, typ)],
+        }
+    }
+}
+
 impl goto_program::Symbol {
+    /// CBMC expects the contract of function `foo` in a separate symbol
+    /// `contract::foo`, whose type is the type of `foo` with the clauses of the
+    /// contract attached to it.
+    fn contract_to_irep(&self, contract: &FunctionContract, mm: &MachineModel) -> super::Symbol {
+        let assigns = contract.assigns.iter().map(|target| target.to_irep(mm)).collect();
+        let mut symbol = self.to_irep(mm);
+        symbol.name = format!("contract::{}", self.name).into();
+        symbol.typ = symbol.typ.with_named_sub(IrepId::CSpecAssigns, Irep::just_sub(assigns));
+        symbol.value = Irep::nil();
+        symbol.is_property = true;
+        symbol.is_lvalue = false;
+        symbol
+    }
+
     pub fn to_irep(&self, mm: &MachineModel) -> super::Symbol {
         super::Symbol {
             typ: self.typ.to_irep(mm),
@@ -549,7 +648,10 @@ pub fn to_irep(&self) -> super::SymbolTable {
         let mm = self.machine_model();
         let mut st = super::SymbolTable::new();
         for (_key, value) in self.iter() {
-            st.insert(value.to_irep(mm))
+            st.insert(value.to_irep(mm));
+            if let Some(contract) = &value.contract {
+                st.insert(value.contract_to_irep(contract, mm));
+            }
         }
         st
     }
@@ -1,6 +1,8 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use kani_metadata::{CbmcSolver, FfiPolicy, Stub};
+use std::str::FromStr;
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 use tracing_subscriber::filter::Directive;
 
@@ -37,6 +39,9 @@ pub struct Arguments {
     /// Option used for suppressing global ASM error.
     #[clap(long)]
     pub ignore_global_asm: bool,
+    /// Option used to replace inline assembly blocks with nondeterministic outputs.
+    #[clap(long)]
+    pub ignore_asm: bool,
     #[clap(long)]
     /// Option used to write JSON symbol tables instead of GOTO binaries.
     ///
@@ -45,8 +50,90 @@ pub struct Arguments {
     /// Option name used to select which reachability analysis to perform.
     #[clap(long = "reachability", default_value = "none")]
     pub reachability_analysis: ReachabilityType,
+    /// Option name used to bound the depth of the call stack. When set, Kani adds a check that
+    /// fails if more than this number of function calls can be active at the same time.
+    #[clap(long = "max-stack-depth")]
+    pub max_stack_depth: Option<u32>,
     #[clap(long = "enable-stubbing")]
     pub stubbing_enabled: bool,
+    /// Option name used to choose how an undefined foreign function is modeled, in the format
+    /// `<link_name>=<policy>`. Foreign functions without a policy fail verification when called.
+    #[clap(long = "ffi-policy", value_parser = parse_ffi_policy)]
+    pub ffi_policies: Vec<(String, FfiPolicy)>,
+    /// Option name used to set the default unwind bound of the harnesses of the crate, from the
+    /// `[package.metadata.kani]` table of its package. The driver ranks it below `#[kani::unwind]`
+    /// and `--default-unwind`.
+    #[clap(long = "package-unwind")]
+    pub package_unwind: Option<u32>,
+    /// Option name used to set the default solver of the harnesses of the crate, from the
+    /// `[package.metadata.kani]` table of its package. The driver ranks it below
+    /// `#[kani::solver]` and `--solver`.
+    #[clap(long = "package-solver", value_parser = parse_package_solver)]
+    pub package_solver: Option<CbmcSolver>,
+    /// Option name used to add a stub to every harness of the crate, in the format
+    /// `<original>=<replacement>`, from the `[package.metadata.kani.stubs]` table of its package.
+    #[clap(long = "package-stub", value_parser = parse_stub)]
+    pub package_stubs: Vec<Stub>,
+    /// Option name used to replace a function with the contract of a spec function in every
+    /// harness, in the format `<function>=<spec function>`, from the `--contract-specs` file.
+    #[clap(long = "contract-spec", value_parser = parse_stub)]
+    pub contract_specs: Vec<Stub>,
+    /// Option name used to fix a symbolic input of the harnesses to a concrete value, in the
+    /// format `<name>=<value>`, where the input is named after the variable that holds it.
+    #[clap(long = "concrete-input", value_parser = parse_concrete_input)]
+    pub concrete_inputs: Vec<(String, String)>,
+    /// Option name used to synthesize a harness for every `Drop` implementation of the crate.
+    #[clap(long = "drop-checks")]
+    pub drop_checks: bool,
+    /// Option name used to synthesize harnesses that check the laws of the `PartialEq`, `Eq`, `Ord`
+    /// and `Hash` implementations of the crate.
+    #[clap(long = "trait-laws")]
+    pub trait_laws: bool,
+    /// Option name used to synthesize a harness that calls every non-generic `const fn` of the
+    /// crate with arbitrary arguments.
+    #[clap(long = "const-fn-checks")]
+    pub const_fn_checks: bool,
+    /// Option name used to check that the bytes given to the unchecked UTF-8 conversions of `str`
+    /// and `String` are valid UTF-8.
+    #[clap(long = "utf8-checks")]
+    pub utf8_checks: bool,
+    /// Option name used to check that the integer `as` casts of the crate don't change the value
+    /// that is cast, i.e., that they neither truncate it nor change its sign.
+    #[clap(long = "lossy-cast-checks")]
+    pub lossy_cast_checks: bool,
+    /// Option name used to check that the values of enum types created by transmutes, raw
+    /// pointer and union reads, and foreign functions have a valid discriminant and payload.
+    #[clap(long = "enum-validity-checks")]
+    pub enum_validity_checks: bool,
+    /// Option name used to check that the harnesses don't read uninitialized memory.
+    #[clap(long = "uninit-checks")]
+    pub uninit_checks: bool,
+    /// Option name used to check that pointer arithmetic stays in the allocation of the pointer
+    /// and that the crate doesn't cast integers to pointers.
+    #[clap(long = "pointer-provenance-checks")]
+    pub pointer_provenance_checks: bool,
+    /// Option name used to check that the branches and memory accesses of the harnesses don't
+    /// depend on the values marked with `kani::secret`.
+    #[clap(long = "constant-time-checks")]
+    pub constant_time_checks: bool,
+    /// Option name used to make the iteration order of `HashMap` and `HashSet` nondeterministic.
+    #[clap(long = "nondet-hash-order")]
+    pub nondet_hash_order: bool,
+    /// Option name used to stub the standard containers with the models of `kani::abstractions`
+    /// in every harness.
+    #[clap(long = "use-abstractions")]
+    pub use_abstractions: bool,
+    /// Option name used to stub the threads and mutexes of the standard library with the models
+    /// of `kani::thread` in every harness.
+    #[clap(long = "race-checks")]
+    pub race_checks: bool,
+    /// Option name used to stop restricting the strings generated by Kani to valid UTF-8.
+    #[clap(long = "no-utf8-assumptions")]
+    pub no_utf8_assumptions: bool,
+    /// Option name used to restrict the floats generated by `kani::any` to the values that are not
+    /// NaN (`no-nan`), that are finite (`finite`), or that are finite and not subnormal (`normal`).
+    #[clap(long = "float-values", value_parser = ["no-nan", "finite", "normal"])]
+    pub float_values: Option<String>,
     /// Option name used to define unstable features.
     #[clap(short = 'Z', long = "unstable")]
     pub unstable_features: Vec<String>,
@@ -72,3 +159,33 @@ pub struct Arguments {
     /// A legacy flag that is now ignored.
     goto_c: bool,
 }
+
+/// Parse an FFI policy argument of the format `<link_name>=<policy>`.
+fn parse_package_solver(arg: &str) -> Result<CbmcSolver, String> {
+    match arg.split_once('=') {
+        Some(("bin", binary)) => Ok(CbmcSolver::Binary(binary.to_string())),
+        _ => CbmcSolver::from_str(arg).map_err(|_| format!("unknown solver `{arg}`")),
+    }
+}
+
+fn parse_stub(arg: &str) -> Result<Stub, String> {
+    let (original, replacement) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected `<original>=<replacement>`, found `{arg}`"))?;
+    Ok(Stub { original: original.to_string(), replacement: replacement.to_string() })
+}
+
+fn parse_ffi_policy(arg: &str) -> Result<(String, FfiPolicy), String> {
+    let (name, policy) = arg
+        .rsplit_once('=')
+        .ok_or_else(|| format!("expected `<link_name>=<policy>`, found `{arg}`"))?;
+    let policy =
+        FfiPolicy::from_str(policy).map_err(|_| format!("unknown FFI policy `{policy}`"))?;
+    Ok((name.to_string(), policy))
+}
+
+fn parse_concrete_input(arg: &str) -> Result<(String, String), String> {
+    let (name, value) =
+        arg.split_once('=').ok_or_else(|| format!("expected `<name>=<value>`, found `{arg}`"))?;
+    Ok((name.to_string(), value.to_string()))
+}
@@ -23,6 +23,7 @@
 use cbmc::InternedString;
 use rustc_span::Span;
 use std::convert::AsRef;
+use std::str::FromStr;
 use strum_macros::{AsRefStr, EnumString};
 use tracing::debug;
 
@@ -41,6 +42,17 @@ pub enum PropertyClass {
     ///
     /// SPECIAL BEHAVIOR: None? Possibly confusing to customers that a Rust assume is a Kani assert.
     Assume,
+    /// A numeric bound declared with `kani::bounds!`. The bound is assumed, and the property
+    /// checks whether its condition is satisfiable, like a cover property.
+    ///
+    /// SPECIAL BEHAVIOR: "Errors" for this type of assertion mean that the bound can be satisfied.
+    Bounds,
+    /// Checks added with `--constant-time-checks` at the branches and memory accesses that depend
+    /// on a value marked with `kani::secret`. The condition is `false`, so the check fails if the
+    /// harness reaches it.
+    ///
+    /// SPECIAL BEHAVIOR: Assertions that may not exist when running code normally (i.e. not under Kani)
+    ConstantTime,
     /// See [GotocCtx::codegen_cover] below. Generally just an `assert(false)` that's not an error.
     ///
     /// SPECIAL BEHAVIOR: "Errors" for this type of assertion just mean "reachable" not failure.
@@ -58,6 +70,11 @@ pub enum PropertyClass {
     ///
     /// SPECIAL BEHAVIOR: "Errors" for this type of assertion just mean "reachable" not failure.
     CodeCoverage,
+    /// Checks added with `--enum-validity-checks` that a value of an enum type created without
+    /// the guarantees of the compiler (e.g., by a transmute) has a valid discriminant and payload.
+    ///
+    /// SPECIAL BEHAVIOR: The driver shows the value of the tag of the enum when this check fails.
+    EnumValidity,
     /// Ordinary (Rust) assertions and panics.
     ///
     /// SPECIAL BEHAVIOR: These assertion failures should be observable during normal execution of Rust code.
@@ -72,6 +89,23 @@ pub enum PropertyClass {
     ///
     /// SPECIAL BEHAVIOR: None TODO: Why should this exist?
     FiniteCheck,
+    /// Checks added with `--lossy-cast-checks` that an integer `as` cast doesn't truncate the
+    /// value or change its sign.
+    ///
+    /// SPECIAL BEHAVIOR: Assertions that may not exist when running code normally (i.e. not under Kani)
+    LossyCast,
+    /// Assertions added with `kani::check!`, whose description starts with the name of the check
+    /// followed by `: `.
+    ///
+    /// SPECIAL BEHAVIOR: The check doesn't assume its condition, and the driver groups the results
+    /// by the name of the check.
+    NamedCheck,
+    /// Checks added with `--check pointer-provenance` that pointer arithmetic stays in the
+    /// allocation of the pointer, and that the crate doesn't cast integers to pointers, which
+    /// creates pointers without provenance.
+    ///
+    /// SPECIAL BEHAVIOR: Assertions that may not exist when running code normally (i.e. not under Kani)
+    PointerProvenance,
     /// Checks added by Kani compiler to determine whether a property (e.g.
     /// `PropertyClass::Assertion` or `PropertyClass:Cover`) is reachable
     ReachabilityCheck,
@@ -84,6 +118,17 @@ pub enum PropertyClass {
     ///
     /// SPECIAL BEHAVIOR: Should not be normally rendered as a checked assertion, as it's expected to succeed.
     SanityCheck,
+    /// Checks added by Kani compiler when a maximum call stack depth is given to bound the
+    /// number of nested function calls, e.g., to detect stack overflows caused by recursion.
+    ///
+    /// SPECIAL BEHAVIOR: Assertions that may not exist when running code normally (i.e. not under Kani)
+    StackDepth,
+    /// Added with `--max-stack-depth` when the harness returns, to check whether a call stack
+    /// depth was reached.
+    ///
+    /// SPECIAL BEHAVIOR: "Errors" for this type of assertion mean that the depth was reached. The
+    /// driver reports the deepest call stack instead of these properties.
+    StackDepthReached,
     /// See `codegen_unimplemented`. Used to indicate an unsupported construct was reachable.
     ///
     /// SPECIAL BEHAVIOR: Reachability of these assertions is notable, in order to measure Kani support.
@@ -95,6 +140,21 @@ pub enum PropertyClass {
     /// `std::intrinsics::unreachable()` and can't tell the difference between that case
     /// and other cases where the Rust compiler thinks things should be unreachable.
     Unreachable,
+    /// Checks added with `--check uninit` that the memory that is read was initialized.
+    ///
+    /// SPECIAL BEHAVIOR: Assertions that may not exist when running code normally (i.e. not under Kani)
+    Uninit,
+    /// Checks added with `--utf8-checks` that the bytes converted to a string without
+    /// validation are valid UTF-8.
+    ///
+    /// SPECIAL BEHAVIOR: Assertions that may not exist when running code normally (i.e. not under Kani)
+    Utf8,
+    /// The predicate of `kani::any_where`. The predicate is assumed, and the property checks
+    /// whether it is satisfiable, like a cover property.
+    ///
+    /// SPECIAL BEHAVIOR: "Errors" for this type of assertion mean that the predicate can be
+    /// satisfied. The driver warns that the harness is vacuous if it can't.
+    Vacuity,
 }
 
 #[allow(dead_code)]
@@ -102,6 +162,16 @@ impl PropertyClass {
     pub fn as_str(&self) -> &str {
         self.as_ref()
     }
+
+    /// Whether `class` can be used as a property class by `kani::assert_class!`. The class is
+    /// part of the name of the property, which is `<function>.<class>.<index>`, so it is
+    /// restricted to lowercase ASCII letters, digits and `_`, and it can't be one of Kani's own
+    /// classes, which the driver handles specially.
+    pub fn is_valid_user_class(class: &str) -> bool {
+        class.starts_with(|c: char| c.is_ascii_lowercase())
+            && class.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            && PropertyClass::from_str(class).is_err()
+    }
 }
 
 impl<'tcx> GotocCtx<'tcx> {
@@ -137,6 +207,22 @@ pub fn codegen_assert_assume(
         )
     }
 
+    /// Generates a CBMC assertion in a property class chosen by the user with
+    /// `kani::assert_class!`, followed by an assumption of the same condition.
+    /// The class must have been validated with [PropertyClass::is_valid_user_class].
+    pub fn codegen_assert_assume_user_class(
+        &self,
+        cond: Expr,
+        class: &str,
+        message: &str,
+        loc: Location,
+    ) -> Stmt {
+        Stmt::block(
+            vec![Stmt::assert(cond.clone(), class, message, loc), Stmt::assume(cond, loc)],
+            loc,
+        )
+    }
+
     /// Generate code to cover the given condition at the current location
     pub fn codegen_cover(&self, cond: Expr, msg: &str, span: Option<Span>) -> Stmt {
         let loc = self.codegen_caller_span(&span);
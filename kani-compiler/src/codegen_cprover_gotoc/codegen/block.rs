@@ -1,8 +1,11 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use crate::codegen_cprover_gotoc::codegen::PropertyClass;
 use crate::codegen_cprover_gotoc::GotocCtx;
-use rustc_middle::mir::{BasicBlock, BasicBlockData};
+use cbmc::goto_program::{Expr, Stmt};
+use rustc_middle::mir::{BasicBlock, BasicBlockData, Location};
+use rustc_span::Span;
 use tracing::debug;
 
 impl<'tcx> GotocCtx<'tcx> {
@@ -15,7 +18,7 @@ impl<'tcx> GotocCtx<'tcx> {
     pub fn codegen_block(&mut self, bb: BasicBlock, bbd: &BasicBlockData<'tcx>) {
         debug!(?bb, "Codegen basicblock");
         self.current_fn_mut().set_current_bb(bb);
-        let label: String = self.current_fn().find_label(&bb);
+        let label: String = self.current_fn().block_label(&bb);
         let check_coverage = self.queries.args().check_coverage;
         // the first statement should be labelled. if there is no statements, then the
         // terminator should be labelled.
@@ -23,6 +26,9 @@ pub fn codegen_block(&mut self, bb: BasicBlock, bbd: &BasicBlockData<'tcx>) {
             0 => {
                 let term = bbd.terminator();
                 let tcode = self.codegen_terminator(term);
+                let tcode = self.codegen_secret_check(bb, 0, term.source_info.span, tcode);
+                let tcode = self.codegen_union_read_checks(bb, 0, term.source_info.span, tcode);
+                let tcode = self.codegen_uninit_read_checks(bb, 0, term.source_info.span, tcode);
                 // When checking coverage, the `coverage` check should be
                 // labelled instead.
                 if check_coverage {
@@ -37,6 +43,9 @@ pub fn codegen_block(&mut self, bb: BasicBlock, bbd: &BasicBlockData<'tcx>) {
             _ => {
                 let stmt = &bbd.statements[0];
                 let scode = self.codegen_statement(stmt);
+                let scode = self.codegen_secret_check(bb, 0, stmt.source_info.span, scode);
+                let scode = self.codegen_union_read_checks(bb, 0, stmt.source_info.span, scode);
+                let scode = self.codegen_uninit_read_checks(bb, 0, stmt.source_info.span, scode);
                 // When checking coverage, the `coverage` check should be
                 // labelled instead.
                 if check_coverage {
@@ -48,13 +57,16 @@ pub fn codegen_block(&mut self, bb: BasicBlock, bbd: &BasicBlockData<'tcx>) {
                     self.current_fn_mut().push_onto_block(scode.with_label(label));
                 }
 
-                for s in &bbd.statements[1..] {
+                for (idx, s) in bbd.statements.iter().enumerate().skip(1) {
                     if check_coverage {
                         let span = s.source_info.span;
                         let cover = self.codegen_coverage(span);
                         self.current_fn_mut().push_onto_block(cover);
                     }
                     let stmt = self.codegen_statement(s);
+                    let stmt = self.codegen_secret_check(bb, idx, s.source_info.span, stmt);
+                    let stmt = self.codegen_union_read_checks(bb, idx, s.source_info.span, stmt);
+                    let stmt = self.codegen_uninit_read_checks(bb, idx, s.source_info.span, stmt);
                     self.current_fn_mut().push_onto_block(stmt);
                 }
                 let term = bbd.terminator();
@@ -64,9 +76,60 @@ pub fn codegen_block(&mut self, bb: BasicBlock, bbd: &BasicBlockData<'tcx>) {
                     self.current_fn_mut().push_onto_block(cover);
                 }
                 let tcode = self.codegen_terminator(term);
+                let idx = bbd.statements.len();
+                let tcode = self.codegen_secret_check(bb, idx, term.source_info.span, tcode);
+                let tcode = self.codegen_union_read_checks(bb, idx, term.source_info.span, tcode);
+                let tcode = self.codegen_uninit_read_checks(bb, idx, term.source_info.span, tcode);
                 self.current_fn_mut().push_onto_block(tcode);
             }
         }
+        self.codegen_loop_latch(bb, bbd);
         self.current_fn_mut().reset_current_bb();
     }
+
+    /// Generate the goto that carries the contracts of a loop that `bb` jumps back to, after the
+    /// last block of the loop that does. Every back edge of the loop jumps to this goto instead of
+    /// the loop head (see `CurrentFnCtx::find_label`), whatever the kind of its terminator, so the
+    /// loop has a single back edge with the contracts.
+    fn codegen_loop_latch(&mut self, bb: BasicBlock, bbd: &BasicBlockData<'tcx>) {
+        let mut heads: Vec<_> = bbd
+            .terminator()
+            .successors()
+            .filter(|target| self.current_fn().is_contract_back_edge(target))
+            .collect();
+        heads.sort();
+        heads.dedup();
+        let loc = self.codegen_span(&bbd.terminator().source_info.span);
+        for head in heads {
+            let current_fn = self.current_fn();
+            if current_fn.loop_latches(&head).last() != Some(&bb) {
+                continue;
+            }
+            let inv = current_fn.loop_invariant(&head).unwrap().clone();
+            let decreases = current_fn.loop_decreases(&head).cloned();
+            let goto = Stmt::goto(current_fn.block_label(&head), loc)
+                .with_loop_contracts(inv, decreases)
+                .with_label(current_fn.latch_label(&head));
+            self.current_fn_mut().push_onto_block(goto);
+        }
+    }
+
+    /// With `--constant-time-checks`, prepend a failing `constant_time` check to the statement
+    /// or terminator at `statement_index` if it branches or accesses memory depending on a secret.
+    fn codegen_secret_check(
+        &mut self,
+        block: BasicBlock,
+        statement_index: usize,
+        span: Span,
+        code: Stmt,
+    ) -> Stmt {
+        let location = Location { block, statement_index };
+        let Some(sink) = self.secret_sinks.get(self.current_fn().instance(), location) else {
+            return code;
+        };
+        let loc = self.codegen_span(&span);
+        let msg = format!("{} is not constant-time", sink.description());
+        let check = self.codegen_assert(Expr::bool_false(), PropertyClass::ConstantTime, &msg, loc);
+        Stmt::block(vec![check, code], loc)
+    }
 }
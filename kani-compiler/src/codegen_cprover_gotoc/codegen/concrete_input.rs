@@ -0,0 +1,86 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module generates the assumptions that fix the symbolic inputs of a harness to the
+//! concrete values given with `--concrete`, e.g., `x == 5` after `let x: u32 = kani::any();`
+//! for `--concrete x=5`. The other inputs of the harness stay symbolic.
+
+use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::kani_middle::attributes::is_proof_harness;
+use crate::kani_middle::concrete_inputs::concrete_input_locals;
+use cbmc::goto_program::{Expr, Location, Stmt};
+use rustc_middle::mir::Place;
+use rustc_middle::ty::layout::LayoutOf;
+use rustc_middle::ty::{self, FloatTy, ScalarInt, Ty};
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// If the current function is a harness, register the concrete values of the inputs that
+    /// `--concrete` fixes.
+    pub fn codegen_concrete_inputs(&mut self) {
+        let inputs = self.queries.args().concrete_inputs.clone();
+        if inputs.is_empty() || !is_proof_harness(self.tcx, self.current_fn().instance().def_id()) {
+            return;
+        }
+        let mir = self.current_fn().mir();
+        for (local, (name, value)) in concrete_input_locals(self.tcx, mir, &inputs) {
+            let decl = &mir.local_decls[local];
+            let ty = self.monomorphize(decl.ty);
+            match self.codegen_concrete_value(ty, value) {
+                Some(expr) => self.current_fn_mut().register_concrete_input(local, expr),
+                None => {
+                    let mut diag = self.tcx.sess.struct_span_err(
+                        decl.source_info.span,
+                        format!("cannot fix input `{name}` of type `{ty}` to `{value}`"),
+                    );
+                    diag.help(
+                        "`--concrete` supports inputs of type `bool`, `char`, and of the integer \
+                        and floating-point types, with values written like Rust literals without \
+                        a suffix",
+                    );
+                    diag.emit();
+                }
+            }
+        }
+    }
+
+    /// The assumption that the symbolic input that a call to `kani::any` stores in
+    /// `destination` is equal to its concrete value, if `--concrete` fixes it.
+    pub fn codegen_concrete_input_assumption(
+        &mut self,
+        destination: &Place<'tcx>,
+        loc: Location,
+    ) -> Option<Stmt> {
+        if !destination.projection.is_empty() {
+            return None;
+        }
+        let value = self.current_fn().concrete_input(&destination.local)?.clone();
+        let input = self.codegen_place(destination).ok()?.goto_expr;
+        Some(Stmt::assume(input.eq(value), loc))
+    }
+
+    /// The expression of `value` as a value of type `ty`, if it is one.
+    fn codegen_concrete_value(&mut self, ty: Ty<'tcx>, value: &str) -> Option<Expr> {
+        let typ = self.codegen_ty(ty);
+        match ty.kind() {
+            ty::Bool => value.parse().ok().map(Expr::c_bool_constant),
+            ty::Char => {
+                let mut chars = value.chars();
+                let c = chars.next().filter(|_| chars.next().is_none())?;
+                Some(Expr::int_constant(u32::from(c), typ))
+            }
+            ty::Int(_) => {
+                let value: i128 = value.parse().ok()?;
+                ScalarInt::try_from_int(value, self.layout_of(ty).size)?;
+                Some(Expr::int_constant(value, typ))
+            }
+            ty::Uint(_) => {
+                let value: u128 = value.parse().ok()?;
+                ScalarInt::try_from_uint(value, self.layout_of(ty).size)?;
+                Some(Expr::int_constant(value, typ))
+            }
+            ty::Float(FloatTy::F32) => value.parse().ok().map(Expr::float_constant),
+            ty::Float(FloatTy::F64) => value.parse().ok().map(Expr::double_constant),
+            _ => None,
+        }
+    }
+}
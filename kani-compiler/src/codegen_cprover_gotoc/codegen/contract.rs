@@ -0,0 +1,76 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This file contains the code that hands over parts of a function contract to CBMC.
+//!
+//! Most of a contract is checked by code that the contract macros generate. The exception is
+//! the `modifies` clause: only CBMC can check that a function does not write to any memory
+//! other than the one listed, so we emit it as the assigns clause of a goto contract.
+
+use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::kani_middle::attributes::{GFnContract, KaniAttributes};
+use cbmc::goto_program::{Expr, FunctionContract, Lambda};
+use rustc_span::Symbol;
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// If the current function lists the memory it may modify with
+    /// `#[kanitool::modifies(..)]`, attach the corresponding goto contract to
+    /// its symbol and record the function, so the driver can ask CBMC to
+    /// enforce the contract.
+    pub fn codegen_modifies_contract(&mut self) {
+        let def_id = self.current_fn().instance().def_id();
+        let Some(contract) = KaniAttributes::for_item(self.tcx, def_id).modifies_contract() else {
+            return;
+        };
+        let name = self.current_fn().name();
+        if self.symbol_table.lookup(&name).unwrap().contract.is_some() {
+            return;
+        }
+        let contract = self.as_goto_contract(&contract);
+        self.symbol_table.attach_contract(&name, contract);
+        self.contracted_functions.push(name.into());
+    }
+
+    /// Translate the targets of a `modifies` clause into the assigns clause of a
+    /// goto contract. Each target must be an argument of the current function
+    /// that is a reference or a raw pointer to a sized type. The function may
+    /// then write to the memory the argument points to.
+    fn as_goto_contract(&mut self, contract: &GFnContract<Symbol>) -> FunctionContract {
+        let fn_typ = self.fn_typ();
+        let span = self.tcx.def_span(self.current_fn().instance().def_id());
+        let mir = self.current_fn().mir();
+        let assigns = contract
+            .assigns()
+            .iter()
+            .filter_map(|target| {
+                let Some(arg) = mir
+                    .args_iter()
+                    .find(|arg| self.find_debug_info(arg).is_some_and(|info| info.name == *target))
+                else {
+                    self.tcx.sess.span_err(
+                        span,
+                        format!("`{target}` in the `modifies` clause is not an argument"),
+                    );
+                    return None;
+                };
+                let arg_ty = self.monomorphize(mir.local_decls[arg].ty);
+                let is_thin_pointer =
+                    arg_ty.builtin_deref(true).is_some_and(|pointee| !self.is_unsized(pointee.ty));
+                if !is_thin_pointer {
+                    self.tcx.sess.span_err(
+                        span,
+                        format!(
+                            "`{target}` in the `modifies` clause has type `{arg_ty}`, but only \
+                            references and raw pointers to sized types are supported"
+                        ),
+                    );
+                    return None;
+                }
+                let arg_expr =
+                    Expr::symbol_expression(self.codegen_var_name(&arg), self.codegen_ty(arg_ty));
+                Some(Lambda::as_contract_for(&fn_typ, arg_expr.dereference()))
+            })
+            .collect();
+        FunctionContract::new(assigns)
+    }
+}
@@ -0,0 +1,295 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module generates the checks of `--enum-validity-checks`, which make sure that a value of
+//! an enum type that was created without the guarantees of the compiler carries a valid
+//! discriminant, and that the payload of its variant is valid as well.
+//!
+//! The values that are checked are the results of transmutes, of reads through raw pointers
+//! (which includes `std::ptr::read`) and of reads of union fields, as well as the values returned
+//! by foreign functions. The checks follow the layout that rustc chose for the enum, so they are
+//! precise down to the bit: a niche-encoded `Option<bool>` whose byte is 3 is reported just like
+//! a discriminant that doesn't belong to any variant.
+//!
+//! Before checking it, the tag of the enum is copied to a variable whose name starts with
+//! [ENUM_TAG_PREFIX], so the driver can show the invalid value when the check fails.
+
+use crate::codegen_cprover_gotoc::codegen::PropertyClass;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use cbmc::goto_program::{Expr, Location, Stmt, Type};
+use rustc_middle::mir::{CastKind, Operand, Place, ProjectionElem, Rvalue};
+use rustc_middle::ty::layout::{LayoutOf, TyAndLayout};
+use rustc_middle::ty::{self, Ty};
+use rustc_target::abi::{
+    Abi, FieldIdx, FieldsShape, Primitive, Scalar, TagEncoding, VariantIdx, Variants, WrappingRange,
+};
+
+/// The prefix of the variables that hold the tag of the checked enums.
+const ENUM_TAG_PREFIX: &str = "kani_enum_tag";
+
+/// How deep the payload of an enum is inspected. For instance, the `bool` in
+/// `Option<(u8, Option<bool>)>` is three levels deep.
+const MAX_PAYLOAD_DEPTH: usize = 4;
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// Whether the value that `rvalue` produces may be an invalid value of an enum type, and thus
+    /// needs to be checked after it's assigned.
+    pub fn needs_enum_validity_check(&self, rvalue: &Rvalue<'tcx>) -> bool {
+        if !self.queries.args().enum_validity_checks {
+            return false;
+        }
+        match rvalue {
+            Rvalue::Cast(CastKind::Transmute, ..) => true,
+            Rvalue::Use(Operand::Copy(place) | Operand::Move(place))
+            | Rvalue::CopyForDeref(place) => self.is_unchecked_read(place),
+            _ => false,
+        }
+    }
+
+    /// Whether reading `place` goes through a raw pointer or a union field.
+    fn is_unchecked_read(&self, place: &Place<'tcx>) -> bool {
+        place.iter_projections().any(|(base, elem)| {
+            let base_ty = self.monomorphize(base.ty(self.current_fn().mir(), self.tcx).ty);
+            match elem {
+                ProjectionElem::Deref => base_ty.is_unsafe_ptr(),
+                ProjectionElem::Field(..) => base_ty.is_union(),
+                _ => false,
+            }
+        })
+    }
+
+    /// With `--enum-validity-checks`, generate the checks that `value`, an lvalue of type `ty`,
+    /// has a valid discriminant and a valid payload if `ty` is an enum.
+    pub fn codegen_enum_validity_check(
+        &mut self,
+        value: Expr,
+        ty: Ty<'tcx>,
+        loc: Location,
+    ) -> Option<Stmt> {
+        if !self.queries.args().enum_validity_checks || !ty.is_enum() {
+            return None;
+        }
+        let layout = self.layout_of(ty);
+        if layout.abi.is_uninhabited() {
+            return Some(self.codegen_assert_assume(
+                Expr::bool_false(),
+                PropertyClass::EnumValidity,
+                &format!("invalid discriminant for enum `{ty}`, which has no valid values"),
+                loc,
+            ));
+        }
+        let mut stmts = vec![];
+        let valid_tag = if let Variants::Multiple { .. } = layout.variants {
+            let tag = self.codegen_enum_tag(value.clone(), layout);
+            let (tag_var, decl) =
+                self.decl_named_temp_variable(ENUM_TAG_PREFIX, tag.typ().clone(), Some(tag), loc);
+            let valid_tag = self.codegen_valid_tag(tag_var, layout);
+            stmts.extend(valid_tag.is_some().then_some(decl));
+            valid_tag
+        } else {
+            None
+        };
+        if let Some(cond) = valid_tag {
+            stmts.push(self.codegen_assert_assume(
+                cond,
+                PropertyClass::EnumValidity,
+                &format!("invalid discriminant for enum `{ty}`"),
+                loc,
+            ));
+        }
+        if let Some(cond) = self.codegen_valid_payload(value, layout, 0) {
+            stmts.push(self.codegen_assert_assume(
+                cond,
+                PropertyClass::EnumValidity,
+                &format!("invalid payload for the variant of enum `{ty}`"),
+                loc,
+            ));
+        }
+        (!stmts.is_empty()).then(|| Stmt::block(stmts, loc))
+    }
+
+    /// The raw tag of an enum with multiple variants, i.e., its discriminant field with a direct
+    /// encoding, or the field that holds the niche otherwise.
+    fn codegen_enum_tag(&mut self, value: Expr, layout: TyAndLayout<'tcx>) -> Expr {
+        let Variants::Multiple { tag_encoding, .. } = &layout.variants else {
+            unreachable!("only enums with multiple variants have a tag")
+        };
+        match tag_encoding {
+            TagEncoding::Direct => self.codegen_discriminant_field(value, layout.ty),
+            TagEncoding::Niche { .. } => {
+                let offset = match &layout.fields {
+                    FieldsShape::Arbitrary { offsets, .. } => offsets[0usize.into()],
+                    _ => unreachable!("niche encoding must have arbitrary fields"),
+                };
+                let tag_ty = self.codegen_enum_discr_typ(layout.ty);
+                let tag_ty = self.codegen_ty(tag_ty);
+                self.codegen_get_niche(value, offset, tag_ty)
+            }
+        }
+    }
+
+    /// The condition under which `tag` encodes one of the variants of the enum. Returns `None`
+    /// if every value of the tag is valid.
+    fn codegen_valid_tag(&mut self, tag: Expr, layout: TyAndLayout<'tcx>) -> Option<Expr> {
+        let Variants::Multiple { tag: tag_scalar, tag_encoding, variants, .. } = &layout.variants
+        else {
+            return None;
+        };
+        match tag_encoding {
+            TagEncoding::Direct => {
+                let range = tag_scalar.valid_range(self);
+                let size = tag_scalar.size(self);
+                let range_len = size.truncate(range.end.wrapping_sub(range.start)) + 1;
+                if range_len == variants.len() as u128 {
+                    // The discriminants are contiguous, so the range check is exact.
+                    self.codegen_in_range(tag, *tag_scalar)
+                } else {
+                    let ty = layout.ty;
+                    let matches: Vec<Expr> = variants
+                        .indices()
+                        .map(|idx| {
+                            let discr = self.codegen_discriminant_value(ty, idx);
+                            tag.clone().eq(discr.cast_to(tag.typ().clone()))
+                        })
+                        .collect();
+                    matches.into_iter().reduce(Expr::or)
+                }
+            }
+            // Any value of the niche that isn't reserved for the other variants belongs to the
+            // untagged variant, whose own invariant is included in the valid range of the tag.
+            TagEncoding::Niche { .. } => self.codegen_in_range(tag, *tag_scalar),
+        }
+    }
+
+    /// The condition under which the integer `value` is in the valid range of `scalar`. Returns
+    /// `None` if the whole range is valid, or if `value` is a pointer.
+    fn codegen_in_range(&self, value: Expr, scalar: Scalar) -> Option<Expr> {
+        let size = scalar.size(self);
+        let WrappingRange { start, end } = scalar.valid_range(self);
+        if scalar.is_always_valid(self) || matches!(scalar.primitive(), Primitive::Pointer(_)) {
+            return None;
+        }
+        let unsigned = Type::unsigned_int(size.bits());
+        let value = value.cast_to(unsigned.clone());
+        // The range may wrap around, e.g., `-1..=1` for `std::cmp::Ordering`.
+        let offset = value.sub(Expr::int_constant(start, unsigned.clone()));
+        let len = size.truncate(end.wrapping_sub(start));
+        Some(offset.le(Expr::int_constant(len, unsigned)))
+    }
+
+    /// The discriminant of the variant `idx` of the enum `ty`, with the type of the discriminant.
+    fn codegen_discriminant_value(&mut self, ty: Ty<'tcx>, idx: VariantIdx) -> Expr {
+        let discr = ty.discriminant_for_variant(self.tcx, idx).unwrap();
+        Expr::int_constant(discr.val, self.codegen_ty(discr.ty))
+    }
+
+    /// The condition under which the payload of the current variant of `value`, an enum, is
+    /// valid. Returns `None` if no payload can be invalid.
+    fn codegen_valid_payload(
+        &mut self,
+        value: Expr,
+        layout: TyAndLayout<'tcx>,
+        depth: usize,
+    ) -> Option<Expr> {
+        let ty = layout.ty;
+        let discr_ty = ty.discriminant_ty(self.tcx);
+        let variants: Vec<VariantIdx> = match &layout.variants {
+            Variants::Single { index } => vec![*index],
+            Variants::Multiple { variants, .. } => variants.indices().collect(),
+        };
+        let single_variant = variants.len() == 1;
+        let mut conditions = vec![];
+        for idx in variants {
+            let variant_layout = layout.for_variant(self, idx);
+            let valid_fields = if variant_layout.abi.is_uninhabited() {
+                Some(Expr::bool_false())
+            } else {
+                self.codegen_valid_fields(value.clone(), variant_layout, Some(idx), depth)
+            };
+            let Some(valid_fields) = valid_fields else { continue };
+            if single_variant {
+                conditions.push(valid_fields);
+            } else {
+                let discr = self.codegen_get_discriminant(value.clone(), ty, discr_ty);
+                let variant_discr = self.codegen_discriminant_value(ty, idx);
+                let is_variant = variant_discr.cast_to(discr.typ().clone()).eq(discr);
+                conditions.push(is_variant.implies(valid_fields));
+            }
+        }
+        conditions.into_iter().reduce(Expr::and)
+    }
+
+    /// The condition under which the fields of `value` (or of its variant `variant`) are valid.
+    fn codegen_valid_fields(
+        &mut self,
+        value: Expr,
+        layout: TyAndLayout<'tcx>,
+        variant: Option<VariantIdx>,
+        depth: usize,
+    ) -> Option<Expr> {
+        let mut conditions = vec![];
+        for idx in 0..layout.fields.count() {
+            let field = layout.field(self, idx);
+            if field.abi.is_uninhabited() {
+                conditions.push(Expr::bool_false());
+            } else if !field.is_zst() {
+                let field_value = self.codegen_value_field(
+                    value.clone(),
+                    layout.ty,
+                    variant,
+                    FieldIdx::from_usize(idx),
+                    field.ty,
+                );
+                conditions.extend(self.codegen_valid_value(field_value, field, depth + 1));
+            }
+        }
+        conditions.into_iter().reduce(Expr::and)
+    }
+
+    /// The condition under which `value`, a field of an enum payload, is a valid value of its
+    /// type. Only the types whose validity depends on the value itself are inspected, i.e.,
+    /// enums, scalars such as `bool` and `char`, and the structs and tuples that contain them.
+    fn codegen_valid_value(
+        &mut self,
+        value: Expr,
+        layout: TyAndLayout<'tcx>,
+        depth: usize,
+    ) -> Option<Expr> {
+        if depth > MAX_PAYLOAD_DEPTH {
+            return None;
+        }
+        match layout.ty.kind() {
+            ty::Adt(def, _) if def.is_enum() => {
+                let valid_tag = if let Variants::Multiple { .. } = layout.variants {
+                    let tag = self.codegen_enum_tag(value.clone(), layout);
+                    self.codegen_valid_tag(tag, layout)
+                } else {
+                    None
+                };
+                let valid_payload = self.codegen_valid_payload(value, layout, depth);
+                match (valid_tag, valid_payload) {
+                    (Some(tag), Some(payload)) => Some(tag.and(payload)),
+                    (tag, payload) => tag.or(payload),
+                }
+            }
+            ty::Bool | ty::Char => self.codegen_valid_scalar(value, layout),
+            ty::Adt(def, _) if def.is_struct() => self
+                .codegen_valid_scalar(value.clone(), layout)
+                .or_else(|| self.codegen_valid_fields(value, layout, None, depth)),
+            ty::Tuple(_) => self.codegen_valid_fields(value, layout, None, depth),
+            _ => None,
+        }
+    }
+
+    /// The condition under which a value with a scalar layout, e.g., a `bool` or a
+    /// `NonZeroU8`, is in its valid range.
+    fn codegen_valid_scalar(&self, value: Expr, layout: TyAndLayout<'tcx>) -> Option<Expr> {
+        match layout.abi {
+            Abi::Scalar(scalar) if value.can_take_address_of() => {
+                let int = value.reinterpret_cast(Type::unsigned_int(scalar.size(self).bits()));
+                self.codegen_in_range(int, scalar)
+            }
+            _ => None,
+        }
+    }
+}
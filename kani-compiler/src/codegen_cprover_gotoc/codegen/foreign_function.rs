@@ -3,10 +3,12 @@
 //! This module implements foreign function handling.
 //!
 //! Kani currently only support CBMC built-in functions that are declared in the `cprover_bindings`
-//! crate, and allocation functions defined in `kani_lib.c`.
+//! crate, allocation functions defined in `kani_lib.c`, and the LLVM intrinsics of `std::arch`
+//! that are listed in [LLVM_INTRINSICS].
 //!
 //! All other functions will be replaced by an unimplemented check, due to current issues with
-//! linking and usability unless unstable C-FFI support is enabled.
+//! linking and usability unless unstable C-FFI support is enabled, or unless the user chose how to
+//! model them with the unstable `--ffi-policy` option.
 use std::collections::HashSet;
 
 use crate::codegen_cprover_gotoc::codegen::PropertyClass;
@@ -14,8 +16,9 @@
 use crate::kani_middle;
 use cbmc::goto_program::{Expr, Location, Stmt, Symbol, Type};
 use cbmc::{InternString, InternedString};
+use kani_metadata::FfiPolicy;
 use lazy_static::lazy_static;
-use rustc_middle::ty::Instance;
+use rustc_middle::ty::{self, Instance, Ty};
 use rustc_target::abi::call::Conv;
 use tracing::{debug, trace};
 
@@ -34,6 +37,17 @@
             "__rust_realloc".into(),
         ])
     };
+
+    /// The LLVM intrinsics that `std::arch` declares as foreign functions, and for which Kani
+    /// generates a body. The other SIMD operations of `std::arch` use the `simd_*` intrinsics.
+    static ref LLVM_INTRINSICS: HashSet<InternedString> = {
+        HashSet::from([
+            // `_mm_shuffle_epi8`
+            "llvm.x86.ssse3.pshuf.b.128".into(),
+            // `_mm256_shuffle_epi8`
+            "llvm.x86.avx2.pshuf.b".into(),
+        ])
+    };
 }
 
 impl<'tcx> GotocCtx<'tcx> {
@@ -50,12 +64,39 @@ pub fn codegen_foreign_fn(&mut self, instance: Instance<'tcx>) -> &Symbol {
         if self.symbol_table.contains(fn_name) {
             // Symbol has been added (either a built-in CBMC function or a Rust allocation function).
             self.symbol_table.lookup(fn_name).unwrap()
+        } else if LLVM_INTRINSICS.contains(&fn_name) {
+            let shim_name = format!("{fn_name}_shim");
+            trace!(?shim_name, "codegen_foreign_function");
+            self.ensure(&shim_name, |gcx, _| {
+                let typ = gcx.codegen_ffi_type(instance);
+                Symbol::function(
+                    &shim_name,
+                    typ,
+                    Some(gcx.codegen_llvm_intrinsic_body(instance)),
+                    gcx.readable_instance_name(instance),
+                    Location::none(),
+                )
+            })
+        } else if let Some(policy) = self.ffi_policy(fn_name) {
+            let shim_name = format!("{fn_name}_ffi_shim");
+            trace!(?shim_name, ?policy, "codegen_foreign_function");
+            self.ensure(&shim_name, |gcx, _| {
+                let typ = gcx.codegen_ffi_type(instance);
+                Symbol::function(
+                    &shim_name,
+                    typ,
+                    Some(gcx.codegen_ffi_policy_body(instance, policy)),
+                    gcx.readable_instance_name(instance),
+                    Location::none(),
+                )
+            })
         } else if RUST_ALLOC_FNS.contains(&fn_name)
             || (self.is_cffi_enabled() && kani_middle::fn_abi(self.tcx, instance).conv == Conv::C)
         {
             // Add a Rust alloc lib function as is declared by core.
-            // When C-FFI feature is enabled, we just trust the rust declaration.
-            // TODO: Add proper casting and clashing definitions check.
+            // When C-FFI feature is enabled, we just trust the rust declaration. The driver
+            // compares it with the definitions of the C files given with `--c-lib` when linking.
+            // TODO: Add proper casting.
             // https://github.com/model-checking/kani/issues/1350
             // https://github.com/model-checking/kani/issues/2426
             self.ensure(fn_name, |gcx, _| {
@@ -92,6 +133,112 @@ fn is_cffi_enabled(&self) -> bool {
         self.queries.args().unstable_features.contains(&"c-ffi".to_string())
     }
 
+    /// The policy that the user chose for the foreign function with the given link name, if any.
+    fn ffi_policy(&self, fn_name: InternedString) -> Option<FfiPolicy> {
+        self.queries
+            .args()
+            .ffi_policies
+            .iter()
+            .find_map(|(name, policy)| (fn_name == name.as_str()).then_some(*policy))
+    }
+
+    /// Generate the body of a foreign function according to its FFI policy.
+    ///
+    /// For `havoc` and `zeroed`, the function writes to the pointee of its non-null mutable
+    /// pointer arguments (except for `c_void` and unsized pointees, whose size is unknown), and
+    /// then returns. The values aren't restricted to the valid values of their Rust types.
+    /// Functions that never return just block the execution.
+    fn codegen_ffi_policy_body(&mut self, instance: Instance<'tcx>, policy: FfiPolicy) -> Stmt {
+        let loc = self.codegen_span(&self.tcx.def_span(instance.def_id()));
+        if policy == FfiPolicy::Stub {
+            let fn_name = self.symbol_name(instance);
+            self.tcx.sess.span_err(
+                self.tcx.def_span(instance.def_id()),
+                format!(
+                    "foreign function `{fn_name}` is reachable, but the FFI policy requires it to \
+                    be stubbed"
+                ),
+            );
+            return self.codegen_ffi_shim(format!("{fn_name}_ffi_shim").into(), instance);
+        }
+
+        let fn_name = self.symbol_name(instance);
+        let fn_abi = kani_middle::fn_abi(self.tcx, instance);
+        let mut body = vec![];
+        for (idx, arg) in fn_abi.args.iter().enumerate().filter(|&(_, arg)| !arg.is_ignore()) {
+            let Some(pointee) = self.ffi_out_param_pointee(arg.layout.ty) else { continue };
+            let param = Expr::symbol_expression(
+                format!("{fn_name}::param_{idx}"),
+                self.codegen_ty(arg.layout.ty),
+            );
+            let value = self.ffi_policy_value(policy, pointee);
+            body.push(param.clone().is_nonnull().if_then_else(
+                param.dereference().assign(value, loc),
+                None,
+                loc,
+            ));
+        }
+        if fn_abi.ret.layout.abi.is_uninhabited() {
+            body.push(Stmt::assume(Expr::bool_false(), loc));
+        } else {
+            body.push(self.ffi_policy_value(policy, fn_abi.ret.layout.ty).ret(loc));
+        }
+        Stmt::block(body, loc)
+    }
+
+    /// Generate the body of one of the [LLVM_INTRINSICS].
+    ///
+    /// `pshufb` picks the bytes of its first vector with the indices of its second vector within
+    /// each 128-bit lane, or zero if the most significant bit of the index is set.
+    fn codegen_llvm_intrinsic_body(&mut self, instance: Instance<'tcx>) -> Stmt {
+        let loc = self.codegen_span(&self.tcx.def_span(instance.def_id()));
+        let fn_name = self.symbol_name(instance);
+        let fn_abi = kani_middle::fn_abi(self.tcx, instance);
+        let params: Vec<_> = (0..fn_abi.args.len())
+            .map(|idx| {
+                let typ = self.codegen_ty(fn_abi.args[idx].layout.ty);
+                Expr::symbol_expression(format!("{fn_name}::param_{idx}"), typ)
+            })
+            .collect();
+        let ret_typ = self.codegen_ty(fn_abi.ret.layout.ty);
+        let (bytes, indices) = (&params[0], &params[1]);
+        let byte_typ = ret_typ.base_type().unwrap().clone();
+        let elems = (0..ret_typ.len().unwrap())
+            .map(|i| {
+                let index = indices.clone().index_array(Expr::int_constant(i, Type::ssize_t()));
+                let lane_start = Expr::int_constant(i / 16 * 16, Type::ssize_t());
+                let byte = bytes.clone().index_array(
+                    index
+                        .clone()
+                        .bitand(Expr::int_constant(0x0f, byte_typ.clone()))
+                        .cast_to(Type::ssize_t())
+                        .plus(lane_start),
+                );
+                let is_zeroed = index.bitand(Expr::int_constant(0x80, byte_typ.clone())).is_zero();
+                is_zeroed.ternary(byte, byte_typ.zero())
+            })
+            .collect();
+        Stmt::block(vec![Expr::vector_expr(ret_typ, elems).ret(loc)], loc)
+    }
+
+    /// The pointee of a mutable pointer argument that a foreign function may write to.
+    fn ffi_out_param_pointee(&self, ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
+        let pointee = ty.builtin_deref(true).filter(|pointee| pointee.mutbl.is_mut())?.ty;
+        let is_c_void = matches!(pointee.kind(), ty::Adt(def, _)
+            if self.tcx.lang_items().c_void() == Some(def.did()));
+        (!is_c_void && pointee.is_sized(self.tcx, ty::ParamEnv::reveal_all())).then_some(pointee)
+    }
+
+    /// The value that a foreign function produces for the given type according to its policy.
+    fn ffi_policy_value(&mut self, policy: FfiPolicy, ty: Ty<'tcx>) -> Expr {
+        let typ = self.codegen_ty(ty);
+        match policy {
+            FfiPolicy::Havoc => typ.nondet(),
+            FfiPolicy::Zeroed => typ.zero_initializer(&self.symbol_table),
+            FfiPolicy::Stub => unreachable!("stubbed foreign functions have no body"),
+        }
+    }
+
     /// Generate code for a foreign function shim.
     fn codegen_ffi_shim(&mut self, shim_name: InternedString, instance: Instance<'tcx>) -> Stmt {
         debug!(?shim_name, ?instance, sym=?self.symbol_table.lookup(shim_name), "generate_foreign_shim");
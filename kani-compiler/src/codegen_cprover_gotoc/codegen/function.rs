@@ -3,8 +3,10 @@
 
 //! This file contains functions related to codegenning MIR functions into gotoc
 
+use crate::codegen_cprover_gotoc::codegen::PropertyClass;
 use crate::codegen_cprover_gotoc::GotocCtx;
-use cbmc::goto_program::{Expr, Stmt, Symbol};
+use crate::kani_middle::attributes::is_proof_harness;
+use cbmc::goto_program::{Expr, Location, Stmt, Symbol, Type};
 use cbmc::InternString;
 use rustc_middle::mir::traversal::reverse_postorder;
 use rustc_middle::mir::{Body, HasLocalDecls, Local};
@@ -13,6 +15,14 @@
 use std::iter::FromIterator;
 use tracing::{debug, debug_span};
 
+/// Name of the thread-local variable that keeps track of the current call stack depth.
+/// This is only used when the user sets a maximum stack depth.
+const STACK_DEPTH_VAR_NAME: &str = "__kani_stack_depth";
+
+/// Name of the global variable that keeps track of the deepest call stack of any thread.
+/// This is only used when the user sets a maximum stack depth.
+const MAX_STACK_DEPTH_VAR_NAME: &str = "__kani_max_stack_depth";
+
 /// Codegen MIR functions into gotoc
 impl<'tcx> GotocCtx<'tcx> {
     /// Get the number of parameters that the current function expects.
@@ -79,7 +89,11 @@ pub fn codegen_function(&mut self, instance: Instance<'tcx>) {
             let mir = self.current_fn().mir();
             self.print_instance(instance, mir);
             self.codegen_function_prelude();
+            self.codegen_stack_depth_entry(self.codegen_span(&mir.span));
             self.codegen_declare_variables();
+            self.codegen_declare_union_init_vars();
+            self.codegen_uninit_prelude();
+            self.codegen_concrete_inputs();
 
             reverse_postorder(mir).for_each(|(bb, bbd)| self.codegen_block(bb, bbd));
 
@@ -91,6 +105,99 @@ pub fn codegen_function(&mut self, instance: Instance<'tcx>) {
         self.reset_current_fn();
     }
 
+    /// Returns the variable used to track the call stack depth, declaring it if needed.
+    ///
+    /// Each thread has its own call stack, so the variable is thread-local. CBMC initializes the
+    /// thread-local variables of a new thread to zero.
+    fn stack_depth_var(&mut self) -> Expr {
+        if !self.symbol_table.contains(STACK_DEPTH_VAR_NAME.into()) {
+            let sym = Symbol::static_variable(
+                STACK_DEPTH_VAR_NAME,
+                STACK_DEPTH_VAR_NAME,
+                Type::unsigned_int(32),
+                Location::none(),
+            )
+            .with_is_thread_local(true)
+            .with_is_hidden(false);
+            let var = sym.to_expr();
+            self.symbol_table.insert(sym);
+            let zero = Expr::int_constant(0, Type::unsigned_int(32));
+            self.register_initializer(
+                STACK_DEPTH_VAR_NAME,
+                Stmt::assign(var, zero, Location::none()),
+            );
+        }
+        self.symbol_table.lookup(STACK_DEPTH_VAR_NAME).unwrap().to_expr()
+    }
+
+    /// Returns the global variable used to track the deepest call stack of any thread, declaring
+    /// it if needed.
+    fn max_stack_depth_var(&mut self) -> Expr {
+        self.ensure_global_var(
+            MAX_STACK_DEPTH_VAR_NAME,
+            false,
+            Type::unsigned_int(32),
+            Location::none(),
+            |_, var| {
+                Some(Stmt::assign(
+                    var,
+                    Expr::int_constant(0, Type::unsigned_int(32)),
+                    Location::none(),
+                ))
+            },
+        )
+    }
+
+    /// If the user set a maximum stack depth, increment the call stack depth when entering the
+    /// current function, record the deepest call stack, and check that it doesn't exceed the
+    /// maximum.
+    fn codegen_stack_depth_entry(&mut self, loc: Location) {
+        let Some(max_depth) = self.queries.args().max_stack_depth else { return };
+        let depth = self.stack_depth_var();
+        let deepest = self.max_stack_depth_var();
+        let one = Expr::int_constant(1, depth.typ().clone());
+        let increment = depth.clone().assign(depth.clone().plus(one), loc);
+        let record = deepest
+            .clone()
+            .assign(depth.clone().gt(deepest.clone()).ternary(depth.clone(), deepest), loc);
+        let check = self.codegen_assert_assume(
+            depth.le(Expr::int_constant(max_depth, Type::unsigned_int(32))),
+            PropertyClass::StackDepth,
+            &format!("exceeded the maximum call stack depth of {max_depth}"),
+            loc,
+        );
+        self.current_fn_mut().push_onto_block(increment);
+        self.current_fn_mut().push_onto_block(record);
+        self.current_fn_mut().push_onto_block(check);
+    }
+
+    /// If the user set a maximum stack depth, generate the statement that decrements the call
+    /// stack depth before returning from the current function.
+    ///
+    /// When the harness returns, this also checks which call stack depths were reached, with one
+    /// cover-like property per depth up to the maximum, so that the driver can report the deepest
+    /// call stack.
+    pub fn codegen_stack_depth_exit(&mut self, loc: Location) -> Option<Stmt> {
+        let max_depth = self.queries.args().max_stack_depth?;
+        let depth = self.stack_depth_var();
+        let one = Expr::int_constant(1, depth.typ().clone());
+        let decrement = depth.clone().assign(depth.sub(one), loc);
+        if !is_proof_harness(self.tcx, self.current_fn().instance().def_id()) {
+            return Some(decrement);
+        }
+        let deepest = self.max_stack_depth_var();
+        let reached = (1..=max_depth).map(|bound| {
+            let cond = deepest.clone().ge(Expr::int_constant(bound, Type::unsigned_int(32)));
+            self.codegen_assert(
+                cond.not(),
+                PropertyClass::StackDepthReached,
+                &format!("reached a call stack depth of {bound}"),
+                loc,
+            )
+        });
+        Some(Stmt::block(reached.chain([decrement]).collect(), loc))
+    }
+
     /// Codegen changes required due to the function ABI.
     /// We currently untuple arguments for RustCall ABI where the `spread_arg` is set.
     fn codegen_function_prelude(&mut self) {
@@ -237,6 +344,7 @@ pub fn declare_function(&mut self, instance: Instance<'tcx>) {
                 ctx.codegen_span(&mir.span),
             )
         });
+        self.codegen_modifies_contract();
         self.reset_current_fn();
     }
 }
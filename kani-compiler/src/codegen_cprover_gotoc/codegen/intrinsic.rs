@@ -54,11 +54,17 @@ pub fn codegen_funcall_of_intrinsic(
         if let Some(target) = target {
             let loc = self.codegen_span(&span);
             let fargs = self.codegen_funcall_args(args, false);
+            let arg_tys: Vec<_> = args.iter().map(|arg| self.operand_ty(arg)).collect();
+            let intrinsic = self.symbol_name(instance);
+            let (uninit_read, uninit_write) =
+                self.codegen_uninit_intrinsic(&intrinsic, &fargs, &arg_tys, loc);
             Stmt::block(
-                vec![
-                    self.codegen_intrinsic(instance, fargs, destination, Some(span)),
-                    Stmt::goto(self.current_fn().find_label(target), loc),
-                ],
+                uninit_read
+                    .into_iter()
+                    .chain([self.codegen_intrinsic(instance, fargs, destination, Some(span))])
+                    .chain(uninit_write)
+                    .chain([Stmt::goto(self.current_fn().find_label(target), loc)])
+                    .collect(),
                 loc,
             )
         } else {
@@ -256,8 +262,9 @@ macro_rules! codegen_size_align {
         // Most atomic intrinsics do:
         //   1. Perform an operation on a primary argument (e.g., addition)
         //   2. Return the previous value of the primary argument
-        // The primary argument is always passed by reference. In a sequential
-        // context, atomic orderings can be ignored.
+        // The primary argument is always passed by reference. The operation
+        // is an atomic block, and its ordering is modeled with fences around
+        // the block, which only matter under a weak memory model.
         //
         // Atomic binops are transformed as follows:
         // -------------------------
@@ -281,7 +288,8 @@ macro_rules! codegen_atomic_binop {
                 let op_expr = (var1.clone()).$op(var2).with_location(loc);
                 let assign_stmt = (var1.clone()).assign(op_expr, loc);
                 let res_stmt = self.codegen_expr_to_place(p, tmp.clone());
-                Stmt::atomic_block(vec![decl_stmt, assign_stmt, res_stmt], loc)
+                let block = Stmt::atomic_block(vec![decl_stmt, assign_stmt, res_stmt], loc);
+                self.codegen_atomic_ordering(intrinsic, block, loc)
             }};
         }
 
@@ -330,10 +338,10 @@ macro_rules! unstable_codegen {
             name if name.starts_with("atomic_cxchg") => {
                 self.codegen_atomic_cxchg(intrinsic, fargs, p, loc)
             }
-            "atomic_fence_seqcst" => self.codegen_atomic_noop(intrinsic, loc),
-            "atomic_fence_acquire" => self.codegen_atomic_noop(intrinsic, loc),
-            "atomic_fence_acqrel" => self.codegen_atomic_noop(intrinsic, loc),
-            "atomic_fence_release" => self.codegen_atomic_noop(intrinsic, loc),
+            "atomic_fence_seqcst" => self.codegen_atomic_fence(intrinsic, loc),
+            "atomic_fence_acquire" => self.codegen_atomic_fence(intrinsic, loc),
+            "atomic_fence_acqrel" => self.codegen_atomic_fence(intrinsic, loc),
+            "atomic_fence_release" => self.codegen_atomic_fence(intrinsic, loc),
             "atomic_load_seqcst" => self.codegen_atomic_load(intrinsic, fargs, p, loc),
             "atomic_load_acquire" => self.codegen_atomic_load(intrinsic, fargs, p, loc),
             "atomic_load_relaxed" => self.codegen_atomic_load(intrinsic, fargs, p, loc),
@@ -511,6 +519,21 @@ macro_rules! unstable_codegen {
                 loc,
             ),
             "simd_and" => codegen_intrinsic_binop!(bitand),
+            // Like scalar `as` casts, float to int casts don't saturate.
+            "simd_as" | "simd_cast" => {
+                let elem_typ = cbmc_ret_ty.base_type().unwrap().clone();
+                self.codegen_simd_lanewise(fargs, p, farg_types, ret_ty, span, |mut lanes| {
+                    lanes.remove(0).cast_to(elem_typ.clone())
+                })
+            }
+            "simd_ceil" => self.codegen_simd_float_op(
+                (BuiltinFn::Ceilf, BuiltinFn::Ceil),
+                fargs,
+                p,
+                farg_types,
+                ret_ty,
+                span,
+            ),
             // TODO: `simd_rem` doesn't check for overflow cases for floating point operands.
             // <https://github.com/model-checking/kani/pull/2645>
             "simd_div" | "simd_rem" => {
@@ -520,6 +543,38 @@ macro_rules! unstable_codegen {
             "simd_extract" => {
                 self.codegen_intrinsic_simd_extract(fargs, p, farg_types, ret_ty, span)
             }
+            "simd_fabs" => self.codegen_simd_float_op(
+                (BuiltinFn::Fabsf, BuiltinFn::Fabs),
+                fargs,
+                p,
+                farg_types,
+                ret_ty,
+                span,
+            ),
+            "simd_floor" => self.codegen_simd_float_op(
+                (BuiltinFn::Floorf, BuiltinFn::Floor),
+                fargs,
+                p,
+                farg_types,
+                ret_ty,
+                span,
+            ),
+            "simd_fmax" => self.codegen_simd_float_op(
+                (BuiltinFn::Fmaxf, BuiltinFn::Fmax),
+                fargs,
+                p,
+                farg_types,
+                ret_ty,
+                span,
+            ),
+            "simd_fmin" => self.codegen_simd_float_op(
+                (BuiltinFn::Fminf, BuiltinFn::Fmin),
+                fargs,
+                p,
+                farg_types,
+                ret_ty,
+                span,
+            ),
             "simd_ge" => self.codegen_simd_cmp(Expr::vector_ge, fargs, p, span, farg_types, ret_ty),
             "simd_gt" => self.codegen_simd_cmp(Expr::vector_gt, fargs, p, span, farg_types, ret_ty),
             "simd_insert" => {
@@ -538,7 +593,51 @@ macro_rules! unstable_codegen {
             "simd_ne" => {
                 self.codegen_simd_cmp(Expr::vector_neq, fargs, p, span, farg_types, ret_ty)
             }
+            "simd_neg" => {
+                self.codegen_simd_lanewise(fargs, p, farg_types, ret_ty, span, |mut lanes| {
+                    lanes.remove(0).neg()
+                })
+            }
             "simd_or" => codegen_intrinsic_binop!(bitor),
+            "simd_reduce_add_ordered" | "simd_reduce_add_unordered" => {
+                self.codegen_simd_reduce(fargs, p, Expr::plus)
+            }
+            "simd_reduce_all" => self.codegen_simd_reduce_mask(fargs, p, ret_ty, Expr::and),
+            "simd_reduce_and" => self.codegen_simd_reduce(fargs, p, Expr::bitand),
+            "simd_reduce_any" => self.codegen_simd_reduce_mask(fargs, p, ret_ty, Expr::or),
+            "simd_reduce_max" | "simd_reduce_max_nanless" => {
+                self.codegen_simd_reduce_min_max(true, fargs, p, loc)
+            }
+            "simd_reduce_min" | "simd_reduce_min_nanless" => {
+                self.codegen_simd_reduce_min_max(false, fargs, p, loc)
+            }
+            "simd_reduce_mul_ordered" | "simd_reduce_mul_unordered" => {
+                self.codegen_simd_reduce(fargs, p, Expr::mul)
+            }
+            "simd_reduce_or" => self.codegen_simd_reduce(fargs, p, Expr::bitor),
+            "simd_reduce_xor" => self.codegen_simd_reduce(fargs, p, Expr::bitxor),
+            "simd_saturating_add" => {
+                let mm = self.symbol_table.machine_model().clone();
+                self.codegen_simd_lanewise(fargs, p, farg_types, ret_ty, span, |mut lanes| {
+                    let a = lanes.remove(0);
+                    a.saturating_add(lanes.remove(0), &mm)
+                })
+            }
+            "simd_saturating_sub" => {
+                let mm = self.symbol_table.machine_model().clone();
+                self.codegen_simd_lanewise(fargs, p, farg_types, ret_ty, span, |mut lanes| {
+                    let a = lanes.remove(0);
+                    a.saturating_sub(lanes.remove(0), &mm)
+                })
+            }
+            "simd_select" => {
+                self.codegen_simd_lanewise(fargs, p, farg_types, ret_ty, span, |mut lanes| {
+                    let mask = lanes.remove(0);
+                    let (t, e) = (lanes.remove(0), lanes.remove(0));
+                    mask.is_zero().ternary(e, t)
+                })
+            }
+            "simd_select_bitmask" => self.codegen_simd_select_bitmask(fargs, p, ret_ty, loc),
             "simd_shl" | "simd_shr" => {
                 self.codegen_simd_shift_with_distance_check(fargs, intrinsic, p, loc)
             }
@@ -836,22 +935,22 @@ fn codegen_atomic_load(
         let var1_ref = fargs.remove(0);
         let var1 = var1_ref.dereference().with_location(loc);
         let res_stmt = self.codegen_expr_to_place(p, var1);
-        Stmt::atomic_block(vec![res_stmt], loc)
+        let block = Stmt::atomic_block(vec![res_stmt], loc);
+        self.codegen_atomic_ordering(intrinsic, block, loc)
     }
 
     /// An atomic compare-and-exchange updates the value referenced in
     /// its primary argument and returns a tuple that contains:
     ///  * the previous value
     ///  * a boolean value indicating whether the operation was successful or not
-    /// In a sequential context, the update is always sucessful so we assume the
-    /// second value to be true.
+    /// The weak variants never fail spuriously.
     /// -------------------------
     /// var = atomic_cxchg(var1, var2, var3)
     /// -------------------------
     /// unsigned char tmp;
     /// tmp = *var1;
     /// if (*var1 == var2) *var1 = var3;
-    /// var = (tmp, true);
+    /// var = (tmp, tmp == var2);
     /// -------------------------
     fn codegen_atomic_cxchg(
         &mut self,
@@ -867,16 +966,18 @@ fn codegen_atomic_cxchg(
             self.decl_temp_variable(var1.typ().clone(), Some(var1.to_owned()), loc);
         let var2 = fargs.remove(0).with_location(loc);
         let var3 = fargs.remove(0).with_location(loc);
-        let eq_expr = (var1.clone()).eq(var2);
+        let eq_expr = (var1.clone()).eq(var2.clone());
         let assign_stmt = var1.assign(var3, loc);
         let cond_update_stmt = Stmt::if_then_else(eq_expr, assign_stmt, None, loc);
         let place_type = self.place_ty(p);
         let res_type = self.codegen_ty(place_type);
+        let success = tmp.clone().eq(var2).cast_to(Type::c_bool());
         let tuple_expr =
-            Expr::struct_expr_from_values(res_type, vec![tmp, Expr::c_true()], &self.symbol_table)
+            Expr::struct_expr_from_values(res_type, vec![tmp, success], &self.symbol_table)
                 .with_location(loc);
         let res_stmt = self.codegen_expr_to_place(p, tuple_expr);
-        Stmt::atomic_block(vec![decl_stmt, cond_update_stmt, res_stmt], loc)
+        let block = Stmt::atomic_block(vec![decl_stmt, cond_update_stmt, res_stmt], loc);
+        self.codegen_atomic_ordering(intrinsic, block, loc)
     }
 
     /// An atomic store updates the value referenced in
@@ -904,16 +1005,47 @@ fn codegen_atomic_store(
         let var2 = fargs.remove(0).with_location(loc);
         let assign_stmt = var1.assign(var2, loc);
         let res_stmt = self.codegen_expr_to_place(p, tmp);
-        Stmt::atomic_block(vec![decl_stmt, assign_stmt, res_stmt], loc)
+        let block = Stmt::atomic_block(vec![decl_stmt, assign_stmt, res_stmt], loc);
+        self.codegen_atomic_ordering(intrinsic, block, loc)
     }
 
-    /// Atomic no-ops (e.g., atomic_fence) are transformed into SKIP statements
+    /// Atomic no-ops (e.g., atomic_singlethreadfence) are transformed into SKIP statements
     fn codegen_atomic_noop(&mut self, intrinsic: &str, loc: Location) -> Stmt {
         self.store_concurrent_construct(intrinsic, loc);
         let skip_stmt = Stmt::skip(loc);
         Stmt::atomic_block(vec![skip_stmt], loc)
     }
 
+    /// Atomic fences are transformed into CBMC fences, which no memory access is reordered
+    /// across. The fence is stronger than acquire and release fences.
+    fn codegen_atomic_fence(&mut self, intrinsic: &str, loc: Location) -> Stmt {
+        self.store_concurrent_construct(intrinsic, loc);
+        Stmt::fence(loc)
+    }
+
+    /// Models the memory ordering of an atomic operation with fences around its atomic block:
+    ///  * Operations with release semantics (`release`, `acqrel` and `seqcst`) are preceded by
+    ///    a fence, so earlier memory accesses can't be delayed after them.
+    ///  * Operations with acquire semantics (`acquire`, `acqrel` and `seqcst`) are followed by
+    ///    a fence, so later memory accesses can't be made before them.
+    ///
+    /// For compare-exchange intrinsics, both the success and the failure orderings count.
+    /// Relaxed operations have no fence. The fences are stronger than acquire and release
+    /// semantics, e.g., a release store also can't be delayed after a later read.
+    fn codegen_atomic_ordering(&self, intrinsic: &str, block: Stmt, loc: Location) -> Stmt {
+        let orderings: Vec<_> = intrinsic.split('_').skip(2).collect();
+        let has_ordering = |names: &[&str]| orderings.iter().any(|o| names.contains(o));
+        let mut stmts = vec![];
+        if has_ordering(&["release", "acqrel", "seqcst"]) {
+            stmts.push(Stmt::fence(loc));
+        }
+        stmts.push(block);
+        if has_ordering(&["acquire", "acqrel", "seqcst"]) {
+            stmts.push(Stmt::fence(loc));
+        }
+        if stmts.len() == 1 { stmts.pop().unwrap() } else { Stmt::block(stmts, loc) }
+    }
+
     /// Copies `count * size_of::<T>()` bytes from `src` to `dst`.
     ///
     /// Note that this function handles code generation for:
@@ -1001,6 +1133,11 @@ pub fn codegen_compare_bytes(
         let lhs = fargs.remove(0).cast_to(Type::void_pointer());
         let rhs = fargs.remove(0).cast_to(Type::void_pointer());
         let len = fargs.remove(0);
+        if let Some(len) = constant_byte_count(&len) {
+            let place_type = self.codegen_ty(self.place_ty(p));
+            let cmp = self.codegen_memcmp_bitvector(lhs, rhs, len);
+            return self.codegen_expr_to_place(p, cmp.cast_to(place_type));
+        }
         let (len_var, len_decl) = self.decl_temp_variable(len.typ().clone(), Some(len), loc);
         let (lhs_var, lhs_decl) = self.decl_temp_variable(lhs.typ().clone(), Some(lhs), loc);
         let (rhs_var, rhs_decl) = self.decl_temp_variable(rhs.typ().clone(), Some(rhs), loc);
@@ -1024,6 +1161,29 @@ pub fn codegen_compare_bytes(
         Stmt::block(vec![len_decl, lhs_decl, rhs_decl, code], loc)
     }
 
+    /// Compare `len` bytes starting at `lhs` and `rhs` like `memcmp` does, but as a single
+    /// comparison between two `len * 8` bit vectors instead of CBMC's byte by byte loop, which
+    /// must be unwound `len` times. Returns a `c_int` that is zero if the bytes are equal,
+    /// negative if the first mismatch in `lhs` is smaller, and positive otherwise.
+    ///
+    /// `len` must be non-zero, since the pointers are dereferenced, and at most
+    /// [MAX_BITVECTOR_COMPARISON_BYTES].
+    pub fn codegen_memcmp_bitvector(&self, lhs: Expr, rhs: Expr, len: u64) -> Expr {
+        let bytes = Type::unsigned_int(len * 8);
+        let is_big_endian = self.symbol_table.machine_model().is_big_endian;
+        // The first byte is the most significant one when the bytes are read in big-endian
+        // order, so comparing the bit vectors as integers gives the lexicographic order.
+        let load = |ptr: Expr| {
+            let value = ptr.cast_to(bytes.clone().to_pointer()).dereference();
+            if is_big_endian { value } else { value.bswap() }
+        };
+        let (lhs, rhs) = (load(lhs), load(rhs));
+        lhs.clone().eq(rhs.clone()).ternary(
+            Type::c_int().zero(),
+            lhs.lt(rhs).ternary(Expr::int_constant(-1, Type::c_int()), Type::c_int().one()),
+        )
+    }
+
     // In some contexts (e.g., compilation-time evaluation),
     // `ptr_guaranteed_cmp` compares two pointers and returns:
     //  * 2 if the result is unknown.
@@ -1207,7 +1367,9 @@ fn codegen_intrinsic_transmute(
     // `raw_eq` determines whether the raw bytes of two values are equal.
     // https://doc.rust-lang.org/core/intrinsics/fn.raw_eq.html
     //
-    // The implementation below calls `memcmp` and returns equal if the result is zero.
+    // The size of the values is known, so the implementation below compares their bytes as two
+    // bit vectors, which doesn't require any unwinding. Zero-sized values are always equal, and
+    // values larger than `MAX_BITVECTOR_COMPARISON_BYTES` are compared with `memcmp` instead.
     //
     // TODO: It's UB to call `raw_eq` if any of the bytes in the first or second
     // arguments are uninitialized. At present, we cannot detect if there is
@@ -1224,13 +1386,23 @@ fn codegen_intrinsic_raw_eq(
         let ty = self.monomorphize(instance.args.type_at(0));
         let dst = fargs.remove(0).cast_to(Type::void_pointer());
         let val = fargs.remove(0).cast_to(Type::void_pointer());
-        let layout = self.layout_of(ty);
-        let sz = Expr::int_constant(layout.size.bytes(), Type::size_t())
-            .with_size_of_annotation(self.codegen_ty(ty));
-        let e = BuiltinFn::Memcmp
-            .call(vec![dst, val, sz], loc)
-            .eq(Type::c_int().zero())
-            .cast_to(Type::c_bool());
+        let size = self.layout_of(ty).size.bytes();
+        let e = if size == 0 {
+            Type::c_bool().one()
+        } else if size > MAX_BITVECTOR_COMPARISON_BYTES {
+            let sz = Expr::int_constant(size, Type::size_t())
+                .with_size_of_annotation(self.codegen_ty(ty));
+            BuiltinFn::Memcmp
+                .call(vec![dst, val, sz], loc)
+                .eq(Type::c_int().zero())
+                .cast_to(Type::c_bool())
+        } else {
+            let bits = Type::unsigned_int(size * 8).to_pointer();
+            dst.cast_to(bits.clone())
+                .dereference()
+                .eq(val.cast_to(bits).dereference())
+                .cast_to(Type::c_bool())
+        };
         self.codegen_expr_to_place(p, e)
     }
 
@@ -1768,6 +1940,158 @@ fn codegen_intrinsic_simd_shuffle(
         self.codegen_expr_to_place(p, Expr::vector_expr(cbmc_ret_ty, elems))
     }
 
+    /// Codegen for the SIMD intrinsics that apply an operation to each lane of their vector
+    /// arguments, e.g., `simd_neg` and `simd_select`. The lanes at the same position in every
+    /// argument are given to `f`, which computes the lane of the result at this position.
+    fn codegen_simd_lanewise<F: FnMut(Vec<Expr>) -> Expr>(
+        &mut self,
+        fargs: Vec<Expr>,
+        p: &Place<'tcx>,
+        rust_arg_types: &[Ty<'tcx>],
+        rust_ret_type: Ty<'tcx>,
+        span: Option<Span>,
+        mut f: F,
+    ) -> Stmt {
+        let ret_typ = self.codegen_ty(rust_ret_type);
+        let len = ret_typ.len().unwrap();
+        for (arg, arg_ty) in fargs.iter().zip(rust_arg_types) {
+            if arg.typ().len() != Some(len) {
+                let err_msg = format!(
+                    "expected argument of length {len} (same as return type `{rust_ret_type}`), \
+                    found `{arg_ty}`"
+                );
+                self.tcx.sess.span_err(span.unwrap(), err_msg);
+            }
+        }
+        self.tcx.sess.abort_if_errors();
+
+        let elems = (0..len)
+            .map(|i| {
+                let index = Expr::int_constant(i, Type::ssize_t());
+                f(fargs.iter().map(|arg| arg.clone().index_array(index.clone())).collect())
+            })
+            .collect();
+        self.codegen_expr_to_place(p, Expr::vector_expr(ret_typ, elems))
+    }
+
+    /// Codegen for the element-wise SIMD intrinsics on floats that correspond to a function of
+    /// the C library, e.g., `simd_fabs`. The first function of `fns` is used for vectors of
+    /// `f32`, and the second one for vectors of `f64`.
+    fn codegen_simd_float_op(
+        &mut self,
+        (f32_fn, f64_fn): (BuiltinFn, BuiltinFn),
+        fargs: Vec<Expr>,
+        p: &Place<'tcx>,
+        rust_arg_types: &[Ty<'tcx>],
+        rust_ret_type: Ty<'tcx>,
+        span: Option<Span>,
+    ) -> Stmt {
+        let loc = self.codegen_span_option(span);
+        self.codegen_simd_lanewise(fargs, p, rust_arg_types, rust_ret_type, span, |lanes| {
+            let f = if lanes[0].typ().is_float() { f32_fn } else { f64_fn };
+            f.call(lanes, loc)
+        })
+    }
+
+    /// The lanes of a SIMD vector.
+    fn simd_lanes(vector: Expr) -> impl Iterator<Item = Expr> {
+        (0..vector.typ().len().unwrap())
+            .map(move |i| vector.clone().index_array(Expr::int_constant(i, Type::ssize_t())))
+    }
+
+    /// Codegen for the SIMD intrinsics that combine the lanes of a vector with `f` in order,
+    /// e.g., `simd_reduce_add_ordered`. The first lane is combined with the accumulator argument
+    /// if there is one.
+    ///
+    /// The unordered reductions may combine the lanes in any order, which is only observable for
+    /// floats. We combine them in order as well.
+    fn codegen_simd_reduce<F: FnMut(Expr, Expr) -> Expr>(
+        &mut self,
+        mut fargs: Vec<Expr>,
+        p: &Place<'tcx>,
+        f: F,
+    ) -> Stmt {
+        let vector = fargs.remove(0);
+        let result = fargs.pop().into_iter().chain(Self::simd_lanes(vector)).reduce(f).unwrap();
+        self.codegen_expr_to_place(p, result)
+    }
+
+    /// Codegen for `simd_reduce_all` and `simd_reduce_any`, which combine the lanes of a mask
+    /// with `f` after converting them to booleans.
+    fn codegen_simd_reduce_mask<F: FnMut(Expr, Expr) -> Expr>(
+        &mut self,
+        mut fargs: Vec<Expr>,
+        p: &Place<'tcx>,
+        rust_ret_type: Ty<'tcx>,
+        f: F,
+    ) -> Stmt {
+        let mask = fargs.remove(0);
+        let result = Self::simd_lanes(mask).map(|lane| lane.is_zero().not()).reduce(f).unwrap();
+        let ret_typ = self.codegen_ty(rust_ret_type);
+        self.codegen_expr_to_place(p, result.cast_to(ret_typ))
+    }
+
+    /// Codegen for `simd_reduce_max` (if `is_max`) and `simd_reduce_min`, and for their `nanless`
+    /// variants. Integers are compared directly, while floats are combined with `fmax` or `fmin`,
+    /// which ignore NaN like `f32::max` and `f32::min`.
+    fn codegen_simd_reduce_min_max(
+        &mut self,
+        is_max: bool,
+        fargs: Vec<Expr>,
+        p: &Place<'tcx>,
+        loc: Location,
+    ) -> Stmt {
+        let (f32_fn, f64_fn) = if is_max {
+            (BuiltinFn::Fmaxf, BuiltinFn::Fmax)
+        } else {
+            (BuiltinFn::Fminf, BuiltinFn::Fmin)
+        };
+        self.codegen_simd_reduce(fargs, p, |a, b| {
+            if a.typ().is_float() {
+                f32_fn.call(vec![a, b], loc)
+            } else if a.typ().is_double() {
+                f64_fn.call(vec![a, b], loc)
+            } else if is_max {
+                a.clone().gt(b.clone()).ternary(a, b)
+            } else {
+                a.clone().lt(b.clone()).ternary(a, b)
+            }
+        })
+    }
+
+    /// Codegen for `simd_select_bitmask`, which picks the lanes of its first vector argument for
+    /// which the bit at the same position in the mask is set, and the lanes of its second vector
+    /// argument for the others. The first lane corresponds to the least significant bit.
+    fn codegen_simd_select_bitmask(
+        &mut self,
+        mut fargs: Vec<Expr>,
+        p: &Place<'tcx>,
+        rust_ret_type: Ty<'tcx>,
+        loc: Location,
+    ) -> Stmt {
+        let mask = fargs.remove(0);
+        if !mask.typ().is_integer() {
+            return self.codegen_unimplemented_stmt(
+                "`simd_select_bitmask` with an array mask",
+                loc,
+                "https://github.com/model-checking/kani/issues/new/choose",
+            );
+        }
+        let (t, e) = (fargs.remove(0), fargs.remove(0));
+        let ret_typ = self.codegen_ty(rust_ret_type);
+        let mask_typ = mask.typ().clone();
+        let elems = Self::simd_lanes(t)
+            .zip(Self::simd_lanes(e))
+            .enumerate()
+            .map(|(i, (t, e))| {
+                let bit = Expr::int_constant(i, mask_typ.clone());
+                let is_set = mask.clone().lshr(bit).bitand(mask_typ.one()).is_zero().not();
+                is_set.ternary(t, e)
+            })
+            .collect();
+        self.codegen_expr_to_place(p, Expr::vector_expr(ret_typ, elems))
+    }
+
     /// A volatile load of a memory location:
     /// <https://doc.rust-lang.org/std/ptr/fn.read_volatile.html>
     ///
@@ -1895,3 +2219,17 @@ pub fn count_in_bytes(
         (size_of_count_elems.result, assert_stmt)
     }
 }
+
+/// The largest number of bytes that are compared as a single bit vector. The solver handles
+/// wider bit vectors worse than the loop that compares the bytes one by one.
+const MAX_BITVECTOR_COMPARISON_BYTES: u64 = 256;
+
+/// The number of bytes to compare if it is a non-zero constant that is at most
+/// [MAX_BITVECTOR_COMPARISON_BYTES], in which case the comparison can be encoded without a loop
+/// by [GotocCtx::codegen_memcmp_bitvector].
+pub fn constant_byte_count(count: &Expr) -> Option<u64> {
+    count
+        .int_constant_value()
+        .and_then(|count| u64::try_from(count).ok())
+        .filter(|count| (1..=MAX_BITVECTOR_COMPARISON_BYTES).contains(count))
+}
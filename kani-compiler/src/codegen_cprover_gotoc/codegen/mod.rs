@@ -6,18 +6,26 @@
 
 mod assert;
 mod block;
+mod concrete_input;
+mod contract;
+mod enum_validity;
 mod foreign_function;
 mod function;
 mod intrinsic;
 mod operand;
 mod place;
+mod quantifier;
 mod rvalue;
 mod span;
 mod statement;
 mod static_var;
+mod uninit;
+mod union_init;
 
 // Visible for all codegen module.
 pub(super) mod typ;
 
 pub use assert::PropertyClass;
+pub use intrinsic::constant_byte_count;
+pub use quantifier::QuantifierKind;
 pub use typ::TypeExt;
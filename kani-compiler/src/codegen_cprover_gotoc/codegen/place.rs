@@ -677,6 +677,28 @@ pub fn codegen_place(
         }
     }
 
+    /// Generate the lvalue of the field `field` of `value`, an lvalue of type `ty`, or of the
+    /// field of its variant `variant` if `ty` is an enum. This is useful to inspect values that
+    /// don't come from a MIR place, e.g., to check their validity.
+    pub fn codegen_value_field(
+        &mut self,
+        value: Expr,
+        ty: Ty<'tcx>,
+        variant: Option<VariantIdx>,
+        field: FieldIdx,
+        field_ty: Ty<'tcx>,
+    ) -> Expr {
+        let mut projection =
+            ProjectedPlace::try_new(value, TypeOrVariant::Type(ty), None, None, self);
+        if let Some(variant) = variant {
+            projection =
+                self.codegen_projection(projection, ProjectionElem::Downcast(None, variant));
+        }
+        self.codegen_projection(projection, ProjectionElem::Field(field, field_ty))
+            .unwrap()
+            .goto_expr
+    }
+
     /// Given a projection, generate an lvalue that represents the given variant index.
     pub fn codegen_variant_lvalue(
         &mut self,
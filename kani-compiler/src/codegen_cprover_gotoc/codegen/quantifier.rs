@@ -0,0 +1,257 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This file contains the code generation of `kani::forall!` and `kani::exists!`.
+//!
+//! CBMC quantifiers must be pure expressions: their domain cannot contain function calls or
+//! side effects. The predicate of the quantifier is a closure, so we codegen the closure as
+//! usual and then inline its body, i.e., we interpret the straight-line goto code of the
+//! closure and the functions it calls, and collect the expression that it returns.
+
+use crate::codegen_cprover_gotoc::GotocCtx;
+use cbmc::goto_program::{Expr, ExprValue, Location, Stmt, StmtBody, SymbolValues, Type};
+use cbmc::InternedString;
+use rustc_middle::mir::{BasicBlock, Place, TerminatorKind};
+use rustc_middle::ty::{self, Instance, InstanceDef, ParamEnv};
+use rustc_span::Span;
+use std::collections::{BTreeMap, HashSet};
+
+/// How many nested calls the predicate of a quantifier may have.
+const MAX_INLINE_DEPTH: usize = 16;
+/// How many statements we interpret for each inlined function before we give up.
+const MAX_INLINE_STEPS: usize = 4096;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuantifierKind {
+    Forall,
+    Exists,
+}
+
+/// The statements of a function body after removing the nested blocks.
+enum Item<'a> {
+    Label(InternedString),
+    Stmt(&'a Stmt),
+}
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// Codegen a call to `kani::internal::kani_forall` or `kani::internal::kani_exists`.
+    ///
+    /// The arguments are the lower and upper bounds of the range, followed by the closure
+    /// environment, which is omitted if the closure doesn't capture anything.
+    pub fn codegen_quantifier(
+        &mut self,
+        kind: QuantifierKind,
+        instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        assign_to: &Place<'tcx>,
+        target: BasicBlock,
+        span: Option<Span>,
+    ) -> Stmt {
+        let loc = self.codegen_span_option(span);
+        let closure_ty = instance.args.type_at(0);
+        let ty::Closure(def_id, args) = *closure_ty.kind() else {
+            unreachable!(
+                "Expected a closure as the predicate of a quantifier, but found {closure_ty:?}"
+            )
+        };
+        let predicate = Instance::resolve_closure(self.tcx, def_id, args, ty::ClosureKind::Fn)
+            .expect("a closure can always be resolved");
+        self.codegen_predicate_callees(predicate);
+
+        let lower = fargs.remove(0);
+        let upper = fargs.remove(0);
+        let env_typ = self.codegen_ty(closure_ty);
+        let (env, env_decl) = self.decl_temp_variable(env_typ, fargs.pop(), loc);
+        let (lower, lower_decl) = self.decl_temp_variable(lower.typ().clone(), Some(lower), loc);
+        let (upper, upper_decl) = self.decl_temp_variable(upper.typ().clone(), Some(upper), loc);
+        let (var, var_decl) = self.decl_temp_variable(Type::size_t(), None, loc);
+
+        let call = self
+            .codegen_func_expr(predicate, span.as_ref())
+            .call(vec![env.address_of(), var.clone()]);
+        let body = match self.inline_call(&call, 0) {
+            Ok(body) => body.cast_to(Type::bool()),
+            Err(reason) => {
+                let msg = format!(
+                    "Kani cannot encode the predicate of `{}` as a quantified expression: {reason}",
+                    if kind == QuantifierKind::Forall { "kani::forall!" } else { "kani::exists!" }
+                );
+                self.tcx.sess.span_err(span.unwrap_or(self.tcx.def_span(def_id)), msg);
+                return Stmt::skip(loc);
+            }
+        };
+        let in_range = lower.le(var.clone()).and(var.clone().lt(upper));
+        let quantified = match kind {
+            QuantifierKind::Forall => Expr::forall(var, in_range.implies(body)),
+            QuantifierKind::Exists => Expr::exists(var, in_range.and(body)),
+        };
+        Stmt::block(
+            vec![
+                env_decl,
+                lower_decl,
+                upper_decl,
+                var_decl,
+                self.codegen_expr_to_place(assign_to, quantified.cast_to(Type::c_bool())),
+                Stmt::goto(self.current_fn().find_label(&target), loc),
+            ],
+            loc,
+        )
+    }
+
+    /// Make sure that the body of the predicate, and of every function that it calls, has been
+    /// generated, since we need them for inlining.
+    fn codegen_predicate_callees(&mut self, predicate: Instance<'tcx>) {
+        let caller = self.current_fn.take();
+        let mut visited = HashSet::new();
+        let mut queue = vec![predicate];
+        while let Some(instance) = queue.pop() {
+            if !visited.insert(instance) {
+                continue;
+            }
+            if self.symbol_table.lookup(self.symbol_name(instance)).is_none() {
+                self.declare_function(instance);
+            }
+            self.codegen_function(instance);
+
+            let body = self.tcx.instance_mir(instance.def);
+            for bb in body.basic_blocks.iter() {
+                let TerminatorKind::Call { func, .. } = &bb.terminator().kind else { continue };
+                let func_ty = instance.instantiate_mir_and_normalize_erasing_regions(
+                    self.tcx,
+                    ParamEnv::reveal_all(),
+                    ty::EarlyBinder::bind(func.ty(body, self.tcx)),
+                );
+                let ty::FnDef(def_id, args) = *func_ty.kind() else { continue };
+                let Ok(Some(callee)) =
+                    Instance::resolve(self.tcx, ParamEnv::reveal_all(), def_id, args)
+                else {
+                    continue;
+                };
+                let is_inlinable = matches!(callee.def, InstanceDef::Item(..))
+                    && self.tcx.is_mir_available(callee.def_id())
+                    && self.hooks.hook_applies(self.tcx, callee).is_none();
+                if is_inlinable {
+                    queue.push(callee);
+                }
+            }
+        }
+        self.current_fn = caller;
+    }
+
+    /// Inline a call expression. Calls that are nested in other expressions are left as is,
+    /// which CBMC will reject, but straight-line code in Rust only calls functions in the
+    /// right-hand side of assignments.
+    fn inline_call(&self, expr: &Expr, depth: usize) -> Result<Expr, String> {
+        match expr.value() {
+            ExprValue::FunctionCall { function, arguments } => {
+                self.inline_function(function, arguments.clone(), depth + 1)
+            }
+            _ => Ok(expr.clone()),
+        }
+    }
+
+    /// Compute the value returned by `function` in terms of its `arguments`.
+    fn inline_function(
+        &self,
+        function: &Expr,
+        arguments: Vec<Expr>,
+        depth: usize,
+    ) -> Result<Expr, String> {
+        if depth > MAX_INLINE_DEPTH {
+            return Err("it has too many nested function calls".to_string());
+        }
+        let ExprValue::Symbol { identifier } = function.value() else {
+            return Err("it calls a function pointer".to_string());
+        };
+        let symbol = self.symbol_table.lookup(*identifier).unwrap();
+        let name = symbol.pretty_name.unwrap_or(symbol.name);
+        let SymbolValues::Stmt(body) = &symbol.value else {
+            return Err(format!("the body of `{name}` is not available"));
+        };
+        let mut values: BTreeMap<InternedString, Expr> = symbol
+            .typ
+            .parameters()
+            .unwrap()
+            .iter()
+            .filter_map(|param| param.identifier())
+            .zip(arguments)
+            .collect();
+
+        let mut items = vec![];
+        flatten(body, &mut items);
+        let mut pc = 0;
+        for _ in 0..MAX_INLINE_STEPS {
+            let Some(item) = items.get(pc) else {
+                return Err(format!("`{name}` doesn't return a value"));
+            };
+            pc += 1;
+            let Item::Stmt(stmt) = item else { continue };
+            match stmt.body() {
+                StmtBody::Decl { lhs, value: Some(rhs) } | StmtBody::Assign { lhs, rhs } => {
+                    let value =
+                        self.inline_call(&rhs.clone().substitute_symbols(&values), depth)?;
+                    values.insert(assigned_symbol(lhs, stmt.location())?, value);
+                }
+                StmtBody::FunctionCall { lhs, function, arguments } => {
+                    let arguments =
+                        arguments.iter().map(|arg| arg.clone().substitute_symbols(&values));
+                    let value = self.inline_function(function, arguments.collect(), depth + 1)?;
+                    if let Some(lhs) = lhs {
+                        values.insert(assigned_symbol(lhs, stmt.location())?, value);
+                    }
+                }
+                StmtBody::Return(Some(value)) => {
+                    return self.inline_call(&value.clone().substitute_symbols(&values), depth);
+                }
+                StmtBody::Goto { dest, .. } => {
+                    pc = items
+                        .iter()
+                        .position(|item| matches!(item, Item::Label(label) if label == dest))
+                        .unwrap();
+                }
+                StmtBody::Decl { value: None, .. }
+                | StmtBody::Assert { .. }
+                | StmtBody::Assume { .. }
+                | StmtBody::Deinit(_)
+                | StmtBody::Expression(_)
+                | StmtBody::Skip => {}
+                _ => {
+                    return Err(format!(
+                        "`{}` has a branch or a loop{}",
+                        name,
+                        describe_location(stmt.location())
+                    ));
+                }
+            }
+        }
+        Err(format!("`{name}` has a loop"))
+    }
+}
+
+/// Collect the statements of `stmt` in order, replacing the labelled statements by their label
+/// followed by their body.
+fn flatten<'a>(stmt: &'a Stmt, items: &mut Vec<Item<'a>>) {
+    match stmt.body() {
+        StmtBody::Block(stmts) | StmtBody::AtomicBlock(stmts) => {
+            stmts.iter().for_each(|stmt| flatten(stmt, items))
+        }
+        StmtBody::Label { label, body } => {
+            items.push(Item::Label(*label));
+            flatten(body, items);
+        }
+        _ => items.push(Item::Stmt(stmt)),
+    }
+}
+
+/// The variable that is written by an assignment. Writes to fields or through pointers can't be
+/// expressed as a substitution.
+fn assigned_symbol(lhs: &Expr, loc: &Location) -> Result<InternedString, String> {
+    match lhs.value() {
+        ExprValue::Symbol { identifier } => Ok(*identifier),
+        _ => Err(format!("it writes to memory{}", describe_location(loc))),
+    }
+}
+
+fn describe_location(loc: &Location) -> String {
+    if loc.is_none() { String::new() } else { format!(" at {}", loc.short_string()) }
+}
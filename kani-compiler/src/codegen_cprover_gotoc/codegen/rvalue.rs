@@ -6,6 +6,7 @@
 use crate::codegen_cprover_gotoc::codegen::PropertyClass;
 use crate::codegen_cprover_gotoc::utils::{dynamic_fat_ptr, slice_fat_ptr};
 use crate::codegen_cprover_gotoc::{GotocCtx, VtableCtx};
+use crate::kani_middle::attributes::allows_lossy_cast;
 use crate::kani_middle::coercion::{
     extract_unsize_casting, CoerceUnsizedInfo, CoerceUnsizedIterator, CoercionBase,
 };
@@ -22,6 +23,7 @@
 use rustc_middle::ty::adjustment::PointerCoercion;
 use rustc_middle::ty::layout::LayoutOf;
 use rustc_middle::ty::{self, Instance, IntTy, Ty, TyCtxt, UintTy, VtblEntry};
+use rustc_span::Span;
 use rustc_target::abi::{FieldIdx, FieldsShape, Size, TagEncoding, VariantIdx, Variants};
 use std::collections::BTreeMap;
 use tracing::{debug, trace, warn};
@@ -402,7 +404,8 @@ fn codegen_rvalue_binary_op(
                 // https://doc.rust-lang.org/std/primitive.pointer.html#method.offset
                 // These checks may allow a wrapping-around behavior in CBMC:
                 // https://github.com/model-checking/kani/issues/1150
-                let overflow_res = ce1.clone().cast_to(Type::ssize_t()).add_overflow(offset_bytes);
+                let overflow_res =
+                    ce1.clone().cast_to(Type::ssize_t()).add_overflow(offset_bytes.clone());
                 let overflow_check = self.codegen_assert_assume(
                     overflow_res.overflowed.not(),
                     PropertyClass::ArithmeticOverflow,
@@ -410,8 +413,18 @@ fn codegen_rvalue_binary_op(
                     loc,
                 );
                 let res = ce1.clone().plus(ce2);
+                let provenance_check = self.codegen_offset_provenance_check(
+                    ce1.clone(),
+                    res.clone(),
+                    offset_bytes,
+                    loc,
+                );
                 Expr::statement_expression(
-                    vec![bytes_overflow_check, overflow_check, res.as_stmt(loc)],
+                    [bytes_overflow_check, overflow_check]
+                        .into_iter()
+                        .chain(provenance_check)
+                        .chain([res.as_stmt(loc)])
+                        .collect(),
                     ce1.typ().clone(),
                 )
             }
@@ -913,6 +926,103 @@ fn codegen_fat_ptr_to_thin_ptr_cast(&mut self, src: &Operand<'tcx>, dst_t: Ty<'t
         src_goto_expr.member("data", &self.symbol_table).cast_to(dst_goto_typ)
     }
 
+    /// With `--lossy-cast-checks`, generate a check that the integer `as` cast in `rvalue`
+    /// preserves the value of its operand, i.e., that casting the result back to the type of the
+    /// operand yields the same value. Only casts in the functions of the current crate are
+    /// checked, unless they are allowed with `#[kanitool::allow_lossy_cast]`.
+    pub fn codegen_lossy_cast_check(
+        &mut self,
+        rvalue: &Rvalue<'tcx>,
+        span: Span,
+        loc: Location,
+    ) -> Option<Stmt> {
+        if !self.queries.args().lossy_cast_checks {
+            return None;
+        }
+        let Rvalue::Cast(CastKind::IntToInt, operand, dst_t) = rvalue else { return None };
+        let def_id = self.current_fn().instance().def_id().as_local()?;
+        let src_t = self.operand_ty(operand);
+        let dst_t = self.monomorphize(*dst_t);
+        if !((src_t.is_integral() || src_t.is_char()) && dst_t.is_integral())
+            || self.is_lossless_int_cast(src_t, dst_t)
+            || allows_lossy_cast(self.tcx, def_id, span)
+        {
+            return None;
+        }
+        let value = self.codegen_operand(operand);
+        let src_typ = value.typ().clone();
+        let round_trip = value.clone().cast_to(self.codegen_ty(dst_t)).cast_to(src_typ);
+        Some(self.codegen_assert(
+            round_trip.eq(value),
+            PropertyClass::LossyCast,
+            &format!("`as` cast from `{src_t}` to `{dst_t}` changes the value"),
+            loc,
+        ))
+    }
+
+    /// With `--check pointer-provenance`, check that offsetting `ptr` by `offset_bytes` bytes,
+    /// which gives `res`, stays in the allocation of `ptr` or one byte past its end. Offsetting a
+    /// pointer by zero bytes is always allowed.
+    fn codegen_offset_provenance_check(
+        &mut self,
+        ptr: Expr,
+        res: Expr,
+        offset_bytes: Expr,
+        loc: Location,
+    ) -> Option<Stmt> {
+        if !self.queries.args().pointer_provenance_checks {
+            return None;
+        }
+        let offset = res.pointer_offset();
+        let size = ptr.object_size().cast_to(Type::ssize_t());
+        let in_bounds =
+            offset.clone().ge(Expr::int_constant(0, Type::ssize_t())).and(offset.le(size));
+        Some(self.codegen_assert_assume(
+            offset_bytes.is_zero().or(in_bounds),
+            PropertyClass::PointerProvenance,
+            "pointer arithmetic goes out of the bounds of the allocation of the pointer",
+            loc,
+        ))
+    }
+
+    /// With `--check pointer-provenance`, check that the crate doesn't cast a non-null integer
+    /// to a pointer, since the pointer doesn't get the provenance of any allocation. The
+    /// functions of the standard library that allow it, e.g., `ptr::from_exposed_addr`, aren't
+    /// checked.
+    pub fn codegen_provenance_cast_check(
+        &mut self,
+        rvalue: &Rvalue<'tcx>,
+        loc: Location,
+    ) -> Option<Stmt> {
+        if !self.queries.args().pointer_provenance_checks {
+            return None;
+        }
+        let Rvalue::Cast(CastKind::PointerFromExposedAddress, operand, dst_t) = rvalue else {
+            return None;
+        };
+        self.current_fn().instance().def_id().as_local()?;
+        let src_t = self.operand_ty(operand);
+        let dst_t = self.monomorphize(*dst_t);
+        let value = self.codegen_operand(operand);
+        Some(self.codegen_assert_assume(
+            value.is_zero(),
+            PropertyClass::PointerProvenance,
+            &format!("`as` cast from `{src_t}` to `{dst_t}` creates a pointer without provenance"),
+            loc,
+        ))
+    }
+
+    /// Whether every value of the integer type `src_t` can be represented by `dst_t`.
+    fn is_lossless_int_cast(&self, src_t: Ty<'tcx>, dst_t: Ty<'tcx>) -> bool {
+        let src_bits = self.layout_of(src_t).size.bits();
+        let dst_bits = self.layout_of(dst_t).size.bits();
+        match (src_t.is_signed(), dst_t.is_signed()) {
+            (false, false) | (true, true) => dst_bits >= src_bits,
+            (false, true) => dst_bits > src_bits,
+            (true, false) => false,
+        }
+    }
+
     /// This handles all kinds of casts, except a limited subset that are instead
     /// handled by [`Self::codegen_pointer_cast`].
     fn codegen_misc_cast(&mut self, src: &Operand<'tcx>, dst_t: Ty<'tcx>) -> Expr {
@@ -1182,7 +1292,7 @@ fn codegen_vtable_method_field(
             if self.vtable_ctx.emit_vtable_restrictions {
                 // Add to the possible method names for this trait type
                 self.vtable_ctx.add_possible_method(
-                    self.normalized_trait_name(t).into(),
+                    self.vtable_restriction_trait_name(t).into(),
                     idx,
                     fn_name.into(),
                 );
@@ -1218,7 +1328,7 @@ fn codegen_vtable_drop_in_place(&mut self, ty: Ty<'tcx>, trait_ty: ty::Ty<'tcx>)
             if self.vtable_ctx.emit_vtable_restrictions {
                 // Add to the possible method names for this trait type
                 self.vtable_ctx.add_possible_method(
-                    self.normalized_trait_name(trait_ty).into(),
+                    self.vtable_restriction_trait_name(trait_ty).into(),
                     VtableCtx::drop_index(),
                     drop_sym_name,
                 );
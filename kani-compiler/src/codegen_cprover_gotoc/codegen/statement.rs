@@ -9,8 +9,8 @@
 use rustc_hir::def_id::DefId;
 use rustc_middle::mir;
 use rustc_middle::mir::{
-    AssertKind, BasicBlock, NonDivergingIntrinsic, Operand, Place, Statement, StatementKind,
-    SwitchTargets, Terminator, TerminatorKind,
+    AssertKind, BasicBlock, InlineAsmOperand, NonDivergingIntrinsic, Operand, Place, Statement,
+    StatementKind, SwitchTargets, Terminator, TerminatorKind,
 };
 use rustc_middle::ty;
 use rustc_middle::ty::layout::LayoutOf;
@@ -34,8 +34,10 @@ pub fn codegen_statement(&mut self, stmt: &Statement<'tcx>) -> Stmt {
             StatementKind::Assign(box (l, r)) => {
                 let lty = self.place_ty(l);
                 let rty = self.rvalue_ty(r);
+                let union_init = self.codegen_union_init_write(l, r, location);
+                let uninit = self.codegen_uninit_assign(l, r, location);
                 // we ignore assignment for all zero size types
-                if self.is_zst(lty) {
+                let assign = if self.is_zst(lty) {
                     Stmt::skip(location)
                 } else if lty.is_fn_ptr() && rty.is_fn() && !rty.is_fn_ptr() {
                     // implicit address of a function pointer, e.g.
@@ -49,9 +51,41 @@ pub fn codegen_statement(&mut self, stmt: &Statement<'tcx>) -> Stmt {
                         .goto_expr
                         .assign(self.codegen_rvalue(r, location).cast_to(Type::c_bool()), location)
                 } else {
-                    unwrap_or_return_codegen_unimplemented_stmt!(self, self.codegen_place(l))
-                        .goto_expr
-                        .assign(self.codegen_rvalue(r, location), location)
+                    let assign =
+                        unwrap_or_return_codegen_unimplemented_stmt!(self, self.codegen_place(l))
+                            .goto_expr
+                            .assign(self.codegen_rvalue(r, location), location);
+                    let enum_check = if self.needs_enum_validity_check(r) {
+                        let place = unwrap_or_return_codegen_unimplemented_stmt!(
+                            self,
+                            self.codegen_place(l)
+                        )
+                        .goto_expr;
+                        self.codegen_enum_validity_check(place, lty, location)
+                    } else {
+                        None
+                    };
+                    let lossy_check =
+                        self.codegen_lossy_cast_check(r, stmt.source_info.span, location);
+                    let provenance_check = self.codegen_provenance_cast_check(r, location);
+                    match (lossy_check, provenance_check, enum_check) {
+                        (None, None, None) => assign,
+                        (check, provenance_check, enum_check) => Stmt::block(
+                            check
+                                .into_iter()
+                                .chain(provenance_check)
+                                .chain([assign])
+                                .chain(enum_check)
+                                .collect(),
+                            location,
+                        ),
+                    }
+                };
+                let updates: Vec<_> = union_init.into_iter().chain(uninit).collect();
+                if updates.is_empty() {
+                    assign
+                } else {
+                    Stmt::block([assign].into_iter().chain(updates).collect(), location)
                 }
             }
             StatementKind::Deinit(place) => self.codegen_deinit(place, location),
@@ -62,7 +96,16 @@ pub fn codegen_statement(&mut self, stmt: &Statement<'tcx>) -> Stmt {
                         .goto_expr;
                 self.codegen_set_discriminant(dest_ty, dest_expr, *variant_index, location)
             }
-            StatementKind::StorageLive(_) => Stmt::skip(location), // TODO: fix me
+            StatementKind::StorageLive(local) => {
+                let reset = self.codegen_union_init_reset(*local, location);
+                let alloc = self.codegen_uninit_local_alloc(*local, location);
+                match (reset, alloc) {
+                    (None, None) => Stmt::skip(location), // TODO: fix me
+                    (reset, alloc) => {
+                        Stmt::block(reset.into_iter().chain(alloc).collect(), location)
+                    }
+                }
+            }
             StatementKind::StorageDead(_) => Stmt::skip(location), // TODO: fix me
             StatementKind::Intrinsic(box NonDivergingIntrinsic::CopyNonOverlapping(
                 mir::CopyNonOverlapping { ref src, ref dst, ref count },
@@ -75,7 +118,25 @@ pub fn codegen_statement(&mut self, stmt: &Statement<'tcx>) -> Stmt {
                 ];
                 let farg_types =
                     &[self.operand_ty(src), self.operand_ty(dst), self.operand_ty(count)];
-                self.codegen_copy("copy_nonoverlapping", true, fargs, farg_types, None, location)
+                let uninit = self.codegen_uninit_copy_values(
+                    fargs[0].clone(),
+                    fargs[1].clone(),
+                    fargs[2].clone(),
+                    farg_types[0],
+                    location,
+                );
+                let copy = self.codegen_copy(
+                    "copy_nonoverlapping",
+                    true,
+                    fargs,
+                    farg_types,
+                    None,
+                    location,
+                );
+                match uninit {
+                    Some(update) => Stmt::block(vec![copy, update], location),
+                    None => copy,
+                }
             }
             StatementKind::Intrinsic(box NonDivergingIntrinsic::Assume(ref op)) => {
                 let cond = self.codegen_operand(op).cast_to(Type::bool());
@@ -130,7 +191,7 @@ pub fn codegen_terminator(&mut self, term: &Terminator<'tcx>) -> Stmt {
             ),
             TerminatorKind::Return => {
                 let rty = self.current_fn().sig().skip_binder().output();
-                if rty.is_unit() {
+                let ret = if rty.is_unit() {
                     self.codegen_ret_unit()
                 } else {
                     let p = Place::from(mir::RETURN_PLACE);
@@ -142,6 +203,16 @@ pub fn codegen_terminator(&mut self, term: &Terminator<'tcx>) -> Stmt {
                     } else {
                         v.ret(loc)
                     }
+                };
+                let exit: Vec<_> = self
+                    .codegen_uninit_return(loc)
+                    .into_iter()
+                    .chain(self.codegen_stack_depth_exit(loc))
+                    .collect();
+                if exit.is_empty() {
+                    ret
+                } else {
+                    Stmt::block(exit.into_iter().chain([ret]).collect(), loc)
                 }
             }
             TerminatorKind::Unreachable => self.codegen_assert_assume_false(
@@ -198,14 +269,49 @@ pub fn codegen_terminator(&mut self, term: &Terminator<'tcx>) -> Stmt {
             TerminatorKind::Yield { .. } | TerminatorKind::CoroutineDrop => {
                 unreachable!("we should not hit these cases") // why?
             }
-            TerminatorKind::InlineAsm { .. } => self.codegen_unimplemented_stmt(
-                "TerminatorKind::InlineAsm",
-                loc,
-                "https://github.com/model-checking/kani/issues/2",
-            ),
+            TerminatorKind::InlineAsm { operands, destination, .. } => {
+                if self.queries.args().ignore_asm {
+                    self.codegen_havoc_asm(operands, *destination, loc)
+                } else {
+                    self.codegen_unimplemented_stmt(
+                        "TerminatorKind::InlineAsm",
+                        loc,
+                        "https://github.com/model-checking/kani/issues/2",
+                    )
+                }
+            }
         }
     }
 
+    /// Replace an inline assembly block with nondeterministic values for its outputs, which is
+    /// how `--ignore-asm` models them. Any other effect of the block, e.g., on memory, is lost.
+    /// A block that doesn't return (`options(noreturn)`) blocks the paths that reach it.
+    fn codegen_havoc_asm(
+        &mut self,
+        operands: &[InlineAsmOperand<'tcx>],
+        destination: Option<BasicBlock>,
+        loc: Location,
+    ) -> Stmt {
+        let mut stmts = vec![];
+        for operand in operands {
+            let (InlineAsmOperand::Out { place: Some(place), .. }
+            | InlineAsmOperand::InOut { out_place: Some(place), .. }) = operand
+            else {
+                continue;
+            };
+            let typ = self.codegen_ty(self.place_ty(place));
+            let place =
+                unwrap_or_return_codegen_unimplemented_stmt!(self, self.codegen_place(place))
+                    .goto_expr;
+            stmts.push(place.assign(typ.nondet(), loc));
+        }
+        stmts.push(match destination {
+            Some(target) => Stmt::goto(self.current_fn().find_label(&target), loc),
+            None => Stmt::assume(Expr::bool_false(), loc),
+        });
+        Stmt::block(stmts, loc)
+    }
+
     /// Create a statement that sets the variable discriminant to the value that corresponds to the
     /// variant index.
     pub fn codegen_set_discriminant(
@@ -527,7 +633,8 @@ fn codegen_funcall(
                         .unwrap();
 
                 // TODO(celina): Move this check to be inside codegen_funcall_args.
-                if self.ty_needs_untupled_args(funct) {
+                let untupled = self.ty_needs_untupled_args(funct);
+                if untupled {
                     self.codegen_untupled_args(instance, &mut fargs, args.last());
                 }
 
@@ -565,10 +672,41 @@ fn codegen_funcall(
                         // We need to handle FnDef items in a special way because `codegen_operand` compiles them to dummy structs.
                         // (cf. the function documentation)
                         let func_exp = self.codegen_func_expr(instance, None);
-                        vec![
+                        let is_foreign = self.tcx.is_foreign_item(instance.def_id());
+                        let mut stmts = if is_foreign || untupled {
+                            vec![]
+                        } else {
+                            self.codegen_uninit_call(args, destination, loc)
+                        };
+                        let uninit = is_foreign
+                            .then(|| {
+                                let name = self.tcx.item_name(instance.def_id());
+                                self.codegen_uninit_foreign_call(
+                                    name.as_str(),
+                                    &fargs,
+                                    destination,
+                                    loc,
+                                )
+                            })
+                            .flatten();
+                        stmts.push(
                             self.codegen_expr_to_place(destination, func_exp.call(fargs))
                                 .with_location(loc),
-                        ]
+                        );
+                        stmts.extend(uninit);
+                        stmts.extend(self.codegen_uninit_end_call(loc));
+                        stmts.extend(self.codegen_concrete_input_assumption(destination, loc));
+                        if is_foreign {
+                            // Foreign functions may return values that Rust considers invalid.
+                            let place = unwrap_or_return_codegen_unimplemented_stmt!(
+                                self,
+                                self.codegen_place(destination)
+                            )
+                            .goto_expr;
+                            let ret_ty = self.place_ty(destination);
+                            stmts.extend(self.codegen_enum_validity_check(place, ret_ty, loc));
+                        }
+                        stmts
                     }
                     InstanceDef::ThreadLocalShim(_) => todo!(),
                 };
@@ -620,6 +620,26 @@ pub fn normalized_trait_name(&self, t: Ty<'tcx>) -> String {
         self.ty_mangled_name(t).to_string()
     }
 
+    /// Gives the name of a trait without its auto traits, e.g., `dyn Any` for `dyn Any + Send`.
+    ///
+    /// Casting away auto traits keeps the vtable, e.g., `<dyn Error + Send>::downcast_ref`
+    /// calls `type_id` through a `dyn Error` that points to the vtable of a
+    /// `dyn Error + Send`. Vtable restrictions must use this name so that the call sites and
+    /// the methods of the vtables of both types match.
+    pub fn vtable_restriction_trait_name(&self, t: Ty<'tcx>) -> String {
+        let ty::Dynamic(preds, region, kind) = *t.kind() else {
+            unreachable!("Type {t} must be a trait type (a dynamic type)")
+        };
+        if preds.principal().is_none() {
+            return self.normalized_trait_name(t);
+        }
+        let preds =
+            self.tcx.mk_poly_existential_predicates_from_iter(preds.iter().filter(|pred| {
+                !matches!(pred.skip_binder(), ty::ExistentialPredicate::AutoTrait(_))
+            }));
+        self.normalized_trait_name(Ty::new_dynamic(self.tcx, preds, region, kind))
+    }
+
     /// Gives the vtable name for a type.
     /// In some cases, we have &T, in other cases T, so normalize.
     ///
@@ -0,0 +1,546 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module generates the checks of `--check uninit` that the harnesses don't read memory
+//! that was never initialized, e.g., with `MaybeUninit::uninit().assume_init()`.
+//!
+//! Keeping the initialization state of every byte would make the model much larger, so we track
+//! a single byte instead. Whenever memory that may be left uninitialized is allocated, i.e., a
+//! local variable whose type contains a union, like `MaybeUninit<T>`, or a heap allocation, one
+//! of its bytes may be chosen nondeterministically as the tracked byte. We then keep whether
+//! the tracked byte was written since, and we check that every scalar that is read doesn't cover
+//! the tracked byte while it's uninitialized. Since CBMC explores every choice, the check fails
+//! if any read of the harness can read an uninitialized byte.
+//!
+//! The bytes of an aggregate are copied when it's moved, e.g., passed to or returned from a
+//! function, before its fields are read. So a copy of an aggregate that covers the tracked byte
+//! may also move the tracked byte to the copy, which lets us follow uninitialized memory until
+//! it's read. Writes whose effect on memory we don't model, e.g., by foreign functions, may make
+//! the check report uninitialized memory that was actually initialized.
+
+use super::union_init::read_places;
+use crate::codegen_cprover_gotoc::codegen::PropertyClass;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use cbmc::goto_program::{Expr, Location as GotoLocation, Stmt, Type};
+use rustc_middle::mir::{
+    AggregateKind, BasicBlock, CastKind, Local, Location, Operand, Place, Rvalue, RETURN_PLACE,
+};
+use rustc_middle::ty::layout::{LayoutOf, TyAndLayout};
+use rustc_middle::ty::{self, Ty};
+use rustc_span::Span;
+
+/// Whether a byte is tracked, which only becomes true once a byte has been chosen.
+const TRACKED_VAR_NAME: &str = "__kani_uninit_tracked";
+/// A pointer to the tracked byte.
+const BYTE_VAR_NAME: &str = "__kani_uninit_byte";
+/// Whether the tracked byte was written since it was allocated.
+const INITIALIZED_VAR_NAME: &str = "__kani_uninit_initialized";
+/// The index of the parameter that holds the tracked byte when a function is called, starting
+/// at 1, or 0 if no parameter holds it.
+const PENDING_ARG_VAR_NAME: &str = "__kani_uninit_pending_arg";
+/// The offset of the tracked byte in the parameter that holds it.
+const PENDING_OFFSET_VAR_NAME: &str = "__kani_uninit_pending_offset";
+/// The address of the place that the function being called returns to, or `NULL` if it's unknown.
+const RETURN_VAR_NAME: &str = "__kani_uninit_return";
+
+impl<'tcx> GotocCtx<'tcx> {
+    fn uninit_checks_enabled(&self) -> bool {
+        self.queries.args().uninit_checks
+    }
+
+    fn uninit_var(&mut self, name: &str, typ: Type, init: Expr) -> Expr {
+        self.ensure_global_var(name, false, typ, GotoLocation::none(), |_, var| {
+            Some(Stmt::assign(var, init, GotoLocation::none()))
+        })
+    }
+
+    fn uninit_tracked(&mut self) -> Expr {
+        self.uninit_var(TRACKED_VAR_NAME, Type::bool(), Expr::bool_false())
+    }
+
+    fn uninit_byte(&mut self) -> Expr {
+        let typ = Type::unsigned_int(8).to_pointer();
+        self.uninit_var(BYTE_VAR_NAME, typ.clone(), typ.null())
+    }
+
+    fn uninit_initialized(&mut self) -> Expr {
+        self.uninit_var(INITIALIZED_VAR_NAME, Type::bool(), Expr::bool_true())
+    }
+
+    fn uninit_pending_arg(&mut self) -> Expr {
+        self.uninit_var(PENDING_ARG_VAR_NAME, Type::size_t(), Expr::int_constant(0, Type::size_t()))
+    }
+
+    fn uninit_pending_offset(&mut self) -> Expr {
+        let zero = Expr::int_constant(0, Type::ssize_t());
+        self.uninit_var(PENDING_OFFSET_VAR_NAME, Type::ssize_t(), zero)
+    }
+
+    fn uninit_return(&mut self) -> Expr {
+        self.uninit_var(RETURN_VAR_NAME, Type::void_pointer(), Type::void_pointer().null())
+    }
+
+    /// Whether the `size` bytes at `ptr` cover the tracked byte.
+    fn uninit_covers(&mut self, ptr: Expr, size: Expr) -> Expr {
+        let byte = self.uninit_byte();
+        let start = ptr.clone().pointer_offset();
+        let offset = byte.clone().pointer_offset();
+        self.uninit_tracked()
+            .and(ptr.same_object(byte))
+            .and(start.clone().le(offset.clone()))
+            .and(offset.lt(start.plus(size.cast_to(Type::ssize_t()))))
+    }
+
+    /// The offset of the tracked byte from `ptr`, which must point to the same object.
+    fn uninit_offset_from(&mut self, ptr: Expr) -> Expr {
+        self.uninit_byte().pointer_offset().sub(ptr.pointer_offset())
+    }
+
+    /// Move the tracked byte to `offset` bytes after `ptr`.
+    fn uninit_move_byte(&mut self, ptr: Expr, offset: Expr, loc: GotoLocation) -> Stmt {
+        let byte = self.uninit_byte();
+        let typ = byte.typ().clone();
+        byte.assign(ptr.cast_to(typ).plus(offset), loc)
+    }
+
+    /// The `size` bytes at `ptr` are allocated and uninitialized, so one of them may become the
+    /// tracked byte.
+    fn codegen_uninit_alloc(&mut self, ptr: Expr, size: Expr, loc: GotoLocation) -> Stmt {
+        let (index, decl) = self.decl_temp_variable(Type::size_t(), None, loc);
+        let choose =
+            Type::bool().nondet().and(index.clone().lt(size.clone().cast_to(Type::size_t())));
+        let tracked = self.uninit_tracked();
+        let initialized = self.uninit_initialized();
+        let chosen = Stmt::block(
+            vec![
+                tracked.assign(Expr::bool_true(), loc),
+                self.uninit_move_byte(ptr.clone(), index, loc),
+                initialized.clone().assign(Expr::bool_false(), loc),
+            ],
+            loc,
+        );
+        // The tracked byte may also be allocated again, e.g., when a local is live again.
+        let covered = self.uninit_covers(ptr, size);
+        let reset =
+            Stmt::if_then_else(covered, initialized.assign(Expr::bool_false(), loc), None, loc);
+        Stmt::block(vec![decl, Stmt::if_then_else(choose, chosen, Some(reset), loc)], loc)
+    }
+
+    /// The `size` bytes at `ptr` are written.
+    fn codegen_uninit_write(&mut self, ptr: Expr, size: Expr, loc: GotoLocation) -> Stmt {
+        let covered = self.uninit_covers(ptr, size);
+        let initialized = self.uninit_initialized();
+        Stmt::if_then_else(covered, initialized.assign(Expr::bool_true(), loc), None, loc)
+    }
+
+    /// The `size` bytes at `src` are copied to `dst`. If they cover the tracked byte, we may keep
+    /// tracking the original byte or start tracking its copy instead.
+    fn codegen_uninit_copy(&mut self, src: Expr, dst: Expr, size: Expr, loc: GotoLocation) -> Stmt {
+        let copied = self.uninit_covers(src.clone(), size.clone()).and(Type::bool().nondet());
+        let offset = self.uninit_offset_from(src);
+        let follow = self.uninit_move_byte(dst.clone(), offset, loc);
+        let write = self.codegen_uninit_write(dst, size, loc);
+        Stmt::if_then_else(copied, follow, Some(write), loc)
+    }
+
+    /// The check that reading a value of type `ty` from `ptr` doesn't read the tracked byte
+    /// while it's uninitialized.
+    fn codegen_uninit_read(&mut self, ptr: Expr, ty: Ty<'tcx>, loc: GotoLocation) -> Stmt {
+        let size = Expr::int_constant(self.layout_of(ty).size.bytes(), Type::size_t());
+        let covered = self.uninit_covers(ptr, size);
+        let initialized = self.uninit_initialized();
+        self.codegen_assert_assume(
+            covered.not().or(initialized),
+            PropertyClass::Uninit,
+            &format!("reading uninitialized memory of type `{ty}`"),
+            loc,
+        )
+    }
+
+    /// The address and size of `place`, if its bytes can be tracked, i.e., it's sized and not a
+    /// ZST.
+    fn uninit_place(&mut self, place: &Place<'tcx>) -> Option<(Expr, Expr)> {
+        let ty = self.place_ty(place);
+        if !ty.is_sized(self.tcx, ty::ParamEnv::reveal_all()) || self.is_zst(ty) {
+            return None;
+        }
+        let size = Expr::int_constant(self.layout_of(ty).size.bytes(), Type::size_t());
+        let ptr = self.codegen_place(place).ok()?.goto_expr.address_of();
+        Some((ptr, size))
+    }
+
+    /// Whether a value of type `ty` is read as a whole, rather than copied byte by byte.
+    fn is_uninit_scalar(ty: Ty<'tcx>) -> bool {
+        ty.is_scalar() || ty.is_ref()
+    }
+
+    /// Whether a value of type `ty` may have uninitialized bytes, i.e., it contains a union.
+    fn may_be_uninit(&self, ty: Ty<'tcx>) -> bool {
+        match ty.kind() {
+            ty::Adt(def, _) if def.is_union() => true,
+            ty::Adt(def, args) => def.all_fields().any(|field| {
+                let field_ty = self.tcx.normalize_erasing_regions(
+                    ty::ParamEnv::reveal_all(),
+                    field.ty(self.tcx, args),
+                );
+                self.may_be_uninit(field_ty)
+            }),
+            ty::Array(elem, _) => self.may_be_uninit(*elem),
+            ty::Tuple(tys) => tys.iter().any(|ty| self.may_be_uninit(ty)),
+            _ => false,
+        }
+    }
+
+    /// The statement that allocates `local` if it may have uninitialized bytes.
+    pub fn codegen_uninit_local_alloc(&mut self, local: Local, loc: GotoLocation) -> Option<Stmt> {
+        if !self.uninit_checks_enabled() {
+            return None;
+        }
+        let ty = self.monomorphize(self.current_fn().mir().local_decls[local].ty);
+        if !self.may_be_uninit(ty) {
+            return None;
+        }
+        let (ptr, size) = self.uninit_place(&Place::from(local))?;
+        Some(self.codegen_uninit_alloc(ptr, size, loc))
+    }
+
+    /// Track the uninitialized bytes of the current function: allocate the locals that may have
+    /// uninitialized bytes, follow the tracked byte into the parameter that holds it, and save
+    /// where the function returns to.
+    pub fn codegen_uninit_prelude(&mut self) {
+        if !self.uninit_checks_enabled() {
+            return;
+        }
+        let mir = self.current_fn().mir();
+        let loc = self.codegen_span(&mir.span);
+        let ret_var = self.uninit_return();
+        let (saved, decl) =
+            self.decl_temp_variable(Type::void_pointer(), Some(ret_var.clone()), loc);
+        self.current_fn_mut().push_onto_block(decl);
+        self.current_fn_mut().push_onto_block(ret_var.assign(Type::void_pointer().null(), loc));
+        self.current_fn_mut().set_uninit_return(saved);
+
+        let pending = self.uninit_pending_arg();
+        // The parameters don't match the arguments of the caller if they are spread.
+        if mir.spread_arg.is_none() {
+            for arg in mir.args_iter() {
+                let ty = self.monomorphize(mir.local_decls[arg].ty);
+                if Self::is_uninit_scalar(ty) {
+                    continue;
+                }
+                let Some((ptr, _)) = self.uninit_place(&Place::from(arg)) else { continue };
+                let offset = self.uninit_pending_offset();
+                let follow = self.uninit_move_byte(ptr, offset, loc);
+                let index = Expr::int_constant(arg.as_usize(), Type::size_t());
+                self.current_fn_mut().push_onto_block(Stmt::if_then_else(
+                    pending.clone().eq(index),
+                    follow,
+                    None,
+                    loc,
+                ));
+            }
+        }
+        let zero = Expr::int_constant(0, Type::size_t());
+        self.current_fn_mut().push_onto_block(pending.assign(zero, loc));
+
+        let locals: Vec<_> = mir
+            .local_decls
+            .indices()
+            .filter(|local| local.index() == 0 || local.index() > mir.arg_count)
+            .collect();
+        for local in locals {
+            if let Some(alloc) = self.codegen_uninit_local_alloc(local, loc) {
+                self.current_fn_mut().push_onto_block(alloc);
+            }
+        }
+    }
+
+    /// The statement that updates the tracked byte after `rvalue` is assigned to `place`.
+    pub fn codegen_uninit_assign(
+        &mut self,
+        place: &Place<'tcx>,
+        rvalue: &Rvalue<'tcx>,
+        loc: GotoLocation,
+    ) -> Option<Stmt> {
+        if !self.uninit_checks_enabled() {
+            return None;
+        }
+        let ty = self.place_ty(place);
+        // A local that holds a scalar never holds the tracked byte.
+        if place.projection.is_empty() && Self::is_uninit_scalar(ty) {
+            return None;
+        }
+        let (dst, size) = self.uninit_place(place)?;
+        match rvalue {
+            Rvalue::Use(Operand::Copy(src) | Operand::Move(src))
+            | Rvalue::Cast(CastKind::Transmute, Operand::Copy(src) | Operand::Move(src), _)
+                if !Self::is_uninit_scalar(ty) =>
+            {
+                let (src, _) = self.uninit_place(src)?;
+                Some(self.codegen_uninit_copy(src, dst, size, loc))
+            }
+            Rvalue::Aggregate(kind, operands) => {
+                let layout = self.layout_of(ty);
+                let offsets = |layout: TyAndLayout<'tcx>| {
+                    (0..operands.len()).map(|i| layout.fields.offset(i).bytes()).collect()
+                };
+                let (size, offsets): (u64, Vec<u64>) = match **kind {
+                    // Only the active field of a union is written.
+                    AggregateKind::Adt(_, _, _, _, Some(field)) => {
+                        (layout.field(self, field.as_usize()).size.bytes(), vec![0])
+                    }
+                    AggregateKind::Adt(_, variant, ..) => {
+                        (layout.size.bytes(), offsets(layout.for_variant(self, variant)))
+                    }
+                    AggregateKind::Array(_) | AggregateKind::Tuple | AggregateKind::Closure(..) => {
+                        (layout.size.bytes(), offsets(layout))
+                    }
+                    AggregateKind::Coroutine(..) => (layout.size.bytes(), vec![]),
+                };
+                let size = Expr::int_constant(size, Type::size_t());
+                let mut stmts = vec![self.codegen_uninit_write(dst.clone(), size, loc)];
+                for (operand, offset) in operands.iter().zip(offsets) {
+                    let (Operand::Copy(src) | Operand::Move(src)) = operand else { continue };
+                    if Self::is_uninit_scalar(self.place_ty(src)) {
+                        continue;
+                    }
+                    let Some((src, size)) = self.uninit_place(src) else { continue };
+                    let field = dst
+                        .clone()
+                        .cast_to(Type::unsigned_int(8).to_pointer())
+                        .plus(Expr::int_constant(offset, Type::size_t()));
+                    stmts.push(self.codegen_uninit_copy(src, field, size, loc));
+                }
+                Some(Stmt::block(stmts, loc))
+            }
+            _ => Some(self.codegen_uninit_write(dst, size, loc)),
+        }
+    }
+
+    /// The statement that updates the tracked byte after `count` values of the pointee type of
+    /// `ty` are copied from `src` to `dst`.
+    pub fn codegen_uninit_copy_values(
+        &mut self,
+        src: Expr,
+        dst: Expr,
+        count: Expr,
+        ty: Ty<'tcx>,
+        loc: GotoLocation,
+    ) -> Option<Stmt> {
+        if !self.uninit_checks_enabled() {
+            return None;
+        }
+        let size = self.uninit_values_size(count, ty);
+        Some(self.codegen_uninit_copy(src, dst, size, loc))
+    }
+
+    /// The number of bytes of `count` values of the pointee type of `ty`.
+    fn uninit_values_size(&self, count: Expr, ty: Ty<'tcx>) -> Expr {
+        let pointee = ty.builtin_deref(true).unwrap().ty;
+        let size = Expr::int_constant(self.layout_of(pointee).size.bytes(), Type::size_t());
+        count.cast_to(Type::size_t()).mul(size)
+    }
+
+    /// The statements that update the tracked byte before and after a call to the intrinsic
+    /// `intrinsic` with arguments `fargs` of types `arg_tys`.
+    pub fn codegen_uninit_intrinsic(
+        &mut self,
+        intrinsic: &str,
+        fargs: &[Expr],
+        arg_tys: &[Ty<'tcx>],
+        loc: GotoLocation,
+    ) -> (Option<Stmt>, Option<Stmt>) {
+        if !self.uninit_checks_enabled() {
+            return (None, None);
+        }
+        let one = || Expr::int_constant(1, Type::size_t());
+        match intrinsic {
+            "volatile_load" | "unaligned_volatile_load" => {
+                let pointee = arg_tys[0].builtin_deref(true).unwrap().ty;
+                (Some(self.codegen_uninit_read(fargs[0].clone(), pointee, loc)), None)
+            }
+            _ if intrinsic.starts_with("atomic_load") => {
+                let pointee = arg_tys[0].builtin_deref(true).unwrap().ty;
+                (Some(self.codegen_uninit_read(fargs[0].clone(), pointee, loc)), None)
+            }
+            "copy" => {
+                let size = self.uninit_values_size(fargs[2].clone(), arg_tys[0]);
+                (
+                    None,
+                    Some(self.codegen_uninit_copy(fargs[0].clone(), fargs[1].clone(), size, loc)),
+                )
+            }
+            "volatile_copy_memory" | "volatile_copy_nonoverlapping_memory" => {
+                let size = self.uninit_values_size(fargs[2].clone(), arg_tys[0]);
+                (
+                    None,
+                    Some(self.codegen_uninit_copy(fargs[1].clone(), fargs[0].clone(), size, loc)),
+                )
+            }
+            "write_bytes" | "volatile_set_memory" => {
+                let size = self.uninit_values_size(fargs[2].clone(), arg_tys[0]);
+                (None, Some(self.codegen_uninit_write(fargs[0].clone(), size, loc)))
+            }
+            "volatile_store" | "unaligned_volatile_store" => {
+                let size = self.uninit_values_size(one(), arg_tys[0]);
+                (None, Some(self.codegen_uninit_write(fargs[0].clone(), size, loc)))
+            }
+            _ if intrinsic.starts_with("atomic_") && !intrinsic.contains("fence") => {
+                let size = self.uninit_values_size(one(), arg_tys[0]);
+                (None, Some(self.codegen_uninit_write(fargs[0].clone(), size, loc)))
+            }
+            _ => (None, None),
+        }
+    }
+
+    /// The statements that update the tracked byte before a call to a function with a body,
+    /// i.e., that record where the function returns to and which argument holds the tracked
+    /// byte, if any.
+    pub fn codegen_uninit_call(
+        &mut self,
+        args: &[Operand<'tcx>],
+        destination: &Place<'tcx>,
+        loc: GotoLocation,
+    ) -> Vec<Stmt> {
+        if !self.uninit_checks_enabled() {
+            return vec![];
+        }
+        let mut stmts = vec![];
+        let ret_var = self.uninit_return();
+        let ret = match self.uninit_place(destination) {
+            Some((ptr, size)) if !Self::is_uninit_scalar(self.place_ty(destination)) => {
+                // The call overwrites the destination, unless the tracked byte is returned.
+                stmts.push(self.codegen_uninit_write(ptr.clone(), size, loc));
+                ptr.cast_to(Type::void_pointer())
+            }
+            _ => Type::void_pointer().null(),
+        };
+        stmts.push(ret_var.assign(ret, loc));
+        for (idx, arg) in args.iter().enumerate() {
+            let (Operand::Copy(place) | Operand::Move(place)) = arg else { continue };
+            if Self::is_uninit_scalar(self.place_ty(place)) {
+                continue;
+            }
+            let Some((ptr, size)) = self.uninit_place(place) else { continue };
+            let passed = self.uninit_covers(ptr.clone(), size).and(Type::bool().nondet());
+            let pending = self.uninit_pending_arg();
+            let pending_offset = self.uninit_pending_offset();
+            let offset = self.uninit_offset_from(ptr);
+            let index = Expr::int_constant(idx + 1, Type::size_t());
+            stmts.push(Stmt::if_then_else(
+                passed,
+                Stmt::block(
+                    vec![pending.assign(index, loc), pending_offset.assign(offset, loc)],
+                    loc,
+                ),
+                None,
+                loc,
+            ));
+        }
+        stmts
+    }
+
+    /// The statements that update the tracked byte after a call returns.
+    pub fn codegen_uninit_end_call(&mut self, loc: GotoLocation) -> Vec<Stmt> {
+        if !self.uninit_checks_enabled() {
+            return vec![];
+        }
+        // The function may not have consumed them, e.g., if it has no body.
+        let pending = self.uninit_pending_arg();
+        let ret = self.uninit_return();
+        vec![
+            pending.assign(Expr::int_constant(0, Type::size_t()), loc),
+            ret.assign(Type::void_pointer().null(), loc),
+        ]
+    }
+
+    /// The statement that updates the tracked byte after a call to the foreign function
+    /// `name` that returned to `destination`. The allocator functions allocate memory, while the
+    /// other functions are assumed to initialize the value that they return.
+    pub fn codegen_uninit_foreign_call(
+        &mut self,
+        name: &str,
+        fargs: &[Expr],
+        destination: &Place<'tcx>,
+        loc: GotoLocation,
+    ) -> Option<Stmt> {
+        if !self.uninit_checks_enabled() {
+            return None;
+        }
+        match name {
+            "__rust_alloc" => {
+                let ptr = self.codegen_place(destination).ok()?.goto_expr;
+                Some(self.codegen_uninit_alloc(ptr, fargs[0].clone(), loc))
+            }
+            "__rust_realloc" => {
+                // The old bytes are moved to the new allocation, and the new bytes after them are
+                // uninitialized.
+                let ptr = self.codegen_place(destination).ok()?.goto_expr;
+                let (old, old_size, new_size) = (&fargs[0], &fargs[1], &fargs[3]);
+                let moved = self.uninit_covers(old.clone(), old_size.clone());
+                let offset = self.uninit_offset_from(old.clone());
+                let follow = self.uninit_move_byte(ptr.clone(), offset, loc);
+                let tail = ptr.cast_to(Type::unsigned_int(8).to_pointer()).plus(old_size.clone());
+                let alloc =
+                    self.codegen_uninit_alloc(tail, new_size.clone().sub(old_size.clone()), loc);
+                let grown =
+                    Stmt::if_then_else(new_size.clone().gt(old_size.clone()), alloc, None, loc);
+                Some(Stmt::if_then_else(moved, follow, Some(grown), loc))
+            }
+            _ if Self::is_uninit_scalar(self.place_ty(destination)) => None,
+            _ => {
+                let (ptr, size) = self.uninit_place(destination)?;
+                Some(self.codegen_uninit_write(ptr, size, loc))
+            }
+        }
+    }
+
+    /// The statement that moves the tracked byte to where the current function returns to, if
+    /// it's in the return value.
+    pub fn codegen_uninit_return(&mut self, loc: GotoLocation) -> Option<Stmt> {
+        if !self.uninit_checks_enabled() {
+            return None;
+        }
+        let ret = Place::from(RETURN_PLACE);
+        if Self::is_uninit_scalar(self.place_ty(&ret)) {
+            return None;
+        }
+        let saved = self.current_fn().uninit_return()?.clone();
+        let (ptr, size) = self.uninit_place(&ret)?;
+        let returned = saved.clone().is_nonnull().and(self.uninit_covers(ptr.clone(), size));
+        let offset = self.uninit_offset_from(ptr);
+        let follow = self.uninit_move_byte(saved, offset, loc);
+        Some(Stmt::if_then_else(returned, follow, None, loc))
+    }
+
+    /// Prepend to the statement or terminator at `statement_index` the checks that the scalars
+    /// it reads from memory are initialized.
+    pub fn codegen_uninit_read_checks(
+        &mut self,
+        block: BasicBlock,
+        statement_index: usize,
+        span: Span,
+        code: Stmt,
+    ) -> Stmt {
+        if !self.uninit_checks_enabled() {
+            return code;
+        }
+        let location = Location { block, statement_index };
+        let places = read_places(self.current_fn().mir(), location);
+        let loc = self.codegen_span(&span);
+        let mut checks = vec![];
+        for place in places {
+            let ty = self.place_ty(&place);
+            // A local that holds a scalar never holds the tracked byte.
+            if place.projection.is_empty() || !Self::is_uninit_scalar(ty) {
+                continue;
+            }
+            let Some((ptr, _)) = self.uninit_place(&place) else { continue };
+            checks.push(self.codegen_uninit_read(ptr, ty, loc));
+        }
+        if checks.is_empty() {
+            return code;
+        }
+        Stmt::block(checks.into_iter().chain([code]).collect(), loc)
+    }
+}
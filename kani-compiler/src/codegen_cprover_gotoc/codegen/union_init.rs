@@ -0,0 +1,201 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module generates the checks that reading a field of a union doesn't read bytes that
+//! were never initialized, e.g., reading a `u32` field after writing only a `u8` field.
+//!
+//! Unions are modeled as C unions, so reading a field other than the one that was last written
+//! reinterprets the bits of the union. The bytes that no write covered are nondeterministic,
+//! though, and reading them is undefined behavior in Rust. For each union that is a local
+//! variable of the function, we keep in a variable how many of its leading bytes are
+//! initialized, and we check it whenever a field is read. All the fields of a union start at
+//! offset 0, so this count is enough to tell which fields can be read.
+//!
+//! We only track the locals whose address is never taken and that are only written by
+//! assignments, since we can't tell how the other writes affect them. Function parameters are
+//! initialized by the caller, so they are never tracked either.
+
+use crate::codegen_cprover_gotoc::codegen::PropertyClass;
+use crate::codegen_cprover_gotoc::GotocCtx;
+use cbmc::goto_program::{Expr, Location as GotoLocation, Stmt, Type};
+use rustc_middle::mir::visit::{MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::{
+    AggregateKind, BasicBlock, Body, Local, Location, Place, ProjectionElem, Rvalue,
+};
+use rustc_middle::ty::layout::LayoutOf;
+use rustc_middle::ty::Ty;
+use rustc_span::Span;
+use rustc_target::abi::FieldIdx;
+use std::collections::BTreeSet;
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// Declare the variables that track how many bytes of each union local are initialized.
+    pub fn codegen_declare_union_init_vars(&mut self) {
+        let mir = self.current_fn().mir();
+        let mut collector = UnionLocals {
+            candidates: mir
+                .local_decls
+                .indices()
+                .filter(|local| local.index() == 0 || local.index() > mir.arg_count)
+                .filter(|local| self.monomorphize(mir.local_decls[*local].ty).is_union())
+                .collect(),
+        };
+        if collector.candidates.is_empty() {
+            return;
+        }
+        collector.visit_body(mir);
+        for local in collector.candidates {
+            let loc = self.codegen_span(&mir.local_decls[local].source_info.span);
+            let name = format!("{}::union_init", self.codegen_var_name(&local));
+            let base_name = format!("{}::union_init", self.codegen_var_base_name(&local));
+            let zero = Expr::int_constant(0, Type::size_t());
+            let var = self.declare_variable(name, base_name, Type::size_t(), Some(zero), loc);
+            self.current_fn_mut().register_union_init_var(local, var.to_expr());
+        }
+    }
+
+    /// The statement that updates the number of initialized bytes of the union that `place`
+    /// writes to, if it's a tracked union. `rvalue` is the value that is assigned.
+    pub fn codegen_union_init_write(
+        &mut self,
+        place: &Place<'tcx>,
+        rvalue: &Rvalue<'tcx>,
+        loc: GotoLocation,
+    ) -> Option<Stmt> {
+        let var = self.current_fn().union_init_var(&place.local)?.clone();
+        let union_ty = self.monomorphize(self.current_fn().mir().local_decls[place.local].ty);
+        let size = match (place.projection.first(), rvalue) {
+            // Only the active field of a union expression is initialized.
+            (None, Rvalue::Aggregate(box AggregateKind::Adt(.., Some(field)), _)) => {
+                self.union_field_size(union_ty, *field)
+            }
+            (None, _) => self.layout_of(union_ty).size.bytes(),
+            // Writing a field doesn't uninitialize the bytes that follow it.
+            (Some(ProjectionElem::Field(field, _)), _) => {
+                let size =
+                    Expr::int_constant(self.union_field_size(union_ty, *field), var.typ().clone());
+                return Some(var.clone().assign(var.max(size), loc));
+            }
+            (Some(_), _) => unreachable!("union `{union_ty}` is only written by assignments"),
+        };
+        Some(var.clone().assign(Expr::int_constant(size, var.typ().clone()), loc))
+    }
+
+    /// Forget which bytes of `local` were initialized, since its storage is live again.
+    pub fn codegen_union_init_reset(&mut self, local: Local, loc: GotoLocation) -> Option<Stmt> {
+        let var = self.current_fn().union_init_var(&local)?.clone();
+        let zero = Expr::int_constant(0, var.typ().clone());
+        Some(var.assign(zero, loc))
+    }
+
+    /// The check that reading `place` doesn't read uninitialized bytes of a tracked union.
+    pub fn codegen_union_init_read(
+        &mut self,
+        place: &Place<'tcx>,
+        loc: GotoLocation,
+    ) -> Option<Stmt> {
+        let var = self.current_fn().union_init_var(&place.local)?.clone();
+        let Some(ProjectionElem::Field(field, field_ty)) = place.projection.first() else {
+            return None;
+        };
+        let union_ty = self.monomorphize(self.current_fn().mir().local_decls[place.local].ty);
+        let size = self.union_field_size(union_ty, *field);
+        if size == 0 {
+            return None;
+        }
+        let field_name = &union_ty.ty_adt_def().unwrap().non_enum_variant().fields[*field].name;
+        let cond = var.clone().ge(Expr::int_constant(size, var.typ().clone()));
+        Some(self.codegen_assert_assume(
+            cond,
+            PropertyClass::SafetyCheck,
+            &format!(
+                "reading field `{field_name}` of type `{}` from union `{union_ty}` reads \
+                uninitialized bytes",
+                self.monomorphize(*field_ty)
+            ),
+            loc,
+        ))
+    }
+
+    /// Prepend to the statement or terminator at `statement_index` the checks that the union
+    /// fields it reads are initialized.
+    pub fn codegen_union_read_checks(
+        &mut self,
+        block: BasicBlock,
+        statement_index: usize,
+        span: Span,
+        code: Stmt,
+    ) -> Stmt {
+        if !self.current_fn().has_union_init_vars() {
+            return code;
+        }
+        let location = Location { block, statement_index };
+        let places = read_places(self.current_fn().mir(), location);
+        let loc = self.codegen_span(&span);
+        let checks: Vec<_> =
+            places.iter().filter_map(|place| self.codegen_union_init_read(place, loc)).collect();
+        if checks.is_empty() {
+            return code;
+        }
+        Stmt::block(checks.into_iter().chain([code]).collect(), loc)
+    }
+
+    fn union_field_size(&self, union_ty: Ty<'tcx>, field: FieldIdx) -> u64 {
+        self.layout_of(union_ty).field(self, field.as_usize()).size.bytes()
+    }
+}
+
+/// Finds the union locals whose initialized bytes can be tracked, by removing from the
+/// candidates the ones that are used in any way other than reading and assigning.
+struct UnionLocals {
+    candidates: BTreeSet<Local>,
+}
+
+impl<'tcx> Visitor<'tcx> for UnionLocals {
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, _location: Location) {
+        let tracked = match context {
+            PlaceContext::NonMutatingUse(
+                NonMutatingUseContext::SharedBorrow
+                | NonMutatingUseContext::ShallowBorrow
+                | NonMutatingUseContext::AddressOf,
+            ) => false,
+            PlaceContext::NonMutatingUse(_) => true,
+            PlaceContext::MutatingUse(MutatingUseContext::Store) => {
+                matches!(place.projection.first(), None | Some(ProjectionElem::Field(..)))
+            }
+            PlaceContext::MutatingUse(MutatingUseContext::Drop) => true,
+            PlaceContext::MutatingUse(_) => false,
+            PlaceContext::NonUse(_) => true,
+        };
+        if !tracked {
+            self.candidates.remove(&place.local);
+        }
+    }
+}
+
+/// The places that the statement or terminator at `location` reads.
+pub(super) fn read_places<'tcx>(mir: &Body<'tcx>, location: Location) -> Vec<Place<'tcx>> {
+    let mut reads = ReadPlaces { places: vec![] };
+    let bbd = &mir.basic_blocks[location.block];
+    match bbd.statements.get(location.statement_index) {
+        Some(stmt) => reads.visit_statement(stmt, location),
+        None => reads.visit_terminator(bbd.terminator(), location),
+    }
+    reads.places
+}
+
+/// Collects the places that a statement or terminator reads.
+struct ReadPlaces<'tcx> {
+    places: Vec<Place<'tcx>>,
+}
+
+impl<'tcx> Visitor<'tcx> for ReadPlaces<'tcx> {
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, _location: Location) {
+        if matches!(
+            context,
+            PlaceContext::NonMutatingUse(NonMutatingUseContext::Copy | NonMutatingUseContext::Move)
+        ) {
+            self.places.push(*place);
+        }
+    }
+}
@@ -6,13 +6,16 @@
 use crate::args::ReachabilityType;
 use crate::codegen_cprover_gotoc::GotocCtx;
 use crate::kani_middle::analysis;
-use crate::kani_middle::attributes::is_test_harness_description;
+use crate::kani_middle::assumptions::harness_assumptions;
+use crate::kani_middle::attributes::{is_proof_harness, is_test_harness_description};
 use crate::kani_middle::metadata::gen_test_metadata;
 use crate::kani_middle::provide;
 use crate::kani_middle::reachability::{
     collect_reachable_items, filter_const_crate_items, filter_crate_items,
 };
-use crate::kani_middle::{check_reachable_items, dump_mir_items};
+use crate::kani_middle::secret_taint::SecretSinks;
+use crate::kani_middle::stubbing::stub_usage;
+use crate::kani_middle::{asm_blocks, check_reachable_items, dump_mir_items, synthesized_harness};
 use crate::kani_queries::QueryDb;
 use cbmc::goto_program::Location;
 use cbmc::irep::goto_binary_serde::write_goto_binary_file;
@@ -31,7 +34,7 @@
 use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
 use rustc_data_structures::temp_dir::MaybeTempDir;
 use rustc_errors::{ErrorGuaranteed, DEFAULT_LOCALE_RESOURCE};
-use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 use rustc_hir::definitions::DefPathHash;
 use rustc_metadata::fs::{emit_wrapper_file, METADATA_FILENAME};
 use rustc_metadata::EncodedMetadata;
@@ -93,6 +96,9 @@ fn codegen_items<'tcx>(
         // https://rustc-dev-guide.rust-lang.org/conventions.html#naming-conventions
         let mut gcx = GotocCtx::new(tcx, (*self.queries.lock().unwrap()).clone(), machine_model);
         check_reachable_items(gcx.tcx, &gcx.queries, &items);
+        if gcx.queries.args().constant_time_checks {
+            gcx.secret_sinks = SecretSinks::new(tcx, &items);
+        }
 
         with_timer(
             || {
@@ -148,6 +154,8 @@ fn codegen_items<'tcx>(
             "codegen",
         );
 
+        with_timer(|| gcx.symbol_table.simplify(), "goto simplification");
+
         // Map from name to prettyName for all symbols
         let pretty_name_map: BTreeMap<InternedString, Option<InternedString>> =
             BTreeMap::from_iter(gcx.symbol_table.iter().map(|(k, s)| (*k, s.pretty_name)));
@@ -230,13 +238,60 @@ fn codegen_crate(
                 let harnesses = queries.target_harnesses();
                 let mut items: HashSet<DefPathHash> = HashSet::with_capacity(harnesses.len());
                 items.extend(harnesses);
-                let harnesses =
-                    filter_crate_items(tcx, |_, def_id| items.contains(&tcx.def_path_hash(def_id)));
-                for harness in harnesses {
-                    let model_path =
-                        queries.harness_model_path(&tcx.def_path_hash(harness.def_id())).unwrap();
+                let harnesses = filter_crate_items(tcx, |_, def_id| {
+                    is_proof_harness(tcx, def_id) && items.contains(&tcx.def_path_hash(def_id))
+                });
+                // Harnesses synthesized by Kani are identified by the function they check, which
+                // may be generic, so we find them from their identifier instead.
+                let mut synthesized = Vec::from_iter(items.iter().copied());
+                synthesized.sort();
+                let synthesized = synthesized.into_iter().filter_map(|hash| {
+                    let target = tcx.def_path_hash_to_def_id(hash, &mut || panic!());
+                    let harness = synthesized_harness(tcx, queries.args(), target)?;
+                    Some((target, MonoItem::Fn(harness)))
+                });
+                let harnesses = harnesses
+                    .into_iter()
+                    .map(|harness| (harness.def_id(), harness))
+                    .chain(synthesized);
+                for (harness_id, root) in harnesses {
+                    let harness_hash = tcx.def_path_hash(harness_id);
+                    let model_path = queries.harness_model_path(&harness_hash).unwrap();
                     let (gcx, items) =
-                        self.codegen_items(tcx, &[harness], model_path, &results.machine_model);
+                        self.codegen_items(tcx, &[root], model_path, &results.machine_model);
+                    if let Some(contract) = harness_contract(tcx, &gcx, harness_id) {
+                        self.queries
+                            .lock()
+                            .unwrap()
+                            .harness_contracts
+                            .insert(harness_hash, contract);
+                    }
+                    let stub_usage = stub_usage(tcx, &items);
+                    if !stub_usage.is_empty() {
+                        self.queries
+                            .lock()
+                            .unwrap()
+                            .harness_stub_usage
+                            .insert(harness_hash, stub_usage);
+                    }
+                    let assumptions = harness_assumptions(tcx, queries.args(), &items);
+                    if !assumptions.is_empty() {
+                        self.queries
+                            .lock()
+                            .unwrap()
+                            .harness_assumptions
+                            .insert(harness_hash, assumptions);
+                    }
+                    if queries.args().ignore_asm {
+                        let havocked_asm = asm_blocks(tcx, &items);
+                        if !havocked_asm.is_empty() {
+                            self.queries
+                                .lock()
+                                .unwrap()
+                                .harness_havocked_asm
+                                .insert(harness_hash, havocked_asm);
+                        }
+                    }
                     results.extend(gcx, items, None);
                 }
             }
@@ -390,16 +445,19 @@ fn check_target(session: &Session) {
     let is_x86_64_darwin_target = session.target.llvm_target.starts_with("x86_64-apple-");
     // looking for `arm64-apple-*`
     let is_arm64_darwin_target = session.target.llvm_target.starts_with("arm64-apple-");
+    // 32-bit ARM without an operating system, e.g., `thumbv7em-none-eabihf`, for `no_std` crates.
+    let is_arm_bare_metal_target = session.target.arch == "arm" && session.target.os == "none";
 
     if !is_x86_64_linux_target
         && !is_arm64_linux_target
         && !is_x86_64_darwin_target
         && !is_arm64_darwin_target
+        && !is_arm_bare_metal_target
     {
         let err_msg = format!(
             "Kani requires the target platform to be `x86_64-unknown-linux-gnu`, \
-            `aarch64-unknown-linux-gnu`, `x86_64-apple-*` or `arm64-apple-*`, but \
-            it is {}",
+            `aarch64-unknown-linux-gnu`, `x86_64-apple-*`, `arm64-apple-*` or a bare-metal \
+            32-bit ARM target such as `thumbv7em-none-eabihf`, but it is {}",
             &session.target.llvm_target
         );
         session.err(err_msg);
@@ -509,6 +567,38 @@ pub fn write_file<T>(base_path: &Path, file_type: ArtifactType, source: &T, pret
     }
 }
 
+/// Find the function whose goto contract CBMC should enforce for the given harness.
+///
+/// CBMC can only enforce the contract of one function per harness, so we report
+/// an error if the harness reaches several of them, e.g. different
+/// instantiations of a generic function with a `modifies` clause.
+fn harness_contract<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    gcx: &GotocCtx<'tcx>,
+    harness: DefId,
+) -> Option<String> {
+    match gcx.contracted_functions.as_slice() {
+        [] => None,
+        [function] => Some(function.to_string()),
+        functions => {
+            let names = functions
+                .iter()
+                .map(|name| gcx.symbol_table.lookup(*name).unwrap().pretty_name.unwrap_or(*name))
+                .collect::<Vec<_>>();
+            tcx.sess.span_err(
+                tcx.def_span(harness),
+                format!(
+                    "the `modifies` clauses of {} functions are reachable from this harness, but \
+                    only one can be checked per harness: `{}`",
+                    names.len(),
+                    names.iter().map(|name| name.to_string()).collect::<Vec<_>>().join("`, `")
+                ),
+            );
+            None
+        }
+    }
+}
+
 struct GotoCodegenResults<'tcx> {
     reachability: ReachabilityType,
     harnesses: Vec<HarnessMetadata>,
@@ -620,8 +710,8 @@ fn print_report(&self, tcx: TyCtxt<'tcx>) {
 
 /// Builds a machine model which is required by CBMC
 fn new_machine_model(sess: &Session) -> MachineModel {
-    // The model assumes a `x86_64-unknown-linux-gnu`, `x86_64-apple-darwin`
-    // or `aarch64-apple-darwin` platform. We check the target platform in function
+    // The model assumes a `x86_64-unknown-linux-gnu`, `x86_64-apple-darwin`,
+    // `aarch64-apple-darwin` or bare-metal 32-bit ARM platform. We check the target platform in function
     // `check_target` from src/kani-compiler/src/codegen_cprover_gotoc/compiler_interface.rs
     // and error if it is not any of the ones we expect.
     let architecture = &sess.target.arch;
@@ -732,6 +822,52 @@ fn new_machine_model(sess: &Session) -> MachineModel {
                 word_size: int_width,
             }
         }
+        "arm" => {
+            // The sizes of the ARM EABI, which bare-metal targets such as
+            // `thumbv7em-none-eabihf` use.
+            let bool_width = 8;
+            let char_is_unsigned = true;
+            let char_width = 8;
+            let double_width = 64;
+            let float_width = 32;
+            let int_width = 32;
+            let long_double_width = 64;
+            let long_int_width = 32;
+            let long_long_int_width = 64;
+            let short_int_width = 16;
+            let single_width = 32;
+            let wchar_t_is_unsigned = true;
+            let wchar_t_width = 32;
+
+            MachineModel {
+                // CBMC distinguishes the hard-float ABI from the soft-float one.
+                architecture: if sess.target.llvm_target.ends_with("hf") {
+                    "armhf".to_string()
+                } else {
+                    "armel".to_string()
+                },
+                alignment,
+                bool_width,
+                char_is_unsigned,
+                char_width,
+                double_width,
+                float_width,
+                int_width,
+                is_big_endian,
+                long_double_width,
+                long_int_width,
+                long_long_int_width,
+                memory_operand_size: int_width / 8,
+                null_is_zero: true,
+                pointer_width,
+                rounding_mode: RoundingMode::ToNearest,
+                short_int_width,
+                single_width,
+                wchar_t_is_unsigned,
+                wchar_t_width,
+                word_size: int_width,
+            }
+        }
         _ => {
             panic!("Unsupported architecture: {architecture}");
         }
@@ -2,17 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use crate::codegen_cprover_gotoc::GotocCtx;
-use cbmc::goto_program::Stmt;
+use cbmc::goto_program::{Expr, Stmt};
 use rustc_middle::mir::BasicBlock;
 use rustc_middle::mir::Body;
+use rustc_middle::mir::Local;
 use rustc_middle::ty::Instance;
 use rustc_middle::ty::PolyFnSig;
+use std::collections::HashMap;
 
 /// This structure represents useful data about the function we are currently compiling.
 #[derive(Debug)]
 pub struct CurrentFnCtx<'tcx> {
     /// The GOTO block we are compiling into
     block: Vec<Stmt>,
+    /// The concrete values of the symbolic inputs that `--concrete` fixes, if this is a
+    /// harness.
+    concrete_inputs: HashMap<Local, Expr>,
     /// The current MIR basic block
     current_bb: Option<BasicBlock>,
     /// The codegen instance for the current function
@@ -21,6 +26,11 @@ pub struct CurrentFnCtx<'tcx> {
     krate: String,
     /// The goto labels for all blocks
     labels: Vec<String>,
+    /// The invariants registered with `#[kani::loop_invariant]`, indexed by the loop head.
+    loop_invariants: HashMap<BasicBlock, Expr>,
+    /// The components of the measures registered with `#[kani::decreases]`, indexed by the loop
+    /// head.
+    loop_decreases: HashMap<BasicBlock, Vec<Expr>>,
     /// The mir for the current instance
     mir: &'tcx Body<'tcx>,
     /// The symbol name of the current function
@@ -31,6 +41,10 @@ pub struct CurrentFnCtx<'tcx> {
     sig: PolyFnSig<'tcx>,
     /// A counter to enable creating temporary variables
     temp_var_counter: u64,
+    /// The variable that saves where the function returns to, with `--check uninit`.
+    uninit_return: Option<Expr>,
+    /// The variables that hold how many bytes of each tracked union local are initialized.
+    union_init_vars: HashMap<Local, Expr>,
 }
 
 /// Constructor
@@ -38,15 +52,20 @@ impl<'tcx> CurrentFnCtx<'tcx> {
     pub fn new(instance: Instance<'tcx>, gcx: &GotocCtx<'tcx>, labels: Vec<String>) -> Self {
         Self {
             block: vec![],
+            concrete_inputs: HashMap::new(),
             current_bb: None,
             instance,
             krate: gcx.get_crate(instance),
             labels,
+            loop_invariants: HashMap::new(),
+            loop_decreases: HashMap::new(),
             mir: gcx.tcx.instance_mir(instance.def),
             name: gcx.symbol_name(instance),
             readable_name: gcx.readable_instance_name(instance),
             sig: gcx.fn_sig_of_instance(instance),
             temp_var_counter: 0,
+            uninit_return: None,
+            union_init_vars: HashMap::new(),
         }
     }
 }
@@ -64,6 +83,11 @@ pub fn get_and_incr_counter(&mut self) -> u64 {
         rval
     }
 
+    /// Register the concrete value of the symbolic input held by `local`.
+    pub fn register_concrete_input(&mut self, local: Local, value: Expr) {
+        self.concrete_inputs.insert(local, value);
+    }
+
     pub fn push_onto_block(&mut self, s: Stmt) {
         self.block.push(s)
     }
@@ -75,6 +99,28 @@ pub fn reset_current_bb(&mut self) {
     pub fn set_current_bb(&mut self, bb: BasicBlock) {
         self.current_bb = Some(bb);
     }
+
+    /// Register the invariant of the loop whose head is the current basic block.
+    pub fn register_loop_invariant(&mut self, inv: Expr) {
+        let head = self.current_bb.expect("loop invariants are registered inside a basic block");
+        self.loop_invariants.insert(head, inv);
+    }
+
+    /// Register the variable that saves where the function returns to.
+    pub fn set_uninit_return(&mut self, var: Expr) {
+        self.uninit_return = Some(var);
+    }
+
+    /// Register the variable that holds how many bytes of the union `local` are initialized.
+    pub fn register_union_init_var(&mut self, local: Local, var: Expr) {
+        self.union_init_vars.insert(local, var);
+    }
+
+    /// Register the components of the measure of the loop whose head is the current basic block.
+    pub fn register_loop_decreases(&mut self, measures: Vec<Expr>) {
+        let head = self.current_bb.expect("loop measures are registered inside a basic block");
+        self.loop_decreases.insert(head, measures);
+    }
 }
 
 /// Getters
@@ -84,11 +130,77 @@ pub fn instance(&self) -> Instance<'tcx> {
         self.instance
     }
 
+    /// The MIR basic block being compiled, if any.
+    pub fn current_bb(&self) -> Option<BasicBlock> {
+        self.current_bb
+    }
+
+    /// The concrete value of the symbolic input held by `local`, if `--concrete` fixes it.
+    pub fn concrete_input(&self, local: &Local) -> Option<&Expr> {
+        self.concrete_inputs.get(local)
+    }
+
     /// The crate that function came from
     pub fn krate(&self) -> String {
         self.krate.to_string()
     }
 
+    /// The invariant of the loop with the given head, if one has been registered.
+    pub fn loop_invariant(&self, head: &BasicBlock) -> Option<&Expr> {
+        self.loop_invariants.get(head)
+    }
+
+    /// The blocks that jump back to the loop with the given head, i.e., its predecessors that it
+    /// dominates, in the order in which they are compiled. A loop may have several of them, and
+    /// their terminators can be of any kind.
+    pub fn loop_latches(&self, head: &BasicBlock) -> Vec<BasicBlock> {
+        let dominators = self.mir.basic_blocks.dominators();
+        let predecessors = &self.mir.basic_blocks.predecessors()[*head];
+        self.mir
+            .basic_blocks
+            .reverse_postorder()
+            .iter()
+            .copied()
+            .filter(|bb| predecessors.contains(bb) && dominators.dominates(*head, *bb))
+            .collect()
+    }
+
+    /// Whether the edge from the current block to `target` is a back edge of a loop with an
+    /// invariant, which goes through the label returned by [Self::latch_label].
+    pub fn is_contract_back_edge(&self, target: &BasicBlock) -> bool {
+        self.loop_invariants.contains_key(target)
+            && self
+                .current_bb
+                .is_some_and(|bb| self.mir.basic_blocks.dominators().dominates(*target, bb))
+    }
+
+    /// The label of the goto that carries the contracts of the loop with the given head. All the
+    /// back edges of the loop go through this goto, so CBMC sees a single one.
+    pub fn latch_label(&self, head: &BasicBlock) -> String {
+        format!("{}_latch", self.block_label(head))
+    }
+
+    /// The components of the measure of the loop with the given head, if one has been registered.
+    pub fn loop_decreases(&self, head: &BasicBlock) -> Option<&Vec<Expr>> {
+        self.loop_decreases.get(head)
+    }
+
+    /// The variable that saves where the function returns to, if `--check uninit` is enabled.
+    pub fn uninit_return(&self) -> Option<&Expr> {
+        self.uninit_return.as_ref()
+    }
+
+    /// The variable that holds how many bytes of the union `local` are initialized, if its
+    /// initialization is tracked.
+    pub fn union_init_var(&self, local: &Local) -> Option<&Expr> {
+        self.union_init_vars.get(local)
+    }
+
+    /// Whether the initialization of any union local is tracked.
+    pub fn has_union_init_vars(&self) -> bool {
+        !self.union_init_vars.is_empty()
+    }
+
     /// The MIR for the function we are currently compiling
     pub fn mir(&self) -> &'tcx Body<'tcx> {
         self.mir
@@ -117,7 +229,17 @@ pub fn is_std(&self) -> bool {
         self.krate == "std" || self.krate == "core"
     }
 
+    /// The label to jump to `bb` from the current block. A back edge of a loop with an invariant
+    /// jumps to the goto that carries the contracts of the loop instead, which then jumps to `bb`.
     pub fn find_label(&self, bb: &BasicBlock) -> String {
+        match self.current_bb {
+            Some(_) if self.is_contract_back_edge(bb) => self.latch_label(bb),
+            _ => self.block_label(bb),
+        }
+    }
+
+    /// The label of the first statement of `bb`.
+    pub fn block_label(&self, bb: &BasicBlock) -> String {
         self.labels[bb.index()].clone()
     }
 }
@@ -18,6 +18,7 @@
 use crate::codegen_cprover_gotoc::overrides::{fn_hooks, GotocHooks};
 use crate::codegen_cprover_gotoc::utils::full_crate_name;
 use crate::codegen_cprover_gotoc::UnsupportedConstructs;
+use crate::kani_middle::secret_taint::SecretSinks;
 use crate::kani_queries::QueryDb;
 use cbmc::goto_program::{DatatypeComponent, Expr, Location, Stmt, Symbol, SymbolTable, Type};
 use cbmc::utils::aggr_tag;
@@ -68,6 +69,12 @@ pub struct GotocCtx<'tcx> {
     /// We collect them and print one warning at the end if not empty instead of printing one
     /// warning at each occurrence.
     pub concurrent_constructs: UnsupportedConstructs,
+    /// The functions with a goto contract that should be enforced, i.e. the check
+    /// functions of contracts with a `modifies` clause.
+    pub contracted_functions: Vec<InternedString>,
+    /// The branches and memory accesses that depend on a secret, which are only computed with
+    /// `--constant-time-checks`.
+    pub secret_sinks: SecretSinks<'tcx>,
 }
 
 /// Constructor
@@ -97,6 +104,8 @@ pub fn new(
             global_checks_count: 0,
             unsupported_constructs: FxHashMap::default(),
             concurrent_constructs: FxHashMap::default(),
+            contracted_functions: vec![],
+            secret_sinks: SecretSinks::default(),
         }
     }
 }
@@ -168,10 +177,22 @@ pub fn decl_temp_variable(
         t: Type,
         value: Option<Expr>,
         loc: Location,
+    ) -> (Expr, Stmt) {
+        self.decl_named_temp_variable("temp", t, value, loc)
+    }
+
+    /// Like [Self::decl_temp_variable], but the name of the variable starts with `prefix`, which
+    /// makes it easy to find in the traces of CBMC.
+    pub fn decl_named_temp_variable(
+        &mut self,
+        prefix: &str,
+        t: Type,
+        value: Option<Expr>,
+        loc: Location,
     ) -> (Expr, Stmt) {
         let c = self.current_fn_mut().get_and_incr_counter();
         let var =
-            self.gen_stack_variable(c, &self.current_fn().name(), "temp", t, loc, false).to_expr();
+            self.gen_stack_variable(c, &self.current_fn().name(), prefix, t, loc, false).to_expr();
         let value = value.or_else(|| self.codegen_default_initializer(&var));
         let decl = Stmt::decl(var.clone(), value, loc);
         (var, decl)
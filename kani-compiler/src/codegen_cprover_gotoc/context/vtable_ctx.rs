@@ -30,7 +30,7 @@ pub struct VtableCtx {
     // Option to actually enable restrictions
     pub emit_vtable_restrictions: bool,
 
-    // Map: (normalized trait name, method index) -> possible implementations
+    // Map: (trait name without auto traits, method index) -> possible implementations
     possible_methods: FxHashMap<TraitDefinedMethod, Vec<InternedString>>,
 
     // All sites where a virtual call takes place
@@ -121,7 +121,8 @@ pub fn virtual_call_with_restricted_fn_ptr(
         // Retrieve the MIR for `&dyn T` and normalize the name.
         assert!(trait_ref.is_struct_tag());
         let trait_ref_mir_type = self.type_map.get(&trait_ref.tag().unwrap()).unwrap();
-        let trait_name = self.normalized_trait_name(pointee_type(*trait_ref_mir_type).unwrap());
+        let trait_name =
+            self.vtable_restriction_trait_name(pointee_type(*trait_ref_mir_type).unwrap());
 
         // Label
         self.vtable_ctx.add_call_site(
@@ -8,14 +8,15 @@
 //! It would be too nasty if we spread around these sort of undocumented hooks in place, so
 //! this module addresses this issue.
 
-use crate::codegen_cprover_gotoc::codegen::PropertyClass;
+use crate::codegen_cprover_gotoc::codegen::{constant_byte_count, PropertyClass, QuantifierKind};
 use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::kani_middle::attributes::is_function_contract_generated;
 use crate::unwrap_or_return_codegen_unimplemented_stmt;
 use cbmc::goto_program::{BuiltinFn, Expr, Location, Stmt, Type};
 use rustc_middle::mir::{BasicBlock, Place};
 use rustc_middle::ty::print::with_no_trimmed_paths;
-use rustc_middle::ty::{Instance, TyCtxt};
-use rustc_span::Span;
+use rustc_middle::ty::{GenericArgKind, Instance, TyCtxt, TyKind};
+use rustc_span::{Span, Symbol};
 use std::rc::Rc;
 use tracing::debug;
 
@@ -118,6 +119,363 @@ fn handle(
     }
 }
 
+/// A hook for Kani's `bounds::assume_bound` function, which is used by the `kani::bounds!`
+/// macro. The function takes a condition and the description of the bound.
+/// The condition is assumed, but first it is checked for satisfiability the same way as a cover
+/// property, so the driver can report the bounds that were declared and whether any of them
+/// excludes all values.
+struct AssumeBound;
+impl<'tcx> GotocHook<'tcx> for AssumeBound {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniAssumeBound")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        _instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        _assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 2);
+        let cond = fargs.remove(0).cast_to(Type::bool());
+        let msg = fargs.remove(0);
+        let msg = tcx.extract_const_message(&msg).unwrap();
+        let target = target.unwrap();
+        let caller_loc = tcx.codegen_caller_span(&span);
+
+        let (msg, reach_stmt) = tcx.codegen_reachability_check(msg, span);
+
+        Stmt::block(
+            vec![
+                reach_stmt,
+                tcx.codegen_assert(cond.clone().not(), PropertyClass::Bounds, &msg, caller_loc),
+                tcx.codegen_assume(cond, caller_loc),
+                Stmt::goto(tcx.current_fn().find_label(&target), caller_loc),
+            ],
+            caller_loc,
+        )
+    }
+}
+
+/// A hook for Kani's `internal::assume_predicate` function, which `kani::any_where` calls with
+/// the result of its predicate. The result is assumed, but first it is checked for
+/// satisfiability the same way as a cover property, so the driver can warn about harnesses that
+/// are vacuous because the predicate excludes all values. The location of the check is the
+/// predicate itself, which is the type argument of the function, if it is a closure or a function.
+struct AssumePredicate;
+impl<'tcx> GotocHook<'tcx> for AssumePredicate {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniAssumePredicate")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        _assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 1);
+        let cond = fargs.remove(0).cast_to(Type::bool());
+        let target = target.unwrap();
+        let predicate_span = match instance.args.type_at(0).kind() {
+            TyKind::Closure(def_id, _) | TyKind::FnDef(def_id, _) => {
+                Some(tcx.tcx.def_span(*def_id))
+            }
+            _ => span,
+        };
+        let loc = tcx.codegen_caller_span(&predicate_span);
+        let msg = "the predicate of `kani::any_where` is satisfiable".to_string();
+        let (msg, reach_stmt) = tcx.codegen_reachability_check(msg, predicate_span);
+
+        Stmt::block(
+            vec![
+                reach_stmt,
+                tcx.codegen_assert(cond.clone().not(), PropertyClass::Vacuity, &msg, loc),
+                tcx.codegen_assume(cond, loc),
+                Stmt::goto(tcx.current_fn().find_label(&target), loc),
+            ],
+            loc,
+        )
+    }
+}
+
+/// A hook for Kani's `internal::check_utf8` function, which the models of the unchecked UTF-8
+/// conversions call with `--utf8-checks`. The function takes a condition and a message, like
+/// `kani::assert`, but the assertion belongs to the `utf8` property class.
+struct CheckUtf8;
+impl<'tcx> GotocHook<'tcx> for CheckUtf8 {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniCheckUtf8")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        _instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        _assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 2);
+        let cond = fargs.remove(0).cast_to(Type::bool());
+        let msg = fargs.remove(0);
+        let msg = tcx.extract_const_message(&msg).unwrap();
+        let target = target.unwrap();
+        let caller_loc = tcx.codegen_caller_span(&span);
+
+        let (msg, reach_stmt) = tcx.codegen_reachability_check(msg, span);
+
+        Stmt::block(
+            vec![
+                reach_stmt,
+                tcx.codegen_assert_assume(cond, PropertyClass::Utf8, &msg, caller_loc),
+                Stmt::goto(tcx.current_fn().find_label(&target), caller_loc),
+            ],
+            caller_loc,
+        )
+    }
+}
+
+/// A hook for Kani's `internal::named_check` function, which `kani::check!` calls with the name
+/// of the check as the prefix of the message. Unlike `kani::assert`, the condition isn't assumed
+/// after the check, so the checks that follow it are still verified when it fails.
+struct NamedCheck;
+impl<'tcx> GotocHook<'tcx> for NamedCheck {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniNamedCheck")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        _instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        _assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 2);
+        let cond = fargs.remove(0).cast_to(Type::bool());
+        let msg = fargs.remove(0);
+        let msg = tcx.extract_const_message(&msg).unwrap();
+        let target = target.unwrap();
+        let caller_loc = tcx.codegen_caller_span(&span);
+
+        let (msg, reach_stmt) = tcx.codegen_reachability_check(msg, span);
+
+        Stmt::block(
+            vec![
+                reach_stmt,
+                tcx.codegen_assert(cond, PropertyClass::NamedCheck, &msg, caller_loc),
+                Stmt::goto(tcx.current_fn().find_label(&target), caller_loc),
+            ],
+            caller_loc,
+        )
+    }
+}
+
+/// A hook for Kani's `internal::assert_class` function, which is used by the
+/// `kani::assert_class!` macro. The function takes a condition, a property class and a message,
+/// and it is handled like `kani::assert`, except that the assertion belongs to the given class.
+struct AssertClass;
+impl<'tcx> GotocHook<'tcx> for AssertClass {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniAssertClass")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        _instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        _assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 3);
+        let cond = fargs.remove(0).cast_to(Type::bool());
+        let class = fargs.remove(0);
+        let mut class = tcx.extract_const_message(&class).unwrap();
+        let msg = fargs.remove(0);
+        let msg = tcx.extract_const_message(&msg).unwrap();
+        let target = target.unwrap();
+        let caller_loc = tcx.codegen_caller_span(&span);
+
+        if !PropertyClass::is_valid_user_class(&class) {
+            let mut diag = tcx.tcx.sess.struct_span_err(
+                span.unwrap(),
+                format!("invalid property class `{class}` in `kani::assert_class!`"),
+            );
+            diag.help(
+                "use lowercase ASCII letters, digits and `_`, and a name that isn't the class of \
+                one of Kani's own checks, e.g., `assertion` or `cover`",
+            );
+            diag.emit();
+            class = PropertyClass::Assertion.as_str().to_string();
+        }
+
+        let (msg, reach_stmt) = tcx.codegen_reachability_check(msg, span);
+
+        let (tmp, decl) = tcx.decl_temp_variable(cond.typ().clone(), Some(cond), caller_loc);
+        Stmt::block(
+            vec![
+                reach_stmt,
+                decl,
+                tcx.codegen_assert_assume_user_class(tmp, &class, &msg, caller_loc),
+                Stmt::goto(tcx.current_fn().find_label(&target), caller_loc),
+            ],
+            caller_loc,
+        )
+    }
+}
+
+/// A hook for Kani's `loop_invariant` function, which the `#[kani::loop_invariant]` attribute
+/// inserts at the beginning of the condition of the annotated loop. The function takes a
+/// reference to a closure that evaluates the invariant.
+///
+/// The call itself always evaluates to `true`. When loop contracts are enabled, the call to the
+/// function (whose body calls the closure) is registered as the invariant of the loop, and it
+/// gets attached to the back edge of the loop once it is compiled.
+struct LoopInvariantRegister;
+impl<'tcx> GotocHook<'tcx> for LoopInvariantRegister {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniLoopInvariant")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        instance: Instance<'tcx>,
+        fargs: Vec<Expr>,
+        assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 1);
+        let loc = tcx.codegen_span_option(span);
+        if tcx.queries.args().unstable_features.contains(&"loop-contracts".to_string()) {
+            let func_exp = tcx.codegen_func_expr(instance, span.as_ref());
+            let inv = func_exp.call(fargs).cast_to(Type::bool());
+            tcx.current_fn_mut().register_loop_invariant(inv);
+            check_loop_latches(tcx, span);
+        }
+        Stmt::block(
+            vec![
+                tcx.codegen_expr_to_place(&assign_to, Expr::c_true()),
+                Stmt::goto(tcx.current_fn().find_label(&target.unwrap()), loc),
+            ],
+            loc,
+        )
+    }
+}
+
+/// Report an error if the loop whose contracts were just registered in the current block never
+/// jumps back to it, since the contracts are attached to the back edges of the loop.
+fn check_loop_latches(tcx: &GotocCtx, span: Option<Span>) {
+    let head = tcx.current_fn().current_bb().unwrap();
+    if tcx.current_fn().loop_latches(&head).is_empty() {
+        tcx.tcx.sess.span_err(
+            span.unwrap(),
+            "couldn't find the back edge of the loop to attach its contracts to",
+        );
+    }
+}
+
+/// A hook for Kani's `loop_decreases` function, which replaces `loop_invariant` in the condition
+/// of a loop annotated with `#[kani::decreases]`. The function takes a reference to a closure
+/// that evaluates the invariant (which is `true` if the loop has none), and a reference to a
+/// closure that evaluates the measure of the loop.
+///
+/// The invariant is registered like in [LoopInvariantRegister]. The measure is evaluated by a
+/// call to `loop_measure`, and if it is a tuple, each of its fields is a component of the
+/// measure, compared in lexicographic order.
+struct LoopDecreasesRegister;
+impl<'tcx> GotocHook<'tcx> for LoopDecreasesRegister {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniLoopDecreases")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        instance: Instance<'tcx>,
+        fargs: Vec<Expr>,
+        assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 2);
+        let loc = tcx.codegen_span_option(span);
+        if tcx.queries.args().unstable_features.contains(&"loop-contracts".to_string()) {
+            let measure_fn =
+                tcx.tcx.get_diagnostic_item(Symbol::intern("KaniLoopMeasure")).unwrap();
+            let measure_args = tcx.tcx.mk_args(&instance.args[1..]);
+            let measure_instance = Instance::new(measure_fn, measure_args);
+            let measure =
+                tcx.codegen_func_expr(measure_instance, span.as_ref()).call(vec![fargs[1].clone()]);
+            let measure_ty = instance.args.type_at(2);
+            let measures = match measure_ty.kind() {
+                TyKind::Tuple(fields) => (0..fields.len())
+                    .map(|idx| {
+                        measure.clone().member(GotocCtx::tuple_fld_name(idx), &tcx.symbol_table)
+                    })
+                    .collect(),
+                _ => vec![measure],
+            };
+            if measures.iter().all(|measure| measure.typ().is_integer()) {
+                let func_exp = tcx.codegen_func_expr(instance, span.as_ref());
+                let inv = func_exp.call(fargs).cast_to(Type::bool());
+                tcx.current_fn_mut().register_loop_invariant(inv);
+                tcx.current_fn_mut().register_loop_decreases(measures);
+                check_loop_latches(tcx, span);
+            } else {
+                tcx.tcx.sess.span_err(
+                    span.unwrap(),
+                    format!(
+                        "the measure of a loop must be an integer or a tuple of integers, \
+                        found `{measure_ty}`"
+                    ),
+                );
+            }
+        }
+        Stmt::block(
+            vec![
+                tcx.codegen_expr_to_place(&assign_to, Expr::c_true()),
+                Stmt::goto(tcx.current_fn().find_label(&target.unwrap()), loc),
+            ],
+            loc,
+        )
+    }
+}
+
+/// The conditions of a function contract are checked (or assumed) once per instantiation of
+/// the function they are attached to. If the current function was generated from a contract
+/// and it has generic arguments, return them in the form `T = u8, U = bool` so the assertions of
+/// each instantiation can be told apart.
+fn contract_instantiation(tcx: &GotocCtx) -> Option<String> {
+    let instance = tcx.current_fn().instance();
+    let def_id = instance.def_id();
+    if instance.args.is_empty() || !is_function_contract_generated(tcx.tcx, def_id) {
+        return None;
+    }
+    let generics = tcx.tcx.generics_of(def_id);
+    let params = instance
+        .args
+        .iter()
+        .enumerate()
+        .filter(|(_, arg)| !matches!(arg.unpack(), GenericArgKind::Lifetime(_)))
+        .map(|(idx, arg)| format!("{} = {arg}", generics.param_at(idx, tcx.tcx).name))
+        .collect::<Vec<_>>();
+    (!params.is_empty()).then(|| params.join(", "))
+}
+
 struct Assert;
 impl<'tcx> GotocHook<'tcx> for Assert {
     fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
@@ -136,7 +494,10 @@ fn handle(
         assert_eq!(fargs.len(), 2);
         let cond = fargs.remove(0).cast_to(Type::bool());
         let msg = fargs.remove(0);
-        let msg = tcx.extract_const_message(&msg).unwrap();
+        let mut msg = tcx.extract_const_message(&msg).unwrap();
+        if let Some(instantiation) = contract_instantiation(tcx) {
+            msg = format!("{msg} (with {instantiation})");
+        }
         let target = target.unwrap();
         let caller_loc = tcx.codegen_caller_span(&span);
 
@@ -269,6 +630,9 @@ fn handle(
 /// second_var = second;
 /// count_var == 0 && first_var != NULL && second_var != NULL ? 0 : memcmp(first_var, second_var, count_var)
 /// ```
+///
+/// If `count` is a small enough non-zero constant, the bytes are compared as two bit vectors
+/// instead, so the comparison doesn't have to be unwound.
 pub struct MemCmp;
 
 impl<'tcx> GotocHook<'tcx> for MemCmp {
@@ -291,6 +655,17 @@ fn handle(
         let first = fargs.remove(0);
         let second = fargs.remove(0);
         let count = fargs.remove(0);
+        if let Some(count) = constant_byte_count(&count) {
+            let place_type = tcx.codegen_ty(tcx.place_ty(&assign_to));
+            let cmp = tcx.codegen_memcmp_bitvector(first, second, count);
+            return Stmt::block(
+                vec![
+                    tcx.codegen_expr_to_place(&assign_to, cmp.cast_to(place_type)),
+                    Stmt::goto(tcx.current_fn().find_label(&target), loc),
+                ],
+                loc,
+            );
+        }
         let (count_var, count_decl) = tcx.decl_temp_variable(count.typ().clone(), Some(count), loc);
         let (first_var, first_decl) = tcx.decl_temp_variable(first.typ().clone(), Some(first), loc);
         let (second_var, second_decl) =
@@ -368,17 +743,208 @@ fn handle(
     }
 }
 
+/// A hook for Kani's `contract_reentry_flag` function, which the recursion wrapper of a function
+/// contract uses to find out whether the function is being re-entered. The function takes a
+/// reference to a closure that is defined in the wrapper and returns a pointer to a boolean flag.
+///
+/// The closure type carries the generic arguments of the wrapper, so every instantiation of
+/// the wrapper calls a different instance of this function. We derive the name of the global
+/// flag from the instance, which gives each instantiation of a generic function its own flag.
+struct ContractReentryFlag;
+impl<'tcx> GotocHook<'tcx> for ContractReentryFlag {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniContractReentryFlag")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        instance: Instance<'tcx>,
+        fargs: Vec<Expr>,
+        assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 1);
+        let loc = tcx.codegen_span_option(span);
+        let flag_name = format!("{}::REENTRY", tcx.symbol_name(instance));
+        let flag = tcx.ensure_global_var(flag_name, false, Type::c_bool(), loc, |_, var| {
+            Some(Stmt::assign(var, Expr::c_false(), Location::none()))
+        });
+        Stmt::block(
+            vec![
+                tcx.codegen_expr_to_place(&assign_to, flag.address_of()),
+                Stmt::goto(tcx.current_fn().find_label(&target.unwrap()), loc),
+            ],
+            loc,
+        )
+    }
+}
+
+/// A hook for Kani's `contract_measure` function, which the recursion wrapper of a function
+/// contract with a `decreases` clause uses to store the measure of the outermost call. Like
+/// [ContractReentryFlag], the name of the global slot is derived from the instance, so every
+/// instantiation of the wrapper has its own slot.
+struct ContractMeasure;
+impl<'tcx> GotocHook<'tcx> for ContractMeasure {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniContractMeasure")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        instance: Instance<'tcx>,
+        fargs: Vec<Expr>,
+        assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 1);
+        let loc = tcx.codegen_span_option(span);
+        let slot_name = format!("{}::MEASURE", tcx.symbol_name(instance));
+        let slot_type = tcx.codegen_ty(instance.args.type_at(1));
+        // The slot is always written by the outermost call before it is read.
+        let slot = tcx.ensure_global_var(slot_name, false, slot_type, loc, |_, _| None);
+        Stmt::block(
+            vec![
+                tcx.codegen_expr_to_place(&assign_to, slot.address_of()),
+                Stmt::goto(tcx.current_fn().find_label(&target.unwrap()), loc),
+            ],
+            loc,
+        )
+    }
+}
+
+/// A hook for Kani's quantifiers, `kani::forall!` and `kani::exists!` (declared in
+/// `library/kani/src/lib.rs`), which call `kani_forall` and `kani_exists` from
+/// `library/kani/src/internal.rs`. The call is replaced by a quantified expression over the
+/// range, where the predicate closure is inlined.
+struct Quantifier;
+
+impl<'tcx> GotocHook<'tcx> for Quantifier {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniForall")
+            || matches_function(tcx, instance, "KaniExists")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        instance: Instance<'tcx>,
+        fargs: Vec<Expr>,
+        assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        let kind = if matches_function(tcx.tcx, instance, "KaniForall") {
+            QuantifierKind::Forall
+        } else {
+            QuantifierKind::Exists
+        };
+        tcx.codegen_quantifier(kind, instance, fargs, &assign_to, target.unwrap(), span)
+    }
+}
+
+/// A hook for `read_ok` and `write_ok` from `library/kani/src/mem.rs`, which take a pointer and a
+/// size in bytes. The call is replaced by CBMC's `__CPROVER_r_ok` or `__CPROVER_w_ok` predicate,
+/// which holds if the pointer points to an allocated object with at least that many bytes left.
+struct MemoryAccessOk;
+
+impl<'tcx> GotocHook<'tcx> for MemoryAccessOk {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniReadOk")
+            || matches_function(tcx, instance, "KaniWriteOk")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 2);
+        let loc = tcx.codegen_span_option(span);
+        let target = target.unwrap();
+        let ptr = fargs.remove(0).cast_to(Type::void_pointer());
+        let size = fargs.remove(0).cast_to(Type::c_size_t());
+        let access_ok = if matches_function(tcx.tcx, instance, "KaniReadOk") {
+            ptr.r_ok(size)
+        } else {
+            ptr.w_ok(size)
+        };
+        let pe = unwrap_or_return_codegen_unimplemented_stmt!(tcx, tcx.codegen_place(&assign_to))
+            .goto_expr;
+        Stmt::block(
+            vec![
+                pe.assign(access_ok.cast_to(Type::c_bool()), loc),
+                Stmt::goto(tcx.current_fn().find_label(&target), loc),
+            ],
+            loc,
+        )
+    }
+}
+
+/// A hook for `spawn_thread` from `library/kani/src/internal.rs`, which takes the entry point of
+/// a thread and the pointer it is called with. The call is replaced by CBMC's `start_thread`
+/// construct, so the entry point runs in a new thread whose steps are interleaved with those of
+/// the other threads in every possible way.
+struct SpawnThread;
+
+impl<'tcx> GotocHook<'tcx> for SpawnThread {
+    fn hook_applies(&self, tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+        matches_function(tcx, instance, "KaniSpawnThread")
+    }
+
+    fn handle(
+        &self,
+        tcx: &mut GotocCtx<'tcx>,
+        _instance: Instance<'tcx>,
+        mut fargs: Vec<Expr>,
+        _assign_to: Place<'tcx>,
+        target: Option<BasicBlock>,
+        span: Option<Span>,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 2);
+        let loc = tcx.codegen_span_option(span);
+        let entry = fargs.remove(0);
+        let arg = fargs.remove(0);
+        Stmt::block(
+            vec![
+                Stmt::start_thread(entry.dereference().call(vec![arg]).as_stmt(loc), loc),
+                Stmt::goto(tcx.current_fn().find_label(&target.unwrap()), loc),
+            ],
+            loc,
+        )
+    }
+}
+
 pub fn fn_hooks<'tcx>() -> GotocHooks<'tcx> {
     GotocHooks {
         hooks: vec![
             Rc::new(Panic),
             Rc::new(Assume),
+            Rc::new(AssumeBound),
+            Rc::new(AssumePredicate),
+            Rc::new(LoopInvariantRegister),
+            Rc::new(LoopDecreasesRegister),
             Rc::new(Assert),
+            Rc::new(AssertClass),
+            Rc::new(CheckUtf8),
+            Rc::new(NamedCheck),
             Rc::new(Cover),
             Rc::new(Nondet),
             Rc::new(RustAlloc),
             Rc::new(MemCmp),
             Rc::new(UntrackedDeref),
+            Rc::new(ContractReentryFlag),
+            Rc::new(ContractMeasure),
+            Rc::new(Quantifier),
+            Rc::new(MemoryAccessOk),
+            Rc::new(SpawnThread),
         ],
     }
 }
@@ -19,10 +19,16 @@
 #[cfg(feature = "cprover")]
 use crate::codegen_cprover_gotoc::GotocCodegenBackend;
 use crate::kani_middle::attributes::is_proof_harness;
-use crate::kani_middle::check_crate_items;
-use crate::kani_middle::metadata::gen_proof_metadata;
+use crate::kani_middle::concrete_inputs::concrete_input_names;
+use crate::kani_middle::const_fn_checks::const_fn_check_targets;
+use crate::kani_middle::drop_checks::drop_check_targets;
+use crate::kani_middle::metadata::{
+    apply_package_config, gen_proof_metadata, gen_synthesized_metadata,
+};
 use crate::kani_middle::reachability::filter_crate_items;
-use crate::kani_middle::stubbing::{self, harness_stub_map};
+use crate::kani_middle::stubbing::{self, harness_stub_map, Stubs};
+use crate::kani_middle::trait_laws::trait_law_targets;
+use crate::kani_middle::{check_crate_items, synthesized_harness};
 use crate::kani_queries::QueryDb;
 use crate::session::init_session;
 use clap::Parser;
@@ -69,9 +75,6 @@ fn backend(queries: Arc<Mutex<QueryDb>>) -> Box<CodegenBackend> {
 /// A stable (across compilation sessions) identifier for the harness function.
 type HarnessId = DefPathHash;
 
-/// A set of stubs.
-type Stubs = BTreeMap<DefPathHash, DefPathHash>;
-
 #[derive(Clone, Debug)]
 struct HarnessInfo {
     pub metadata: HarnessMetadata,
@@ -224,6 +227,33 @@ pub fn run(&mut self, orig_args: Vec<String>) -> Result<(), ErrorGuaranteed> {
 
     /// Set up the next compilation stage after a `rustc` run.
     fn next_stage(&mut self) {
+        if let CompilationStage::CodegenNoStubs { all_harnesses, .. }
+        | CompilationStage::CodegenWithStubs { all_harnesses, .. } = &mut self.stage
+        {
+            // Record the contracts, the stub usage, the havocked assembly and the assumptions found
+            // while generating code for the harnesses.
+            let queries = self.queries.lock().unwrap();
+            for (harness, contract) in &queries.harness_contracts {
+                if let Some(info) = all_harnesses.get_mut(harness) {
+                    info.metadata.contract = Some(contract.clone());
+                }
+            }
+            for (harness, stub_usage) in &queries.harness_stub_usage {
+                if let Some(info) = all_harnesses.get_mut(harness) {
+                    info.metadata.stub_usage = stub_usage.clone();
+                }
+            }
+            for (harness, havocked_asm) in &queries.harness_havocked_asm {
+                if let Some(info) = all_harnesses.get_mut(harness) {
+                    info.metadata.havocked_asm = havocked_asm.clone();
+                }
+            }
+            for (harness, assumptions) in &queries.harness_assumptions {
+                if let Some(info) = all_harnesses.get_mut(harness) {
+                    info.metadata.assumptions = assumptions.clone();
+                }
+            }
+        }
         self.stage = match &mut self.stage {
             CompilationStage::Init => {
                 // This may occur when user passes arguments like --version or --help.
@@ -280,16 +310,46 @@ fn process_harnesses(&self, tcx: TyCtxt) -> CompilationStage {
         {
             let base_filename = tcx.output_filenames(()).output_path(OutputType::Object);
             let harnesses = filter_crate_items(tcx, |_, def_id| is_proof_harness(tcx, def_id));
-            let all_harnesses = harnesses
+            let args = self.queries.lock().unwrap().args().clone();
+            let mut all_harnesses = harnesses
                 .into_iter()
                 .map(|harness| {
                     let def_id = harness.def_id();
                     let def_path = tcx.def_path_hash(def_id);
-                    let metadata = gen_proof_metadata(tcx, def_id, &base_filename);
+                    let mut metadata = gen_proof_metadata(tcx, def_id, &base_filename);
+                    apply_package_config(&args, &mut metadata.attributes);
+                    if args.use_abstractions {
+                        let stubs = stubbing::abstraction_stubs(tcx, def_id.expect_local());
+                        metadata.attributes.stubs.extend(stubs);
+                    }
+                    if args.race_checks {
+                        let stubs = stubbing::thread_stubs(tcx, def_id.expect_local());
+                        metadata.attributes.stubs.extend(stubs);
+                    }
+                    let specs = &args.contract_specs;
+                    if !specs.is_empty() {
+                        let stubs =
+                            stubbing::contract_spec_stubs(tcx, def_id.expect_local(), specs);
+                        metadata.attributes.stubs.extend(stubs);
+                    }
+                    metadata.concrete_inputs =
+                        concrete_input_names(tcx, tcx.optimized_mir(def_id), &args.concrete_inputs);
                     let stub_map = harness_stub_map(tcx, def_id, &metadata);
                     (def_path, HarnessInfo { metadata, stub_map })
                 })
                 .collect::<HashMap<_, _>>();
+            // Harnesses synthesized by Kani are identified by the function they check.
+            let drop_checks = args.drop_checks.then(|| drop_check_targets(tcx));
+            let trait_laws = args.trait_laws.then(|| trait_law_targets(tcx));
+            let const_fn_checks = args.const_fn_checks.then(|| const_fn_check_targets(tcx));
+            let targets =
+                drop_checks.into_iter().chain(trait_laws).chain(const_fn_checks).flatten();
+            all_harnesses.extend(targets.map(|target| {
+                let harness = synthesized_harness(tcx, &args, target).unwrap();
+                let metadata = gen_synthesized_metadata(tcx, target, harness, &base_filename);
+                let info = HarnessInfo { metadata, stub_map: Stubs::default() };
+                (tcx.def_path_hash(target), info)
+            }));
 
             let (no_stubs, with_stubs): (Vec<_>, Vec<_>) =
                 if self.queries.lock().unwrap().args().stubbing_enabled {
@@ -437,7 +497,7 @@ fn metadata_output_path(tcx: TyCtxt) -> PathBuf {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use kani_metadata::{HarnessAttributes, HarnessMetadata};
+    use kani_metadata::{HarnessAssumptions, HarnessAttributes, HarnessMetadata};
     use rustc_data_structures::fingerprint::Fingerprint;
     use rustc_hir::definitions::DefPathHash;
     use std::collections::HashMap;
@@ -458,6 +518,11 @@ fn mock_metadata(name: String, krate: String) -> HarnessMetadata {
             original_start_line: 10,
             original_end_line: 20,
             goto_file: None,
+            contract: None,
+            stub_usage: vec![],
+            havocked_asm: vec![],
+            concrete_inputs: vec![],
+            assumptions: HarnessAssumptions::default(),
             attributes: HarnessAttributes::default(),
         }
     }
@@ -479,9 +544,9 @@ fn test_group_by_stubs_works() {
         let stub_3 = (mock_next_id(), mock_next_id());
         let stub_4 = (stub_3.0, mock_next_id());
 
-        let set_1 = Stubs::from([stub_1, stub_2, stub_3]);
-        let set_2 = Stubs::from([stub_1, stub_2, stub_4]);
-        let set_3 = Stubs::from([stub_1, stub_3, stub_2]);
+        let set_1 = Stubs { fns: BTreeMap::from([stub_1, stub_2, stub_3]), ..Default::default() };
+        let set_2 = Stubs { fns: BTreeMap::from([stub_1, stub_2, stub_4]), ..Default::default() };
+        let set_3 = Stubs { fns: BTreeMap::from([stub_1, stub_3, stub_2]), ..Default::default() };
         assert_eq!(set_1, set_3);
         assert_ne!(set_1, set_2);
 
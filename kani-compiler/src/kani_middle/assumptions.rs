@@ -0,0 +1,54 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module finds the assumptions that the code reachable from a harness makes, so that they
+//! can be audited along with its verification result.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use kani_metadata::HarnessAssumptions;
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::mir::TerminatorKind;
+use rustc_middle::ty::{self, EarlyBinder, Instance, ParamEnv, TyCtxt};
+use rustc_span::Symbol;
+
+use crate::args::Arguments;
+use crate::kani_middle::span_location;
+
+/// The calls to `kani::assume` and to the foreign functions modeled by `--ffi-policy` in
+/// `items`. The calls of the Kani library itself, e.g. the ones that restrict the values of
+/// `kani::any` to valid values, are left out.
+pub fn harness_assumptions<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    args: &Arguments,
+    items: &[MonoItem<'tcx>],
+) -> HarnessAssumptions {
+    let mut assumes = BTreeSet::new();
+    let mut foreign_functions = BTreeMap::new();
+    for item in items {
+        let MonoItem::Fn(instance) = item else { continue };
+        let body = tcx.instance_mir(instance.def);
+        for block in body.basic_blocks.iter() {
+            let TerminatorKind::Call { func, .. } = &block.terminator().kind else { continue };
+            let callee_ty = instance.instantiate_mir_and_normalize_erasing_regions(
+                tcx,
+                ParamEnv::reveal_all(),
+                EarlyBinder::bind(func.ty(body, tcx)),
+            );
+            let ty::FnDef(callee, _) = *callee_ty.kind() else { continue };
+            if tcx.is_diagnostic_item(Symbol::intern("KaniAssume"), callee) {
+                let caller = instance.def_id();
+                if tcx.crate_name(caller.krate).as_str() != "kani" {
+                    let location = span_location(tcx, block.terminator().source_info.span);
+                    assumes.insert(format!("{} at {location}", tcx.def_path_str(caller)));
+                }
+            } else if tcx.is_foreign_item(callee) && tcx.generics_of(callee).count() == 0 {
+                // The generic foreign items are the intrinsics, which aren't modeled by a policy.
+                let name = tcx.symbol_name(Instance::mono(tcx, callee)).name;
+                if let Some((_, policy)) = args.ffi_policies.iter().find(|(link, _)| link == name) {
+                    foreign_functions.insert(name.to_string(), *policy);
+                }
+            }
+        }
+    }
+    HarnessAssumptions { assumes: assumes.into_iter().collect(), foreign_functions }
+}
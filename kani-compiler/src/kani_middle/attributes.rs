@@ -4,13 +4,17 @@
 
 use std::collections::BTreeMap;
 
-use kani_metadata::{CbmcSolver, HarnessAttributes, Stub};
+use kani_metadata::{CbmcPathStrategy, CbmcSolver, HarnessAttributes, Stub, TraitImplStub};
 use rustc_ast::{
     attr, AttrArgs, AttrArgsEq, AttrKind, Attribute, ExprKind, LitKind, MetaItem, MetaItemKind,
-    NestedMetaItem,
+    MetaItemLit, NestedMetaItem,
 };
 use rustc_errors::ErrorGuaranteed;
-use rustc_hir::{def::DefKind, def_id::DefId};
+use rustc_hir::{
+    def::DefKind,
+    def_id::{DefId, LocalDefId},
+    HirId,
+};
 use rustc_middle::ty::{Instance, TyCtxt, TyKind};
 use rustc_session::Session;
 use rustc_span::{Span, Symbol};
@@ -27,10 +31,16 @@ enum KaniAttributeKind {
     Proof,
     ShouldPanic,
     Solver,
+    PathStrategy,
     Stub,
+    /// Stubs all the methods of a trait implementation, e.g.
+    /// `#[kanitool::stub_trait_impl(Read, File, MockFile)]`.
+    StubTraitImpl,
     /// Attribute used to mark unstable APIs.
     Unstable,
     Unwind,
+    /// Time limit for verifying a harness, e.g. `#[kanitool::timeout(60)]`.
+    Timeout,
     /// A sound [`Self::Stub`] that replaces a function by a stub generated from
     /// its contract.
     StubVerified,
@@ -48,6 +58,12 @@ enum KaniAttributeKind {
     /// Attribute on a function that was auto-generated from expanding a
     /// function contract.
     IsContractGenerated,
+    /// Attribute on the check function of a contract that lists the arguments
+    /// pointing to memory the function may modify, e.g.
+    /// `#[kanitool::modifies(counter)]`.
+    Modifies,
+    /// Disables the lossy cast checks in the statement or item that it's attached to.
+    AllowLossyCast,
 }
 
 impl KaniAttributeKind {
@@ -57,14 +73,19 @@ pub fn is_harness_only(self) -> bool {
             KaniAttributeKind::Proof
             | KaniAttributeKind::ShouldPanic
             | KaniAttributeKind::Solver
+            | KaniAttributeKind::PathStrategy
             | KaniAttributeKind::Stub
+            | KaniAttributeKind::StubTraitImpl
             | KaniAttributeKind::ProofForContract
             | KaniAttributeKind::StubVerified
-            | KaniAttributeKind::Unwind => true,
+            | KaniAttributeKind::Unwind
+            | KaniAttributeKind::Timeout => true,
             KaniAttributeKind::Unstable
             | KaniAttributeKind::ReplacedWith
             | KaniAttributeKind::CheckedWith
-            | KaniAttributeKind::IsContractGenerated => false,
+            | KaniAttributeKind::Modifies
+            | KaniAttributeKind::IsContractGenerated
+            | KaniAttributeKind::AllowLossyCast => false,
         }
     }
 
@@ -209,6 +230,19 @@ pub fn replaced_with(&self) -> Option<Result<Symbol, ErrorGuaranteed>> {
             .map(|target| expect_key_string_value(self.tcx.sess, target))
     }
 
+    /// Extract the part of the contract of this function that has to be
+    /// enforced by the backend, i.e. the names of the arguments that point to
+    /// memory this function may modify, as listed by
+    /// `#[kanitool::modifies(..)]`.
+    ///
+    /// `None` indicates this function has no such attribute.
+    pub fn modifies_contract(&self) -> Option<GFnContract<Symbol>> {
+        let attrs = self.map.get(&KaniAttributeKind::Modifies)?;
+        Some(GFnContract::new(
+            attrs.iter().flat_map(|attr| parse_modifies(self.tcx, attr)).collect(),
+        ))
+    }
+
     /// Resolve a function that is known to reside in the same module as the one
     /// these attributes belong to (`self.item`).
     fn resolve_sibling(&self, path_str: &str) -> Result<DefId, ResolveError<'tcx>> {
@@ -238,7 +272,7 @@ pub(super) fn check_attributes(&self) {
                 KaniAttributeKind::ShouldPanic => {
                     expect_single(self.tcx, kind, &attrs);
                     attrs.iter().for_each(|attr| {
-                        expect_no_args(self.tcx, kind, attr);
+                        parse_expected_panic(self.tcx, attr);
                     })
                 }
                 KaniAttributeKind::Solver => {
@@ -247,15 +281,30 @@ pub(super) fn check_attributes(&self) {
                         parse_solver(self.tcx, attr);
                     })
                 }
+                KaniAttributeKind::PathStrategy => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| {
+                        parse_path_strategy(self.tcx, attr);
+                    })
+                }
                 KaniAttributeKind::Stub => {
                     parse_stubs(self.tcx, self.item, attrs);
                 }
+                KaniAttributeKind::StubTraitImpl => {
+                    parse_trait_impl_stubs(self.tcx, self.item, attrs);
+                }
                 KaniAttributeKind::Unwind => {
                     expect_single(self.tcx, kind, &attrs);
                     attrs.iter().for_each(|attr| {
                         parse_unwind(self.tcx, attr);
                     })
                 }
+                KaniAttributeKind::Timeout => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| {
+                        parse_timeout(self.tcx, attr);
+                    })
+                }
                 KaniAttributeKind::Proof => {
                     if self.map.contains_key(&KaniAttributeKind::ProofForContract) {
                         local_error(
@@ -277,7 +326,9 @@ pub(super) fn check_attributes(&self) {
                     expect_single(self.tcx, kind, &attrs);
                 }
                 KaniAttributeKind::StubVerified => {
-                    expect_single(self.tcx, kind, &attrs);
+                    // A harness may replace several functions by their contracts. Each
+                    // target is resolved, and its errors reported, when the harness
+                    // attributes are collected.
                 }
                 KaniAttributeKind::CheckedWith | KaniAttributeKind::ReplacedWith => {
                     self.expect_maybe_one(kind)
@@ -288,6 +339,12 @@ pub(super) fn check_attributes(&self) {
                     // to communicate with one another. So by the time it gets
                     // here we don't care if it's valid or not.
                 }
+                KaniAttributeKind::Modifies => attrs.iter().for_each(|attr| {
+                    parse_modifies(self.tcx, attr);
+                }),
+                KaniAttributeKind::AllowLossyCast => attrs.iter().for_each(|attr| {
+                    expect_no_args(self.tcx, kind, attr);
+                }),
             }
         }
     }
@@ -370,16 +427,29 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
         assert!(self.is_harness());
         self.map.iter().fold(HarnessAttributes::default(), |mut harness, (kind, attributes)| {
             match kind {
-                KaniAttributeKind::ShouldPanic => harness.should_panic = true,
+                KaniAttributeKind::ShouldPanic => {
+                    harness.should_panic = true;
+                    harness.expected_panic = parse_expected_panic(self.tcx, attributes[0]);
+                }
                 KaniAttributeKind::Solver => {
                     harness.solver = parse_solver(self.tcx, attributes[0]);
                 }
+                KaniAttributeKind::PathStrategy => {
+                    harness.path_strategy = parse_path_strategy(self.tcx, attributes[0]);
+                }
                 KaniAttributeKind::Stub => {
                     harness.stubs.extend_from_slice(&parse_stubs(self.tcx, self.item, attributes));
                 }
+                KaniAttributeKind::StubTraitImpl => {
+                    harness.trait_impl_stubs =
+                        parse_trait_impl_stubs(self.tcx, self.item, attributes);
+                }
                 KaniAttributeKind::Unwind => {
                     harness.unwind_value = parse_unwind(self.tcx, attributes[0])
                 }
+                KaniAttributeKind::Timeout => {
+                    harness.timeout = parse_timeout(self.tcx, attributes[0])
+                }
                 KaniAttributeKind::Proof => harness.proof = true,
                 KaniAttributeKind::ProofForContract => self.handle_proof_for_contract(&mut harness),
                 KaniAttributeKind::StubVerified => self.handle_stub_verified(&mut harness),
@@ -387,8 +457,12 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
                     // Internal attribute which shouldn't exist here.
                     unreachable!()
                 }
+                KaniAttributeKind::AllowLossyCast => {
+                    // Only affects the code generation of the harness body.
+                }
                 KaniAttributeKind::CheckedWith
                 | KaniAttributeKind::IsContractGenerated
+                | KaniAttributeKind::Modifies
                 | KaniAttributeKind::ReplacedWith => {
                     self.tcx.sess.span_err(self.tcx.def_span(self.item), format!("Contracts are not supported on harnesses. (Found the kani-internal contract attribute `{}`)", kind.as_ref()));
                 }
@@ -420,6 +494,7 @@ fn handle_proof_for_contract(&self, harness: &mut HarnessAttributes) {
             return;
         };
         harness.stubs.push(self.stub_for_relative_item(name, replacement_name));
+        harness.for_contract = Some(contract_target_name(self.tcx, id));
     }
 
     fn handle_stub_verified(&self, harness: &mut HarnessAttributes) {
@@ -454,7 +529,8 @@ fn handle_stub_verified(&self, harness: &mut HarnessAttributes) {
                 Some(Ok(replacement_name)) => replacement_name,
                 Some(Err(_)) => continue,
             };
-            harness.stubs.push(self.stub_for_relative_item(name, replacement_name))
+            harness.stubs.push(self.stub_for_relative_item(name, replacement_name));
+            harness.verified_stubs.push(contract_target_name(self.tcx, def_id));
         }
     }
 
@@ -530,6 +606,26 @@ pub fn test_harness_name(tcx: TyCtxt, def_id: DefId) -> String {
     parse_str_value(&marker).unwrap()
 }
 
+/// Whether the code at `span` in the body of `def_id` opted out of the lossy cast checks with
+/// `#[kanitool::allow_lossy_cast]`. The attribute can be attached to a statement that contains
+/// the code, to the function itself, or to an item that encloses the function (e.g. an `impl`
+/// block or a module).
+pub fn allows_lossy_cast(tcx: TyCtxt, def_id: LocalDefId, span: Span) -> bool {
+    let hir = tcx.hir();
+    let is_allow = |attrs: &[Attribute]| {
+        attrs.iter().any(|attr| attr_kind(tcx, attr) == Some(KaniAttributeKind::AllowLossyCast))
+    };
+    // Code expanded from a macro belongs to the statement where the macro is invoked.
+    let span = span.source_callsite();
+    let hir_id = hir.local_def_id_to_hir_id(def_id);
+    let owner = hir_id.owner;
+    // The attributes of the function and of the statements in its body.
+    let in_owner = tcx.hir_attrs(owner).map.iter().any(|(local_id, attrs)| {
+        is_allow(attrs) && hir.span_with_body(HirId { owner, local_id: *local_id }).contains(span)
+    });
+    in_owner || hir.parent_owner_iter(hir_id).any(|(parent, _)| is_allow(hir.attrs(parent.into())))
+}
+
 /// Expect the contents of this attribute to be of the format #[attribute =
 /// "value"] and return the `"value"`.
 fn expect_key_string_value(
@@ -680,6 +776,66 @@ fn parse_unwind(tcx: TyCtxt, attr: &Attribute) -> Option<u32> {
     }
 }
 
+/// Return the number of seconds from a `timeout` attribute.
+fn parse_timeout(tcx: TyCtxt, attr: &Attribute) -> Option<u64> {
+    match parse_integer(attr).and_then(|secs| u64::try_from(secs).ok()) {
+        Some(secs) if secs > 0 => Some(secs),
+        _ => {
+            tcx.sess.span_err(
+                attr.span,
+                "invalid argument for `timeout` attribute, expected a number of seconds greater \
+                than 0",
+            );
+            None
+        }
+    }
+}
+
+/// The name under which the driver matches the functions used in `stub_verified` with the
+/// ones checked by `proof_for_contract`. The path is prefixed with the crate name, since the
+/// harnesses may belong to different crates.
+fn contract_target_name(tcx: TyCtxt, def_id: DefId) -> String {
+    format!("{}{}", tcx.crate_name(def_id.krate), tcx.def_path(def_id).to_string_no_crate_verbose())
+}
+
+/// The contract of a function, generic over the representation of its clauses.
+///
+/// Preconditions and postconditions are compiled into the check and replace
+/// functions by the contract macros, so only the assigns clause (the targets
+/// of `modifies`) is kept here.
+#[derive(Debug, Clone)]
+pub struct GFnContract<C> {
+    assigns: Vec<C>,
+}
+
+impl<C> GFnContract<C> {
+    pub fn new(assigns: Vec<C>) -> Self {
+        Self { assigns }
+    }
+
+    /// The memory the function may write to.
+    pub fn assigns(&self) -> &[C] {
+        &self.assigns
+    }
+}
+
+/// Return the argument names listed by a `#[kanitool::modifies(..)]` attribute.
+fn parse_modifies(tcx: TyCtxt, attr: &Attribute) -> Vec<Symbol> {
+    let args = attr.meta_item_list().unwrap_or_default();
+    args.iter()
+        .filter_map(|arg| {
+            let name = arg.ident().filter(|_| arg.is_word()).map(|ident| ident.name);
+            if name.is_none() {
+                tcx.sess.span_err(
+                    arg.span(),
+                    "invalid argument for `modifies`, expected the name of an argument",
+                );
+            }
+            name
+        })
+        .collect()
+}
+
 fn parse_stubs(tcx: TyCtxt, harness: DefId, attributes: &[&Attribute]) -> Vec<Stub> {
     let current_module = tcx.parent_module_from_def_id(harness.expect_local());
     let check_resolve = |attr: &Attribute, name: &str| {
@@ -690,7 +846,7 @@ fn parse_stubs(tcx: TyCtxt, harness: DefId, attributes: &[&Attribute]) -> Vec<St
     };
     attributes
         .iter()
-        .filter_map(|attr| match parse_paths(attr) {
+        .filter_map(|attr| match parse_stub_paths(attr) {
             Ok(paths) => match paths.as_slice() {
                 [orig, replace] => {
                     check_resolve(attr, orig);
@@ -719,6 +875,46 @@ fn parse_stubs(tcx: TyCtxt, harness: DefId, attributes: &[&Attribute]) -> Vec<St
         .collect()
 }
 
+fn parse_trait_impl_stubs(
+    tcx: TyCtxt,
+    harness: DefId,
+    attributes: &[&Attribute],
+) -> Vec<TraitImplStub> {
+    let current_module = tcx.parent_module_from_def_id(harness.expect_local());
+    attributes
+        .iter()
+        .filter_map(|attr| match parse_paths(attr).as_deref() {
+            Ok([trait_name, self_type, replacement]) => {
+                let result = resolve::resolve_trait_impl(
+                    tcx,
+                    current_module.to_local_def_id(),
+                    trait_name,
+                    self_type,
+                );
+                if let Err(err) = result {
+                    tcx.sess.span_err(
+                        attr.span,
+                        format!("failed to resolve `impl {trait_name} for {self_type}`: {err}"),
+                    );
+                }
+                Some(TraitImplStub {
+                    trait_name: trait_name.clone(),
+                    self_type: self_type.clone(),
+                    replacement: replacement.clone(),
+                })
+            }
+            _ => {
+                tcx.sess.span_err(
+                    attr.span,
+                    "attribute `kani::stub_trait_impl` expects `impl Trait for Type, replacement`, \
+                    where `Trait`, `Type` and `replacement` are paths",
+                );
+                None
+            }
+        })
+        .collect()
+}
+
 fn parse_solver(tcx: TyCtxt, attr: &Attribute) -> Option<CbmcSolver> {
     // TODO: Argument validation should be done as part of the `kani_macros` crate
     // <https://github.com/model-checking/kani/issues/2192>
@@ -726,7 +922,7 @@ fn parse_solver(tcx: TyCtxt, attr: &Attribute) -> Option<CbmcSolver> {
     let invalid_arg_err = |attr: &Attribute| {
         tcx.sess.span_err(
                 attr.span,
-                format!("invalid argument for `{ATTRIBUTE}` attribute, expected one of the supported solvers (e.g. `kissat`) or a SAT solver binary (e.g. `bin=\"<SAT_SOLVER_BINARY>\"`)")
+                format!("invalid argument for `{ATTRIBUTE}` attribute, expected one of the supported solvers (e.g. `kissat`), a SAT solver binary (e.g. `bin=\"<SAT_SOLVER_BINARY>\"`) or an SMT solver command (e.g. `smt2=\"<SMT_SOLVER_COMMAND>\"`)")
             )
     };
 
@@ -764,6 +960,9 @@ fn parse_solver(tcx: TyCtxt, attr: &Attribute) -> Option<CbmcSolver> {
         MetaItemKind::NameValue(lit) if ident_str == "bin" && lit.kind.is_str() => {
             Some(CbmcSolver::Binary(lit.symbol.to_string()))
         }
+        MetaItemKind::NameValue(lit) if ident_str == "smt2" && lit.kind.is_str() => {
+            Some(CbmcSolver::Smt2(lit.symbol.to_string()))
+        }
         _ => {
             invalid_arg_err(attr);
             None
@@ -771,6 +970,53 @@ fn parse_solver(tcx: TyCtxt, attr: &Attribute) -> Option<CbmcSolver> {
     }
 }
 
+/// Return the expected panic of `#[kani::should_panic(expected = "<panic>")]`, if any.
+fn parse_expected_panic(tcx: TyCtxt, attr: &Attribute) -> Option<String> {
+    let attr_args = attr.meta_item_list().unwrap_or_default();
+    match attr_args.as_slice() {
+        [] => None,
+        [arg] if arg.has_name(Symbol::intern("expected")) && arg.value_str().is_some() => {
+            Some(arg.value_str().unwrap().to_string())
+        }
+        _ => {
+            tcx.sess
+                .struct_span_err(
+                    attr.span,
+                    "the `#[kani::should_panic]` attribute only accepts an `expected` argument",
+                )
+                .help(
+                    "use `expected = \"<message>\"` with a substring of the panic message, or \
+                    with the name of the panic check (e.g. `my_harness.assertion.1`)",
+                )
+                .emit();
+            None
+        }
+    }
+}
+
+fn parse_path_strategy(tcx: TyCtxt, attr: &Attribute) -> Option<CbmcPathStrategy> {
+    const ATTRIBUTE: &str = "#[kani::path_strategy]";
+    let attr_args = attr.meta_item_list().unwrap_or_default();
+    let strategy = match attr_args.as_slice() {
+        [arg] if arg.is_word() => arg.ident().unwrap().name,
+        _ => {
+            tcx.sess.span_err(
+                attr.span,
+                format!(
+                    "the `{ATTRIBUTE}` attribute expects a single argument, the name of a \
+                    strategy (e.g. `lifo`)"
+                ),
+            );
+            return None;
+        }
+    };
+    CbmcPathStrategy::from_str(strategy.as_str())
+        .map_err(|_| {
+            tcx.sess.span_err(attr.span, format!("unknown path strategy `{strategy}`"));
+        })
+        .ok()
+}
+
 /// Extracts the integer value argument from the attribute provided
 /// For example, `unwind(8)` return `Some(8)`
 fn parse_integer(attr: &Attribute) -> Option<u128> {
@@ -804,6 +1050,24 @@ fn parse_paths(attr: &Attribute) -> Result<Vec<String>, Span> {
         .collect()
 }
 
+/// Extracts the path arguments of a `kani::stub` attribute. A qualified path to a trait method,
+/// e.g., `<Type as Trait>::method`, is lowered to a string literal by `kani_macros`, since it
+/// can't be an attribute argument.
+fn parse_stub_paths(attr: &Attribute) -> Result<Vec<String>, Span> {
+    let attr_args = attr.meta_item_list();
+    attr_args
+        .unwrap_or_default()
+        .iter()
+        .map(|arg| match arg {
+            NestedMetaItem::Lit(MetaItemLit { kind: LitKind::Str(path, _), .. }) => {
+                Ok(path.to_string())
+            }
+            NestedMetaItem::Lit(item) => Err(item.span),
+            NestedMetaItem::MetaItem(item) => parse_path(item).ok_or(item.span),
+        })
+        .collect()
+}
+
 /// Extracts a path from an attribute item, returning `None` if the item is not
 /// syntactically a path.
 fn parse_path(meta_item: &MetaItem) -> Option<String> {
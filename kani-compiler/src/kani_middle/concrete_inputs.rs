@@ -0,0 +1,73 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module finds the symbolic inputs of a harness that `--concrete` fixes to a concrete
+//! value.
+//!
+//! The inputs of a harness are its local variables that are initialized with `kani::any()` or
+//! `kani::any_where()`, and they are named after the variable, e.g., `x` in
+//! `let x: u32 = kani::any();`. The other inputs of the harness stay symbolic.
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir::{Body, Local, TerminatorKind, VarDebugInfoContents};
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::Symbol;
+use std::collections::HashMap;
+
+/// The functions of the Kani library that create the symbolic inputs of a harness.
+const SYMBOLIC_INPUT_FUNCTIONS: [&str; 2] = ["KaniAny", "KaniAnyWhere"];
+
+/// The locals of the harness `body` that hold one of `inputs`, given as `(name, value)` pairs,
+/// with the input that each of them holds.
+pub fn concrete_input_locals<'a, 'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    inputs: &'a [(String, String)],
+) -> HashMap<Local, &'a (String, String)> {
+    if inputs.is_empty() {
+        return HashMap::new();
+    }
+    let functions: Vec<DefId> = SYMBOLIC_INPUT_FUNCTIONS
+        .iter()
+        .filter_map(|name| tcx.get_diagnostic_item(Symbol::intern(name)))
+        .collect();
+    let names: HashMap<Local, Symbol> = body
+        .var_debug_info
+        .iter()
+        .filter_map(|info| match info.value {
+            VarDebugInfoContents::Place(place) if place.projection.is_empty() => {
+                Some((place.local, info.name))
+            }
+            _ => None,
+        })
+        .collect();
+    body.basic_blocks
+        .iter()
+        .filter_map(|block| {
+            let TerminatorKind::Call { func, destination, .. } = &block.terminator().kind else {
+                return None;
+            };
+            let ty::FnDef(def_id, _) = *func.ty(body, tcx).kind() else { return None };
+            if !functions.contains(&def_id) || !destination.projection.is_empty() {
+                return None;
+            }
+            let name = names.get(&destination.local)?;
+            let input = inputs.iter().find(|(input, _)| name.as_str() == input)?;
+            Some((destination.local, input))
+        })
+        .collect()
+}
+
+/// The names of the inputs of the harness `body` that `inputs` fix, sorted and deduplicated.
+pub fn concrete_input_names<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    inputs: &[(String, String)],
+) -> Vec<String> {
+    let mut names: Vec<String> = concrete_input_locals(tcx, body, inputs)
+        .into_values()
+        .map(|(name, _)| name.clone())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
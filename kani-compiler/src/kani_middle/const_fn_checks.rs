@@ -0,0 +1,106 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module synthesizes the harnesses used by `--const-fn-checks`.
+//!
+//! A `const fn` is usually written with compile-time evaluation in mind, but it is just as often
+//! called at runtime, where a panic is especially surprising. Every non-generic `const fn` of the
+//! local crate gets a harness that calls it with arbitrary arguments. The harness is an instance
+//! of the library function `kani::internal::const_fn_check::<F, Args>`, where `F` is the type of
+//! the function and `Args` the tuple of its argument types, and it is identified by the function.
+
+use super::attributes::{is_function_contract_generated, is_proof_harness};
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
+use rustc_hir::Unsafety;
+use rustc_middle::ty::{Instance, ParamEnv, Ty, TyCtxt};
+use rustc_span::Symbol;
+use rustc_target::spec::abi::Abi;
+
+/// Collect the local `const fn`s that can be checked.
+///
+/// A function can only be checked if it is safe, not generic, and if all of its argument types
+/// implement `kani::Arbitrary`. We emit a warning for each function that is skipped.
+pub fn const_fn_check_targets(tcx: TyCtxt) -> Vec<DefId> {
+    let Some(arbitrary) = tcx.get_diagnostic_item(Symbol::intern("KaniArbitrary")) else {
+        tcx.sess.err("`--const-fn-checks` requires the `kani` library");
+        return vec![];
+    };
+    tcx.hir_crate_items(())
+        .definitions()
+        .map(|local_id| local_id.to_def_id())
+        .filter(|def_id| is_const_fn(tcx, *def_id))
+        .filter(|def_id| {
+            let name = tcx.def_path_str(*def_id);
+            let skip_reason = if tcx.generics_of(*def_id).count() > 0 {
+                Some("it is generic")
+            } else if !is_safe_rust_fn(tcx, *def_id) {
+                Some("it is not a safe Rust function")
+            } else if !is_arbitrary(tcx, arbitrary, arguments_ty(tcx, *def_id)) {
+                Some("its arguments do not implement `kani::Arbitrary`")
+            } else {
+                None
+            };
+            if let Some(reason) = &skip_reason {
+                tcx.sess.span_warn(
+                    tcx.def_span(*def_id),
+                    format!("skipping the const fn check of `{name}` since {reason}"),
+                );
+            }
+            skip_reason.is_none()
+        })
+        .collect()
+}
+
+/// Build the harness that checks the `const fn` `def_id`.
+/// Return `None` if `def_id` isn't one of the functions returned by [const_fn_check_targets].
+pub fn const_fn_check_harness(tcx: TyCtxt, def_id: DefId) -> Option<Instance> {
+    if !is_const_fn(tcx, def_id)
+        || tcx.generics_of(def_id).count() > 0
+        || !is_safe_rust_fn(tcx, def_id)
+    {
+        return None;
+    }
+    let const_fn_check = tcx.get_diagnostic_item(Symbol::intern("KaniConstFnCheck"))?;
+    let fn_ty = tcx.type_of(def_id).instantiate_identity();
+    let args_ty = arguments_ty(tcx, def_id);
+    Some(Instance::new(const_fn_check, tcx.mk_args(&[fn_ty.into(), args_ty.into()])))
+}
+
+/// Whether `def_id` is a `const fn` written by the user, and not a harness or a function
+/// generated by Kani.
+fn is_const_fn(tcx: TyCtxt, def_id: DefId) -> bool {
+    matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+        && def_id.is_local()
+        && tcx.is_const_fn_raw(def_id)
+        && !is_proof_harness(tcx, def_id)
+        && !is_function_contract_generated(tcx, def_id)
+}
+
+/// Only safe functions with the Rust ABI implement `FnOnce`, which the harness uses to call them.
+fn is_safe_rust_fn(tcx: TyCtxt, def_id: DefId) -> bool {
+    let sig = tcx.fn_sig(def_id).instantiate_identity();
+    sig.unsafety() == Unsafety::Normal && sig.abi() == Abi::Rust
+}
+
+/// The tuple of the argument types of the function `def_id`.
+fn arguments_ty(tcx: TyCtxt, def_id: DefId) -> Ty {
+    let sig = tcx.fn_sig(def_id).instantiate_identity();
+    let sig = tcx.erase_late_bound_regions(sig);
+    Ty::new_tup(tcx, sig.inputs())
+}
+
+/// Whether `ty` implements `kani::Arbitrary`, i.e., whether its `any` method can be resolved.
+fn is_arbitrary<'tcx>(tcx: TyCtxt<'tcx>, arbitrary: DefId, ty: Ty<'tcx>) -> bool {
+    let Some(any) = tcx
+        .associated_item_def_ids(arbitrary)
+        .iter()
+        .copied()
+        .find(|item| tcx.item_name(*item) == Symbol::intern("any"))
+    else {
+        return false;
+    };
+    matches!(
+        Instance::resolve(tcx, ParamEnv::reveal_all(), any, tcx.mk_args(&[ty.into()])),
+        Ok(Some(_))
+    )
+}
@@ -0,0 +1,62 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module synthesizes the harnesses used by `--drop-checks`.
+//!
+//! Every `Drop` implementation of the local crate gets a harness that creates an arbitrary value
+//! of the implementing type and drops it. The harness is an instance of the library function
+//! `kani::internal::drop_check::<T>`, and it is identified by the `drop` method of the
+//! implementation, which is also the item whose name and location we report to the user.
+
+use super::{implements_trait, local_impls};
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::{Instance, TyCtxt};
+use rustc_span::Symbol;
+
+/// Collect the `drop` methods of the local `Drop` implementations that can be checked.
+///
+/// An implementation can only be checked if it is not generic and if its type implements
+/// `kani::Arbitrary`. We emit a warning for each implementation that is skipped.
+pub fn drop_check_targets(tcx: TyCtxt) -> Vec<DefId> {
+    let Some(drop_trait) = tcx.lang_items().drop_trait() else { return vec![] };
+    let Some(arbitrary) = tcx.get_diagnostic_item(Symbol::intern("KaniArbitrary")) else {
+        tcx.sess.err("`--drop-checks` requires the `kani` library");
+        return vec![];
+    };
+    local_impls(tcx, drop_trait)
+        .filter_map(|impl_id| {
+            let ty = tcx.type_of(impl_id).instantiate_identity();
+            if tcx.generics_of(impl_id).count() > 0 {
+                tcx.sess.span_warn(
+                    tcx.def_span(impl_id),
+                    format!("skipping the drop check of `{ty}` since its `Drop` impl is generic"),
+                );
+                None
+            } else if !implements_trait(tcx, arbitrary, ty) {
+                tcx.sess.span_warn(
+                    tcx.def_span(impl_id),
+                    format!(
+                        "skipping the drop check of `{ty}` since it does not implement \
+                        `kani::Arbitrary`"
+                    ),
+                );
+                None
+            } else {
+                tcx.associated_item_def_ids(impl_id).first().copied()
+            }
+        })
+        .collect()
+}
+
+/// Build the harness that checks the `Drop` implementation that `drop_fn` belongs to.
+/// Return `None` if `drop_fn` isn't the `drop` method of a non-generic `Drop` implementation.
+pub fn drop_check_harness(tcx: TyCtxt, drop_fn: DefId) -> Option<Instance> {
+    let impl_id = tcx.impl_of_method(drop_fn)?;
+    if tcx.trait_id_of_impl(impl_id) != tcx.lang_items().drop_trait()
+        || tcx.generics_of(impl_id).count() > 0
+    {
+        return None;
+    }
+    let drop_check = tcx.get_diagnostic_item(Symbol::intern("KaniDropCheck"))?;
+    let ty = tcx.type_of(impl_id).instantiate_identity();
+    Some(Instance::new(drop_check, tcx.mk_args(&[ty.into()])))
+}
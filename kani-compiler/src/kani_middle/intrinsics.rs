@@ -2,10 +2,23 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 //! This module contains a MIR pass that replaces some intrinsics by rust intrinsics models as
 //! well as validation logic that can only be added during monomorphization.
+//!
+//! The same pass also replaces functions that read from the entropy source of the operating
+//! system by models that return nondeterministic bytes, and it handles the UTF-8 invariant of
+//! strings according to the `--utf8-checks` and `--no-utf8-assumptions` options.
+//!
+//! With `--nondet-hash-order`, the hashes of `HashMap` and `HashSet` are replaced by a model
+//! that makes their iteration order nondeterministic. See [hash_order_model].
+//!
+//! The float intrinsics that CBMC doesn't support, `fma` and `powi`, are replaced by models that
+//! compute them with the operations that it supports, and the assumptions that `kani::any` makes
+//! about floats are replaced according to `--float-values`.
+use rustc_hir::def_id::DefId;
 use rustc_index::IndexVec;
 use rustc_middle::mir::{Body, Const as mirConst, ConstValue, Operand, TerminatorKind};
 use rustc_middle::mir::{Local, LocalDecl};
-use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
 use rustc_middle::ty::{Const, GenericArgsRef};
 use rustc_span::symbol::{sym, Symbol};
 use tracing::{debug, trace};
@@ -14,6 +27,13 @@ pub struct ModelIntrinsics<'tcx> {
     tcx: TyCtxt<'tcx>,
     /// Local declarations of the function being transformed.
     local_decls: IndexVec<Local, LocalDecl<'tcx>>,
+    /// Whether the unchecked UTF-8 conversions in the function being transformed are checked.
+    utf8_checks: bool,
+    /// Whether the generators of the Kani library assume that strings are valid UTF-8.
+    utf8_assumptions: bool,
+    /// The model of the assumption that `kani::any` makes about floats, if they are restricted
+    /// with `--float-values`.
+    float_values_model: Option<&'static str>,
 }
 
 impl<'tcx> ModelIntrinsics<'tcx> {
@@ -24,7 +44,17 @@ impl<'tcx> ModelIntrinsics<'tcx> {
     /// However, this pass runs after lowering intrinsics, which may replace the terminator by
     /// an intrinsic statement (non-diverging intrinsic).
     pub fn run_pass(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
-        ModelIntrinsics { tcx, local_decls: body.local_decls.clone() }.transform(body)
+        // Conversions in the standard library and in the Kani library are trusted.
+        let krate = tcx.crate_name(body.source.def_id().krate);
+        let trusted = matches!(krate.as_str(), "core" | "alloc" | "std" | "kani");
+        ModelIntrinsics {
+            tcx,
+            local_decls: body.local_decls.clone(),
+            utf8_checks: !trusted && has_compiler_flag(tcx, "--utf8-checks"),
+            utf8_assumptions: !has_compiler_flag(tcx, "--no-utf8-assumptions"),
+            float_values_model: float_values_model(tcx),
+        }
+        .transform(body)
     }
 
     pub fn transform(&self, body: &mut Body<'tcx>) {
@@ -37,6 +67,16 @@ pub fn transform(&self, body: &mut Body<'tcx>) {
                     trace!(?func, ?intrinsic_name, "run_pass");
                     if intrinsic_name == sym::simd_bitmask {
                         self.replace_simd_bitmask(func, args, generics)
+                    } else if let Some(model_id) = float_intrinsic_model(self.tcx, intrinsic_name) {
+                        self.replace_function(func, model_id)
+                    }
+                } else if let ty::FnDef(def_id, _) = *func_ty.kind() {
+                    if let Some(model_id) = entropy_model(self.tcx, def_id) {
+                        self.replace_entropy_source(func, def_id, model_id)
+                    } else if let Some(model_id) = self.utf8_model(def_id) {
+                        self.replace_function(func, model_id)
+                    } else if let Some(model_id) = self.float_assumption_model(def_id) {
+                        self.replace_function(func, model_id)
                     }
                 }
             }
@@ -79,6 +119,179 @@ fn replace_simd_bitmask(
     }
 }
 
+impl<'tcx> ModelIntrinsics<'tcx> {
+    /// Change the function call to use the model of the entropy source.
+    ///
+    /// The models of the `getrandom` crate are generic over its error type, which we take from
+    /// the signature of the original function. The call is left untouched if the signatures of
+    /// the original function and the model do not match.
+    fn replace_entropy_source(&self, func: &mut Operand<'tcx>, source_id: DefId, model_id: DefId) {
+        let tcx = self.tcx;
+        let source_sig = tcx.erase_late_bound_regions(tcx.fn_sig(source_id).skip_binder());
+        let model_args = if tcx.generics_of(model_id).count() == 0 {
+            ty::List::empty()
+        } else {
+            let ty::Adt(_, result_args) = source_sig.output().kind() else { return };
+            tcx.mk_args(&[result_args.type_at(1).into()])
+        };
+        let model_sig =
+            tcx.erase_late_bound_regions(tcx.fn_sig(model_id).instantiate(tcx, model_args));
+        if source_sig.inputs_and_output != model_sig.inputs_and_output {
+            debug!(?source_sig, ?model_sig, "replace_entropy_source failed");
+            return;
+        }
+        debug!(?func, ?model_id, "replace_entropy_source");
+        let Operand::Constant(fn_def) = func else { unreachable!() };
+        fn_def.const_ = mirConst::from_value(
+            ConstValue::ZeroSized,
+            tcx.type_of(model_id).instantiate(tcx, model_args),
+        );
+    }
+}
+
+impl<'tcx> ModelIntrinsics<'tcx> {
+    /// Return the model of `def_id` if it is a function whose handling of the UTF-8 invariant
+    /// depends on the options of the user:
+    ///  - With `--utf8-checks`, the unchecked conversions are replaced by models that check the
+    ///    bytes first.
+    ///  - With `--no-utf8-assumptions`, the assumption made by the generators is dropped.
+    fn utf8_model(&self, def_id: DefId) -> Option<DefId> {
+        let tcx = self.tcx;
+        let model = match tcx.get_diagnostic_name(def_id).as_ref().map(Symbol::as_str) {
+            Some("str_from_utf8_unchecked") if self.utf8_checks => "KaniModelFromUtf8Unchecked",
+            Some("str_from_utf8_unchecked_mut") if self.utf8_checks => {
+                "KaniModelFromUtf8UncheckedMut"
+            }
+            Some("KaniAssumeUtf8") if !self.utf8_assumptions => "KaniModelSkipUtf8Assumption",
+            Some(_) => return None,
+            None if self.utf8_checks => {
+                match with_no_trimmed_paths!(tcx.def_path_str(def_id)).as_str() {
+                    "std::string::String::from_utf8_unchecked"
+                    | "alloc::string::String::from_utf8_unchecked" => {
+                        "KaniModelStringFromUtf8Unchecked"
+                    }
+                    _ => return None,
+                }
+            }
+            None => return None,
+        };
+        tcx.get_diagnostic_item(Symbol::intern(model))
+    }
+
+    /// Return the model that replaces `def_id` if it is the assumption that `kani::any` makes
+    /// about floats, and they are restricted with `--float-values`.
+    fn float_assumption_model(&self, def_id: DefId) -> Option<DefId> {
+        let model = self.float_values_model?;
+        if self.tcx.get_diagnostic_name(def_id)?.as_str() != "KaniAssumeFloatValues" {
+            return None;
+        }
+        self.tcx.get_diagnostic_item(Symbol::intern(model))
+    }
+
+    /// Change the function call to use the given model, e.g., a UTF-8 or a float model. Neither
+    /// the models nor the functions they replace have generic parameters.
+    fn replace_function(&self, func: &mut Operand<'tcx>, model_id: DefId) {
+        debug!(?func, ?model_id, "replace_function");
+        let Operand::Constant(fn_def) = func else { unreachable!() };
+        fn_def.const_ = mirConst::from_value(
+            ConstValue::ZeroSized,
+            self.tcx.type_of(model_id).instantiate(self.tcx, ty::List::empty()),
+        );
+    }
+}
+
+/// Return the model of the float intrinsic `intrinsic_name` if CBMC doesn't support it.
+fn float_intrinsic_model(tcx: TyCtxt, intrinsic_name: Symbol) -> Option<DefId> {
+    let model = match intrinsic_name.as_str() {
+        "fmaf32" => "KaniModelFmaf32",
+        "fmaf64" => "KaniModelFmaf64",
+        "powif32" => "KaniModelPowif32",
+        "powif64" => "KaniModelPowif64",
+        _ => return None,
+    };
+    tcx.get_diagnostic_item(Symbol::intern(model))
+}
+
+/// Return the name of the model of the float assumption of `kani::any` that corresponds to the
+/// `--float-values` flag of the compiler, if any.
+fn float_values_model(tcx: TyCtxt) -> Option<&'static str> {
+    let llvm_args = &tcx.sess.opts.cg.llvm_args;
+    match llvm_args.iter().find_map(|arg| arg.strip_prefix("--float-values="))? {
+        "no-nan" => Some("KaniModelAssumeNoNan"),
+        "finite" => Some("KaniModelAssumeFinite"),
+        "normal" => Some("KaniModelAssumeNormal"),
+        _ => None,
+    }
+}
+
+/// Whether the Kani compiler was invoked with the given flag. The flags are passed to the
+/// compiler with `-C llvm-args`, one flag per argument.
+fn has_compiler_flag(tcx: TyCtxt, flag: &str) -> bool {
+    tcx.sess.opts.cg.llvm_args.iter().any(|arg| arg == flag)
+}
+
+/// Return the model of `def_id` if it is a function that reads from the entropy source of the
+/// operating system.
+///
+/// We model the function std uses to seed `HashMap`, the public API of the `getrandom` crate,
+/// which most crates use to access randomness, as well as any foreign declaration of the
+/// `getrandom` and `getentropy` system calls.
+fn entropy_model(tcx: TyCtxt, def_id: DefId) -> Option<DefId> {
+    let model = if tcx.is_foreign_item(def_id) {
+        match tcx.item_name(def_id).as_str() {
+            "getrandom" => "KaniModelGetrandomSyscall",
+            "getentropy" => "KaniModelGetentropy",
+            _ => return None,
+        }
+    } else {
+        match with_no_trimmed_paths!(tcx.def_path_str(def_id)).as_str() {
+            "std::sys::unix::rand::hashmap_random_keys"
+                if has_compiler_flag(tcx, "--nondet-hash-order") =>
+            {
+                "KaniModelHashmapFixedKeys"
+            }
+            "std::sys::unix::rand::hashmap_random_keys" => "KaniModelHashmapRandomKeys",
+            "getrandom::getrandom" => "KaniModelGetrandom",
+            "getrandom::getrandom_uninit" => "KaniModelGetrandomUninit",
+            _ => return None,
+        }
+    };
+    tcx.get_diagnostic_item(Symbol::intern(model))
+}
+
+/// Redirect `<RandomState as BuildHasher>::hash_one`, which `HashMap` and `HashSet` use to hash
+/// their keys, to a model that XORs every hash with a nondeterministic seed when the crate is
+/// compiled with `--nondet-hash-order`. The keys of `RandomState` are fixed in this mode, so the
+/// seed alone decides the order in which the elements are visited.
+///
+/// The call is generic in the standard library, so it can only be replaced once the instance is
+/// resolved.
+pub fn hash_order_model<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> Instance<'tcx> {
+    let ty::InstanceDef::Item(def_id) = instance.def else { return instance };
+    if tcx.opt_item_name(def_id).map_or(true, |name| name.as_str() != "hash_one")
+        || !tcx.trait_of_item(def_id).is_some_and(|trait_id| {
+            with_no_trimmed_paths!(tcx.def_path_str(trait_id)) == "core::hash::BuildHasher"
+        })
+    {
+        return instance;
+    }
+    let ty::Adt(hasher, _) = instance.args.type_at(0).kind() else { return instance };
+    let hasher_path = with_no_trimmed_paths!(tcx.def_path_str(hasher.did()));
+    if !matches!(
+        hasher_path.as_str(),
+        "std::collections::hash_map::RandomState" | "std::collections::hash::map::RandomState"
+    ) || !has_compiler_flag(tcx, "--nondet-hash-order")
+    {
+        return instance;
+    }
+    let Some(model) = tcx.get_diagnostic_item(Symbol::intern("KaniModelRandomStateHashOne")) else {
+        return instance;
+    };
+    debug!(?instance, "hash_order_model");
+    // The model takes the type of the hashed value, without `Self`.
+    Instance::new(model, tcx.mk_args(&instance.args[1..]))
+}
+
 fn simd_len_and_type<'tcx>(tcx: TyCtxt<'tcx>, simd_ty: Ty<'tcx>) -> (Const<'tcx>, Ty<'tcx>) {
     match simd_ty.kind() {
         ty::Adt(def, args) => {
@@ -5,8 +5,9 @@
 
 use std::path::Path;
 
+use crate::args::Arguments;
 use crate::kani_middle::attributes::test_harness_name;
-use kani_metadata::{ArtifactType, HarnessAttributes, HarnessMetadata};
+use kani_metadata::{ArtifactType, HarnessAssumptions, HarnessAttributes, HarnessMetadata};
 use rustc_hir::def_id::DefId;
 use rustc_middle::ty::{Instance, InstanceDef, TyCtxt};
 
@@ -39,6 +40,54 @@ pub fn gen_proof_metadata(tcx: TyCtxt, def_id: DefId, base_name: &Path) -> Harne
         attributes,
         // TODO: This no longer needs to be an Option.
         goto_file: Some(model_file),
+        contract: None,
+        stub_usage: vec![],
+        havocked_asm: vec![],
+        concrete_inputs: vec![],
+        assumptions: HarnessAssumptions::default(),
+    }
+}
+
+/// Apply the configuration of the package of the crate to the attributes of one of its
+/// harnesses. The package unwind bound and solver are stored apart from the attributes of the
+/// harness, since the driver ranks them below its own defaults, and its stubs are added to the
+/// ones of the harness.
+pub fn apply_package_config(args: &Arguments, attributes: &mut HarnessAttributes) {
+    attributes.package_unwind = args.package_unwind;
+    attributes.package_solver = args.package_solver.clone();
+    attributes.stubs.extend(args.package_stubs.iter().cloned());
+}
+
+/// Create the harness metadata for a harness synthesized by Kani to check the function `target`
+/// (see [`super::synthesized_harness`]). The harness is reported under the name and location of
+/// `target`.
+pub fn gen_synthesized_metadata<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    target: DefId,
+    harness: Instance<'tcx>,
+    base_name: &Path,
+) -> HarnessMetadata {
+    let pretty_name = tcx.def_path_str(target);
+    let mangled_name = tcx.symbol_name(harness).to_string();
+    let body = tcx.instance_mir(InstanceDef::Item(target));
+    let loc = SourceLocation::new(tcx, &body.span);
+    let file_stem = format!("{}_{mangled_name}", base_name.file_stem().unwrap().to_str().unwrap());
+    let model_file = base_name.with_file_name(file_stem).with_extension(ArtifactType::SymTabGoto);
+
+    HarnessMetadata {
+        pretty_name,
+        mangled_name,
+        crate_name: tcx.crate_name(target.krate).to_string(),
+        original_file: loc.filename,
+        original_start_line: loc.start_line,
+        original_end_line: loc.end_line,
+        attributes: HarnessAttributes { proof: true, ..Default::default() },
+        goto_file: Some(model_file),
+        contract: None,
+        stub_usage: vec![],
+        havocked_asm: vec![],
+        concrete_inputs: vec![],
+        assumptions: HarnessAssumptions::default(),
     }
 }
 
@@ -67,5 +116,10 @@ pub fn gen_test_metadata<'tcx>(
         attributes: HarnessAttributes::default(),
         // TODO: This no longer needs to be an Option.
         goto_file: Some(model_file),
+        contract: None,
+        stub_usage: vec![],
+        havocked_asm: vec![],
+        concrete_inputs: vec![],
+        assumptions: HarnessAssumptions::default(),
     }
 }
@@ -3,13 +3,15 @@
 //! This module contains code that are backend agnostic. For example, MIR analysis
 //! and transformations.
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::path::Path;
 
+use crate::args::Arguments;
 use crate::kani_queries::QueryDb;
 use rustc_hir::{def::DefKind, def_id::DefId, def_id::LOCAL_CRATE};
+use rustc_infer::infer::TyCtxtInferExt;
 use rustc_middle::mir::mono::MonoItem;
-use rustc_middle::mir::write_mir_pretty;
+use rustc_middle::mir::{write_mir_pretty, TerminatorKind};
 use rustc_middle::span_bug;
 use rustc_middle::ty::layout::{
     FnAbiError, FnAbiOf, FnAbiOfHelpers, FnAbiRequest, HasParamEnv, HasTyCtxt, LayoutError,
@@ -21,6 +23,7 @@
 use rustc_span::Span;
 use rustc_target::abi::call::FnAbi;
 use rustc_target::abi::{HasDataLayout, TargetDataLayout};
+use rustc_trait_selection::infer::InferCtxtExt;
 use std::fs::File;
 use std::io::BufWriter;
 use std::io::Write;
@@ -28,14 +31,21 @@
 use self::attributes::KaniAttributes;
 
 pub mod analysis;
+pub mod assumptions;
 pub mod attributes;
 pub mod coercion;
+pub mod concrete_inputs;
+pub mod const_fn_checks;
+pub mod drop_checks;
 mod intrinsics;
 pub mod metadata;
 pub mod provide;
 pub mod reachability;
 pub mod resolve;
+pub mod secret_taint;
 pub mod stubbing;
+mod symbolic_size;
+pub mod trait_laws;
 
 /// Check that all crate items are supported and there's no misconfiguration.
 /// This method will exhaustively print any error / warning and it will abort at the end if any
@@ -60,9 +70,41 @@ pub fn check_crate_items(tcx: TyCtxt, ignore_asm: bool) {
             }
         }
     }
+    check_harness_names(tcx);
     tcx.sess.abort_if_errors();
 }
 
+/// Check that every proof harness in the crate has a unique name.
+///
+/// Harnesses are selected by their name, so two harnesses with the same name cannot be told
+/// apart. Rust doesn't allow this for most items, but it can still happen, for example, for
+/// harnesses declared inside different anonymous constants (`const _: () = { .. };`), which is
+/// common in code generated by macros.
+fn check_harness_names(tcx: TyCtxt) {
+    let mut harnesses: BTreeMap<String, Vec<DefId>> = BTreeMap::new();
+    for def_id in tcx.hir_crate_items(()).definitions() {
+        let def_id = def_id.to_def_id();
+        if matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+            && attributes::is_proof_harness(tcx, def_id)
+        {
+            harnesses.entry(tcx.def_path_str(def_id)).or_default().push(def_id);
+        }
+    }
+    for (name, def_ids) in harnesses.iter().filter(|(_, def_ids)| def_ids.len() > 1) {
+        let mut diag = tcx.sess.struct_span_err(
+            tcx.def_span(def_ids[0]),
+            format!("the proof harness name `{name}` is defined multiple times"),
+        );
+        for other in &def_ids[1..] {
+            diag.span_note(tcx.def_span(*other), "another harness with the same name is here");
+        }
+        diag.help(
+            "harnesses are selected by name, rename or move the harnesses to make them unique",
+        )
+        .emit();
+    }
+}
+
 /// Check that all given items are supported and there's no misconfiguration.
 /// This method will exhaustively print any error / warning and it will abort at the end if any
 /// error was found.
@@ -86,6 +128,7 @@ pub fn check_reachable_items<'tcx>(tcx: TyCtxt<'tcx>, queries: &QueryDb, items:
             }
         }
     }
+    symbolic_size::check_symbolic_value_sizes(tcx, items);
     tcx.sess.abort_if_errors();
 }
 
@@ -201,6 +244,30 @@ fn visible_item<'tcx>(item: &MonoItem<'tcx>) -> Option<(MonoItem<'tcx>, DefId)>
     }
 }
 
+/// The inline assembly blocks of `items`, identified by the function that contains them and their
+/// location, e.g. `my_crate::rdtsc at src/lib.rs:4:5`.
+pub fn asm_blocks<'tcx>(tcx: TyCtxt<'tcx>, items: &[MonoItem<'tcx>]) -> Vec<String> {
+    let mut blocks = BTreeSet::new();
+    for item in items {
+        let MonoItem::Fn(instance) = item else { continue };
+        let body = tcx.instance_mir(instance.def);
+        for block in body.basic_blocks.iter() {
+            let terminator = block.terminator();
+            if let TerminatorKind::InlineAsm { .. } = terminator.kind {
+                let location = span_location(tcx, terminator.source_info.span);
+                blocks.insert(format!("{} at {location}", tcx.def_path_str(instance.def_id())));
+            }
+        }
+    }
+    blocks.into_iter().collect()
+}
+
+/// The location of the start of a span, e.g. `src/lib.rs:4:5`.
+pub fn span_location(tcx: TyCtxt, span: Span) -> String {
+    let lo = tcx.sess.source_map().lookup_char_pos(span.lo());
+    format!("{}:{}:{}", lo.file.name.prefer_local(), lo.line, 1 + lo.col_display)
+}
+
 /// Structure that represents the source location of a definition.
 /// TODO: Use `InternedString` once we move it out of the cprover_bindings.
 /// <https://github.com/model-checking/kani/issues/2435>
@@ -230,6 +297,42 @@ pub fn new(tcx: TyCtxt, span: &Span) -> Self {
     }
 }
 
+/// Find the harness that Kani synthesizes to check the item `def_id`, if any.
+///
+/// Synthesized harnesses don't exist in the source code, so they are identified by the item they
+/// check instead (see [drop_checks], [trait_laws] and [const_fn_checks]).
+pub fn synthesized_harness<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    args: &Arguments,
+    def_id: DefId,
+) -> Option<Instance<'tcx>> {
+    let drop_check = || drop_checks::drop_check_harness(tcx, def_id);
+    let trait_law = || trait_laws::trait_law_harness(tcx, def_id);
+    let const_fn_check = || const_fn_checks::const_fn_check_harness(tcx, def_id);
+    args.drop_checks
+        .then(drop_check)
+        .flatten()
+        .or_else(|| args.trait_laws.then(trait_law).flatten())
+        .or_else(|| args.const_fn_checks.then(const_fn_check).flatten())
+}
+
+/// Iterate over the implementations of `trait_id` in the local crate.
+pub fn local_impls(tcx: TyCtxt, trait_id: DefId) -> impl Iterator<Item = DefId> + '_ {
+    tcx.all_local_trait_impls(())
+        .get(&trait_id)
+        .into_iter()
+        .flatten()
+        .map(|impl_id| impl_id.to_def_id())
+}
+
+/// Whether `ty` implements `trait_id`, including through blanket and upstream implementations.
+pub fn implements_trait<'tcx>(tcx: TyCtxt<'tcx>, trait_id: DefId, ty: Ty<'tcx>) -> bool {
+    tcx.infer_ctxt()
+        .build()
+        .type_implements_trait(trait_id, [ty], ParamEnv::reveal_all())
+        .must_apply_modulo_regions()
+}
+
 /// Get the FnAbi of a given instance with no extra variadic arguments.
 pub fn fn_abi<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> &'tcx FnAbi<'tcx, Ty<'tcx>> {
     let helper = CompilerHelpers { tcx };
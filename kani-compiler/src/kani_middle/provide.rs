@@ -5,14 +5,16 @@
 //! custom MIR transformations.
 
 use crate::args::{Arguments, ReachabilityType};
-use crate::kani_middle::intrinsics::ModelIntrinsics;
+use crate::kani_middle::intrinsics::{hash_order_model, ModelIntrinsics};
 use crate::kani_middle::reachability::{collect_reachable_items, filter_crate_items};
 use crate::kani_middle::stubbing;
 use crate::kani_queries::QueryDb;
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_interface;
+use rustc_middle::ty::{GenericArgsRef, Instance, ParamEnvAnd};
 use rustc_middle::util::Providers;
 use rustc_middle::{mir::Body, query::queries, ty::TyCtxt};
+use rustc_span::ErrorGuaranteed;
 
 /// Sets up rustc's query mechanism to apply Kani's custom queries to code from
 /// a crate.
@@ -26,6 +28,9 @@ pub fn provide(providers: &mut Providers, queries: &QueryDb) {
             // TODO: Check if there's at least one stub being applied.
             providers.collect_and_partition_mono_items = collect_and_partition_mono_items;
         }
+        if args.stubbing_enabled || args.nondet_hash_order {
+            providers.resolve_instance = resolve_instance;
+        }
     }
 }
 
@@ -33,6 +38,20 @@ fn should_override(args: &Arguments) -> bool {
     args.reachability_analysis != ReachabilityType::None && !args.build_std
 }
 
+/// Resolves the instance called by a function with the default query, and then redirects calls
+/// to default trait methods that are stubbed for the `Self` type of the call, as well as the
+/// hashes of `HashMap` and `HashSet` with `--nondet-hash-order`.
+fn resolve_instance<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: ParamEnvAnd<'tcx, (DefId, GenericArgsRef<'tcx>)>,
+) -> Result<Option<Instance<'tcx>>, ErrorGuaranteed> {
+    let instance = (rustc_interface::DEFAULT_QUERY_PROVIDERS.resolve_instance)(tcx, key)?;
+    Ok(instance.map(|instance| {
+        let instance = stubbing::stub_default_method(tcx, instance);
+        hash_order_model(tcx, instance)
+    }))
+}
+
 /// Returns the optimized code for the external function associated with `def_id` by
 /// running rustc's optimization passes followed by Kani-specific passes.
 fn run_mir_passes_extern(tcx: TyCtxt, def_id: DefId) -> &Body {
@@ -59,7 +78,7 @@ fn run_kani_mir_passes<'tcx>(
 ) -> &'tcx Body<'tcx> {
     tracing::debug!(?def_id, "Run Kani transformation passes");
     let mut transformed_body = stubbing::transform(tcx, def_id, body);
-    stubbing::transform_foreign_functions(tcx, &mut transformed_body);
+    stubbing::transform_foreign_functions(tcx, def_id, &mut transformed_body);
     // This should be applied after stubbing so user stubs take precedence.
     ModelIntrinsics::run_pass(tcx, &mut transformed_body);
     tcx.arena.alloc(transformed_body)
@@ -4,8 +4,8 @@
 //! `DefId`s for functions and methods. For the definition of a simple path, see
 //! <https://doc.rust-lang.org/reference/paths.html#simple-paths>.
 //!
-//! TODO: Extend this logic to support resolving qualified paths.
-//! <https://github.com/model-checking/kani/issues/1997>
+//! Trait methods can also be named by a qualified path `<Type as Trait>::method`, where `Type`
+//! and `Trait` are simple paths.
 //!
 //! Note that glob use statements can form loops. The paths can also walk through the loop.
 
@@ -16,19 +16,25 @@
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::{DefId, LocalDefId, LocalModDefId, CRATE_DEF_INDEX, LOCAL_CRATE};
 use rustc_hir::{ItemKind, UseKind};
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::{self, AssocKind, TyCtxt};
 use tracing::debug;
 
-/// Attempts to resolve a simple path (in the form of a string) to a function / method `DefId`.
+/// Attempts to resolve a simple path or a qualified path to a trait method (in the form of a
+/// string) to a function / method `DefId`.
 ///
-/// TODO: Extend this implementation to handle qualified paths and simple paths
-/// corresponding to trait methods.
-/// <https://github.com/model-checking/kani/issues/1997>
+/// A qualified path resolves to the method of the trait implementation, or to the default method
+/// of the trait if the implementation inherits it.
 pub fn resolve_fn<'tcx>(
     tcx: TyCtxt<'tcx>,
     current_module: LocalDefId,
     path_str: &str,
 ) -> Result<DefId, ResolveError<'tcx>> {
+    if is_qualified_path(path_str) {
+        return match resolve_trait_method(tcx, current_module, path_str)? {
+            TraitMethod::Impl(def_id) => Ok(def_id),
+            TraitMethod::Default { method, .. } => Ok(method),
+        };
+    }
     let result = resolve_path(tcx, current_module, path_str);
     match result {
         Ok(def_id) => {
@@ -47,6 +53,91 @@ pub fn resolve_fn<'tcx>(
     }
 }
 
+/// Attempts to resolve the implementation of the trait `trait_path` for the type `type_path`,
+/// both of which are simple paths, to the `DefId` of the impl block.
+pub fn resolve_trait_impl<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    current_module: LocalDefId,
+    trait_path: &str,
+    type_path: &str,
+) -> Result<DefId, ResolveError<'tcx>> {
+    let trait_id = resolve_path(tcx, current_module, trait_path)?;
+    if tcx.def_kind(trait_id) != DefKind::Trait {
+        return Err(ResolveError::UnexpectedType { tcx, item: trait_id, expected: "trait" });
+    }
+    let type_id = resolve_path(tcx, current_module, type_path)?;
+    if !matches!(tcx.def_kind(type_id), DefKind::Struct | DefKind::Enum | DefKind::Union) {
+        return Err(ResolveError::UnexpectedType {
+            tcx,
+            item: type_id,
+            expected: "struct, enum or union",
+        });
+    }
+    tcx.all_impls(trait_id)
+        .find(|impl_id| {
+            matches!(tcx.type_of(*impl_id).instantiate_identity().kind(),
+                ty::Adt(def, _) if def.did() == type_id)
+        })
+        .ok_or(ResolveError::MissingTraitImpl { tcx, trait_id, type_id })
+}
+
+/// The method that a qualified path `<Type as Trait>::method` resolves to.
+#[derive(Copy, Clone, Debug)]
+pub enum TraitMethod {
+    /// The method is defined in the implementation of the trait for the type.
+    Impl(DefId),
+    /// The implementation `impl_id` inherits the default `method` of the trait.
+    Default { method: DefId, impl_id: DefId },
+}
+
+/// Whether `path_str` is a qualified path, i.e., a path that starts with `<Type as Trait>`.
+pub fn is_qualified_path(path_str: &str) -> bool {
+    path_str.trim_start().starts_with('<')
+}
+
+/// Attempts to resolve a qualified path `<Type as Trait>::method` to the method that a call
+/// to `method` on `Type` resolves to.
+pub fn resolve_trait_method<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    current_module: LocalDefId,
+    path_str: &str,
+) -> Result<TraitMethod, ResolveError<'tcx>> {
+    let (type_path, trait_path, name) = split_qualified_path(path_str)?;
+    let impl_id = resolve_trait_impl(tcx, current_module, trait_path, type_path)?;
+    let impl_method = tcx
+        .associated_items(impl_id)
+        .in_definition_order()
+        .find(|item| item.kind == AssocKind::Fn && item.name.as_str() == name);
+    if let Some(item) = impl_method {
+        return Ok(TraitMethod::Impl(item.def_id));
+    }
+    let trait_id = tcx.trait_id_of_impl(impl_id).unwrap();
+    let method = resolve_in_trait(tcx, trait_id, name)?;
+    if tcx.def_kind(method) != DefKind::AssocFn {
+        return Err(ResolveError::UnexpectedType { tcx, item: method, expected: "method" });
+    }
+    if !tcx.defaultness(method).has_value() {
+        // The implementation must define the methods without a default.
+        return Err(ResolveError::MissingItem { tcx, base: impl_id, unresolved: name.to_string() });
+    }
+    Ok(TraitMethod::Default { method, impl_id })
+}
+
+/// Split a qualified path `<Type as Trait>::method` into `Type`, `Trait` and `method`.
+fn split_qualified_path<'tcx>(path_str: &str) -> Result<(&str, &str, &str), ResolveError<'tcx>> {
+    let invalid = || ResolveError::InvalidPath {
+        msg: format!("expected a qualified path `<Type as Trait>::method`, found `{path_str}`"),
+    };
+    let qualified = path_str.trim().strip_prefix('<').ok_or_else(invalid)?;
+    let (qself, method) = qualified.rsplit_once('>').ok_or_else(invalid)?;
+    let method = method.trim().strip_prefix("::").ok_or_else(invalid)?.trim();
+    let (type_path, trait_path) = qself.split_once(" as ").ok_or_else(invalid)?;
+    if method.is_empty() || method.contains("::") {
+        return Err(invalid());
+    }
+    Ok((type_path.trim(), trait_path.trim(), method))
+}
+
 /// Attempts to resolve a simple path (in the form of a string) to a `DefId`.
 /// The current module is provided as an argument in order to resolve relative
 /// paths.
@@ -66,6 +157,7 @@ fn resolve_path<'tcx>(
         let next_item = match def_kind {
             DefKind::ForeignMod | DefKind::Mod => resolve_in_module(tcx, base, &name),
             DefKind::Struct | DefKind::Enum | DefKind::Union => resolve_in_type(tcx, base, &name),
+            DefKind::Trait => resolve_in_trait(tcx, base, &name),
             kind => {
                 debug!(?base, ?kind, "resolve_path: unexpected item");
                 Err(ResolveError::UnexpectedType { tcx, item: base, expected: "module" })
@@ -86,6 +178,8 @@ pub enum ResolveError<'tcx> {
     InvalidPath { msg: String },
     /// Unable to find an item.
     MissingItem { tcx: TyCtxt<'tcx>, base: DefId, unresolved: String },
+    /// The type doesn't implement the trait.
+    MissingTraitImpl { tcx: TyCtxt<'tcx>, trait_id: DefId, type_id: DefId },
     /// Error triggered when the identifier points to an item with unexpected type.
     UnexpectedType { tcx: TyCtxt<'tcx>, item: DefId, expected: &'static str },
 }
@@ -125,6 +219,12 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 let def_desc = description(*tcx, *base);
                 write!(f, "unable to find `{unresolved}` inside {def_desc}")
             }
+            ResolveError::MissingTraitImpl { tcx, trait_id, type_id } => write!(
+                f,
+                "unable to find an implementation of `{}` for `{}`",
+                tcx.def_path_str(*trait_id),
+                tcx.def_path_str(*type_id)
+            ),
         }
     }
 }
@@ -145,6 +245,9 @@ struct Path {
 const CRATE: &str = "crate";
 /// rustc represents initial `::` as `{{root}}`.
 const ROOT: &str = "{{root}}";
+/// The first segment of a path string that starts with `::`, e.g. the paths of the models that
+/// Kani stubs functions with.
+const ROOT_STR: &str = "";
 /// Identifier for the current module.
 const SELF: &str = "self";
 /// Identifier for the parent of the current module.
@@ -169,7 +272,7 @@ fn resolve_prefix<'tcx>(
     // `self` may be followed be `super` (handled below).
     let first = segments.peek().unwrap().as_str();
     match first {
-        ROOT => {
+        ROOT | ROOT_STR => {
             // Skip root and get the external crate from the name that follows `::`.
             let next = segments.nth(1);
             if let Some(next_name) = next {
@@ -391,8 +494,8 @@ fn resolve_in_glob_use(tcx: TyCtxt, res: &Res, name: &str) -> RelativeResolution
     }
 }
 
-/// Resolves a method in a type. It currently does not resolve trait methods
-/// (see <https://github.com/model-checking/kani/issues/1997>).
+/// Resolves a method in a type. Trait methods are resolved with a qualified path instead, see
+/// [resolve_trait_method].
 fn resolve_in_type<'tcx>(
     tcx: TyCtxt<'tcx>,
     type_id: DefId,
@@ -415,3 +518,21 @@ fn resolve_in_type<'tcx>(
             unresolved: name.to_string(),
         })
 }
+
+/// Resolves an item declared in a trait, e.g., the declaration of a trait method.
+fn resolve_in_trait<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    trait_id: DefId,
+    name: &str,
+) -> Result<DefId, ResolveError<'tcx>> {
+    debug!(?name, ?trait_id, "resolve_in_trait");
+    tcx.associated_items(trait_id)
+        .in_definition_order()
+        .find(|item| item.name.as_str() == name)
+        .map(|item| item.def_id)
+        .ok_or_else(|| ResolveError::MissingItem {
+            tcx,
+            base: trait_id,
+            unresolved: name.to_string(),
+        })
+}
@@ -0,0 +1,426 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module finds the branches and memory accesses that depend on secrets for
+//! `--constant-time-checks`. Secrets are the values returned by `kani::secret`.
+//!
+//! We run a taint analysis over the items that are reachable from a harness. Every local of
+//! every function instance has two nodes in a graph: one for its value and one for the memory it
+//! points to, if it is a pointer. This way, the length of a slice of secret bytes isn't a secret,
+//! but the bytes it points to are. Assignments and calls add edges from the nodes that are read
+//! to the nodes that are written, and borrows make the pointee of the borrow and the borrowed
+//! place the same memory, i.e., add edges in both directions. The analysis is flow and context
+//! insensitive, so it may report accesses that don't actually depend on a secret, but it doesn't
+//! miss any that the harness reaches through code with a body.
+//!
+//! Codegen then adds a failing check of class `constant_time` to every branch on a tainted value,
+//! and to every memory access at a tainted index or offset.
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::{
+    BinOp, Body, Local, Location, NonDivergingIntrinsic, Operand, Place, ProjectionElem, Rvalue,
+    StatementKind, TerminatorKind, RETURN_PLACE,
+};
+use rustc_middle::ty::{self, EarlyBinder, Instance, ParamEnv, Ty, TyCtxt};
+use rustc_span::Symbol;
+use std::collections::{BTreeMap, HashMap};
+
+/// An operation whose timing may reveal a secret.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SecretSink {
+    /// A branch on a value that depends on a secret.
+    Branch,
+    /// A memory access at an index or offset that depends on a secret.
+    Index,
+}
+
+impl SecretSink {
+    pub fn description(&self) -> &'static str {
+        match self {
+            SecretSink::Branch => "branch on a secret value",
+            SecretSink::Index => "memory access at an index that depends on a secret value",
+        }
+    }
+}
+
+/// The operations that depend on a secret in each function instance.
+#[derive(Default)]
+pub struct SecretSinks<'tcx> {
+    sinks: HashMap<Instance<'tcx>, BTreeMap<Location, SecretSink>>,
+}
+
+impl<'tcx> SecretSinks<'tcx> {
+    /// Find the operations that depend on a secret in the functions among `items`.
+    pub fn new(tcx: TyCtxt<'tcx>, items: &[MonoItem<'tcx>]) -> SecretSinks<'tcx> {
+        let Some(secret) = tcx.get_diagnostic_item(Symbol::intern("KaniSecret")) else {
+            return SecretSinks::default();
+        };
+        let instances: Vec<_> = items
+            .iter()
+            .filter_map(
+                |item| if let MonoItem::Fn(instance) = item { Some(*instance) } else { None },
+            )
+            .collect();
+        if !instances.iter().any(|instance| instance.def_id() == secret) {
+            return SecretSinks::default();
+        }
+        let mut graph = TaintGraph::new(tcx, secret, &instances);
+        for instance in &instances {
+            graph.add_body(*instance);
+        }
+        let tainted = graph.propagate();
+        let mut sinks = SecretSinks::default();
+        for candidate in graph.candidates {
+            if candidate.nodes.iter().any(|node| tainted[*node]) {
+                sinks
+                    .sinks
+                    .entry(candidate.instance)
+                    .or_default()
+                    .entry(candidate.location)
+                    .or_insert(candidate.sink);
+            }
+        }
+        sinks
+    }
+
+    /// The operation at `location` of `instance` that depends on a secret, if any.
+    pub fn get(&self, instance: Instance<'tcx>, location: Location) -> Option<SecretSink> {
+        self.sinks.get(&instance)?.get(&location).copied()
+    }
+}
+
+/// An operation that depends on a secret if any of `nodes` is tainted.
+struct Candidate<'tcx> {
+    instance: Instance<'tcx>,
+    location: Location,
+    sink: SecretSink,
+    nodes: Vec<usize>,
+}
+
+struct TaintGraph<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    /// The definition of `kani::secret`.
+    secret: DefId,
+    /// The index of the first node of each instance.
+    offsets: HashMap<Instance<'tcx>, usize>,
+    edges: Vec<Vec<usize>>,
+    sources: Vec<usize>,
+    candidates: Vec<Candidate<'tcx>>,
+}
+
+impl<'tcx> TaintGraph<'tcx> {
+    fn new(tcx: TyCtxt<'tcx>, secret: DefId, instances: &[Instance<'tcx>]) -> Self {
+        let mut offsets = HashMap::new();
+        let mut size = 0;
+        for instance in instances {
+            offsets.insert(*instance, size);
+            size += 2 * tcx.instance_mir(instance.def).local_decls.len();
+        }
+        TaintGraph {
+            tcx,
+            secret,
+            offsets,
+            edges: vec![vec![]; size],
+            sources: vec![],
+            candidates: vec![],
+        }
+    }
+
+    fn add_body(&mut self, instance: Instance<'tcx>) {
+        let body = self.tcx.instance_mir(instance.def);
+        let offset = self.offsets[&instance];
+        let mut builder = BodyTaint { graph: self, instance, body, offset };
+        for (block, data) in body.basic_blocks.iter_enumerated() {
+            for (statement_index, statement) in data.statements.iter().enumerate() {
+                let location = Location { block, statement_index };
+                builder.add_statement(&statement.kind, location);
+                builder.add_index_candidates(location, |visitor| {
+                    visitor.visit_statement(statement, location)
+                });
+            }
+            let terminator = data.terminator();
+            let location = body.terminator_loc(block);
+            builder.add_terminator(&terminator.kind, location);
+            builder.add_index_candidates(location, |visitor| {
+                visitor.visit_terminator(terminator, location)
+            });
+        }
+    }
+
+    /// The nodes that are reachable from a secret.
+    fn propagate(&self) -> Vec<bool> {
+        let mut tainted = vec![false; self.edges.len()];
+        let mut worklist = self.sources.clone();
+        while let Some(node) = worklist.pop() {
+            if tainted[node] {
+                continue;
+            }
+            tainted[node] = true;
+            worklist.extend(self.edges[node].iter().filter(|next| !tainted[**next]));
+        }
+        tainted
+    }
+}
+
+/// Adds the edges of one function instance to the graph.
+struct BodyTaint<'a, 'tcx> {
+    graph: &'a mut TaintGraph<'tcx>,
+    instance: Instance<'tcx>,
+    body: &'tcx Body<'tcx>,
+    offset: usize,
+}
+
+impl<'a, 'tcx> BodyTaint<'a, 'tcx> {
+    fn value(&self, local: Local) -> usize {
+        value_node(self.offset, local)
+    }
+
+    fn pointee(&self, local: Local) -> usize {
+        pointee_node(self.offset, local)
+    }
+
+    /// The node of the memory that `place` refers to.
+    fn place_node(&self, place: &Place<'tcx>) -> usize {
+        if place.is_indirect() { self.pointee(place.local) } else { self.value(place.local) }
+    }
+
+    /// The nodes that reading `place` depends on: the memory it refers to and its indices.
+    fn place_sources(&self, place: &Place<'tcx>) -> Vec<usize> {
+        let mut sources = vec![self.place_node(place)];
+        sources.extend(place.projection.iter().filter_map(|elem| match elem {
+            ProjectionElem::Index(local) => Some(self.value(local)),
+            _ => None,
+        }));
+        sources
+    }
+
+    fn operand_sources(&self, operand: &Operand<'tcx>) -> Vec<usize> {
+        operand.place().map(|place| self.place_sources(&place)).unwrap_or_default()
+    }
+
+    /// The node of the memory that the value of `operand` points to, if it is a place.
+    fn operand_pointee(&self, operand: &Operand<'tcx>) -> Option<usize> {
+        operand.place().map(|place| self.pointee(place.local))
+    }
+
+    fn flow(&mut self, from: impl IntoIterator<Item = usize>, to: usize) {
+        for node in from {
+            self.graph.edges[node].push(to);
+        }
+    }
+
+    /// Make `first` and `second` the same memory.
+    fn alias(&mut self, first: usize, second: Option<usize>) {
+        if let Some(second) = second {
+            self.graph.edges[first].push(second);
+            self.graph.edges[second].push(first);
+        }
+    }
+
+    fn candidate(&mut self, location: Location, sink: SecretSink, nodes: Vec<usize>) {
+        if !nodes.is_empty() {
+            let instance = self.instance;
+            self.graph.candidates.push(Candidate { instance, location, sink, nodes });
+        }
+    }
+
+    fn add_statement(&mut self, kind: &StatementKind<'tcx>, location: Location) {
+        match kind {
+            StatementKind::Assign(assign) => {
+                let (dest, rvalue) = &**assign;
+                self.add_assign(dest, rvalue, location)
+            }
+            StatementKind::Intrinsic(intrinsic) => {
+                if let NonDivergingIntrinsic::CopyNonOverlapping(copy) = &**intrinsic {
+                    if let (Some(src), Some(dst)) =
+                        (self.operand_pointee(&copy.src), self.operand_pointee(&copy.dst))
+                    {
+                        self.flow([src], dst);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn add_assign(&mut self, dest: &Place<'tcx>, rvalue: &Rvalue<'tcx>, location: Location) {
+        let target = self.place_node(dest);
+        let dest_pointee = self.pointee(dest.local);
+        match rvalue {
+            Rvalue::Use(operand)
+            | Rvalue::Repeat(operand, _)
+            | Rvalue::Cast(_, operand, _)
+            | Rvalue::UnaryOp(_, operand)
+            | Rvalue::ShallowInitBox(operand, _) => {
+                self.flow(self.operand_sources(operand), target);
+                self.alias(dest_pointee, self.operand_pointee(operand));
+            }
+            Rvalue::CopyForDeref(place) | Rvalue::Discriminant(place) => {
+                self.flow(self.place_sources(place), target);
+                self.alias(dest_pointee, Some(self.pointee(place.local)));
+            }
+            Rvalue::Ref(_, _, place) | Rvalue::AddressOf(_, place) => {
+                // The address depends on the indices of the place.
+                let mut sources = self.place_sources(place);
+                sources.remove(0);
+                self.flow(sources, target);
+                self.alias(dest_pointee, Some(self.place_node(place)));
+            }
+            Rvalue::Len(place) => {
+                // Only the length of a slice behind a pointer is stored in a local.
+                if place.is_indirect() {
+                    self.flow([self.value(place.local)], target);
+                }
+            }
+            Rvalue::BinaryOp(op, operands) | Rvalue::CheckedBinaryOp(op, operands) => {
+                let (lhs, rhs) = &**operands;
+                self.flow(self.operand_sources(lhs), target);
+                self.flow(self.operand_sources(rhs), target);
+                if *op == BinOp::Offset {
+                    self.alias(dest_pointee, self.operand_pointee(lhs));
+                    self.candidate(location, SecretSink::Index, self.operand_sources(rhs));
+                }
+            }
+            Rvalue::Aggregate(_, operands) => {
+                for operand in operands {
+                    self.flow(self.operand_sources(operand), target);
+                    self.alias(dest_pointee, self.operand_pointee(operand));
+                }
+            }
+            Rvalue::NullaryOp(..) | Rvalue::ThreadLocalRef(_) => {}
+        }
+    }
+
+    fn add_terminator(&mut self, kind: &TerminatorKind<'tcx>, location: Location) {
+        match kind {
+            TerminatorKind::SwitchInt { discr, .. } => {
+                self.candidate(location, SecretSink::Branch, self.operand_sources(discr));
+            }
+            TerminatorKind::Call { func, args, destination, .. } => {
+                self.add_call(func, args, destination)
+            }
+            _ => {}
+        }
+    }
+
+    fn add_call(&mut self, func: &Operand<'tcx>, args: &[Operand<'tcx>], dest: &Place<'tcx>) {
+        let target = self.place_node(dest);
+        let dest_pointee = self.pointee(dest.local);
+        let callee = match self.monomorphize(func.ty(self.body, self.graph.tcx)).kind() {
+            ty::FnDef(def_id, fn_args) => {
+                Instance::resolve(self.graph.tcx, ParamEnv::reveal_all(), *def_id, fn_args)
+                    .ok()
+                    .flatten()
+            }
+            _ => None,
+        };
+        if callee.is_some_and(|callee| callee.def_id() == self.graph.secret) {
+            // Only the data behind pointers is secret, not the pointers and the lengths.
+            let secret_ty = self.monomorphize(dest.ty(self.body, self.graph.tcx).ty);
+            let source =
+                if has_pointers(self.graph.tcx, secret_ty, 0) { dest_pointee } else { target };
+            self.graph.sources.push(source);
+            self.alias(dest_pointee, args.first().and_then(|arg| self.operand_pointee(arg)));
+            return;
+        }
+        let Some((callee, offset)) =
+            callee.and_then(|callee| Some((callee, *self.graph.offsets.get(&callee)?)))
+        else {
+            // Without a body, anything that the arguments point to may flow to the result and to
+            // the memory behind the arguments.
+            let mut sources = vec![];
+            for arg in args {
+                sources.extend(self.operand_sources(arg));
+                sources.extend(self.operand_pointee(arg));
+            }
+            self.flow(sources.clone(), target);
+            self.flow(sources.clone(), dest_pointee);
+            for arg in args {
+                if let Some(pointee) = self.operand_pointee(arg) {
+                    self.flow(sources.clone(), pointee);
+                }
+            }
+            return;
+        };
+        let callee_body = self.graph.tcx.instance_mir(callee.def);
+        let params: Vec<_> = callee_body
+            .args_iter()
+            .map(|param| (value_node(offset, param), pointee_node(offset, param)))
+            .collect();
+        let ret = (value_node(offset, RETURN_PLACE), pointee_node(offset, RETURN_PLACE));
+        // Closures and some shims take their arguments as a tuple, so the arguments can't be
+        // matched one by one.
+        let pairwise = callee_body.spread_arg.is_none() && params.len() == args.len();
+        for (idx, arg) in args.iter().enumerate() {
+            for (param_idx, (value, pointee)) in params.iter().enumerate() {
+                if pairwise && idx != param_idx {
+                    continue;
+                }
+                self.flow(self.operand_sources(arg), *value);
+                self.alias(*pointee, self.operand_pointee(arg));
+            }
+        }
+        self.flow([ret.0], target);
+        self.alias(dest_pointee, Some(ret.1));
+    }
+
+    /// Add a candidate for the places in a statement or terminator that are indexed by a local.
+    fn add_index_candidates(&mut self, location: Location, visit: impl FnOnce(&mut IndexLocals)) {
+        let mut visitor = IndexLocals(vec![]);
+        visit(&mut visitor);
+        let nodes = visitor.0.into_iter().map(|local| self.value(local)).collect();
+        self.candidate(location, SecretSink::Index, nodes);
+    }
+
+    fn monomorphize(&self, ty: Ty<'tcx>) -> Ty<'tcx> {
+        self.instance.instantiate_mir_and_normalize_erasing_regions(
+            self.graph.tcx,
+            ParamEnv::reveal_all(),
+            EarlyBinder::bind(ty),
+        )
+    }
+}
+
+/// The node of the value of `local` in the function whose first node is `offset`.
+fn value_node(offset: usize, local: Local) -> usize {
+    offset + 2 * local.as_usize()
+}
+
+/// The node of the memory that `local` points to in the function whose first node is `offset`.
+fn pointee_node(offset: usize, local: Local) -> usize {
+    offset + 2 * local.as_usize() + 1
+}
+
+/// Collects the locals that index a place.
+struct IndexLocals(Vec<Local>);
+
+impl<'tcx> Visitor<'tcx> for IndexLocals {
+    fn visit_place(&mut self, place: &Place<'tcx>, _context: PlaceContext, _location: Location) {
+        self.0.extend(place.projection.iter().filter_map(|elem| match elem {
+            ProjectionElem::Index(local) => Some(local),
+            _ => None,
+        }));
+    }
+}
+
+/// Whether a value of type `ty` contains pointers. We give up on deeply nested types and assume
+/// that they do.
+fn has_pointers<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>, depth: usize) -> bool {
+    if depth > 8 {
+        return true;
+    }
+    match ty.kind() {
+        ty::Ref(..) | ty::RawPtr(_) | ty::FnPtr(_) | ty::Dynamic(..) => true,
+        ty::Adt(def, _) if def.is_box() => true,
+        ty::Adt(def, args) => {
+            def.all_fields().any(|field| has_pointers(tcx, field.ty(tcx, args), depth + 1))
+        }
+        ty::Array(elem, _) | ty::Slice(elem) => has_pointers(tcx, *elem, depth + 1),
+        ty::Tuple(elems) => elems.iter().any(|elem| has_pointers(tcx, elem, depth + 1)),
+        ty::Closure(_, args) => {
+            args.as_closure().upvar_tys().iter().any(|ty| has_pointers(tcx, ty, depth + 1))
+        }
+        _ => false,
+    }
+}
@@ -4,12 +4,16 @@
 
 use std::collections::BTreeMap;
 
-use kani_metadata::Stub;
+use kani_metadata::{Stub, TraitImplStub};
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_hir::definitions::DefPathHash;
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::{AssocKind, Binder, GenericArgs, Ty, TyCtxt};
 
-use crate::kani_middle::resolve::resolve_fn;
+use super::Stubs;
+use crate::kani_middle::resolve::{
+    is_qualified_path, resolve_fn, resolve_trait_impl, resolve_trait_method, ResolveError,
+    TraitMethod,
+};
 
 /// Given a `kani::stub` attribute, tries to extract a pair of paths (the
 /// original function/method, and its stub). Returns `None` and errors if the
@@ -36,30 +40,214 @@ fn stub_def_ids(tcx: TyCtxt, harness: LocalDefId, stub: &Stub) -> Option<(DefId,
     Some((orig?, stub?))
 }
 
-/// Updates the running map `stub_pairs` that maps a function/method to its
+/// Updates the running map `stubs` that maps a function/method to its
 /// stub. Errors if a function/method is mapped more than once.
-pub fn update_stub_mapping(
+///
+/// A default trait method named by a qualified path `<Type as Trait>::method` is only stubbed
+/// for `Type`, so it is mapped separately, together with the type.
+///
+/// An original that is a single identifier which doesn't resolve to any item is the symbol name of
+/// a foreign function, so that every declaration of the function is stubbed.
+pub fn update_stub_mapping(tcx: TyCtxt, harness: LocalDefId, stub: &Stub, stubs: &mut Stubs) {
+    if is_foreign_symbol(tcx, harness, &stub.original) {
+        let current_module = tcx.parent_module_from_def_id(harness).to_local_def_id();
+        match resolve_fn(tcx, current_module, &stub.replacement) {
+            Ok(stub_id) => {
+                let stub_hash = tcx.def_path_hash(stub_id);
+                if stubs
+                    .foreign_symbols
+                    .insert(stub.original.clone(), stub_hash)
+                    .is_some_and(|other| other != stub_hash)
+                {
+                    tcx.sess.span_err(
+                        tcx.def_span(harness),
+                        format!(
+                            "duplicate stub mapping: `{}` has more than one stub",
+                            stub.original
+                        ),
+                    );
+                }
+            }
+            Err(err) => {
+                tcx.sess.span_err(
+                    tcx.def_span(harness),
+                    format!("failed to resolve `{}`: {err}", stub.replacement),
+                );
+            }
+        }
+        return;
+    }
+    if is_qualified_path(&stub.original) {
+        let current_module = tcx.parent_module_from_def_id(harness).to_local_def_id();
+        if let Ok(TraitMethod::Default { method, impl_id }) =
+            resolve_trait_method(tcx, current_module, &stub.original)
+        {
+            let Some((_, stub_id)) = stub_def_ids(tcx, harness, stub) else { return };
+            if check_default_method_stub(tcx, harness, method, impl_id, stub_id) {
+                let self_ty = tcx.type_of(impl_id).instantiate_identity().ty_adt_def().unwrap();
+                let key = (tcx.def_path_hash(method), tcx.def_path_hash(self_ty.did()));
+                let stub_hash = tcx.def_path_hash(stub_id);
+                if stubs
+                    .default_methods
+                    .insert(key, stub_hash)
+                    .is_some_and(|other| other != stub_hash)
+                {
+                    tcx.sess.span_err(
+                        tcx.def_span(harness),
+                        format!(
+                            "duplicate stub mapping: `{}` has more than one stub",
+                            stub.original
+                        ),
+                    );
+                }
+            }
+            return;
+        }
+    }
+    if let Some((orig_id, stub_id)) = stub_def_ids(tcx, harness, stub) {
+        insert_stub_pair(tcx, harness, orig_id, stub_id, &mut stubs.fns);
+    }
+}
+
+/// Whether `name` is the symbol name of a foreign function rather than a path, i.e., an identifier
+/// that doesn't resolve to any item from the harness.
+fn is_foreign_symbol(tcx: TyCtxt, harness: LocalDefId, name: &str) -> bool {
+    let is_identifier = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    let current_module = tcx.parent_module_from_def_id(harness).to_local_def_id();
+    is_identifier
+        && matches!(resolve_fn(tcx, current_module, name), Err(ResolveError::MissingItem { .. }))
+}
+
+/// Checks whether `stub` can replace the default trait `method` for the `Self` type of the
+/// implementation `impl_id`, i.e., whether it has the signature of the method once `Self` is
+/// replaced by that type. The stub has the generic parameters of the method, if any.
+///
+/// Only implementations that are not generic are supported, since the stub would need the
+/// generic parameters of the implementation as well.
+fn check_default_method_stub(
+    tcx: TyCtxt,
+    harness: LocalDefId,
+    method: DefId,
+    impl_id: DefId,
+    stub: DefId,
+) -> bool {
+    let method_name = tcx.def_path_str(method);
+    let impl_name = format!(
+        "impl {} for {}",
+        tcx.def_path_str(tcx.parent(method)),
+        tcx.type_of(impl_id).instantiate_identity()
+    );
+    if tcx.generics_of(impl_id).count() > 0 || tcx.generics_of(tcx.parent(method)).count() > 1 {
+        tcx.sess.span_err(
+            tcx.def_span(harness),
+            format!(
+                "cannot stub the default method `{method_name}` of `{impl_name}`: only \
+                implementations without generic parameters are supported"
+            ),
+        );
+        return false;
+    }
+    let self_ty = tcx.type_of(impl_id).instantiate_identity();
+    let args = tcx.mk_args_from_iter(
+        std::iter::once(self_ty.into()).chain(GenericArgs::identity_for_item(tcx, stub).iter()),
+    );
+    if tcx.generics_of(method).count() != args.len() {
+        tcx.sess.span_err(
+            tcx.def_span(stub),
+            format!(
+                "mismatch in the number of generic parameters: default method `{method_name}` \
+                takes {} generic parameter(s), stub `{}` takes {}",
+                tcx.generics_of(method).count() - 1,
+                tcx.def_path_str(stub),
+                tcx.generics_of(stub).count()
+            ),
+        );
+        return false;
+    }
+    let erase = |sig| tcx.erase_regions(tcx.erase_late_bound_regions(sig));
+    let expected = erase(tcx.fn_sig(method).instantiate(tcx, args));
+    let actual = erase(tcx.fn_sig(stub).instantiate_identity());
+    if expected.inputs_and_output != actual.inputs_and_output {
+        let fn_ptr = |sig| Ty::new_fn_ptr(tcx, Binder::dummy(sig));
+        tcx.sess.span_err(
+            tcx.def_span(stub),
+            format!(
+                "signature differs: stub `{}` has type `{}` where default method `{method_name}` \
+                of `{impl_name}` has type `{}`",
+                tcx.def_path_str(stub),
+                fn_ptr(actual),
+                fn_ptr(expected)
+            ),
+        );
+        return false;
+    }
+    true
+}
+
+/// Updates the running map `stub_pairs` with every method defined in the
+/// implementation of a trait for a type. Each method is mapped to the function
+/// with the same name inside the replacement module or type. Methods that the
+/// implementation inherits from the trait's default methods are not stubbed.
+pub fn update_trait_impl_stub_mapping(
     tcx: TyCtxt,
     harness: LocalDefId,
-    stub: &Stub,
+    stub: &TraitImplStub,
     stub_pairs: &mut BTreeMap<DefPathHash, DefPathHash>,
 ) {
-    if let Some((orig_id, stub_id)) = stub_def_ids(tcx, harness, stub) {
-        let orig_hash = tcx.def_path_hash(orig_id);
-        let stub_hash = tcx.def_path_hash(stub_id);
-        let other_opt = stub_pairs.insert(orig_hash, stub_hash);
-        if let Some(other) = other_opt {
-            if other != stub_hash {
+    let current_module = tcx.parent_module_from_def_id(harness).to_local_def_id();
+    let impl_desc = format!("impl {} for {}", stub.trait_name, stub.self_type);
+    let impl_id = match resolve_trait_impl(tcx, current_module, &stub.trait_name, &stub.self_type) {
+        Ok(impl_id) => impl_id,
+        Err(err) => {
+            tcx.sess
+                .span_err(tcx.def_span(harness), format!("failed to resolve `{impl_desc}`: {err}"));
+            return;
+        }
+    };
+    for item in tcx.associated_items(impl_id).in_definition_order() {
+        if item.kind != AssocKind::Fn {
+            continue;
+        }
+        let name = format!("{}::{}", stub.replacement, item.name);
+        match resolve_fn(tcx, current_module, &name) {
+            Ok(stub_id) => {
+                tracing::debug!(?stub_id, "Resolved {name} to {}", tcx.def_path_str(stub_id));
+                insert_stub_pair(tcx, harness, item.def_id, stub_id, stub_pairs);
+            }
+            Err(err) => {
                 tcx.sess.span_err(
                     tcx.def_span(harness),
-                    format!(
-                        "duplicate stub mapping: {} mapped to {} and {}",
-                        tcx.def_path_str(orig_id),
-                        tcx.def_path_str(stub_id),
-                        tcx.def_path_str(tcx.def_path_hash_to_def_id(other, &mut || panic!()))
-                    ),
+                    format!("failed to stub method `{}` of `{impl_desc}`: {err}", item.name),
                 );
             }
         }
     }
 }
+
+/// Maps `orig_id` to `stub_id`, reporting an error if `orig_id` was already
+/// mapped to a different stub.
+fn insert_stub_pair(
+    tcx: TyCtxt,
+    harness: LocalDefId,
+    orig_id: DefId,
+    stub_id: DefId,
+    stub_pairs: &mut BTreeMap<DefPathHash, DefPathHash>,
+) {
+    let orig_hash = tcx.def_path_hash(orig_id);
+    let stub_hash = tcx.def_path_hash(stub_id);
+    let other_opt = stub_pairs.insert(orig_hash, stub_hash);
+    if let Some(other) = other_opt {
+        if other != stub_hash {
+            tcx.sess.span_err(
+                tcx.def_span(harness),
+                format!(
+                    "duplicate stub mapping: {} mapped to {} and {}",
+                    tcx.def_path_str(orig_id),
+                    tcx.def_path_str(stub_id),
+                    tcx.def_path_str(tcx.def_path_hash_to_def_id(other, &mut || panic!()))
+                ),
+            );
+        }
+    }
+}
@@ -4,27 +4,140 @@
 
 mod annotations;
 mod transform;
+mod usage;
 
 use std::collections::BTreeMap;
 
-use kani_metadata::HarnessMetadata;
-use rustc_hir::def_id::DefId;
+use kani_metadata::{HarnessMetadata, Stub};
+use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_hir::definitions::DefPathHash;
 use rustc_middle::ty::TyCtxt;
 pub use transform::*;
+pub use usage::stub_usage;
 
-use self::annotations::update_stub_mapping;
+use self::annotations::{update_stub_mapping, update_trait_impl_stub_mapping};
+use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::resolve::resolve_fn;
+
+/// The stubs that `--use-abstractions` adds to every harness, which replace functions of the
+/// standard library with the models of `kani::abstractions`.
+const ABSTRACTIONS: [(&str, &str); 2] = [
+    ("::alloc::vec::Vec::new", "::kani::abstractions::vec_new"),
+    (
+        "<::std::collections::hash_map::RandomState as ::core::hash::BuildHasher>::hash_one",
+        "::kani::abstractions::hash_one",
+    ),
+];
+
+/// The stubs that `--race-checks` adds to every harness, which replace the threads and mutexes of
+/// the standard library with the models of `kani::thread`.
+const THREAD_MODELS: [(&str, &str); 5] = [
+    ("::std::thread::spawn", "::kani::thread::std_spawn"),
+    ("::std::thread::JoinHandle::join", "::kani::thread::std_join"),
+    ("::std::thread::JoinHandle::is_finished", "::kani::thread::std_is_finished"),
+    ("::std::sync::Mutex::lock", "::kani::thread::std_lock"),
+    ("pthread_detach", "::kani::thread::pthread_detach"),
+];
+
+/// The stubs of a harness. The items are identified by their `DefPathHash`, which is stable
+/// across compilation sessions.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Stubs {
+    /// Maps each function/method to its stub.
+    pub fns: BTreeMap<DefPathHash, DefPathHash>,
+    /// Maps a default trait method and the type whose trait implementation inherits it to the
+    /// stub of the method for that type.
+    pub default_methods: BTreeMap<(DefPathHash, DefPathHash), DefPathHash>,
+    /// Maps the symbol name of foreign functions to their stub, for every declaration of the
+    /// function.
+    pub foreign_symbols: BTreeMap<String, DefPathHash>,
+}
+
+impl Stubs {
+    pub fn is_empty(&self) -> bool {
+        self.fns.is_empty() && self.default_methods.is_empty() && self.foreign_symbols.is_empty()
+    }
+}
+
+/// The stubs of `--use-abstractions` for `harness`. The models that need the standard library
+/// are skipped if the Kani library was built without it.
+pub fn abstraction_stubs(tcx: TyCtxt, harness: LocalDefId) -> Vec<Stub> {
+    model_stubs(tcx, harness, &ABSTRACTIONS)
+}
+
+/// The stubs of `--race-checks` for `harness`. Like for `--use-abstractions`, the models that
+/// aren't available in the Kani library, e.g., the ones that are specific to Linux, are skipped.
+pub fn thread_stubs(tcx: TyCtxt, harness: LocalDefId) -> Vec<Stub> {
+    model_stubs(tcx, harness, &THREAD_MODELS)
+}
+
+/// The stubs of `harness` that replace each original item of `models` with its model, if the
+/// model can be resolved.
+fn model_stubs(tcx: TyCtxt, harness: LocalDefId, models: &[(&str, &str)]) -> Vec<Stub> {
+    let current_module = tcx.parent_module_from_def_id(harness).to_local_def_id();
+    models
+        .iter()
+        .filter(|(_, model)| resolve_fn(tcx, current_module, model).is_ok())
+        .map(|(original, model)| Stub {
+            original: original.to_string(),
+            replacement: model.to_string(),
+        })
+        .collect()
+}
+
+/// The stubs of `--contract-spec` for `harness`, which replace each function with the contract
+/// of its spec function, like `stub_verified` does for a function with a contract. The spec
+/// functions are resolved relative to the module of the harness.
+pub fn contract_spec_stubs(tcx: TyCtxt, harness: LocalDefId, specs: &[Stub]) -> Vec<Stub> {
+    let current_module = tcx.parent_module_from_def_id(harness).to_local_def_id();
+    let mut stubs = vec![];
+    for spec in specs {
+        let spec_fn = match resolve_fn(tcx, current_module, &spec.replacement) {
+            Ok(spec_fn) => spec_fn,
+            Err(err) => {
+                tcx.sess.err(format!(
+                    "Failed to resolve the spec function `{}` of `{}`: {err}",
+                    spec.replacement, spec.original
+                ));
+                continue;
+            }
+        };
+        let replace_name = match KaniAttributes::for_item(tcx, spec_fn).replaced_with() {
+            Some(Ok(replace_name)) => replace_name,
+            Some(Err(_)) => continue,
+            None => {
+                tcx.sess
+                    .struct_span_err(
+                        tcx.def_span(spec_fn),
+                        format!(
+                            "The spec function `{}` of `{}` has no contract.",
+                            spec.replacement, spec.original
+                        ),
+                    )
+                    .help("Add a `requires`, `ensures` or `modifies` attribute to this function.")
+                    .emit();
+                continue;
+            }
+        };
+        // The function that implements the contract is a sibling of the spec function.
+        let replacement = match spec.replacement.rsplit_once("::") {
+            Some((parent, _)) => format!("{parent}::{replace_name}"),
+            None => replace_name.to_string(),
+        };
+        stubs.push(Stub { original: spec.original.clone(), replacement });
+    }
+    stubs
+}
 
 /// Collects the stubs from the harnesses in a crate.
-pub fn harness_stub_map(
-    tcx: TyCtxt,
-    harness: DefId,
-    metadata: &HarnessMetadata,
-) -> BTreeMap<DefPathHash, DefPathHash> {
+pub fn harness_stub_map(tcx: TyCtxt, harness: DefId, metadata: &HarnessMetadata) -> Stubs {
     let attrs = &metadata.attributes;
-    let mut stub_pairs = BTreeMap::default();
-    for stubs in &attrs.stubs {
-        update_stub_mapping(tcx, harness.expect_local(), stubs, &mut stub_pairs);
+    let mut stubs = Stubs::default();
+    for stub in &attrs.stubs {
+        update_stub_mapping(tcx, harness.expect_local(), stub, &mut stubs);
+    }
+    for stub in &attrs.trait_impl_stubs {
+        update_trait_impl_stub_mapping(tcx, harness.expect_local(), stub, &mut stubs.fns);
     }
-    stub_pairs
+    stubs
 }
@@ -6,7 +6,7 @@
 //! body of its stub, if appropriate. The stub mapping it uses is set via rustc
 //! arguments.
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::HashMap;
 
 use lazy_static::lazy_static;
 use regex::Regex;
@@ -16,10 +16,13 @@
 use rustc_middle::mir::{
     visit::MutVisitor, Body, Const, ConstValue, Local, LocalDecl, Location, Operand,
 };
-use rustc_middle::ty::{self, TyCtxt};
+use rustc_middle::ty::{self, Instance, InstanceDef, TyCtxt};
 
 use tracing::debug;
 
+use super::Stubs;
+use crate::kani_middle::attributes::is_function_contract_generated;
+
 /// Returns the `DefId` of the stub for the function/method identified by the
 /// parameter `def_id`, and `None` if the function/method is not stubbed.
 pub fn get_stub(tcx: TyCtxt, def_id: DefId) -> Option<DefId> {
@@ -44,14 +47,25 @@ pub fn transform<'tcx>(tcx: TyCtxt<'tcx>, def_id: DefId, old_body: &'tcx Body<'t
     old_body.clone()
 }
 
-/// Traverse `body` searching for calls to foreing functions and, whevever there is
-/// a stub available, replace the call to the foreign function with a call
-/// to its correspondent stub. This happens as a separate step because there is no
-/// body available to foreign functions at this stage.
-pub fn transform_foreign_functions<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
-    if let Some(stub_map) = get_stub_mapping(tcx) {
-        let mut visitor =
-            ForeignFunctionTransformer { tcx, local_decls: body.clone().local_decls, stub_map };
+/// Traverse `body` searching for calls to foreing functions and to trait methods, and, whevever
+/// there is a stub available, replace the call with a call to its correspondent stub. This happens
+/// as a separate step because there is no body available to foreign functions at this stage, and
+/// because a call to a trait method resolves to the method of an implementation only during
+/// monomorphization.
+///
+/// A trait method is only redirected to a stub that is declared in the same trait, which is the
+/// case of the functions generated for a contract on a trait method. The calls inside the
+/// functions generated for the contract of the method itself are left untouched, since its check
+/// function calls the method.
+pub fn transform_foreign_functions<'tcx>(tcx: TyCtxt<'tcx>, def_id: DefId, body: &mut Body<'tcx>) {
+    if let Some((stub_map, _, symbol_stubs)) = get_stub_mappings(tcx) {
+        let mut visitor = ForeignFunctionTransformer {
+            tcx,
+            caller: def_id,
+            local_decls: body.clone().local_decls,
+            stub_map,
+            symbol_stubs,
+        };
         visitor.visit_body(body);
     }
 }
@@ -59,10 +73,67 @@ pub fn transform_foreign_functions<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx
 struct ForeignFunctionTransformer<'tcx> {
     /// The compiler context.
     tcx: TyCtxt<'tcx>,
+    /// The function whose body is being transformed.
+    caller: DefId,
     /// Local declarations of the callee function. Kani searches here for foreign functions.
     local_decls: IndexVec<Local, LocalDecl<'tcx>>,
     /// Map of functions/methods to their correspondent stubs.
     stub_map: HashMap<DefId, DefId>,
+    /// Map of the symbol names of foreign functions to their stubs.
+    symbol_stubs: HashMap<String, DefId>,
+}
+
+impl<'tcx> ForeignFunctionTransformer<'tcx> {
+    /// Whether the function being transformed was generated for the contract of the trait method
+    /// `method`. The generated check method calls the method itself, so that call must not be
+    /// redirected.
+    fn is_contract_of(&self, method: DefId) -> bool {
+        is_function_contract_generated(self.tcx, self.caller)
+            && self.tcx.trait_of_item(self.caller) == self.tcx.trait_of_item(method)
+            && self
+                .tcx
+                .item_name(self.caller)
+                .as_str()
+                .starts_with(&format!("{}_", self.tcx.item_name(method)))
+    }
+
+    /// The stub of the foreign function `function` by its symbol name, if the stub has the
+    /// signature of the function.
+    fn symbol_stub(&self, function: DefId) -> Option<DefId> {
+        // The generic foreign items are the intrinsics, which don't have a symbol.
+        if self.tcx.generics_of(function).count() > 0 {
+            return None;
+        }
+        let symbol = self.tcx.symbol_name(Instance::mono(self.tcx, function)).name;
+        let stub = *self.symbol_stubs.get(symbol)?;
+        let erase = |def_id| {
+            let sig = self.tcx.fn_sig(def_id).instantiate_identity();
+            self.tcx.erase_regions(self.tcx.erase_late_bound_regions(sig))
+        };
+        let (expected, actual) = (erase(function), erase(stub));
+        if expected.inputs_and_output != actual.inputs_and_output
+            || expected.c_variadic != actual.c_variadic
+        {
+            self.tcx.sess.span_err(
+                self.tcx.def_span(stub),
+                format!(
+                    "signature differs: stub `{}` takes `({})` and returns `{}` where foreign \
+                    function `{symbol}` takes `({})` and returns `{}`",
+                    self.tcx.def_path_str(stub),
+                    join_types(actual.inputs()),
+                    actual.output(),
+                    join_types(expected.inputs()),
+                    expected.output(),
+                ),
+            );
+            return None;
+        }
+        Some(stub)
+    }
+}
+
+fn join_types(types: &[ty::Ty]) -> String {
+    types.iter().map(|ty| ty.to_string()).collect::<Vec<_>>().join(", ")
 }
 
 impl<'tcx> MutVisitor<'tcx> for ForeignFunctionTransformer<'tcx> {
@@ -73,16 +144,28 @@ fn tcx(&self) -> TyCtxt<'tcx> {
     fn visit_operand(&mut self, operand: &mut Operand<'tcx>, _location: Location) {
         let func_ty = operand.ty(&self.local_decls, self.tcx);
         if let ty::FnDef(reachable_function, arguments) = *func_ty.kind() {
-            if self.tcx.is_foreign_item(reachable_function) {
-                if let Some(stub) = self.stub_map.get(&reachable_function) {
-                    let Operand::Constant(function_definition) = operand else {
-                        return;
-                    };
-                    function_definition.const_ = Const::from_value(
-                        ConstValue::ZeroSized,
-                        self.tcx.type_of(stub).instantiate(self.tcx, arguments),
-                    );
-                }
+            let stub = if let Some(stub) = self.stub_map.get(&reachable_function) {
+                // Only trait methods stubbed by another method of the same trait are redirected,
+                // since the stub must accept the generic arguments of the call.
+                let is_trait_method_stub = self.tcx.trait_of_item(reachable_function).is_some()
+                    && self.tcx.trait_of_item(reachable_function) == self.tcx.trait_of_item(*stub)
+                    && !self.is_contract_of(reachable_function);
+                (self.tcx.is_foreign_item(reachable_function) || is_trait_method_stub)
+                    .then_some(*stub)
+            } else if self.tcx.is_foreign_item(reachable_function) && !self.symbol_stubs.is_empty()
+            {
+                self.symbol_stub(reachable_function)
+            } else {
+                None
+            };
+            if let Some(stub) = stub {
+                let Operand::Constant(function_definition) = operand else {
+                    return;
+                };
+                function_definition.const_ = Const::from_value(
+                    ConstValue::ZeroSized,
+                    self.tcx.type_of(stub).instantiate(self.tcx, arguments),
+                );
             }
         }
     }
@@ -162,45 +245,88 @@ fn check_compatibility<'a, 'tcx>(
     matches
 }
 
+/// Returns the instance of the stub of a default trait method for the `Self` type of
+/// `instance`, if the method is stubbed for that type. Otherwise, returns `instance`.
+///
+/// The default method is shared by every type whose implementation inherits it, so unlike other
+/// stubs, it can only be replaced once the `Self` type is known, i.e., when the call is resolved
+/// during monomorphization.
+pub fn stub_default_method<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> Instance<'tcx> {
+    let InstanceDef::Item(def_id) = instance.def else { return instance };
+    if tcx.trait_of_item(def_id).is_none() {
+        return instance;
+    }
+    let ty::Adt(self_ty, _) = instance.args.type_at(0).kind() else { return instance };
+    let Some(stubs) = get_default_method_stubs(tcx) else { return instance };
+    match stubs.get(&(def_id, self_ty.did())) {
+        Some(stub) => {
+            debug!(?instance, stub = tcx.def_path_debug_str(*stub), "stub_default_method");
+            // The stub takes the generic arguments of the method, without `Self`.
+            Instance::new(*stub, tcx.mk_args(&instance.args[1..]))
+        }
+        None => instance,
+    }
+}
+
 /// The prefix we will use when serializing the stub mapping as a rustc argument.
 const RUSTC_ARG_PREFIX: &str = "kani_stubs=";
 
+/// A `DefPathHash` serialized as a pair of `u64`s.
+type SerializedHash = (u64, u64);
+
+fn serialize_hash(hash: &DefPathHash) -> SerializedHash {
+    let (a, b) = hash.0.split();
+    (a.as_u64(), b.as_u64())
+}
+
 /// Serializes the stub mapping into a rustc argument.
-pub fn mk_rustc_arg(stub_mapping: &BTreeMap<DefPathHash, DefPathHash>) -> String {
-    // Serialize each `DefPathHash` as a pair of `u64`s, and the whole mapping
-    // as an association list.
-    let mut pairs = Vec::new();
-    for (k, v) in stub_mapping {
-        let (k_a, k_b) = k.0.split();
-        let kparts = (k_a.as_u64(), k_b.as_u64());
-        let (v_a, v_b) = v.0.split();
-        let vparts = (v_a.as_u64(), v_b.as_u64());
-        pairs.push((kparts, vparts));
-    }
+pub fn mk_rustc_arg(stubs: &Stubs) -> String {
+    // Serialize each `DefPathHash` as a pair of `u64`s, and each mapping as an association list.
+    let fns: Vec<_> =
+        stubs.fns.iter().map(|(k, v)| (serialize_hash(k), serialize_hash(v))).collect();
+    let default_methods: Vec<_> = stubs
+        .default_methods
+        .iter()
+        .map(|((method, ty), v)| ((serialize_hash(method), serialize_hash(ty)), serialize_hash(v)))
+        .collect();
+    let foreign_symbols: Vec<_> =
+        stubs.foreign_symbols.iter().map(|(k, v)| (k, serialize_hash(v))).collect();
     // Store our serialized mapping as a fake LLVM argument (safe to do since
     // LLVM will never see them).
-    format!("-Cllvm-args='{RUSTC_ARG_PREFIX}{}'", serde_json::to_string(&pairs).unwrap())
+    format!(
+        "-Cllvm-args='{RUSTC_ARG_PREFIX}{}'",
+        serde_json::to_string(&(fns, default_methods, foreign_symbols)).unwrap()
+    )
 }
 
-/// Deserializes the stub mapping from the rustc argument value.
-fn deserialize_mapping(tcx: TyCtxt, val: &str) -> HashMap<DefId, DefId> {
-    type Item = (u64, u64);
-    let item_to_def_id = |item: Item| -> DefId {
+/// The deserialized stub mappings: the stubs of functions/methods, the stubs of default trait
+/// methods for a `Self` type, and the stubs of foreign functions by symbol name.
+pub(super) type StubMappings =
+    (HashMap<DefId, DefId>, HashMap<(DefId, DefId), DefId>, HashMap<String, DefId>);
+
+/// Deserializes the stub mappings from the rustc argument value.
+fn deserialize_mapping(tcx: TyCtxt, val: &str) -> StubMappings {
+    let to_def_id = |item: SerializedHash| -> DefId {
         let hash = DefPathHash(Fingerprint::new(item.0, item.1));
         tcx.def_path_hash_to_def_id(hash, &mut || panic!())
     };
-    let pairs: Vec<(Item, Item)> = serde_json::from_str(val).unwrap();
-    let mut m = HashMap::default();
-    for (k, v) in pairs {
-        let kid = item_to_def_id(k);
-        let vid = item_to_def_id(v);
-        m.insert(kid, vid);
-    }
-    m
+    type Serialized = (
+        Vec<(SerializedHash, SerializedHash)>,
+        Vec<((SerializedHash, SerializedHash), SerializedHash)>,
+        Vec<(String, SerializedHash)>,
+    );
+    let (fns, default_methods, foreign_symbols): Serialized = serde_json::from_str(val).unwrap();
+    let fns = fns.into_iter().map(|(k, v)| (to_def_id(k), to_def_id(v))).collect();
+    let default_methods = default_methods
+        .into_iter()
+        .map(|((method, ty), v)| ((to_def_id(method), to_def_id(ty)), to_def_id(v)))
+        .collect();
+    let foreign_symbols = foreign_symbols.into_iter().map(|(k, v)| (k, to_def_id(v))).collect();
+    (fns, default_methods, foreign_symbols)
 }
 
-/// Retrieves the stub mapping from the compiler configuration.
-fn get_stub_mapping(tcx: TyCtxt) -> Option<HashMap<DefId, DefId>> {
+/// Retrieves the stub mappings from the compiler configuration.
+pub(super) fn get_stub_mappings(tcx: TyCtxt) -> Option<StubMappings> {
     // Use a static so that we compile the regex only once.
     lazy_static! {
         static ref RE: Regex = Regex::new(&format!("'{RUSTC_ARG_PREFIX}(.*)'")).unwrap();
@@ -212,3 +338,13 @@ fn get_stub_mapping(tcx: TyCtxt) -> Option<HashMap<DefId, DefId>> {
     }
     None
 }
+
+/// Retrieves the stub mapping of functions/methods from the compiler configuration.
+fn get_stub_mapping(tcx: TyCtxt) -> Option<HashMap<DefId, DefId>> {
+    get_stub_mappings(tcx).map(|(fns, _, _)| fns)
+}
+
+/// Retrieves the stub mapping of default trait methods from the compiler configuration.
+fn get_default_method_stubs(tcx: TyCtxt) -> Option<HashMap<(DefId, DefId), DefId>> {
+    get_stub_mappings(tcx).map(|(_, default_methods, _)| default_methods)
+}
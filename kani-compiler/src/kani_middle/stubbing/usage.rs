@@ -0,0 +1,117 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module finds the call sites that the stubs of a harness redirected, so that stubs that
+//! don't replace anything aren't silently ignored.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::transform::get_stub_mappings;
+use crate::kani_middle::span_location;
+use kani_metadata::StubUsage;
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::mir::TerminatorKind;
+use rustc_middle::ty::{self, EarlyBinder, Instance, ParamEnv, TyCtxt};
+
+/// The call sites of `items` that each stub of the current compilation redirected. Every stub is
+/// reported, including the ones that didn't redirect any call.
+pub fn stub_usage<'tcx>(tcx: TyCtxt<'tcx>, items: &[MonoItem<'tcx>]) -> Vec<StubUsage> {
+    let Some((fns, default_methods, foreign_symbols)) = get_stub_mappings(tcx) else {
+        return vec![];
+    };
+    // The stubs are identified by the name of the function they replace.
+    let fn_names: HashMap<DefId, String> =
+        fns.keys().map(|original| (*original, tcx.def_path_str(*original))).collect();
+    let default_method_names: HashMap<(DefId, DefId), String> = default_methods
+        .keys()
+        .map(|(method, self_ty)| {
+            let name = format!(
+                "<{} as {}>::{}",
+                tcx.def_path_str(*self_ty),
+                tcx.def_path_str(tcx.parent(*method)),
+                tcx.item_name(*method)
+            );
+            ((*method, *self_ty), name)
+        })
+        .collect();
+    // Calls to foreign functions and trait methods are redirected to the stub by the caller,
+    // instead of replacing the body of the original function.
+    let redirected: HashMap<DefId, &String> = fns
+        .iter()
+        .filter(|(original, stub)| {
+            tcx.is_foreign_item(**original)
+                || (tcx.trait_of_item(**original).is_some()
+                    && tcx.trait_of_item(**original) == tcx.trait_of_item(**stub))
+        })
+        .map(|(original, stub)| (*stub, &fn_names[original]))
+        .chain(foreign_symbols.iter().map(|(symbol, stub)| (*stub, symbol)))
+        .collect();
+
+    let mut call_sites: BTreeMap<&String, BTreeSet<String>> = BTreeMap::new();
+    for item in items {
+        let MonoItem::Fn(instance) = item else { continue };
+        // The body of a stubbed function is the body of its stub.
+        if fns.contains_key(&instance.def_id()) {
+            continue;
+        }
+        let body = tcx.instance_mir(instance.def);
+        for block in body.basic_blocks.iter() {
+            let TerminatorKind::Call { func, .. } = &block.terminator().kind else { continue };
+            let callee_ty = instance.instantiate_mir_and_normalize_erasing_regions(
+                tcx,
+                ParamEnv::reveal_all(),
+                EarlyBinder::bind(func.ty(body, tcx)),
+            );
+            let ty::FnDef(callee, args) = *callee_ty.kind() else { continue };
+            // The calls to a stubbed default method already resolve to its stub, so they're
+            // identified by the declaration of the method that they call instead.
+            let resolved = Instance::resolve(tcx, ParamEnv::reveal_all(), callee, args)
+                .ok()
+                .flatten()
+                .map(|resolved| resolved.def_id())
+                .unwrap_or(callee);
+            let key = if let Some(original) = redirected.get(&callee) {
+                *original
+            } else if let Some(original) = fn_names.get(&resolved) {
+                original
+            } else if let Some(original) = default_method_self_ty(tcx, callee, args)
+                .and_then(|self_ty| default_method_names.get(&(callee, self_ty)))
+            {
+                original
+            } else {
+                continue;
+            };
+            let caller = tcx.def_path_str(instance.def_id());
+            let location = span_location(tcx, block.terminator().source_info.span);
+            call_sites.entry(key).or_default().insert(format!("{caller} at {location}"));
+        }
+    }
+
+    let usage = |original: &String, stub: &DefId| StubUsage {
+        original: original.clone(),
+        replacement: tcx.def_path_str(*stub),
+        call_sites: call_sites
+            .get(original)
+            .map(|sites| sites.iter().cloned().collect())
+            .unwrap_or_default(),
+    };
+    let mut report: Vec<StubUsage> = fns
+        .iter()
+        .map(|(original, stub)| usage(&fn_names[original], stub))
+        .chain(default_methods.iter().map(|(key, stub)| usage(&default_method_names[key], stub)))
+        .chain(foreign_symbols.iter().map(|(symbol, stub)| usage(symbol, stub)))
+        .collect();
+    report.sort_by(|a, b| (&a.original, &a.replacement).cmp(&(&b.original, &b.replacement)));
+    report
+}
+
+/// The type whose implementation inherits the default trait method `method`, if it's one.
+fn default_method_self_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    method: DefId,
+    args: ty::GenericArgsRef<'tcx>,
+) -> Option<DefId> {
+    tcx.trait_of_item(method)?;
+    let ty::Adt(self_ty, _) = args.first()?.as_type()?.kind() else { return None };
+    Some(self_ty.did())
+}
@@ -0,0 +1,119 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module warns about symbolic values that are so large that they are likely to make the
+//! verification intractable, e.g., `kani::any::<[u8; 4096]>()`.
+//!
+//! Every byte of a symbolic value adds eight unconstrained bits to the formula, and every
+//! operation on the value has to take all of them into account. We estimate the size of the
+//! values created by `kani::any` and its variants in the functions of the local crate, which
+//! includes derived `Arbitrary` implementations, and warn if it exceeds
+//! [LARGE_SYMBOLIC_VALUE_BYTES].
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::mir::{Body, TerminatorKind};
+use rustc_middle::ty::{self, EarlyBinder, Instance, ParamEnv, Ty, TyCtxt};
+use rustc_span::{Span, Symbol};
+use std::collections::HashSet;
+
+/// The size in bytes above which a symbolic value is considered too large.
+const LARGE_SYMBOLIC_VALUE_BYTES: u64 = 1024;
+
+/// The functions of the Kani library that create symbolic values.
+const SYMBOLIC_VALUE_FUNCTIONS: [&str; 4] =
+    ["KaniAny", "KaniAnyWhere", "KaniAnyArray", "KaniAnyArrayWhere"];
+
+/// Warn about the calls that create large symbolic values in the local functions among `items`.
+pub fn check_symbolic_value_sizes<'tcx>(tcx: TyCtxt<'tcx>, items: &[MonoItem<'tcx>]) {
+    let functions: Vec<_> = SYMBOLIC_VALUE_FUNCTIONS
+        .iter()
+        .filter_map(|name| tcx.get_diagnostic_item(Symbol::intern(name)))
+        .collect();
+    if functions.is_empty() {
+        return;
+    }
+    // Warn once per call site, even if its function is instantiated several times.
+    let mut reported = HashSet::new();
+    for item in items {
+        let MonoItem::Fn(instance) = item else { continue };
+        if !instance.def_id().is_local() {
+            continue;
+        }
+        let body = tcx.instance_mir(instance.def);
+        for (span, ty) in symbolic_values(tcx, *instance, body, &functions) {
+            let size = symbolic_size(tcx, ty);
+            if size > LARGE_SYMBOLIC_VALUE_BYTES && reported.insert(span) {
+                warn_large_value(tcx, span, ty, size);
+            }
+        }
+    }
+}
+
+/// The span and the type of the symbolic values created in `body` by calls to `functions`.
+fn symbolic_values<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    body: &Body<'tcx>,
+    functions: &[DefId],
+) -> Vec<(Span, Ty<'tcx>)> {
+    let monomorphize = |ty: Ty<'tcx>| {
+        instance.instantiate_mir_and_normalize_erasing_regions(
+            tcx,
+            ParamEnv::reveal_all(),
+            EarlyBinder::bind(ty),
+        )
+    };
+    body.basic_blocks
+        .iter()
+        .filter_map(|block| {
+            let terminator = block.terminator();
+            let TerminatorKind::Call { func, destination, fn_span, .. } = &terminator.kind else {
+                return None;
+            };
+            let ty::FnDef(def_id, _) = *func.ty(body, tcx).kind() else { return None };
+            functions
+                .contains(&def_id)
+                .then(|| (*fn_span, monomorphize(destination.ty(body, tcx).ty)))
+        })
+        .collect()
+}
+
+/// Estimate how many bytes of a value of type `ty` are symbolic. This is the size of the type,
+/// except that the content of boxes counts as well, since `kani::any` generates it too.
+fn symbolic_size<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> u64 {
+    match ty.kind() {
+        ty::Array(elem, len) => {
+            let len = len.try_eval_target_usize(tcx, ParamEnv::reveal_all()).unwrap_or(0);
+            len.saturating_mul(symbolic_size(tcx, *elem))
+        }
+        ty::Tuple(elems) => elems.iter().map(|elem| symbolic_size(tcx, elem)).sum(),
+        ty::Adt(def, args) if def.is_box() => symbolic_size(tcx, args.type_at(0)),
+        ty::Adt(def, args) if def.is_struct() => def
+            .non_enum_variant()
+            .fields
+            .iter()
+            .map(|field| symbolic_size(tcx, field.ty(tcx, args)))
+            .sum(),
+        _ => tcx.layout_of(ParamEnv::reveal_all().and(ty)).map_or(0, |layout| layout.size.bytes()),
+    }
+}
+
+fn warn_large_value(tcx: TyCtxt, span: Span, ty: Ty, size: u64) {
+    let mut diag = tcx.sess.struct_span_warn(
+        span,
+        format!(
+            "this creates a symbolic value of type `{ty}` with about {size} symbolic bytes \
+            ({} bits), which may make verification intractable",
+            size.saturating_mul(8)
+        ),
+    );
+    diag.help(
+        "generate only the values that the harness needs, e.g., a shorter array with \
+        `kani::any_array`, or a collection of bounded length with `kani::bounded_any`",
+    );
+    diag.help(
+        "initialize the parts of the value that don't need to be symbolic with concrete \
+        values, e.g., with a manual `Arbitrary` implementation instead of a derived one",
+    );
+    diag.emit();
+}
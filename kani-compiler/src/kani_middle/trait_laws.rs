@@ -0,0 +1,108 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module synthesizes the harnesses used by `--trait-laws`.
+//!
+//! The standard library expects implementations of `PartialEq`, `Eq`, `Ord` and `Hash` to obey a
+//! few laws, e.g., `eq` must be symmetric and values that are equal must have the same hash.
+//! Collections rely on these laws, but the compiler cannot check them. For every local
+//! implementation of these traits, we check the laws with a harness from `kani::internal`,
+//! instantiated with the implementing type. The harness is identified by the method that
+//! implements the trait: `eq`, `cmp` or `hash`.
+
+use super::{implements_trait, local_impls};
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::{Instance, Ty, TyCtxt};
+use rustc_span::symbol::sym;
+use rustc_span::Symbol;
+use std::collections::HashSet;
+
+/// Collect the methods of the local trait implementations whose laws can be checked.
+///
+/// The laws can only be checked if the implementation is not generic and if the type implements
+/// `kani::Arbitrary`. We emit one warning for each type whose implementations are skipped.
+pub fn trait_law_targets(tcx: TyCtxt) -> Vec<DefId> {
+    let Some(arbitrary) = tcx.get_diagnostic_item(Symbol::intern("KaniArbitrary")) else {
+        tcx.sess.err("`--trait-laws` requires the `kani` library");
+        return vec![];
+    };
+    let mut skipped = HashSet::new();
+    let mut targets = vec![];
+    for (trait_id, method) in checked_methods(tcx) {
+        for impl_id in local_impls(tcx, trait_id) {
+            let trait_ref = tcx.impl_trait_ref(impl_id).unwrap().instantiate_identity();
+            let ty = trait_ref.self_ty();
+            // We only check `PartialEq` for comparisons between values of the same type.
+            if trait_ref.args.types().nth(1).is_some_and(|rhs| rhs != ty) {
+                continue;
+            }
+            if tcx.generics_of(impl_id).count() > 0 {
+                if skipped.insert(ty) {
+                    tcx.sess.span_warn(
+                        tcx.def_span(impl_id),
+                        format!("skipping the trait law checks of `{ty}` since it is generic"),
+                    );
+                }
+            } else if !implements_trait(tcx, arbitrary, ty) {
+                if skipped.insert(ty) {
+                    tcx.sess.span_warn(
+                        tcx.def_span(impl_id),
+                        format!(
+                            "skipping the trait law checks of `{ty}` since it does not implement \
+                            `kani::Arbitrary`"
+                        ),
+                    );
+                }
+            } else if trait_id != hash_trait(tcx) || is_eq(tcx, ty) {
+                // `Hash` only has to agree with `Eq`, so there's nothing to check without it.
+                targets.extend(find_method(tcx, impl_id, method));
+            }
+        }
+    }
+    targets
+}
+
+/// Build the harness that checks the laws of the trait implemented by `method`.
+/// Return `None` if `method` isn't one of the methods returned by [trait_law_targets].
+pub fn trait_law_harness(tcx: TyCtxt, method: DefId) -> Option<Instance> {
+    let impl_id = tcx.impl_of_method(method)?;
+    let trait_id = tcx.trait_id_of_impl(impl_id)?;
+    if tcx.generics_of(impl_id).count() > 0
+        || !checked_methods(tcx).contains(&(trait_id, tcx.item_name(method)))
+    {
+        return None;
+    }
+    let ty = tcx.type_of(impl_id).instantiate_identity();
+    let harness = if Some(trait_id) == tcx.lang_items().eq_trait() {
+        if is_eq(tcx, ty) { "KaniEqLaws" } else { "KaniPartialEqLaws" }
+    } else if trait_id == hash_trait(tcx) {
+        "KaniHashLaws"
+    } else {
+        "KaniOrdLaws"
+    };
+    let harness = tcx.get_diagnostic_item(Symbol::intern(harness))?;
+    Some(Instance::new(harness, tcx.mk_args(&[ty.into()])))
+}
+
+/// The traits whose laws we check, and the method that identifies each check.
+fn checked_methods(tcx: TyCtxt) -> Vec<(DefId, Symbol)> {
+    [
+        (tcx.lang_items().eq_trait(), sym::eq),
+        (tcx.get_diagnostic_item(sym::Ord), sym::cmp),
+        (Some(hash_trait(tcx)), sym::hash),
+    ]
+    .into_iter()
+    .filter_map(|(trait_id, method)| Some((trait_id?, method)))
+    .collect()
+}
+
+fn hash_trait(tcx: TyCtxt) -> DefId {
+    tcx.get_diagnostic_item(sym::Hash).unwrap()
+}
+
+fn is_eq<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> bool {
+    tcx.get_diagnostic_item(sym::Eq).is_some_and(|eq| implements_trait(tcx, eq, ty))
+}
+
+fn find_method(tcx: TyCtxt, impl_id: DefId, name: Symbol) -> Option<DefId> {
+    tcx.associated_item_def_ids(impl_id).iter().copied().find(|item| tcx.item_name(*item) == name)
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 //! Define the communication between KaniCompiler and the codegen implementation.
 
+use kani_metadata::{HarnessAssumptions, StubUsage};
 use rustc_hir::definitions::DefPathHash;
 use std::{
     collections::HashMap,
@@ -17,6 +18,15 @@ pub struct QueryDb {
     args: Option<Arguments>,
     /// Information about all target harnesses.
     pub harnesses_info: HashMap<DefPathHash, PathBuf>,
+    /// The function whose contract should be enforced for each harness, as
+    /// found during codegen.
+    pub harness_contracts: HashMap<DefPathHash, String>,
+    /// The call sites that the stubs of each harness redirected, as found during codegen.
+    pub harness_stub_usage: HashMap<DefPathHash, Vec<StubUsage>>,
+    /// The inline assembly blocks that `--ignore-asm` replaced in the code of each harness.
+    pub harness_havocked_asm: HashMap<DefPathHash, Vec<String>>,
+    /// The assumptions found in the code of each harness.
+    pub harness_assumptions: HashMap<DefPathHash, HarnessAssumptions>,
 }
 
 impl QueryDb {
@@ -11,11 +11,16 @@
 
 use self::common::*;
 use crate::args::cargo::CargoTargetArgs;
+use crate::concrete_inputs::{parse_concrete_input, ConcreteInput};
+use crate::contract_specs::{parse_contract_specs_file, ContractSpecs};
+use crate::ffi_policy::{parse_ffi_policy_file, FfiPolicies};
+use crate::partition::{Partition, PartitionStrategy};
 use crate::util::warning;
 use cargo::CargoCommonArgs;
 use clap::builder::{PossibleValue, TypedValueParser};
 use clap::{error::ContextKind, error::ContextValue, error::Error, error::ErrorKind, ValueEnum};
-use kani_metadata::CbmcSolver;
+use kani_metadata::{CbmcPathStrategy, CbmcSolver};
+use regex::Regex;
 use std::ffi::OsString;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -112,6 +117,21 @@ pub enum CargoKaniSubcommand {
 
     /// Execute concrete playback testcases of a local package.
     Playback(Box<playback_args::CargoPlaybackArgs>),
+
+    /// Start a server that verifies harnesses on request using JSON-RPC over stdio.
+    /// This feature is unstable and it requires `-Z server` to be used.
+    #[command(hide = true)]
+    Server,
+
+    /// List the code gated on `cfg(kani)` and check that it cannot be compiled in normal builds.
+    /// This feature is unstable and it requires `-Z cfg-report` to be used.
+    #[command(hide = true)]
+    CfgReport,
+
+    /// Compile the harnesses and estimate how hard they are to verify, without verifying them.
+    /// This feature is unstable and it requires `-Z estimate` to be used.
+    #[command(hide = true)]
+    Estimate,
 }
 
 // Common arguments for invoking Kani for verification purpose. This gets put into KaniContext,
@@ -123,6 +143,10 @@ pub struct VerificationArgs {
     #[arg(long, hide = true, requires("enable_unstable"))]
     pub assess: bool,
 
+    /// Temporary option to run `cargo kani cfg-report` in our test suite, like `--assess`.
+    #[arg(long, hide = true)]
+    pub cfg_report: bool,
+
     /// Generate visualizer report to `<target-dir>/report/html/index.html`
     #[arg(long)]
     pub visualize: bool,
@@ -137,6 +161,85 @@ pub struct VerificationArgs {
         value_enum
     )]
     pub concrete_playback: Option<ConcretePlaybackMode>,
+    /// Generate a DOT graph of the heap at the failure point of each failing harness to
+    /// `<target-dir>/report-<harness>/heap-graph.dot`.
+    /// This feature is unstable and it requires `-Z heap-graph` to be used.
+    #[arg(long, hide_short_help = true, conflicts_with_all(&["visualize"]))]
+    pub heap_graph: bool,
+    /// Print the `kani::assume` calls that would exclude the input of the first failure of each
+    /// failing harness, based on the values of its variables in the counterexample.
+    /// This feature is unstable and it requires `-Z suggest-assumptions` to be used.
+    #[arg(long, hide_short_help = true, conflicts_with_all(&["visualize"]))]
+    pub suggest_assumptions: bool,
+    /// Also verify every `Drop` implementation of the local crate, using a harness that drops an
+    /// arbitrary value of the implementing type. Types must implement `kani::Arbitrary`.
+    /// This feature is unstable and it requires `-Z drop-checks` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub drop_checks: bool,
+    /// Also verify that the `PartialEq`, `Eq`, `Ord` and `Hash` implementations of the local crate
+    /// obey the laws expected by the standard library, e.g., `eq` must be symmetric.
+    /// Types must implement `kani::Arbitrary`.
+    /// This feature is unstable and it requires `-Z trait-laws` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub trait_laws: bool,
+    /// Also verify that every non-generic `const fn` of the local crate cannot panic when it is
+    /// called at runtime with arbitrary arguments. The argument types must implement
+    /// `kani::Arbitrary`.
+    /// This feature is unstable and it requires `-Z const-fn-checks` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub const_fn_checks: bool,
+    /// Check that the bytes passed to `str::from_utf8_unchecked`, `str::from_utf8_unchecked_mut`
+    /// and `String::from_utf8_unchecked` are valid UTF-8. Conversions in the standard library
+    /// and in the Kani library aren't checked.
+    /// This feature is unstable and it requires `-Z utf8-checks` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub utf8_checks: bool,
+    /// Don't restrict the strings generated by Kani, e.g. with `kani::bounded_any`, to valid
+    /// UTF-8. This makes harnesses cheaper, but the strings may break the invariant of `str`.
+    /// This feature is unstable and it requires `-Z utf8-checks` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub no_utf8_assumptions: bool,
+    /// Restrict the floats generated by `kani::any` in every harness: `no-nan` excludes NaN,
+    /// `finite` also excludes the infinities, and `normal` also excludes the subnormal numbers.
+    /// By default, floats may be any value of their type.
+    /// This feature is unstable and it requires `-Z float-values` to be used.
+    #[arg(long, value_enum, hide_short_help = true)]
+    pub float_values: Option<FloatValues>,
+    /// Check that the integer `as` casts of the crate don't change the value that is cast, i.e.,
+    /// that they neither truncate it nor change its sign. Intentional casts can be allowed with
+    /// `#[cfg_attr(kani, kanitool::allow_lossy_cast)]` on a statement or an item.
+    /// This feature is unstable and it requires `-Z lossy-cast-checks` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub lossy_cast_checks: bool,
+    /// Check that the values of enum types that are created without the guarantees of the
+    /// compiler, i.e., by transmutes, reads through raw pointers or of union fields, and foreign
+    /// functions, have a valid discriminant and a valid payload for their variant.
+    /// This feature is unstable and it requires `-Z enum-validity-checks` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub enum_validity_checks: bool,
+    /// Check that the harnesses don't branch on secrets, i.e., values marked with
+    /// `kani::secret`, or access memory at indices that depend on them.
+    /// This feature is unstable and it requires `-Z constant-time-checks` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub constant_time_checks: bool,
+    /// Check that threads spawned with `kani::thread::spawn` don't race on global variables,
+    /// i.e., that they don't access one at the same time while one of them writes to it.
+    /// `std::thread::spawn` and `std::sync::Mutex` are stubbed with the models of `kani::thread`
+    /// in every harness, so the harnesses can use them directly.
+    /// This feature is unstable and it requires `-Z race-checks` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub race_checks: bool,
+    /// Make the iteration order of `HashMap` and `HashSet` nondeterministic, so that failures
+    /// that only happen for some orders are found. The hashes of their default hasher are
+    /// permuted by a nondeterministic seed, and Kani reports the failures that involve it.
+    /// This feature is unstable and it requires `-Z hash-order` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub nondet_hash_order: bool,
+    /// Stub `Vec` and the hashing of `HashMap` and `HashSet` with the cheaper models of
+    /// `kani::abstractions` in every harness.
+    /// This feature is unstable and it requires `-Z abstractions` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub use_abstractions: bool,
     /// Keep temporary files generated throughout Kani process. This is already the default
     /// behavior for `cargo-kani`.
     #[arg(long, hide_short_help = true)]
@@ -152,6 +255,13 @@ pub struct VerificationArgs {
     #[arg(long)]
     pub target_dir: Option<PathBuf>,
 
+    /// Verify the code for the given target triple, e.g., `thumbv7em-none-eabihf`, instead of
+    /// the host, so the pointer width and the layout of types match the deployment target.
+    /// Kani's libraries must have been built for this target.
+    /// This feature is unstable and it requires `-Z custom-targets` to be used.
+    #[arg(long = "target", value_name = "TRIPLE", hide_short_help = true)]
+    pub target_triple: Option<String>,
+
     /// Force Kani to rebuild all packages before the verification.
     #[arg(long)]
     pub force_build: bool,
@@ -160,6 +270,14 @@ pub struct VerificationArgs {
     #[arg(long, default_value = "regular", ignore_case = true, value_enum)]
     pub output_format: OutputFormat,
 
+    /// Write the report of `--output-format=json` to this file instead of the standard output.
+    #[arg(long, value_name = "PATH")]
+    pub results_file: Option<PathBuf>,
+
+    /// Write the failed checks to this file in the SARIF format, for code scanning tools.
+    #[arg(long, value_name = "PATH")]
+    pub sarif: Option<PathBuf>,
+
     #[command(flatten)]
     pub checks: CheckArgs,
 
@@ -170,6 +288,7 @@ pub struct VerificationArgs {
     /// If specified, only run harnesses that match this filter. This option can be provided
     /// multiple times, which will run all tests matching any of the filters.
     /// If used with --exact, the harness filter will only match the exact fully qualified name of a harness.
+    /// The name can be prefixed with the crate name (e.g. `my_crate::check`) to select harnesses of a single crate.
     #[arg(
         long = "harness",
         conflicts_with = "function",
@@ -178,10 +297,80 @@ pub struct VerificationArgs {
     )]
     pub harnesses: Vec<String>,
 
-    /// When specified, the harness filter will only match the exact fully qualified name of a harness
-    #[arg(long, requires("harnesses"))]
+    /// When specified, the harness filters of --harness and --exclude-harness will only match the
+    /// exact fully qualified name of a harness
+    #[arg(long)]
     pub exact: bool,
 
+    /// If specified, only run harnesses whose fully qualified name matches this regular
+    /// expression, with or without the crate name prefix (e.g. `my_crate::module::check_.*`).
+    /// This option can be provided multiple times, and it can be combined with --harness.
+    #[arg(
+        long = "harness-pattern",
+        conflicts_with = "function",
+        num_args(1),
+        value_name = "REGEX"
+    )]
+    pub harness_patterns: Vec<Regex>,
+
+    /// Skip the harnesses that match this filter, which follows the same rules as --harness.
+    /// This option can be provided multiple times.
+    #[arg(
+        long = "exclude-harness",
+        conflicts_with = "function",
+        num_args(1),
+        value_name = "HARNESS_FILTER"
+    )]
+    pub excluded_harnesses: Vec<String>,
+
+    /// Only verify one of several disjoint shards of the selected harnesses, given as
+    /// `<STRATEGY>:<M>/<N>` for the `M`-th of `N` shards. The strategy is `count` to deal out the
+    /// harnesses sorted by name, `hash` to assign them by a hash of their name, or `time` to
+    /// balance the shards based on the times in the `--timings` file.
+    /// This feature is unstable and it requires `-Z partitioning` to be used.
+    #[arg(long, value_name = "STRATEGY:M/N", value_parser = Partition::from_str, hide_short_help = true)]
+    pub partition: Option<Partition>,
+
+    /// A JSON file with the verification time of each harness. Kani uses it to balance the
+    /// shards of `--partition time:M/N`, and it updates it with the harnesses verified in each run.
+    /// This feature is unstable and it requires `-Z partitioning` to be used.
+    #[arg(long, value_name = "PATH", hide_short_help = true)]
+    pub timings: Option<PathBuf>,
+
+    /// Print the selected harnesses and their time in the `--timings` file in JSON, without
+    /// verifying them, so that external tools can schedule them.
+    /// This feature is unstable and it requires `-Z partitioning` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub list_harnesses: bool,
+
+    /// A JSON file with the fingerprints of the harnesses that were verified successfully. Kani
+    /// skips a harness if its goto model and verification options still have the recorded
+    /// fingerprint, and it updates the file with the harnesses verified in each run.
+    /// This feature is unstable and it requires `-Z verification-cache` to be used.
+    #[arg(long, value_name = "PATH", hide_short_help = true)]
+    pub verification_cache: Option<PathBuf>,
+
+    /// A JSON file with the expected result of each harness. Kani records the status of the
+    /// harnesses verified in each run and their unreachable covers in this file, unless
+    /// `--check-against-baseline` is given.
+    /// This feature is unstable and it requires `-Z baseline` to be used.
+    #[arg(long, value_name = "PATH", hide_short_help = true)]
+    pub baseline: Option<PathBuf>,
+
+    /// Compare the results with the `--baseline` file instead of updating it, and only fail on
+    /// regressions: harnesses that fail or time out but are successful or new in the baseline,
+    /// and covers that became unreachable.
+    /// This feature is unstable and it requires `-Z baseline` to be used.
+    #[arg(long, requires("baseline"), hide_short_help = true)]
+    pub check_against_baseline: bool,
+
+    /// Print the N functions with the most goto instructions in the model of each harness, after
+    /// the functions that the harness can't reach were removed. The largest functions are good
+    /// candidates for stubbing.
+    /// This feature is unstable and it requires `-Z size-report` to be used.
+    #[arg(long, value_name = "N", hide_short_help = true)]
+    pub size_report: Option<usize>,
+
     /// Link external C files referenced by Rust code.
     /// This is an experimental feature and requires `-Z c-ffi` to be used
     #[arg(long, hide = true, num_args(1..))]
@@ -189,6 +378,13 @@ pub struct VerificationArgs {
     /// Enable test function verification. Only use this option when the entry point is a test function
     #[arg(long)]
     pub tests: bool,
+    /// Verify the harnesses in `#[cfg(test)]` modules, which can use the helpers of the unit
+    /// tests. The crates under verification are built with `cfg(test)` set, but unlike with
+    /// `--tests`, the test harness isn't built: `#[test]` functions are removed and
+    /// `dev-dependencies` aren't available.
+    /// This feature is unstable and it requires `-Z test-modules` to be used.
+    #[arg(long, conflicts_with("tests"), hide_short_help = true)]
+    pub test_modules: bool,
     /// Kani will only compile the crate. No verification will be performed
     #[arg(long, hide_short_help = true)]
     pub only_codegen: bool,
@@ -207,10 +403,77 @@ pub struct VerificationArgs {
     /// Specify the value used for loop unwinding for the specified harness in CBMC
     #[arg(long, requires("harnesses"))]
     pub unwind: Option<u32>,
+    /// Search for an unwinding bound that fully unwinds the loops of each harness, and verify the
+    /// harness with it. Kani increases the bound, starting from the one of the harness, until the
+    /// unwinding assertions hold.
+    /// This feature is unstable and it requires `-Z auto-unwind` to be used.
+    #[arg(long, hide_short_help = true, conflicts_with_all(&["unwind", "visualize"]))]
+    pub auto_unwind: bool,
+    /// The largest bound that `--auto-unwind` tries. Defaults to 128.
+    #[arg(long, hide_short_help = true, requires("auto_unwind"), value_name = "N")]
+    pub auto_unwind_max: Option<u32>,
+    /// Stop the search of `--auto-unwind` after this many seconds for each harness.
+    #[arg(long, hide_short_help = true, requires("auto_unwind"), value_name = "SECONDS")]
+    pub auto_unwind_timeout: Option<u64>,
+    /// Check that the call stack never has more than the given number of active function calls.
+    /// This can be used to detect potential stack overflows in recursive code. Kani also reports
+    /// the deepest call stack of the harnesses that return. Each thread has its own call stack.
+    /// This feature is unstable and it requires `-Z stack-depth` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub max_stack_depth: Option<u32>,
     /// Specify the CBMC solver to use. Overrides the harness `solver` attribute.
     /// If no solver is specified (with --solver or harness attribute), Kani will use CaDiCaL.
+    /// Use `bin=<SAT_SOLVER_BINARY>` for a custom SAT solver, and `smt2=<SMT_SOLVER_COMMAND>` for a
+    /// custom SMT solver.
     #[arg(long, value_parser = CbmcSolverValueParser::new(CbmcSolver::VARIANTS))]
     pub solver: Option<CbmcSolver>,
+    /// Specify how CBMC explores the paths of a harness. Overrides the harness `path_strategy`
+    /// attribute. If no strategy is specified, CBMC merges paths where they join.
+    #[arg(long, value_enum)]
+    pub path_strategy: Option<CbmcPathStrategy>,
+    /// Encode arrays with the array theory of the solver instead of one variable per element,
+    /// which is much faster for harnesses over large buffers of which only a few elements are
+    /// accessed. SMT solvers (e.g., `--solver z3`) receive the arrays as SMT arrays, and SAT
+    /// solvers use CBMC's decision procedure for arrays.
+    /// This feature is unstable and it requires `-Z array-theory` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub array_theory: bool,
+    /// A TOML file that tells Kani how to model foreign functions that have no definition. Each
+    /// entry of its `[functions]` table maps a link name to `havoc`, `zeroed` or `stub`.
+    /// This feature is unstable and it requires `-Z ffi-policy` to be used.
+    #[arg(long, value_name = "FILE", value_parser = parse_ffi_policy_file, hide_short_help = true)]
+    pub ffi_policy: Option<FfiPolicies>,
+    /// A TOML file that gives contracts to functions that can't be annotated, such as the
+    /// functions of other crates. Each entry of its `[contracts]` table maps the path of a
+    /// function to a spec function with the same signature and the contract attributes. Every
+    /// harness assumes the contract instead of calling the function.
+    /// This feature is unstable and it requires `-Z function-contracts` to be used.
+    #[arg(
+        long,
+        value_name = "FILE",
+        value_parser = parse_contract_specs_file,
+        hide_short_help = true
+    )]
+    pub contract_specs: Option<ContractSpecs>,
+    /// Fix a symbolic input of the harnesses to a concrete value, given as `<NAME>=<VALUE>`,
+    /// e.g., `--concrete x=5`. An input is named after the variable of the harness that is
+    /// initialized with `kani::any()`, and the inputs that aren't fixed stay symbolic.
+    /// This option can be provided multiple times.
+    /// This feature is unstable and it requires `-Z concrete-inputs` to be used.
+    #[arg(
+        long = "concrete",
+        num_args(1),
+        value_name = "NAME=VALUE",
+        value_parser = parse_concrete_input,
+        hide_short_help = true
+    )]
+    pub concrete_inputs: Vec<ConcreteInput>,
+    /// The memory model under which the threads spawned with `kani::thread::spawn` are verified.
+    /// Under `tso` and `pso`, writes may become visible to other threads later than they are
+    /// made, unless an atomic ordering or a fence prevents it. The default is `sc`.
+    /// This feature is unstable and it requires `-Z memory-model` to be used.
+    #[arg(long, value_enum, hide_short_help = true)]
+    pub memory_model: Option<MemoryModel>,
     /// Pass through directly to CBMC; must be the last flag.
     /// This feature is unstable and it requires `--enable_unstable` to be used
     #[arg(
@@ -222,10 +485,36 @@ pub struct VerificationArgs {
     // consumes everything
     pub cbmc_args: Vec<OsString>,
 
-    /// Number of parallel jobs, defaults to 1
-    #[arg(short, long, hide = true, requires("enable_unstable"))]
+    /// Number of harnesses to verify in parallel, defaults to 1.
+    /// Use `-j` without a value to run as many jobs as there are CPUs.
+    /// The output of each harness is printed once it has been verified.
+    #[arg(short, long)]
     pub jobs: Option<Option<usize>>,
 
+    /// The memory that each CBMC process is expected to need, in MiB. Kani runs fewer jobs in
+    /// parallel if needed to keep their total under the memory available in the system.
+    #[arg(long, requires("jobs"), value_name = "MIB")]
+    pub memory_per_job: Option<u64>,
+
+    /// Stop verifying each harness after this many seconds. Kani then reports the harness as
+    /// timed out, and its properties as UNDETERMINED.
+    /// This feature is unstable and it requires `-Z harness-timeout` to be used.
+    #[arg(long, hide_short_help = true, value_name = "SECONDS", conflicts_with_all(&["visualize"]))]
+    pub harness_timeout: Option<u64>,
+
+    /// Stop verifying harnesses once this many seconds have passed since Kani started. The
+    /// harness being verified at that point is stopped like with `--harness-timeout`, and the
+    /// harnesses that remain are reported as timed out without being verified.
+    /// This feature is unstable and it requires `-Z harness-timeout` to be used.
+    #[arg(long, hide_short_help = true, value_name = "SECONDS", conflicts_with_all(&["visualize"]))]
+    pub total_timeout: Option<u64>,
+
+    /// Stop verifying harnesses once a harness fails a check of this property class, e.g., a
+    /// class given to `kani::assert_class!`. The harnesses that remain are not verified.
+    /// This option can be provided multiple times.
+    #[arg(long, num_args(1), value_name = "CLASS")]
+    pub fail_fast_on_class: Vec<String>,
+
     /// Enable extra pointer checks such as invalid pointers in relation operations and pointer
     /// arithmetic overflow.
     /// This feature is unstable and it may yield false counter examples. It requires
@@ -249,6 +538,13 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true, requires("enable_unstable"))]
     pub ignore_global_asm: bool,
 
+    /// Replace each inline `asm!` block with nondeterministic values for its outputs, instead of
+    /// failing verification when the block is reachable. Kani reports the blocks that were
+    /// replaced after the result of each harness, since their other effects aren't modeled.
+    /// This feature is unstable and it requires `-Z ignore-asm` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub ignore_asm: bool,
+
     /// Write the GotoC symbol table to a file in JSON format instead of goto binary format.
     #[arg(long, hide_short_help = true)]
     pub write_json_symtab: bool,
@@ -293,6 +589,11 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true)]
     pub coverage: bool,
 
+    /// Write the line coverage of all harnesses to the given file in the LCOV format.
+    /// Requires `--coverage`
+    #[arg(long, hide_short_help = true, requires("coverage"))]
+    pub coverage_report: Option<PathBuf>,
+
     /// Arguments to pass down to Cargo
     #[command(flatten)]
     pub cargo: CargoCommonArgs,
@@ -334,16 +635,56 @@ pub fn jobs(&self) -> Option<usize> {
         }
     }
 
+    /// Whether harnesses may be verified in parallel.
+    pub fn is_parallel(&self) -> bool {
+        self.jobs() != Some(1)
+    }
+
+    /// Whether the user restricted the harnesses to verify with `--harness`,
+    /// `--harness-pattern` or `--exclude-harness`.
+    pub fn has_harness_filters(&self) -> bool {
+        !self.harnesses.is_empty()
+            || !self.harness_patterns.is_empty()
+            || !self.excluded_harnesses.is_empty()
+    }
+
+    /// Whether Kani prints its progress and the results of each harness for a human reader,
+    /// which isn't the case when it's quiet or when it prints a JSON report or list instead.
+    pub fn prints_human_output(&self) -> bool {
+        !self.common_args.quiet && self.output_format != OutputFormat::Json && !self.list_harnesses
+    }
+
     /// Are experimental function contracts enabled?
     pub fn is_function_contracts_enabled(&self) -> bool {
         self.common_args.unstable_features.contains(UnstableFeature::FunctionContracts)
     }
 
+    /// Are experimental loop contracts enabled?
+    pub fn is_loop_contracts_enabled(&self) -> bool {
+        self.common_args.unstable_features.contains(UnstableFeature::LoopContracts)
+    }
+
     /// Is experimental stubbing enabled?
     pub fn is_stubbing_enabled(&self) -> bool {
         self.enable_stubbing
             || self.common_args.unstable_features.contains(UnstableFeature::Stubbing)
             || self.is_function_contracts_enabled()
+            || self.use_abstractions
+            || self.race_checks
+    }
+
+    /// The target triple that the code is verified for, which is the host unless `--target`
+    /// was given.
+    pub fn build_target(&self) -> &str {
+        self.target_triple.as_deref().unwrap_or(env!("TARGET"))
+    }
+
+    /// Whether `--target` selects a 32-bit ARM target, whose C data model is ILP32.
+    pub fn is_32_bit_target(&self) -> bool {
+        self.target_triple.as_deref().is_some_and(|target| {
+            (target.starts_with("arm") || target.starts_with("thumb"))
+                && !target.starts_with("arm64")
+        })
     }
 }
 
@@ -360,6 +701,42 @@ pub enum OutputFormat {
     Regular,
     Terse,
     Old,
+    /// A JSON report of all harnesses, printed once verification is done.
+    Json,
+}
+
+/// The memory models that CBMC supports for concurrent programs.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum, strum_macros::AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum MemoryModel {
+    /// Sequential consistency: every thread sees the writes in the order they are made.
+    Sc,
+    /// Total store order: a write may be delayed after later reads of other locations.
+    Tso,
+    /// Partial store order: writes to different locations may also be delayed after each other.
+    Pso,
+}
+
+/// The values that the floats generated by `kani::any` may have.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum, strum_macros::AsRefStr)]
+#[strum(serialize_all = "kebab-case")]
+pub enum FloatValues {
+    /// Any value except NaN.
+    NoNan,
+    /// Any value except NaN and the infinities.
+    Finite,
+    /// Zero and the normal numbers, i.e., any finite value that isn't subnormal.
+    Normal,
+}
+
+/// The checks that are only added to the harnesses when they are enabled with `--check`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OptionalCheck {
+    /// Reads of memory that was never initialized, e.g., with `MaybeUninit::assume_init`.
+    Uninit,
+    /// Pointer arithmetic that leaves the allocation of the pointer, and casts of integers to
+    /// pointers, which don't give them the provenance of an allocation.
+    PointerProvenance,
 }
 
 #[derive(Debug, clap::Args)]
@@ -400,6 +777,13 @@ pub struct CheckArgs {
     /// Turn off default unwinding checks
     #[arg(long)]
     pub no_unwinding_checks: bool,
+
+    /// Turn on a class of checks that is off by default. This option can be provided multiple
+    /// times.
+    /// This feature is unstable and it requires `-Z uninit-checks` for `uninit` and
+    /// `-Z pointer-provenance-checks` for `pointer-provenance` to be used.
+    #[arg(long = "check", num_args(1), value_name = "CLASS", hide_short_help = true)]
+    pub optional_checks: Vec<OptionalCheck>,
 }
 
 impl CheckArgs {
@@ -416,6 +800,12 @@ pub fn undefined_function_on(&self) -> bool {
     pub fn unwinding_on(&self) -> bool {
         !self.no_default_checks && !self.no_unwinding_checks || self.unwinding_checks
     }
+    pub fn uninit_on(&self) -> bool {
+        self.optional_checks.contains(&OptionalCheck::Uninit)
+    }
+    pub fn pointer_provenance_on(&self) -> bool {
+        self.optional_checks.contains(&OptionalCheck::PointerProvenance)
+    }
 }
 
 /// Utility function to error out on arguments that are invalid Cargo specific.
@@ -479,6 +869,9 @@ fn validate(&self) -> Result<(), Error> {
             // Assess doesn't implement validation yet.
             CargoKaniSubcommand::Assess(_) => Ok(()),
             CargoKaniSubcommand::Playback(playback) => playback.validate(),
+            CargoKaniSubcommand::Server
+            | CargoKaniSubcommand::CfgReport
+            | CargoKaniSubcommand::Estimate => Ok(()),
         }
     }
 }
@@ -496,6 +889,31 @@ fn validate(&self) -> Result<(), Error> {
                 "Assess is unstable and requires 'cargo kani --enable-unstable assess'",
             ));
         }
+        if matches!(self.command, Some(CargoKaniSubcommand::Server))
+            && !self.verify_opts.common_args.unstable_features.contains(UnstableFeature::Server)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The server is unstable and requires 'cargo kani -Z server server'",
+            ));
+        }
+        if (matches!(self.command, Some(CargoKaniSubcommand::CfgReport))
+            || self.verify_opts.cfg_report)
+            && !self.verify_opts.common_args.unstable_features.contains(UnstableFeature::CfgReport)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The cfg report is unstable and requires 'cargo kani -Z cfg-report cfg-report'",
+            ));
+        }
+        if matches!(self.command, Some(CargoKaniSubcommand::Estimate))
+            && !self.verify_opts.common_args.unstable_features.contains(UnstableFeature::Estimate)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The estimation mode is unstable and requires 'cargo kani -Z estimate estimate'",
+            ));
+        }
         Ok(())
     }
 }
@@ -521,6 +939,13 @@ fn validate(&self) -> Result<(), Error> {
             );
         }
 
+        if self.exact && self.harnesses.is_empty() && self.excluded_harnesses.is_empty() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--exact` argument requires `--harness` or `--exclude-harness`.",
+            ));
+        }
+
         if self.visualize && !self.common_args.enable_unstable {
             return Err(Error::raw(
                 ErrorKind::MissingRequiredArgument,
@@ -564,19 +989,43 @@ fn validate(&self) -> Result<(), Error> {
                 --output-format=old.",
             ));
         }
-        if self.concrete_playback.is_some() && self.jobs() != Some(1) {
+        if self.concrete_playback.is_some() && self.is_parallel() {
             // Concrete playback currently embeds a lot of assumptions about the order in which harnesses get called.
             return Err(Error::raw(
                 ErrorKind::ArgumentConflict,
                 "Conflicting options: --concrete-playback isn't compatible with --jobs.",
             ));
         }
-        if self.jobs.is_some() && self.output_format != OutputFormat::Terse {
-            // More verbose output formats make it hard to interpret output right now when run in parallel.
-            // This can be removed when we change up how results are printed.
+        if self.results_file.is_some() && self.output_format != OutputFormat::Json {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--results-file` argument requires `--output-format=json`.",
+            ));
+        }
+        if self.sarif.is_some() && (self.visualize || self.output_format == OutputFormat::Old) {
+            return Err(Error::raw(
+                ErrorKind::ArgumentConflict,
+                "Conflicting options: --sarif isn't compatible with --visualize or \
+                --output-format=old.",
+            ));
+        }
+        if self.output_format == OutputFormat::Json && self.visualize {
+            return Err(Error::raw(
+                ErrorKind::ArgumentConflict,
+                "Conflicting options: --output-format=json isn't compatible with --visualize.",
+            ));
+        }
+        if self.is_parallel() && self.output_format == OutputFormat::Old {
+            // The old output format prints the output of CBMC as it is produced.
             return Err(Error::raw(
                 ErrorKind::ArgumentConflict,
-                "Conflicting options: --jobs requires `--output-format=terse`",
+                "Conflicting options: --jobs isn't compatible with --output-format=old.",
+            ));
+        }
+        if self.memory_per_job == Some(0) {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                "Invalid value: --memory-per-job must be greater than 0.",
             ));
         }
         if let Some(out_dir) = &self.target_dir {
@@ -633,114 +1082,538 @@ fn validate(&self) -> Result<(), Error> {
             ));
         }
 
-        Ok(())
-    }
-}
+        if self.max_stack_depth.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::StackDepth)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--max-stack-depth` argument is unstable and requires `-Z stack-depth` to \
+                be used.",
+            ));
+        }
 
-/// clap parser for `CbmcSolver`
-#[derive(Clone, Debug)]
-pub struct CbmcSolverValueParser(Vec<PossibleValue>);
+        if self.contract_specs.is_some() && !self.is_function_contracts_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--contract-specs` argument is unstable and requires `-Z function-contracts` \
+                to be used.",
+            ));
+        }
 
-impl CbmcSolverValueParser {
-    pub fn new(values: impl Into<CbmcSolverValueParser>) -> Self {
-        values.into()
-    }
-}
+        if self.ffi_policy.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::FfiPolicy)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--ffi-policy` argument is unstable and requires `-Z ffi-policy` to be used.",
+            ));
+        }
 
-impl TypedValueParser for CbmcSolverValueParser {
-    type Value = CbmcSolver;
+        if self.heap_graph
+            && !self.common_args.unstable_features.contains(UnstableFeature::HeapGraph)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--heap-graph` argument is unstable and requires `-Z heap-graph` to be used.",
+            ));
+        }
 
-    fn parse_ref(
-        &self,
-        cmd: &clap::builder::Command,
-        arg: Option<&clap::builder::Arg>,
-        value: &std::ffi::OsStr,
-    ) -> Result<Self::Value, clap::error::Error> {
-        let value = value.to_str().unwrap();
-        // `value` is one of the possible `CbmcSolver` values or `bin=<binary>`
-        let segments: Vec<&str> = value.split('=').collect();
+        if self.suggest_assumptions
+            && !self.common_args.unstable_features.contains(UnstableFeature::SuggestAssumptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--suggest-assumptions` argument is unstable and requires `-Z \
+                suggest-assumptions` to be used.",
+            ));
+        }
 
-        let mut err = clap::Error::new(ErrorKind::InvalidValue).with_cmd(cmd);
-        err.insert(ContextKind::InvalidArg, ContextValue::String(arg.unwrap().to_string()));
-        err.insert(ContextKind::InvalidValue, ContextValue::String(value.to_string()));
+        if self.drop_checks
+            && !self.common_args.unstable_features.contains(UnstableFeature::DropChecks)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--drop-checks` argument is unstable and requires `-Z drop-checks` to be \
+                used.",
+            ));
+        }
 
-        if segments.len() == 2 {
-            if segments[0] != "bin" {
-                return Err(err);
-            }
-            return Ok(CbmcSolver::Binary(segments[1].into()));
-        } else if segments.len() == 1 {
-            let solver = CbmcSolver::from_str(value);
-            return solver.map_err(|_| err);
+        if self.trait_laws
+            && !self.common_args.unstable_features.contains(UnstableFeature::TraitLaws)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--trait-laws` argument is unstable and requires `-Z trait-laws` to be used.",
+            ));
         }
-        Err(err)
-    }
 
-    /// Used for the help message
-    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
-        Some(Box::new(self.0.iter().cloned()))
-    }
-}
+        if self.const_fn_checks
+            && !self.common_args.unstable_features.contains(UnstableFeature::ConstFnChecks)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--const-fn-checks` argument is unstable and requires `-Z const-fn-checks` \
+                to be used.",
+            ));
+        }
 
-impl<I, T> From<I> for CbmcSolverValueParser
-where
-    I: IntoIterator<Item = T>,
-    T: Into<PossibleValue>,
-{
-    fn from(values: I) -> Self {
-        Self(values.into_iter().map(|t| t.into()).collect())
-    }
-}
+        if (self.utf8_checks || self.no_utf8_assumptions)
+            && !self.common_args.unstable_features.contains(UnstableFeature::Utf8Checks)
+        {
+            let arg = if self.utf8_checks { "--utf8-checks" } else { "--no-utf8-assumptions" };
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `{arg}` argument is unstable and requires `-Z utf8-checks` to be used."
+                ),
+            ));
+        }
 
-#[cfg(test)]
-mod tests {
-    use clap::Parser;
+        if self.lossy_cast_checks
+            && !self.common_args.unstable_features.contains(UnstableFeature::LossyCastChecks)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--lossy-cast-checks` argument is unstable and requires `-Z \
+                lossy-cast-checks` to be used.",
+            ));
+        }
 
-    use super::*;
+        if self.verification_cache.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::VerificationCache)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--verification-cache` argument is unstable and requires `-Z \
+                verification-cache` to be used.",
+            ));
+        }
 
-    #[test]
-    fn check_arg_parsing() {
-        let a = StandaloneArgs::try_parse_from(vec![
-            "kani",
-            "file.rs",
-            "--enable-unstable",
-            "--cbmc-args",
-            "--multiple",
-            "args",
-            "--here",
-        ])
-        .unwrap();
-        assert_eq!(a.verify_opts.cbmc_args, vec!["--multiple", "args", "--here"]);
-        let _b = StandaloneArgs::try_parse_from(vec![
-            "kani",
-            "file.rs",
-            "--enable-unstable",
-            "--cbmc-args",
-        ])
-        .unwrap();
-        // no assertion: the above might fail if it fails to allow 0 args to cbmc-args
-    }
+        if self.verification_cache.is_some() && (self.visualize || self.coverage) {
+            return Err(Error::raw(
+                ErrorKind::ArgumentConflict,
+                "The `--verification-cache` argument cannot be used with `--visualize` or \
+                `--coverage`.",
+            ));
+        }
 
-    /// Ensure users can pass multiple harnesses options and that the value is accumulated.
-    #[test]
-    fn check_multiple_harnesses() {
-        let args =
-            StandaloneArgs::try_parse_from("kani input.rs --harness a --harness b".split(" "))
-                .unwrap();
-        assert_eq!(args.verify_opts.harnesses, vec!["a".to_owned(), "b".to_owned()]);
-    }
+        if self.baseline.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::Baseline)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--baseline` argument is unstable and requires `-Z baseline` to be used.",
+            ));
+        }
 
-    #[test]
-    fn check_multiple_harnesses_without_flag_fail() {
-        let result = StandaloneArgs::try_parse_from(
-            "kani input.rs --harness harness_1 harness_2".split(" "),
-        );
-        assert!(result.is_err());
-        assert_eq!(result.unwrap_err().kind(), ErrorKind::UnknownArgument);
-    }
+        if self.nondet_hash_order
+            && !self.common_args.unstable_features.contains(UnstableFeature::HashOrder)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--nondet-hash-order` argument is unstable and requires `-Z hash-order` to \
+                be used.",
+            ));
+        }
 
-    #[test]
-    fn check_multiple_packages() {
+        if self.constant_time_checks
+            && !self.common_args.unstable_features.contains(UnstableFeature::ConstantTimeChecks)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--constant-time-checks` argument is unstable and requires \
+                `-Z constant-time-checks` to be used.",
+            ));
+        }
+
+        if self.enum_validity_checks
+            && !self.common_args.unstable_features.contains(UnstableFeature::EnumValidityChecks)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--enum-validity-checks` argument is unstable and requires `-Z \
+                enum-validity-checks` to be used.",
+            ));
+        }
+
+        if !self.concrete_inputs.is_empty()
+            && !self.common_args.unstable_features.contains(UnstableFeature::ConcreteInputs)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--concrete` argument is unstable and requires `-Z concrete-inputs` to be \
+                used.",
+            ));
+        }
+
+        if self.checks.uninit_on()
+            && !self.common_args.unstable_features.contains(UnstableFeature::UninitChecks)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--check uninit` argument is unstable and requires `-Z uninit-checks` to be \
+                used.",
+            ));
+        }
+
+        if self.checks.pointer_provenance_on()
+            && !self
+                .common_args
+                .unstable_features
+                .contains(UnstableFeature::PointerProvenanceChecks)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--check pointer-provenance` argument is unstable and requires \
+                `-Z pointer-provenance-checks` to be used.",
+            ));
+        }
+
+        if self.ignore_asm
+            && !self.common_args.unstable_features.contains(UnstableFeature::IgnoreAsm)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--ignore-asm` argument is unstable and requires `-Z ignore-asm` to be used.",
+            ));
+        }
+
+        if self.size_report.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::SizeReport)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--size-report` argument is unstable and requires `-Z size-report` to be \
+                used.",
+            ));
+        }
+
+        if self.array_theory
+            && !self.common_args.unstable_features.contains(UnstableFeature::ArrayTheory)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--array-theory` argument is unstable and requires `-Z array-theory` to be \
+                used.",
+            ));
+        }
+
+        if self.use_abstractions
+            && !self.common_args.unstable_features.contains(UnstableFeature::Abstractions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--use-abstractions` argument is unstable and requires `-Z abstractions` to \
+                be used.",
+            ));
+        }
+
+        if self.use_abstractions && self.nondet_hash_order {
+            // Both stub the hashing of `HashMap` and `HashSet`, each with its own seed.
+            return Err(Error::raw(
+                ErrorKind::ArgumentConflict,
+                "Conflicting options: --use-abstractions already stubs the hashing of `HashMap` \
+                and `HashSet`, so it isn't compatible with --nondet-hash-order.",
+            ));
+        }
+
+        if self.test_modules
+            && !self.common_args.unstable_features.contains(UnstableFeature::TestModules)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--test-modules` argument is unstable and requires `-Z test-modules` to be \
+                used.",
+            ));
+        }
+
+        if self.float_values.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::FloatValues)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--float-values` argument is unstable and requires `-Z float-values` to be \
+                used.",
+            ));
+        }
+
+        if self.memory_model.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::MemoryModel)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--memory-model` argument is unstable and requires `-Z memory-model` to be \
+                used.",
+            ));
+        }
+
+        if self.race_checks
+            && !self.common_args.unstable_features.contains(UnstableFeature::RaceChecks)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--race-checks` argument is unstable and requires `-Z race-checks` to be \
+                used.",
+            ));
+        }
+
+        if self.target_triple.is_some() {
+            if !self.common_args.unstable_features.contains(UnstableFeature::CustomTargets) {
+                return Err(Error::raw(
+                    ErrorKind::MissingRequiredArgument,
+                    "The `--target` argument is unstable and requires `-Z custom-targets` to be \
+                    used.",
+                ));
+            }
+            if self.concrete_playback.is_some() {
+                return Err(Error::raw(
+                    ErrorKind::ArgumentConflict,
+                    "The `--target` argument cannot be used with `--concrete-playback`, since \
+                    the playback runs on the host.",
+                ));
+            }
+        }
+
+        if self.auto_unwind
+            && !self.common_args.unstable_features.contains(UnstableFeature::AutoUnwind)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--auto-unwind` argument is unstable and requires `-Z auto-unwind` to be used.",
+            ));
+        }
+
+        if self.auto_unwind_max == Some(0) {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                "Invalid value: --auto-unwind-max must be greater than 0.",
+            ));
+        }
+
+        if let Some(timeout) = self.harness_timeout {
+            if !self.common_args.unstable_features.contains(UnstableFeature::HarnessTimeout) {
+                return Err(Error::raw(
+                    ErrorKind::MissingRequiredArgument,
+                    "The `--harness-timeout` argument is unstable and requires `-Z \
+                    harness-timeout` to be used.",
+                ));
+            }
+            if timeout == 0 {
+                return Err(Error::raw(
+                    ErrorKind::InvalidValue,
+                    "Invalid value: --harness-timeout must be greater than 0.",
+                ));
+            }
+            if self.output_format == OutputFormat::Old {
+                return Err(Error::raw(
+                    ErrorKind::ArgumentConflict,
+                    "Conflicting options: --harness-timeout isn't compatible with \
+                    --output-format=old.",
+                ));
+            }
+        }
+
+        if (self.partition.is_some() || self.timings.is_some() || self.list_harnesses)
+            && !self.common_args.unstable_features.contains(UnstableFeature::Partitioning)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--partition`, `--timings` and `--list-harnesses` arguments are unstable \
+                and require `-Z partitioning` to be used.",
+            ));
+        }
+
+        if self.partition.is_some_and(|partition| partition.strategy == PartitionStrategy::Time)
+            && self.timings.is_none()
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--partition time:M/N` argument requires `--timings` to be used.",
+            ));
+        }
+
+        if let Some(timeout) = self.total_timeout {
+            if !self.common_args.unstable_features.contains(UnstableFeature::HarnessTimeout) {
+                return Err(Error::raw(
+                    ErrorKind::MissingRequiredArgument,
+                    "The `--total-timeout` argument is unstable and requires `-Z \
+                    harness-timeout` to be used.",
+                ));
+            }
+            if timeout == 0 {
+                return Err(Error::raw(
+                    ErrorKind::InvalidValue,
+                    "Invalid value: --total-timeout must be greater than 0.",
+                ));
+            }
+            if self.output_format == OutputFormat::Old {
+                return Err(Error::raw(
+                    ErrorKind::ArgumentConflict,
+                    "Conflicting options: --total-timeout isn't compatible with \
+                    --output-format=old.",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// clap parser for `CbmcSolver`
+#[derive(Clone, Debug)]
+pub struct CbmcSolverValueParser(Vec<PossibleValue>);
+
+impl CbmcSolverValueParser {
+    pub fn new(values: impl Into<CbmcSolverValueParser>) -> Self {
+        values.into()
+    }
+}
+
+impl TypedValueParser for CbmcSolverValueParser {
+    type Value = CbmcSolver;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::builder::Command,
+        arg: Option<&clap::builder::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Self::Value, clap::error::Error> {
+        let value = value.to_str().unwrap();
+        // `value` is one of the possible `CbmcSolver` values, `bin=<binary>` or `smt2=<command>`.
+        // The command of an SMT solver may contain `=` itself, e.g. `cvc5 --lang=smt2`.
+        let mut err = clap::Error::new(ErrorKind::InvalidValue).with_cmd(cmd);
+        err.insert(ContextKind::InvalidArg, ContextValue::String(arg.unwrap().to_string()));
+        err.insert(ContextKind::InvalidValue, ContextValue::String(value.to_string()));
+
+        match value.split_once('=') {
+            Some(("bin", binary)) if !binary.contains('=') => Ok(CbmcSolver::Binary(binary.into())),
+            Some(("smt2", command)) if !command.trim().is_empty() => {
+                Ok(CbmcSolver::Smt2(command.into()))
+            }
+            Some(_) => Err(err),
+            None => CbmcSolver::from_str(value).map_err(|_| err),
+        }
+    }
+
+    /// Used for the help message
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(self.0.iter().cloned()))
+    }
+}
+
+impl<I, T> From<I> for CbmcSolverValueParser
+where
+    I: IntoIterator<Item = T>,
+    T: Into<PossibleValue>,
+{
+    fn from(values: I) -> Self {
+        Self(values.into_iter().map(|t| t.into()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use kani_metadata::FfiPolicy;
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn check_arg_parsing() {
+        let a = StandaloneArgs::try_parse_from(vec![
+            "kani",
+            "file.rs",
+            "--enable-unstable",
+            "--cbmc-args",
+            "--multiple",
+            "args",
+            "--here",
+        ])
+        .unwrap();
+        assert_eq!(a.verify_opts.cbmc_args, vec!["--multiple", "args", "--here"]);
+        let _b = StandaloneArgs::try_parse_from(vec![
+            "kani",
+            "file.rs",
+            "--enable-unstable",
+            "--cbmc-args",
+        ])
+        .unwrap();
+        // no assertion: the above might fail if it fails to allow 0 args to cbmc-args
+    }
+
+    /// Ensure users can pass multiple harnesses options and that the value is accumulated.
+    #[test]
+    fn check_multiple_harnesses() {
+        let args =
+            StandaloneArgs::try_parse_from("kani input.rs --harness a --harness b".split(" "))
+                .unwrap();
+        assert_eq!(args.verify_opts.harnesses, vec!["a".to_owned(), "b".to_owned()]);
+    }
+
+    #[test]
+    fn check_harness_selection() {
+        let args = StandaloneArgs::try_parse_from(
+            "kani input.rs --harness-pattern ^check_.* --exclude-harness a --exclude-harness b"
+                .split(" "),
+        )
+        .unwrap();
+        assert!(args.verify_opts.has_harness_filters());
+        assert_eq!(args.verify_opts.harness_patterns[0].as_str(), "^check_.*");
+        assert_eq!(args.verify_opts.excluded_harnesses, vec!["a".to_owned(), "b".to_owned()]);
+
+        let res = StandaloneArgs::try_parse_from("kani input.rs --harness-pattern (".split(" "));
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::ValueValidation);
+
+        expect_validation_error("kani input.rs --exact", ErrorKind::MissingRequiredArgument);
+        for args in
+            ["kani input.rs --exact --harness a", "kani input.rs --exact --exclude-harness a"]
+        {
+            let args = StandaloneArgs::try_parse_from(args.split_whitespace()).unwrap();
+            assert!(args.verify_opts.validate().is_ok());
+        }
+    }
+
+    #[test]
+    fn check_path_strategy() {
+        let args = StandaloneArgs::try_parse_from("kani input.rs --path-strategy lifo".split(' '))
+            .unwrap();
+        assert_eq!(args.verify_opts.path_strategy, Some(CbmcPathStrategy::Lifo));
+        assert!(
+            StandaloneArgs::try_parse_from("kani input.rs --path-strategy dfs".split(' ')).is_err()
+        );
+    }
+
+    #[test]
+    fn check_solver() {
+        let solver = |value: &str| {
+            StandaloneArgs::try_parse_from(["kani", "input.rs", "--solver", value])
+                .map(|args| args.verify_opts.solver.unwrap())
+        };
+        assert_eq!(solver("kissat").unwrap(), CbmcSolver::Kissat);
+        assert_eq!(solver("z3").unwrap(), CbmcSolver::Z3);
+        assert_eq!(solver("bitwuzla").unwrap(), CbmcSolver::Bitwuzla);
+        assert_eq!(solver("bin=my_sat").unwrap(), CbmcSolver::Binary("my_sat".to_string()));
+        assert_eq!(
+            solver("smt2=cvc5 --lang=smt2").unwrap(),
+            CbmcSolver::Smt2("cvc5 --lang=smt2".to_string())
+        );
+        assert!(solver("smt2=").is_err());
+        assert!(solver("bin=a=b").is_err());
+        assert!(solver("yices").is_err());
+    }
+
+    #[test]
+    fn check_multiple_harnesses_without_flag_fail() {
+        let result = StandaloneArgs::try_parse_from(
+            "kani input.rs --harness harness_1 harness_2".split(" "),
+        );
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::UnknownArgument);
+    }
+
+    #[test]
+    fn check_multiple_packages() {
         // accepts repeated:
         let a = CargoKaniArgs::try_parse_from(vec!["cargo-kani", "-p", "a", "-p", "b"]).unwrap();
         assert_eq!(a.verify_opts.cargo.package, vec!["a".to_owned(), "b".to_owned()]);
@@ -845,6 +1718,638 @@ fn check_concrete_playback_unstable() {
         check("kani file.rs --concrete-playback=print");
     }
 
+    #[test]
+    fn check_heap_graph_unstable() {
+        expect_validation_error("kani file.rs --heap-graph", ErrorKind::MissingRequiredArgument);
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --heap-graph -Z heap-graph".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.heap_graph);
+    }
+
+    #[test]
+    fn check_suggest_assumptions_unstable() {
+        expect_validation_error(
+            "kani file.rs --suggest-assumptions",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --suggest-assumptions -Z suggest-assumptions".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.suggest_assumptions);
+    }
+
+    #[test]
+    fn check_harness_timeout() {
+        expect_validation_error(
+            "kani file.rs --harness-timeout 60",
+            ErrorKind::MissingRequiredArgument,
+        );
+        expect_validation_error(
+            "kani file.rs --harness-timeout 0 -Z harness-timeout",
+            ErrorKind::InvalidValue,
+        );
+        expect_validation_error(
+            "kani file.rs --harness-timeout 60 -Z harness-timeout --output-format old",
+            ErrorKind::ArgumentConflict,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --harness-timeout 60 -Z harness-timeout".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.harness_timeout, Some(60));
+    }
+
+    #[test]
+    fn check_total_timeout() {
+        expect_validation_error(
+            "kani file.rs --total-timeout 600",
+            ErrorKind::MissingRequiredArgument,
+        );
+        expect_validation_error(
+            "kani file.rs --total-timeout 0 -Z harness-timeout",
+            ErrorKind::InvalidValue,
+        );
+        expect_validation_error(
+            "kani file.rs --total-timeout 600 -Z harness-timeout --output-format old",
+            ErrorKind::ArgumentConflict,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --total-timeout 600 --harness-timeout 60 -Z harness-timeout"
+                .split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.total_timeout, Some(600));
+    }
+
+    #[test]
+    fn check_partitioning() {
+        expect_validation_error(
+            "kani file.rs --partition count:1/2",
+            ErrorKind::MissingRequiredArgument,
+        );
+        expect_validation_error(
+            "kani file.rs --list-harnesses",
+            ErrorKind::MissingRequiredArgument,
+        );
+        expect_validation_error(
+            "kani file.rs --partition time:1/2 -Z partitioning",
+            ErrorKind::MissingRequiredArgument,
+        );
+        assert!(
+            StandaloneArgs::try_parse_from("kani file.rs --partition count:3/2".split_whitespace())
+                .is_err()
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --partition time:1/2 --timings timings.json -Z partitioning"
+                .split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.partition.unwrap().to_string(), "time:1/2");
+    }
+
+    #[test]
+    fn check_fail_fast_on_class() {
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --fail-fast-on-class protocol --fail-fast-on-class safety"
+                .split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.fail_fast_on_class, ["protocol", "safety"]);
+    }
+
+    #[test]
+    fn check_auto_unwind() {
+        expect_validation_error("kani file.rs --auto-unwind", ErrorKind::MissingRequiredArgument);
+        expect_validation_error(
+            "kani file.rs --auto-unwind --auto-unwind-max 0 -Z auto-unwind",
+            ErrorKind::InvalidValue,
+        );
+        let err = StandaloneArgs::try_parse_from(
+            "kani file.rs --harness check --unwind 3 --auto-unwind".split_whitespace(),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+        let err = StandaloneArgs::try_parse_from(
+            "kani file.rs --auto-unwind-timeout 10".split_whitespace(),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --auto-unwind --auto-unwind-max 32 -Z auto-unwind".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.auto_unwind_max, Some(32));
+    }
+
+    #[test]
+    fn check_jobs_output_format() {
+        let args = StandaloneArgs::try_parse_from("kani file.rs -j 4".split_whitespace()).unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.jobs(), Some(4));
+        assert!(args.verify_opts.is_parallel());
+        expect_validation_error(
+            "kani file.rs -j 4 --output-format old",
+            ErrorKind::ArgumentConflict,
+        );
+    }
+
+    #[test]
+    fn check_memory_per_job() {
+        let res =
+            StandaloneArgs::try_parse_from("kani file.rs --memory-per-job 512".split_whitespace());
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs -j --memory-per-job 512".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.jobs(), None);
+        expect_validation_error("kani file.rs -j --memory-per-job 0", ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn check_drop_checks_unstable() {
+        expect_validation_error("kani file.rs --drop-checks", ErrorKind::MissingRequiredArgument);
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --drop-checks -Z drop-checks".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.drop_checks);
+    }
+
+    #[test]
+    fn check_trait_laws_unstable() {
+        expect_validation_error("kani file.rs --trait-laws", ErrorKind::MissingRequiredArgument);
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --trait-laws -Z trait-laws".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.trait_laws);
+    }
+
+    #[test]
+    fn check_const_fn_checks_unstable() {
+        expect_validation_error(
+            "kani file.rs --const-fn-checks",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --const-fn-checks -Z const-fn-checks".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.const_fn_checks);
+    }
+
+    #[test]
+    fn check_utf8_checks_unstable() {
+        expect_validation_error("kani file.rs --utf8-checks", ErrorKind::MissingRequiredArgument);
+        expect_validation_error(
+            "kani file.rs --no-utf8-assumptions",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --utf8-checks --no-utf8-assumptions -Z utf8-checks".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.utf8_checks);
+        assert!(args.verify_opts.no_utf8_assumptions);
+    }
+
+    #[test]
+    fn check_verification_cache_unstable() {
+        expect_validation_error(
+            "kani file.rs --verification-cache cache.json",
+            ErrorKind::MissingRequiredArgument,
+        );
+        expect_validation_error(
+            "kani file.rs --verification-cache cache.json --visualize -Z verification-cache \
+            --enable-unstable",
+            ErrorKind::ArgumentConflict,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --verification-cache cache.json -Z verification-cache".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.verification_cache, Some(PathBuf::from("cache.json")));
+    }
+
+    #[test]
+    fn check_baseline_args() {
+        expect_validation_error(
+            "kani file.rs --baseline baseline.json",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let res = StandaloneArgs::try_parse_from(
+            "kani file.rs --check-against-baseline -Z baseline".split_whitespace(),
+        );
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --baseline baseline.json --check-against-baseline -Z baseline"
+                .split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.check_against_baseline);
+    }
+
+    #[test]
+    fn check_nondet_hash_order_unstable() {
+        expect_validation_error(
+            "kani file.rs --nondet-hash-order",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --nondet-hash-order -Z hash-order".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.nondet_hash_order);
+    }
+
+    #[test]
+    fn check_constant_time_checks_unstable() {
+        expect_validation_error(
+            "kani file.rs --constant-time-checks",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --constant-time-checks -Z constant-time-checks".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.constant_time_checks);
+    }
+
+    #[test]
+    fn check_lossy_cast_checks_unstable() {
+        expect_validation_error(
+            "kani file.rs --lossy-cast-checks",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --lossy-cast-checks -Z lossy-cast-checks".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.lossy_cast_checks);
+    }
+
+    #[test]
+    fn check_custom_target() {
+        expect_validation_error(
+            "kani file.rs --target thumbv7em-none-eabihf",
+            ErrorKind::MissingRequiredArgument,
+        );
+        expect_validation_error(
+            "kani file.rs --target thumbv7em-none-eabihf -Z custom-targets \
+            --concrete-playback print -Z concrete-playback",
+            ErrorKind::ArgumentConflict,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --target thumbv7em-none-eabihf -Z custom-targets".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.build_target(), "thumbv7em-none-eabihf");
+        assert!(args.verify_opts.is_32_bit_target());
+
+        let args = StandaloneArgs::try_parse_from("kani file.rs".split_whitespace()).unwrap();
+        assert_eq!(args.verify_opts.build_target(), env!("TARGET"));
+        assert!(!args.verify_opts.is_32_bit_target());
+    }
+
+    #[test]
+    fn check_enum_validity_checks_unstable() {
+        expect_validation_error(
+            "kani file.rs --enum-validity-checks",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --enum-validity-checks -Z enum-validity-checks".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.enum_validity_checks);
+    }
+
+    #[test]
+    fn check_uninit_checks_unstable() {
+        expect_validation_error("kani file.rs --check uninit", ErrorKind::MissingRequiredArgument);
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --check uninit -Z uninit-checks".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.checks.uninit_on());
+        assert!(
+            StandaloneArgs::try_parse_from("kani file.rs --check foo".split_whitespace()).is_err()
+        );
+    }
+
+    #[test]
+    fn check_pointer_provenance_checks_unstable() {
+        expect_validation_error(
+            "kani file.rs --check pointer-provenance -Z uninit-checks",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --check pointer-provenance --check uninit -Z pointer-provenance-checks \
+            -Z uninit-checks"
+                .split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.checks.pointer_provenance_on());
+        assert!(args.verify_opts.checks.uninit_on());
+    }
+
+    #[test]
+    fn check_race_checks_unstable() {
+        expect_validation_error("kani file.rs --race-checks", ErrorKind::MissingRequiredArgument);
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --race-checks -Z race-checks".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.race_checks);
+        assert!(args.verify_opts.is_stubbing_enabled());
+    }
+
+    #[test]
+    fn check_array_theory() {
+        expect_validation_error("kani file.rs --array-theory", ErrorKind::MissingRequiredArgument);
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --array-theory -Z array-theory".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.array_theory);
+    }
+
+    #[test]
+    fn check_ignore_asm() {
+        expect_validation_error("kani file.rs --ignore-asm", ErrorKind::MissingRequiredArgument);
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --ignore-asm -Z ignore-asm".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.ignore_asm);
+    }
+
+    #[test]
+    fn check_concrete_inputs() {
+        expect_validation_error("kani file.rs --concrete x=5", ErrorKind::MissingRequiredArgument);
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --concrete x=5 --concrete flag=true -Z concrete-inputs"
+                .split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        let inputs: Vec<_> =
+            args.verify_opts.concrete_inputs.iter().map(|input| input.to_string()).collect();
+        assert_eq!(inputs, ["x=5", "flag=true"]);
+        assert!(
+            StandaloneArgs::try_parse_from(
+                "kani file.rs --concrete x -Z concrete-inputs".split_whitespace()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn check_size_report() {
+        expect_validation_error(
+            "kani file.rs --size-report 10",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --size-report 10 -Z size-report".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.size_report, Some(10));
+    }
+
+    #[test]
+    fn check_use_abstractions() {
+        expect_validation_error(
+            "kani file.rs --use-abstractions",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --use-abstractions -Z abstractions".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(args.verify_opts.is_stubbing_enabled());
+        expect_validation_error(
+            "kani file.rs --use-abstractions --nondet-hash-order -Z abstractions -Z hash-order",
+            ErrorKind::ArgumentConflict,
+        );
+    }
+
+    #[test]
+    fn check_test_modules() {
+        expect_validation_error("kani file.rs --test-modules", ErrorKind::MissingRequiredArgument);
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --test-modules -Z test-modules".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        let res = StandaloneArgs::try_parse_from(
+            "kani file.rs --test-modules --tests -Z test-modules".split_whitespace(),
+        );
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_float_values() {
+        expect_validation_error(
+            "kani file.rs --float-values finite",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --float-values no-nan -Z float-values".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.float_values, Some(FloatValues::NoNan));
+        assert_eq!(FloatValues::NoNan.as_ref(), "no-nan");
+    }
+
+    #[test]
+    fn check_memory_model() {
+        expect_validation_error(
+            "kani file.rs --memory-model tso",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --memory-model pso -Z memory-model".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.memory_model, Some(MemoryModel::Pso));
+        let res = StandaloneArgs::try_parse_from(
+            "kani file.rs --memory-model arm -Z memory-model".split_whitespace(),
+        );
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn check_coverage_report_requires_coverage() {
+        let res = StandaloneArgs::try_parse_from(
+            "kani file.rs --coverage-report lcov.info".split_whitespace(),
+        );
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --coverage --coverage-report lcov.info -Z line-coverage"
+                .split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.coverage_report, Some(PathBuf::from("lcov.info")));
+    }
+
+    #[test]
+    fn check_ffi_policy() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "[functions]\ngetenv = \"havoc\"").unwrap();
+        let path = file.path().display();
+        expect_validation_error(
+            &format!("kani file.rs --ffi-policy {path}"),
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            format!("kani file.rs --ffi-policy {path} -Z ffi-policy").split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        let policies = args.verify_opts.ffi_policy.unwrap();
+        assert_eq!(policies.functions["getenv"], FfiPolicy::Havoc);
+
+        let res = StandaloneArgs::try_parse_from(
+            "kani file.rs --ffi-policy missing.toml -Z ffi-policy".split_whitespace(),
+        );
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::ValueValidation);
+    }
+
+    #[test]
+    fn check_contract_specs() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "[contracts]\n\"std::fs::read\" = \"specs::read\"").unwrap();
+        let path = file.path().display();
+        expect_validation_error(
+            &format!("kani file.rs --contract-specs {path}"),
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            format!("kani file.rs --contract-specs {path} -Z function-contracts")
+                .split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        let specs = args.verify_opts.contract_specs.unwrap();
+        assert_eq!(specs.contracts["std::fs::read"], "specs::read");
+    }
+
+    #[test]
+    fn check_json_output_format() {
+        expect_validation_error(
+            "kani file.rs --results-file results.json",
+            ErrorKind::MissingRequiredArgument,
+        );
+        expect_validation_error(
+            "kani file.rs --output-format json --visualize --enable-unstable",
+            ErrorKind::ArgumentConflict,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --output-format json --results-file results.json".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert!(!args.verify_opts.prints_human_output());
+        assert_eq!(args.verify_opts.results_file, Some(PathBuf::from("results.json")));
+    }
+
+    #[test]
+    fn check_sarif() {
+        expect_validation_error(
+            "kani file.rs --sarif out.sarif --output-format old",
+            ErrorKind::ArgumentConflict,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --sarif out.sarif --output-format terse".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.sarif, Some(PathBuf::from("out.sarif")));
+    }
+
+    #[test]
+    fn check_max_stack_depth_unstable() {
+        expect_validation_error(
+            "kani file.rs --max-stack-depth 10",
+            ErrorKind::MissingRequiredArgument,
+        );
+        let args = StandaloneArgs::try_parse_from(
+            "kani file.rs --max-stack-depth 10 -Z stack-depth".split_whitespace(),
+        )
+        .unwrap();
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.max_stack_depth, Some(10));
+    }
+
+    #[test]
+    fn check_server_unstable() {
+        let args = CargoKaniArgs::try_parse_from(["cargo-kani", "server"]).unwrap();
+        assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+
+        let args = CargoKaniArgs::try_parse_from(["cargo-kani", "-Z", "server", "server"]).unwrap();
+        assert!(args.validate().is_ok());
+        assert!(matches!(args.command, Some(CargoKaniSubcommand::Server)));
+    }
+
+    #[test]
+    fn check_cfg_report_unstable() {
+        let args = CargoKaniArgs::try_parse_from(["cargo-kani", "cfg-report"]).unwrap();
+        assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+
+        let args = CargoKaniArgs::try_parse_from(["cargo-kani", "-Z", "cfg-report", "cfg-report"])
+            .unwrap();
+        assert!(args.validate().is_ok());
+        assert!(matches!(args.command, Some(CargoKaniSubcommand::CfgReport)));
+
+        let args = CargoKaniArgs::try_parse_from(["cargo-kani", "--cfg-report"]).unwrap();
+        assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_estimate_unstable() {
+        let args = CargoKaniArgs::try_parse_from(["cargo-kani", "estimate"]).unwrap();
+        assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+
+        let args =
+            CargoKaniArgs::try_parse_from(["cargo-kani", "-Z", "estimate", "estimate"]).unwrap();
+        assert!(args.validate().is_ok());
+        assert!(matches!(args.command, Some(CargoKaniSubcommand::Estimate)));
+    }
+
     /// Check if parsing the given argument string results in the given error.
     fn expect_validation_error(arg: &str, err: ErrorKind) {
         let args = StandaloneArgs::try_parse_from(arg.split_whitespace()).unwrap();
@@ -1,29 +1,173 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use crate::call_cargo::packages_to_verify;
 use anyhow::{bail, Result};
+use cargo_metadata::{MetadataCommand, Package};
 use clap::Parser;
+use serde_json::Value as JsonValue;
 use std::collections::BTreeMap;
 use std::ffi::OsString;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use toml::value::Table;
 use toml::Value;
 
 /// Produce the list of arguments to pass to ourself (cargo-kani).
 ///
-/// The arguments passed via command line have precedence over the ones from the Cargo.toml.
+/// The arguments passed via command line have precedence over the ones from the Cargo.toml of the
+/// package, which have precedence over the ones of the `[workspace.metadata.kani]` table of the
+/// workspace root.
 pub fn join_args(input_args: Vec<OsString>) -> Result<Vec<OsString>> {
-    let toml_path = cargo_locate_project(&input_args);
+    let toml_path = cargo_locate_project(&input_args, false);
     if toml_path.is_err() {
         // We're not inside a Cargo project. Don't error... yet.
         return Ok(input_args);
     }
-    let file = std::fs::read_to_string(toml_path?)?;
-    let (kani_args, cbmc_args) = toml_to_args(&file)?;
+    let toml_path = toml_path?;
+    let mut kani_args = Vec::new();
+    let mut cbmc_args = Vec::new();
+    if let Ok(root_path) = cargo_locate_project(&input_args, true)
+        && root_path != toml_path
+    {
+        let file = std::fs::read_to_string(&root_path)?;
+        let (root_kani_args, root_cbmc_args) = tables_to_args(&file, &["workspace.metadata.kani"])?;
+        kani_args.extend(resolve_c_lib_paths(root_kani_args, root_path.parent().unwrap()));
+        append_cbmc_args(&mut cbmc_args, root_cbmc_args);
+    }
+    let file = std::fs::read_to_string(&toml_path)?;
+    let (pkg_kani_args, pkg_cbmc_args) = toml_to_args(&file)?;
+    kani_args.extend(resolve_c_lib_paths(pkg_kani_args, toml_path.parent().unwrap()));
+    append_cbmc_args(&mut cbmc_args, pkg_cbmc_args);
+    if file.parse::<Value>()?.get("workspace").is_some() {
+        kani_args.extend(member_unstable_args(&input_args, &toml_path)?);
+    }
     merge_args(input_args, kani_args, cbmc_args)
 }
 
+/// Make the relative paths of the `--c-lib` arguments of a manifest relative to the directory of
+/// the manifest, `dir`, so they don't depend on the directory Kani is run from.
+fn resolve_c_lib_paths(args: Vec<OsString>, dir: &Path) -> Vec<OsString> {
+    let mut resolved = Vec::with_capacity(args.len());
+    for arg in args {
+        let path = Path::new(&arg);
+        if resolved.last().is_some_and(|flag| flag == "--c-lib") && path.is_relative() {
+            resolved.push(dir.join(path).into_os_string());
+        } else {
+            resolved.push(arg);
+        }
+    }
+    resolved
+}
+
+/// Append the `--cbmc-args` of another table to `cbmc_args`, which only keeps the first
+/// `--cbmc-args` flag.
+fn append_cbmc_args(cbmc_args: &mut Vec<OsString>, other: Vec<OsString>) {
+    if cbmc_args.is_empty() {
+        *cbmc_args = other;
+    } else {
+        cbmc_args.extend(other.into_iter().skip(1));
+    }
+}
+
+/// The unstable features enabled by the `[package.metadata.kani.unstable]` tables of the members
+/// of the workspace that will be verified, other than the package of the manifest at `toml_path`,
+/// whose configuration is already applied to the whole run.
+///
+/// Unstable features gate options of the whole run, so they can't be enabled for a single
+/// package.
+fn member_unstable_args(input_args: &[OsString], toml_path: &Path) -> Result<Vec<OsString>> {
+    let current_args = crate::args::CargoKaniArgs::parse_from(input_args);
+    let metadata = MetadataCommand::new().manifest_path(toml_path).no_deps().exec()?;
+    let mut args = Vec::new();
+    for package in packages_to_verify(&current_args.verify_opts, &metadata)? {
+        if package.manifest_path == toml_path {
+            continue;
+        }
+        let unstable =
+            package.metadata.pointer("/kani/unstable").and_then(|table| table.as_object());
+        for (name, value) in unstable.into_iter().flatten() {
+            if let Some(arg) = unstable_entry(name, &json_to_toml(value))? {
+                if !args.contains(&arg) {
+                    args.push(arg);
+                }
+            }
+        }
+    }
+    Ok(args)
+}
+
+/// The flags of the compiler that apply the `[package.metadata.kani]` configuration of `package`
+/// to its harnesses only, as if each harness had the corresponding attributes:
+/// - The `default-unwind` and `solver` flags set the `unwind` and `solver` of the harnesses that
+///   don't have these attributes, unless `--default-unwind` or `--solver` is given.
+/// - The `stubs` table maps the path of each original function to its stub, and it adds these
+///   stubs to every harness.
+///
+/// The flags of the package of the current manifest (`is_current`) are already applied to the
+/// whole run, and they can still be overridden from the command line, so only its stubs are
+/// passed.
+pub fn package_compiler_flags(package: &Package, is_current: bool) -> Result<Vec<String>> {
+    match package.metadata.get("kani") {
+        Some(config) => config_compiler_flags(&package.name, config, is_current),
+        None => Ok(vec![]),
+    }
+}
+
+/// The compiler flags of the `[package.metadata.kani]` table `config` of the package `name`.
+fn config_compiler_flags(name: &str, config: &JsonValue, is_current: bool) -> Result<Vec<String>> {
+    let mut flags = Vec::new();
+    if !is_current {
+        if let Some(unwind) = config.pointer("/flags/default-unwind") {
+            let unwind = match unwind {
+                JsonValue::String(unwind) => unwind.parse::<u32>().ok(),
+                JsonValue::Number(unwind) => {
+                    unwind.as_u64().and_then(|unwind| unwind.try_into().ok())
+                }
+                _ => None,
+            };
+            let Some(unwind) = unwind else {
+                bail!("invalid `default-unwind` in the configuration of package `{}`", name)
+            };
+            flags.push(format!("--package-unwind={unwind}"));
+        }
+        if let Some(solver) = config.pointer("/flags/solver") {
+            // The compiler flags are separated by spaces, so the solver can't contain any.
+            match solver.as_str() {
+                Some(solver) if !solver.contains(char::is_whitespace) => {
+                    flags.push(format!("--package-solver={solver}"))
+                }
+                _ => bail!(
+                    "invalid `solver` in the configuration of package `{}`: expected the name of \
+                    a solver or `bin=<SAT_SOLVER_BINARY>`; use the `#[kani::solver]` attribute \
+                    for other solvers",
+                    name
+                ),
+            }
+        }
+    }
+    if let Some(stubs) = config.get("stubs") {
+        let Some(stubs) = stubs.as_object() else {
+            bail!("expected a table of stubs in the configuration of package `{}`", name)
+        };
+        for (original, replacement) in stubs {
+            let Some(replacement) = replacement.as_str() else {
+                bail!("the stub of `{original}` in package `{}` is not a path", name)
+            };
+            flags.push(format!("--package-stub={original}={replacement}"));
+        }
+    }
+    Ok(flags)
+}
+
+/// Convert a JSON value of the cargo metadata back to the TOML value of the manifest.
+fn json_to_toml(value: &JsonValue) -> Value {
+    match value {
+        JsonValue::Bool(b) => Value::Boolean(*b),
+        other => Value::String(other.to_string()),
+    }
+}
+
 /// Join the arguments passed via command line with the ones found in the Cargo.toml.
 ///
 /// The arguments passed via command line have precedence over the ones from the Cargo.toml. Thus,
@@ -66,15 +210,25 @@ fn merge_args(
 }
 
 /// `locate-project` produces a response like: `/full/path/to/src/cargo-kani/Cargo.toml`
-fn cargo_locate_project(input_args: &[OsString]) -> Result<PathBuf> {
+///
+/// With `workspace`, this is the manifest of the root of the workspace instead.
+fn cargo_locate_project(input_args: &[OsString], workspace: bool) -> Result<PathBuf> {
     // Try parsing our command line arguments as they presently look, to see if a "manifest-path" has been given.
     let current_args = crate::args::CargoKaniArgs::parse_from(input_args);
+    let manifest_path = current_args.verify_opts.cargo.manifest_path;
 
-    if let Some(path) = current_args.verify_opts.cargo.manifest_path {
-        Ok(path)
+    if let Some(path) = manifest_path.as_ref().filter(|_| !workspace) {
+        Ok(path.clone())
     } else {
-        let cmd =
-            Command::new("cargo").args(["locate-project", "--message-format", "plain"]).output()?;
+        let mut cmd = Command::new("cargo");
+        cmd.args(["locate-project", "--message-format", "plain"]);
+        if workspace {
+            cmd.arg("--workspace");
+        }
+        if let Some(path) = manifest_path {
+            cmd.arg("--manifest-path").arg(path);
+        }
+        let cmd = cmd.output()?;
         if !cmd.status.success() {
             let err = std::str::from_utf8(&cmd.stderr)?;
             bail!("{}", err);
@@ -93,11 +247,16 @@ fn cargo_locate_project(input_args: &[OsString]) -> Result<PathBuf> {
 /// The tables supported are:
 /// "workspace.metadata.kani", "package.metadata.kani", "kani"
 fn toml_to_args(tomldata: &str) -> Result<(Vec<OsString>, Vec<OsString>)> {
+    tables_to_args(tomldata, &["workspace.metadata.kani", "package.metadata.kani", "kani"])
+}
+
+/// Extract the cargo-kani arguments of the given tables of a config toml string, in the same way
+/// as [toml_to_args].
+fn tables_to_args(tomldata: &str, tables: &[&str]) -> Result<(Vec<OsString>, Vec<OsString>)> {
     let config = tomldata.parse::<Value>()?;
     // To make testing easier, our function contract is to produce a stable ordering of flags for a given input.
     // Consequently, we use BTreeMap instead of HashMap here.
     let mut map: BTreeMap<String, Value> = BTreeMap::new();
-    let tables = ["workspace.metadata.kani", "package.metadata.kani", "kani"];
     let mut args = Vec::new();
 
     for table in tables {
@@ -292,6 +451,50 @@ fn check_unstable_table_works() {
         assert!(cbmc_args.is_empty());
     }
 
+    #[test]
+    fn check_workspace_table_only() {
+        let data = "[workspace.metadata.kani.flags]
+                         default-unwind = \"4\"
+                         [package.metadata.kani.flags]
+                         solver = \"kissat\"";
+        let (kani_args, cbmc_args) = tables_to_args(data, &["workspace.metadata.kani"]).unwrap();
+        assert_eq!(kani_args, vec!["--default-unwind", "4"]);
+        assert!(cbmc_args.is_empty());
+    }
+
+    #[test]
+    fn check_append_cbmc_args() {
+        let mut cbmc_args = Vec::new();
+        append_cbmc_args(&mut cbmc_args, vec!["--cbmc-args".into(), "--a".into()]);
+        append_cbmc_args(&mut cbmc_args, vec!["--cbmc-args".into(), "--b".into()]);
+        assert_eq!(cbmc_args, vec!["--cbmc-args", "--a", "--b"]);
+    }
+
+    #[test]
+    fn check_package_compiler_flags() {
+        let config = serde_json::json!({
+            "flags": { "default-unwind": "3", "solver": "minisat", "output-format": "terse" },
+            "stubs": { "crate::read_sensor": "crate::mock::read_sensor" },
+        });
+        assert_eq!(
+            config_compiler_flags("member", &config, false).unwrap(),
+            vec![
+                "--package-unwind=3",
+                "--package-solver=minisat",
+                "--package-stub=crate::read_sensor=crate::mock::read_sensor"
+            ]
+        );
+        // The flags of the current package are already applied to the whole run.
+        assert_eq!(
+            config_compiler_flags("member", &config, true).unwrap(),
+            vec!["--package-stub=crate::read_sensor=crate::mock::read_sensor"]
+        );
+        let invalid_solver = serde_json::json!({ "flags": { "solver": "smt2=z3 -in" } });
+        assert!(config_compiler_flags("member", &invalid_solver, false).is_err());
+        let invalid_unwind = serde_json::json!({ "flags": { "default-unwind": "many" } });
+        assert!(config_compiler_flags("member", &invalid_unwind, false).is_err());
+    }
+
     #[test]
     fn check_unstable_entry_enabled() -> Result<()> {
         let name = String::from("feature");
@@ -302,6 +505,14 @@ fn check_unstable_entry_enabled() -> Result<()> {
         Ok(())
     }
 
+    #[test]
+    fn check_resolve_c_lib_paths() {
+        let args: Vec<OsString> =
+            vec!["--c-lib".into(), "src/helper.c".into(), "--c-lib".into(), "/lib/impl.c".into()];
+        let resolved = resolve_c_lib_paths(args, Path::new("/work/pkg"));
+        assert_eq!(resolved, vec!["--c-lib", "/work/pkg/src/helper.c", "--c-lib", "/lib/impl.c"]);
+    }
+
     #[test]
     fn check_unstable_entry_disabled() -> Result<()> {
         let name = String::from("feature");
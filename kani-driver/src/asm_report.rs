@@ -0,0 +1,48 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for reporting the inline assembly that `--ignore-asm` replaced.
+//!
+//! The compiler replaces each inline assembly block reachable from a harness with
+//! nondeterministic values for its outputs, and records where the blocks are. The model of the
+//! harness is then only an approximation of the program, so we list the blocks after the result
+//! of the harness.
+
+use crate::session::KaniSession;
+use crate::util::format_warning;
+use kani_metadata::HarnessMetadata;
+
+impl KaniSession {
+    /// The warning about the inline assembly blocks that were replaced in the model of a harness.
+    pub fn report_havocked_asm(&self, harness: &HarnessMetadata) -> String {
+        if harness.havocked_asm.is_empty() {
+            return String::new();
+        }
+        format_warning(&format_havocked_asm(&harness.pretty_name, &harness.havocked_asm)) + "\n"
+    }
+}
+
+fn format_havocked_asm(name: &str, blocks: &[String]) -> String {
+    let mut output = format!(
+        "The model of harness `{name}` is approximate: `--ignore-asm` replaced the outputs of the \
+        following inline assembly blocks with nondeterministic values, and ignored their other \
+        effects:"
+    );
+    for block in blocks {
+        output.push_str(&format!("\n - {block}"));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_format_havocked_asm() {
+        let blocks = ["crate::rdtsc at src/lib.rs:4:5".to_string()];
+        let output = format_havocked_asm("check_time", &blocks);
+        assert!(output.starts_with("The model of harness `check_time` is approximate"));
+        assert!(output.ends_with(":\n - crate::rdtsc at src/lib.rs:4:5"));
+    }
+}
@@ -0,0 +1,303 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for suggesting assumptions that rule out the input of a counterexample.
+//!
+//! Harnesses are often refined by running them, finding out that the failure was triggered by
+//! an input that the code under verification does not expect, and adding a `kani::assume` to
+//! exclude it. With `--suggest-assumptions`, Kani reads the values that the harness variables got
+//! from `kani::any()` in the trace of the first failure, and prints one assumption per variable.
+//! For values at the edges of the type of the variable, such as `0` or the maximum of an unsigned
+//! integer, the suggestion excludes the whole boundary instead of the single value.
+
+use crate::call_cbmc::VerificationResult;
+use crate::cbmc_output_parser::{CheckStatus, Property, TraceData, TraceItem, TraceValue};
+use crate::session::KaniSession;
+use kani_metadata::HarnessMetadata;
+use std::fmt::Write;
+
+impl KaniSession {
+    /// The assumptions that exclude the input of the first failure of a harness (if any).
+    pub fn suggest_assumptions(
+        &self,
+        harness: &HarnessMetadata,
+        verification_result: &VerificationResult,
+    ) -> String {
+        if !self.args.suggest_assumptions {
+            return String::new();
+        }
+        let Ok(properties) = &verification_result.results else { return String::new() };
+        let Some(failure) = first_failure(properties) else { return String::new() };
+        let inputs = harness_inputs(failure.trace.as_deref().unwrap_or_default(), harness);
+        if inputs.is_empty() {
+            return format!(
+                "WARNING: Kani could not suggest assumptions for `{}` because the failure does \
+                not depend on any scalar variable of the harness.\n",
+                harness.pretty_name
+            );
+        }
+        let mut output = format!(
+            "Assumptions that exclude the input of the failure in `{}`:\n",
+            harness.pretty_name
+        );
+        for input in &inputs {
+            writeln!(output, "    kani::assume({});", input.exclusion()).unwrap();
+        }
+        let exact = inputs.iter().map(Input::equality).collect::<Vec<_>>().join(" && ");
+        writeln!(
+            output,
+            "Any of them excludes this input. To exclude only this exact input, use:\n    \
+            kani::assume(!({exact}));"
+        )
+        .unwrap();
+        output
+    }
+}
+
+/// A variable of the harness and the value it got from `kani::any()`.
+#[derive(Debug, PartialEq)]
+struct Input {
+    name: String,
+    value: InputValue,
+}
+
+#[derive(Debug, PartialEq)]
+enum InputValue {
+    Bool(bool),
+    /// An integer, whose signedness comes from the type of the variable, if CBMC reports it.
+    Int {
+        value: i128,
+        width: u32,
+        signed: Option<bool>,
+    },
+    /// A floating-point number as printed by CBMC, which is an `f32` if `width` is 32.
+    Float {
+        value: String,
+        width: u32,
+    },
+}
+
+impl Input {
+    /// A condition that excludes the value of this input, and the class of values it belongs to.
+    fn exclusion(&self) -> String {
+        let name = &self.name;
+        match &self.value {
+            InputValue::Bool(true) => format!("!{name}"),
+            InputValue::Bool(false) => name.clone(),
+            InputValue::Int { value: 0, .. } => format!("{name} != 0"),
+            InputValue::Int { value, width, signed: Some(signed) } => {
+                let (min, max) = if *signed {
+                    (-(1i128 << (width - 1)), (1i128 << (width - 1)) - 1)
+                } else {
+                    (0, (1i128 << width) - 1)
+                };
+                if *value == max {
+                    format!("{name} < {value}")
+                } else if *value == min {
+                    format!("{name} > {value}")
+                } else {
+                    format!("{name} != {value}")
+                }
+            }
+            InputValue::Int { value, signed: None, .. } => format!("{name} != {value}"),
+            InputValue::Float { value, .. } if value.to_lowercase().contains("nan") => {
+                format!("!{name}.is_nan()")
+            }
+            InputValue::Float { value, .. } if value.to_lowercase().contains("inf") => {
+                format!("{name}.is_finite()")
+            }
+            InputValue::Float { value, .. } => format!("{name} != {value}"),
+        }
+    }
+
+    /// A condition that only holds for the value of this input.
+    fn equality(&self) -> String {
+        let name = &self.name;
+        match &self.value {
+            InputValue::Bool(true) => name.clone(),
+            InputValue::Bool(false) => format!("!{name}"),
+            InputValue::Int { value, .. } => format!("{name} == {value}"),
+            InputValue::Float { value, .. } if value.to_lowercase().contains("nan") => {
+                format!("{name}.is_nan()")
+            }
+            InputValue::Float { value, width } if value.to_lowercase().contains("inf") => {
+                let sign = if value.starts_with('-') { "-" } else { "" };
+                let ty = if *width == 32 { "f32" } else { "f64" };
+                format!("{name} == {sign}{ty}::INFINITY")
+            }
+            InputValue::Float { value, .. } => format!("{name} == {value}"),
+        }
+    }
+}
+
+fn first_failure(properties: &[Property]) -> Option<&Property> {
+    properties.iter().find(|prop| {
+        prop.status == CheckStatus::Failure
+            && !prop.is_cover_property()
+            && prop.property_class() != "unwind"
+    })
+}
+
+/// Collect the variables of the harness that are assigned right after a call to `kani::any()`
+/// returns. Variables that aren't scalars, such as structs, are skipped.
+fn harness_inputs(trace: &[TraceItem], harness: &HarnessMetadata) -> Vec<Input> {
+    let mut inputs: Vec<Input> = vec![];
+    let mut after_any = false;
+    for item in trace {
+        let (Some(lhs), Some(location)) = (&item.lhs, &item.source_location) else { continue };
+        if item.step_type != "assignment" {
+            continue;
+        }
+        let function = location.function.as_deref().unwrap_or_default();
+        if function.starts_with("kani::any_raw_internal") {
+            after_any = true;
+        } else if after_any && function == harness.pretty_name && is_user_variable(lhs) {
+            after_any = false;
+            if let Some(value) = item.value.as_ref().and_then(parse_value) {
+                // Keep the last value if the variable is assigned more than once.
+                inputs.retain(|input| &input.name != lhs);
+                inputs.push(Input { name: lhs.clone(), value });
+            }
+        }
+    }
+    inputs
+}
+
+/// Whether this is a variable declared by the user, i.e., neither a field nor a temporary.
+fn is_user_variable(lhs: &str) -> bool {
+    lhs.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && !lhs.starts_with("var_")
+        && !lhs.starts_with(|c: char| c.is_numeric())
+}
+
+fn parse_value(value: &TraceValue) -> Option<InputValue> {
+    let data = match value.data.as_ref()? {
+        TraceData::Bool(value) => return Some(InputValue::Bool(*value)),
+        TraceData::NonBool(data) => data.trim(),
+    };
+    match data.to_lowercase().as_str() {
+        "true" => return Some(InputValue::Bool(true)),
+        "false" => return Some(InputValue::Bool(false)),
+        _ => {}
+    }
+    if value.name == "float" {
+        let width = value.width.unwrap_or(64);
+        return Some(InputValue::Float { value: data.to_string(), width });
+    }
+    let width = value.width.filter(|width| (1..=64).contains(width))?;
+    // The type is the C name of the type of the variable, e.g., `unsigned char` or `signed int`.
+    let signed = value.typ.as_deref().map(|typ| !typ.starts_with("unsigned"));
+    // CBMC may add a C suffix to integers, e.g.: `0l` or `10ul`.
+    let value = data.trim_end_matches(|c: char| c.is_ascii_alphabetic()).parse().ok()?;
+    Some(InputValue::Int { value, width, signed })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::SourceLocation;
+    use crate::metadata::mock_proof_harness;
+
+    fn assign(function: &str, lhs: &str, data: &str, width: u32) -> TraceItem {
+        let typ = if width == 8 { "unsigned char" } else { "unsigned int" };
+        TraceItem {
+            thread: 0,
+            step_type: "assignment".to_string(),
+            hidden: false,
+            lhs: Some(lhs.to_string()),
+            source_location: Some(SourceLocation {
+                column: None,
+                file: None,
+                function: Some(function.to_string()),
+                line: None,
+            }),
+            value: Some(TraceValue {
+                name: "integer".to_string(),
+                binary: None,
+                data: Some(TraceData::NonBool(data.to_string())),
+                width: Some(width),
+                typ: Some(typ.to_string()),
+                members: vec![],
+                elements: vec![],
+            }),
+            function: None,
+        }
+    }
+
+    fn input(name: &str, value: i128, width: u32) -> Input {
+        Input {
+            name: name.to_string(),
+            value: InputValue::Int { value, width, signed: Some(false) },
+        }
+    }
+
+    fn signed_input(name: &str, value: i128, width: u32) -> Input {
+        Input {
+            name: name.to_string(),
+            value: InputValue::Int { value, width, signed: Some(true) },
+        }
+    }
+
+    #[test]
+    fn check_harness_inputs() {
+        let harness = mock_proof_harness("check", None, None, None);
+        let any = "kani::any_raw_internal::<u8, 1>";
+        let trace = [
+            assign(any, "goto_symex$$return_value", "255", 8),
+            assign("check", "x", "255", 8),
+            assign("check", "sum", "0", 8),
+            assign(any, "goto_symex$$return_value", "3", 8),
+            assign("kani::any::<u8>", "result", "3", 8),
+            assign("check", "var_4", "3", 8),
+            assign(any, "goto_symex$$return_value", "3", 8),
+            assign("check", "y", "3", 8),
+        ];
+        assert_eq!(harness_inputs(&trace, &harness), vec![input("x", 255, 8), input("y", 3, 8)]);
+    }
+
+    #[test]
+    fn check_exclusions() {
+        assert_eq!(input("x", 0, 32).exclusion(), "x != 0");
+        assert_eq!(input("x", 255, 8).exclusion(), "x < 255");
+        assert_eq!(input("x", 127, 8).exclusion(), "x != 127");
+        assert_eq!(signed_input("x", -128, 8).exclusion(), "x > -128");
+        assert_eq!(signed_input("x", 127, 8).exclusion(), "x < 127");
+        assert_eq!(signed_input("x", 42, 8).exclusion(), "x != 42");
+        let unknown = Input {
+            name: "x".to_string(),
+            value: InputValue::Int { value: 255, width: 8, signed: None },
+        };
+        assert_eq!(unknown.exclusion(), "x != 255");
+        let flag = Input { name: "flag".to_string(), value: InputValue::Bool(true) };
+        assert_eq!(flag.exclusion(), "!flag");
+        assert_eq!(flag.equality(), "flag");
+        let float = |value: &str, width| Input {
+            name: "f".to_string(),
+            value: InputValue::Float { value: value.to_string(), width },
+        };
+        assert_eq!(float("NaN", 64).exclusion(), "!f.is_nan()");
+        assert_eq!(float("-INFINITY", 32).equality(), "f == -f32::INFINITY");
+        assert_eq!(float("INFINITY", 64).equality(), "f == f64::INFINITY");
+    }
+
+    #[test]
+    fn check_parse_value() {
+        let parse = |data: &str| {
+            parse_value(&TraceValue {
+                name: "integer".to_string(),
+                binary: None,
+                data: Some(TraceData::NonBool(data.to_string())),
+                width: Some(64),
+                typ: Some("unsigned long int".to_string()),
+                members: vec![],
+                elements: vec![],
+            })
+        };
+        assert_eq!(
+            parse("10ul"),
+            Some(InputValue::Int { value: 10, width: 64, signed: Some(false) })
+        );
+        assert_eq!(parse("TRUE"), Some(InputValue::Bool(true)));
+        assert_eq!(parse("{ .x=1 }"), None);
+    }
+}
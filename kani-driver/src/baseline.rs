@@ -0,0 +1,291 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for gating verification runs on a baseline of known results.
+//!
+//! Codebases that adopt Kani incrementally often have harnesses that are known to fail. The
+//! `--baseline` file records the status of each harness and the covers that it can't reach:
+//!
+//! ```json
+//! {
+//!   "harnesses": {
+//!     "my_crate::check_foo": { "status": "success", "unreachable_covers": [] },
+//!     "my_crate::check_bar": { "status": "failure", "unreachable_covers": ["cover condition: x > 3"] }
+//!   }
+//! }
+//! ```
+//!
+//! Without `--check-against-baseline`, Kani updates the file with the harnesses verified in the
+//! run. With it, Kani compares the results with the file and only fails on regressions, i.e.,
+//! harnesses that fail or time out although they are successful in the baseline or aren't in it
+//! at all, and covers that are unreachable although they weren't before. Covers are identified
+//! by their description.
+
+use crate::call_cbmc::VerificationStatus;
+use crate::cbmc_output_parser::CheckStatus;
+use crate::harness_runner::HarnessResult;
+use crate::session::KaniSession;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::path::Path;
+
+/// The recorded result of a harness.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct BaselineEntry {
+    pub status: VerificationStatus,
+    /// The descriptions of the covers of the harness that are unreachable.
+    #[serde(default)]
+    pub unreachable_covers: Vec<String>,
+}
+
+/// The recorded results of the harnesses, indexed by their name with the crate prefix.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Baseline {
+    #[serde(default)]
+    pub harnesses: BTreeMap<String, BaselineEntry>,
+}
+
+/// A result that is worse than the one in the baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Regression {
+    /// A harness failed or timed out, although it's successful in the baseline.
+    Harness { harness: String, status: VerificationStatus },
+    /// A harness that isn't in the baseline failed or timed out.
+    NewHarness { harness: String, status: VerificationStatus },
+    /// A cover of a harness is unreachable, although it isn't in the baseline.
+    UnreachableCover { harness: String, cover: String },
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Regression::Harness { harness, status } => {
+                write!(f, "`{harness}` {}, but it is successful in the baseline", outcome(*status))
+            }
+            Regression::NewHarness { harness, status } => {
+                write!(f, "`{harness}` {}, and it isn't in the baseline", outcome(*status))
+            }
+            Regression::UnreachableCover { harness, cover } => {
+                write!(f, "the cover \"{cover}\" of `{harness}` became unreachable")
+            }
+        }
+    }
+}
+
+fn outcome(status: VerificationStatus) -> &'static str {
+    match status {
+        VerificationStatus::Success => "succeeded",
+        VerificationStatus::Failure => "failed",
+        VerificationStatus::Timeout => "timed out",
+    }
+}
+
+impl BaselineEntry {
+    fn from_result(result: &HarnessResult<'_>) -> BaselineEntry {
+        let unreachable_covers = result
+            .result
+            .results
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter(|prop| prop.is_cover_property() && prop.status == CheckStatus::Unreachable)
+            .map(|prop| prop.description.clone())
+            .collect();
+        BaselineEntry { status: result.result.status, unreachable_covers }
+    }
+}
+
+impl Baseline {
+    /// Read the baseline at `path`. A file that doesn't exist yet has no harnesses.
+    pub fn read(path: &Path) -> Result<Baseline> {
+        if !path.exists() {
+            return Ok(Baseline::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read the baseline from `{}`", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse the baseline from `{}`", path.display()))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write the baseline to `{}`", path.display()))
+    }
+
+    /// Record the results of this run, keeping the other harnesses.
+    pub fn update(&mut self, results: &[HarnessResult<'_>]) {
+        for result in results {
+            self.harnesses.insert(
+                result.harness.get_harness_name_with_crate(),
+                BaselineEntry::from_result(result),
+            );
+        }
+    }
+
+    /// The results of this run that are worse than the ones in the baseline.
+    pub fn regressions(&self, results: &[HarnessResult<'_>]) -> Vec<Regression> {
+        let mut regressions = vec![];
+        for result in results {
+            let harness = result.harness.get_harness_name_with_crate();
+            let current = BaselineEntry::from_result(result);
+            let Some(expected) = self.harnesses.get(&harness) else {
+                if current.status != VerificationStatus::Success {
+                    regressions.push(Regression::NewHarness { harness, status: current.status });
+                }
+                continue;
+            };
+            if current.status != VerificationStatus::Success
+                && expected.status == VerificationStatus::Success
+            {
+                regressions
+                    .push(Regression::Harness { harness: harness.clone(), status: current.status });
+            }
+            for cover in current.unreachable_covers {
+                if !expected.unreachable_covers.contains(&cover) {
+                    regressions
+                        .push(Regression::UnreachableCover { harness: harness.clone(), cover });
+                }
+            }
+        }
+        regressions
+    }
+
+    /// The harnesses of this run that are successful, although they fail or time out in the
+    /// baseline.
+    pub fn improvements<'a>(&self, results: &'a [HarnessResult<'_>]) -> Vec<&'a str> {
+        results
+            .iter()
+            .filter(|result| {
+                result.result.status == VerificationStatus::Success
+                    && self
+                        .harnesses
+                        .get(&result.harness.get_harness_name_with_crate())
+                        .is_some_and(|entry| entry.status != VerificationStatus::Success)
+            })
+            .map(|result| result.harness.pretty_name.as_str())
+            .collect()
+    }
+}
+
+impl KaniSession {
+    /// Record the results of this run in the `--baseline` file, unless it's only checked
+    /// against with `--check-against-baseline`.
+    pub fn update_baseline(&self, results: &[HarnessResult<'_>]) -> Result<()> {
+        let Some(path) = &self.args.baseline else { return Ok(()) };
+        if self.args.check_against_baseline {
+            return Ok(());
+        }
+        let mut baseline = Baseline::read(path)?;
+        baseline.update(results);
+        baseline.write(path)
+    }
+
+    /// Compare the results of this run with the `--baseline` file and print the differences.
+    /// Return whether there is any regression.
+    pub fn check_against_baseline(&self, results: &[HarnessResult<'_>]) -> Result<bool> {
+        let Some(path) = &self.args.baseline else { return Ok(false) };
+        let baseline = Baseline::read(path)?;
+        let regressions = baseline.regressions(results);
+        if self.args.prints_human_output() {
+            for harness in baseline.improvements(results) {
+                println!(
+                    "Harness `{harness}` is successful now, but it isn't successful in the \
+                    baseline. Update the baseline to keep this improvement."
+                );
+            }
+            if regressions.is_empty() {
+                let known = results
+                    .iter()
+                    .filter(|result| result.result.status != VerificationStatus::Success)
+                    .count();
+                println!(
+                    "No regressions against the baseline `{}` ({known} known failures).",
+                    path.display()
+                );
+            } else {
+                println!("Regressions against the baseline `{}`:", path.display());
+                for regression in &regressions {
+                    println!(" - {regression}");
+                }
+            }
+        }
+        Ok(!regressions.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_cbmc::VerificationResult;
+    use crate::metadata::mock_proof_harness;
+    use kani_metadata::HarnessMetadata;
+
+    fn result(harness: &HarnessMetadata, status: VerificationStatus) -> HarnessResult<'_> {
+        let mut result = VerificationResult::mock_success();
+        result.status = status;
+        HarnessResult { harness, result, fingerprint: None }
+    }
+
+    fn entry(status: VerificationStatus, unreachable_covers: &[&str]) -> BaselineEntry {
+        BaselineEntry {
+            status,
+            unreachable_covers: unreachable_covers.iter().map(|cover| cover.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn check_regressions() {
+        let harnesses = ["check_known", "check_regressed", "check_new", "check_fixed"]
+            .map(|name| mock_proof_harness(name, None, Some("krate"), None));
+        let mut baseline = Baseline::default();
+        baseline.harnesses.insert(
+            "krate::check_known".to_string(),
+            entry(VerificationStatus::Failure, &["cover condition: x > 3"]),
+        );
+        baseline
+            .harnesses
+            .insert("krate::check_regressed".to_string(), entry(VerificationStatus::Success, &[]));
+        baseline
+            .harnesses
+            .insert("krate::check_fixed".to_string(), entry(VerificationStatus::Timeout, &[]));
+        let results = vec![
+            result(&harnesses[0], VerificationStatus::Failure),
+            result(&harnesses[1], VerificationStatus::Timeout),
+            result(&harnesses[2], VerificationStatus::Failure),
+            result(&harnesses[3], VerificationStatus::Success),
+        ];
+        assert_eq!(
+            baseline.regressions(&results),
+            vec![
+                Regression::Harness {
+                    harness: "krate::check_regressed".to_string(),
+                    status: VerificationStatus::Timeout
+                },
+                Regression::NewHarness {
+                    harness: "krate::check_new".to_string(),
+                    status: VerificationStatus::Failure
+                },
+            ]
+        );
+        assert_eq!(baseline.improvements(&results), vec!["check_fixed"]);
+
+        baseline.update(&results);
+        assert_eq!(baseline.harnesses.len(), 4);
+        assert_eq!(
+            baseline.harnesses["krate::check_fixed"],
+            entry(VerificationStatus::Success, &[])
+        );
+        assert!(baseline.regressions(&results).is_empty());
+    }
+
+    #[test]
+    fn check_baseline_format() {
+        let baseline: Baseline =
+            serde_json::from_str(r#"{ "harnesses": { "krate::check": { "status": "timeout" } } }"#)
+                .unwrap();
+        assert_eq!(baseline.harnesses["krate::check"], entry(VerificationStatus::Timeout, &[]));
+    }
+}
@@ -0,0 +1,273 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for the C files of `--c-lib`, which are compiled with `goto-cc` and linked into the
+//! goto model of the Rust code.
+//!
+//! Each file is compiled on its own, so that a compilation error names the file. Before linking,
+//! we compare the Rust declarations of the foreign functions that the C files define with their
+//! C definitions. `goto-cc` links symbols by name only, so a declaration with the wrong number or
+//! the wrong types of parameters would otherwise be verified against a model that doesn't match
+//! the behavior of the program.
+
+use anyhow::{bail, Context, Result};
+use cbmc::irep::goto_binary_serde::read_goto_binary_file;
+use cbmc::irep::{Irep, IrepId, Symbol, SymbolTable};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::debug;
+
+use crate::session::KaniSession;
+
+impl KaniSession {
+    /// Compile the files of `--c-lib` to goto binaries in `dir`. Files that are already goto
+    /// binaries (e.g. `.o` files built with `goto-cc`) are used as is.
+    pub fn compile_c_libs(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut objects = vec![];
+        for (idx, file) in self.args.c_lib.iter().enumerate() {
+            if file.extension().map_or(true, |ext| ext != "c") {
+                objects.push(file.clone());
+                continue;
+            }
+            let stem = file.file_stem().unwrap().to_string_lossy();
+            let object = dir.join(format!("{idx}_{stem}.o"));
+            let mut cmd = Command::new("goto-cc");
+            cmd.arg("-c").arg(file).arg("-o").arg(&object);
+            if self.args.is_32_bit_target() {
+                cmd.arg("-m32");
+            }
+            self.run_suppress(cmd)
+                .with_context(|| format!("Failed to compile C library `{}`", file.display()))?;
+            objects.push(object);
+        }
+        Ok(objects)
+    }
+
+    /// Check that the foreign functions declared by the Rust goto binaries `inputs` and defined by
+    /// the C goto binaries `c_objects` have compatible signatures.
+    ///
+    /// This is a best-effort check: binaries that can't be read are skipped.
+    pub fn check_ffi_signatures(&self, inputs: &[PathBuf], c_objects: &[PathBuf]) -> Result<()> {
+        let read = |file: &PathBuf| {
+            read_goto_binary_file(file)
+                .map_err(|error| debug!(?error, ?file, "check_ffi_signatures"))
+                .ok()
+        };
+        let c_tables: Vec<_> = c_objects.iter().filter_map(read).collect();
+        if c_tables.is_empty() {
+            return Ok(());
+        }
+        let c_functions = c_functions(&c_tables);
+        let mut mismatches = BTreeMap::new();
+        for table in inputs.iter().filter_map(read) {
+            for symbol in
+                table.symbol_table.values().filter(|symbol| is_foreign_declaration(symbol))
+            {
+                let Some(c_symbol) = c_functions.get(symbol.name.to_string().as_str()) else {
+                    continue;
+                };
+                if let Some(mismatch) = signature_mismatch(&symbol.typ, &c_symbol.typ) {
+                    mismatches.insert(symbol.name.to_string(), mismatch);
+                }
+            }
+        }
+        if mismatches.is_empty() {
+            return Ok(());
+        }
+        let mut msg = String::from(
+            "The Rust declarations of the following foreign functions don't match their \
+            definition in the C libraries:",
+        );
+        for (name, mismatch) in mismatches {
+            write!(msg, "\n  - `{name}`: {mismatch}").unwrap();
+        }
+        bail!(msg)
+    }
+}
+
+/// The functions declared or defined by the C libraries, by name.
+fn c_functions(tables: &[SymbolTable]) -> BTreeMap<String, &Symbol> {
+    tables
+        .iter()
+        .flat_map(|table| table.symbol_table.values())
+        .filter(|symbol| !symbol.is_type && !symbol.is_file_local && is_code(&symbol.typ))
+        .map(|symbol| (symbol.name.to_string(), symbol))
+        .collect()
+}
+
+/// Whether `symbol` is a foreign function that the Rust code declares without a definition.
+fn is_foreign_declaration(symbol: &Symbol) -> bool {
+    symbol.is_extern && !symbol.is_type && symbol.value.is_nil() && is_code(&symbol.typ)
+}
+
+fn is_code(typ: &Irep) -> bool {
+    typ.id == IrepId::Code
+}
+
+/// A coarse classification of the types at the FFI boundary. Types of the two languages that
+/// can't be compared, such as a struct and its Rust counterpart, whose tags differ, are assumed
+/// to match.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FfiType {
+    Void,
+    /// An integer, an enum or a boolean, with its width in bits, if known.
+    Integer(Option<u64>),
+    Float(Option<u64>),
+    Pointer,
+    Aggregate,
+    Unknown,
+}
+
+impl FfiType {
+    fn of(typ: &Irep) -> FfiType {
+        let width = typ.lookup_as_string(IrepId::Width).and_then(|width| width.parse().ok());
+        match typ.id {
+            IrepId::Empty => FfiType::Void,
+            IrepId::Signedbv | IrepId::Unsignedbv | IrepId::CBool | IrepId::Bool => {
+                FfiType::Integer(width)
+            }
+            IrepId::CEnum | IrepId::CEnumTag => FfiType::Integer(None),
+            IrepId::Floatbv => FfiType::Float(width),
+            IrepId::Pointer => FfiType::Pointer,
+            // Kani represents `()` as an empty struct.
+            IrepId::StructTag
+                if typ.lookup_as_string(IrepId::Identifier).as_deref() == Some("tag-Unit") =>
+            {
+                FfiType::Void
+            }
+            IrepId::Struct | IrepId::StructTag | IrepId::Union | IrepId::UnionTag => {
+                FfiType::Aggregate
+            }
+            _ => FfiType::Unknown,
+        }
+    }
+
+    fn is_compatible(self, other: FfiType) -> bool {
+        match (self, other) {
+            (FfiType::Unknown, _) | (_, FfiType::Unknown) => true,
+            (FfiType::Integer(a), FfiType::Integer(b)) | (FfiType::Float(a), FfiType::Float(b)) => {
+                a.is_none() || b.is_none() || a == b
+            }
+            _ => self == other,
+        }
+    }
+}
+
+impl std::fmt::Display for FfiType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FfiType::Void => write!(f, "nothing"),
+            FfiType::Integer(Some(width)) => write!(f, "a {width}-bit integer"),
+            FfiType::Integer(None) => write!(f, "an integer"),
+            FfiType::Float(Some(width)) => write!(f, "a {width}-bit float"),
+            FfiType::Float(None) => write!(f, "a float"),
+            FfiType::Pointer => write!(f, "a pointer"),
+            FfiType::Aggregate => write!(f, "a struct or union"),
+            FfiType::Unknown => write!(f, "an unknown type"),
+        }
+    }
+}
+
+/// The parameter types, return type and whether the function is variadic of a code type.
+fn signature(typ: &Irep) -> (Vec<FfiType>, FfiType, bool) {
+    let params = typ.lookup(IrepId::Parameters);
+    let param_types = params
+        .map(|params| {
+            params
+                .sub
+                .iter()
+                .map(|param| param.lookup(IrepId::Type).map_or(FfiType::Unknown, FfiType::of))
+                .collect()
+        })
+        .unwrap_or_default();
+    let variadic = params.and_then(|params| params.lookup(IrepId::Ellipsis)).is_some();
+    let ret = typ.lookup(IrepId::ReturnType).map_or(FfiType::Unknown, FfiType::of);
+    (param_types, ret, variadic)
+}
+
+/// Describe how the signature of the Rust declaration `rust` differs from the C definition `c`,
+/// if it does.
+fn signature_mismatch(rust: &Irep, c: &Irep) -> Option<String> {
+    let (rust_params, rust_ret, rust_variadic) = signature(rust);
+    let (c_params, c_ret, c_variadic) = signature(c);
+    if rust_variadic != c_variadic {
+        let (variadic, other) = if c_variadic { ("C", "Rust") } else { ("Rust", "C") };
+        return Some(format!("the {variadic} function is variadic, but the {other} one isn't"));
+    }
+    let count_differs = if c_variadic {
+        rust_params.len() < c_params.len()
+    } else {
+        rust_params.len() != c_params.len()
+    };
+    if count_differs {
+        return Some(format!(
+            "Rust declares {} parameter(s), but C defines {}",
+            rust_params.len(),
+            c_params.len()
+        ));
+    }
+    for (idx, (rust_param, c_param)) in rust_params.iter().zip(&c_params).enumerate() {
+        if !rust_param.is_compatible(*c_param) {
+            return Some(format!("parameter {idx} is {rust_param} in Rust, but {c_param} in C",));
+        }
+    }
+    if !rust_ret.is_compatible(c_ret) {
+        return Some(format!("Rust returns {rust_ret}, but C returns {c_ret}"));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(width: u64) -> Irep {
+        Irep::just_id(IrepId::Signedbv).with_named_sub(IrepId::Width, Irep::just_int_id(width))
+    }
+
+    fn float() -> Irep {
+        Irep::just_id(IrepId::Floatbv).with_named_sub(IrepId::Width, Irep::just_int_id(32))
+    }
+
+    fn pointer() -> Irep {
+        Irep::just_id(IrepId::Pointer)
+    }
+
+    fn unit() -> Irep {
+        Irep::just_id(IrepId::StructTag)
+            .with_named_sub(IrepId::Identifier, Irep::just_string_id("tag-Unit"))
+    }
+
+    fn code(params: Vec<Irep>, ret: Irep) -> Irep {
+        let params = params
+            .into_iter()
+            .map(|typ| Irep::just_id(IrepId::Parameter).with_named_sub(IrepId::Type, typ))
+            .collect();
+        Irep::just_id(IrepId::Code)
+            .with_named_sub(IrepId::Parameters, Irep::just_sub(params))
+            .with_named_sub(IrepId::ReturnType, ret)
+    }
+
+    #[test]
+    fn check_signature_mismatch() {
+        let c = code(vec![int(32), pointer()], Irep::just_id(IrepId::Empty));
+        assert_eq!(signature_mismatch(&code(vec![int(32), pointer()], unit()), &c), None);
+        assert_eq!(
+            signature_mismatch(&code(vec![int(32)], unit()), &c).unwrap(),
+            "Rust declares 1 parameter(s), but C defines 2"
+        );
+        assert_eq!(
+            signature_mismatch(&code(vec![int(64), pointer()], unit()), &c).unwrap(),
+            "parameter 0 is a 64-bit integer in Rust, but a 32-bit integer in C"
+        );
+        assert_eq!(
+            signature_mismatch(&code(vec![int(32), pointer()], float()), &c).unwrap(),
+            "Rust returns a 32-bit float, but C returns nothing"
+        );
+        // Types that can't be compared are assumed to match.
+        let unknown = Irep::just_id(IrepId::Array);
+        assert_eq!(signature_mismatch(&code(vec![unknown, pointer()], unit()), &c), None);
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use crate::args::VerificationArgs;
+use crate::args_toml::package_compiler_flags;
 use crate::call_single_file::to_rustc_arg;
 use crate::project::Artifact;
 use crate::session::KaniSession;
@@ -45,7 +46,7 @@ pub struct CargoOutputs {
 impl KaniSession {
     /// Calls `cargo_build` to generate `*.symtab.json` files in `target_dir`
     pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
-        let build_target = env!("TARGET"); // see build.rs
+        let build_target = self.args.build_target();
         let metadata = self.cargo_metadata(build_target)?;
         let target_dir = self
             .args
@@ -79,7 +80,7 @@ pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
             cargo_args.push(format!("--features={}", features.join(",")).into());
         }
 
-        cargo_args.append(&mut cargo_config_args());
+        cargo_args.append(&mut cargo_config_args(build_target));
 
         cargo_args.push("--target-dir".into());
         cargo_args.push(target_dir.into());
@@ -102,12 +103,22 @@ pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
         // Arguments that will only be passed to the target package.
         let mut pkg_args: Vec<String> = vec![];
         pkg_args.extend(["--".to_string(), self.reachability_arg()]);
+        if self.args.test_modules {
+            // Only the crates under verification are built with `cfg(test)`.
+            pkg_args.extend(["--cfg".to_string(), "test".to_string()]);
+        }
 
         let mut found_target = false;
         let packages = packages_to_verify(&self.args, &metadata)?;
         let mut artifacts = vec![];
         let mut failed_targets = vec![];
         for package in packages {
+            let is_current = metadata.root_package().is_some_and(|root| root.id == package.id);
+            let package_flags = package_compiler_flags(package, is_current)?;
+            let mut pkg_args = pkg_args.clone();
+            if !package_flags.is_empty() {
+                pkg_args.push(to_rustc_arg(package_flags));
+            }
             for verification_target in package_targets(&self.args, package) {
                 let mut cmd = Command::new("cargo");
                 cmd.arg(session::toolchain_shorthand())
@@ -150,7 +161,7 @@ pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
         })
     }
 
-    fn cargo_metadata(&self, build_target: &str) -> Result<Metadata> {
+    pub fn cargo_metadata(&self, build_target: &str) -> Result<Metadata> {
         let mut cmd = MetadataCommand::new();
 
         // restrict metadata command to host platform. References:
@@ -252,10 +263,10 @@ fn run_cargo(&self, cargo_cmd: Command, target: &Target) -> Result<Option<Artifa
     }
 }
 
-pub fn cargo_config_args() -> Vec<OsString> {
+pub fn cargo_config_args(target: &str) -> Vec<OsString> {
     [
         "--target",
-        env!("TARGET"),
+        target,
         // Propagate `--cfg=kani` to build scripts.
         "-Zhost-config",
         "-Ztarget-applies-to-host",
@@ -306,7 +317,7 @@ fn validate_package_names(package_names: &[String], packages: &[Package]) -> Res
 /// In addition, if either `--package <pkg>` or `--exclude <pkg>` is given,
 /// validate that `<pkg>` is a package name in the workspace, or return an error
 /// otherwise.
-fn packages_to_verify<'b>(
+pub fn packages_to_verify<'b>(
     args: &VerificationArgs,
     metadata: &'b Metadata,
 ) -> Result<Vec<&'b Package>> {
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use anyhow::{bail, Result};
-use kani_metadata::{CbmcSolver, HarnessMetadata};
+use kani_metadata::{CbmcPathStrategy, CbmcSolver, HarnessAttributes, HarnessMetadata};
+use serde::{Deserialize, Serialize};
 use std::ffi::OsString;
 use std::fmt::Write;
 use std::path::Path;
@@ -13,17 +14,22 @@
 use crate::cbmc_output_parser::{
     extract_results, process_cbmc_output, CheckStatus, ParserItem, Property, VerificationOutput,
 };
-use crate::cbmc_property_renderer::{format_coverage, format_result, kani_cbmc_output_filter};
+use crate::cbmc_property_renderer::{
+    format_coverage, format_item, format_result, kani_cbmc_output_filter,
+};
 use crate::session::KaniSession;
 
 /// We will use Cadical by default since it performed better than MiniSAT in our analysis.
 /// Note: Kissat was marginally better, but it is an external solver which could be more unstable.
 static DEFAULT_SOLVER: CbmcSolver = CbmcSolver::Cadical;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum VerificationStatus {
     Success,
     Failure,
+    /// The verification was stopped by a timeout before finding any failure.
+    Timeout,
 }
 
 /// Represents failed properties in three different categories.
@@ -36,6 +42,8 @@ pub enum FailedProperties {
     PanicsOnly,
     // One or more failures that aren't panic-related
     Other,
+    // One or more panic-related failures, some of which aren't the panic that was expected
+    UnexpectedPanics,
 }
 
 /// Our (kani-driver) notions of CBMC results.
@@ -57,6 +65,12 @@ pub struct VerificationResult {
     pub runtime: Duration,
     /// Whether concrete playback generated a test
     pub generated_concrete_test: bool,
+    /// The timeout that stopped the verification before all properties were checked, if any.
+    pub timeout: Option<Duration>,
+    /// The unwinding bound that `--auto-unwind` chose for the harness, if enabled.
+    pub auto_unwind: Option<u32>,
+    /// Whether the result was taken from the `--verification-cache` instead of running CBMC.
+    pub cached: bool,
 }
 
 impl KaniSession {
@@ -64,6 +78,10 @@ impl KaniSession {
     pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<VerificationResult> {
         let args: Vec<OsString> = self.cbmc_flags(file, harness)?;
 
+        if let Some(timeout) = self.harness_timeout(harness) {
+            return self.run_cbmc_with_timeout(args, harness, timeout);
+        }
+
         // TODO get cbmc path from self
         let mut cmd = Command::new("cbmc");
         cmd.args(args);
@@ -88,12 +106,13 @@ pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<Verific
                 kani_cbmc_output_filter(
                     i,
                     self.args.extra_pointer_checks,
-                    self.args.common_args.quiet,
+                    // Messages of parallel jobs are printed with their results instead.
+                    self.args.common_args.quiet || self.args.is_parallel(),
                     &self.args.output_format,
                 )
             })?;
 
-            VerificationResult::from(output, harness.attributes.should_panic, start_time)
+            VerificationResult::from(output, &harness.attributes, start_time)
         };
 
         Ok(verification_results)
@@ -134,7 +153,26 @@ pub fn cbmc_flags(
             args.push(unwind_value.to_string().into());
         }
 
-        self.handle_solver_args(&harness_metadata.attributes.solver, &mut args)?;
+        self.handle_solver_args(&harness_metadata.attributes, &mut args)?;
+
+        if self.args.array_theory {
+            let solver = self.resolve_solver(&harness_metadata.attributes);
+            args.extend(array_theory_flags(solver).into_iter().map(OsString::from));
+        }
+
+        // `--path-strategy` option takes precedence over attributes
+        match self.args.path_strategy.or(harness_metadata.attributes.path_strategy) {
+            None | Some(CbmcPathStrategy::Merge) => {}
+            Some(strategy) => {
+                args.push("--paths".into());
+                args.push(strategy.as_ref().into());
+            }
+        }
+
+        if let Some(memory_model) = self.args.memory_model {
+            args.push("--mm".into());
+            args.push(memory_model.as_ref().into());
+        }
 
         if self.args.run_sanity_checks {
             args.push("--validate-goto-model".into());
@@ -143,12 +181,21 @@ pub fn cbmc_flags(
 
         if !self.args.visualize
             && self.args.concrete_playback.is_none()
+            && !self.args.heap_graph
+            && !self.args.suggest_assumptions
+            && !self.args.nondet_hash_order
             && !self.args.no_slice_formula
         {
             args.push("--slice-formula".into());
         }
 
-        if self.args.concrete_playback.is_some() {
+        // Traces are also needed to explain failed deallocation checks in terms of the Rust code.
+        if self.args.concrete_playback.is_some()
+            || self.args.checks.memory_safety_on()
+            || self.args.heap_graph
+            || self.args.suggest_assumptions
+            || self.args.nondet_hash_order
+        {
             args.push("--trace".into());
         }
 
@@ -201,17 +248,11 @@ pub fn cbmc_check_flags(&self) -> Vec<OsString> {
 
     pub fn handle_solver_args(
         &self,
-        harness_solver: &Option<CbmcSolver>,
+        attributes: &HarnessAttributes,
         args: &mut Vec<OsString>,
     ) -> Result<()> {
-        let solver = if let Some(solver) = &self.args.solver {
-            // `--solver` option takes precedence over attributes
-            solver
-        } else if let Some(solver) = harness_solver {
-            solver
-        } else {
-            &DEFAULT_SOLVER
-        };
+        let solver = self.resolve_solver(attributes);
+        check_solver_available(solver)?;
 
         match solver {
             CbmcSolver::Cadical => {
@@ -227,16 +268,65 @@ pub fn handle_solver_args(
                 // pass any arguments
             }
             CbmcSolver::Binary(solver_binary) => {
-                // Check if the specified binary exists in path
-                if which::which(solver_binary).is_err() {
-                    bail!("the specified solver \"{solver_binary}\" was not found in path")
-                }
                 args.push("--external-sat-solver".into());
                 args.push(solver_binary.into());
             }
+            CbmcSolver::Z3 | CbmcSolver::Cvc5 | CbmcSolver::Bitwuzla | CbmcSolver::Smt2(_) => {
+                args.push("--incremental-smt2-solver".into());
+                args.push(solver.smt2_command().unwrap().into());
+            }
         }
         Ok(())
     }
+
+    /// The solver used for a harness: `--solver` option takes precedence over attributes, which
+    /// take precedence over the solver of the package of the harness.
+    pub fn resolve_solver<'a>(&'a self, attributes: &'a HarnessAttributes) -> &'a CbmcSolver {
+        self.args
+            .solver
+            .as_ref()
+            .or(attributes.solver.as_ref())
+            .or(attributes.package_solver.as_ref())
+            .unwrap_or(&DEFAULT_SOLVER)
+    }
+}
+
+/// The flags of CBMC that encode arrays with the array theory of `solver`, rather than with one
+/// variable per element.
+///
+/// Symbolic execution splits small arrays into their elements, so this is disabled first. The
+/// incremental SMT2 backend then passes the arrays to the solver as SMT arrays, while the SAT
+/// backend needs to be told to use its array decision procedure even for small arrays.
+fn array_theory_flags(solver: &CbmcSolver) -> Vec<&'static str> {
+    let mut flags = vec!["--no-array-field-sensitivity"];
+    if solver.smt2_command().is_none() {
+        flags.push("--arrays-uf-always");
+    }
+    flags
+}
+
+/// Check that the program run by an external solver exists in path. CaDiCaL and MiniSAT are
+/// built into CBMC, and Kissat is included in the Kani bundle.
+pub fn check_solver_available(solver: &CbmcSolver) -> Result<()> {
+    match solver {
+        CbmcSolver::Cadical | CbmcSolver::Kissat | CbmcSolver::Minisat => {}
+        CbmcSolver::Binary(solver_binary) => {
+            if which::which(solver_binary).is_err() {
+                bail!("the specified solver \"{solver_binary}\" was not found in path")
+            }
+        }
+        CbmcSolver::Z3 | CbmcSolver::Cvc5 | CbmcSolver::Bitwuzla | CbmcSolver::Smt2(_) => {
+            let command = solver.smt2_command().unwrap();
+            let program = command.split_whitespace().next().unwrap_or_default();
+            if which::which(program).is_err() {
+                bail!(
+                    "the SMT solver \"{program}\" was not found in path. Install it, or select \
+                    another solver with `--solver` or `#[kani::solver]` (e.g. `cadical`)"
+                )
+            }
+        }
+    }
+    Ok(())
 }
 
 impl VerificationResult {
@@ -248,9 +338,9 @@ impl VerificationResult {
     ///       (CBMC will regularly report "failure" but that's just our cover checks.)
     ///   2. Positively checking for the presence of results.
     ///       (Do not mistake lack of results for success: report it as failure.)
-    fn from(
+    pub fn from(
         output: VerificationOutput,
-        should_panic: bool,
+        attributes: &HarnessAttributes,
         start_time: Instant,
     ) -> VerificationResult {
         let runtime = start_time.elapsed();
@@ -258,7 +348,7 @@ fn from(
 
         if let Some(results) = results {
             let (status, failed_properties) =
-                verification_outcome_from_properties(&results, should_panic);
+                verification_outcome_from_properties(&results, attributes);
             VerificationResult {
                 status,
                 failed_properties,
@@ -266,6 +356,9 @@ fn from(
                 results: Ok(results),
                 runtime,
                 generated_concrete_test: false,
+                timeout: None,
+                auto_unwind: None,
+                cached: false,
             }
         } else {
             // We never got results from CBMC - something went wrong (e.g. crash) so it's failure
@@ -276,6 +369,9 @@ fn from(
                 results: Err(output.process_status),
                 runtime,
                 generated_concrete_test: false,
+                timeout: None,
+                auto_unwind: None,
+                cached: false,
             }
         }
     }
@@ -288,9 +384,18 @@ pub fn mock_success() -> VerificationResult {
             results: Ok(vec![]),
             runtime: Duration::from_secs(0),
             generated_concrete_test: false,
+            timeout: None,
+            auto_unwind: None,
+            cached: false,
         }
     }
 
+    /// The result of a harness that was verified successfully in a previous run, which took
+    /// `runtime` back then.
+    pub fn cached_success(runtime: Duration) -> VerificationResult {
+        VerificationResult { runtime, cached: true, ..VerificationResult::mock_success() }
+    }
+
     fn mock_failure() -> VerificationResult {
         VerificationResult {
             status: VerificationStatus::Failure,
@@ -302,6 +407,9 @@ fn mock_failure() -> VerificationResult {
             results: Err(42),
             runtime: Duration::from_secs(0),
             generated_concrete_test: false,
+            timeout: None,
+            auto_unwind: None,
+            cached: false,
         }
     }
 
@@ -322,6 +430,15 @@ pub fn render(
                 } else {
                     format_result(results, status, should_panic, failed_properties, show_checks)
                 };
+                if let Some(timeout) = self.timeout {
+                    writeln!(
+                        result,
+                        "Verification timed out after {}s. The properties that were not checked \
+                        in time are reported as UNDETERMINED.",
+                        timeout.as_secs()
+                    )
+                    .unwrap();
+                }
                 writeln!(result, "Verification Time: {}s", self.runtime.as_secs_f32()).unwrap();
                 result
             }
@@ -334,6 +451,17 @@ pub fn render(
         }
     }
 
+    /// Render the messages that CBMC printed before the results, one per line.
+    pub fn render_messages(&self, output_format: &OutputFormat) -> String {
+        self.messages.iter().flatten().filter_map(|item| format_item(item, output_format)).fold(
+            String::new(),
+            |mut output, message| {
+                writeln!(output, "{message}").unwrap();
+                output
+            },
+        )
+    }
+
     /// Find the failed properties from this verification run
     pub fn failed_properties(&self) -> Vec<&Property> {
         if let Ok(properties) = &self.results {
@@ -346,27 +474,41 @@ pub fn failed_properties(&self) -> Vec<&Property> {
 }
 
 /// We decide if verification succeeded based on properties, not (typically) on exit code
-fn verification_outcome_from_properties(
+pub fn verification_outcome_from_properties(
     properties: &[Property],
-    should_panic: bool,
+    attributes: &HarnessAttributes,
 ) -> (VerificationStatus, FailedProperties) {
-    let failed_properties = determine_failed_properties(properties);
-    let status = if should_panic {
+    let failed_properties =
+        determine_failed_properties(properties, attributes.expected_panic.as_deref());
+    let status = if attributes.should_panic {
         match failed_properties {
-            FailedProperties::None | FailedProperties::Other => VerificationStatus::Failure,
+            FailedProperties::None
+            | FailedProperties::Other
+            | FailedProperties::UnexpectedPanics => VerificationStatus::Failure,
             FailedProperties::PanicsOnly => VerificationStatus::Success,
         }
     } else {
         match failed_properties {
             FailedProperties::None => VerificationStatus::Success,
-            FailedProperties::PanicsOnly | FailedProperties::Other => VerificationStatus::Failure,
+            FailedProperties::PanicsOnly
+            | FailedProperties::Other
+            | FailedProperties::UnexpectedPanics => VerificationStatus::Failure,
         }
     };
     (status, failed_properties)
 }
 
+/// Whether a failed panic check is the panic that `#[kani::should_panic(expected = ..)]` expects,
+/// i.e., its description contains `expected` or its name is `expected`.
+fn is_expected_panic(prop: &Property, expected: &str) -> bool {
+    prop.description.contains(expected) || prop.property_name() == expected
+}
+
 /// Determines the `FailedProperties` variant that corresponds to an array of properties
-fn determine_failed_properties(properties: &[Property]) -> FailedProperties {
+fn determine_failed_properties(
+    properties: &[Property],
+    expected_panic: Option<&str>,
+) -> FailedProperties {
     let failed_properties: Vec<&Property> =
         properties.iter().filter(|prop| prop.status == CheckStatus::Failure).collect();
     // Return `FAILURE` if there isn't at least one failed property
@@ -377,10 +519,14 @@ fn determine_failed_properties(properties: &[Property]) -> FailedProperties {
         // Note: Panics caused by `panic!` and `assert!` fall into this class.
         let all_failed_checks_are_panics =
             failed_properties.iter().all(|prop| prop.property_class() == "assertion");
-        if all_failed_checks_are_panics {
-            FailedProperties::PanicsOnly
-        } else {
+        if !all_failed_checks_are_panics {
             FailedProperties::Other
+        } else if expected_panic.is_some_and(|expected| {
+            !failed_properties.iter().all(|prop| is_expected_panic(prop, expected))
+        }) {
+            FailedProperties::UnexpectedPanics
+        } else {
+            FailedProperties::PanicsOnly
         }
     }
 }
@@ -392,7 +538,11 @@ pub fn resolve_unwind_value(
 ) -> Option<u32> {
     // Check for which flag is being passed and prioritize extracting unwind from the
     // respective flag/annotation.
-    args.unwind.or(harness_metadata.attributes.unwind_value).or(args.default_unwind)
+    // The package default only applies if the user didn't set a default from the command line.
+    args.unwind
+        .or(harness_metadata.attributes.unwind_value)
+        .or(args.default_unwind)
+        .or(harness_metadata.attributes.package_unwind)
 }
 
 #[cfg(test)]
@@ -433,5 +583,72 @@ fn resolve(args: &[&str], harness: &HarnessMetadata) -> Option<u32> {
         assert_eq!(resolve(&args_only_default, &harness_some), Some(3));
         assert_eq!(resolve(&args_only_harness, &harness_some), Some(1));
         assert_eq!(resolve(&args_both, &harness_some), Some(1));
+
+        // test against the default of the package of the harness
+        let mut harness_package = mock_proof_harness("check_one", None, None, None);
+        harness_package.attributes.package_unwind = Some(4);
+        assert_eq!(resolve(&args_empty, &harness_package), Some(4));
+        assert_eq!(resolve(&args_only_default, &harness_package), Some(2));
+        assert_eq!(resolve(&args_only_harness, &harness_package), Some(1));
+        harness_package.attributes.unwind_value = Some(3);
+        assert_eq!(resolve(&args_empty, &harness_package), Some(3));
+    }
+
+    #[test]
+    fn check_expected_panic_outcome() {
+        use crate::cbmc_output_parser::{PropertyId, SourceLocation};
+        use serde::Deserialize;
+        use serde_json::Value;
+
+        let panic = |name: &str, description: &str| Property {
+            description: description.to_string(),
+            property_id: PropertyId::deserialize(Value::String(name.to_string())).unwrap(),
+            source_location: SourceLocation {
+                column: None,
+                file: None,
+                function: None,
+                line: None,
+            },
+            status: CheckStatus::Failure,
+            reach: None,
+            trace: None,
+        };
+        let properties = [
+            panic("check.assertion.1", "index out of bounds"),
+            panic("check.assertion.2", "attempt to divide by zero"),
+        ];
+        let outcome = |properties: &[Property], expected: Option<&str>| {
+            let attributes = HarnessAttributes {
+                should_panic: true,
+                expected_panic: expected.map(str::to_string),
+                ..Default::default()
+            };
+            verification_outcome_from_properties(properties, &attributes).0
+        };
+
+        assert_eq!(outcome(&properties, None), VerificationStatus::Success);
+        assert_eq!(outcome(&properties, Some("out of bounds")), VerificationStatus::Failure);
+        assert_eq!(outcome(&properties[..1], Some("out of bounds")), VerificationStatus::Success);
+        assert_eq!(
+            outcome(&properties[..1], Some("check.assertion.1")),
+            VerificationStatus::Success
+        );
+        assert_eq!(
+            outcome(&properties[1..], Some("check.assertion.1")),
+            VerificationStatus::Failure
+        );
+    }
+
+    #[test]
+    fn check_array_theory_flags() {
+        assert_eq!(
+            array_theory_flags(&CbmcSolver::Cadical),
+            ["--no-array-field-sensitivity", "--arrays-uf-always"]
+        );
+        assert_eq!(array_theory_flags(&CbmcSolver::Z3), ["--no-array-field-sensitivity"]);
+        assert_eq!(
+            array_theory_flags(&CbmcSolver::Smt2("yices-smt2 --incremental".to_string())),
+            ["--no-array-field-sensitivity"]
+        );
     }
 }
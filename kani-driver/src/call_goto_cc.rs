@@ -2,9 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use anyhow::Result;
+use cbmc::irep::dedup::deduplicate_functions;
+use cbmc::irep::goto_binary_serde::{read_goto_binary_file, write_irep_goto_binary_file};
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use tempfile::TempDir;
+use tracing::debug;
 
 use crate::session::KaniSession;
 
@@ -12,13 +16,26 @@ impl KaniSession {
     /// Given a set of goto binaries (`inputs`), produce `output` by linking everything
     /// together (including essential libraries). The result is generic over all proof harnesses.
     pub fn link_goto_binary(&self, inputs: &[PathBuf], output: &Path) -> Result<()> {
+        // Keep the directory alive until goto-cc is done with the files in it.
+        let deduplicated = if inputs.len() > 1 { self.deduplicate_functions(inputs) } else { None };
+        let inputs = deduplicated.as_ref().map_or(inputs, |(_, files)| files);
+
+        // Keep the directory alive until goto-cc is done with the compiled C libraries.
+        let c_lib_dir = tempfile::tempdir()?;
+        let c_objects = self.compile_c_libs(c_lib_dir.path())?;
+        self.check_ffi_signatures(inputs, &c_objects)?;
+
         let mut args: Vec<OsString> = Vec::new();
         args.extend(inputs.iter().map(|x| x.clone().into_os_string()));
-        args.extend(self.args.c_lib.iter().map(|x| x.clone().into_os_string()));
+        args.extend(c_objects.into_iter().map(PathBuf::into_os_string));
 
         // TODO think about this: kani_lib_c is just an empty c file. Maybe we could just
         // create such an empty file ourselves instead of having to look up this path.
         args.push(self.kani_lib_c.clone().into_os_string());
+        if self.args.is_32_bit_target() {
+            // Compile Kani's C library with the same sizes as the Rust code.
+            args.push("-m32".into());
+        }
 
         args.push("-o".into());
         args.push(output.to_owned().into_os_string());
@@ -32,6 +49,49 @@ pub fn link_goto_binary(&self, inputs: &[PathBuf], output: &Path) -> Result<()>
         Ok(())
     }
 
+    /// Remove the functions that are defined by more than one of the `inputs`, which happens when
+    /// several crates instantiate the same generic function. The binaries that change are written
+    /// to a temporary directory, so the build artifacts are left untouched, and the others are
+    /// linked as they are.
+    ///
+    /// This is only an optimization, so `None` is returned if anything goes wrong, and the
+    /// original files should be linked instead.
+    fn deduplicate_functions(&self, inputs: &[PathBuf]) -> Option<(TempDir, Vec<PathBuf>)> {
+        let result = (|| -> Result<_> {
+            let mut tables = inputs
+                .iter()
+                .map(|input| read_goto_binary_file(input))
+                .collect::<Result<Vec<_>, _>>()?;
+            let deduplication = deduplicate_functions(&mut tables);
+            if self.args.common_args.verbose {
+                println!(
+                    "[Kani] Removed {} duplicated function(s) before linking",
+                    deduplication.removed
+                );
+            }
+            if deduplication.removed == 0 {
+                anyhow::bail!("no duplicated functions");
+            }
+            let dir = tempfile::tempdir()?;
+            let mut files = vec![];
+            for (idx, ((input, table), modified)) in
+                inputs.iter().zip(tables).zip(deduplication.modified).enumerate()
+            {
+                if !modified {
+                    files.push(input.clone());
+                    continue;
+                }
+                let file = dir
+                    .path()
+                    .join(format!("{idx}_{}", input.file_name().unwrap().to_string_lossy()));
+                write_irep_goto_binary_file(&file, &table)?;
+                files.push(file);
+            }
+            Ok((dir, files))
+        })();
+        result.map_err(|error| debug!(?error, "deduplicate_functions")).ok()
+    }
+
     /// Produce a goto binary with its entry point set to a particular proof harness.
     pub fn specialize_to_proof_harness(
         &self,
@@ -46,6 +46,14 @@ pub fn instrument_model(
 
         self.rewrite_back_edges(output)?;
 
+        if self.args.race_checks {
+            self.add_race_checks(output)?;
+        }
+
+        if self.args.is_loop_contracts_enabled() || harness.contract.is_some() {
+            self.instrument_contracts(harness, output)?;
+        }
+
         if self.args.gen_c {
             let c_outfile = alter_extension(output, "c");
             // We don't put the C file into temporaries to be deleted.
@@ -139,6 +147,39 @@ fn rewrite_back_edges(&self, file: &Path) -> Result<()> {
         self.call_goto_instrument(args)
     }
 
+    /// Add an assertion before every access to a global variable that no other thread writes to
+    /// the variable at the same time.
+    fn add_race_checks(&self, file: &Path) -> Result<()> {
+        let args: Vec<OsString> = vec![
+            "--race-check".into(),
+            file.to_owned().into_os_string(), // input
+            file.to_owned().into_os_string(), // output
+        ];
+
+        self.call_goto_instrument(args)
+    }
+
+    /// Replace the loops annotated with `#[kani::loop_invariant]` by their contracts, and
+    /// enforce the goto contract (i.e. the `modifies` clause) of the function under
+    /// verification, if the harness has one.
+    fn instrument_contracts(&self, harness: &HarnessMetadata, file: &Path) -> Result<()> {
+        let mut args: Vec<OsString> = vec!["--dfcc".into(), (&harness.mangled_name).into()];
+        if self.args.is_loop_contracts_enabled() {
+            args.push("--apply-loop-contracts".into());
+        }
+        if let Some(function) = &harness.contract {
+            args.push("--enforce-contract".into());
+            args.push(function.into());
+        }
+        args.extend([
+            "--no-malloc-may-fail".into(),
+            file.to_owned().into_os_string(), // input
+            file.to_owned().into_os_string(), // output
+        ]);
+
+        self.call_goto_instrument(args)
+    }
+
     fn goto_sanity_check(&self, file: &Path) -> Result<()> {
         let args: Vec<OsString> = vec![
             "--validate-goto-model".into(),
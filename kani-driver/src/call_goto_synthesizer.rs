@@ -38,7 +38,7 @@ pub fn synthesize_loop_contracts(
         // goto-synthesizer should take the same backend options as cbmc.
         // Backend options include
         // 1. solver options
-        self.handle_solver_args(&harness_metadata.attributes.solver, &mut args)?;
+        self.handle_solver_args(&harness_metadata.attributes, &mut args)?;
         // 2. object-bits option
         if let Some(object_bits) = self.args.cbmc_object_bits() {
             args.push("--object-bits".into());
@@ -6,7 +6,7 @@
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use crate::session::{lib_folder, KaniSession};
+use crate::session::{lib_folder, target_lib_folder, KaniSession};
 
 impl KaniSession {
     /// Used by `kani` and not `cargo-kani` to process a single Rust file into a `.symtab.json`
@@ -26,6 +26,11 @@ pub fn compile_single_rust_file(
         rustc_args.push(OsString::from(outdir.as_os_str()));
         rustc_args.push("--crate-name".into());
         rustc_args.push(crate_name.into());
+        if let Some(target) = &self.args.target_triple {
+            // Cargo passes the target itself, so this is only needed here.
+            rustc_args.push("--target".into());
+            rustc_args.push(target.into());
+        }
 
         if self.args.tests {
             // e.g. `tests/kani/Options/check_tests.rs` will fail because it already has it
@@ -43,6 +48,10 @@ pub fn compile_single_rust_file(
                 rustc_args.push("--crate-type".into());
                 rustc_args.push("lib".into());
             }
+            if self.args.test_modules {
+                rustc_args.push("--cfg".into());
+                rustc_args.push("test".into());
+            }
         }
 
         // Note that the order of arguments is important. Kani specific flags should precede
@@ -86,6 +95,9 @@ pub fn kani_compiler_flags(&self) -> Vec<String> {
         if self.args.ignore_global_asm {
             flags.push("--ignore-global-asm".into());
         }
+        if self.args.ignore_asm {
+            flags.push("--ignore-asm".into());
+        }
 
         // Users activate it via the command line switch
         if self.args.write_json_symtab {
@@ -100,6 +112,78 @@ pub fn kani_compiler_flags(&self) -> Vec<String> {
             flags.push("--coverage-checks".into());
         }
 
+        if let Some(max_depth) = self.args.max_stack_depth {
+            flags.push(format!("--max-stack-depth={max_depth}"));
+        }
+
+        if let Some(policies) = &self.args.ffi_policy {
+            flags.extend(policies.as_compiler_flags());
+        }
+
+        if let Some(specs) = &self.args.contract_specs {
+            flags.extend(specs.as_compiler_flags());
+        }
+
+        for input in &self.args.concrete_inputs {
+            flags.push(format!("--concrete-input={input}"));
+        }
+
+        if self.args.drop_checks {
+            flags.push("--drop-checks".into());
+        }
+
+        if self.args.trait_laws {
+            flags.push("--trait-laws".into());
+        }
+
+        if self.args.const_fn_checks {
+            flags.push("--const-fn-checks".into());
+        }
+
+        if self.args.utf8_checks {
+            flags.push("--utf8-checks".into());
+        }
+
+        if self.args.no_utf8_assumptions {
+            flags.push("--no-utf8-assumptions".into());
+        }
+
+        if let Some(float_values) = self.args.float_values {
+            flags.push(format!("--float-values={}", float_values.as_ref()));
+        }
+
+        if self.args.nondet_hash_order {
+            flags.push("--nondet-hash-order".into());
+        }
+
+        if self.args.use_abstractions {
+            flags.push("--use-abstractions".into());
+        }
+
+        if self.args.race_checks {
+            flags.push("--race-checks".into());
+        }
+
+        if self.args.lossy_cast_checks {
+            flags.push("--lossy-cast-checks".into());
+        }
+
+        if self.args.constant_time_checks {
+            flags.push("--constant-time-checks".into());
+        }
+
+        if self.args.enum_validity_checks {
+            flags.push("--enum-validity-checks".into());
+        }
+
+        if self.args.checks.uninit_on() {
+            flags.push("--uninit-checks".into());
+        }
+
+        if self.args.checks.pointer_provenance_on() {
+            flags.push("--pointer-provenance-checks".into());
+        }
+
         flags.extend(self.args.common_args.unstable_features.as_arguments().map(str::to_string));
 
         // This argument will select the Kani flavour of the compiler. It will be removed before
@@ -111,7 +195,10 @@ pub fn kani_compiler_flags(&self) -> Vec<String> {
 
     /// This function generates all rustc configurations required by our goto-c codegen.
     pub fn kani_rustc_flags(&self) -> Vec<OsString> {
-        let lib_path = lib_folder().unwrap();
+        let lib_path = match &self.args.target_triple {
+            Some(target) => target_lib_folder(target).unwrap(),
+            None => lib_folder().unwrap(),
+        };
         let mut flags: Vec<_> = base_rustc_flags(lib_path);
         // We only use panic abort strategy for verification since we cannot handle unwind logic.
         flags.extend_from_slice(
@@ -170,12 +257,15 @@ pub fn base_rustc_flags(lib_path: PathBuf) -> Vec<OsString> {
         lib_path.to_str().unwrap(),
         "--extern",
         "kani",
-        "--extern",
-        kani_std_wrapper.as_str(),
     ]
     .map(OsString::from)
     .to_vec();
 
+    // The libraries of targets without a standard library don't include Kani's `std` wrapper.
+    if kani_std_rlib.exists() {
+        flags.extend(["--extern", kani_std_wrapper.as_str()].map(OsString::from));
+    }
+
     // e.g. compiletest will set 'compile-flags' here and we should pass those down to rustc
     // and we fail in `tests/kani/Match/match_bool.rs`
     if let Ok(str) = std::env::var("RUSTFLAGS") {
@@ -27,13 +27,14 @@
 use console::style;
 use pathdiff::diff_paths;
 use rustc_demangle::demangle;
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
 
+use std::collections::BTreeMap;
 use std::env;
 use std::io::{BufRead, BufReader};
 use std::os::unix::process::ExitStatusExt;
 use std::path::PathBuf;
-use std::process::{Child, ChildStdout};
+use std::process::{Child, ChildStdout, ExitStatus};
 
 const RESULT_ITEM_PREFIX: &str = "  {\n    \"result\":";
 
@@ -97,6 +98,10 @@ pub struct PropertyId {
 impl Property {
     const COVER_PROPERTY_CLASS: &'static str = "cover";
     const COVERAGE_PROPERTY_CLASS: &'static str = "code_coverage";
+    const BOUNDS_PROPERTY_CLASS: &'static str = "bounds";
+    const VACUITY_PROPERTY_CLASS: &'static str = "vacuity";
+    const STACK_DEPTH_REACHED_PROPERTY_CLASS: &'static str = "stack_depth_reached";
+    const NAMED_CHECK_PROPERTY_CLASS: &'static str = "named_check";
 
     pub fn property_class(&self) -> String {
         self.property_id.class.clone()
@@ -112,6 +117,36 @@ pub fn is_cover_property(&self) -> bool {
         self.property_id.class == Self::COVER_PROPERTY_CLASS
     }
 
+    /// Returns true if this property records a bound declared with `kani::bounds!`
+    pub fn is_bounds_property(&self) -> bool {
+        self.property_id.class == Self::BOUNDS_PROPERTY_CLASS
+    }
+
+    /// Returns true if this property checks that the predicate of `kani::any_where` is
+    /// satisfiable
+    pub fn is_vacuity_property(&self) -> bool {
+        self.property_id.class == Self::VACUITY_PROPERTY_CLASS
+    }
+
+    /// The call stack depth that this property checks is reached, with `--max-stack-depth`
+    pub fn reached_stack_depth(&self) -> Option<u32> {
+        if self.property_id.class == Self::STACK_DEPTH_REACHED_PROPERTY_CLASS {
+            self.description.rsplit(' ').next().and_then(|depth| depth.parse().ok())
+        } else {
+            None
+        }
+    }
+
+    /// The name of the check if this property was created with `kani::check!`, whose
+    /// description starts with the name of the check followed by `: `
+    pub fn check_name(&self) -> Option<&str> {
+        if self.property_id.class == Self::NAMED_CHECK_PROPERTY_CLASS {
+            self.description.split_once(": ").map(|(name, _)| name)
+        } else {
+            None
+        }
+    }
+
     pub fn property_name(&self) -> String {
         let class = &self.property_id.class;
         let id = self.property_id.id;
@@ -126,6 +161,30 @@ pub fn has_property_class_format(string: &str) -> bool {
     }
 }
 
+/// The status of each check created with `kani::check!`, by the name of the check.
+///
+/// A name can be used by several properties, e.g. when the check is in a function that is called
+/// several times, in which case the check fails if any of them fails, and it is only unreachable if
+/// all of them are.
+pub fn named_check_results(properties: &[Property]) -> BTreeMap<&str, CheckStatus> {
+    let rank = |status: CheckStatus| match status {
+        CheckStatus::Failure => 3,
+        CheckStatus::Undetermined => 2,
+        CheckStatus::Unreachable => 0,
+        _ => 1,
+    };
+    let mut results: BTreeMap<&str, CheckStatus> = BTreeMap::new();
+    for prop in properties {
+        if let Some(name) = prop.check_name() {
+            let status = results.entry(name).or_insert(prop.status);
+            if rank(prop.status) > rank(*status) {
+                *status = prop.status;
+            }
+        }
+    }
+    results
+}
+
 impl<'de> serde::Deserialize<'de> for PropertyId {
     /// Gets all property attributes from the property ID.
     ///
@@ -216,7 +275,7 @@ fn deserialize<D>(d: D) -> Result<Self, D::Error>
 ///
 /// Source locations may be completely empty, which is why
 /// all members are optional.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SourceLocation {
     pub column: Option<String>,
     pub file: Option<String>,
@@ -268,7 +327,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 }
 
 /// Returns a path relative to the current working directory.
-fn filepath(file: String) -> String {
+pub(crate) fn filepath(file: String) -> String {
     let file_path = PathBuf::from(file.clone());
     let cur_dir = env::current_dir().unwrap();
 
@@ -293,18 +352,78 @@ pub struct TraceItem {
     pub lhs: Option<String>,
     pub source_location: Option<SourceLocation>,
     pub value: Option<TraceValue>,
+    /// The function that is called or returned from, in `function-call` and `function-return`
+    /// steps.
+    pub function: Option<TraceFunction>,
+}
+
+/// Struct that represents the function of a `function-call` or `function-return` trace step.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFunction {
+    pub display_name: String,
+    pub identifier: String,
 }
 
 /// Struct that represents a trace value.
 ///
 /// Note: this struct can have a lot of different fields depending on the value type.
-/// The fields included right now are relevant to primitive types.
+/// The fields included right now are relevant to primitive types, structs and arrays.
 #[derive(Clone, Debug, Deserialize)]
 pub struct TraceValue {
     pub name: String,
     pub binary: Option<String>,
     pub data: Option<TraceData>,
     pub width: Option<u32>,
+    /// The C name of the type of the value, e.g., `unsigned char` or `double`.
+    #[serde(rename = "type")]
+    pub typ: Option<String>,
+    /// The fields of a struct or union value.
+    #[serde(default)]
+    pub members: Vec<TraceMember>,
+    /// The elements of an array value.
+    #[serde(default)]
+    pub elements: Vec<TraceElement>,
+}
+
+/// A field of a struct or union trace value.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TraceMember {
+    pub name: String,
+    pub value: TraceValue,
+}
+
+/// An element of an array trace value.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TraceElement {
+    pub index: u64,
+    pub value: TraceValue,
+}
+
+impl std::fmt::Display for TraceValue {
+    /// Render the value in a Rust-like syntax, e.g.: `{ x: 1, y: [2, 3] }`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(data) = &self.data {
+            return write!(f, "{data}");
+        }
+        if !self.elements.is_empty() {
+            let elements: Vec<_> =
+                self.elements.iter().map(|elem| elem.value.to_string()).collect();
+            return write!(f, "[{}]", elements.join(", "));
+        }
+        // Padding fields are added by the compiler and have no meaning to the user.
+        let members: Vec<_> = self
+            .members
+            .iter()
+            .filter(|member| !member.name.starts_with("$pad"))
+            .map(|member| format!("{}: {}", member.name, member.value))
+            .collect();
+        if members.is_empty() {
+            write!(f, "{{}}")
+        } else {
+            write!(f, "{{ {} }}", members.join(", "))
+        }
+    }
 }
 
 /// Enum that represents a trace data item.
@@ -324,17 +443,17 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     }
 }
 
-#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum CheckStatus {
     Failure,
     Covered,   // for `code_coverage` properties only
-    Satisfied, // for `cover` properties only
+    Satisfied, // for `cover` properties and bounds only
     Success,
     Undetermined,
     Unreachable,
     Uncovered,     // for `code_coverage` properties only
-    Unsatisfiable, // for `cover` properties only
+    Unsatisfiable, // for `cover` properties and bounds only
 }
 
 impl std::fmt::Display for CheckStatus {
@@ -526,14 +645,26 @@ pub fn process_cbmc_output(
     eager_filter: impl FnMut(ParserItem) -> Option<ParserItem>,
 ) -> Result<VerificationOutput> {
     let stdout = process.stdout.as_mut().unwrap();
-    let mut stdout_reader = BufReader::new(stdout);
-    let parser = Parser::new(&mut stdout_reader);
-    // This should run until stdout is closed (which should mean the process exited)
-    let processed_items: Vec<_> = parser.filter_map(eager_filter).collect();
+    let processed_items = parse_cbmc_output(stdout, eager_filter);
     // This will get us the process's exit code
     let status = process.wait()?;
+    Ok(VerificationOutput { process_status: process_status(status), processed_items })
+}
+
+/// Parse the items that CBMC prints to `stdout`, filtering them with `eager_filter` as they come.
+/// This should run until stdout is closed (which should mean the process exited).
+pub fn parse_cbmc_output(
+    stdout: &mut ChildStdout,
+    eager_filter: impl FnMut(ParserItem) -> Option<ParserItem>,
+) -> Vec<ParserItem> {
+    let mut stdout_reader = BufReader::new(stdout);
+    let parser = Parser::new(&mut stdout_reader);
+    parser.filter_map(eager_filter).collect()
+}
 
-    let process_status = match (status.code(), status.signal()) {
+/// Convert the exit status of a CBMC process into an exit code.
+pub fn process_status(status: ExitStatus) -> i32 {
+    match (status.code(), status.signal()) {
         // normal unix exit codes (cbmc uses currently 0-10)
         // https://github.com/diffblue/cbmc/blob/develop/src/util/exit_codes.h
         (Some(x), _) => x,
@@ -543,9 +674,7 @@ pub fn process_cbmc_output(
         (_, Some(x)) => 128 + x,
         // I think this shouldn't happen? either exit or signal, right?
         (None, None) => unreachable!("Process exited with neither status code nor signal?"),
-    };
-
-    Ok(VerificationOutput { process_status, processed_items })
+    }
 }
 
 /// Takes (by ownership) a vector of messages, and returns that vector with the `Result`
@@ -569,6 +698,36 @@ pub fn extract_results(mut items: Vec<ParserItem>) -> (Vec<ParserItem>, Option<V
 mod tests {
     use super::*;
 
+    #[test]
+    fn check_named_check_results() {
+        let prop = |class: &str, id, description: &str, status| Property {
+            description: description.to_string(),
+            property_id: PropertyId { fn_name: Some("check_foo".into()), class: class.into(), id },
+            source_location: SourceLocation {
+                function: None,
+                file: None,
+                column: None,
+                line: None,
+            },
+            status,
+            reach: None,
+            trace: None,
+        };
+        let properties = vec![
+            prop("named_check", 1, "in_range: x < 10", CheckStatus::Success),
+            prop("named_check", 2, "in_range: y < 10", CheckStatus::Failure),
+            prop("named_check", 3, "in_range: z < 10", CheckStatus::Unreachable),
+            prop("named_check", 4, "parser.no_overflow: x + 1 > x", CheckStatus::Unreachable),
+            prop("assertion", 1, "done: x > 0", CheckStatus::Failure),
+        ];
+        assert_eq!(properties[3].check_name(), Some("parser.no_overflow"));
+        assert_eq!(properties[4].check_name(), None);
+        let results = named_check_results(&properties);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results["in_range"], CheckStatus::Failure);
+        assert_eq!(results["parser.no_overflow"], CheckStatus::Unreachable);
+    }
+
     #[test]
     fn check_property_id_deserialization_general() {
         let prop_id_string = "\"alloc::raw_vec::RawVec::<u8>::allocate_in.sanity_check.1\"";
@@ -764,4 +923,23 @@ fn check_result_deserialization_works() {
         assert!(parser_item.is_ok());
         assert!(result_struct.is_ok());
     }
+
+    #[test]
+    fn check_trace_value_display() {
+        let value: TraceValue = serde_json::from_str(
+            r#"{
+                "name": "struct",
+                "members": [
+                    { "name": "x", "value": { "name": "integer", "data": "1", "width": 32 } },
+                    { "name": "$pad1", "value": { "name": "integer", "data": "0", "width": 32 } },
+                    { "name": "ys", "value": { "name": "array", "elements": [
+                        { "index": 0, "value": { "name": "boolean", "data": true } },
+                        { "index": 1, "value": { "name": "boolean", "data": false } }
+                    ] } }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(value.to_string(), "{ x: 1, ys: [true, false] }");
+    }
 }
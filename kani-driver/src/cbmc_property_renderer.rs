@@ -3,7 +3,10 @@
 
 use crate::args::OutputFormat;
 use crate::call_cbmc::{FailedProperties, VerificationStatus};
-use crate::cbmc_output_parser::{CheckStatus, ParserItem, Property, TraceItem};
+use crate::cbmc_output_parser::{
+    named_check_results, CheckStatus, ParserItem, Property, TraceItem,
+};
+use crate::dealloc_checks::update_dealloc_check_locations;
 use console::style;
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -150,10 +153,10 @@
     map
 });
 
-#[derive(PartialEq, Eq, AsRefStr, Clone, Copy, Display)]
+#[derive(PartialEq, Eq, AsRefStr, Clone, Copy, Display, Debug)]
 #[strum(serialize_all = "UPPERCASE")]
 // The status of coverage reported by Kani
-enum CoverageStatus {
+pub enum CoverageStatus {
     Full,
     Partial,
     None,
@@ -163,6 +166,7 @@ enum CoverageStatus {
 const UNWINDING_ASSERT_DESC: &str = "unwinding assertion loop";
 const UNWINDING_ASSERT_REC_DESC: &str = "recursion unwinding assertion";
 const DEFAULT_ASSERTION: &str = "assertion";
+const ENUM_VALIDITY_PROPERTY_CLASS: &str = "enum_validity";
 
 impl ParserItem {
     /// Determines if an item must be skipped or not.
@@ -219,11 +223,13 @@ fn process_item(item: ParserItem, extra_ptr_checks: bool) -> ParserItem {
 }
 
 /// Returns an optional formatted item based on the output format
-fn format_item(item: &ParserItem, output_format: &OutputFormat) -> Option<String> {
+pub fn format_item(item: &ParserItem, output_format: &OutputFormat) -> Option<String> {
     match output_format {
         OutputFormat::Old => todo!(),
         OutputFormat::Regular => format_item_regular(item),
         OutputFormat::Terse => format_item_terse(item),
+        // The results are only printed once all harnesses have been verified.
+        OutputFormat::Json => None,
     }
 }
 
@@ -271,6 +277,19 @@ pub fn format_result(
     let mut number_covers_unreachable = 0;
     let mut number_covers_unsatisfiable = 0;
 
+    // predicates of `kani::any_where`, which are only reported if they are unsatisfiable
+    let mut number_predicates = 0;
+    let mut unsatisfiable_predicates: Vec<&Property> = vec![];
+
+    // call stack depths checked with `--max-stack-depth`, which are only reported as the deepest
+    let mut number_stack_depths = 0;
+    let mut deepest_stack: Option<u32> = None;
+
+    // bounds declared with `kani::bounds!`
+    let mut number_bounds = 0;
+    let mut number_bounds_unsatisfiable = 0;
+    let mut number_bounds_unreachable = 0;
+
     let mut index = 1;
 
     if show_checks {
@@ -283,29 +302,59 @@ pub fn format_result(
         let description = &prop.description;
         let location = &prop.source_location;
 
+        if prop.is_bounds_property() {
+            number_bounds += 1;
+            match status {
+                CheckStatus::Unsatisfiable => number_bounds_unsatisfiable += 1,
+                CheckStatus::Unreachable => number_bounds_unreachable += 1,
+                _ => (),
+            }
+        }
+
+        if prop.is_vacuity_property() {
+            number_predicates += 1;
+            if *status == CheckStatus::Unsatisfiable {
+                unsatisfiable_predicates.push(prop);
+            }
+        }
+
+        if let Some(depth) = prop.reached_stack_depth() {
+            number_stack_depths += 1;
+            if *status == CheckStatus::Satisfied {
+                deepest_stack = deepest_stack.max(Some(depth));
+            }
+        }
+
         match status {
+            _ if prop.is_vacuity_property() || prop.reached_stack_depth().is_some() => (),
             CheckStatus::Failure => {
                 number_checks_failed += 1;
                 failed_tests.push(prop);
             }
             CheckStatus::Undetermined => {
-                if prop.is_cover_property() {
+                if prop.is_bounds_property() {
+                    // Reported in the summary of bounds.
+                } else if prop.is_cover_property() {
                     number_covers_undetermined += 1;
                 } else {
                     number_checks_undetermined += 1;
                 }
             }
             CheckStatus::Unreachable => {
-                if prop.is_cover_property() {
+                if prop.is_bounds_property() {
+                    // Reported in the summary of bounds.
+                } else if prop.is_cover_property() {
                     number_covers_unreachable += 1;
                 } else {
                     number_checks_unreachable += 1;
                 }
             }
+            CheckStatus::Satisfied if prop.is_bounds_property() => (),
             CheckStatus::Satisfied => {
                 assert!(prop.is_cover_property());
                 number_covers_satisfied += 1;
             }
+            CheckStatus::Unsatisfiable if prop.is_bounds_property() => (),
             CheckStatus::Unsatisfiable => {
                 assert!(prop.is_cover_property());
                 number_covers_unsatisfiable += 1;
@@ -343,7 +392,11 @@ pub fn format_result(
         + number_covers_unsatisfiable
         + number_covers_undetermined;
 
-    let number_properties = properties.len() - number_cover_properties;
+    let number_properties = properties.len()
+        - number_cover_properties
+        - number_bounds
+        - number_predicates
+        - number_stack_depths;
 
     let summary = format!("\n ** {number_checks_failed} of {number_properties} failed");
     result_str.push_str(&summary);
@@ -364,6 +417,19 @@ pub fn format_result(
     }
     result_str.push('\n');
 
+    if number_checks_failed > 0 {
+        // Group the failures by property class, so that classes given to `kani::assert_class!`
+        // can be told apart from the built-in ones
+        let mut failed_classes = BTreeMap::<String, usize>::new();
+        for prop in &failed_tests {
+            *failed_classes.entry(prop.property_class()).or_default() += 1;
+        }
+        let failed_classes: Vec<String> =
+            failed_classes.iter().map(|(class, count)| format!("{class} ({count})")).collect();
+        result_str
+            .push_str(&format!(" ** Failed checks by class: {}\n", failed_classes.join(", ")));
+    }
+
     if number_cover_properties > 0 {
         // Print a summary line for cover properties
         let summary = format!(
@@ -388,23 +454,67 @@ pub fn format_result(
         result_str.push('\n');
     }
 
+    if number_bounds > 0 {
+        // Print a summary line for the bounds assumed by the harness, so they can be audited
+        let summary = format!("\n ** {number_bounds} bounds assumed");
+        result_str.push_str(&summary);
+        let mut other_status = Vec::<String>::new();
+        if number_bounds_unsatisfiable > 0 {
+            let unsatisfiable_str = format!("{number_bounds_unsatisfiable} unsatisfiable");
+            other_status.push(unsatisfiable_str);
+        }
+        if number_bounds_unreachable > 0 {
+            let unreachable_str = format!("{number_bounds_unreachable} unreachable");
+            other_status.push(unreachable_str);
+        }
+        if !other_status.is_empty() {
+            result_str.push_str(" (");
+            result_str.push_str(&other_status.join(","));
+            result_str.push(')');
+        }
+        result_str.push('\n');
+        for prop in properties.iter().filter(|prop| prop.is_bounds_property()) {
+            let location = &prop.source_location;
+            let bound = format!("    - {} ({}, {location})\n", prop.description, prop.status);
+            result_str.push_str(&bound);
+        }
+        result_str.push('\n');
+    }
+
+    if let Some(depth) = deepest_stack {
+        result_str.push_str(&format!("\n ** Deepest call stack: {depth} calls\n\n"));
+    }
+
+    let named_checks = named_check_results(properties);
+    if !named_checks.is_empty() {
+        // List the status of each named check, so it can be tracked across runs
+        result_str.push_str(&format!("\n ** {} named checks\n", named_checks.len()));
+        for (name, status) in named_checks {
+            result_str.push_str(&format!("    - {name}: {status}\n"));
+        }
+        result_str.push('\n');
+    }
+
     for prop in failed_tests {
-        let failure_message = build_failure_message(prop.description.clone(), &prop.trace.clone());
+        let failure_message = build_failure_message(prop);
         result_str.push_str(&failure_message);
     }
 
-    let verification_result = if status == VerificationStatus::Success {
-        style("SUCCESSFUL").green()
-    } else {
-        style("FAILED").red()
+    let verification_result = match status {
+        VerificationStatus::Success => style("SUCCESSFUL").green(),
+        VerificationStatus::Failure => style("FAILED").red(),
+        VerificationStatus::Timeout => style("TIMEOUT").yellow(),
     };
-    let should_panic_info = if should_panic {
+    let should_panic_info = if should_panic && status != VerificationStatus::Timeout {
         match failed_properties {
             FailedProperties::None => " (encountered no panics, but at least one was expected)",
             FailedProperties::PanicsOnly => " (encountered one or more panics as expected)",
             FailedProperties::Other => {
                 " (encountered failures other than panics, which were unexpected)"
             }
+            FailedProperties::UnexpectedPanics => {
+                " (encountered one or more panics, but not all of them were expected)"
+            }
         }
     } else {
         ""
@@ -412,6 +522,14 @@ pub fn format_result(
     let overall_result = format!("\nVERIFICATION:- {verification_result}{should_panic_info}\n");
     result_str.push_str(&overall_result);
 
+    for prop in unsatisfiable_predicates {
+        let location = &prop.source_location;
+        result_str.push_str(&format!(
+            "** WARNING: The predicate of `kani::any_where` at {location} is unsatisfiable, so \
+            the checks that follow it hold vacuously.\n"
+        ));
+    }
+
     // Ideally, we should generate two `ParserItem::Message` and push them
     // into the parser iterator so they are the next messages to be processed.
     // However, we haven't figured out the best way to do this for now.
@@ -431,6 +549,37 @@ pub fn format_result(
     result_str
 }
 
+/// Compute the coverage status of each line from the coverage properties (i.e., checks with the
+/// `code_coverage` property class), grouped by file. Loops through each of the checks on a line
+/// and gives:
+///  - A status `FULL` if all checks pertaining to a line number are `COVERED`
+///  - A status `NONE` if all checks related to a line are `UNCOVERED`
+///  - Otherwise (i.e., if the line contains both) it reports `PARTIAL`.
+pub fn line_coverage(properties: &[Property]) -> BTreeMap<String, BTreeMap<usize, CoverageStatus>> {
+    let mut coverage_results: BTreeMap<String, BTreeMap<usize, CoverageStatus>> =
+        BTreeMap::default();
+    for prop in properties.iter().filter(|prop| prop.is_code_coverage_property()) {
+        let src = prop.source_location.clone();
+        let file_entries = coverage_results.entry(src.file.unwrap()).or_default();
+        let check_status = if prop.status == CheckStatus::Covered {
+            CoverageStatus::Full
+        } else {
+            CoverageStatus::None
+        };
+
+        // Create Map<file, Map<line, status>>
+        file_entries
+            .entry(src.line.unwrap().parse().unwrap())
+            .and_modify(|line_status| {
+                if *line_status != check_status {
+                    *line_status = CoverageStatus::Partial
+                }
+            })
+            .or_insert(check_status);
+    }
+    coverage_results
+}
+
 /// Separate checks into coverage and non-coverage based on property class and format them separately for --coverage. We report both verification and processed coverage
 /// results
 pub fn format_coverage(
@@ -452,10 +601,7 @@ pub fn format_coverage(
 }
 
 /// Generate coverage result from all coverage properties (i.e., checks with `code_coverage` property class).
-/// Loops through each of the checks with the `code_coverage` property class on a line and gives:
-///  - A status `FULL` if all checks pertaining to a line number are `COVERED`
-///  - A status `NONE` if all checks related to a line are `UNCOVERED`
-///  - Otherwise (i.e., if the line contains both) it reports `PARTIAL`.
+/// See [line_coverage] for how the status of each line is computed.
 ///
 /// Used when the user requests coverage information with `--coverage`.
 /// Output is tested through the `coverage-based` testing suite, not the regular
@@ -464,27 +610,7 @@ fn format_result_coverage(properties: &[Property]) -> String {
     let mut formatted_output = String::new();
     formatted_output.push_str("\nCoverage Results:\n");
 
-    let mut coverage_results: BTreeMap<String, BTreeMap<usize, CoverageStatus>> =
-        BTreeMap::default();
-    for prop in properties {
-        let src = prop.source_location.clone();
-        let file_entries = coverage_results.entry(src.file.unwrap()).or_default();
-        let check_status = if prop.status == CheckStatus::Covered {
-            CoverageStatus::Full
-        } else {
-            CoverageStatus::None
-        };
-
-        // Create Map<file, Map<line, status>>
-        file_entries
-            .entry(src.line.unwrap().parse().unwrap())
-            .and_modify(|line_status| {
-                if *line_status != check_status {
-                    *line_status = CoverageStatus::Partial
-                }
-            })
-            .or_insert(check_status);
-    }
+    let coverage_results = line_coverage(properties);
 
     // Create formatted string that is returned to the user as output
     for (file, checks) in coverage_results.iter() {
@@ -499,7 +625,9 @@ fn format_result_coverage(properties: &[Property]) -> String {
 
 /// Attempts to build a message for a failed property with as much detailed
 /// information on the source location as possible.
-fn build_failure_message(description: String, trace: &Option<Vec<TraceItem>>) -> String {
+fn build_failure_message(prop: &Property) -> String {
+    let description = &prop.description;
+    let trace = &prop.trace;
     let backup_failure_message = format!("Failed Checks: {description}\n");
     if trace.is_none() {
         return backup_failure_message;
@@ -519,13 +647,55 @@ fn build_failure_message(description: String, trace: &Option<Vec<TraceItem>>) ->
         let failure_file = failure_source.file.unwrap();
         let failure_function = failure_source.function.unwrap();
         let failure_line = failure_source.line.unwrap();
+        let values = if prop.property_class() == ENUM_VALIDITY_PROPERTY_CLASS {
+            enum_tag_value(&failure_trace)
+        } else {
+            assert_operand_values(&failure_trace)
+        };
+        let values = values.unwrap_or_default();
         return format!(
-            "Failed Checks: {description}\n File: \"{failure_file}\", line {failure_line}, in {failure_function}\n"
+            "Failed Checks: {description}\n File: \"{failure_file}\", line {failure_line}, in {failure_function}\n{values}"
         );
     }
     backup_failure_message
 }
 
+/// Extract the value of the operands of a failed `kani::assert_eq!` or `kani::assert_ne!` from
+/// its trace, formatted as one line per operand.
+///
+/// These macros copy each operand to a variable with a well known name right before the
+/// comparison, so the last assignment to these variables holds the values that failed the
+/// assertion. Returns `None` if the trace doesn't have the value of both operands.
+fn assert_operand_values(trace: &[TraceItem]) -> Option<String> {
+    let last_value = |variable: &str| {
+        trace.iter().rev().find_map(|item| {
+            let lhs = item.lhs.as_ref()?;
+            let is_variable = lhs == variable
+                || lhs.strip_suffix(variable).is_some_and(|prefix| prefix.ends_with("::"));
+            if item.step_type == "assignment" && is_variable { item.value.as_ref() } else { None }
+        })
+    };
+    let left = last_value("kani_left")?;
+    let right = last_value("kani_right")?;
+    Some(format!(" left: {left}\n right: {right}\n"))
+}
+
+/// Extract the tag of the enum value that failed a check of `--enum-validity-checks` from its
+/// trace. The compiler copies the tag to a variable named `kani_enum_tag_<n>` right before the
+/// check, so the value of the last such assignment is the one that was checked.
+fn enum_tag_value(trace: &[TraceItem]) -> Option<String> {
+    let value = trace.iter().rev().find_map(|item| {
+        let lhs = item.lhs.as_ref()?;
+        let name = lhs.rsplit("::").next().unwrap_or(lhs);
+        if item.step_type == "assignment" && name.starts_with("kani_enum_tag_") {
+            item.value.as_ref()
+        } else {
+            None
+        }
+    })?;
+    Some(format!(" tag: {value}\n"))
+}
+
 /// Edits an error message.
 ///
 /// At present, we only know one case where CBMC emits an error message, related
@@ -579,6 +749,8 @@ pub fn postprocess_result(properties: Vec<Property>, extra_ptr_checks: bool) ->
         annotate_properties_with_reach_results(properties_without_sanity_checks, reach_checks);
     // Remove reachability check IDs from regular property descriptions
     let properties_without_ids = remove_check_ids_from_description(properties_annotated);
+    // Move failed deallocation checks from `kani_lib.c` to the Rust code that deallocates
+    let properties_without_ids = update_dealloc_check_locations(properties_without_ids);
 
     // Filter out extra pointer checks if needed
     let properties_filtered = if !extra_ptr_checks {
@@ -718,7 +890,8 @@ fn update_results_of_code_covererage_checks(mut properties: Vec<Property>) -> Ve
     properties
 }
 
-/// Update the results of cover properties.
+/// Update the results of cover properties, and of the bounds declared with
+/// `kani::bounds!` and the predicates of `kani::any_where` which are encoded the same way.
 /// We encode cover(cond) as assert(!cond), so if the assertion
 /// fails, then the cover property is satisfied and vice versa.
 /// - SUCCESS -> UNSATISFIABLE
@@ -728,7 +901,11 @@ fn update_results_of_code_covererage_checks(mut properties: Vec<Property>) -> Ve
 /// `update_properties_with_reach_status` is called beforehand
 fn update_results_of_cover_checks(mut properties: Vec<Property>) -> Vec<Property> {
     for prop in properties.iter_mut() {
-        if prop.is_cover_property() {
+        if prop.is_cover_property()
+            || prop.is_bounds_property()
+            || prop.is_vacuity_property()
+            || prop.reached_stack_depth().is_some()
+        {
             if prop.status == CheckStatus::Success {
                 prop.status = CheckStatus::Unsatisfiable;
             } else if prop.status == CheckStatus::Failure {
@@ -0,0 +1,831 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `cargo kani cfg-report`, which lists the code of a package that is gated on
+//! `cfg(kani)` and checks that verification-only code cannot end up in normal builds.
+//!
+//! The report is based on the attributes in the source code, so it doesn't require compiling
+//! the package. Starting from the root file of each target, we follow the `mod` declarations,
+//! find the `#[cfg(..)]`, `#![cfg(..)]` and `#[cfg_attr(..)]` attributes that mention `kani`,
+//! and classify the code they gate by evaluating the predicate in a build without Kani:
+//!
+//!  - Verification only: the predicate is false, so the code is only compiled by Kani.
+//!  - Skipped by Kani: the predicate is false when Kani compiles the code, e.g. `not(kani)`.
+//!  - Leak: the predicate is true, e.g., because it depends on a feature enabled by default.
+//!  - Possible leak: the predicate depends on something else, e.g. `any(kani, test)`.
+//!
+//! We also report the uses of the `kani` crate outside of verification-only code, as well as
+//! the normal dependencies on the `kani` crate, since they make normal builds depend on Kani.
+//!
+//! Since the source isn't expanded, the report doesn't cover:
+//!
+//!  - Modules loaded from other files with `#[path = ".."]` or `include!`, and modules declared
+//!    with `mod name;` inside an inline `mod { .. }`, which we look up next to the parent file.
+//!  - Attributes generated by macros, including `macro_rules!` and derive macros.
+//!  - The `cfg!(kani)` macro, and the `cfg` values set by build scripts.
+
+use crate::call_cargo::packages_to_verify;
+use crate::session::KaniSession;
+use anyhow::{bail, Result};
+use cargo_metadata::{DependencyKind, Package};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+
+/// Run the report on the selected packages. Fails if any verification-only code may be
+/// compiled without Kani.
+pub fn run_cfg_report(session: &KaniSession) -> Result<()> {
+    let metadata = session.cargo_metadata(session.args.build_target())?;
+    let root = metadata.workspace_root.as_std_path();
+    let mut problems = 0;
+    for package in packages_to_verify(&session.args, &metadata)? {
+        let report = PackageReport::new(package);
+        print!("{}", report.render(root));
+        problems += report.problems();
+    }
+    if problems > 0 {
+        bail!("found {problems} place(s) where verification-only code may leak into normal builds");
+    }
+    Ok(())
+}
+
+/// A predicate of a `cfg` attribute.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Cfg {
+    Name(String),
+    KeyValue(String, String),
+    All(Vec<Cfg>),
+    Any(Vec<Cfg>),
+    Not(Box<Cfg>),
+}
+
+impl Cfg {
+    fn mentions_kani(&self) -> bool {
+        match self {
+            Cfg::Name(name) => name == "kani",
+            Cfg::KeyValue(..) => false,
+            Cfg::All(preds) | Cfg::Any(preds) => preds.iter().any(Cfg::mentions_kani),
+            Cfg::Not(pred) => pred.mentions_kani(),
+        }
+    }
+
+    /// Evaluate the predicate, where `None` means that the value depends on the build.
+    /// Features that are enabled by default are known to be set; all the other options, except
+    /// for `kani`, are unknown.
+    fn eval(&self, kani: bool, default_features: &BTreeSet<String>) -> Option<bool> {
+        match self {
+            Cfg::Name(name) if name == "kani" => Some(kani),
+            Cfg::KeyValue(key, value) if key == "feature" && default_features.contains(value) => {
+                Some(true)
+            }
+            Cfg::Name(_) | Cfg::KeyValue(..) => None,
+            Cfg::All(preds) | Cfg::Any(preds) => {
+                // `all` is false if any predicate is false, and `any` is true if any is true.
+                let absorbing = matches!(self, Cfg::Any(_));
+                let values: Vec<_> =
+                    preds.iter().map(|pred| pred.eval(kani, default_features)).collect();
+                if values.contains(&Some(absorbing)) {
+                    Some(absorbing)
+                } else if values.iter().all(Option::is_some) {
+                    Some(!absorbing)
+                } else {
+                    None
+                }
+            }
+            Cfg::Not(pred) => pred.eval(kani, default_features).map(|value| !value),
+        }
+    }
+
+    /// The options other than `kani` that the predicate depends on.
+    fn conditions(&self, conditions: &mut BTreeSet<String>) {
+        match self {
+            Cfg::Name(name) if name == "kani" => {}
+            Cfg::Name(_) | Cfg::KeyValue(..) => {
+                conditions.insert(self.to_string());
+            }
+            Cfg::All(preds) | Cfg::Any(preds) => {
+                preds.iter().for_each(|pred| pred.conditions(conditions))
+            }
+            Cfg::Not(pred) => pred.conditions(conditions),
+        }
+    }
+
+    fn classify(&self, default_features: &BTreeSet<String>) -> Gating {
+        if self.eval(true, default_features) == Some(false) {
+            return Gating::SkippedByKani;
+        }
+        let mut conditions = BTreeSet::new();
+        self.conditions(&mut conditions);
+        match self.eval(false, default_features) {
+            Some(false) => Gating::VerificationOnly,
+            Some(true) => Gating::Leak(conditions),
+            None => Gating::PossibleLeak(conditions),
+        }
+    }
+}
+
+impl Display for Cfg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |preds: &[Cfg]| preds.iter().map(Cfg::to_string).collect::<Vec<_>>().join(", ");
+        match self {
+            Cfg::Name(name) => write!(f, "{name}"),
+            Cfg::KeyValue(key, value) => write!(f, "{key} = {value:?}"),
+            Cfg::All(preds) => write!(f, "all({})", list(preds)),
+            Cfg::Any(preds) => write!(f, "any({})", list(preds)),
+            Cfg::Not(pred) => write!(f, "not({pred})"),
+        }
+    }
+}
+
+/// How the code gated by an attribute relates to normal builds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Gating {
+    VerificationOnly,
+    SkippedByKani,
+    /// The code is compiled without Kani, given the options that the predicate depends on.
+    Leak(BTreeSet<String>),
+    PossibleLeak(BTreeSet<String>),
+}
+
+impl Gating {
+    fn is_problem(&self) -> bool {
+        matches!(self, Gating::Leak(_) | Gating::PossibleLeak(_))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RegionKind {
+    /// A whole file, gated by an inner attribute or by the declaration of its module.
+    File,
+    /// The item, statement, field, etc. that follows an outer `cfg` attribute.
+    Item,
+    /// The attributes applied by `cfg_attr`, which don't remove any code.
+    Attribute,
+}
+
+/// The code gated by a `cfg` attribute that mentions `kani`, as byte offsets in the file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Region {
+    kind: RegionKind,
+    start: usize,
+    end: usize,
+    cfg: Cfg,
+}
+
+/// The result of scanning one source file.
+#[derive(Debug, Default)]
+struct FileScan {
+    regions: Vec<Region>,
+    /// The modules declared with `mod name;`, and the `cfg` of the declaration, if any.
+    modules: Vec<(String, Option<Cfg>)>,
+    /// Byte offsets of the uses of the `kani` crate.
+    kani_uses: Vec<usize>,
+}
+
+struct FileReport {
+    path: PathBuf,
+    text: String,
+    regions: Vec<(Region, Gating)>,
+    /// Uses of the `kani` crate in code that isn't gated on `kani`.
+    ungated_uses: Vec<usize>,
+}
+
+struct PackageReport {
+    name: String,
+    files: Vec<FileReport>,
+    /// The normal dependencies on the `kani` crate.
+    kani_dependencies: Vec<String>,
+}
+
+impl PackageReport {
+    fn new(package: &Package) -> PackageReport {
+        let default_features = default_features(package);
+        let kani_dependencies = package
+            .dependencies
+            .iter()
+            .filter(|dep| dep.name == "kani" && dep.kind == DependencyKind::Normal)
+            .filter(|dep| {
+                dep.target.as_ref().map_or(true, |target| !target.to_string().contains("kani"))
+            })
+            .map(|dep| dep.req.to_string())
+            .collect();
+
+        let mut visited = HashSet::new();
+        let mut queue: Vec<(PathBuf, Option<Cfg>, bool)> = package
+            .targets
+            .iter()
+            .filter(|target| !target.is_custom_build())
+            .map(|target| (target.src_path.clone().into_std_path_buf(), None, true))
+            .collect();
+        let mut files = vec![];
+        while let Some((path, inherited, is_root)) = queue.pop() {
+            if !visited.insert(path.clone()) {
+                continue;
+            }
+            let Ok(text) = std::fs::read_to_string(&path) else { continue };
+            let scan = scan_source(&text);
+            for (name, cfg) in &scan.modules {
+                let Some(module) = module_path(&path, name, is_root) else { continue };
+                let cfg = match (&inherited, cfg) {
+                    (Some(outer), Some(inner)) => {
+                        Some(Cfg::All(vec![outer.clone(), inner.clone()]))
+                    }
+                    (outer, inner) => outer.clone().or(inner.clone()),
+                };
+                queue.push((module, cfg.filter(Cfg::mentions_kani), false));
+            }
+            let mut regions = scan.regions;
+            if let Some(cfg) = inherited {
+                for region in &mut regions {
+                    region.cfg = Cfg::All(vec![cfg.clone(), region.cfg.clone()]);
+                }
+                regions
+                    .insert(0, Region { kind: RegionKind::File, start: 0, end: text.len(), cfg });
+            }
+            let regions: Vec<_> = regions
+                .into_iter()
+                .map(|region| {
+                    let gating = region.cfg.classify(&default_features);
+                    (region, gating)
+                })
+                .collect();
+            // Uses inside code that is only compiled by Kani are fine. Uses inside code that
+            // leaks are already reported with the region.
+            let ungated_uses = scan
+                .kani_uses
+                .into_iter()
+                .filter(|offset| {
+                    !regions.iter().any(|(region, gating)| {
+                        *gating != Gating::SkippedByKani
+                            && (region.start..region.end).contains(offset)
+                    })
+                })
+                .collect();
+            files.push(FileReport { path, text, regions, ungated_uses });
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        PackageReport { name: package.name.clone(), files, kani_dependencies }
+    }
+
+    fn problems(&self) -> usize {
+        let regions = self.files.iter().flat_map(|file| &file.regions);
+        let uses = self.files.iter().map(|file| file.ungated_uses.len()).sum::<usize>();
+        regions.filter(|(_, gating)| gating.is_problem()).count()
+            + uses
+            + self.kani_dependencies.len()
+    }
+
+    fn render(&self, root: &Path) -> String {
+        let mut output = format!("cfg(kani) report for `{}`:\n", self.name);
+        let mut verification_only = 0;
+        for file in &self.files {
+            let path = file.path.strip_prefix(root).unwrap_or(&file.path).display();
+            for (region, gating) in &file.regions {
+                let lines = line_range(&file.text, region.start, region.end);
+                let what = match region.kind {
+                    RegionKind::File => "file",
+                    RegionKind::Item => "code",
+                    RegionKind::Attribute => "attribute",
+                };
+                let status = match gating {
+                    Gating::VerificationOnly => {
+                        verification_only += 1;
+                        "verification only".to_string()
+                    }
+                    Gating::SkippedByKani => "skipped by Kani".to_string(),
+                    Gating::Leak(conditions) => {
+                        format!("LEAK: compiled without Kani by default ({})", join(conditions))
+                    }
+                    Gating::PossibleLeak(conditions) => {
+                        format!(
+                            "POSSIBLE LEAK: compiled without Kani depending on {}",
+                            join(conditions)
+                        )
+                    }
+                };
+                output.push_str(&format!(
+                    "  {path}:{lines}: {what} `cfg({})`: {status}\n",
+                    region.cfg
+                ));
+            }
+            for offset in &file.ungated_uses {
+                let line = line_of(&file.text, *offset);
+                output.push_str(&format!(
+                    "  {path}:{line}: LEAK: uses the `kani` crate outside of `cfg(kani)`\n"
+                ));
+            }
+        }
+        for req in &self.kani_dependencies {
+            output.push_str(&format!(
+                "  Cargo.toml: LEAK: depends on `kani` ({req}) in normal builds; use \
+                `[target.'cfg(kani)'.dependencies]` or remove the dependency\n"
+            ));
+        }
+        output.push_str(&format!(
+            "Summary: {verification_only} verification-only region(s), {} problem(s).\n",
+            self.problems()
+        ));
+        output
+    }
+}
+
+fn join(conditions: &BTreeSet<String>) -> String {
+    conditions.iter().map(|cond| format!("`{cond}`")).collect::<Vec<_>>().join(", ")
+}
+
+/// The features that are enabled when the package is built with its default features.
+fn default_features(package: &Package) -> BTreeSet<String> {
+    let mut enabled = BTreeSet::new();
+    let mut queue = vec!["default".to_string()];
+    while let Some(feature) = queue.pop() {
+        if let Some(implied) = package.features.get(&feature) {
+            if enabled.insert(feature) {
+                // Skip the features of dependencies, e.g. `dep:name` and `name/feature`.
+                queue.extend(implied.iter().filter(|f| !f.contains([':', '/'])).cloned());
+            }
+        }
+    }
+    enabled
+}
+
+/// The file of the module `name` declared in `parent`, following the same rules as rustc.
+fn module_path(parent: &Path, name: &str, parent_is_root: bool) -> Option<PathBuf> {
+    let dir = parent.parent()?;
+    let is_mod_rs = parent.file_name().map_or(false, |file| file == "mod.rs");
+    let dir =
+        if parent_is_root || is_mod_rs { dir.to_path_buf() } else { dir.join(parent.file_stem()?) };
+    [dir.join(format!("{name}.rs")), dir.join(name).join("mod.rs")]
+        .into_iter()
+        .find(|path| path.is_file())
+}
+
+fn line_of(text: &str, offset: usize) -> usize {
+    text[..offset].matches('\n').count() + 1
+}
+
+fn line_range(text: &str, start: usize, end: usize) -> String {
+    let first = line_of(text, start);
+    let last = line_of(text, end.max(start + 1) - 1);
+    if first == last { first.to_string() } else { format!("{first}-{last}") }
+}
+
+/// Replace the comments and the contents of string and character literals by spaces, so they
+/// don't confuse the scanner. Line breaks and byte offsets are preserved.
+fn mask_non_code(src: &str) -> Vec<u8> {
+    let bytes = src.as_bytes();
+    let mut masked = bytes.to_vec();
+    let mut mask = |from: usize, to: usize| {
+        for byte in &mut masked[from..to] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    };
+    let is_ident = |byte: u8| byte.is_ascii_alphanumeric() || byte == b'_';
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+        if rest.starts_with(b"//") {
+            let end = rest.iter().position(|b| *b == b'\n').map_or(bytes.len(), |pos| i + pos);
+            mask(i, end);
+            i = end;
+        } else if rest.starts_with(b"/*") {
+            let mut depth = 0;
+            let mut j = i;
+            while j < bytes.len() {
+                if bytes[j..].starts_with(b"/*") {
+                    depth += 1;
+                    j += 2;
+                } else if bytes[j..].starts_with(b"*/") {
+                    depth -= 1;
+                    j += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    j += 1;
+                }
+            }
+            mask(i, j);
+            i = j;
+        } else if bytes[i] == b'r'
+            && (i == 0 || !is_ident(bytes[i - 1]) || bytes[i - 1] == b'b')
+            && rest[1..].iter().position(|b| *b != b'#').map_or(false, |pos| rest[1 + pos] == b'"')
+        {
+            // Raw string: r"..." or r#"..."#.
+            let hashes = rest[1..].iter().take_while(|b| **b == b'#').count();
+            let content = i + 2 + hashes;
+            let closing = [b"\"".as_slice(), &vec![b'#'; hashes]].concat();
+            let end = bytes[content..]
+                .windows(closing.len())
+                .position(|window| window == closing)
+                .map_or(bytes.len(), |pos| content + pos);
+            mask(content, end);
+            i = (end + closing.len()).min(bytes.len());
+        } else if bytes[i] == b'"' {
+            let mut j = i + 1;
+            while j < bytes.len() && bytes[j] != b'"' {
+                j += if bytes[j] == b'\\' { 2 } else { 1 };
+            }
+            mask(i + 1, j.min(bytes.len()));
+            i = j + 1;
+        } else if bytes[i] == b'\'' {
+            // Either a character literal or a lifetime.
+            let char_len = src[i + 1..].chars().next().map_or(0, char::len_utf8);
+            if rest.get(1) == Some(&b'\\') {
+                let mut j = i + 2;
+                while j < bytes.len() && bytes[j] != b'\'' {
+                    j += 1;
+                }
+                mask(i + 1, j.min(bytes.len()));
+                i = j + 1;
+            } else if bytes.get(i + 1 + char_len) == Some(&b'\'') {
+                mask(i + 1, i + 1 + char_len);
+                i += 2 + char_len;
+            } else {
+                i += 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    masked
+}
+
+/// Find the attributes, module declarations and uses of `kani` of a source file.
+fn scan_source(src: &str) -> FileScan {
+    let code = mask_non_code(src);
+    let mut scan = FileScan::default();
+    let mut i = 0;
+    while i < code.len() {
+        if code[i] == b'#' {
+            if let Some(attr) = parse_cfg_attr(src, &code, i) {
+                let cfg = attr.cfg.filter(Cfg::mentions_kani);
+                let item_end = if attr.inner { code.len() } else { item_end(&code, attr.end) };
+                if let (Some(cfg), false) = (&cfg, attr.is_cfg_attr) {
+                    if let Some(name) = declared_module(&code, attr.end, item_end) {
+                        scan.modules.push((name, Some(cfg.clone())));
+                    }
+                }
+                if let Some(cfg) = cfg {
+                    let (kind, start, end) = match (attr.inner, attr.is_cfg_attr) {
+                        (_, true) => (RegionKind::Attribute, i, attr.end),
+                        (true, false) => (RegionKind::File, 0, code.len()),
+                        (false, false) => (RegionKind::Item, i, item_end),
+                    };
+                    scan.regions.push(Region { kind, start, end, cfg });
+                }
+                i = attr.end;
+                continue;
+            }
+        }
+        if is_word_at(&code, i, b"mod") {
+            if let Some(name) = declared_module(&code, i, code.len()) {
+                if !scan.modules.iter().any(|(declared, _)| *declared == name) {
+                    scan.modules.push((name, None));
+                }
+            }
+        }
+        if is_word_at(&code, i, b"kani") {
+            let after = skip_whitespace(&code, i + 4);
+            let before = code[..i].iter().rposition(|byte| !byte.is_ascii_whitespace());
+            let is_extern = before.map_or(false, |pos| code[..=pos].ends_with(b"crate"));
+            if code[after..].starts_with(b"::") || is_extern {
+                scan.kani_uses.push(i);
+            }
+        }
+        i += 1;
+    }
+    scan
+}
+
+struct CfgAttr {
+    inner: bool,
+    is_cfg_attr: bool,
+    /// The predicate, if it could be parsed.
+    cfg: Option<Cfg>,
+    /// Offset after the closing bracket.
+    end: usize,
+}
+
+/// Parse the `cfg` or `cfg_attr` attribute that starts at `start`, if any.
+fn parse_cfg_attr(src: &str, code: &[u8], start: usize) -> Option<CfgAttr> {
+    let mut i = skip_whitespace(code, start + 1);
+    let inner = code.get(i) == Some(&b'!');
+    if inner {
+        i = skip_whitespace(code, i + 1);
+    }
+    if code.get(i) != Some(&b'[') {
+        return None;
+    }
+    i = skip_whitespace(code, i + 1);
+    let is_cfg_attr = is_word_at(code, i, b"cfg_attr");
+    if !is_cfg_attr && !is_word_at(code, i, b"cfg") {
+        return None;
+    }
+    i = skip_whitespace(code, i + if is_cfg_attr { 8 } else { 3 });
+    if code.get(i) != Some(&b'(') {
+        return None;
+    }
+    let close = matching_close(code, i)?;
+    let mut pred_end = close;
+    if is_cfg_attr {
+        // The predicate is the first argument of `cfg_attr`.
+        let mut depth = 0;
+        for (j, byte) in code.iter().enumerate().take(close).skip(i + 1) {
+            match byte {
+                b'(' | b'[' | b'{' => depth += 1,
+                b')' | b']' | b'}' => depth -= 1,
+                b',' if depth == 0 => {
+                    pred_end = j;
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+    let end = skip_whitespace(code, close + 1);
+    if code.get(end) != Some(&b']') {
+        return None;
+    }
+    let cfg = parse_cfg(&src[i + 1..pred_end]);
+    Some(CfgAttr { inner, is_cfg_attr, cfg, end: end + 1 })
+}
+
+/// The offset after the item that starts at `start`, i.e., after its body or its terminating
+/// `;` or `,`. The item ends early if the enclosing block closes.
+///
+/// Commas inside of generic arguments don't end the item. Angle brackets can also be
+/// comparisons, so we only track them outside of any other bracket, and only until the body.
+fn item_end(code: &[u8], start: usize) -> usize {
+    let mut depth = 0usize;
+    let mut angles = 0usize;
+    let mut i = start;
+    while i < code.len() {
+        match code[i] {
+            b'<' if depth == 0 => angles += 1,
+            b'>' if depth == 0 && !matches!(code[i - 1], b'-' | b'=') => {
+                angles = angles.saturating_sub(1)
+            }
+            b'{' if depth == 0 => {
+                angles = 0;
+                depth += 1;
+            }
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' if depth == 0 => return i,
+            b')' | b']' => depth -= 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    // The block may be part of a larger expression or statement.
+                    let next = skip_whitespace(code, i + 1);
+                    match code.get(next) {
+                        Some(b';' | b',') => return next + 1,
+                        Some(b'.' | b'?') => {}
+                        _ if is_word_at(code, next, b"else") => {}
+                        _ => return i + 1,
+                    }
+                }
+            }
+            b';' if depth == 0 => return i + 1,
+            b',' if depth == 0 && angles == 0 => return i + 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    code.len()
+}
+
+/// The name of the module declared with `mod name;` between `start` and `end`, if any.
+fn declared_module(code: &[u8], start: usize, end: usize) -> Option<String> {
+    let len = code[start..end].iter().position(|byte| matches!(byte, b';' | b'{'))?;
+    if code[start + len] != b';' {
+        return None;
+    }
+    let text = std::str::from_utf8(&code[start..start + len]).ok()?;
+    let mut words = text.split_whitespace().skip_while(|word| word.starts_with("pub"));
+    if words.next()? != "mod" {
+        return None;
+    }
+    let name = words.next()?;
+    words.next().is_none().then(|| name.to_string())
+}
+
+fn matching_close(code: &[u8], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, byte) in code.iter().enumerate().skip(open) {
+        match byte {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn skip_whitespace(code: &[u8], mut i: usize) -> usize {
+    while code.get(i).map_or(false, u8::is_ascii_whitespace) {
+        i += 1;
+    }
+    i
+}
+
+/// Whether `word` is at offset `i` as a whole identifier.
+fn is_word_at(code: &[u8], i: usize, word: &[u8]) -> bool {
+    let is_ident = |byte: &u8| byte.is_ascii_alphanumeric() || *byte == b'_';
+    code[i..].starts_with(word)
+        && (i == 0 || !is_ident(&code[i - 1]))
+        && !code.get(i + word.len()).map_or(false, is_ident)
+}
+
+/// Parse a `cfg` predicate, e.g.: `any(kani, feature = "verify")`.
+fn parse_cfg(text: &str) -> Option<Cfg> {
+    let tokens = tokenize(text)?;
+    let (cfg, rest) = parse_cfg_tokens(&tokens)?;
+    rest.is_empty().then_some(cfg)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Punct(char),
+}
+
+fn tokenize(text: &str) -> Option<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' | ')' | ',' | '=' => tokens.push(Token::Punct(c)),
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => value.push(chars.next()?),
+                        c => value.push(c),
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut ident = c.to_string();
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                    ident.push(c);
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            _ => return None,
+        }
+    }
+    Some(tokens)
+}
+
+fn parse_cfg_tokens(tokens: &[Token]) -> Option<(Cfg, &[Token])> {
+    let (Token::Ident(name), rest) = tokens.split_first()? else { return None };
+    match rest {
+        [Token::Punct('='), Token::Str(value), rest @ ..] => {
+            Some((Cfg::KeyValue(name.clone(), value.clone()), rest))
+        }
+        [Token::Punct('('), rest @ ..] => {
+            let mut preds = vec![];
+            let mut rest = rest;
+            loop {
+                if let [Token::Punct(')'), tail @ ..] = rest {
+                    rest = tail;
+                    break;
+                }
+                let (pred, tail) = parse_cfg_tokens(rest)?;
+                preds.push(pred);
+                rest = match tail {
+                    [Token::Punct(','), tail @ ..] => tail,
+                    [Token::Punct(')'), ..] => tail,
+                    _ => return None,
+                };
+            }
+            let cfg = match name.as_str() {
+                "all" => Cfg::All(preds),
+                "any" => Cfg::Any(preds),
+                "not" if preds.len() == 1 => Cfg::Not(Box::new(preds.pop().unwrap())),
+                _ => return None,
+            };
+            Some((cfg, rest))
+        }
+        _ => Some((Cfg::Name(name.clone()), rest)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn check_parse_cfg() {
+        assert_eq!(parse_cfg("kani"), Some(Cfg::Name("kani".into())));
+        assert_eq!(
+            parse_cfg(r#"any( kani , feature = "verify" )"#),
+            Some(Cfg::Any(vec![
+                Cfg::Name("kani".into()),
+                Cfg::KeyValue("feature".into(), "verify".into())
+            ]))
+        );
+        assert_eq!(parse_cfg("not(kani)").unwrap().to_string(), "not(kani)");
+        assert_eq!(parse_cfg("not(kani, test)"), None);
+        assert_eq!(parse_cfg("all(kani"), None);
+    }
+
+    #[test]
+    fn check_classify() {
+        let classify = |text: &str| parse_cfg(text).unwrap().classify(&features(&["verify"]));
+        assert_eq!(classify("kani"), Gating::VerificationOnly);
+        assert_eq!(classify("all(kani, test)"), Gating::VerificationOnly);
+        assert_eq!(classify("not(kani)"), Gating::SkippedByKani);
+        assert_eq!(
+            classify(r#"any(kani, feature = "verify")"#),
+            Gating::Leak(features(&[r#"feature = "verify""#]))
+        );
+        assert_eq!(classify("any(kani, test)"), Gating::PossibleLeak(features(&["test"])));
+    }
+
+    #[test]
+    fn check_mask_non_code() {
+        let src = "let s = \"kani::any()\"; // kani::any()\nlet c = '\"'; fn f<'a>() {}";
+        let masked = String::from_utf8(mask_non_code(src)).unwrap();
+        assert_eq!(masked.len(), src.len());
+        assert!(!masked.contains("kani"));
+        assert!(masked.contains("fn f<'a>() {}"));
+        assert!(masked.contains('\n'));
+    }
+
+    #[test]
+    fn check_scan_source() {
+        let src = r#"
+mod model;
+#[cfg(kani)]
+mod proofs;
+
+pub fn add(a: u8, b: u8) -> u8 {
+    #[cfg(kani)]
+    kani::assume(a < 100);
+    a + b
+}
+
+#[cfg(any(kani, feature = "verify"))]
+impl Checked {
+    fn check(&self) { kani::cover!(); }
+}
+
+#[cfg_attr(kani, kani::proof)]
+fn check() {
+    let x: u8 = kani::any();
+}
+"#;
+        let scan = scan_source(src);
+        assert_eq!(
+            scan.modules,
+            vec![
+                ("model".to_string(), None),
+                ("proofs".to_string(), Some(Cfg::Name("kani".into())))
+            ]
+        );
+        let lines: Vec<_> = scan
+            .regions
+            .iter()
+            .map(|region| (region.kind, line_range(src, region.start, region.end)))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (RegionKind::Item, "3-4".to_string()),
+                (RegionKind::Item, "7-8".to_string()),
+                (RegionKind::Item, "12-15".to_string()),
+                (RegionKind::Attribute, "17".to_string()),
+            ]
+        );
+        let uses: Vec<_> = scan.kani_uses.iter().map(|offset| line_of(src, *offset)).collect();
+        assert_eq!(uses, vec![8, 14, 19]);
+    }
+
+    #[test]
+    fn check_item_end() {
+        let code = b"let x = if a { 1 } else { 2 }; next";
+        assert_eq!(item_end(code, 0), 30);
+        let code = b"struct S { a: u8 } next";
+        assert_eq!(item_end(code, 0), 18);
+        let code = b"field: u8, other: u8 }";
+        assert_eq!(item_end(code, 0), 10);
+        let code = b"fn f<A, B>() -> u8 { 0 } next";
+        assert_eq!(item_end(code, 0), 24);
+    }
+}
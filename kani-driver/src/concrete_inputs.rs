@@ -0,0 +1,123 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for the concrete inputs of `--concrete`, which fix some symbolic inputs of the
+//! harnesses to concrete values while the others stay symbolic, e.g., `--concrete x=5` for
+//! `let x: u32 = kani::any();`. This helps to find which inputs drive a failure or make the
+//! verification of a harness blow up.
+//!
+//! The compiler adds the assumption that each input is equal to its value, and records which
+//! inputs of each harness it fixed. We tell the user which inputs were fixed after the result of
+//! each harness, and warn about the inputs that the harness doesn't have.
+
+use crate::session::KaniSession;
+use crate::util::format_warning;
+use kani_metadata::HarnessMetadata;
+use std::fmt::{self, Display};
+
+/// A symbolic input of the harnesses and the concrete value it is fixed to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConcreteInput {
+    /// The name of the variable that holds the input.
+    pub name: String,
+    /// The value, written like a Rust literal.
+    pub value: String,
+}
+
+impl Display for ConcreteInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.value)
+    }
+}
+
+/// Parse a `--concrete` argument of the format `<name>=<value>`.
+pub fn parse_concrete_input(arg: &str) -> Result<ConcreteInput, String> {
+    let (name, value) =
+        arg.split_once('=').ok_or_else(|| format!("expected `<NAME>=<VALUE>`, found `{arg}`"))?;
+    let is_identifier = name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if !is_identifier {
+        return Err(format!("`{name}` is not the name of a variable"));
+    }
+    // The values are passed on to the compiler in a list of arguments separated by spaces.
+    if value.is_empty() || value.contains(char::is_whitespace) {
+        return Err(format!("invalid value `{value}` for input `{name}`"));
+    }
+    Ok(ConcreteInput { name: name.to_string(), value: value.to_string() })
+}
+
+impl KaniSession {
+    /// Tell which inputs of a harness `--concrete` fixed, and warn about the ones it doesn't have.
+    pub fn report_concrete_inputs(&self, harness: &HarnessMetadata) -> String {
+        let mut output = String::new();
+        if self.args.concrete_inputs.is_empty() {
+            return output;
+        }
+        let (fixed, missing): (Vec<_>, Vec<_>) = self
+            .args
+            .concrete_inputs
+            .iter()
+            .partition(|input| harness.concrete_inputs.contains(&input.name));
+        if !fixed.is_empty() {
+            output.push_str(&format_fixed_inputs(&harness.pretty_name, &fixed));
+            output.push('\n');
+        }
+        if !missing.is_empty() {
+            output
+                .push_str(&format_warning(&format_missing_inputs(&harness.pretty_name, &missing)));
+            output.push('\n');
+        }
+        output
+    }
+}
+
+fn format_fixed_inputs(name: &str, inputs: &[&ConcreteInput]) -> String {
+    let inputs: Vec<_> = inputs.iter().map(|input| input.to_string()).collect();
+    format!(
+        "The result of harness `{name}` only holds for the inputs fixed with `--concrete`: {}",
+        inputs.join(", ")
+    )
+}
+
+fn format_missing_inputs(name: &str, inputs: &[&ConcreteInput]) -> String {
+    let names: Vec<_> = inputs.iter().map(|input| format!("`{}`", input.name)).collect();
+    format!(
+        "Harness `{name}` has no symbolic input named {}, so `--concrete` didn't fix it. An \
+        input is named after the variable that is initialized with `kani::any()` in the harness.",
+        names.join(" or ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_parse_concrete_input() {
+        let input = parse_concrete_input("x=5").unwrap();
+        assert_eq!(input, ConcreteInput { name: "x".to_string(), value: "5".to_string() });
+        assert_eq!(input.to_string(), "x=5");
+        assert_eq!(parse_concrete_input("len=-1").unwrap().value, "-1");
+        assert!(parse_concrete_input("x").is_err());
+        assert!(parse_concrete_input("x=").is_err());
+        assert!(parse_concrete_input("=5").is_err());
+        assert!(parse_concrete_input("1x=5").is_err());
+        assert!(parse_concrete_input("s.len=5").is_err());
+        assert!(parse_concrete_input("c= ").is_err());
+    }
+
+    #[test]
+    fn check_format_concrete_inputs() {
+        let x = parse_concrete_input("x=5").unwrap();
+        let y = parse_concrete_input("y=true").unwrap();
+        assert_eq!(
+            format_fixed_inputs("check_add", &[&x, &y]),
+            "The result of harness `check_add` only holds for the inputs fixed with \
+            `--concrete`: x=5, y=true"
+        );
+        assert!(
+            format_missing_inputs("check_add", &[&x, &y])
+                .starts_with("Harness `check_add` has no symbolic input named `x` or `y`")
+        );
+    }
+}
@@ -11,6 +11,7 @@
 use concrete_vals_extractor::{extract_harness_values, ConcreteVal};
 use kani_metadata::HarnessMetadata;
 use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
 use std::ffi::OsString;
 use std::fs::{read_to_string, File};
 use std::hash::{Hash, Hasher};
@@ -41,14 +42,8 @@ pub fn gen_and_add_concrete_playback(
                     harness.pretty_name
                 )
             } else {
-                let mut unit_tests: Vec<UnitTest> = harness_values
-                    .iter()
-                    .map(|concrete_vals| {
-                        let pretty_name = harness.get_harness_name_unqualified();
-                        format_unit_test(&pretty_name, &concrete_vals)
-                    })
-                    .collect();
-                unit_tests.dedup_by(|a, b| a.name == b.name);
+                let unit_tests =
+                    format_unit_tests(harness.get_harness_name_unqualified(), &harness_values);
                 match playback_mode {
                     ConcretePlaybackMode::Print => {
                         for generated_unit_test in unit_tests.iter() {
@@ -231,6 +226,17 @@ fn run_rustfmt(
     }
 }
 
+/// Generate one unit test for each list of concrete values. Different failures often produce the
+/// same values, so only the first of the identical tests is kept.
+fn format_unit_tests(harness_name: &str, harness_values: &[Vec<ConcreteVal>]) -> Vec<UnitTest> {
+    let mut names = HashSet::new();
+    harness_values
+        .iter()
+        .map(|concrete_vals| format_unit_test(harness_name, concrete_vals))
+        .filter(|unit_test| names.insert(unit_test.name.clone()))
+        .collect()
+}
+
 /// Generate a formatted unit test from a list of concrete values.
 fn format_unit_test(harness_name: &str, concrete_vals: &[ConcreteVal]) -> UnitTest {
     // Hash the concrete values along with the proof harness name.
@@ -532,6 +538,18 @@ fn check_hashes_are_unique() {
         assert_ne!(hash_base, hash_diff_interp_val);
     }
 
+    /// Unit tests with the same concrete values as an earlier one should not be generated.
+    #[test]
+    fn check_identical_tests_are_removed() {
+        let val = |byte: u8| ConcreteVal { byte_arr: vec![byte], interp_val: byte.to_string() };
+        let harness_values = [vec![val(1)], vec![val(2)], vec![val(1)], vec![val(2), val(1)]];
+        let unit_tests = format_unit_tests("harness", &harness_values);
+        assert_eq!(unit_tests.len(), 3);
+        assert_eq!(unit_tests[0].name, format_unit_test("harness", &harness_values[0]).name);
+        assert_eq!(unit_tests[1].name, format_unit_test("harness", &harness_values[1]).name);
+        assert_eq!(unit_tests[2].name, format_unit_test("harness", &harness_values[3]).name);
+    }
+
     #[test]
     fn check_rustfmt_args_no_line_ranges() {
         let file_line_ranges = [FileLineRange { file: "file1".to_string(), line_range: None }];
@@ -603,7 +621,11 @@ fn check_concrete_vals_extractor() {
                     binary: Some("0000001100000001".to_string()),
                     data: Some(TraceData::NonBool("385".to_string())),
                     width: Some(16),
+                    typ: None,
+                    members: vec![],
+                    elements: vec![],
                 }),
+                function: None,
             }]),
         }];
         let concrete_vals = extract_harness_values(&processed_items).pop().unwrap();
@@ -0,0 +1,100 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for the contract spec file (`--contract-specs`), which gives contracts to functions
+//! that can't be annotated, e.g., the functions of other crates. For example:
+//!
+//! ```toml
+//! [contracts]
+//! "regex::Regex::is_match" = "crate::specs::is_match"
+//! ```
+//!
+//! Each function is mapped to a spec function of the crate under verification, which has the
+//! same signature and carries the `requires`, `ensures` and `modifies` attributes of the
+//! contract. Every harness then uses the contract instead of the function, like with
+//! `stub_verified`, except that the contract is assumed rather than checked.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// The content of a contract spec file.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ContractSpecs {
+    /// The spec function of each function, indexed by the path of the function.
+    #[serde(default)]
+    pub contracts: BTreeMap<String, String>,
+}
+
+impl ContractSpecs {
+    /// The arguments that pass these specs on to the compiler.
+    pub fn as_compiler_flags(&self) -> impl Iterator<Item = String> + '_ {
+        self.contracts.iter().map(|(function, spec)| format!("--contract-spec={function}={spec}"))
+    }
+}
+
+/// Read the contract spec file at `path`. This is used to parse the `--contract-specs` argument,
+/// so that a malformed file is reported like any other invalid argument.
+pub fn parse_contract_specs_file(path: &str) -> Result<ContractSpecs, String> {
+    let content = std::fs::read_to_string(PathBuf::from(path))
+        .map_err(|err| format!("failed to read `{path}`: {err}"))?;
+    let specs =
+        parse_contract_specs(&content).map_err(|err| format!("failed to parse `{path}`: {err}"))?;
+    // The paths are passed on to the compiler in a list of arguments separated by spaces.
+    let has_space = |name: &&String| name.contains(char::is_whitespace);
+    if let Some(name) = specs.contracts.iter().flat_map(|(f, spec)| [f, spec]).find(has_space) {
+        return Err(format!(
+            "invalid path `{name}` in `{path}`: the paths of `--contract-specs` can't contain \
+            spaces"
+        ));
+    }
+    Ok(specs)
+}
+
+fn parse_contract_specs(content: &str) -> Result<ContractSpecs, toml::de::Error> {
+    toml::from_str(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_parse_contract_specs() {
+        let specs = parse_contract_specs(
+            r#"
+            [contracts]
+            "regex::Regex::is_match" = "crate::specs::is_match"
+            "std::fs::read" = "specs::read"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            specs.as_compiler_flags().collect::<Vec<_>>(),
+            [
+                "--contract-spec=regex::Regex::is_match=crate::specs::is_match",
+                "--contract-spec=std::fs::read=specs::read"
+            ]
+        );
+        assert_eq!(parse_contract_specs("").unwrap(), ContractSpecs::default());
+    }
+
+    #[test]
+    fn check_parse_invalid_contract_specs() {
+        assert!(parse_contract_specs("[contracts]\n\"std::fs::read\" = 1").is_err());
+        assert!(parse_contract_specs("[contract]\n\"std::fs::read\" = \"read\"").is_err());
+    }
+
+    #[test]
+    fn check_spaces_in_contract_specs() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            b"[contracts]\n\"<Foo as Bar>::baz\" = \"specs::baz\"",
+        )
+        .unwrap();
+        let err = parse_contract_specs_file(&file.path().display().to_string()).unwrap_err();
+        assert!(err.contains("can't contain spaces"), "{err}");
+    }
+}
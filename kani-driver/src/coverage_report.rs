@@ -0,0 +1,94 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Aggregate the line coverage of all harnesses into a single report in the LCOV format
+//! (`--coverage-report`), which tools such as `genhtml` can turn into an HTML report.
+//!
+//! A line counts as hit once for every harness that reaches it, even if only partially.
+
+use crate::cbmc_property_renderer::{line_coverage, CoverageStatus};
+use crate::harness_runner::HarnessResult;
+use crate::session::KaniSession;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// The number of harnesses that reach each line, grouped by file.
+type LineHits = BTreeMap<String, BTreeMap<usize, usize>>;
+
+impl KaniSession {
+    /// Write the coverage report requested with `--coverage-report`, if any.
+    pub fn gen_coverage_report(&self, results: &[HarnessResult<'_>]) -> Result<()> {
+        let Some(report) = &self.args.coverage_report else { return Ok(()) };
+        let mut hits = LineHits::new();
+        for properties in results.iter().filter_map(|result| result.result.results.as_ref().ok()) {
+            add_line_hits(&mut hits, line_coverage(properties));
+        }
+        std::fs::write(report, format_lcov(&hits))
+            .with_context(|| format!("Failed to write coverage report `{}`", report.display()))?;
+        if self.args.prints_human_output() {
+            println!("Coverage report written to `{}`", report.display());
+        }
+        Ok(())
+    }
+}
+
+/// Add the coverage of one harness to the lines hit so far.
+fn add_line_hits(hits: &mut LineHits, coverage: BTreeMap<String, BTreeMap<usize, CoverageStatus>>) {
+    for (file, lines) in coverage {
+        let file_hits = hits.entry(file).or_default();
+        for (line, status) in lines {
+            *file_hits.entry(line).or_default() += usize::from(status != CoverageStatus::None);
+        }
+    }
+}
+
+/// Format the lines hit in the LCOV tracefile format, with one record per source file.
+fn format_lcov(hits: &LineHits) -> String {
+    let mut lcov = String::from("TN:\n");
+    for (file, lines) in hits {
+        writeln!(lcov, "SF:{file}").unwrap();
+        for (line, count) in lines {
+            writeln!(lcov, "DA:{line},{count}").unwrap();
+        }
+        writeln!(lcov, "LF:{}", lines.len()).unwrap();
+        writeln!(lcov, "LH:{}", lines.values().filter(|count| **count > 0).count()).unwrap();
+        lcov.push_str("end_of_record\n");
+    }
+    lcov
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coverage(
+        file: &str,
+        lines: &[(usize, CoverageStatus)],
+    ) -> BTreeMap<String, BTreeMap<usize, CoverageStatus>> {
+        BTreeMap::from([(file.to_string(), lines.iter().copied().collect())])
+    }
+
+    #[test]
+    fn check_lcov_aggregates_harnesses() {
+        let mut hits = LineHits::new();
+        add_line_hits(
+            &mut hits,
+            coverage(
+                "src/lib.rs",
+                &[(1, CoverageStatus::Full), (2, CoverageStatus::None), (3, CoverageStatus::None)],
+            ),
+        );
+        add_line_hits(
+            &mut hits,
+            coverage("src/lib.rs", &[(1, CoverageStatus::Full), (2, CoverageStatus::Partial)]),
+        );
+        add_line_hits(&mut hits, coverage("src/main.rs", &[(7, CoverageStatus::None)]));
+        assert_eq!(
+            format_lcov(&hits),
+            "TN:\n\
+            SF:src/lib.rs\nDA:1,2\nDA:2,1\nDA:3,0\nLF:3\nLH:2\nend_of_record\n\
+            SF:src/main.rs\nDA:7,0\nLF:1\nLH:0\nend_of_record\n"
+        );
+    }
+}
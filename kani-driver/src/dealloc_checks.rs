@@ -0,0 +1,287 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for explaining failed deallocation checks in terms of the Rust code.
+//!
+//! Double frees and invalid frees are caught by the preconditions of CBMC's `free`, which
+//! `__rust_dealloc` calls, so their location is always the same line of `kani_lib.c`. We replay
+//! the calls of the trace of such a failure to find the Rust code that deallocated the memory:
+//! the innermost function of the crate under verification (or of its dependencies) that is on
+//! the call stack. The checks are moved to the location where this function calls into the
+//! standard library, and we print the type whose drop glue deallocated the memory, the drop
+//! path, and where the memory was allocated.
+
+use crate::call_cbmc::VerificationResult;
+use crate::cbmc_output_parser::{CheckStatus, Property, SourceLocation, TraceData, TraceItem};
+use crate::heap_graph::parse_pointee;
+use crate::session::KaniSession;
+use kani_metadata::HarnessMetadata;
+use std::fmt::Write;
+
+/// The descriptions of the checks of CBMC's `free` and of Kani's `__rust_dealloc`.
+const DEALLOC_CHECKS: [&str; 7] = [
+    "double free",
+    "free argument has offset zero",
+    "free argument must be dynamic object",
+    "free argument must be NULL or valid pointer",
+    "free called for new[] object",
+    "free called for stack-allocated object",
+    "rust_dealloc must be called on an object whose allocated size matches its layout",
+];
+
+/// The prefixes of the functions that aren't part of the code under verification.
+const LIBRARY_PREFIXES: [&str; 10] = [
+    "std::",
+    "<std::",
+    "core::",
+    "<core::",
+    "alloc::",
+    "<alloc::",
+    "kani::",
+    "<kani::",
+    "__rust_",
+    "__CPROVER",
+];
+
+/// The functions of the C library that Kani's allocation functions call.
+const C_ALLOC_FUNCTIONS: [&str; 4] = ["malloc", "calloc", "realloc", "free"];
+
+/// A function on the call stack of a trace.
+#[derive(Clone, Debug)]
+struct Frame {
+    function: String,
+    /// The location of the call to the function in its caller.
+    call_site: Option<SourceLocation>,
+}
+
+/// How the memory of a failed deallocation check was deallocated.
+#[derive(Clone, Debug)]
+pub struct DeallocContext {
+    /// The location where the code under verification calls into the standard library to
+    /// deallocate the memory.
+    pub location: SourceLocation,
+    /// The type whose drop glue deallocated the memory, if the memory was dropped.
+    pub owner: Option<String>,
+    /// The functions on the call stack, from the code under verification to the deallocation.
+    pub drop_path: Vec<String>,
+    /// The location where the code under verification allocated the memory, if it's a heap
+    /// allocation.
+    pub allocation: Option<SourceLocation>,
+}
+
+pub fn is_dealloc_check(prop: &Property) -> bool {
+    DEALLOC_CHECKS.iter().any(|desc| prop.description == *desc)
+}
+
+fn is_library_function(function: &str) -> bool {
+    LIBRARY_PREFIXES.iter().any(|prefix| function.starts_with(prefix))
+        || C_ALLOC_FUNCTIONS.contains(&function)
+}
+
+/// The call stack after the last step of `trace`.
+fn call_stack(trace: &[TraceItem]) -> Vec<Frame> {
+    let mut stack = vec![];
+    for item in trace {
+        match (item.step_type.as_str(), &item.function) {
+            ("function-call", Some(function)) => stack.push(Frame {
+                function: function.display_name.clone(),
+                call_site: item.source_location.clone(),
+            }),
+            ("function-return", Some(_)) => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+    stack
+}
+
+/// The index of the innermost frame of the code under verification and the location where it
+/// calls into the standard library.
+fn user_call_site(stack: &[Frame]) -> Option<(usize, SourceLocation)> {
+    let user = stack.iter().rposition(|frame| !is_library_function(&frame.function))?;
+    let location = stack.get(user + 1)?.call_site.clone()?;
+    Some((user, location))
+}
+
+/// The type dropped by the outermost drop glue in `frames`, e.g. `std::vec::Vec<u8>` for
+/// `std::ptr::drop_in_place::<std::vec::Vec<u8>>`.
+fn dropped_type(frames: &[Frame]) -> Option<String> {
+    frames.iter().find_map(|frame| {
+        let (_, ty) = frame.function.split_once("drop_in_place::<")?;
+        Some(ty.strip_suffix('>')?.to_string())
+    })
+}
+
+/// The object that a pointer value in the trace points to, if any.
+fn pointee(item: &TraceItem) -> Option<String> {
+    let TraceData::NonBool(data) = item.value.as_ref()?.data.as_ref()? else { return None };
+    Some(parse_pointee(data)?.0)
+}
+
+/// Find how the memory of the failed deallocation check at the end of `trace` was deallocated.
+pub fn dealloc_context(trace: &[TraceItem]) -> Option<DeallocContext> {
+    let end = trace.iter().position(|item| item.step_type == "failure").unwrap_or(trace.len());
+    let trace = &trace[..end];
+    let stack = call_stack(trace);
+    let (user, location) = user_call_site(&stack)?;
+    let owner = dropped_type(&stack[user + 1..]);
+    let drop_path = stack[user..].iter().map(|frame| frame.function.clone()).collect();
+    // The pointer that is freed is the last one that was assigned, i.e., the argument of `free`.
+    let freed = trace.iter().rev().filter(|item| item.step_type == "assignment").find_map(pointee);
+    let allocation =
+        freed.filter(|object| object.starts_with("dynamic_object")).and_then(|object| {
+            let first = trace.iter().position(|item| pointee(item).as_ref() == Some(&object))?;
+            Some(user_call_site(&call_stack(&trace[..=first]))?.1)
+        });
+    Some(DeallocContext { location, owner, drop_path, allocation })
+}
+
+/// Move the failed deallocation checks to the location where the code under verification
+/// deallocates the memory, if their trace is available.
+pub fn update_dealloc_check_locations(mut properties: Vec<Property>) -> Vec<Property> {
+    for prop in &mut properties {
+        if prop.status != CheckStatus::Failure || !is_dealloc_check(prop) {
+            continue;
+        }
+        if let Some(context) = prop.trace.as_deref().and_then(dealloc_context) {
+            prop.source_location = context.location;
+        }
+    }
+    properties
+}
+
+impl KaniSession {
+    /// How the memory of the failed deallocation checks of a harness was deallocated.
+    pub fn report_dealloc_failures(
+        &self,
+        harness: &HarnessMetadata,
+        result: &VerificationResult,
+    ) -> String {
+        let mut output = String::new();
+        let Ok(properties) = &result.results else { return output };
+        for prop in properties {
+            if prop.status != CheckStatus::Failure || !is_dealloc_check(prop) {
+                continue;
+            }
+            let Some(context) = prop.trace.as_deref().and_then(dealloc_context) else { continue };
+            let what = match &context.owner {
+                Some(owner) => format!("dropping `{owner}`"),
+                None => format!("a deallocation in `{}`", context.drop_path[0]),
+            };
+            writeln!(
+                output,
+                "NOTE: The check \"{}\" of `{}` fails when {what} at {}.",
+                prop.description, harness.pretty_name, context.location
+            )
+            .unwrap();
+            if let Some(allocation) = &context.allocation {
+                writeln!(output, "  The memory was allocated at {allocation}.").unwrap();
+            }
+            writeln!(output, "  Drop path: {}", context.drop_path.join(" -> ")).unwrap();
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::{TraceFunction, TraceValue};
+
+    fn location(function: &str, line: &str) -> SourceLocation {
+        SourceLocation {
+            column: None,
+            file: Some("src/lib.rs".to_string()),
+            function: Some(function.to_string()),
+            line: Some(line.to_string()),
+        }
+    }
+
+    fn step(step_type: &str, caller: &str, line: &str, callee: Option<&str>) -> TraceItem {
+        TraceItem {
+            thread: 0,
+            step_type: step_type.to_string(),
+            hidden: false,
+            lhs: None,
+            source_location: Some(location(caller, line)),
+            value: None,
+            function: callee.map(|callee| TraceFunction {
+                display_name: callee.to_string(),
+                identifier: callee.to_string(),
+            }),
+        }
+    }
+
+    fn call(caller: &str, line: &str, callee: &str) -> TraceItem {
+        step("function-call", caller, line, Some(callee))
+    }
+
+    fn ret(caller: &str, line: &str, callee: &str) -> TraceItem {
+        step("function-return", caller, line, Some(callee))
+    }
+
+    fn assign_ptr(function: &str, data: &str) -> TraceItem {
+        let mut item = step("assignment", function, "0", None);
+        item.lhs = Some("ptr".to_string());
+        item.value = Some(TraceValue {
+            name: "pointer".to_string(),
+            binary: None,
+            data: Some(TraceData::NonBool(data.to_string())),
+            width: Some(64),
+            typ: None,
+            members: vec![],
+            elements: vec![],
+        });
+        item
+    }
+
+    #[test]
+    fn check_double_free_context() {
+        let drop_vec = "std::ptr::drop_in_place::<std::vec::Vec<u8>>";
+        let trace = vec![
+            call("__CPROVER__start", "0", "check_double_free"),
+            call("check_double_free", "4", "std::vec::Vec::<u8>::with_capacity"),
+            call("std::vec::Vec::<u8>::with_capacity", "0", "__rust_alloc"),
+            call("__rust_alloc", "0", "malloc"),
+            assign_ptr("malloc", "&dynamic_object$1"),
+            ret("__rust_alloc", "0", "malloc"),
+            ret("std::vec::Vec::<u8>::with_capacity", "0", "__rust_alloc"),
+            ret("check_double_free", "4", "std::vec::Vec::<u8>::with_capacity"),
+            call("check_double_free", "9", drop_vec),
+            call(drop_vec, "0", "alloc::alloc::dealloc"),
+            call("alloc::alloc::dealloc", "0", "__rust_dealloc"),
+            call("__rust_dealloc", "0", "free"),
+            assign_ptr("free", "&dynamic_object$1"),
+            step("failure", "__rust_dealloc", "0", None),
+        ];
+        let context = dealloc_context(&trace).unwrap();
+        assert_eq!(context.location.line.as_deref(), Some("9"));
+        assert_eq!(context.owner.as_deref(), Some("std::vec::Vec<u8>"));
+        assert_eq!(
+            context.drop_path,
+            vec!["check_double_free", drop_vec, "alloc::alloc::dealloc", "__rust_dealloc", "free"]
+        );
+        assert_eq!(context.allocation.unwrap().line.as_deref(), Some("4"));
+    }
+
+    #[test]
+    fn check_custom_dealloc_context() {
+        let drop_fn = "<Buffer as std::ops::Drop>::drop";
+        let trace = vec![
+            call("__CPROVER__start", "0", "check_stack"),
+            call("check_stack", "12", "std::ptr::drop_in_place::<Buffer>"),
+            call("std::ptr::drop_in_place::<Buffer>", "0", drop_fn),
+            call(drop_fn, "30", "std::alloc::dealloc"),
+            call("std::alloc::dealloc", "0", "__rust_dealloc"),
+            call("__rust_dealloc", "0", "free"),
+            assign_ptr("free", "(unsigned char *)&var_7"),
+            step("failure", "__rust_dealloc", "0", None),
+        ];
+        let context = dealloc_context(&trace).unwrap();
+        assert_eq!(context.location.function.as_deref(), Some(drop_fn));
+        assert_eq!(context.location.line.as_deref(), Some("30"));
+        assert_eq!(context.owner, None);
+        assert!(context.allocation.is_none());
+    }
+}
@@ -0,0 +1,279 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `cargo kani estimate`, which predicts how hard each harness is to verify without
+//! running a solver.
+//!
+//! The harnesses are compiled and instrumented exactly like for verification. Then CBMC lists the
+//! loops (`--show-loops`), the properties (`--show-properties`) and the instructions
+//! (`--show-goto-functions`) of each model, which only requires loading the model. From these we
+//! derive a rough estimate of the number of verification conditions (VCCs) that symbolic
+//! execution generates, and a difficulty class that helps to triage which harnesses are likely
+//! to be feasible.
+
+use crate::call_cbmc::resolve_unwind_value;
+use crate::project::Project;
+use crate::session::KaniSession;
+use anyhow::{bail, Context, Result};
+use comfy_table::{ContentArrangement, Table};
+use kani_metadata::{ArtifactType, HarnessMetadata};
+use serde::Deserialize;
+use std::ffi::OsString;
+use std::fmt::{self, Display};
+use std::io::Read;
+use std::path::Path;
+use std::process::Command;
+
+/// The functions of the Kani library that allocate memory on the heap.
+const ALLOCATION_FUNCTIONS: [&str; 3] = ["__rust_alloc", "__rust_alloc_zeroed", "__rust_realloc"];
+
+/// The harnesses with at most this many estimated VCCs and allocations are easy.
+const EASY_VCCS: u64 = 2_000;
+const EASY_ALLOCATIONS: usize = 10;
+/// The harnesses with more than this many estimated VCCs or allocations are hard.
+const HARD_VCCS: u64 = 50_000;
+const HARD_ALLOCATIONS: usize = 100;
+
+/// Compile the harnesses of the project and print an estimate for each of them.
+pub fn run_estimate(session: &KaniSession, project: &Project) -> Result<()> {
+    let harnesses = session.determine_targets(&project.get_all_harnesses())?;
+    let mut estimates = vec![];
+    for harness in crate::metadata::sort_harnesses_by_loc(&harnesses) {
+        let goto_file = project.get_harness_artifact(harness, ArtifactType::Goto).unwrap();
+        session.instrument_model(goto_file, goto_file, project, harness)?;
+        if let Some(top) = session.args.size_report {
+            session.print_size_report(goto_file, harness, top)?;
+        }
+        estimates.push(session.estimate_harness(goto_file, harness)?);
+    }
+    println!("{}", render(&estimates));
+    println!(
+        "No harness was verified. The estimates only take the size of the models into account."
+    );
+    Ok(())
+}
+
+/// How hard a harness is expected to be to verify.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Difficulty {
+    Easy,
+    Moderate,
+    Hard,
+    /// The harness has loops but no unwinding bound, so symbolic execution may not terminate.
+    Unbounded,
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Moderate => "moderate",
+            Difficulty::Hard => "hard",
+            Difficulty::Unbounded => "unbounded loops",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The size of the model of a harness.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct HarnessEstimate {
+    harness: String,
+    /// The number of functions with a body.
+    functions: usize,
+    /// The number of goto instructions in those functions.
+    instructions: usize,
+    loops: usize,
+    /// The call sites of the allocation functions.
+    allocations: usize,
+    properties: usize,
+    /// The unwinding bound of the harness, if any.
+    unwind: Option<u32>,
+}
+
+impl HarnessEstimate {
+    /// Symbolic execution creates a VCC per property for every time it is reached. Without
+    /// further information, we assume that every property may be reached in every iteration of
+    /// the loops.
+    fn estimated_vccs(&self) -> u64 {
+        let iterations = if self.loops > 0 { self.unwind.unwrap_or(1).max(1) } else { 1 };
+        (self.properties as u64).saturating_mul(u64::from(iterations))
+    }
+
+    fn difficulty(&self) -> Difficulty {
+        if self.loops > 0 && self.unwind.is_none() {
+            return Difficulty::Unbounded;
+        }
+        let vccs = self.estimated_vccs();
+        if vccs <= EASY_VCCS && self.allocations <= EASY_ALLOCATIONS {
+            Difficulty::Easy
+        } else if vccs > HARD_VCCS || self.allocations > HARD_ALLOCATIONS {
+            Difficulty::Hard
+        } else {
+            Difficulty::Moderate
+        }
+    }
+}
+
+/// A function as listed by `cbmc --show-goto-functions`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GotoFunction {
+    pub name: String,
+    pub is_body_available: bool,
+    /// Whether the function is part of CBMC's own library rather than the model.
+    #[serde(default)]
+    pub is_internal: bool,
+    #[serde(default)]
+    pub instructions: Vec<GotoInstruction>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GotoInstruction {
+    instruction_id: String,
+    #[serde(default)]
+    instruction: String,
+}
+
+impl GotoInstruction {
+    fn is_allocation(&self) -> bool {
+        self.instruction_id == "FUNCTION_CALL"
+            && ALLOCATION_FUNCTIONS
+                .iter()
+                .any(|name| self.instruction.contains(&format!("{name}(")))
+    }
+}
+
+impl KaniSession {
+    /// Collect the size of the instrumented model of a harness.
+    fn estimate_harness(&self, file: &Path, harness: &HarnessMetadata) -> Result<HarnessEstimate> {
+        let args = self.cbmc_flags(file, harness)?;
+        let loops = self.cbmc_listing(&args, "--show-loops", "loops")?;
+        let properties = self.cbmc_listing(&args, "--show-properties", "properties")?;
+        let functions: Vec<_> =
+            self.goto_functions(&args)?.into_iter().filter(|f| f.is_body_available).collect();
+        let instructions = functions.iter().flat_map(|f| &f.instructions);
+        Ok(HarnessEstimate {
+            harness: harness.pretty_name.clone(),
+            functions: functions.len(),
+            instructions: instructions.clone().count(),
+            loops: loops.as_array().map_or(0, Vec::len),
+            allocations: instructions.filter(|i| i.is_allocation()).count(),
+            properties: properties.as_array().map_or(0, Vec::len),
+            unwind: resolve_unwind_value(&self.args, harness),
+        })
+    }
+
+    /// The functions of the model loaded with the CBMC arguments `args`.
+    pub(crate) fn goto_functions(&self, args: &[OsString]) -> Result<Vec<GotoFunction>> {
+        let functions = self.cbmc_listing(args, "--show-goto-functions", "functions")?;
+        Ok(serde_json::from_value(functions)?)
+    }
+
+    /// Run CBMC with a `--show-*` option and return the list that it printed under `key`, which
+    /// CBMC omits if the list is empty.
+    fn cbmc_listing(
+        &self,
+        args: &[OsString],
+        option: &str,
+        key: &str,
+    ) -> Result<serde_json::Value> {
+        let mut cmd = Command::new("cbmc");
+        cmd.args(args);
+        cmd.args([option, "--json-ui"]);
+        let Some(mut process) = self.run_piped(cmd)? else { bail!("Failed to run cbmc") };
+        let mut output = String::new();
+        process.stdout.take().unwrap().read_to_string(&mut output)?;
+        let status = process.wait()?;
+        if !status.success() {
+            bail!("cbmc {option} failed with status {status}");
+        }
+        let items: Vec<serde_json::Value> = serde_json::from_str(&output)
+            .with_context(|| format!("Failed to parse the output of cbmc {option}"))?;
+        Ok(items
+            .into_iter()
+            .find_map(|mut item| item.get_mut(key).map(serde_json::Value::take))
+            .unwrap_or_else(|| serde_json::Value::Array(vec![])))
+    }
+}
+
+fn render(estimates: &[HarnessEstimate]) -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header([
+        "Harness",
+        "Functions",
+        "Instructions",
+        "Loops",
+        "Unwind",
+        "Allocations",
+        "Properties",
+        "Estimated VCCs",
+        "Difficulty",
+    ]);
+    for estimate in estimates {
+        table.add_row([
+            estimate.harness.clone(),
+            estimate.functions.to_string(),
+            estimate.instructions.to_string(),
+            estimate.loops.to_string(),
+            estimate.unwind.map_or_else(|| "-".to_string(), |unwind| unwind.to_string()),
+            estimate.allocations.to_string(),
+            estimate.properties.to_string(),
+            estimate.estimated_vccs().to_string(),
+            estimate.difficulty().to_string(),
+        ]);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn estimate(loops: usize, unwind: Option<u32>, properties: usize) -> HarnessEstimate {
+        HarnessEstimate { loops, unwind, properties, ..Default::default() }
+    }
+
+    #[test]
+    fn check_estimated_vccs() {
+        assert_eq!(estimate(0, None, 10).estimated_vccs(), 10);
+        assert_eq!(estimate(0, Some(100), 10).estimated_vccs(), 10);
+        assert_eq!(estimate(2, Some(100), 10).estimated_vccs(), 1_000);
+        assert_eq!(estimate(2, Some(0), 10).estimated_vccs(), 10);
+    }
+
+    #[test]
+    fn check_difficulty() {
+        assert_eq!(estimate(0, None, 10).difficulty(), Difficulty::Easy);
+        assert_eq!(estimate(1, None, 10).difficulty(), Difficulty::Unbounded);
+        assert_eq!(estimate(1, Some(1_000), 10).difficulty(), Difficulty::Moderate);
+        assert_eq!(estimate(1, Some(10_000), 10).difficulty(), Difficulty::Hard);
+        let allocating = HarnessEstimate { allocations: 200, ..estimate(0, None, 10) };
+        assert_eq!(allocating.difficulty(), Difficulty::Hard);
+    }
+
+    #[test]
+    fn check_allocation_calls() {
+        let functions: Vec<GotoFunction> = serde_json::from_value(serde_json::json!([
+            {
+                "name": "harness",
+                "isBodyAvailable": true,
+                "isInternal": false,
+                "instructions": [
+                    { "instructionId": "FUNCTION_CALL", "instruction": "x = __rust_alloc(4, 4)" },
+                    { "instructionId": "FUNCTION_CALL", "instruction": "y = foo(x)" },
+                    { "instructionId": "ASSIGN", "instruction": "z = __rust_alloc_zeroed" },
+                ]
+            },
+            { "name": "malloc", "isBodyAvailable": false, "isInternal": true }
+        ]))
+        .unwrap();
+        let allocations: Vec<_> =
+            functions[0].instructions.iter().map(GotoInstruction::is_allocation).collect();
+        assert_eq!(allocations, [true, false, false]);
+        assert!(!functions[1].is_body_available);
+    }
+}
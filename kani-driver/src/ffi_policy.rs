@@ -0,0 +1,79 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for the FFI policy file (`--ffi-policy`), which tells Kani how to model each foreign
+//! function that has no definition. For example:
+//!
+//! ```toml
+//! [functions]
+//! getenv = "havoc"
+//! rand = "zeroed"
+//! exit = "stub"
+//! ```
+//!
+//! The functions are identified by their link name. The policies are passed on to the compiler,
+//! which generates the body of each function accordingly.
+
+use kani_metadata::FfiPolicy;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// The content of an FFI policy file.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct FfiPolicies {
+    /// The policy of each foreign function, indexed by its link name.
+    #[serde(default)]
+    pub functions: BTreeMap<String, FfiPolicy>,
+}
+
+impl FfiPolicies {
+    /// The arguments that pass these policies on to the compiler.
+    pub fn as_compiler_flags(&self) -> impl Iterator<Item = String> + '_ {
+        self.functions
+            .iter()
+            .map(|(name, policy)| format!("--ffi-policy={name}={}", policy.as_ref()))
+    }
+}
+
+/// Read the FFI policy file at `path`. This is used to parse the `--ffi-policy` argument, so
+/// that a malformed file is reported like any other invalid argument.
+pub fn parse_ffi_policy_file(path: &str) -> Result<FfiPolicies, String> {
+    let content = std::fs::read_to_string(PathBuf::from(path))
+        .map_err(|err| format!("failed to read `{path}`: {err}"))?;
+    parse_ffi_policies(&content).map_err(|err| format!("failed to parse `{path}`: {err}"))
+}
+
+fn parse_ffi_policies(content: &str) -> Result<FfiPolicies, toml::de::Error> {
+    toml::from_str(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_parse_ffi_policies() {
+        let policies = parse_ffi_policies(
+            r#"
+            [functions]
+            getenv = "havoc"
+            rand = "zeroed"
+            exit = "stub"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            policies.as_compiler_flags().collect::<Vec<_>>(),
+            ["--ffi-policy=exit=stub", "--ffi-policy=getenv=havoc", "--ffi-policy=rand=zeroed"]
+        );
+        assert_eq!(parse_ffi_policies("").unwrap(), FfiPolicies::default());
+    }
+
+    #[test]
+    fn check_parse_invalid_ffi_policies() {
+        assert!(parse_ffi_policies("[functions]\ngetenv = \"ignore\"").is_err());
+        assert!(parse_ffi_policies("[function]\ngetenv = \"havoc\"").is_err());
+    }
+}
@@ -2,15 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use anyhow::{bail, Result};
-use kani_metadata::{ArtifactType, HarnessMetadata};
+use kani_metadata::{ArtifactType, HarnessMetadata, UnstableFeature};
 use rayon::prelude::*;
+use std::collections::HashSet;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::args::OutputFormat;
-use crate::call_cbmc::{VerificationResult, VerificationStatus};
+use crate::call_cbmc::{check_solver_available, VerificationResult, VerificationStatus};
 use crate::project::Project;
 use crate::session::KaniSession;
-use crate::util::error;
+use crate::util::{available_memory_mib, error, warning};
 
 /// A HarnessRunner is responsible for checking all proof harnesses. The data in this structure represents
 /// "background information" that the controlling driver (e.g. cargo-kani or kani) computed.
@@ -28,6 +30,8 @@ pub(crate) struct HarnessRunner<'sess, 'pr> {
 pub(crate) struct HarnessResult<'pr> {
     pub harness: &'pr HarnessMetadata,
     pub result: VerificationResult,
+    /// The fingerprint of the harness for the `--verification-cache`, if it's enabled.
+    pub fingerprint: Option<String>,
 }
 
 impl<'sess, 'pr> HarnessRunner<'sess, 'pr> {
@@ -38,20 +42,26 @@ pub(crate) fn check_all_harnesses(
         harnesses: &'pr [&HarnessMetadata],
     ) -> Result<Vec<HarnessResult<'pr>>> {
         self.check_stubbing(harnesses)?;
+        self.check_timeouts(harnesses)?;
+        self.check_solvers(harnesses)?;
 
         let sorted_harnesses = crate::metadata::sort_harnesses_by_loc(harnesses);
+        let cache = self.sess.read_verification_cache()?;
 
         let pool = {
             let mut builder = rayon::ThreadPoolBuilder::new();
-            if let Some(x) = self.sess.args.jobs() {
+            if let Some(x) = self.num_jobs() {
                 builder = builder.num_threads(x);
             }
             builder.build()?
         };
 
+        // Set once a harness fails a check of a class of `--fail-fast-on-class`.
+        let stop = AtomicBool::new(false);
         let results = pool.install(|| -> Result<Vec<HarnessResult<'pr>>> {
             sorted_harnesses
                 .par_iter()
+                .filter(|_| !stop.load(Ordering::Relaxed))
                 .map(|harness| -> Result<HarnessResult<'pr>> {
                     let harness_filename = harness.pretty_name.replace("::", "-");
                     let report_dir = self.project.outdir.join(format!("report-{harness_filename}"));
@@ -63,15 +73,98 @@ pub(crate) fn check_all_harnesses(
                         self.sess.synthesize_loop_contracts(goto_file, &goto_file, &harness)?;
                     }
 
+                    if let Some(top) = self.sess.args.size_report {
+                        self.sess.print_size_report(goto_file, harness, top)?;
+                    }
+
+                    let fingerprint = if self.sess.args.verification_cache.is_some() {
+                        Some(self.sess.harness_fingerprint(goto_file, harness)?)
+                    } else {
+                        None
+                    };
+                    let cached = fingerprint.as_ref().and_then(|fp| cache.get(harness, fp));
+                    if let Some(result) = cached {
+                        if self.sess.args.prints_human_output() {
+                            println!(
+                                "Skipping harness {}: it was verified successfully with the same \
+                                goto model and options before.",
+                                harness.pretty_name
+                            );
+                        }
+                        return Ok(HarnessResult { harness, result, fingerprint });
+                    }
+
                     let result = self.sess.check_harness(goto_file, &report_dir, harness)?;
-                    Ok(HarnessResult { harness, result })
+                    if let Some(class) = self.fail_fast_class(&result) {
+                        if !stop.swap(true, Ordering::Relaxed) && !self.sess.args.common_args.quiet
+                        {
+                            println!(
+                                "Stopping the verification since harness `{}` failed a check \
+                                of class `{class}`.",
+                                harness.pretty_name
+                            );
+                        }
+                    }
+                    Ok(HarnessResult { harness, result, fingerprint })
                 })
                 .collect::<Result<Vec<_>>>()
         })?;
 
+        if results.len() < harnesses.len() && !self.sess.args.common_args.quiet {
+            warning(&format!(
+                "{} of {} harnesses were not verified because of `--fail-fast-on-class`.",
+                harnesses.len() - results.len(),
+                harnesses.len()
+            ));
+        }
         Ok(results)
     }
 
+    /// The class of `--fail-fast-on-class` of a check that failed in `result`, if any.
+    fn fail_fast_class<'a>(&'a self, result: &VerificationResult) -> Option<&'a str> {
+        let classes = &self.sess.args.fail_fast_on_class;
+        if classes.is_empty() {
+            return None;
+        }
+        result
+            .failed_properties()
+            .into_iter()
+            .find_map(|prop| classes.iter().find(|class| **class == prop.property_class()))
+            .map(String::as_str)
+    }
+
+    /// The number of harnesses to verify in parallel, or `None` to use one job per CPU.
+    ///
+    /// With `--memory-per-job`, the number of jobs is capped so that their combined memory
+    /// doesn't exceed what is currently available.
+    fn num_jobs(&self) -> Option<usize> {
+        let jobs = self.sess.args.jobs();
+        let Some(memory_per_job) = self.sess.args.memory_per_job else { return jobs };
+        let Some(available) = available_memory_mib() else {
+            warning("Kani could not determine the available memory; ignoring `--memory-per-job`.");
+            return jobs;
+        };
+        let max_jobs = usize::try_from(available / memory_per_job).unwrap_or(usize::MAX).max(1);
+        let requested = jobs.unwrap_or_else(rayon::current_num_threads);
+        if requested <= max_jobs {
+            return jobs;
+        }
+        if !self.sess.args.common_args.quiet {
+            println!(
+                "Running {max_jobs} job(s) in parallel instead of {requested} to fit in the \
+                available memory ({available} MiB)."
+            );
+        }
+        Some(max_jobs)
+    }
+
+    /// Return an error if the solver of any harness is missing, before running any of them.
+    fn check_solvers(&self, harnesses: &[&HarnessMetadata]) -> Result<()> {
+        let solvers: HashSet<_> =
+            harnesses.iter().map(|harness| self.sess.resolve_solver(&harness.attributes)).collect();
+        solvers.into_iter().try_for_each(check_solver_available)
+    }
+
     /// Return an error if the user is trying to verify a harness with stubs without enabling the
     /// experimental feature.
     fn check_stubbing(&self, harnesses: &[&HarnessMetadata]) -> Result<()> {
@@ -79,7 +172,9 @@ fn check_stubbing(&self, harnesses: &[&HarnessMetadata]) -> Result<()> {
             let with_stubs: Vec<_> = harnesses
                 .iter()
                 .filter_map(|harness| {
-                    (!harness.attributes.stubs.is_empty()).then_some(harness.pretty_name.as_str())
+                    let attributes = &harness.attributes;
+                    (!attributes.stubs.is_empty() || !attributes.trait_impl_stubs.is_empty())
+                        .then_some(harness.pretty_name.as_str())
                 })
                 .collect();
             match with_stubs.as_slice() {
@@ -98,6 +193,29 @@ fn check_stubbing(&self, harnesses: &[&HarnessMetadata]) -> Result<()> {
         }
         Ok(())
     }
+
+    /// Return an error if a harness has a `#[kani::timeout]` attribute that can't be honored.
+    fn check_timeouts(&self, harnesses: &[&HarnessMetadata]) -> Result<()> {
+        let Some(harness) = harnesses.iter().find(|harness| harness.attributes.timeout.is_some())
+        else {
+            return Ok(());
+        };
+        if !self.sess.args.common_args.unstable_features.contains(UnstableFeature::HarnessTimeout) {
+            bail!(
+                "Use of unstable feature 'harness-timeout' in harness `{}`.\n\
+                To enable timeouts, pass option `-Z harness-timeout`",
+                harness.pretty_name
+            )
+        }
+        if self.sess.args.output_format == OutputFormat::Old || self.sess.args.visualize {
+            bail!(
+                "The timeout of harness `{}` isn't compatible with `--output-format=old` or \
+                `--visualize`",
+                harness.pretty_name
+            )
+        }
+        Ok(())
+    }
 }
 
 impl KaniSession {
@@ -108,7 +226,8 @@ pub(crate) fn check_harness(
         report_dir: &Path,
         harness: &HarnessMetadata,
     ) -> Result<VerificationResult> {
-        if !self.args.common_args.quiet {
+        // In parallel, the output of each harness is printed at once when it's done instead.
+        if self.args.prints_human_output() && !self.args.is_parallel() {
             println!("Checking harness {}...", harness.pretty_name);
         }
 
@@ -117,25 +236,74 @@ pub(crate) fn check_harness(
             // Strictly speaking, we're faking success here. This is more "no error"
             Ok(VerificationResult::mock_success())
         } else {
+            let mut search_output = String::new();
+            let mut bounded_harness = None;
+            if self.args.auto_unwind {
+                let (unwind, message) = self.discover_unwind(binary, harness);
+                search_output = format!("{message}\n");
+                let mut bounded = harness.clone();
+                // `--unwind` can't be combined with `--auto-unwind`, so the attribute is used.
+                bounded.attributes.unwind_value = Some(unwind);
+                bounded_harness = Some(bounded);
+            }
+            let harness = bounded_harness.as_ref().unwrap_or(harness);
+            if self.args.prints_human_output() && !self.args.is_parallel() {
+                print!("{search_output}");
+            }
+
             let mut result = self.with_timer(|| self.run_cbmc(binary, harness), "run_cbmc")?;
+            result.auto_unwind = bounded_harness.as_ref().and(harness.attributes.unwind_value);
 
             // When quiet, we don't want to print anything at all.
             // When output is old, we also don't have real results to print.
-            if !self.args.common_args.quiet && self.args.output_format != OutputFormat::Old {
-                println!(
-                    "{}",
-                    result.render(
+            if self.args.prints_human_output() {
+                let reports = self.harness_reports(binary, harness, &result);
+                if self.args.output_format == OutputFormat::Old {
+                    print!("{reports}");
+                } else {
+                    let output = result.render(
                         &self.args.output_format,
                         harness.attributes.should_panic,
-                        self.args.coverage
-                    )
-                );
+                        self.args.coverage,
+                    );
+                    if self.args.is_parallel() {
+                        print!(
+                            "Checking harness {}...\n{search_output}{}{output}\n{reports}",
+                            harness.pretty_name,
+                            result.render_messages(&self.args.output_format)
+                        );
+                    } else {
+                        println!("{output}");
+                        print!("{reports}");
+                    }
+                }
             }
             self.gen_and_add_concrete_playback(harness, &mut result)?;
+            self.gen_heap_graph(harness, report_dir, &result)?;
             Ok(result)
         }
     }
 
+    /// The reports that are printed after the result of a harness, as a single block so that
+    /// they aren't interleaved with the output of other harnesses.
+    fn harness_reports(
+        &self,
+        binary: &Path,
+        harness: &HarnessMetadata,
+        result: &VerificationResult,
+    ) -> String {
+        [
+            self.suggest_assumptions(harness, result),
+            self.report_hash_order(harness, result),
+            self.report_dealloc_failures(harness, result),
+            self.report_stub_usage(harness),
+            self.report_havocked_asm(harness),
+            self.report_concrete_inputs(harness),
+            self.suggest_unwind(binary, harness, result),
+        ]
+        .concat()
+    }
+
     /// Concludes a session by printing a summary report and exiting the process with an
     /// error code (if applicable).
     ///
@@ -144,10 +312,14 @@ pub(crate) fn check_harness(
     pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result<()> {
         let (successes, failures): (Vec<_>, Vec<_>) =
             results.iter().partition(|r| r.result.status == VerificationStatus::Success);
+        // Harnesses that timed out are not verified, so they also make Kani fail.
+        let (timeouts, failures): (Vec<_>, Vec<_>) =
+            failures.into_iter().partition(|r| r.result.status == VerificationStatus::Timeout);
 
         let succeeding = successes.len();
         let failing = failures.len();
-        let total = succeeding + failing;
+        let timing_out = timeouts.len();
+        let total = succeeding + failing + timing_out;
 
         if self.args.concrete_playback.is_some()
             && !self.args.common_args.quiet
@@ -158,21 +330,40 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
             )
         }
 
-        // We currently omit a summary if there was just 1 harness
-        if !self.args.common_args.quiet && !self.args.visualize {
-            if failing > 0 {
+        self.write_sarif_report(results)?;
+        if self.args.output_format == OutputFormat::Json {
+            self.write_json_report(results)?;
+        } else if !self.args.common_args.quiet && !self.args.visualize {
+            // We currently omit a summary if there was just 1 harness
+            if failing + timing_out > 0 {
                 println!("Summary:");
             }
             for failure in failures.iter() {
                 println!("Verification failed for - {}", failure.harness.pretty_name);
             }
+            for timeout in timeouts.iter() {
+                println!("Verification timed out for - {}", timeout.harness.pretty_name);
+            }
 
             if total > 0 {
+                let timeout_count =
+                    if timing_out > 0 { format!("{timing_out} timeouts, ") } else { String::new() };
+                let cached = successes.iter().filter(|r| r.result.cached).count();
+                let cached_count = if cached > 0 {
+                    format!(" ({cached} from the verification cache)")
+                } else {
+                    String::new()
+                };
                 println!(
-                    "Complete - {succeeding} successfully verified harnesses, {failing} failures, {total} total."
+                    "Complete - {succeeding} successfully verified harnesses{cached_count}, {failing} failures, {timeout_count}{total} total."
                 );
             } else {
                 match (self.args.harnesses.as_slice(), &self.args.function) {
+                    _ if !self.args.harness_patterns.is_empty()
+                        || !self.args.excluded_harnesses.is_empty() =>
+                    {
+                        bail!("no harnesses matched the harness selection")
+                    }
                     ([], None) =>
                     // TODO: This could use a better message, possibly with links to Kani documentation.
                     // New users may encounter this and could use a pointer to how to write proof harnesses.
@@ -196,7 +387,16 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
             }
         }
 
-        if failing > 0 {
+        if self.args.check_against_baseline {
+            // Known failures are fine, only the regressions fail the run.
+            if self.check_against_baseline(results)? {
+                drop(self);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+
+        if failing + timing_out > 0 {
             // Failure exit code without additional error message
             drop(self);
             std::process::exit(1);
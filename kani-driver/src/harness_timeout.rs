@@ -0,0 +1,210 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for verifying a harness with a timeout (`--harness-timeout`, `--total-timeout` or
+//! `#[kani::timeout]`).
+//!
+//! The harness is verified by a single CBMC process, like without a timeout: CBMC runs symbolic
+//! execution once and then checks all the properties with incremental solving. Once the timeout
+//! expires, the process is killed. CBMC only prints the status of the properties once all of them
+//! have been checked, so the properties of a harness that was stopped are listed with
+//! `cbmc --show-properties`, which doesn't run symbolic execution, and reported as `UNDETERMINED`.
+
+use crate::call_cbmc::{
+    verification_outcome_from_properties, FailedProperties, VerificationResult, VerificationStatus,
+};
+use crate::cbmc_output_parser::{
+    extract_results, parse_cbmc_output, process_status, CheckStatus, ParserItem, Property,
+    PropertyId, SourceLocation, VerificationOutput,
+};
+use crate::cbmc_property_renderer::{kani_cbmc_output_filter, postprocess_result};
+use crate::session::KaniSession;
+use anyhow::{bail, Context, Result};
+use kani_metadata::HarnessMetadata;
+use serde::Deserialize;
+use std::ffi::OsString;
+use std::io::Read;
+use std::process::Command;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A property as listed by `cbmc --show-properties`.
+#[derive(Debug, Deserialize)]
+struct ListedProperty {
+    name: String,
+    description: String,
+    #[serde(rename = "sourceLocation")]
+    source_location: SourceLocation,
+}
+
+impl ListedProperty {
+    /// A property that couldn't be checked before the timeout.
+    fn undetermined(self) -> Result<Property> {
+        Ok(Property {
+            description: self.description,
+            property_id: PropertyId::deserialize(serde_json::Value::String(self.name))?,
+            source_location: self.source_location,
+            status: CheckStatus::Undetermined,
+            reach: None,
+            trace: None,
+        })
+    }
+}
+
+impl KaniSession {
+    /// The time that verifying `harness` may take, if limited: the `#[kani::timeout]` of the
+    /// harness, or else `--harness-timeout`, but never beyond the budget of `--total-timeout`.
+    pub fn harness_timeout(&self, harness: &HarnessMetadata) -> Option<Duration> {
+        let timeout =
+            harness.attributes.timeout.or(self.args.harness_timeout).map(Duration::from_secs);
+        let remaining =
+            self.deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        match (timeout, remaining) {
+            (Some(timeout), Some(remaining)) => Some(timeout.min(remaining)),
+            (timeout, remaining) => timeout.or(remaining),
+        }
+    }
+
+    /// Verify a harness like [KaniSession::run_cbmc], but stop CBMC after `timeout` and report
+    /// the properties of the harness as undetermined if they weren't checked by then.
+    pub fn run_cbmc_with_timeout(
+        &self,
+        args: Vec<OsString>,
+        harness: &HarnessMetadata,
+        timeout: Duration,
+    ) -> Result<VerificationResult> {
+        let start_time = Instant::now();
+        // Don't start CBMC at all if there's no time left, e.g. once `--total-timeout` ran out.
+        let messages = if timeout.is_zero() {
+            vec![]
+        } else {
+            let filter = |item| {
+                kani_cbmc_output_filter(
+                    item,
+                    self.args.extra_pointer_checks,
+                    // Messages of parallel jobs are printed with their results instead.
+                    self.args.common_args.quiet || self.args.is_parallel(),
+                    &self.args.output_format,
+                )
+            };
+            let mut cmd = Command::new("cbmc");
+            cmd.args(&args).arg("--json-ui");
+            let (expired, output) = self.run_cbmc_until(cmd, start_time + timeout, filter)?;
+            let has_results =
+                output.processed_items.iter().any(|item| matches!(item, ParserItem::Result { .. }));
+            if has_results || !expired {
+                return Ok(VerificationResult::from(output, &harness.attributes, start_time));
+            }
+            extract_results(output.processed_items).0
+        };
+
+        let properties = self
+            .list_properties(&args)?
+            .into_iter()
+            .map(ListedProperty::undetermined)
+            .collect::<Result<Vec<_>>>()?;
+        let results = postprocess_result(properties, self.args.extra_pointer_checks);
+        let (status, failed_properties) =
+            verification_outcome_from_properties(&results, &harness.attributes);
+        Ok(VerificationResult {
+            status: timed_out_status(status, failed_properties),
+            failed_properties,
+            messages: Some(messages),
+            results: Ok(results),
+            runtime: start_time.elapsed(),
+            generated_concrete_test: false,
+            timeout: Some(timeout),
+            auto_unwind: None,
+            cached: false,
+        })
+    }
+
+    /// List the properties that CBMC would check with the given arguments.
+    fn list_properties(&self, args: &[OsString]) -> Result<Vec<ListedProperty>> {
+        let mut cmd = Command::new("cbmc");
+        cmd.args(args);
+        cmd.args(["--show-properties", "--json-ui"]);
+        let Some(mut process) = self.run_piped(cmd)? else { bail!("Failed to run cbmc") };
+        let mut output = String::new();
+        process.stdout.take().unwrap().read_to_string(&mut output)?;
+        let status = process.wait()?;
+        if !status.success() {
+            bail!("cbmc failed to list the properties of the harness with status {status}");
+        }
+        let items: Vec<serde_json::Value> =
+            serde_json::from_str(&output).context("Failed to parse the properties from cbmc")?;
+        let Some(properties) = items
+            .into_iter()
+            .find_map(|mut item| item.get_mut("properties").map(serde_json::Value::take))
+        else {
+            bail!("cbmc didn't list the properties of the harness")
+        };
+        Ok(serde_json::from_value(properties)?)
+    }
+
+    /// Run a CBMC command with `--json-ui`, killing it if it's still running at `deadline`, and
+    /// filter its output with `eager_filter`. Returns whether it was killed, and what it printed
+    /// until then.
+    pub(crate) fn run_cbmc_until(
+        &self,
+        cmd: Command,
+        deadline: Instant,
+        eager_filter: impl FnMut(ParserItem) -> Option<ParserItem>,
+    ) -> Result<(bool, VerificationOutput)> {
+        let Some(mut process) = self.run_piped(cmd)? else { bail!("Failed to run cbmc") };
+        let mut stdout = process.stdout.take().unwrap();
+
+        let (finished, wait_finished) = mpsc::channel::<()>();
+        let watchdog = thread::spawn(move || {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let expired = wait_finished.recv_timeout(remaining) == Err(RecvTimeoutError::Timeout);
+            if expired {
+                // The process may have exited in the meantime, in which case there's nothing
+                // to kill.
+                let _ = process.kill();
+            }
+            process.wait().map(|status| (expired, status))
+        });
+        let processed_items = parse_cbmc_output(&mut stdout, eager_filter);
+        // The watchdog may have already given up waiting, in which case nobody is listening.
+        let _ = finished.send(());
+        let (expired, status) = watchdog.join().unwrap()?;
+        Ok((
+            expired,
+            VerificationOutput { process_status: process_status(status), processed_items },
+        ))
+    }
+}
+
+/// The status of a harness that was stopped before all of its properties were checked.
+///
+/// Properties that weren't checked may still fail (or, for `should_panic`, still panic), so the
+/// harness is neither verified nor refuted unless a property already failed. A panic that
+/// `should_panic` doesn't expect is a failure whatever the other properties do.
+fn timed_out_status(
+    status: VerificationStatus,
+    failed_properties: FailedProperties,
+) -> VerificationStatus {
+    match failed_properties {
+        FailedProperties::None => VerificationStatus::Timeout,
+        _ => status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_timed_out_status() {
+        use FailedProperties::*;
+        use VerificationStatus::*;
+        assert_eq!(timed_out_status(Success, None), Timeout);
+        // A `should_panic` harness whose panic wasn't reached yet.
+        assert_eq!(timed_out_status(Failure, None), Timeout);
+        assert_eq!(timed_out_status(Failure, Other), Failure);
+        assert_eq!(timed_out_status(Failure, UnexpectedPanics), Failure);
+        assert_eq!(timed_out_status(Success, PanicsOnly), Success);
+    }
+}
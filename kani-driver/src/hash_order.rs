@@ -0,0 +1,116 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for reporting failures that may depend on the iteration order of hash collections.
+//!
+//! With `--nondet-hash-order`, the compiler XORs the hashes of `HashMap` and `HashSet` with a
+//! nondeterministic seed, which the Kani library picks the first time a harness hashes a value.
+//! A seed of zero visits the elements in the order of the fixed hashes, so a counterexample with
+//! any other seed relies on a permuted order. We read the seed from the trace of the first
+//! failure and tell the user about it.
+
+use crate::call_cbmc::VerificationResult;
+use crate::cbmc_output_parser::{CheckStatus, Property, TraceData, TraceItem};
+use crate::session::KaniSession;
+use kani_metadata::HarnessMetadata;
+
+/// The function of the Kani library that picks the seed of the hash order.
+const SEED_FUNCTION: &str = "kani::models::hash_order::hash_order_seed";
+
+impl KaniSession {
+    /// A note if the first failure of a harness (if any) permutes the iteration order of hash
+    /// collections.
+    pub fn report_hash_order(
+        &self,
+        harness: &HarnessMetadata,
+        result: &VerificationResult,
+    ) -> String {
+        if !self.args.nondet_hash_order {
+            return String::new();
+        }
+        let Ok(properties) = &result.results else { return String::new() };
+        let seed = first_failure(properties)
+            .and_then(|failure| hash_order_seed(failure.trace.as_deref().unwrap_or_default()));
+        match seed {
+            Some(seed) if seed != 0 => format!(
+                "NOTE: The failure of `{}` may depend on the iteration order of a `HashMap` or \
+                `HashSet`, since its counterexample permutes their elements with the hash order \
+                seed `{seed:#x}`. If the result must not depend on this order, iterate in a \
+                deterministic order, e.g., by sorting the elements or with a `BTreeMap`.\n",
+                harness.pretty_name
+            ),
+            _ => String::new(),
+        }
+    }
+}
+
+fn first_failure(properties: &[Property]) -> Option<&Property> {
+    properties.iter().find(|prop| prop.status == CheckStatus::Failure && !prop.is_cover_property())
+}
+
+/// The seed of the hash order in a trace, i.e., the first value returned by [SEED_FUNCTION].
+/// Traces of harnesses that don't hash any value with the default hasher don't have one.
+fn hash_order_seed(trace: &[TraceItem]) -> Option<u64> {
+    trace.iter().find_map(|item| {
+        let (Some(lhs), Some(location)) = (&item.lhs, &item.source_location) else { return None };
+        if item.step_type != "assignment"
+            || !lhs.starts_with("goto_symex$$return_value")
+            || location.function.as_deref() != Some(SEED_FUNCTION)
+        {
+            return None;
+        }
+        let TraceData::NonBool(data) = item.value.as_ref()?.data.as_ref()? else { return None };
+        // CBMC may add a C suffix to integers, e.g.: `10ul`.
+        data.trim().trim_end_matches(|c: char| c.is_ascii_alphabetic()).parse().ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::{SourceLocation, TraceValue};
+
+    fn assign(function: &str, lhs: &str, data: &str) -> TraceItem {
+        TraceItem {
+            thread: 0,
+            step_type: "assignment".to_string(),
+            hidden: false,
+            lhs: Some(lhs.to_string()),
+            source_location: Some(SourceLocation {
+                column: None,
+                file: None,
+                function: Some(function.to_string()),
+                line: None,
+            }),
+            value: Some(TraceValue {
+                name: "integer".to_string(),
+                binary: None,
+                data: Some(TraceData::NonBool(data.to_string())),
+                width: Some(64),
+                typ: None,
+                members: vec![],
+                elements: vec![],
+            }),
+            function: None,
+        }
+    }
+
+    #[test]
+    fn check_hash_order_seed() {
+        let trace = vec![
+            assign("kani::any_raw_internal::<u64>", "goto_symex$$return_value", "7ul"),
+            assign(SEED_FUNCTION, "goto_symex$$return_value$$hash_order_seed", "7ul"),
+            assign(SEED_FUNCTION, "goto_symex$$return_value$$hash_order_seed", "3ul"),
+        ];
+        assert_eq!(hash_order_seed(&trace), Some(7));
+    }
+
+    #[test]
+    fn check_no_hash_order_seed() {
+        let trace = vec![
+            assign("kani::any_raw_internal::<u64>", "goto_symex$$return_value", "7ul"),
+            assign("harness", "x", "7ul"),
+        ];
+        assert_eq!(hash_order_seed(&trace), None);
+    }
+}
@@ -0,0 +1,333 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for extracting the shape of the memory at the failure point of a counterexample,
+//! and rendering it as a [DOT](https://graphviz.org/doc/info/lang.html) graph.
+//!
+//! The graph is built by replaying every pointer assignment in the trace of the first failing
+//! property, including the pointers inside of struct and array values that are assigned at once.
+//! The last value written to each pointer field is kept, so the resulting graph
+//! reflects the memory state right before the failure. Objects that are pointed to from more
+//! than one location are highlighted as aliased, and edges that are part of a cycle are
+//! colored in red.
+
+use crate::call_cbmc::{VerificationResult, VerificationStatus};
+use crate::cbmc_output_parser::{CheckStatus, Property, TraceItem, TraceValue};
+use crate::session::KaniSession;
+use anyhow::{Context, Result};
+use kani_metadata::HarnessMetadata;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::Path;
+
+impl KaniSession {
+    /// Generate the heap graph for the first failure of a failing harness inside `report_dir`.
+    pub fn gen_heap_graph(
+        &self,
+        harness: &HarnessMetadata,
+        report_dir: &Path,
+        verification_result: &VerificationResult,
+    ) -> Result<()> {
+        if !self.args.heap_graph || verification_result.status != VerificationStatus::Failure {
+            return Ok(());
+        }
+        let Ok(properties) = &verification_result.results else { return Ok(()) };
+        let Some(graph) = HeapGraph::from_failure(properties) else {
+            // E.g., a `should_panic` harness that didn't panic.
+            if self.args.prints_human_output() {
+                println!(
+                    "WARNING: Kani could not produce a heap graph for `{}` because there \
+                    were no failing checks.",
+                    harness.pretty_name
+                );
+            }
+            return Ok(());
+        };
+
+        std::fs::create_dir_all(report_dir)?;
+        let path = report_dir.join("heap-graph.dot");
+        std::fs::write(&path, graph.to_dot())
+            .with_context(|| format!("Failed to write heap graph to `{}`", path.display()))?;
+        if self.args.prints_human_output() {
+            println!("Heap graph for `{}` written to: {}", harness.pretty_name, path.display());
+        }
+        Ok(())
+    }
+}
+
+/// The points-to graph between memory objects.
+#[derive(Debug, Default)]
+struct HeapGraph {
+    /// Maps an object and the path to one of its pointer fields (e.g. `.next`) to the object
+    /// currently pointed to and the offset inside of it.
+    edges: BTreeMap<(String, String), (String, Option<String>)>,
+}
+
+impl HeapGraph {
+    /// Build the graph from the trace of the first failing (non-cover) property.
+    fn from_failure(properties: &[Property]) -> Option<HeapGraph> {
+        let failure = properties
+            .iter()
+            .find(|prop| prop.status == CheckStatus::Failure && !prop.is_cover_property())?;
+        Some(Self::from_trace(failure.trace.as_deref().unwrap_or_default()))
+    }
+
+    fn from_trace(trace: &[TraceItem]) -> HeapGraph {
+        let mut graph = HeapGraph::default();
+        for item in trace {
+            let (Some(lhs), Some(value)) = (&item.lhs, &item.value) else { continue };
+            if item.step_type != "assignment" || is_internal(lhs) {
+                continue;
+            }
+            let (object, field) = split_object(lhs);
+            graph.assign(object, field.to_string(), value);
+        }
+        graph
+    }
+
+    /// Replay the assignment of `value` to the field `field` of `object`. The pointers inside of
+    /// a struct or an array are assigned to the corresponding fields.
+    fn assign(&mut self, object: &str, field: String, value: &TraceValue) {
+        match value.name.as_str() {
+            "pointer" => {
+                let key = (object.to_string(), field);
+                match value.data.as_ref().and_then(|data| parse_pointee(&data.to_string())) {
+                    Some((target, offset)) if !is_internal(&target) => {
+                        self.edges.insert(key, (target, offset));
+                    }
+                    // Null, invalid or internal pointers: the field no longer points to any
+                    // object.
+                    _ => {
+                        self.edges.remove(&key);
+                    }
+                }
+            }
+            "struct" | "union" => {
+                for member in &value.members {
+                    self.assign(object, format!("{field}.{}", member.name), &member.value);
+                }
+            }
+            "array" => {
+                for element in &value.elements {
+                    self.assign(object, format!("{field}[{}]", element.index), &element.value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn nodes(&self) -> BTreeSet<&str> {
+        self.edges
+            .iter()
+            .flat_map(|((source, _), (target, _))| [source.as_str(), target.as_str()])
+            .collect()
+    }
+
+    /// Objects that are pointed to by more than one pointer.
+    fn aliased(&self) -> BTreeSet<&str> {
+        let mut counts = BTreeMap::<&str, usize>::new();
+        for (target, _) in self.edges.values() {
+            *counts.entry(target).or_default() += 1;
+        }
+        counts.into_iter().filter_map(|(target, count)| (count > 1).then_some(target)).collect()
+    }
+
+    /// Whether there is a path from `from` to `to`.
+    fn reaches(&self, from: &str, to: &str) -> bool {
+        let mut visited = BTreeSet::new();
+        let mut pending = vec![from];
+        while let Some(node) = pending.pop() {
+            if node == to {
+                return true;
+            }
+            if visited.insert(node) {
+                pending.extend(
+                    self.edges
+                        .iter()
+                        .filter(|((source, _), _)| source == node)
+                        .map(|(_, (target, _))| target.as_str()),
+                );
+            }
+        }
+        false
+    }
+
+    fn to_dot(&self) -> String {
+        let aliased = self.aliased();
+        let mut dot = String::from("digraph heap {\n    node [shape=box];\n");
+        for node in self.nodes() {
+            let mut attrs = vec![format!("label=\"{}\"", escape(node))];
+            if is_heap_object(node) {
+                attrs.push("style=rounded".to_string());
+            }
+            if aliased.contains(node) {
+                attrs.push("color=orange".to_string());
+                attrs.push("xlabel=\"aliased\"".to_string());
+            }
+            writeln!(dot, "    \"{}\" [{}];", escape(node), attrs.join(", ")).unwrap();
+        }
+        for ((source, field), (target, offset)) in &self.edges {
+            let label = match offset {
+                Some(offset) => format!("{field} (+{offset})"),
+                None => field.clone(),
+            };
+            let mut attrs = vec![format!("label=\"{}\"", escape(&label))];
+            if self.reaches(target, source) {
+                attrs.push("color=red".to_string());
+            }
+            writeln!(
+                dot,
+                "    \"{}\" -> \"{}\" [{}];",
+                escape(source),
+                escape(target),
+                attrs.join(", ")
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Split an assignment left-hand side such as `node.next[0]` into the object it belongs to
+/// (`node`) and the path to the field being assigned (`.next[0]`).
+fn split_object(lhs: &str) -> (&str, &str) {
+    lhs.split_at(lhs.find(['.', '[']).unwrap_or(lhs.len()))
+}
+
+/// Parse the object pointed to by a pointer value in the trace, e.g.: `&dynamic_object$1`,
+/// `&var_3.data` or `&dynamic_object$2[0] + 8`. Return `None` for null or invalid pointers.
+pub(crate) fn parse_pointee(data: &str) -> Option<(String, Option<String>)> {
+    let data = data.trim();
+    let (address, offset) = match data.split_once(" + ") {
+        Some((address, offset)) => (address.trim(), Some(offset.trim().to_string())),
+        None => (data, None),
+    };
+    // Pointers can be printed with a cast, e.g. `(char *)&var_1`.
+    let address = address.rsplit_once(')').map_or(address, |(_, address)| address);
+    let object = address.strip_prefix('&')?;
+    let (object, _) = split_object(object);
+    (!object.is_empty()).then(|| (object.to_string(), offset))
+}
+
+/// Whether this is a compiler or CBMC generated symbol that should not be displayed.
+fn is_internal(name: &str) -> bool {
+    name.starts_with("__CPROVER") || name.contains("$$")
+}
+
+fn is_heap_object(name: &str) -> bool {
+    name.starts_with("dynamic_object")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::{TraceData, TraceValue};
+
+    fn assign(lhs: &str, data: &str) -> TraceItem {
+        TraceItem {
+            thread: 0,
+            step_type: "assignment".to_string(),
+            hidden: false,
+            lhs: Some(lhs.to_string()),
+            source_location: None,
+            value: Some(TraceValue {
+                name: "pointer".to_string(),
+                binary: None,
+                data: Some(TraceData::NonBool(data.to_string())),
+                width: Some(64),
+                typ: None,
+                members: vec![],
+                elements: vec![],
+            }),
+            function: None,
+        }
+    }
+
+    #[test]
+    fn check_parse_pointee() {
+        assert_eq!(parse_pointee("&var_1"), Some(("var_1".to_string(), None)));
+        assert_eq!(
+            parse_pointee("&dynamic_object$2.data"),
+            Some(("dynamic_object$2".into(), None))
+        );
+        assert_eq!(
+            parse_pointee("(char *)&dynamic_object$2[0] + 8"),
+            Some(("dynamic_object$2".to_string(), Some("8".to_string())))
+        );
+        assert_eq!(parse_pointee("NULL"), None);
+        assert_eq!(parse_pointee("INVALID-128"), None);
+    }
+
+    #[test]
+    fn check_last_assignment_wins() {
+        let graph = HeapGraph::from_trace(&[
+            assign("head", "&dynamic_object$1"),
+            assign("dynamic_object$1.next", "&dynamic_object$2"),
+            assign("dynamic_object$1.next", "NULL"),
+            assign("head", "&dynamic_object$2"),
+        ]);
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.nodes(), BTreeSet::from(["head", "dynamic_object$2"]));
+    }
+
+    #[test]
+    fn check_struct_assignment() {
+        // The value of `node = Node { value: 1, next: &dynamic_object$1 }` in a CBMC trace.
+        let item: TraceItem = serde_json::from_str(
+            r#"{
+                "hidden": false,
+                "lhs": "node",
+                "stepType": "assignment",
+                "thread": 0,
+                "value": {
+                    "members": [
+                        {
+                            "name": "value",
+                            "value": { "binary": "00000001", "data": "1", "name": "integer", "type": "unsigned char", "width": 8 }
+                        },
+                        {
+                            "name": "next",
+                            "value": { "data": "&dynamic_object$1", "name": "pointer", "type": "struct Node *" }
+                        }
+                    ],
+                    "name": "struct"
+                }
+            }"#,
+        )
+        .unwrap();
+        let graph = HeapGraph::from_trace(&[item]);
+        assert_eq!(
+            graph.edges,
+            BTreeMap::from([(
+                ("node".to_string(), ".next".to_string()),
+                ("dynamic_object$1".to_string(), None)
+            )])
+        );
+    }
+
+    #[test]
+    fn check_cycles_and_aliases() {
+        let graph = HeapGraph::from_trace(&[
+            assign("head", "&dynamic_object$1"),
+            assign("tail", "&dynamic_object$2"),
+            assign("dynamic_object$1.next", "&dynamic_object$2"),
+            assign("dynamic_object$2.next", "&dynamic_object$1"),
+            assign("goto_symex$$return_value", "&dynamic_object$1"),
+        ]);
+        assert_eq!(graph.aliased(), BTreeSet::from(["dynamic_object$1", "dynamic_object$2"]));
+        assert!(graph.reaches("dynamic_object$1", "dynamic_object$1"));
+        assert!(!graph.reaches("dynamic_object$1", "head"));
+
+        let dot = graph.to_dot();
+        assert!(dot.contains(
+            "\"dynamic_object$1\" -> \"dynamic_object$2\" [label=\".next\", color=red];"
+        ));
+        assert!(dot.contains("\"head\" -> \"dynamic_object$1\" [label=\"\"];"));
+        assert!(!dot.contains("goto_symex"));
+    }
+}
@@ -0,0 +1,275 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for the machine-readable verification report, which is printed (or written to
+//! `--results-file`) with `--output-format=json`, and which is also what `cargo kani server`
+//! replies to `verify` requests.
+//!
+//! The report of each harness has the following format:
+//!
+//! ```json
+//! {
+//!   "harness": "check_foo",
+//!   "file": "src/lib.rs",
+//!   "status": "failure",
+//!   "runtime": 1.25,
+//!   "solver_time": 0.5,
+//!   "timeout": null,
+//!   "unwind": { "bound": 10, "failed_unwinding_assertions": ["foo.unwind.0"] },
+//!   "failed_properties": [{ "name": "...", "description": "...", "status": "FAILURE", "location": {...} }],
+//!   "checks": { "no_overflow": "SUCCESS" },
+//!   "properties": [...],
+//!   "assumptions": {
+//!     "assumes": ["my_crate::check_foo at src/lib.rs:10:5"],
+//!     "stubs": [{ "original": "...", "replacement": "...", "call_sites": ["..."] }],
+//!     "foreign_functions": { "getenv": "havoc" },
+//!     "havocked_asm": [],
+//!     "disabled_checks": ["overflow"]
+//!   }
+//! }
+//! ```
+//!
+//! `status` is one of `success`, `failure` or `timeout`, the latter when the harness was stopped
+//! by a timeout before finding any failure.
+//!
+//! `checks` has the status of each check created with `kani::check!` by its name, and the
+//! properties of these checks also have a `check` field with the name.
+//!
+//! `solver_time` is the time that CBMC spent in the decision procedure, if CBMC reported it.
+//!
+//! `assumptions` is the ledger of what the result of the harness is conditioned on, besides its
+//! code: the calls to `kani::assume` outside of the Kani library, the stubs that replaced at
+//! least one call, the foreign functions modeled by `--ffi-policy`, the inline assembly replaced
+//! by `--ignore-asm`, and the default checks that were turned off.
+
+use crate::args::VerificationArgs;
+use crate::call_cbmc::{resolve_unwind_value, VerificationResult, VerificationStatus};
+use crate::cbmc_output_parser::{
+    named_check_results, CheckStatus, ParserItem, Property, SourceLocation,
+};
+use crate::harness_runner::HarnessResult;
+use crate::session::KaniSession;
+use crate::unwind_suggestions::is_failed_unwinding;
+use anyhow::{Context, Result};
+use kani_metadata::HarnessMetadata;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// The outcome of a single property.
+#[derive(Debug, Serialize)]
+struct PropertyReport<'a> {
+    name: String,
+    description: &'a str,
+    class: String,
+    status: CheckStatus,
+    location: &'a SourceLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    check: Option<&'a str>,
+}
+
+impl<'a> From<&'a Property> for PropertyReport<'a> {
+    fn from(prop: &'a Property) -> Self {
+        PropertyReport {
+            name: prop.property_name(),
+            description: &prop.description,
+            class: prop.property_class(),
+            status: prop.status,
+            location: &prop.source_location,
+            check: prop.check_name(),
+        }
+    }
+}
+
+impl KaniSession {
+    /// Print the report of all harnesses, or write it to `--results-file`.
+    pub(crate) fn write_json_report(&self, results: &[HarnessResult<'_>]) -> Result<()> {
+        let harnesses: Vec<Value> = results
+            .iter()
+            .map(|res| harness_report(&self.args, res.harness, &res.result))
+            .collect();
+        let count = |status| results.iter().filter(|res| res.result.status == status).count();
+        let failures = count(VerificationStatus::Failure);
+        let timeouts = count(VerificationStatus::Timeout);
+        let report = json!({
+            "kani_version": env!("CARGO_PKG_VERSION"),
+            "harnesses": harnesses,
+            "summary": {
+                "total": results.len(),
+                "successes": results.len() - failures - timeouts,
+                "failures": failures,
+                "timeouts": timeouts,
+            },
+        });
+        let output = serde_json::to_string_pretty(&report)?;
+        match &self.args.results_file {
+            Some(path) => std::fs::write(path, output)
+                .with_context(|| format!("Failed to write results to `{}`", path.display())),
+            None => {
+                println!("{output}");
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Build the structured report for the verification result of a harness.
+pub fn harness_report(
+    args: &VerificationArgs,
+    harness: &HarnessMetadata,
+    result: &VerificationResult,
+) -> Value {
+    let status = match result.status {
+        VerificationStatus::Success => "success",
+        VerificationStatus::Failure => "failure",
+        VerificationStatus::Timeout => "timeout",
+    };
+    let properties = result.results.as_deref().unwrap_or_default();
+    let failed: Vec<PropertyReport> = properties
+        .iter()
+        .filter(|prop| prop.status == CheckStatus::Failure)
+        .map(PropertyReport::from)
+        .collect();
+    let failed_unwinding: Vec<String> = properties
+        .iter()
+        .filter(|prop| is_failed_unwinding(prop))
+        .map(Property::property_name)
+        .collect();
+    let checks = named_check_results(properties);
+    let properties: Vec<PropertyReport> = properties.iter().map(PropertyReport::from).collect();
+    json!({
+        "harness": harness.pretty_name,
+        "file": harness.original_file,
+        "status": status,
+        "runtime": result.runtime.as_secs_f64(),
+        "cached": result.cached,
+        "solver_time": solver_time(result),
+        "timeout": result.timeout.map(|timeout| timeout.as_secs()),
+        "unwind": {
+            "bound": result.auto_unwind.or_else(|| resolve_unwind_value(args, harness)),
+            "failed_unwinding_assertions": failed_unwinding,
+        },
+        "failed_properties": failed,
+        "checks": checks,
+        "properties": properties,
+        "assumptions": assumptions_ledger(args, harness),
+    })
+}
+
+/// The assumptions that the result of a harness is conditioned on.
+fn assumptions_ledger(args: &VerificationArgs, harness: &HarnessMetadata) -> Value {
+    let stubs: Vec<_> =
+        harness.stub_usage.iter().filter(|usage| !usage.call_sites.is_empty()).collect();
+    let checks = &args.checks;
+    let disabled_checks: Vec<_> = [
+        ("memory_safety", checks.memory_safety_on()),
+        ("overflow", checks.overflow_on()),
+        ("undefined_function", checks.undefined_function_on()),
+        ("unwinding", checks.unwinding_on()),
+    ]
+    .into_iter()
+    .filter_map(|(check, on)| (!on).then_some(check))
+    .collect();
+    json!({
+        "assumes": harness.assumptions.assumes,
+        "stubs": stubs,
+        "foreign_functions": harness.assumptions.foreign_functions,
+        "havocked_asm": harness.havocked_asm,
+        "disabled_checks": disabled_checks,
+    })
+}
+
+/// The time that CBMC reported for the decision procedure, in seconds.
+fn solver_time(result: &VerificationResult) -> Option<f64> {
+    result.messages.as_deref().unwrap_or_default().iter().find_map(|item| match item {
+        ParserItem::Message { message_text, .. } => message_text
+            .strip_prefix("Runtime decision procedure: ")
+            .and_then(|time| time.trim_end_matches('s').parse().ok()),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::StandaloneArgs;
+    use crate::metadata::mock_proof_harness;
+    use clap::Parser;
+    use kani_metadata::{FfiPolicy, StubUsage};
+    use std::time::Duration;
+
+    fn message(text: &str) -> ParserItem {
+        ParserItem::Message {
+            message_text: text.to_string(),
+            message_type: "STATUS-MESSAGE".into(),
+        }
+    }
+
+    #[test]
+    fn check_harness_report() {
+        let args = StandaloneArgs::parse_from(["kani", "file.rs", "--default-unwind", "3"]);
+        let harness = mock_proof_harness("check_foo", Some(5), None, None);
+        let mut result = VerificationResult::mock_success();
+        result.messages = Some(vec![
+            message("Runtime Symex: 0.125s"),
+            message("Runtime decision procedure: 0.5s"),
+        ]);
+        let report = harness_report(&args.verify_opts, &harness, &result);
+        assert_eq!(report["harness"], "check_foo");
+        assert_eq!(report["status"], "success");
+        assert_eq!(report["runtime"], Duration::ZERO.as_secs_f64());
+        assert_eq!(report["solver_time"], 0.5);
+        assert_eq!(report["unwind"]["bound"], 5);
+        assert!(report["timeout"].is_null());
+        assert!(report["failed_properties"].as_array().unwrap().is_empty());
+        assert!(report["checks"].as_object().unwrap().is_empty());
+        assert!(report["properties"].as_array().unwrap().is_empty());
+        assert!(report["assumptions"]["assumes"].as_array().unwrap().is_empty());
+        assert!(report["assumptions"]["disabled_checks"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn check_assumptions_ledger() {
+        let args = StandaloneArgs::parse_from(["kani", "file.rs", "--no-overflow-checks"]);
+        let mut harness = mock_proof_harness("check_foo", None, None, None);
+        harness.assumptions.assumes.push("check_foo at src/lib.rs:4:5".into());
+        harness.assumptions.foreign_functions.insert("getenv".into(), FfiPolicy::Havoc);
+        harness.stub_usage = vec![
+            StubUsage {
+                original: "foo".into(),
+                replacement: "mock_foo".into(),
+                call_sites: vec!["check_foo at src/lib.rs:5:5".into()],
+            },
+            StubUsage {
+                original: "bar".into(),
+                replacement: "mock_bar".into(),
+                call_sites: vec![],
+            },
+        ];
+        let ledger = assumptions_ledger(&args.verify_opts, &harness);
+        assert_eq!(ledger["assumes"], json!(["check_foo at src/lib.rs:4:5"]));
+        assert_eq!(ledger["stubs"].as_array().unwrap().len(), 1);
+        assert_eq!(ledger["stubs"][0]["original"], "foo");
+        assert_eq!(ledger["foreign_functions"], json!({ "getenv": "havoc" }));
+        assert_eq!(ledger["disabled_checks"], json!(["overflow"]));
+    }
+
+    #[test]
+    fn check_timeout_report() {
+        let args = StandaloneArgs::parse_from(["kani", "file.rs"]);
+        let harness = mock_proof_harness("check_foo", None, None, None);
+        let mut result = VerificationResult::mock_success();
+        result.status = VerificationStatus::Timeout;
+        result.timeout = Some(Duration::from_secs(60));
+        let report = harness_report(&args.verify_opts, &harness, &result);
+        assert_eq!(report["status"], "timeout");
+        assert_eq!(report["timeout"], 60);
+    }
+
+    #[test]
+    fn check_solver_time_missing() {
+        let mut result = VerificationResult::mock_success();
+        assert_eq!(solver_time(&result), None);
+        result.messages = Some(vec![message("Runtime decision procedure: unknown")]);
+        assert_eq!(solver_time(&result), None);
+    }
+}
@@ -20,7 +20,11 @@
 
 mod args;
 mod args_toml;
+mod asm_report;
 mod assess;
+mod assumption_suggestions;
+mod baseline;
+mod c_lib;
 mod call_cargo;
 mod call_cbmc;
 mod call_cbmc_viewer;
@@ -30,12 +34,30 @@
 mod call_single_file;
 mod cbmc_output_parser;
 mod cbmc_property_renderer;
+mod cfg_report;
+mod concrete_inputs;
 mod concrete_playback;
+mod contract_specs;
+mod coverage_report;
+mod dealloc_checks;
+mod estimate;
+mod ffi_policy;
 mod harness_runner;
+mod harness_timeout;
+mod hash_order;
+mod heap_graph;
+mod json_report;
 mod metadata;
+mod partition;
 mod project;
+mod sarif;
+mod server;
 mod session;
+mod size_report;
+mod stub_usage;
+mod unwind_suggestions;
 mod util;
+mod verification_cache;
 mod version;
 
 /// The main function for the `kani-driver`.
@@ -63,12 +85,17 @@ fn main() -> ExitCode {
 /// The main function for the `cargo kani` command.
 fn cargokani_main(input_args: Vec<OsString>) -> Result<()> {
     let input_args = join_args(input_args)?;
-    let args = args::CargoKaniArgs::parse_from(&input_args);
+    let mut args = args::CargoKaniArgs::parse_from(&input_args);
     check_is_valid(&args);
 
+    // The server uses stdout to communicate with its client, so nothing else can be printed.
+    if matches!(args.command, Some(CargoKaniSubcommand::Server)) {
+        args.verify_opts.common_args.quiet = true;
+    }
+
     let session = session::KaniSession::new(args.verify_opts)?;
 
-    if !session.args.common_args.quiet {
+    if session.args.prints_human_output() {
         print_kani_version(InvocationType::CargoKani(input_args));
     }
 
@@ -79,6 +106,16 @@ fn cargokani_main(input_args: Vec<OsString>) -> Result<()> {
         Some(CargoKaniSubcommand::Playback(args)) => {
             return playback_cargo(*args);
         }
+        Some(CargoKaniSubcommand::Server) => {
+            return server::run_server(session);
+        }
+        Some(CargoKaniSubcommand::CfgReport) => {
+            return cfg_report::run_cfg_report(&session);
+        }
+        Some(CargoKaniSubcommand::Estimate) => {
+            let project = project::cargo_project(&session, false)?;
+            return estimate::run_estimate(&session, &project);
+        }
         None => {}
     }
 
@@ -86,6 +123,10 @@ fn cargokani_main(input_args: Vec<OsString>) -> Result<()> {
         return assess::run_assess(session, assess::AssessArgs::default());
     }
 
+    if session.args.cfg_report {
+        return cfg_report::run_cfg_report(&session);
+    }
+
     let project = project::cargo_project(&session, false)?;
     if session.args.only_codegen { Ok(()) } else { verify_project(project, session) }
 }
@@ -101,7 +142,7 @@ fn standalone_main() -> Result<()> {
 
     let session = session::KaniSession::new(args.verify_opts)?;
 
-    if !session.args.common_args.quiet {
+    if session.args.prints_human_output() {
         print_kani_version(InvocationType::Standalone);
     }
 
@@ -115,10 +156,19 @@ fn verify_project(project: Project, session: KaniSession) -> Result<()> {
     let harnesses = session.determine_targets(&project.get_all_harnesses())?;
     debug!(n = harnesses.len(), ?harnesses, "verify_project");
 
+    if session.args.list_harnesses {
+        partition::print_harness_list(&harnesses, &session.read_timings()?);
+        return Ok(());
+    }
+
     // Verification
     let runner = harness_runner::HarnessRunner { sess: &session, project: &project };
     let results = runner.check_all_harnesses(&harnesses)?;
+    session.update_timings(&results)?;
+    session.update_verification_cache(&results)?;
+    session.update_baseline(&results)?;
 
+    session.gen_coverage_report(&results)?;
     session.print_final_summary(&results)
 }
 
@@ -6,14 +6,18 @@
 use tracing::{debug, trace};
 
 use kani_metadata::{
-    HarnessAttributes, HarnessMetadata, InternedString, KaniMetadata, TraitDefinedMethod,
-    VtableCtxResults,
+    HarnessAssumptions, HarnessAttributes, HarnessMetadata, InternedString, KaniMetadata,
+    TraitDefinedMethod, VtableCtxResults,
 };
+use regex::Regex;
 use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
 
+use crate::partition::partition_harnesses;
 use crate::session::KaniSession;
+use crate::util::warning;
 use serde::Deserialize;
 
 /// From either a file or a path with multiple files, output the CBMC restrictions file we should use.
@@ -121,39 +125,146 @@ pub fn determine_targets<'a>(
             BTreeSet::from_iter(self.args.harnesses.iter())
         };
 
-        let total_harnesses = harnesses.len();
         let all_targets = &harnesses;
 
-        if harnesses.is_empty() {
-            Ok(Vec::from(all_harnesses))
+        let targets = if harnesses.is_empty() {
+            Vec::from(all_harnesses)
         } else {
             let harnesses_found: Vec<&HarnessMetadata> =
                 find_proof_harnesses(&harnesses, all_harnesses, self.args.exact);
 
-            // If even one harness was not found with --exact, return an error to user
-            if self.args.exact && harnesses_found.len() < total_harnesses {
-                let harness_found_names: BTreeSet<&String> =
-                    harnesses_found.iter().map(|&h| &h.pretty_name).collect();
-
-                // Check which harnesses are missing from the difference of targets and harnesses_found
-                let harnesses_missing: Vec<&String> =
-                    all_targets.difference(&harness_found_names).cloned().collect();
-                let joined_string = harnesses_missing
+            if self.args.exact {
+                // If even one harness was not found with --exact, return an error to user
+                let harnesses_missing: Vec<&str> = all_targets
                     .iter()
-                    .map(|&s| (*s).clone())
-                    .collect::<Vec<String>>()
-                    .join("`, `");
+                    .filter(|target| !harnesses_found.iter().any(|h| h.is_named(target)))
+                    .map(|target| target.as_str())
+                    .collect();
+                if !harnesses_missing.is_empty() {
+                    let joined_string = harnesses_missing.join("`, `");
+                    bail!(
+                        "Failed to match the following harness(es):\n{joined_string}\nPlease specify the fully-qualified name of a harness.",
+                    );
+                }
 
-                bail!(
-                    "Failed to match the following harness(es):\n{joined_string}\nPlease specify the fully-qualified name of a harness.",
-                );
+                // The same name may be used by harnesses from different crates.
+                for target in all_targets {
+                    let matches: Vec<String> = harnesses_found
+                        .iter()
+                        .filter(|h| h.is_named(target))
+                        .map(|h| h.get_harness_name_with_crate())
+                        .collect();
+                    if matches.len() > 1 {
+                        bail!(
+                            "The harness name `{target}` is ambiguous, since it matches {} harnesses from different crates.\nPlease prefix the name with the crate name to select one of: `{}`.",
+                            matches.len(),
+                            matches.join("`, `")
+                        );
+                    }
+                }
             }
 
-            Ok(harnesses_found)
+            harnesses_found
+        };
+        let targets = select_harnesses(targets, &self.args.harness_patterns);
+        let targets = self.add_contract_checks(targets, all_harnesses);
+        // Exclusions apply to the harnesses that check contracts too.
+        let excluded = BTreeSet::from_iter(self.args.excluded_harnesses.iter());
+        let targets = exclude_harnesses(targets, &excluded, self.args.exact);
+        let targets = match &self.args.partition {
+            Some(partition) => partition_harnesses(targets, partition, &self.read_timings()?),
+            None => targets,
+        };
+        if (self.args.has_harness_filters() || self.args.partition.is_some())
+            && self.args.prints_human_output()
+        {
+            print_selection(&targets, all_harnesses.len());
+        }
+        Ok(targets)
+    }
+
+    /// Add the harnesses that check the contracts which the selected harnesses use through
+    /// `stub_verified`, so that the contracts are verified in the same run. Warn about
+    /// contracts that no harness checks, since harnesses using them may be unsound.
+    fn add_contract_checks<'a>(
+        &self,
+        mut targets: Vec<&'a HarnessMetadata>,
+        all_harnesses: &[&'a HarnessMetadata],
+    ) -> Vec<&'a HarnessMetadata> {
+        let stubbed: BTreeSet<&String> =
+            targets.iter().flat_map(|harness| &harness.attributes.verified_stubs).collect();
+        for function in stubbed {
+            let checks = find_contract_checks(function, all_harnesses);
+            if checks.is_empty() && self.args.prints_human_output() {
+                warning(&format!(
+                    "No harness checks the contract of `{function}`, which is used by \
+                    `stub_verified`. Add a `proof_for_contract` harness for it to make sure \
+                    the contract holds."
+                ));
+            }
+            for check in checks {
+                if !targets.contains(&check) {
+                    debug!(harness = check.pretty_name, function, "add_contract_checks");
+                    targets.push(check);
+                }
+            }
         }
+        targets
     }
 }
 
+/// Keep the harnesses that match at least one of `patterns` (if any).
+fn select_harnesses<'a>(
+    targets: Vec<&'a HarnessMetadata>,
+    patterns: &[Regex],
+) -> Vec<&'a HarnessMetadata> {
+    targets
+        .into_iter()
+        .filter(|harness| {
+            patterns.is_empty()
+                || patterns.iter().any(|pattern| {
+                    pattern.is_match(&harness.pretty_name)
+                        || pattern.is_match(&harness.get_harness_name_with_crate())
+                })
+        })
+        .collect()
+}
+
+/// Remove the harnesses that are matched by the `excluded` filters.
+fn exclude_harnesses<'a>(
+    targets: Vec<&'a HarnessMetadata>,
+    excluded: &BTreeSet<&String>,
+    exact_filter: bool,
+) -> Vec<&'a HarnessMetadata> {
+    if excluded.is_empty() {
+        return targets;
+    }
+    let excluded_harnesses = find_proof_harnesses(excluded, &targets, exact_filter);
+    targets.into_iter().filter(|harness| !excluded_harnesses.contains(harness)).collect()
+}
+
+/// Print the harnesses selected by the harness filters, so that the same selection can be
+/// reproduced with `--harness <name> --exact`.
+fn print_selection(targets: &[&HarnessMetadata], total: usize) {
+    let mut output = format!("Selected {} of {total} harnesses:\n", targets.len());
+    for harness in targets {
+        writeln!(output, "    - {}", harness.get_harness_name_with_crate()).unwrap();
+    }
+    print!("{output}");
+}
+
+/// Find the harnesses that check the contract of `function`.
+fn find_contract_checks<'a>(
+    function: &str,
+    all_harnesses: &[&'a HarnessMetadata],
+) -> Vec<&'a HarnessMetadata> {
+    all_harnesses
+        .iter()
+        .filter(|harness| harness.attributes.for_contract.as_deref() == Some(function))
+        .copied()
+        .collect()
+}
+
 /// Sort harnesses such that for two harnesses in the same file, it is guaranteed that later
 /// appearing harnesses get processed earlier.
 /// This is necessary for the concrete playback feature (with in-place unit test modification)
@@ -184,13 +295,18 @@ pub fn mock_proof_harness(
         original_end_line: 0,
         attributes: HarnessAttributes { unwind_value, proof: true, ..Default::default() },
         goto_file: model_file,
+        contract: None,
+        stub_usage: vec![],
+        havocked_asm: vec![],
+        concrete_inputs: vec![],
+        assumptions: HarnessAssumptions::default(),
     }
 }
 
 /// Search for a proof harness with a particular name.
 /// At the present time, we use `no_mangle` so collisions shouldn't happen,
 /// but this function is written to be robust against that changing in the future.
-fn find_proof_harnesses<'a>(
+pub(crate) fn find_proof_harnesses<'a>(
     targets: &BTreeSet<&String>,
     all_harnesses: &[&'a HarnessMetadata],
     exact_filter: bool,
@@ -200,7 +316,7 @@ fn find_proof_harnesses<'a>(
     for md in all_harnesses.iter() {
         if exact_filter {
             // Check for exact match only
-            if targets.contains(&md.pretty_name) {
+            if targets.iter().any(|target| md.is_named(target)) {
                 // if exact match found, stop searching
                 result.push(*md);
             } else {
@@ -211,6 +327,13 @@ fn find_proof_harnesses<'a>(
             if targets.contains(&md.pretty_name)
                 || targets.contains(&md.get_harness_name_unqualified().to_string())
                 || targets.iter().any(|target| md.pretty_name.contains(*target))
+                || targets.iter().any(|target| {
+                    // The target may also be prefixed by the name of the crate.
+                    target
+                        .strip_prefix(md.crate_name.as_str())
+                        .and_then(|rest| rest.strip_prefix("::"))
+                        .is_some_and(|rest| md.pretty_name.contains(rest))
+                })
             {
                 result.push(*md);
             } else {
@@ -268,6 +391,116 @@ fn check_find_proof_harness_without_exact() {
         );
     }
 
+    #[test]
+    fn check_find_proof_harness_with_crate_name() {
+        let harnesses = vec![
+            mock_proof_harness("check", None, Some("first"), None),
+            mock_proof_harness("check", None, Some("second"), None),
+            mock_proof_harness("module::check_two", None, Some("second"), None),
+        ];
+        let ref_harnesses = harnesses.iter().collect::<Vec<_>>();
+
+        let found = find_proof_harnesses(
+            &BTreeSet::from([&"second::check".to_string()]),
+            &ref_harnesses,
+            true,
+        );
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].crate_name, "second");
+
+        // Both harnesses have the same name.
+        assert_eq!(
+            find_proof_harnesses(&BTreeSet::from([&"check".to_string()]), &ref_harnesses, true)
+                .len(),
+            2
+        );
+
+        // The crate name can also be used to filter harnesses without `--exact`.
+        assert_eq!(
+            find_proof_harnesses(
+                &BTreeSet::from([&"second::check".to_string()]),
+                &ref_harnesses,
+                false
+            )
+            .len(),
+            2
+        );
+        assert!(
+            find_proof_harnesses(
+                &BTreeSet::from([&"first::module".to_string()]),
+                &ref_harnesses,
+                false
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn check_select_harnesses() {
+        let harnesses = vec![
+            mock_proof_harness("first::check_foo", None, Some("my_crate"), None),
+            mock_proof_harness("first::check_slow", None, Some("my_crate"), None),
+            mock_proof_harness("second::check_foo", None, Some("my_crate"), None),
+            mock_proof_harness("second::verify_foo", None, Some("other_crate"), None),
+        ];
+        let targets = harnesses.iter().collect::<Vec<_>>();
+        let names = |selected: Vec<&HarnessMetadata>| {
+            selected.iter().map(|h| h.get_harness_name_with_crate()).collect::<Vec<_>>()
+        };
+
+        let patterns = [Regex::new("^first::").unwrap(), Regex::new("^other_crate::").unwrap()];
+        assert_eq!(
+            names(select_harnesses(targets.clone(), &patterns)),
+            [
+                "my_crate::first::check_foo",
+                "my_crate::first::check_slow",
+                "other_crate::second::verify_foo"
+            ]
+        );
+
+        let excluded = "slow".to_string();
+        assert_eq!(
+            names(exclude_harnesses(
+                select_harnesses(targets.clone(), &patterns),
+                &BTreeSet::from([&excluded]),
+                false
+            )),
+            ["my_crate::first::check_foo", "other_crate::second::verify_foo"]
+        );
+
+        // Exclusions follow `--exact` like `--harness` does.
+        let excluded = "check_foo".to_string();
+        assert_eq!(
+            names(exclude_harnesses(targets.clone(), &BTreeSet::from([&excluded]), false)),
+            ["my_crate::first::check_slow", "other_crate::second::verify_foo"]
+        );
+        assert_eq!(exclude_harnesses(targets.clone(), &BTreeSet::from([&excluded]), true).len(), 4);
+        let excluded = "my_crate::first::check_foo".to_string();
+        assert_eq!(
+            names(exclude_harnesses(targets, &BTreeSet::from([&excluded]), true)),
+            [
+                "my_crate::first::check_slow",
+                "my_crate::second::check_foo",
+                "other_crate::second::verify_foo"
+            ]
+        );
+    }
+
+    #[test]
+    fn check_find_contract_checks() {
+        let mut check = mock_proof_harness("check_div", None, None, None);
+        check.attributes.for_contract = Some("my_crate::div".to_string());
+        let mut user = mock_proof_harness("use_div", None, None, None);
+        user.attributes.verified_stubs = vec!["my_crate::div".to_string()];
+        let harnesses = vec![check, user];
+        let ref_harnesses = harnesses.iter().collect::<Vec<_>>();
+
+        let found = find_contract_checks("my_crate::div", &ref_harnesses);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].pretty_name, "check_div");
+        assert!(find_contract_checks("my_crate::gcd", &ref_harnesses).is_empty());
+    }
+
     #[test]
     fn check_find_proof_harness_with_exact() {
         // Check with exact match
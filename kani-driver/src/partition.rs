@@ -0,0 +1,308 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for splitting the harnesses of a project across several Kani runs, e.g., across the
+//! machines of a CI pipeline, in the same way as `cargo nextest --partition`.
+//!
+//! `--partition <STRATEGY>:<M>/<N>` verifies the `M`-th of `N` disjoint shards of the selected
+//! harnesses, where the strategy is one of:
+//! - `count`: the harnesses are sorted by name and dealt out to the shards in turn.
+//! - `hash`: each harness goes to the shard given by a hash of its name, so adding a harness
+//!   doesn't move the others to a different shard.
+//! - `time`: the harnesses are distributed such that the shards take about the same time, based
+//!   on the verification time of each harness in the `--timings` file.
+//!
+//! The `--timings` file records the verification time of each harness, and it is updated after
+//! each run with the harnesses that were verified:
+//!
+//! ```json
+//! {
+//!   "harnesses": {
+//!     "my_crate::check_foo": 1.25,
+//!     "my_crate::check_bar": 30.5
+//!   }
+//! }
+//! ```
+//!
+//! `--list-harnesses` prints the selected harnesses, with their recorded time, in the format of
+//! the `--timings` file plus the file of each harness, so that external schedulers can build
+//! their own partitions without verifying anything.
+
+use crate::harness_runner::HarnessResult;
+use crate::session::KaniSession;
+use crate::util::fnv1a;
+use anyhow::{Context, Result};
+use kani_metadata::HarnessMetadata;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::path::Path;
+use std::str::FromStr;
+
+/// How the harnesses are assigned to the shards.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PartitionStrategy {
+    Count,
+    Hash,
+    Time,
+}
+
+/// The shard of the harnesses that a run verifies, given by `--partition`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Partition {
+    pub strategy: PartitionStrategy,
+    /// The 1-based index of the shard.
+    pub shard: usize,
+    pub total: usize,
+}
+
+impl FromStr for Partition {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (strategy, shards) = value
+            .split_once(':')
+            .ok_or_else(|| format!("expected `<strategy>:<M>/<N>`, found `{value}`"))?;
+        let strategy = match strategy {
+            "count" => PartitionStrategy::Count,
+            "hash" => PartitionStrategy::Hash,
+            "time" => PartitionStrategy::Time,
+            _ => {
+                return Err(format!(
+                    "unknown strategy `{strategy}`, expected `count`, `hash` or `time`"
+                ));
+            }
+        };
+        let (shard, total) = shards
+            .split_once('/')
+            .ok_or_else(|| format!("expected `<M>/<N>` after the strategy, found `{shards}`"))?;
+        let shard: usize = shard.parse().map_err(|_| format!("invalid shard index `{shard}`"))?;
+        let total: usize =
+            total.parse().map_err(|_| format!("invalid number of shards `{total}`"))?;
+        if total == 0 || shard == 0 || shard > total {
+            return Err(format!("the shard index must be between 1 and {total}, found {shard}"));
+        }
+        Ok(Partition { strategy, shard, total })
+    }
+}
+
+impl Display for Partition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let strategy = match self.strategy {
+            PartitionStrategy::Count => "count",
+            PartitionStrategy::Hash => "hash",
+            PartitionStrategy::Time => "time",
+        };
+        write!(f, "{strategy}:{}/{}", self.shard, self.total)
+    }
+}
+
+/// The verification time of each harness in seconds, indexed by its name with the crate prefix.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Timings {
+    #[serde(default)]
+    pub harnesses: BTreeMap<String, f64>,
+}
+
+impl Timings {
+    /// Read the timings at `path`. A file that doesn't exist yet has no timings.
+    pub fn read(path: &Path) -> Result<Timings> {
+        if !path.exists() {
+            return Ok(Timings::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read timings from `{}`", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse timings from `{}`", path.display()))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write timings to `{}`", path.display()))
+    }
+
+    /// The recorded time of `harness`, if any.
+    pub fn get(&self, harness: &HarnessMetadata) -> Option<f64> {
+        self.harnesses.get(&harness.get_harness_name_with_crate()).copied()
+    }
+}
+
+impl KaniSession {
+    /// The timings of `--timings`, or no timings if the argument wasn't given.
+    pub fn read_timings(&self) -> Result<Timings> {
+        match &self.args.timings {
+            Some(path) => Timings::read(path),
+            None => Ok(Timings::default()),
+        }
+    }
+
+    /// Record the time of the harnesses verified in this run in the `--timings` file, keeping
+    /// the times of the other harnesses.
+    pub fn update_timings(&self, results: &[HarnessResult<'_>]) -> Result<()> {
+        let Some(path) = &self.args.timings else { return Ok(()) };
+        let mut timings = Timings::read(path)?;
+        for result in results {
+            timings.harnesses.insert(
+                result.harness.get_harness_name_with_crate(),
+                result.result.runtime.as_secs_f64(),
+            );
+        }
+        timings.write(path)
+    }
+}
+
+/// Print the harnesses with their recorded time for `--list-harnesses`.
+pub fn print_harness_list(harnesses: &[&HarnessMetadata], timings: &Timings) {
+    let harnesses: Vec<_> = harnesses
+        .iter()
+        .map(|harness| {
+            json!({
+                "name": harness.get_harness_name_with_crate(),
+                "file": harness.original_file,
+                "time": timings.get(harness),
+            })
+        })
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&json!({ "harnesses": harnesses })).unwrap());
+}
+
+/// Keep the harnesses that belong to the shard of `partition`.
+pub fn partition_harnesses<'a>(
+    harnesses: Vec<&'a HarnessMetadata>,
+    partition: &Partition,
+    timings: &Timings,
+) -> Vec<&'a HarnessMetadata> {
+    let mut harnesses: Vec<_> = harnesses
+        .into_iter()
+        .map(|harness| (harness.get_harness_name_with_crate(), harness))
+        .collect();
+    harnesses.sort_by(|(name1, _), (name2, _)| name1.cmp(name2));
+    let shards: Vec<usize> = match partition.strategy {
+        PartitionStrategy::Count => (0..harnesses.len()).map(|idx| idx % partition.total).collect(),
+        PartitionStrategy::Hash => harnesses
+            .iter()
+            .map(|(name, _)| (fnv1a(name.as_bytes()) % partition.total as u64) as usize)
+            .collect(),
+        PartitionStrategy::Time => {
+            let times: Vec<Option<f64>> =
+                harnesses.iter().map(|(_, harness)| timings.get(harness)).collect();
+            balance_shards(&times, partition.total)
+        }
+    };
+    harnesses
+        .into_iter()
+        .zip(shards)
+        .filter(|(_, shard)| *shard == partition.shard - 1)
+        .map(|((_, harness), _)| harness)
+        .collect()
+}
+
+/// Assign each job to one of `total` shards, such that the sum of the times of each shard is
+/// about the same. The longest jobs are assigned first, each to the shard with the least time so
+/// far. The jobs without a time are assumed to take the average time of the others.
+fn balance_shards(times: &[Option<f64>], total: usize) -> Vec<usize> {
+    let known: Vec<f64> = times.iter().flatten().copied().collect();
+    let default =
+        if known.is_empty() { 1.0 } else { known.iter().sum::<f64>() / known.len() as f64 };
+    let mut jobs: Vec<(usize, f64)> =
+        times.iter().map(|time| time.unwrap_or(default)).enumerate().collect();
+    // The sort is stable, so jobs with the same time stay sorted by name.
+    jobs.sort_by(|(_, time1), (_, time2)| time2.total_cmp(time1));
+    let mut loads = vec![0.0_f64; total];
+    let mut shards = vec![0; times.len()];
+    for (job, time) in jobs {
+        let (shard, _) = loads
+            .iter()
+            .enumerate()
+            .min_by(|(_, load1), (_, load2)| load1.total_cmp(load2))
+            .unwrap();
+        loads[shard] += time;
+        shards[job] = shard;
+    }
+    shards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::mock_proof_harness;
+
+    fn mock_harnesses(names: &[&str]) -> Vec<HarnessMetadata> {
+        names.iter().map(|name| mock_proof_harness(name, None, Some("krate"), None)).collect()
+    }
+
+    fn shard_names(
+        harnesses: &[HarnessMetadata],
+        partition: &str,
+        timings: &Timings,
+    ) -> Vec<String> {
+        let partition = Partition::from_str(partition).unwrap();
+        partition_harnesses(harnesses.iter().collect(), &partition, timings)
+            .iter()
+            .map(|harness| harness.pretty_name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn check_parse_partition() {
+        let partition = Partition::from_str("count:2/3").unwrap();
+        assert_eq!(partition, Partition { strategy: PartitionStrategy::Count, shard: 2, total: 3 });
+        assert_eq!(partition.to_string(), "count:2/3");
+        assert!(Partition::from_str("hash:1/1").is_ok());
+        assert!(Partition::from_str("time:4/4").is_ok());
+        assert!(Partition::from_str("2/3").is_err());
+        assert!(Partition::from_str("slice:2/3").is_err());
+        assert!(Partition::from_str("count:0/3").is_err());
+        assert!(Partition::from_str("count:4/3").is_err());
+        assert!(Partition::from_str("count:1/0").is_err());
+        assert!(Partition::from_str("count:one/3").is_err());
+    }
+
+    #[test]
+    fn check_count_partition() {
+        let harnesses = mock_harnesses(&["d", "a", "c", "b", "e"]);
+        let timings = Timings::default();
+        assert_eq!(shard_names(&harnesses, "count:1/2", &timings), ["a", "c", "e"]);
+        assert_eq!(shard_names(&harnesses, "count:2/2", &timings), ["b", "d"]);
+    }
+
+    #[test]
+    fn check_hash_partition_is_disjoint_and_complete() {
+        let names: Vec<String> = (0..50).map(|idx| format!("check_{idx}")).collect();
+        let harnesses = mock_harnesses(&names.iter().map(String::as_str).collect::<Vec<_>>());
+        let timings = Timings::default();
+        let mut all: Vec<String> = (1..=3)
+            .flat_map(|shard| shard_names(&harnesses, &format!("hash:{shard}/3"), &timings))
+            .collect();
+        all.sort();
+        let mut expected = names.clone();
+        expected.sort();
+        assert_eq!(all, expected);
+    }
+
+    #[test]
+    fn check_time_partition() {
+        let harnesses = mock_harnesses(&["a", "b", "c", "d", "e"]);
+        let timings = Timings {
+            harnesses: BTreeMap::from([
+                ("krate::a".to_string(), 10.0),
+                ("krate::b".to_string(), 6.0),
+                ("krate::c".to_string(), 4.0),
+                ("krate::d".to_string(), 1.0),
+            ]),
+        };
+        // `e` has no time, so it is assumed to take the average of 5.25s.
+        assert_eq!(shard_names(&harnesses, "time:1/2", &timings), ["a", "c"]);
+        assert_eq!(shard_names(&harnesses, "time:2/2", &timings), ["b", "d", "e"]);
+    }
+
+    #[test]
+    fn check_parse_timings() {
+        let timings: Timings =
+            serde_json::from_str(r#"{ "harnesses": { "krate::a": 1.5 } }"#).unwrap();
+        assert_eq!(timings.harnesses["krate::a"], 1.5);
+        assert_eq!(serde_json::from_str::<Timings>("{}").unwrap(), Timings::default());
+    }
+}
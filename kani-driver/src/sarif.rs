@@ -0,0 +1,188 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for exporting the failed checks in the [SARIF](https://sarifweb.azurewebsites.net/)
+//! format (`--sarif <PATH>`), which code scanning integrations of CI systems can consume.
+//!
+//! Every failed check of a failed harness becomes a SARIF result, whose rule is the class of the
+//! check (e.g. `assertion` or `pointer_dereference`). Checks that fail in harnesses that are
+//! expected to panic aren't reported, since these harnesses succeed.
+
+use crate::call_cbmc::VerificationStatus;
+use crate::cbmc_output_parser::{filepath, CheckStatus, Property};
+use crate::harness_runner::HarnessResult;
+use crate::session::KaniSession;
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+impl KaniSession {
+    /// Write the failed checks of all harnesses to the `--sarif` file, if any.
+    pub(crate) fn write_sarif_report(&self, results: &[HarnessResult<'_>]) -> Result<()> {
+        let Some(path) = &self.args.sarif else { return Ok(()) };
+        let report = serde_json::to_string_pretty(&sarif_report(results))?;
+        std::fs::write(path, report)
+            .with_context(|| format!("Failed to write SARIF report to `{}`", path.display()))
+    }
+}
+
+fn sarif_report(results: &[HarnessResult<'_>]) -> Value {
+    let failures: Vec<(&str, &Property)> = results
+        .iter()
+        .filter(|res| res.result.status == VerificationStatus::Failure)
+        .flat_map(|res| {
+            let properties = res.result.results.as_deref().unwrap_or_default();
+            properties
+                .iter()
+                .filter(|prop| prop.status == CheckStatus::Failure)
+                .map(|prop| (res.harness.pretty_name.as_str(), prop))
+        })
+        .collect();
+
+    // Each property class is a rule, which results refer to by index.
+    let rules: Vec<String> = failures
+        .iter()
+        .map(|(_, prop)| prop.property_class())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let sarif_results: Vec<Value> = failures
+        .iter()
+        .map(|(harness, prop)| {
+            let class = prop.property_class();
+            json!({
+                "ruleId": class,
+                "ruleIndex": rules.binary_search(&class).unwrap(),
+                "level": "error",
+                "message": { "text": format!("{} (harness `{harness}`)", prop.description) },
+                "locations": location(prop).into_iter().collect::<Vec<_>>(),
+                "partialFingerprints": { "kaniProperty": format!("{harness}/{}", prop.property_name()) },
+            })
+        })
+        .collect();
+    let sarif_rules: Vec<Value> = rules
+        .iter()
+        .map(|class| {
+            json!({
+                "id": class,
+                "name": class,
+                "shortDescription": { "text": format!("Kani `{class}` check") },
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "Kani",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/model-checking/kani",
+                    "rules": sarif_rules,
+                },
+            },
+            "results": sarif_results,
+        }],
+    })
+}
+
+/// The SARIF location of a check, if its source location includes a file.
+fn location(prop: &Property) -> Option<Value> {
+    let loc = &prop.source_location;
+    let file = filepath(loc.file.clone()?);
+    let mut region = serde_json::Map::new();
+    if let Some(line) = loc.line.as_ref().and_then(|line| line.parse::<u64>().ok()) {
+        region.insert("startLine".into(), line.into());
+        if let Some(column) = loc.column.as_ref().and_then(|column| column.parse::<u64>().ok()) {
+            region.insert("startColumn".into(), column.into());
+        }
+    }
+    let mut physical_location = json!({ "artifactLocation": { "uri": file } });
+    if !region.is_empty() {
+        physical_location["region"] = Value::Object(region);
+    }
+    Some(json!({ "physicalLocation": physical_location }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_cbmc::VerificationResult;
+    use crate::cbmc_output_parser::{PropertyId, SourceLocation};
+    use crate::metadata::mock_proof_harness;
+    use serde::Deserialize;
+
+    fn property(name: &str, status: CheckStatus, line: Option<&str>) -> Property {
+        Property {
+            description: format!("description of {name}"),
+            property_id: PropertyId::deserialize(Value::String(name.to_string())).unwrap(),
+            source_location: SourceLocation {
+                column: line.map(|_| "5".to_string()),
+                file: line.map(|_| "src/lib.rs".to_string()),
+                function: None,
+                line: line.map(str::to_string),
+            },
+            status,
+            reach: None,
+            trace: None,
+        }
+    }
+
+    fn result(status: VerificationStatus, properties: Vec<Property>) -> VerificationResult {
+        VerificationResult { status, results: Ok(properties), ..VerificationResult::mock_success() }
+    }
+
+    #[test]
+    fn check_sarif_report() {
+        let failing = mock_proof_harness("check_fail", None, None, None);
+        let passing = mock_proof_harness("check_pass", None, None, None);
+        let results = [
+            HarnessResult {
+                harness: &failing,
+                result: result(
+                    VerificationStatus::Failure,
+                    vec![
+                        property("foo.assertion.1", CheckStatus::Failure, Some("10")),
+                        property("foo.assertion.2", CheckStatus::Success, Some("11")),
+                        property("bar.pointer_dereference.1", CheckStatus::Failure, None),
+                    ],
+                ),
+                fingerprint: None,
+            },
+            HarnessResult {
+                harness: &passing,
+                result: result(VerificationStatus::Success, vec![]),
+                fingerprint: None,
+            },
+        ];
+        let report = sarif_report(&results);
+        let run = &report["runs"][0];
+        let rules: Vec<_> = run["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|rule| rule["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(rules, ["assertion", "pointer_dereference"]);
+
+        let sarif_results = run["results"].as_array().unwrap();
+        assert_eq!(sarif_results.len(), 2);
+        assert_eq!(sarif_results[0]["ruleId"], "assertion");
+        assert_eq!(sarif_results[0]["ruleIndex"], 0);
+        assert_eq!(
+            sarif_results[0]["message"]["text"],
+            "description of foo.assertion.1 (harness `check_fail`)"
+        );
+        let location = &sarif_results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/lib.rs");
+        assert_eq!(location["region"]["startLine"], 10);
+        assert_eq!(location["region"]["startColumn"], 5);
+        assert_eq!(sarif_results[1]["ruleIndex"], 1);
+        assert!(sarif_results[1]["locations"].as_array().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `cargo kani server`, a long-running process that keeps the compiled project around
+//! and verifies harnesses on demand.
+//!
+//! The server speaks [JSON-RPC 2.0](https://www.jsonrpc.org/specification) over stdio, where
+//! each message is a single line of JSON. The following methods are supported:
+//!
+//!  - `harnesses`: List the harnesses of the project.
+//!  - `verify`: Verify the harnesses selected by the parameters (all of them by default):
+//!    `{ "harnesses": ["name"], "exact": false, "unwind": 10, "solver": "kissat" }`.
+//!    While a request is being handled, the server sends `progress` notifications to report
+//!    when each harness starts and finishes.
+//!  - `rebuild`: Recompile the project, e.g., after the source code changed.
+//!  - `shutdown`: Stop the server.
+//!
+//! All the other options (checks, unwinding defaults, etc.) are the ones given when starting the
+//! server.
+
+use crate::harness_runner::HarnessRunner;
+use crate::json_report::harness_report;
+use crate::metadata::find_proof_harnesses;
+use crate::project::{self, Project};
+use crate::session::KaniSession;
+use anyhow::Result;
+use kani_metadata::{ArtifactType, CbmcSolver, HarnessMetadata};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// JSON-RPC error codes defined by the specification.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// Implementation defined error used when a request fails to be executed.
+const SERVER_ERROR: i64 = -32000;
+
+/// Run the server until the input is closed or a `shutdown` request is received.
+pub fn run_server(session: KaniSession) -> Result<()> {
+    let mut server = Server { project: project::cargo_project(&session, false)?, session };
+    server.snapshot_models()?;
+
+    let stdin = std::io::stdin();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => request,
+            Err(err) => {
+                let code = if serde_json::from_str::<Value>(&line).is_ok() {
+                    INVALID_REQUEST
+                } else {
+                    PARSE_ERROR
+                };
+                send(&error_response(Value::Null, code, err.to_string()));
+                continue;
+            }
+        };
+        let shutdown = request.method == "shutdown";
+        let response = server.handle(&request);
+        // Notifications (requests without an id) must not be answered.
+        if let Some(id) = request.id {
+            match response {
+                Ok(result) => send(&json!({ "jsonrpc": "2.0", "id": id, "result": result })),
+                Err((code, message)) => send(&error_response(id, code, message)),
+            }
+        }
+        if shutdown {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// A JSON-RPC request or notification.
+#[derive(Debug, Deserialize)]
+struct Request {
+    #[allow(dead_code)]
+    jsonrpc: String,
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// The parameters of a `verify` request.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct VerifyParams {
+    harnesses: Vec<String>,
+    exact: bool,
+    unwind: Option<u32>,
+    solver: Option<String>,
+}
+
+type RpcResult = std::result::Result<Value, (i64, String)>;
+
+struct Server {
+    session: KaniSession,
+    project: Project,
+}
+
+impl Server {
+    fn handle(&mut self, request: &Request) -> RpcResult {
+        match request.method.as_str() {
+            "harnesses" => Ok(self.harnesses()),
+            "verify" => {
+                let params = if request.params.is_null() {
+                    VerifyParams::default()
+                } else {
+                    serde_json::from_value(request.params.clone())
+                        .map_err(|err| (INVALID_PARAMS, err.to_string()))?
+                };
+                self.verify(params)
+            }
+            "rebuild" => {
+                self.project = project::cargo_project(&self.session, false)
+                    .map_err(|err| (SERVER_ERROR, format!("{err:#}")))?;
+                self.snapshot_models().map_err(|err| (SERVER_ERROR, format!("{err:#}")))?;
+                Ok(self.harnesses())
+            }
+            "shutdown" => Ok(Value::Null),
+            method => Err((METHOD_NOT_FOUND, format!("Unknown method `{method}`"))),
+        }
+    }
+
+    fn harnesses(&self) -> Value {
+        self.project
+            .get_all_harnesses()
+            .iter()
+            .map(|harness| {
+                json!({
+                    "name": harness.pretty_name,
+                    "file": harness.original_file,
+                    "line": harness.original_start_line,
+                })
+            })
+            .collect()
+    }
+
+    fn verify(&self, params: VerifyParams) -> RpcResult {
+        let solver = params
+            .solver
+            .as_deref()
+            .map(CbmcSolver::from_str)
+            .transpose()
+            .map_err(|err| (INVALID_PARAMS, format!("Invalid solver: {err}")))?;
+        let all_harnesses = self.project.get_all_harnesses();
+        let selected = if params.harnesses.is_empty() {
+            all_harnesses
+        } else {
+            let targets = BTreeSet::from_iter(params.harnesses.iter());
+            find_proof_harnesses(&targets, &all_harnesses, params.exact)
+        };
+        // Apply the per-request options on top of the options from each harness attributes.
+        let harnesses: Vec<HarnessMetadata> = selected
+            .into_iter()
+            .map(|harness| {
+                let mut harness = harness.clone();
+                harness.attributes.unwind_value = params.unwind.or(harness.attributes.unwind_value);
+                harness.attributes.solver = solver.clone().or(harness.attributes.solver);
+                harness
+            })
+            .collect();
+
+        let runner = HarnessRunner { sess: &self.session, project: &self.project };
+        let mut reports = vec![];
+        for harness in &harnesses {
+            notify("progress", json!({ "harness": harness.pretty_name, "state": "started" }));
+            self.restore_model(harness).map_err(|err| (SERVER_ERROR, format!("{err:#}")))?;
+            let targets = [harness];
+            let mut results = runner
+                .check_all_harnesses(&targets)
+                .map_err(|err| (SERVER_ERROR, format!("{err:#}")))?;
+            let report =
+                harness_report(&self.session.args, harness, &results.pop().unwrap().result);
+            notify(
+                "progress",
+                json!({ "harness": harness.pretty_name, "state": "finished", "status": report["status"] }),
+            );
+            reports.push(report);
+        }
+        Ok(Value::Array(reports))
+    }
+
+    /// Goto models are instrumented in place before verification. Keep a copy of the original
+    /// models so every request starts from the same model.
+    fn snapshot_models(&self) -> Result<()> {
+        for (model, snapshot) in self.model_snapshots() {
+            std::fs::copy(&model, &snapshot)?;
+            self.session.record_temporary_file(&snapshot);
+        }
+        Ok(())
+    }
+
+    fn restore_model(&self, harness: &HarnessMetadata) -> Result<()> {
+        if let Some(model) = self.project.get_harness_artifact(harness, ArtifactType::Goto) {
+            std::fs::copy(snapshot_path(model), model)?;
+        }
+        Ok(())
+    }
+
+    fn model_snapshots(&self) -> HashMap<PathBuf, PathBuf> {
+        self.project
+            .get_all_harnesses()
+            .into_iter()
+            .filter_map(|harness| self.project.get_harness_artifact(harness, ArtifactType::Goto))
+            .map(|model| (model.to_path_buf(), snapshot_path(model)))
+            .collect()
+    }
+}
+
+fn snapshot_path(model: &std::path::Path) -> PathBuf {
+    let mut path = model.as_os_str().to_owned();
+    path.push(".server");
+    path.into()
+}
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn notify(method: &str, params: Value) {
+    send(&json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+}
+
+/// Write a message to the client. Each message is written in a single line.
+fn send(message: &Value) {
+    let mut stdout = std::io::stdout().lock();
+    writeln!(stdout, "{message}").unwrap();
+    stdout.flush().unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_parse_requests() {
+        let request: Request =
+            serde_json::from_str(r#"{"jsonrpc": "2.0", "id": 1, "method": "harnesses"}"#).unwrap();
+        assert_eq!(request.id, Some(json!(1)));
+        assert!(request.params.is_null());
+
+        let request: Request = serde_json::from_str(
+            r#"{"jsonrpc": "2.0", "id": "a", "method": "verify",
+                "params": {"harnesses": ["check_foo"], "unwind": 3}}"#,
+        )
+        .unwrap();
+        let params: VerifyParams = serde_json::from_value(request.params).unwrap();
+        assert_eq!(params.harnesses, ["check_foo"]);
+        assert_eq!(params.unwind, Some(3));
+        assert!(!params.exact);
+
+        assert!(serde_json::from_value::<VerifyParams>(json!({"unknown": 1})).is_err());
+    }
+}
@@ -10,7 +10,7 @@
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, ExitStatus, Stdio};
 use std::sync::Mutex;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use strum_macros::Display;
 use tracing::level_filters::LevelFilter;
 use tracing_subscriber::{layer::SubscriberExt, EnvFilter, Registry};
@@ -38,6 +38,9 @@ pub struct KaniSession {
 
     /// The temporary files we littered that need to be cleaned up at the end of execution
     pub temporaries: Mutex<Vec<PathBuf>>,
+
+    /// When the time budget of `--total-timeout` runs out, if any.
+    pub deadline: Option<Instant>,
 }
 
 /// Represents where we detected Kani, with helper methods for using that information to find critical paths
@@ -54,8 +57,20 @@ impl KaniSession {
     pub fn new(args: VerificationArgs) -> Result<Self> {
         init_logger(&args);
         let install = InstallType::new()?;
+        if let Some(target) = &args.target_triple {
+            let lib = target_lib_folder(target)?;
+            if !lib.exists() {
+                bail!(
+                    "Kani's libraries are not available for target `{target}`, since `{}` does \
+                    not exist. In a development build, add them with `cargo build-dev --target \
+                    {target}`.",
+                    lib.display()
+                );
+            }
+        }
 
         Ok(KaniSession {
+            deadline: args.total_timeout.map(|secs| Instant::now() + Duration::from_secs(secs)),
             args,
             codegen_tests: false,
             kani_compiler: install.kani_compiler()?,
@@ -275,6 +290,12 @@ pub fn lib_folder() -> Result<PathBuf> {
     Ok(base_folder()?.join("lib"))
 }
 
+/// Return the path for the folder where the rust libraries pre-compiled for `target`, a target
+/// other than the host, are located. This folder is the `lib/` folder of a sysroot of its own.
+pub fn target_lib_folder(target: &str) -> Result<PathBuf> {
+    Ok(base_folder()?.join("targets").join(target).join("lib"))
+}
+
 /// Return the path for the folder where the pre-compiled rust libraries are located.
 pub fn lib_playback_folder() -> Result<PathBuf> {
     Ok(base_folder()?.join("playback/lib"))
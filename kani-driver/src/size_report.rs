@@ -0,0 +1,140 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `--size-report`, which lists the largest functions in the model of each harness.
+//!
+//! The report is computed from the instrumented model, after goto-instrument removed the
+//! functions that the harness can't reach, so it only shows the code that CBMC actually has to
+//! analyze. Large functions of dependencies are usually the best candidates for stubbing.
+
+use crate::estimate::GotoFunction;
+use crate::session::KaniSession;
+use anyhow::Result;
+use comfy_table::{ContentArrangement, Table};
+use kani_metadata::HarnessMetadata;
+use rustc_demangle::demangle;
+use std::path::Path;
+
+/// A function of the model and its size.
+#[derive(Debug, PartialEq, Eq)]
+struct FunctionSize {
+    /// The demangled name of the function.
+    name: String,
+    instructions: usize,
+}
+
+impl FunctionSize {
+    /// The crate that defines the function, or `-` for the functions that aren't Rust functions,
+    /// e.g., the models of Kani and the C functions linked with `--c-lib`.
+    fn krate(&self) -> &str {
+        let path = self.name.trim_start_matches('<');
+        match path.split_once("::") {
+            Some((krate, _))
+                if !krate.is_empty()
+                    && krate.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+            {
+                krate
+            }
+            _ => "-",
+        }
+    }
+}
+
+/// The functions of a model with a body, from the largest to the smallest.
+fn function_sizes(functions: Vec<GotoFunction>) -> Vec<FunctionSize> {
+    let mut sizes: Vec<_> = functions
+        .into_iter()
+        .filter(|function| function.is_body_available && !function.is_internal)
+        .map(|function| FunctionSize {
+            name: format!("{:#}", demangle(&function.name)),
+            instructions: function.instructions.len(),
+        })
+        .collect();
+    sizes.sort_by(|a, b| b.instructions.cmp(&a.instructions).then_with(|| a.name.cmp(&b.name)));
+    sizes
+}
+
+impl KaniSession {
+    /// Print the `top` largest functions in the instrumented model of a harness.
+    pub fn print_size_report(
+        &self,
+        file: &Path,
+        harness: &HarnessMetadata,
+        top: usize,
+    ) -> Result<()> {
+        let args = self.cbmc_flags(file, harness)?;
+        let sizes = function_sizes(self.goto_functions(&args)?);
+        println!("{}", render(&harness.pretty_name, &sizes, top));
+        Ok(())
+    }
+}
+
+fn render(harness: &str, sizes: &[FunctionSize], top: usize) -> String {
+    let total: usize = sizes.iter().map(|size| size.instructions).sum();
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header(["Function", "Crate", "Instructions", "Share"]);
+    for size in sizes.iter().take(top) {
+        let share = size.instructions as f64 * 100.0 / total.max(1) as f64;
+        table.add_row([
+            size.name.clone(),
+            size.krate().to_string(),
+            size.instructions.to_string(),
+            format!("{share:.1}%"),
+        ]);
+    }
+    format!(
+        "Largest functions in the model of harness `{harness}` ({total} instructions in {} \
+        functions):\n{table}",
+        sizes.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn size(name: &str, instructions: usize) -> FunctionSize {
+        FunctionSize { name: name.to_string(), instructions }
+    }
+
+    #[test]
+    fn check_crate_of_function() {
+        assert_eq!(size("serde_json::de::from_str", 1).krate(), "serde_json");
+        assert_eq!(size("<regex::Regex as core::fmt::Debug>::fmt", 1).krate(), "regex");
+        assert_eq!(size("__rust_alloc", 1).krate(), "-");
+        assert_eq!(size("<*const T>::is_null", 1).krate(), "-");
+    }
+
+    #[test]
+    fn check_function_sizes() {
+        let functions: Vec<GotoFunction> = serde_json::from_value(serde_json::json!([
+            {
+                "name": "_RNvCs1234_4test5small",
+                "isBodyAvailable": true,
+                "isInternal": false,
+                "instructions": [{ "instructionId": "END_FUNCTION" }]
+            },
+            {
+                "name": "_RNvCs1234_4test5large",
+                "isBodyAvailable": true,
+                "isInternal": false,
+                "instructions": [
+                    { "instructionId": "ASSIGN" },
+                    { "instructionId": "END_FUNCTION" }
+                ]
+            },
+            { "name": "__CPROVER_initialize", "isBodyAvailable": true, "isInternal": true },
+            { "name": "malloc", "isBodyAvailable": false, "isInternal": true }
+        ]))
+        .unwrap();
+        let sizes = function_sizes(functions);
+        assert_eq!(sizes, [size("test::large", 2), size("test::small", 1)]);
+        let report = render("check", &sizes, 1);
+        assert!(report.contains("(3 instructions in 2 functions)"));
+        assert!(report.contains("test::large"));
+        assert!(report.contains("66.7%"));
+        assert!(!report.contains("test::small"));
+    }
+}
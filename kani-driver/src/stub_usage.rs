@@ -0,0 +1,108 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for reporting what the stubs of a harness replaced.
+//!
+//! A stub whose original function isn't reachable from the harness, or that is only reachable
+//! through a path the stub can't redirect, has no effect on verification. The compiler records
+//! the call sites that each stub redirected after reachability analysis, and we print them after
+//! the result of the harness, with a warning for the stubs that didn't redirect anything.
+
+use crate::session::KaniSession;
+use crate::util::format_warning;
+use kani_metadata::{HarnessMetadata, StubUsage};
+
+/// The models of `--use-abstractions` and `--race-checks`, which are added to every harness
+/// whether it uses the containers and threads or not.
+const MODEL_PREFIXES: [&str; 2] = ["kani::abstractions::", "kani::thread::"];
+
+impl KaniSession {
+    /// The call sites that each stub of a harness redirected.
+    pub fn report_stub_usage(&self, harness: &HarnessMetadata) -> String {
+        let usages: Vec<_> = harness.stub_usage.iter().filter(|usage| is_reported(usage)).collect();
+        if usages.is_empty() {
+            return String::new();
+        }
+        let mut output = format_stub_usage(&harness.pretty_name, &usages);
+        for usage in usages.iter().filter(|usage| usage.call_sites.is_empty()) {
+            let message = format!(
+                "The stub `{}` of `{}` in harness `{}` didn't replace any call: `{}` isn't \
+                reachable from the harness, so the stub has no effect.",
+                usage.replacement, usage.original, harness.pretty_name, usage.original
+            );
+            output.push_str(&format_warning(&message));
+            output.push('\n');
+        }
+        output
+    }
+}
+
+/// Whether the usage of a stub is reported. The models of `--use-abstractions` and
+/// `--race-checks` are only reported if they replaced a call.
+fn is_reported(usage: &StubUsage) -> bool {
+    !usage.call_sites.is_empty()
+        || !MODEL_PREFIXES.iter().any(|prefix| usage.replacement.starts_with(prefix))
+}
+
+/// The stubs of the harness `name`, each followed by the call sites it redirected.
+fn format_stub_usage(name: &str, usages: &[&StubUsage]) -> String {
+    let mut output = format!("Stubs of `{name}`:\n");
+    for usage in usages {
+        let count = match usage.call_sites.len() {
+            0 => "no call site".to_string(),
+            1 => "1 call site".to_string(),
+            count => format!("{count} call sites"),
+        };
+        output.push_str(&format!(
+            " - `{}` replaced by `{}` at {count}\n",
+            usage.original, usage.replacement
+        ));
+        for call_site in &usage.call_sites {
+            output.push_str(&format!("     - in {call_site}\n"));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_format_stub_usage() {
+        let usages = vec![
+            StubUsage {
+                original: "std::time::Instant::now".to_string(),
+                replacement: "mock_now".to_string(),
+                call_sites: vec![
+                    "elapsed at src/lib.rs:4:5".to_string(),
+                    "check_time at src/lib.rs:20:13".to_string(),
+                ],
+            },
+            StubUsage {
+                original: "rand::random".to_string(),
+                replacement: "mock_random".to_string(),
+                call_sites: vec![],
+            },
+            StubUsage {
+                original: "alloc::vec::Vec::<T>::new".to_string(),
+                replacement: "kani::abstractions::vec_new".to_string(),
+                call_sites: vec![],
+            },
+            StubUsage {
+                original: "pthread_detach".to_string(),
+                replacement: "kani::thread::pthread_detach".to_string(),
+                call_sites: vec![],
+            },
+        ];
+        let usages: Vec<_> = usages.iter().filter(|usage| is_reported(usage)).collect();
+        assert_eq!(
+            format_stub_usage("check_time", &usages),
+            "Stubs of `check_time`:\n \
+            - `std::time::Instant::now` replaced by `mock_now` at 2 call sites\n     \
+            - in elapsed at src/lib.rs:4:5\n     \
+            - in check_time at src/lib.rs:20:13\n \
+            - `rand::random` replaced by `mock_random` at no call site\n"
+        );
+    }
+}
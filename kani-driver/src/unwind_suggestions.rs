@@ -0,0 +1,321 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for diagnosing unwinding assertions that failed.
+//!
+//! An unwinding assertion fails when a loop (or a recursive call) may run more times than the
+//! unwinding bound of the harness. Besides pointing at the loops that were not fully unwound, Kani
+//! looks for the smallest bound that would be sufficient. It runs CBMC again on the same goto
+//! binary with a larger `--unwind` value, only checking the unwinding assertions, doubling the
+//! bound until the assertions hold and then bisecting. Since this happens after every harness
+//! whose unwinding assertions fail, the search is meant to be quick: it gives up when the bound
+//! exceeds [MAX_SUGGESTED_UNWIND] or once it has run for [SEARCH_BUDGET].
+//!
+//! With `--auto-unwind`, the same search runs before the verification instead, and the harness is
+//! then verified with the bound that was found. Its limits are set by `--auto-unwind-max` and
+//! `--auto-unwind-timeout`.
+
+use crate::args::OutputFormat;
+use crate::call_cbmc::{resolve_unwind_value, VerificationResult};
+use crate::cbmc_output_parser::{extract_results, process_cbmc_output, CheckStatus, Property};
+use crate::session::KaniSession;
+use anyhow::{bail, Result};
+use kani_metadata::HarnessMetadata;
+use std::ffi::OsString;
+use std::fmt::Write;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+/// The largest bound that Kani tries when looking for a sufficient one.
+const MAX_SUGGESTED_UNWIND: u32 = 128;
+/// The time that the search for a suggested bound may take for each harness. The CBMC process
+/// that is running when it runs out is killed.
+const SEARCH_BUDGET: Duration = Duration::from_secs(10);
+
+/// The outcome of the search for a sufficient bound.
+#[derive(Debug, PartialEq)]
+enum Suggestion {
+    /// The unwinding assertions hold with this bound.
+    Bound(u32),
+    /// No bound up to [MAX_SUGGESTED_UNWIND] is sufficient.
+    AboveLimit,
+    /// The search ran out of time or CBMC failed before a sufficient bound was found.
+    Inconclusive,
+}
+
+impl KaniSession {
+    /// The loops that failed their unwinding assertion (if any), together with a bound for
+    /// `#[kani::unwind]` that is enough to unwind them.
+    pub fn suggest_unwind(
+        &self,
+        binary: &Path,
+        harness: &HarnessMetadata,
+        verification_result: &VerificationResult,
+    ) -> String {
+        // The search already happened before the verification with `--auto-unwind`.
+        if self.args.auto_unwind
+            || matches!(self.args.output_format, OutputFormat::Old | OutputFormat::Json)
+        {
+            return String::new();
+        }
+        let Ok(properties) = &verification_result.results else { return String::new() };
+        let loops: Vec<_> = properties.iter().filter(|prop| is_failed_unwinding(prop)).collect();
+        if loops.is_empty() {
+            return String::new();
+        }
+        let mut output =
+            format!("Loops that were not fully unwound in `{}`:\n", harness.pretty_name);
+        for prop in &loops {
+            writeln!(output, "    {}", describe_loop(prop)).unwrap();
+        }
+        // Without a bound, CBMC unwinds loops until they terminate. The loops must then be
+        // bounded through `--cbmc-args`, which we can't adjust.
+        let Some(bound) = resolve_unwind_value(&self.args, harness) else { return output };
+        let deadline = Instant::now() + SEARCH_BUDGET;
+        let suggestion = search_bound(bound, MAX_SUGGESTED_UNWIND, |unwind| {
+            if Instant::now() >= deadline {
+                return None;
+            }
+            self.unwinding_holds(binary, harness, unwind, Some(deadline))
+                .map_err(|err| tracing::debug!(?err, unwind, "suggest_unwind"))
+                .ok()
+        });
+        match suggestion {
+            Suggestion::Bound(unwind) => write!(
+                output,
+                "The loops are fully unwound with a bound of {unwind}. Use \
+                `#[kani::unwind({unwind})]` on the harness, or pass `--unwind {unwind}`."
+            ),
+            Suggestion::AboveLimit => write!(
+                output,
+                "Kani could not find a sufficient bound up to {MAX_SUGGESTED_UNWIND}. The loops \
+                may not terminate, or their number of iterations may depend on an input that \
+                should be restricted with `kani::assume`."
+            ),
+            Suggestion::Inconclusive => write!(
+                output,
+                "Kani could not find a sufficient bound in time. Consider increasing the bound \
+                with `#[kani::unwind(<n>)]` on the harness."
+            ),
+        }
+        .unwrap();
+        output.push('\n');
+        output
+    }
+
+    /// Search for the bound to verify `harness` with when `--auto-unwind` is enabled, and return
+    /// it together with a message that describes the outcome of the search.
+    ///
+    /// The search starts from the bound of the harness, or from 1 if it has none. If no sufficient
+    /// bound is found, the harness is verified with the starting bound, so the loops that weren't
+    /// fully unwound are reported as failed unwinding assertions.
+    pub(crate) fn discover_unwind(
+        &self,
+        binary: &Path,
+        harness: &HarnessMetadata,
+    ) -> (u32, String) {
+        let start = resolve_unwind_value(&self.args, harness);
+        let limit = self.args.auto_unwind_max.unwrap_or(MAX_SUGGESTED_UNWIND);
+        let deadline =
+            self.args.auto_unwind_timeout.map(|secs| Instant::now() + Duration::from_secs(secs));
+        let holds = |unwind| {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return None;
+            }
+            self.unwinding_holds(binary, harness, unwind, deadline)
+                .map_err(|err| tracing::debug!(?err, unwind, "discover_unwind"))
+                .ok()
+        };
+        let suggestion = match start {
+            // The bound of the harness may already be enough, in which case it's kept.
+            Some(start) => match holds(start) {
+                Some(true) => Suggestion::Bound(start),
+                Some(false) => search_bound(start, limit, holds),
+                None => Suggestion::Inconclusive,
+            },
+            None => search_bound(0, limit, holds),
+        };
+        let name = &harness.pretty_name;
+        let fallback = start.unwrap_or(1);
+        match suggestion {
+            Suggestion::Bound(unwind) => (
+                unwind,
+                format!(
+                    "Found an unwinding bound of {unwind} for `{name}`. Add \
+                    `#[kani::unwind({unwind})]` to the harness to skip the search."
+                ),
+            ),
+            Suggestion::AboveLimit => (
+                fallback,
+                format!(
+                    "Kani could not find a sufficient unwinding bound up to {limit} for `{name}`, \
+                    so it's verified with a bound of {fallback}."
+                ),
+            ),
+            Suggestion::Inconclusive => (
+                fallback,
+                format!(
+                    "Kani could not find a sufficient unwinding bound for `{name}` in time, so \
+                    it's verified with a bound of {fallback}."
+                ),
+            ),
+        }
+    }
+
+    /// Run CBMC with the given bound and only the unwinding assertions enabled, and return whether
+    /// they all hold. Fails if CBMC is still running at `deadline`.
+    fn unwinding_holds(
+        &self,
+        binary: &Path,
+        harness: &HarnessMetadata,
+        unwind: u32,
+        deadline: Option<Instant>,
+    ) -> Result<bool> {
+        let mut args: Vec<OsString> = vec![
+            "--unwind".into(),
+            unwind.to_string().into(),
+            "--unwinding-assertions".into(),
+            // User assertions don't restrict the executions, so they can be ignored.
+            "--no-assertions".into(),
+            "--slice-formula".into(),
+        ];
+        if let Some(object_bits) = self.args.cbmc_object_bits() {
+            args.push("--object-bits".into());
+            args.push(object_bits.to_string().into());
+        }
+        self.handle_solver_args(&harness.attributes, &mut args)?;
+        args.extend(self.args.cbmc_args.iter().cloned());
+        args.push(binary.to_owned().into_os_string());
+        args.push("--json-ui".into());
+
+        let mut cmd = Command::new("cbmc");
+        cmd.args(args);
+        let output = match deadline {
+            Some(deadline) => {
+                let (expired, output) = self.run_cbmc_until(cmd, deadline, Some)?;
+                if expired {
+                    bail!("cbmc didn't check the bound {unwind} in time");
+                }
+                output
+            }
+            None => {
+                let Some(process) = self.run_piped(cmd)? else { bail!("Failed to run cbmc") };
+                process_cbmc_output(process, Some)?
+            }
+        };
+        let (_, results) = extract_results(output.processed_items);
+        let Some(properties) = results else {
+            bail!("cbmc exited with status {} without results", output.process_status)
+        };
+        Ok(properties
+            .iter()
+            .filter(|prop| is_unwinding(prop))
+            .all(|prop| prop.status == CheckStatus::Success))
+    }
+}
+
+fn is_unwinding(prop: &Property) -> bool {
+    matches!(prop.property_class().as_str(), "unwind" | "recursion")
+}
+
+pub(crate) fn is_failed_unwinding(prop: &Property) -> bool {
+    is_unwinding(prop) && prop.status == CheckStatus::Failure
+}
+
+fn describe_loop(prop: &Property) -> String {
+    if prop.property_class() == "recursion" {
+        format!("recursive call at {}", prop.source_location)
+    } else {
+        format!("loop {} at {}", prop.property_id.id, prop.source_location)
+    }
+}
+
+/// Find a bound above `failed`, which is known to be insufficient, for which `holds` returns
+/// `Some(true)`. Since larger bounds are sufficient whenever smaller ones are, we double the bound
+/// until it's sufficient and then bisect to find the smallest one. `holds` returns `None` if it
+/// can't decide, in which case we return the smallest sufficient bound found so far (if any).
+fn search_bound(failed: u32, limit: u32, mut holds: impl FnMut(u32) -> Option<bool>) -> Suggestion {
+    let mut insufficient = failed;
+    let mut sufficient = loop {
+        if insufficient >= limit {
+            return Suggestion::AboveLimit;
+        }
+        let candidate = insufficient.saturating_mul(2).clamp(insufficient + 1, limit);
+        match holds(candidate) {
+            Some(true) => break candidate,
+            Some(false) => insufficient = candidate,
+            None => return Suggestion::Inconclusive,
+        }
+    };
+    while sufficient - insufficient > 1 {
+        let candidate = insufficient + (sufficient - insufficient) / 2;
+        match holds(candidate) {
+            Some(true) => sufficient = candidate,
+            Some(false) => insufficient = candidate,
+            None => break,
+        }
+    }
+    Suggestion::Bound(sufficient)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::{PropertyId, SourceLocation};
+
+    fn property(class: &str, id: u32, status: CheckStatus) -> Property {
+        Property {
+            description: format!("unwinding assertion loop {id}"),
+            property_id: PropertyId {
+                fn_name: Some("check".to_string()),
+                class: class.to_string(),
+                id,
+            },
+            source_location: SourceLocation {
+                column: None,
+                file: None,
+                function: None,
+                line: None,
+            },
+            status,
+            reach: None,
+            trace: None,
+        }
+    }
+
+    #[test]
+    fn check_search_bound() {
+        let mut probes = vec![];
+        let result = search_bound(2, 128, |unwind| {
+            probes.push(unwind);
+            Some(unwind >= 37)
+        });
+        assert_eq!(result, Suggestion::Bound(37));
+        assert_eq!(probes, [4, 8, 16, 32, 64, 48, 40, 36, 38, 37]);
+    }
+
+    #[test]
+    fn check_search_bound_limits() {
+        assert_eq!(search_bound(0, 128, |unwind| Some(unwind >= 1)), Suggestion::Bound(1));
+        assert_eq!(search_bound(100, 128, |unwind| Some(unwind >= 101)), Suggestion::Bound(101));
+        assert_eq!(search_bound(2, 128, |_| Some(false)), Suggestion::AboveLimit);
+        assert_eq!(search_bound(128, 128, |_| Some(true)), Suggestion::AboveLimit);
+        assert_eq!(search_bound(2, 128, |_| None), Suggestion::Inconclusive);
+        // A sufficient bound is still useful if bisecting is interrupted.
+        let result = search_bound(2, 128, |unwind| (unwind != 6).then_some(unwind >= 8));
+        assert_eq!(result, Suggestion::Bound(8));
+    }
+
+    #[test]
+    fn check_failed_unwinding() {
+        assert!(is_failed_unwinding(&property("unwind", 0, CheckStatus::Failure)));
+        assert!(is_failed_unwinding(&property("recursion", 1, CheckStatus::Failure)));
+        assert!(!is_failed_unwinding(&property("unwind", 0, CheckStatus::Success)));
+        assert!(!is_failed_unwinding(&property("assertion", 0, CheckStatus::Failure)));
+        assert_eq!(
+            describe_loop(&property("unwind", 2, CheckStatus::Failure)),
+            "loop 2 at Unknown file"
+        );
+    }
+}
@@ -79,11 +79,37 @@ pub fn render_command(cmd: &Command) -> OsString {
     str
 }
 
+/// The memory available to start new processes in MiB, if it can be determined.
+/// This is only supported on Linux, where it is read from `/proc/meminfo`.
+pub fn available_memory_mib() -> Option<u64> {
+    parse_mem_available(&std::fs::read_to_string("/proc/meminfo").ok()?)
+}
+
+/// Extract the `MemAvailable` entry of `/proc/meminfo` (in kB) and convert it to MiB.
+fn parse_mem_available(meminfo: &str) -> Option<u64> {
+    let line = meminfo.lines().find_map(|line| line.strip_prefix("MemAvailable:"))?;
+    let kib: u64 = line.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some(kib / 1024)
+}
+
+/// The 64-bit FNV-1a hash of `bytes`, which, unlike the hasher of the standard library, is
+/// guaranteed to be the same across Rust versions and platforms.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(0xcbf29ce484222325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
+
 /// Print a warning message. This will add a "warning:" tag before the message and style accordingly.
 pub fn warning(msg: &str) {
+    println!("{}", format_warning(msg))
+}
+
+/// Format a warning message like [warning] does, for output that is printed at once later.
+pub fn format_warning(msg: &str) -> String {
     let warning = console::style("warning:").bold().yellow();
     let msg_fmt = console::style(msg).bold();
-    println!("{warning} {msg_fmt}")
+    format!("{warning} {msg_fmt}")
 }
 
 /// Print an error message. This will add an "error:" tag before the message and style accordingly.
@@ -125,6 +151,15 @@ fn check_guess_rlib_name() {
         assert_eq!(guess_rlib_name(Path::new("a/b/foo.rs")), PathBuf::from("a/b/libfoo.rlib"));
     }
 
+    #[test]
+    fn check_parse_mem_available() {
+        let meminfo = "MemTotal:       16315496 kB\n\
+                       MemFree:         1254884 kB\n\
+                       MemAvailable:    8388608 kB\n";
+        assert_eq!(parse_mem_available(meminfo), Some(8192));
+        assert_eq!(parse_mem_available("MemTotal:       16315496 kB\n"), None);
+    }
+
     #[test]
     fn check_exe_basename() {
         assert_eq!(
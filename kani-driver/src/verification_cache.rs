@@ -0,0 +1,184 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module for skipping the harnesses that were already verified in a previous run.
+//!
+//! With `--reachability=harnesses`, the default, the goto model of each harness only contains
+//! the code that is reachable from it. Together with the arguments given to CBMC and the
+//! attributes of the harness, it determines the result of the verification, so a harness whose
+//! model and options didn't change since it was verified successfully doesn't need to be
+//! verified again.
+//!
+//! The `--verification-cache` file records the fingerprint of each harness that was verified
+//! successfully, i.e., a hash of these inputs and of the version of Kani, together with the time
+//! the verification took:
+//!
+//! ```json
+//! {
+//!   "harnesses": {
+//!     "my_crate::check_foo": { "fingerprint": "5f1a0c2b9e3d4a67", "time": 1.25 }
+//!   }
+//! }
+//! ```
+//!
+//! Harnesses that fail or time out are removed from the file, so they are verified again in the
+//! next run.
+
+use crate::call_cbmc::{VerificationResult, VerificationStatus};
+use crate::harness_runner::HarnessResult;
+use crate::session::KaniSession;
+use crate::util::fnv1a;
+use anyhow::{Context, Result};
+use kani_metadata::HarnessMetadata;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// A harness that was verified successfully.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct CacheEntry {
+    /// The fingerprint of the goto model and options of the harness, in hexadecimal.
+    pub fingerprint: String,
+    /// The verification time of the harness in seconds.
+    pub time: f64,
+}
+
+/// The harnesses that were verified successfully, indexed by their name with the crate prefix.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct VerificationCache {
+    #[serde(default)]
+    pub harnesses: BTreeMap<String, CacheEntry>,
+}
+
+impl VerificationCache {
+    /// Read the cache at `path`. A file that doesn't exist yet has no harnesses.
+    pub fn read(path: &Path) -> Result<VerificationCache> {
+        if !path.exists() {
+            return Ok(VerificationCache::default());
+        }
+        let content = std::fs::read_to_string(path).with_context(|| {
+            format!("Failed to read the verification cache from `{}`", path.display())
+        })?;
+        serde_json::from_str(&content).with_context(|| {
+            format!("Failed to parse the verification cache from `{}`", path.display())
+        })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content).with_context(|| {
+            format!("Failed to write the verification cache to `{}`", path.display())
+        })
+    }
+
+    /// The result of `harness` if it was verified successfully with the same `fingerprint`.
+    pub fn get(&self, harness: &HarnessMetadata, fingerprint: &str) -> Option<VerificationResult> {
+        let entry = self.harnesses.get(&harness.get_harness_name_with_crate())?;
+        (entry.fingerprint == fingerprint)
+            .then(|| VerificationResult::cached_success(Duration::from_secs_f64(entry.time)))
+    }
+
+    /// Record the harnesses that were verified successfully and drop the ones that weren't.
+    pub fn update(&mut self, results: &[HarnessResult<'_>]) {
+        for result in results {
+            let name = result.harness.get_harness_name_with_crate();
+            match &result.fingerprint {
+                Some(fingerprint) if result.result.status == VerificationStatus::Success => {
+                    let time = result.result.runtime.as_secs_f64();
+                    self.harnesses
+                        .insert(name, CacheEntry { fingerprint: fingerprint.clone(), time });
+                }
+                _ => {
+                    self.harnesses.remove(&name);
+                }
+            }
+        }
+    }
+}
+
+impl KaniSession {
+    /// The cache of `--verification-cache`, or an empty cache if the argument wasn't given.
+    pub fn read_verification_cache(&self) -> Result<VerificationCache> {
+        match &self.args.verification_cache {
+            Some(path) => VerificationCache::read(path),
+            None => Ok(VerificationCache::default()),
+        }
+    }
+
+    /// Record the harnesses verified in this run in the `--verification-cache` file, keeping
+    /// the other harnesses.
+    pub fn update_verification_cache(&self, results: &[HarnessResult<'_>]) -> Result<()> {
+        let Some(path) = &self.args.verification_cache else { return Ok(()) };
+        let mut cache = VerificationCache::read(path)?;
+        cache.update(results);
+        cache.write(path)
+    }
+
+    /// The fingerprint of the verification of `harness` with the goto model in `goto_file`,
+    /// which must be fully instrumented. It covers everything that the result depends on:
+    /// the version of Kani, which fixes the version of CBMC, the arguments to CBMC, the
+    /// attributes of the harness, and the goto model.
+    pub fn harness_fingerprint(
+        &self,
+        goto_file: &Path,
+        harness: &HarnessMetadata,
+    ) -> Result<String> {
+        let mut data = env!("CARGO_PKG_VERSION").as_bytes().to_vec();
+        for arg in self.cbmc_flags(goto_file, harness)? {
+            data.extend(arg.as_encoded_bytes());
+            data.push(0);
+        }
+        data.extend(serde_json::to_vec(&harness.attributes)?);
+        // The unwinding bound that `--auto-unwind` finds isn't part of the attributes.
+        data.push(self.args.auto_unwind as u8);
+        data.extend(
+            std::fs::read(goto_file).with_context(|| {
+                format!("Failed to read the goto model `{}`", goto_file.display())
+            })?,
+        );
+        Ok(format!("{:016x}", fnv1a(&data)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::mock_proof_harness;
+
+    #[test]
+    fn check_cache_update() {
+        let harnesses = ["check_foo", "check_bar", "check_baz"]
+            .map(|name| mock_proof_harness(name, None, Some("krate"), None));
+        let mut failure = VerificationResult::mock_success();
+        failure.status = VerificationStatus::Failure;
+        let results = vec![
+            HarnessResult {
+                harness: &harnesses[0],
+                result: VerificationResult::mock_success(),
+                fingerprint: Some("01".to_string()),
+            },
+            HarnessResult {
+                harness: &harnesses[1],
+                result: failure,
+                fingerprint: Some("02".to_string()),
+            },
+        ];
+        let mut cache = VerificationCache::default();
+        for name in ["krate::check_bar", "krate::check_baz"] {
+            cache
+                .harnesses
+                .insert(name.to_string(), CacheEntry { fingerprint: "00".to_string(), time: 1.0 });
+        }
+        cache.update(&results);
+
+        assert_eq!(
+            cache.harnesses.keys().collect::<Vec<_>>(),
+            vec!["krate::check_baz", "krate::check_foo"]
+        );
+        assert!(cache.get(&harnesses[0], "01").is_some_and(|result| result.cached));
+        assert!(cache.get(&harnesses[0], "02").is_none());
+        assert!(cache.get(&harnesses[1], "02").is_none());
+        assert_eq!(cache.get(&harnesses[2], "00").unwrap().runtime, Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// An enum for the strategies CBMC's symbolic execution can use to explore the
+/// paths of a program.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    clap::ValueEnum
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum CbmcPathStrategy {
+    /// Explore all paths at once, merging them where they join (CBMC's default)
+    Merge,
+
+    /// Explore one path at a time, depth first (CBMC's `--paths lifo`)
+    Lifo,
+
+    /// Explore one path at a time, breadth first (CBMC's `--paths fifo`)
+    Fifo,
+}
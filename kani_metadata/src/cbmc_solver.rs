@@ -6,7 +6,8 @@
 
 /// An enum for CBMC solver options. All variants are handled by Kani, except for
 /// the `Binary` one, which it passes as is to CBMC's `--external-sat-solver`
-/// option.
+/// option, and the `Smt2` one, which it passes as is to CBMC's
+/// `--incremental-smt2-solver` option.
 #[derive(
     Debug,
     Clone,
@@ -15,6 +16,7 @@
     EnumVariantNames,
     PartialEq,
     Eq,
+    Hash,
     Serialize,
     Deserialize
 )]
@@ -29,8 +31,40 @@ pub enum CbmcSolver {
     /// MiniSAT (CBMC's default solver)
     Minisat,
 
+    /// Z3, used through CBMC's incremental SMT2 backend. `z3` must be in path.
+    Z3,
+
+    /// cvc5, used through CBMC's incremental SMT2 backend. `cvc5` must be in path.
+    Cvc5,
+
+    /// Bitwuzla, used through CBMC's incremental SMT2 backend. `bitwuzla` must
+    /// be in path.
+    Bitwuzla,
+
     /// A solver binary variant whose argument gets passed to
     /// `--external-sat-solver`. The specified binary must exist in path.
     #[strum(disabled, serialize = "bin=<SAT_SOLVER_BINARY>")]
     Binary(String),
+
+    /// A command that runs an SMT solver reading SMT-LIB 2 commands from its
+    /// standard input, e.g. `yices-smt2 --incremental`. It gets passed to
+    /// `--incremental-smt2-solver`. The program must exist in path.
+    #[strum(disabled, serialize = "smt2=<SMT_SOLVER_COMMAND>")]
+    Smt2(String),
+}
+
+impl CbmcSolver {
+    /// The command that CBMC runs for SMT solvers, which is `None` for SAT solvers.
+    pub fn smt2_command(&self) -> Option<&str> {
+        match self {
+            CbmcSolver::Z3 => Some("z3 --smt2 -in"),
+            CbmcSolver::Cvc5 => Some("cvc5 --lang=smt2 --incremental"),
+            CbmcSolver::Bitwuzla => Some("bitwuzla --lang smt2"),
+            CbmcSolver::Smt2(command) => Some(command),
+            CbmcSolver::Cadical
+            | CbmcSolver::Kissat
+            | CbmcSolver::Minisat
+            | CbmcSolver::Binary(_) => None,
+        }
+    }
 }
@@ -0,0 +1,35 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// How Kani models a call to a foreign function that has no definition, according to the FFI
+/// policy file given to `--ffi-policy`.
+///
+/// Foreign functions that aren't listed in the policy file keep the default behavior, which is
+/// to fail verification with an unsupported construct check when they are called.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum FfiPolicy {
+    /// The function returns an arbitrary value, and writes arbitrary values through the mutable
+    /// pointers that it receives.
+    Havoc,
+    /// The function returns zero, and writes zeros through the mutable pointers that it receives.
+    Zeroed,
+    /// The function must be stubbed. Compilation fails if it is reachable from a harness.
+    Stub,
+}
@@ -1,8 +1,9 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use crate::CbmcSolver;
+use crate::{CbmcPathStrategy, CbmcSolver, FfiPolicy};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// We emit this structure for each annotated proof harness (`#[kani::proof]`) we find.
@@ -24,6 +25,36 @@ pub struct HarnessMetadata {
     pub goto_file: Option<PathBuf>,
     /// The `#[kani::<>]` attributes added to a harness.
     pub attributes: HarnessAttributes,
+    /// The name of the function in the CBMC symbol table whose contract CBMC
+    /// should enforce when verifying this harness (e.g. the `modifies` clause of
+    /// the function targeted by `proof_for_contract`).
+    pub contract: Option<String>,
+    /// How the stubs of the harness were applied, as found during codegen.
+    pub stub_usage: Vec<StubUsage>,
+    /// The inline assembly blocks reachable from the harness, which `--ignore-asm` replaced with
+    /// nondeterministic outputs, e.g. `my_crate::rdtsc at src/lib.rs:4:5`.
+    pub havocked_asm: Vec<String>,
+    /// The symbolic inputs of the harness that `--concrete` fixed to a concrete value.
+    pub concrete_inputs: Vec<String>,
+    /// What the code reachable from the harness assumes, as found during codegen.
+    pub assumptions: HarnessAssumptions,
+}
+
+/// The assumptions that the code reachable from a harness makes, which restrict what its
+/// verification covers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct HarnessAssumptions {
+    /// The calls to `kani::assume` outside of the Kani library, e.g.
+    /// `my_crate::check at src/lib.rs:4:5`.
+    pub assumes: Vec<String>,
+    /// The foreign functions that are modeled according to `--ffi-policy`, by link name.
+    pub foreign_functions: BTreeMap<String, FfiPolicy>,
+}
+
+impl HarnessAssumptions {
+    pub fn is_empty(&self) -> bool {
+        self.assumes.is_empty() && self.foreign_functions.is_empty()
+    }
 }
 
 /// The attributes added by the user to control how a harness is executed.
@@ -33,12 +64,33 @@ pub struct HarnessAttributes {
     pub proof: bool,
     /// Whether the harness is expected to panic or not.
     pub should_panic: bool,
+    /// The substring of the description, or the name, of the panic that the harness is expected
+    /// to trigger (`#[kani::should_panic(expected = "...")]`).
+    pub expected_panic: Option<String>,
     /// Optional data to store solver.
     pub solver: Option<CbmcSolver>,
+    /// Optional strategy for exploring the paths of the harness.
+    pub path_strategy: Option<CbmcPathStrategy>,
     /// Optional data to store unwind value.
     pub unwind_value: Option<u32>,
+    /// The `default-unwind` of the `[package.metadata.kani]` table of the package of the harness,
+    /// if it isn't the package of the current manifest. It ranks below `--default-unwind`.
+    pub package_unwind: Option<u32>,
+    /// The `solver` of the `[package.metadata.kani]` table of the package of the harness, if it
+    /// isn't the package of the current manifest. It ranks below the `solver` attribute.
+    pub package_solver: Option<CbmcSolver>,
+    /// Optional time limit for verifying the harness, in seconds.
+    pub timeout: Option<u64>,
     /// The stubs used in this harness.
     pub stubs: Vec<Stub>,
+    /// The trait implementations whose methods are all stubbed in this harness.
+    pub trait_impl_stubs: Vec<TraitImplStub>,
+    /// The function whose contract this harness checks (`proof_for_contract`), as a path
+    /// prefixed with the crate name.
+    pub for_contract: Option<String>,
+    /// The functions that are replaced by their contracts in this harness (`stub_verified`),
+    /// named like [`Self::for_contract`].
+    pub verified_stubs: Vec<String>,
 }
 
 /// The stubbing type.
@@ -48,6 +100,24 @@ pub struct Stub {
     pub replacement: String,
 }
 
+/// The call sites that a stub redirected in the code reachable from a harness.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StubUsage {
+    pub original: String,
+    pub replacement: String,
+    /// The callers and locations of the redirected calls, e.g. `my_crate::foo at src/lib.rs:4:5`.
+    pub call_sites: Vec<String>,
+}
+
+/// Stubs every method of the implementation of `trait_name` for `self_type` by the function with
+/// the same name inside `replacement`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TraitImplStub {
+    pub trait_name: String,
+    pub self_type: String,
+    pub replacement: String,
+}
+
 impl HarnessMetadata {
     /// get the unqualifed (i.e. without ::) harness name. If the
     /// harness name contains ::, then we use rightmost name..
@@ -60,4 +130,20 @@ pub fn get_harness_name_unqualified(&self) -> &str {
             &self.pretty_name
         }
     }
+
+    /// Get the harness name prefixed by the name of its crate (e.g. `my_crate::module::check`),
+    /// which can be used to tell apart harnesses with the same name in different crates.
+    pub fn get_harness_name_with_crate(&self) -> String {
+        format!("{}::{}", self.crate_name, self.pretty_name)
+    }
+
+    /// Whether `name` refers to this harness exactly, either by its name or by its name prefixed
+    /// with the crate name.
+    pub fn is_named(&self, name: &str) -> bool {
+        self.pretty_name == name
+            || name
+                .strip_prefix(self.crate_name.as_str())
+                .and_then(|rest| rest.strip_prefix("::"))
+                .is_some_and(|rest| rest == self.pretty_name)
+    }
 }
@@ -8,12 +8,16 @@
 use serde::{Deserialize, Serialize};
 
 pub use artifact::ArtifactType;
+pub use cbmc_path_strategy::CbmcPathStrategy;
 pub use cbmc_solver::CbmcSolver;
+pub use ffi_policy::FfiPolicy;
 pub use harness::*;
 pub use vtable::*;
 
 pub mod artifact;
+mod cbmc_path_strategy;
 mod cbmc_solver;
+mod ffi_policy;
 mod harness;
 pub mod unstable;
 mod vtable;
@@ -82,6 +82,85 @@ pub enum UnstableFeature {
     LineCoverage,
     /// Enable function contracts [RFC 9](https://model-checking.github.io/kani/rfc/rfcs/0009-function-contracts.html)
     FunctionContracts,
+    /// Generate a graph of the memory objects and pointers at the failure point.
+    HeapGraph,
+    /// Suggest assumptions that exclude the input of a failure.
+    SuggestAssumptions,
+    /// Enable checks that bound the depth of the call stack.
+    StackDepth,
+    /// Enable the `cargo kani server` subcommand.
+    Server,
+    /// Enable loop contracts, i.e., use the invariants given with `#[kani::loop_invariant]`
+    /// instead of unwinding the annotated loops.
+    LoopContracts,
+    /// Allow Kani to synthesize harnesses that check the `Drop` implementations of a crate.
+    DropChecks,
+    /// Allow Kani to synthesize harnesses that check the laws of `PartialEq`, `Eq`, `Ord` and
+    /// `Hash` implementations.
+    TraitLaws,
+    /// Allow Kani to synthesize harnesses that check that `const fn`s don't panic when they are
+    /// called at runtime.
+    ConstFnChecks,
+    /// Allow Kani to check the UTF-8 invariant of strings at the unchecked conversions, and to
+    /// stop assuming it when generating arbitrary strings.
+    Utf8Checks,
+    /// Enable the `cargo kani cfg-report` subcommand.
+    CfgReport,
+    /// Stop the verification of a harness after `--harness-timeout` and report the properties
+    /// that were checked in time.
+    HarnessTimeout,
+    /// Allow users to choose how Kani models undefined foreign functions with `--ffi-policy`.
+    FfiPolicy,
+    /// Search for an unwinding bound that is sufficient for each harness with `--auto-unwind`.
+    AutoUnwind,
+    /// Check that integer `as` casts don't truncate values or change their sign.
+    LossyCastChecks,
+    /// Check that the values that transmutes, raw pointer reads, union reads and foreign
+    /// functions produce for enum types have a valid discriminant and payload.
+    EnumValidityChecks,
+    /// Verify crates for a target other than the host, e.g., an embedded target, with `--target`.
+    CustomTargets,
+    /// Check for data races between the threads spawned with `kani::thread::spawn`.
+    RaceChecks,
+    /// Verify threads under a weak memory model with `--memory-model`.
+    MemoryModel,
+    /// Enable the `cargo kani estimate` subcommand.
+    Estimate,
+    /// Split the harnesses across several runs with `--partition`, and record their verification
+    /// time with `--timings`.
+    Partitioning,
+    /// Make the iteration order of `HashMap` and `HashSet` nondeterministic with
+    /// `--nondet-hash-order`.
+    HashOrder,
+    /// Skip the harnesses that were already verified with the same goto model and options with
+    /// `--verification-cache`.
+    VerificationCache,
+    /// Record the results of the harnesses in a `--baseline` file, and only fail on regressions
+    /// against it with `--check-against-baseline`.
+    Baseline,
+    /// Check that the harnesses don't branch or access memory depending on values marked with
+    /// `kani::secret` with `--constant-time-checks`.
+    ConstantTimeChecks,
+    /// Verify the harnesses in `#[cfg(test)]` modules with `--test-modules`.
+    TestModules,
+    /// Stub the standard containers with the models of `kani::abstractions` with
+    /// `--use-abstractions`.
+    Abstractions,
+    /// Encode arrays with the array theory of the solver with `--array-theory`.
+    ArrayTheory,
+    /// Restrict the floats generated by `kani::any` with `--float-values`.
+    FloatValues,
+    /// Print the largest functions of the model of each harness with `--size-report`.
+    SizeReport,
+    /// Replace inline assembly with nondeterministic outputs with `--ignore-asm`.
+    IgnoreAsm,
+    /// Fix symbolic inputs of the harnesses to concrete values with `--concrete`.
+    ConcreteInputs,
+    /// Check that the harnesses don't read uninitialized memory with `--check uninit`.
+    UninitChecks,
+    /// Check that pointer arithmetic stays in bounds and that pointers aren't created from
+    /// integers with `--check pointer-provenance`.
+    PointerProvenanceChecks,
 }
 
 impl UnstableFeature {
@@ -0,0 +1,93 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Models of the standard containers that are cheaper to verify than their implementation. With
+//! `--use-abstractions`, Kani stubs the functions of the standard library with these models in
+//! every harness:
+//!
+//! - `Vec::new` allocates a backing array of [VEC_CAPACITY] elements up front, so the vector
+//!   doesn't reallocate and copy its elements until it grows past this capacity. The vector is
+//!   otherwise unchanged, so pushing more elements is still supported.
+//! - The hash of the keys of `HashMap` and `HashSet` with their default hasher is computed with
+//!   a multiplicative hash instead of SipHash, starting from a nondeterministic seed. Equal keys
+//!   still have equal hashes, and the seed covers many orders of iteration over the elements.
+//!
+//! The models keep the documented behavior of the containers, but not the unspecified one: e.g.,
+//! the capacity of a new vector isn't 0.
+//!
+//! The stubs are applied after the harnesses are compiled, so these functions don't need to be
+//! called directly.
+
+use alloc::vec::Vec;
+
+/// The capacity of the vectors created by `Vec::new` with `--use-abstractions`.
+pub const VEC_CAPACITY: usize = 16;
+
+/// Model for `Vec::new`, which allocates a backing array of [VEC_CAPACITY] elements.
+pub fn vec_new<T>() -> Vec<T> {
+    Vec::with_capacity(VEC_CAPACITY)
+}
+
+#[cfg(feature = "std")]
+pub use hash::hash_one;
+
+#[cfg(feature = "std")]
+mod hash {
+    use core::hash::{Hash, Hasher};
+    use std::collections::hash_map::RandomState;
+
+    /// The seed of the hashes, picked the first time the harness hashes a value.
+    static mut SEED: Option<u64> = None;
+
+    /// A hasher that multiplies its state with a constant after adding each value, like the
+    /// hasher of `rustc_hash`. Integers are hashed as a whole rather than byte by byte.
+    struct AbstractHasher(u64);
+
+    impl AbstractHasher {
+        fn add(&mut self, value: u64) {
+            self.0 = (self.0.rotate_left(5) ^ value).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+        }
+    }
+
+    impl Hasher for AbstractHasher {
+        fn write(&mut self, bytes: &[u8]) {
+            for byte in bytes {
+                self.add(*byte as u64);
+            }
+        }
+
+        fn write_u8(&mut self, i: u8) {
+            self.add(i as u64);
+        }
+
+        fn write_u16(&mut self, i: u16) {
+            self.add(i as u64);
+        }
+
+        fn write_u32(&mut self, i: u32) {
+            self.add(i as u64);
+        }
+
+        fn write_u64(&mut self, i: u64) {
+            self.add(i);
+        }
+
+        fn write_usize(&mut self, i: usize) {
+            self.add(i as u64);
+        }
+
+        fn finish(&self) -> u64 {
+            self.0
+        }
+    }
+
+    /// Model for `<RandomState as BuildHasher>::hash_one`, which `HashMap` and `HashSet` use to
+    /// hash their keys.
+    pub fn hash_one<T: Hash>(_state: &RandomState, x: T) -> u64 {
+        // SAFETY: The seed is only accessed here, and it never changes once it is set.
+        let seed = unsafe { *SEED.get_or_insert_with(crate::any) };
+        let mut hasher = AbstractHasher(seed);
+        x.hash(&mut hasher);
+        hasher.finish()
+    }
+}
@@ -4,13 +4,15 @@
 //! This module introduces the Arbitrary trait as well as implementation for primitive types and
 //! other std containers.
 
-use std::{
+use alloc::boxed::Box;
+use core::{
     marker::{PhantomData, PhantomPinned},
     num::*,
 };
 
 /// This trait should be used to generate symbolic variables that represent any valid value of
 /// its type.
+#[rustc_diagnostic_item = "KaniArbitrary"]
 pub trait Arbitrary
 where
     Self: Sized,
@@ -20,7 +22,7 @@ pub trait Arbitrary
     // the requirement defined in the where clause must appear on the `impl`'s method `any_array`
     // but also on the corresponding trait's method
     where
-        [(); std::mem::size_of::<[Self; MAX_ARRAY_LENGTH]>()]:,
+        [(); core::mem::size_of::<[Self; MAX_ARRAY_LENGTH]>()]:,
     {
         [(); MAX_ARRAY_LENGTH].map(|_| Self::any())
     }
@@ -33,18 +35,18 @@ impl Arbitrary for $type {
             #[inline(always)]
             fn any() -> Self {
                 // This size_of call does not use generic_const_exprs feature. It's inside a macro, and Self isn't generic.
-                unsafe { crate::any_raw_internal::<Self, { std::mem::size_of::<Self>() }>() }
+                unsafe { crate::any_raw_internal::<Self, { core::mem::size_of::<Self>() }>() }
             }
             fn any_array<const MAX_ARRAY_LENGTH: usize>() -> [Self; MAX_ARRAY_LENGTH]
             where
                 // `generic_const_exprs` requires all potential errors to be reflected in the signature/header.
                 // We must repeat the expression in the header, to make sure that if the body can fail the header will also fail.
-                [(); { std::mem::size_of::<[$type; MAX_ARRAY_LENGTH]>() }]:,
+                [(); { core::mem::size_of::<[$type; MAX_ARRAY_LENGTH]>() }]:,
             {
                 unsafe {
                     crate::any_raw_internal::<
                         [Self; MAX_ARRAY_LENGTH],
-                        { std::mem::size_of::<[Self; MAX_ARRAY_LENGTH]>() },
+                        { core::mem::size_of::<[Self; MAX_ARRAY_LENGTH]>() },
                     >()
                 }
             }
@@ -66,10 +68,28 @@ fn any() -> Self {
 trivial_arbitrary!(i128);
 trivial_arbitrary!(isize);
 
-// We do not constraint floating points values per type spec. Users must add assumptions to their
-// verification code if they want to eliminate NaN, infinite, or subnormal.
-trivial_arbitrary!(f32);
-trivial_arbitrary!(f64);
+/// Floats may be NaN, infinite or subnormal, unless they are restricted with `--float-values`.
+/// Harnesses can also restrict them with [crate::any_finite_f32] and similar functions.
+macro_rules! float_arbitrary {
+    ( $type: ty ) => {
+        impl Arbitrary for $type {
+            #[inline(always)]
+            fn any() -> Self {
+                let val =
+                    unsafe { crate::any_raw_internal::<Self, { core::mem::size_of::<Self>() }>() };
+                crate::internal::assume_float_values(
+                    val.is_nan(),
+                    val.is_infinite(),
+                    val.is_subnormal(),
+                );
+                val
+            }
+        }
+    };
+}
+
+float_arbitrary!(f32);
+float_arbitrary!(f64);
 
 trivial_arbitrary!(());
 
@@ -124,7 +144,7 @@ fn any() -> Self {
 impl<T, const N: usize> Arbitrary for [T; N]
 where
     T: Arbitrary,
-    [(); std::mem::size_of::<[T; N]>()]:,
+    [(); core::mem::size_of::<[T; N]>()]:,
 {
     fn any() -> Self {
         T::any_array()
@@ -150,19 +170,19 @@ fn any() -> Self {
     }
 }
 
-impl<T: ?Sized> Arbitrary for std::marker::PhantomData<T> {
+impl<T: ?Sized> Arbitrary for core::marker::PhantomData<T> {
     fn any() -> Self {
         PhantomData
     }
 }
 
-impl Arbitrary for std::marker::PhantomPinned {
+impl Arbitrary for core::marker::PhantomPinned {
     fn any() -> Self {
         PhantomPinned
     }
 }
 
-impl<T> Arbitrary for std::boxed::Box<T>
+impl<T> Arbitrary for Box<T>
 where
     T: Arbitrary,
 {
@@ -171,11 +191,11 @@ fn any() -> Self {
     }
 }
 
-impl Arbitrary for std::time::Duration {
+impl Arbitrary for core::time::Duration {
     fn any() -> Self {
         const NANOS_PER_SEC: u32 = 1_000_000_000;
         let nanos = u32::any();
         crate::assume(nanos < NANOS_PER_SEC);
-        std::time::Duration::new(u64::any(), nanos)
+        core::time::Duration::new(u64::any(), nanos)
     }
 }
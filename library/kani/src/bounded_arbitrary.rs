@@ -0,0 +1,100 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module introduces the `BoundedArbitrary` trait, which generates symbolic values of types
+//! whose size is not fixed, such as vectors and strings, up to a given bound.
+
+use crate::{any_where, Arbitrary};
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+/// This trait should be used to generate symbolic variables that represent any valid value of
+/// its type whose size is at most `N`.
+///
+/// What the bound limits depends on the type: it is the number of elements of a collection and
+/// the number of bytes of a string.
+///
+/// Note that building these values iterates over the bound, so the harness must unwind loops at
+/// least `N + 1` times.
+pub trait BoundedArbitrary {
+    fn bounded_any<const N: usize>() -> Self;
+}
+
+impl<T: Arbitrary> BoundedArbitrary for Vec<T> {
+    fn bounded_any<const N: usize>() -> Self {
+        let len: usize = any_where(|len| *len <= N);
+        let elems: Box<[T; N]> = Box::new(core::array::from_fn(|_| T::any()));
+        let mut vec = <[T]>::into_vec(elems);
+        vec.truncate(len);
+        vec
+    }
+}
+
+impl<T: Arbitrary> BoundedArbitrary for Box<[T]> {
+    fn bounded_any<const N: usize>() -> Self {
+        Vec::bounded_any::<N>().into_boxed_slice()
+    }
+}
+
+impl<T: Arbitrary> BoundedArbitrary for VecDeque<T> {
+    fn bounded_any<const N: usize>() -> Self {
+        Vec::bounded_any::<N>().into()
+    }
+}
+
+impl BoundedArbitrary for String {
+    fn bounded_any<const N: usize>() -> Self {
+        let bytes = Vec::<u8>::bounded_any::<N>();
+        crate::internal::assume_utf8(&bytes);
+        // SAFETY: We just assumed that the bytes are valid UTF-8, unless the user opted out of
+        // the invariant with `--no-utf8-assumptions`.
+        unsafe { String::from_utf8_unchecked(bytes) }
+    }
+}
+
+impl<T: Arbitrary + Ord> BoundedArbitrary for BTreeSet<T> {
+    fn bounded_any<const N: usize>() -> Self {
+        Vec::bounded_any::<N>().into_iter().collect()
+    }
+}
+
+impl<K: Arbitrary + Ord, V: Arbitrary> BoundedArbitrary for BTreeMap<K, V> {
+    fn bounded_any<const N: usize>() -> Self {
+        Vec::<(K, V)>::bounded_any::<N>().into_iter().collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> BoundedArbitrary for HashSet<T, S>
+where
+    T: Arbitrary + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn bounded_any<const N: usize>() -> Self {
+        Vec::bounded_any::<N>().into_iter().collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> BoundedArbitrary for HashMap<K, V, S>
+where
+    K: Arbitrary + Eq + Hash,
+    V: Arbitrary,
+    S: BuildHasher + Default,
+{
+    fn bounded_any<const N: usize>() -> Self {
+        Vec::<(K, V)>::bounded_any::<N>().into_iter().collect()
+    }
+}
+
+impl<T: BoundedArbitrary> BoundedArbitrary for Option<T> {
+    fn bounded_any<const N: usize>() -> Self {
+        if bool::any() { Some(T::bounded_any::<N>()) } else { None }
+    }
+}
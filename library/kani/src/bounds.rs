@@ -0,0 +1,60 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for declaring the numeric envelope that a harness is verified against.
+//!
+//! Harnesses over large arithmetic (e.g. [Duration](core::time::Duration) helpers or wide
+//! integers) often need to constrain their inputs to be tractable. Using [crate::bounds!]
+//! instead of plain [crate::assume] keeps all these constraints in one place, and Kani
+//! reports each of them in the verification summary so they can be audited.
+//!
+//! Each bound is also checked for vacuity: if no value in the declared range can reach the
+//! bound, it is reported as unsatisfiable.
+
+/// Declares a numeric envelope for one or more variables.
+///
+/// Each `variable in range` pair is assumed to hold for the rest of the harness, where `range`
+/// can be any of Rust's range expressions (e.g. `0..10`, `..=MAX`, or `a..`). The bounds are
+/// listed in the verification results, and reported as unsatisfiable if they exclude every
+/// possible value of their variable.
+///
+/// # Example:
+///
+/// ```rust
+/// let secs: u64 = kani::any();
+/// let nanos: u32 = kani::any();
+/// kani::bounds!(secs in 0..=3600, nanos in 0..1_000_000_000);
+/// let duration = std::time::Duration::new(secs, nanos);
+/// assert!(duration.checked_add(duration).is_some());
+/// ```
+#[macro_export]
+macro_rules! bounds {
+    ($($var:ident in $range:expr),+ $(,)?) => {
+        $(
+            $crate::bounds::assume_bound(
+                ::core::ops::RangeBounds::contains(&$range, &$var),
+                concat!(stringify!($var), " in ", stringify!($range)),
+            );
+        )+
+    };
+}
+
+/// Assumes `cond` and records it as a bound of the harness with the given description.
+///
+/// This should only be used through the [crate::bounds!] macro.
+#[doc(hidden)]
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniAssumeBound"]
+#[cfg(not(feature = "concrete_playback"))]
+pub fn assume_bound(cond: bool, msg: &'static str) {
+    let _ = cond;
+    let _ = msg;
+}
+
+#[doc(hidden)]
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniAssumeBound"]
+#[cfg(feature = "concrete_playback")]
+pub fn assume_bound(cond: bool, msg: &'static str) {
+    assert!(cond, "`kani::bounds` should always hold: {msg}");
+}
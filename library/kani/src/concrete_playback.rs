@@ -3,9 +3,10 @@
 
 //! Helper code for concrete playback.
 
-use std::cell::RefCell;
+use alloc::vec::Vec;
+use core::cell::RefCell;
 
-thread_local! {
+std::thread_local! {
     /// thread_local! gives us a separate CONCRETE_VALS instance for each thread.
     /// This allows us to run concrete playback unit tests in parallel.
     /// RefCell is necessary for mut statics.
@@ -61,5 +62,5 @@ pub(crate) unsafe fn any_raw_internal<T, const SIZE_T: usize>() -> T {
     let bytes_t: [u8; SIZE_T] = next_concrete_val.try_into().expect(&format!(
         "Expected {SIZE_T} bytes instead of {next_concrete_val_len} bytes in the following det vals vec"
     ));
-    std::mem::transmute_copy::<[u8; SIZE_T], T>(&bytes_t)
+    core::mem::transmute_copy::<[u8; SIZE_T], T>(&bytes_t)
 }
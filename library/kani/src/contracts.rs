@@ -14,7 +14,8 @@
 //! are two parts to it.
 //!
 //! 1. [Contract specification attributes](#specification-attributes-overview):
-//!    [`requires`][macro@requires] and [`ensures`][macro@ensures].
+//!    [`requires`][macro@requires], [`ensures`][macro@ensures] and
+//!    [`modifies`][macro@modifies].
 //! 2. [Contract use attributes](#contract-use-attributes-overview):
 //!    [`proof_for_contract`][macro@proof_for_contract] and
 //!    [`stub_verified`][macro@stub_verified].
@@ -60,6 +61,40 @@
 //! also has access to the value returned from the function in a variable called
 //! `result`.
 //!
+//! The postcondition must not move `result`, which rules out methods like
+//! `Option::is_some_and` on a `result` that is not `Copy`. The
+//! [`matches_ok!`](crate::matches_ok), [`matches_err!`](crate::matches_err) and
+//! [`matches_some!`](crate::matches_some) macros match a `Result` or `Option`
+//! by reference instead:
+//!
+//! ```
+//! #[kani::ensures(
+//!   kani::matches_ok!(result, v if v.len() == n as usize)
+//!     || kani::matches_err!(result, e if *e == n)
+//! )]
+//! fn zeros(n: u8) -> Result<Vec<u8>, u8> {
+//!   if n <= 4 { Ok(vec![0; n as usize]) } else { Err(n) }
+//! }
+//! ```
+//!
+//! If the function modifies memory through a mutable reference, the
+//! postcondition can compare the new contents to the ones the function started
+//! with by wrapping an expression in `old(..)`. The wrapped expression is
+//! evaluated when the function is entered, while the rest of the postcondition
+//! sees the state on exit, e.g. `*counter` and `old(*counter)` for a `counter:
+//! &mut u32` are its final and initial value. If the wrapped expression is a
+//! place, such as `old(*v)` or `old(self.items)`, it is cloned, which allows
+//! relating values that are not `Copy`, like the contents of a `v: &mut
+//! Vec<u8>`. Other expressions, like `old(v.len())`, must produce an owned
+//! value.
+//!
+//! ```
+//! #[kani::ensures(*counter == old(*counter) + 1)]
+//! fn increment(counter: &mut u32) {
+//!   *counter += 1
+//! }
+//! ```
+//!
 //! You may combine as many [`requires`][macro@requires] and
 //! [`ensures`][macro@ensures] attributes on a single function as you please.
 //! They all get enforced (as if their conditions were `&&`ed together) and the
@@ -118,13 +153,26 @@
 //!
 //! ## Specification Attributes Overview
 //!
-//! There are currently two specification attributes available for describing
-//! function behavior: [`requires`][macro@requires] for preconditions and
-//! [`ensures`][macro@ensures] for postconditions. Both admit arbitrary Rust
-//! expressions as their bodies which may also reference the function arguments
-//! but must not mutate memory or perform I/O. The postcondition may
-//! additionally reference the return value of the function as the variable
-//! `result`.
+//! There are currently three specification attributes available for describing
+//! function behavior: [`requires`][macro@requires] for preconditions,
+//! [`ensures`][macro@ensures] for postconditions and
+//! [`modifies`][macro@modifies] for the memory the function writes to. The
+//! first two admit arbitrary Rust expressions as their bodies which may also
+//! reference the function arguments but must not mutate memory or perform I/O.
+//! The postcondition may additionally reference the return value of the
+//! function as the variable `result`.
+//!
+//! A [`modifies`][macro@modifies] attribute names arguments that are mutable
+//! references or raw pointers, e.g. `#[kani::modifies(counter)]` on the
+//! `increment` function from above. When the contract is checked, Kani reports
+//! every write to memory that is not behind one of these arguments (writes to
+//! the function's own local variables are always allowed). When the contract
+//! is used as a stub, the memory behind the arguments is overwritten with
+//! arbitrary values that must implement `kani::Arbitrary`, and any
+//! [`ensures`][macro@ensures] clause then constrains them. Without a
+//! `modifies` attribute the stub leaves all memory unchanged. Checking a
+//! `modifies` clause is done by CBMC and requires the harness to target only
+//! one function with such a clause.
 //!
 //! During verified stubbing the return value of a function with a contract is
 //! replaced by a call to `kani::any`. As such the return value must implement
@@ -152,9 +200,14 @@
 //! The checking harness is expected to set up the arguments that `foo` should
 //! be called with and initialized any `static mut` globals that are reachable.
 //! All of these should be initialized to as general value as possible, usually
-//! achieved using `kani::any`. The harness must call e.g. `foo` at least once
-//! and if `foo` has type parameters, only one instantiation of those parameters
-//! is admissible. Violating either results in a compile error.
+//! achieved using `kani::any`. The harness must call e.g. `foo` at least once.
+//!
+//! If `foo` has type parameters the harness may call several instantiations of
+//! it and the contract is checked separately for each of them. The
+//! descriptions of the checks of a generic function name the instantiation
+//! they belong to, e.g. `result >= x (with T = i8)`, so a failure can be traced
+//! back to the type arguments that caused it. The same holds for the
+//! precondition checks of a generic function replaced by `stub_verified`.
 //!
 //! If any inputs have special invariants you *can* use `kani::assume` to
 //! enforce them but this may introduce unsoundness. In general all restrictions
@@ -171,13 +224,98 @@
 //! Unlike `proof_for_contract` multiple `stub_verified` attributes are allowed
 //! on the same proof harness though they must target different functions.
 //!
+//! A verified stub is only as good as the check of its contract. Whenever a
+//! harness that uses `stub_verified(foo)` is verified, Kani also verifies the
+//! `proof_for_contract(foo)` harnesses, whether or not they were selected with
+//! `--harness`. If `foo` has no such harness, Kani warns that the contract is
+//! used without being checked.
+//!
+//! ## Contracts of Other Crates
+//!
+//! The functions of other crates can't be annotated, but they can still be
+//! given a contract with a spec file, passed to Kani with `--contract-specs`.
+//! Its `[contracts]` table maps the path of each function to a spec function
+//! of the crate under verification, which has the same signature and carries
+//! the specification attributes:
+//!
+//! ```toml
+//! [contracts]
+//! "regex::Regex::is_match" = "crate::specs::is_match"
+//! ```
+//!
+//! ```ignore
+//! #[cfg(kani)]
+//! mod specs {
+//!     #[kani::requires(!text.is_empty())]
+//!     pub fn is_match(regex: &regex::Regex, text: &str) -> bool {
+//!         unreachable!()
+//!     }
+//! }
+//! ```
+//!
+//! Every harness then uses the contract instead of the function, like with
+//! `stub_verified`. The body of the spec function is never used, and the
+//! contract is assumed rather than verified, so it should be reviewed as
+//! carefully as any other stub.
+//!
+//! ## Contracts on Trait Methods
+//!
+//! Specification attributes may also be placed on the declaration of a trait
+//! method without a default body. Every implementation of the trait inherits
+//! the contract, whether the method is called on a concrete type or through a
+//! `dyn` trait object:
+//!
+//! ```
+//! trait Counter {
+//!     #[kani::requires(step > 0)]
+//!     #[kani::ensures(result > old(self.value()))]
+//!     fn advance(&mut self, step: u32) -> u32;
+//!
+//!     fn value(&self) -> u32;
+//! }
+//! ```
+//!
+//! A `proof_for_contract(Counter::advance)` harness checks the contract for
+//! the implementations that it calls and `stub_verified(Counter::advance)`
+//! replaces the calls to every implementation with the contract. Like for
+//! functions, the recursive calls of an implementation are replaced by the
+//! contract while it is checked (see [Inductive
+//! Verification](#inductive-verification)), including the calls through
+//! another implementation. The arguments of such a method must be plain names
+//! and implementations cannot strengthen or weaken the inherited contract.
+//!
+//! ## Contracts on Closures
+//!
+//! [`requires`][macro@requires] and [`ensures`][macro@ensures] may also be
+//! placed on closure expressions, which requires the unstable
+//! `stmt_expr_attributes` and `proc_macro_hygiene` features. A closure can't be
+//! named in `proof_for_contract` or `stub_verified`, so its contract is instead
+//! checked every time the closure is called: the precondition is asserted
+//! before the body runs and the postcondition after it returns.
+//!
+//! ```ignore
+//! let double = #[kani::requires(x < 100)] #[kani::ensures(result > x)] |x: u32| x * 2 + 1;
+//! ```
+//!
 //! ## Inductive Verification
 //!
 //! Function contracts by default use inductive verification to efficiently
 //! verify recursive functions. In inductive verification a recursive function
 //! is executed once and every recursive call instead uses the contract
 //! replacement. In this way many recursive calls can be checked with a
-//! single verification pass.
+//! single verification pass. Every instantiation of a generic function is its
+//! own induction: a call to `foo::<u8>` made while checking `foo::<u16>` runs
+//! the check of `foo::<u8>` rather than its replacement.
+//!
+//! Induction on its own proves that the contract holds whenever the function
+//! returns, but not that it returns at all. A [`decreases`][macro@decreases]
+//! attribute gives a measure over the arguments, e.g. `#[kani::decreases(n)]`
+//! on a function that calls itself with `n - 1`, that must be smaller at every
+//! recursive call than at the call that the harness makes.
+//! Kani asserts this at each recursive call, which together with an unsigned
+//! measure proves that the recursion terminates. A measure with several comma
+//! separated components is compared lexicographically. The same attribute can
+//! be placed on a `while` loop with a [`loop_invariant`][macro@crate::loop_invariant].
 //!
 //! The downside of inductive verification is that the return value of a
 //! contracted function must implement `kani::Arbitrary`. Due to restrictions to
@@ -189,4 +327,4 @@
 //! If you feel strongly about this issue you can join the discussion on issue
 //! [#2823](https://github.com/model-checking/kani/issues/2823) to enable
 //! opt-out of inductive verification.
-pub use super::{ensures, proof_for_contract, requires, stub_verified};
+pub use super::{decreases, ensures, modifies, proof_for_contract, requires, stub_verified};
@@ -0,0 +1,54 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Generation of fixed-capacity containers, such as the ones of the `heapless` and `arrayvec`
+//! crates, which are common in `no_std` code:
+//!
+//! ```rust
+//! let vec: heapless::Vec<u8, 4> = kani::fixed_capacity::any_collection::<_, _, 4>();
+//! let name: arrayvec::ArrayString<8> = kani::fixed_capacity::any_str::<_, 8>();
+//! ```
+//!
+//! The Kani library is compiled ahead of time, so it can't implement [Arbitrary] for the types
+//! of the versions of these crates that a project depends on. These functions only rely on
+//! standard traits instead, which such containers implement. The bound `N` is usually the
+//! capacity of the container: a larger bound makes the conversion panic once the container is
+//! full, which Kani reports as a failure of the harness.
+//!
+//! Neither function allocates, but they both iterate over the bound, so the harness must unwind
+//! loops at least `N + 1` times.
+
+use crate::{any_where, Arbitrary};
+use core::str::FromStr;
+
+/// Generates a collection of at most `N` arbitrary elements, e.g., a `heapless::Vec<T, N>`, an
+/// `arrayvec::ArrayVec<T, N>` or a `heapless::LinearMap<K, V, N>`.
+pub fn any_collection<C, T, const N: usize>() -> C
+where
+    C: FromIterator<T>,
+    T: Arbitrary,
+{
+    let len: usize = any_where(|len| *len <= N);
+    let elems: [T; N] = core::array::from_fn(|_| T::any());
+    elems.into_iter().take(len).collect()
+}
+
+/// Generates a string of at most `N` bytes, e.g., a `heapless::String<N>` or an
+/// `arrayvec::ArrayString<N>`.
+///
+/// # Panics
+///
+/// This function panics if `S` can't hold the string, i.e., if `N` exceeds its capacity.
+pub fn any_str<S: FromStr, const N: usize>() -> S {
+    let len: usize = any_where(|len| *len <= N);
+    let bytes: [u8; N] = core::array::from_fn(|_| u8::any());
+    let bytes = &bytes[..len];
+    crate::internal::assume_utf8(bytes);
+    // SAFETY: We just assumed that the bytes are valid UTF-8, unless the user opted out of the
+    // invariant with `--no-utf8-assumptions`.
+    let string = unsafe { core::str::from_utf8_unchecked(bytes) };
+    match S::from_str(string) {
+        Ok(value) => value,
+        Err(_) => panic!("the bound of `kani::fixed_capacity::any_str` exceeds the capacity"),
+    }
+}
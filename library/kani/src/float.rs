@@ -0,0 +1,51 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Generators of floats that restrict which special values they may be.
+//!
+//! `kani::any::<f64>()` may be any value of the type, including NaN, the infinities and the
+//! subnormal numbers. Harnesses for code that doesn't handle these values can use the functions
+//! of this module instead, or restrict every float of the harness with `--float-values`.
+
+use crate::Arbitrary;
+
+/// Create an `f32` that is neither NaN nor infinite.
+#[inline(always)]
+pub fn any_finite_f32() -> f32 {
+    let val = f32::any();
+    crate::assume(val.is_finite());
+    val
+}
+
+/// Create an `f64` that is neither NaN nor infinite.
+///
+/// # Example:
+///
+/// ```no_run
+/// let x = kani::any_finite_f64();
+/// assert!(x - x == 0.0);
+/// ```
+#[inline(always)]
+pub fn any_finite_f64() -> f64 {
+    let val = f64::any();
+    crate::assume(val.is_finite());
+    val
+}
+
+/// Create an `f32` that is either zero or a normal number, i.e., that is neither NaN, infinite,
+/// nor subnormal. Unlike [f32::is_normal], this includes zero.
+#[inline(always)]
+pub fn any_normal_f32() -> f32 {
+    let val = f32::any();
+    crate::assume(val.is_normal() || val == 0.0);
+    val
+}
+
+/// Create an `f64` that is either zero or a normal number, i.e., that is neither NaN, infinite,
+/// nor subnormal. Unlike [f64::is_normal], this includes zero.
+#[inline(always)]
+pub fn any_normal_f64() -> f64 {
+    let val = f64::any();
+    crate::assume(val.is_normal() || val == 0.0);
+    val
+}
@@ -3,7 +3,9 @@
 
 //! This module contains functions to work with futures (and async/.await) in Kani.
 
-use std::{
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::{
     future::Future,
     pin::Pin,
     task::{Context, RawWaker, RawWakerVTable, Waker},
@@ -26,8 +28,8 @@ pub fn block_on<T>(mut fut: impl Future<Output = T>) -> T {
     let mut fut = unsafe { Pin::new_unchecked(&mut fut) };
     loop {
         match fut.as_mut().poll(cx) {
-            std::task::Poll::Ready(res) => return res,
-            std::task::Poll::Pending => continue,
+            core::task::Poll::Ready(res) => return res,
+            core::task::Poll::Pending => continue,
         }
     }
 }
@@ -42,7 +44,7 @@ unsafe fn clone_waker(_: *const ()) -> RawWaker {
     #[inline]
     unsafe fn noop(_: *const ()) {}
 
-    RawWaker::new(std::ptr::null(), &RawWakerVTable::new(clone_waker, noop, noop, noop))
+    RawWaker::new(core::ptr::null(), &RawWakerVTable::new(clone_waker, noop, noop, noop))
 };
 
 /// The global executor used by [`spawn`] and [`block_on_with_spawn`] to run tasks.
@@ -104,6 +106,21 @@ fn pick_task(&mut self, num_tasks: usize) -> (usize, SchedulingAssumption) {
     }
 }
 
+/// Picks the next task nondeterministically among the running tasks
+///
+/// This explores every interleaving of the polls of the tasks, so it can find bugs that only occur
+/// with a specific scheduling, at the cost of a much harder verification problem than [`RoundRobin`].
+/// Since a task may be picked again and again, the harness usually needs an unwinding bound.
+#[derive(Default)]
+pub struct Nondeterministic;
+
+impl SchedulingStrategy for Nondeterministic {
+    #[inline]
+    fn pick_task(&mut self, num_tasks: usize) -> (usize, SchedulingAssumption) {
+        (crate::any_where(|index| *index < num_tasks), SchedulingAssumption::CanAssumeRunning)
+    }
+}
+
 pub(crate) struct Scheduler {
     tasks: Vec<Option<BoxFuture>>,
     num_running: usize,
@@ -133,11 +150,11 @@ fn run(&mut self, mut scheduling_plan: impl SchedulingStrategy) {
             let task = &mut self.tasks[index];
             if let Some(fut) = task.as_mut() {
                 match fut.as_mut().poll(cx) {
-                    std::task::Poll::Ready(()) => {
+                    core::task::Poll::Ready(()) => {
                         self.num_running -= 1;
                         let _prev = task.take();
                     }
-                    std::task::Poll::Pending => (),
+                    core::task::Poll::Pending => (),
                 }
             } else if let SchedulingAssumption::CanAssumeRunning = assumption {
                 crate::assume(false); // useful so that we can assume that a nondeterministically picked task is still running
@@ -166,12 +183,12 @@ pub struct JoinHandle {
 impl Future for JoinHandle {
     type Output = ();
 
-    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> std::task::Poll<Self::Output> {
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> core::task::Poll<Self::Output> {
         if unsafe { GLOBAL_EXECUTOR.as_mut().unwrap().tasks[self.index].is_some() } {
-            std::task::Poll::Pending
+            core::task::Poll::Pending
         } else {
             cx.waker().wake_by_ref(); // For completeness. But Kani currently ignores wakers.
-            std::task::Poll::Ready(())
+            core::task::Poll::Ready(())
         }
     }
 }
@@ -219,13 +236,13 @@ struct YieldNow {
     impl Future for YieldNow {
         type Output = ();
 
-        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> std::task::Poll<Self::Output> {
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> core::task::Poll<Self::Output> {
             if self.yielded {
                 cx.waker().wake_by_ref(); // For completeness. But Kani currently ignores wakers.
-                std::task::Poll::Ready(())
+                core::task::Poll::Ready(())
             } else {
                 self.yielded = true;
-                std::task::Poll::Pending
+                core::task::Poll::Pending
             }
         }
     }
@@ -0,0 +1,273 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Helpers for the code generated by the function contract and quantifier macros,
+//! for the harnesses synthesized by the compiler, and for the generators and
+//! models of the Kani library. These are not meant to be used directly.
+
+use crate::Arbitrary;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::marker::Tuple;
+
+/// A pointer that can be the target of a `modifies` clause.
+///
+/// The replace function of a contract writes a nondeterministic value through
+/// each target, and this trait lets it do so without knowing whether the
+/// argument is a mutable reference or a raw pointer.
+pub trait Pointer<'a> {
+    /// The type of the value this pointer points to.
+    type Inner;
+
+    /// Get a mutable reference to the pointee.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid for writes and the pointee must not be
+    /// accessed through any other pointer while the reference is alive.
+    unsafe fn assignable(self) -> &'a mut Self::Inner;
+}
+
+impl<'a, T> Pointer<'a> for &'a mut T {
+    type Inner = T;
+
+    unsafe fn assignable(self) -> &'a mut T {
+        self
+    }
+}
+
+impl<'a, T: 'a> Pointer<'a> for *mut T {
+    type Inner = T;
+
+    unsafe fn assignable(self) -> &'a mut T {
+        &mut *self
+    }
+}
+
+/// The nondeterministic result of a function that is replaced by its contract.
+///
+/// The contract macros generate this call in place of the call to the function,
+/// so that the targets of its `modifies` clauses are havocked right before it.
+#[inline(always)]
+pub fn contract_result<T: Arbitrary>() -> T {
+    crate::any()
+}
+
+/// The harness that the compiler instantiates for every `Drop` implementation
+/// of the crate when `--drop-checks` is enabled.
+///
+/// Dropping an arbitrary value runs the destructor of `T` followed by the
+/// destructors of its fields, so any panic or undefined behavior in them is
+/// reported as a failure of this harness.
+#[rustc_diagnostic_item = "KaniDropCheck"]
+#[inline(never)]
+pub fn drop_check<T: Arbitrary>() {
+    let value: T = crate::any();
+    drop(value);
+}
+
+/// The harness that the compiler instantiates for every non-generic `const fn`
+/// of the crate when `--const-fn-checks` is enabled. `F` is the type of the
+/// function and `Args` the tuple of its argument types.
+///
+/// Const functions are also called at runtime, where their arguments are not
+/// known in advance. Calling the function with arbitrary arguments reports any
+/// panic that such a call can trigger.
+#[rustc_diagnostic_item = "KaniConstFnCheck"]
+#[inline(never)]
+pub fn const_fn_check<F: FnOnce<Args>, Args: Arbitrary + Tuple>() {
+    // SAFETY: The type of a function item is zero-sized, so it has a single value.
+    let function: F = unsafe { core::mem::zeroed() };
+    let _ = function.call_once(crate::any());
+}
+
+/// The harness that the compiler instantiates for every `PartialEq` implementation
+/// of a type that does not implement `Eq` when `--trait-laws` is enabled.
+#[rustc_diagnostic_item = "KaniPartialEqLaws"]
+#[inline(never)]
+pub fn partial_eq_laws<T: Arbitrary + PartialEq>() {
+    let (a, b, c): (T, T, T) = crate::any();
+    check_partial_eq(&a, &b, &c);
+}
+
+/// The harness that the compiler instantiates for every `PartialEq` implementation
+/// of a type that also implements `Eq` when `--trait-laws` is enabled.
+#[rustc_diagnostic_item = "KaniEqLaws"]
+#[inline(never)]
+#[allow(clippy::eq_op)]
+pub fn eq_laws<T: Arbitrary + Eq>() {
+    let (a, b, c): (T, T, T) = crate::any();
+    assert!(a == a, "`eq` must be reflexive");
+    check_partial_eq(&a, &b, &c);
+}
+
+// The laws look like tautologies to clippy, which assumes that the implementations obey them.
+#[allow(clippy::eq_op, clippy::nonminimal_bool)]
+fn check_partial_eq<T: PartialEq>(a: &T, b: &T, c: &T) {
+    assert!((a == b) == (b == a), "`eq` must be symmetric");
+    assert!(!(a == b && b == c) || a == c, "`eq` must be transitive");
+    assert!((a != b) != (a == b), "`ne` must be the negation of `eq`");
+}
+
+/// The harness that the compiler instantiates for every `Ord` implementation
+/// when `--trait-laws` is enabled. It checks that `cmp` is a total order, and
+/// that it agrees with the `PartialEq` and `PartialOrd` implementations.
+#[rustc_diagnostic_item = "KaniOrdLaws"]
+#[inline(never)]
+pub fn ord_laws<T: Arbitrary + Ord>() {
+    let (a, b, c): (T, T, T) = crate::any();
+    let ord = a.cmp(&b);
+    assert!(ord == b.cmp(&a).reverse(), "`cmp` must be antisymmetric");
+    assert!(!(ord.is_le() && b.cmp(&c).is_le()) || a.cmp(&c).is_le(), "`cmp` must be transitive");
+    assert!(ord.is_eq() == (a == b), "`cmp` must agree with `eq`");
+    assert!(a.partial_cmp(&b) == Some(ord), "`partial_cmp` must agree with `cmp`");
+    assert!((a < b) == (ord == Ordering::Less), "`lt` must agree with `cmp`");
+    assert!((a <= b) == ord.is_le(), "`le` must agree with `cmp`");
+    assert!((a > b) == (ord == Ordering::Greater), "`gt` must agree with `cmp`");
+    assert!((a >= b) == ord.is_ge(), "`ge` must agree with `cmp`");
+}
+
+/// The harness that the compiler instantiates for every `Hash` implementation
+/// of a type that implements `Eq` when `--trait-laws` is enabled.
+///
+/// Equal values must feed the same data to any hasher, so we record the data
+/// that each value feeds to the hasher and compare it directly. Comparing the
+/// hashes instead could miss a violation when the hashes collide.
+#[rustc_diagnostic_item = "KaniHashLaws"]
+#[inline(never)]
+pub fn hash_laws<T: Arbitrary + Eq + Hash>() {
+    let (a, b): (T, T) = crate::any();
+    if a == b {
+        assert!(hashed_data(&a) == hashed_data(&b), "`hash` must agree with `eq`");
+    }
+}
+
+fn hashed_data<T: Hash>(value: &T) -> Vec<u8> {
+    let mut hasher = RecordingHasher(Vec::new());
+    value.hash(&mut hasher);
+    hasher.0
+}
+
+/// A hasher that records the data it's fed instead of hashing it.
+struct RecordingHasher(Vec<u8>);
+
+impl Hasher for RecordingHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        unreachable!("the recorded data is compared directly")
+    }
+}
+
+/// Whether `predicate` holds for every value in `lower_bound..upper_bound`.
+///
+/// Kani replaces calls to this function by a quantified expression, so the
+/// range isn't iterated during verification. See [crate::forall].
+#[rustc_diagnostic_item = "KaniForall"]
+#[inline(never)]
+pub fn kani_forall<F: Fn(usize) -> bool>(
+    lower_bound: usize,
+    upper_bound: usize,
+    predicate: F,
+) -> bool {
+    (lower_bound..upper_bound).all(predicate)
+}
+
+/// Whether `predicate` holds for some value in `lower_bound..upper_bound`.
+///
+/// Kani replaces calls to this function by a quantified expression, so the
+/// range isn't iterated during verification. See [crate::exists].
+#[rustc_diagnostic_item = "KaniExists"]
+#[inline(never)]
+pub fn kani_exists<F: Fn(usize) -> bool>(
+    lower_bound: usize,
+    upper_bound: usize,
+    predicate: F,
+) -> bool {
+    (lower_bound..upper_bound).any(predicate)
+}
+
+/// Assumes that `bytes` are valid UTF-8. Generators call this before they build a
+/// string out of arbitrary bytes. The compiler removes the call with
+/// `--no-utf8-assumptions`.
+#[rustc_diagnostic_item = "KaniAssumeUtf8"]
+#[inline(never)]
+pub fn assume_utf8(bytes: &[u8]) {
+    crate::assume(core::str::from_utf8(bytes).is_ok());
+}
+
+/// Restricts the floats generated by `kani::any` according to `--float-values`. Without the
+/// option, floats may be any value, so the compiler only replaces the call when it is given.
+#[rustc_diagnostic_item = "KaniAssumeFloatValues"]
+#[inline(never)]
+pub fn assume_float_values(_is_nan: bool, _is_infinite: bool, _is_subnormal: bool) {}
+
+/// Checks the UTF-8 invariant of a string. Kani reports the check in the `utf8`
+/// property class instead of as an ordinary assertion.
+#[rustc_diagnostic_item = "KaniCheckUtf8"]
+#[inline(never)]
+pub fn check_utf8(cond: bool, msg: &'static str) {
+    assert!(cond, "{msg}");
+}
+
+/// Checks a condition of [crate::check!], whose message starts with the name of the check. Kani
+/// reports the check in the `named_check` property class and doesn't assume the condition.
+#[rustc_diagnostic_item = "KaniNamedCheck"]
+#[inline(never)]
+pub fn named_check(cond: bool, msg: &'static str) {
+    assert!(cond, "{msg}");
+}
+
+/// Assumes `cond`, which is the result of the predicate `F` of [crate::any_where]. Kani also
+/// checks that the condition is satisfiable, and reports the check at the location of `F`.
+#[doc(hidden)]
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniAssumePredicate"]
+#[cfg(not(feature = "concrete_playback"))]
+pub fn assume_predicate<F>(cond: bool) {
+    let _ = cond;
+}
+
+#[doc(hidden)]
+#[inline(never)]
+#[rustc_diagnostic_item = "KaniAssumePredicate"]
+#[cfg(feature = "concrete_playback")]
+pub fn assume_predicate<F>(cond: bool) {
+    assert!(cond, "the predicate of `kani::any_where` should always hold");
+}
+
+/// Asserts a condition of [crate::assert_class!], which Kani reports in the property class
+/// `class` instead of `assertion`.
+#[rustc_diagnostic_item = "KaniAssertClass"]
+#[inline(never)]
+pub fn assert_class(cond: bool, class: &'static str, msg: &'static str) {
+    let _ = class;
+    assert!(cond, "{msg}");
+}
+
+/// Runs `f` on two independent secrets and the same public input, and returns both outputs.
+/// See [crate::assert_noninterference!].
+#[doc(hidden)]
+pub fn self_compose<S, P, O, F>(f: F) -> (O, O)
+where
+    S: Arbitrary,
+    P: Arbitrary + Clone,
+    F: Fn(S, P) -> O,
+{
+    let public = P::any();
+    let first = f(crate::secret(S::any()), public.clone());
+    let second = f(crate::secret(S::any()), public);
+    (first, second)
+}
+
+/// Runs `entry(arg)` in a new thread. Kani replaces calls to this function by CBMC's
+/// `start_thread` construct. Outside of verification, e.g., in concrete playback, the thread
+/// runs to completion before this function returns. See [crate::thread::spawn].
+#[rustc_diagnostic_item = "KaniSpawnThread"]
+#[inline(never)]
+pub fn spawn_thread(entry: fn(*mut u8), arg: *mut u8) {
+    entry(arg)
+}
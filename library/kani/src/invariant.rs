@@ -0,0 +1,43 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module introduces the `Invariant` trait, which describes the values of a type that are
+//! valid beyond what its layout guarantees, e.g., a buffer whose length never exceeds its
+//! capacity.
+//!
+//! The trait is usually derived with `#[derive(kani::Invariant)]`, which takes the invariant from
+//! `#[kani(invariant = "..")]` annotations:
+//!
+//! ```ignore
+//! #[derive(kani::Arbitrary, kani::Invariant)]
+//! #[kani(invariant = "self.len <= self.cap")]
+//! struct Buffer {
+//!     #[kani(invariant = "*cap <= 16")]
+//!     cap: usize,
+//!     len: usize,
+//! }
+//! ```
+//!
+//! The derived `Arbitrary` implementation of a type with such annotations only generates values
+//! that satisfy the invariant, and the invariant can be checked at API boundaries, e.g., in the
+//! postcondition of a function contract with `#[kani::ensures(result.is_valid())]`.
+
+/// This trait should be implemented by types whose values must satisfy an invariant.
+pub trait Invariant {
+    /// Whether `self` satisfies the invariant of its type.
+    fn is_valid(&self) -> bool;
+}
+
+/// Checks that `value` satisfies the invariant of its type.
+///
+/// # Example:
+///
+/// ```ignore
+/// fn push(buffer: &mut Buffer) {
+///     buffer.len += 1;
+///     kani::assert_invariant(buffer);
+/// }
+/// ```
+pub fn assert_invariant<T: Invariant + ?Sized>(value: &T) {
+    crate::assert(value.is_valid(), "type invariant");
+}
@@ -1,6 +1,9 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+// Without the `std` feature, the library only depends on `core` and `alloc`, so that it can be
+// used to verify `no_std` crates built for targets that don't have a standard library.
+#![no_std]
 // Required so we can use kani_macros attributes.
 #![feature(register_tool)]
 #![register_tool(kanitool)]
@@ -12,30 +15,62 @@
 #![allow(incomplete_features)]
 // Used to model simd.
 #![feature(repr_simd)]
+// Used to compare snapshots as bit-vectors.
+#![feature(core_intrinsics)]
+// Used to call the functions checked by `--const-fn-checks`.
+#![feature(fn_traits, tuple_trait, unboxed_closures)]
 // Features used for tests only.
 #![cfg_attr(test, feature(platform_intrinsics, portable_simd))]
 // Required for rustc_diagnostic_item
 #![allow(internal_features)]
 
+#[macro_use]
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+pub mod abstractions;
 pub mod arbitrary;
+pub mod bounded_arbitrary;
+pub mod bounds;
 #[cfg(feature = "concrete_playback")]
 mod concrete_playback;
+pub mod fixed_capacity;
+pub mod float;
 pub mod futures;
+#[doc(hidden)]
+pub mod internal;
+pub mod invariant;
+pub mod mem;
+pub mod proptest;
+pub mod ptr;
+pub mod roundtrip;
+pub mod scenario;
 pub mod slice;
+pub mod snapshot;
+#[cfg(feature = "std")]
+pub mod stream;
+pub mod string;
+#[cfg(target_has_atomic = "ptr")]
+pub mod thread;
 pub mod tuple;
 pub mod vec;
 
 mod models;
 
 pub use arbitrary::Arbitrary;
+pub use bounded_arbitrary::BoundedArbitrary;
 #[cfg(feature = "concrete_playback")]
 pub use concrete_playback::concrete_playback_run;
 #[cfg(not(feature = "concrete_playback"))]
 /// NOP `concrete_playback` for type checking during verification mode.
-pub fn concrete_playback_run<F: Fn()>(_: Vec<Vec<u8>>, _: F) {
+pub fn concrete_playback_run<F: Fn()>(_: alloc::vec::Vec<alloc::vec::Vec<u8>>, _: F) {
     unreachable!("Concrete playback does not work during verification")
 }
-pub use futures::{block_on, block_on_with_spawn, spawn, yield_now, RoundRobin};
+pub use float::{any_finite_f32, any_finite_f64, any_normal_f32, any_normal_f64};
+pub use futures::{block_on, block_on_with_spawn, spawn, yield_now, Nondeterministic, RoundRobin};
+pub use invariant::{assert_invariant, Invariant};
+pub use string::any_string_matching;
 
 /// Creates an assumption that will be valid after this statement run. Note that the assumption
 /// will only be applied for paths that follow the assumption. If the assumption doesn't hold, the
@@ -85,11 +120,150 @@ pub fn assume(cond: bool) {
 /// are joined with `||` in the expansion. E.g. `implies!(a, b => c)` expands to
 /// `!a || !b || c` and says that `c` is true if both `a` and `b` are true (see
 /// also [Horn Clauses](https://en.wikipedia.org/wiki/Horn_clause)).
+///
+/// `implies!(premise, conclusion)` is a shorthand for `implies!(premise => conclusion)`. In both
+/// forms the conclusion is only evaluated if all the premises are true, so it may rely on them,
+/// e.g. `implies!(i < v.len() => v[i] > 0)`.
+///
+/// When used in an assertion, the property description is the implication as written, e.g.
+/// `assertion failed: kani::implies!(x > 0 => y > 0)`.
 #[macro_export]
 macro_rules! implies {
     ($($premise:expr),+ => $conclusion:expr) => {
         $(!$premise)||+ || ($conclusion)
     };
+    ($premise:expr, $conclusion:expr $(,)?) => {
+        !($premise) || ($conclusion)
+    };
+}
+
+/// `iff!(lhs, rhs)` means that `lhs` is true if and only if `rhs` is true, i.e., both are true
+/// or both are false.
+///
+/// Unlike [implies!], both sides are always evaluated, and both must be `bool`s.
+///
+/// # Example:
+///
+/// ```rust
+/// let x: u8 = kani::any();
+/// assert!(kani::iff!(x % 2 == 0, x & 1 == 0));
+/// ```
+#[macro_export]
+macro_rules! iff {
+    ($lhs:expr, $rhs:expr $(,)?) => {{
+        let (lhs, rhs): (bool, bool) = ($lhs, $rhs);
+        lhs == rhs
+    }};
+}
+
+/// `matches_ok!(result, pattern)` is true if `result` is an `Ok` whose value matches `pattern`,
+/// which may be followed by an `if` guard, like in [matches!]. `matches_ok!(result)` is true if
+/// `result` is an `Ok`.
+///
+/// The value is matched by reference, so the `result` is neither moved nor partially moved even
+/// if its type is not `Copy`, and the variables bound by the pattern are references. This makes
+/// it convenient in the postconditions of functions that return a `Result`, which must not move
+/// the `result` binding, e.g.
+/// `#[kani::ensures(kani::matches_ok!(result, v if v.len() == n))]`.
+///
+/// # Example:
+///
+/// ```rust
+/// let parsed: Result<u8, String> = "42".parse::<u8>().map_err(|e| e.to_string());
+/// assert!(kani::matches_ok!(parsed, v if *v == 42));
+/// assert!(!kani::matches_err!(parsed));
+/// ```
+#[macro_export]
+macro_rules! matches_ok {
+    ($result:expr $(,)?) => {
+        ::core::result::Result::is_ok(&$result)
+    };
+    ($result:expr, $pattern:pat $(if $guard:expr)? $(,)?) => {
+        ::core::matches!(&$result, ::core::result::Result::Ok($pattern) $(if $guard)?)
+    };
+}
+
+/// `matches_err!(result, pattern)` is true if `result` is an `Err` whose error matches
+/// `pattern`, which may be followed by an `if` guard. `matches_err!(result)` is true if `result`
+/// is an `Err`.
+///
+/// Like [matches_ok!], the error is matched by reference.
+#[macro_export]
+macro_rules! matches_err {
+    ($result:expr $(,)?) => {
+        ::core::result::Result::is_err(&$result)
+    };
+    ($result:expr, $pattern:pat $(if $guard:expr)? $(,)?) => {
+        ::core::matches!(&$result, ::core::result::Result::Err($pattern) $(if $guard)?)
+    };
+}
+
+/// `matches_some!(option, pattern)` is true if `option` is a `Some` whose value matches
+/// `pattern`, which may be followed by an `if` guard. `matches_some!(option)` is true if
+/// `option` is a `Some`.
+///
+/// Like [matches_ok!], the value is matched by reference.
+///
+/// # Example:
+///
+/// ```rust
+/// let name = Some(String::from("kani"));
+/// assert!(kani::matches_some!(name, n if n.starts_with('k')));
+/// assert_eq!(name.unwrap(), "kani");
+/// ```
+#[macro_export]
+macro_rules! matches_some {
+    ($option:expr $(,)?) => {
+        ::core::option::Option::is_some(&$option)
+    };
+    ($option:expr, $pattern:pat $(if $guard:expr)? $(,)?) => {
+        ::core::matches!(&$option, ::core::option::Option::Some($pattern) $(if $guard)?)
+    };
+}
+
+/// `forall!(|i in (lower, upper)| predicate)` is true if `predicate` holds for every `i: usize`
+/// such that `lower <= i < upper`.
+///
+/// Kani encodes the condition as a quantified expression instead of a loop, so it doesn't need
+/// to be unwound, and it can be used in [assert], [assume] and in function contracts.
+///
+/// The predicate is inlined in the quantified expression, which limits what it can do: it may
+/// read variables and call functions, but it must not have any branches, loops or side effects.
+/// Also note that the safety checks inside the predicate, such as bounds checks, are not
+/// performed.
+///
+/// # Example:
+///
+/// ```rust
+/// let buf: [u8; 100] = kani::any();
+/// kani::assume(kani::forall!(|i in (0, 100)| buf[i] < 10));
+/// assert!(buf[42] < 10);
+/// ```
+#[macro_export]
+macro_rules! forall {
+    (|$i:ident in ($lower:expr, $upper:expr)| $predicate:expr) => {
+        $crate::internal::kani_forall($lower, $upper, |$i: usize| $predicate)
+    };
+}
+
+/// `exists!(|i in (lower, upper)| predicate)` is true if `predicate` holds for some `i: usize`
+/// such that `lower <= i < upper`.
+///
+/// Like [forall!], the condition is encoded as a quantified expression, with the same
+/// restrictions on the predicate.
+///
+/// # Example:
+///
+/// ```rust
+/// let buf: [u8; 100] = kani::any();
+/// kani::assume(buf[7] == 0);
+/// assert!(kani::exists!(|i in (0, 100)| buf[i] == 0));
+/// ```
+#[macro_export]
+macro_rules! exists {
+    (|$i:ident in ($lower:expr, $upper:expr)| $predicate:expr) => {
+        $crate::internal::kani_exists($lower, $upper, |$i: usize| $predicate)
+    };
 }
 
 /// A way to break the ownerhip rules. Only used by contracts where we can
@@ -101,6 +275,62 @@ pub fn untracked_deref<T>(_: &T) -> T {
     todo!()
 }
 
+/// Returns the flag that the recursion wrapper of a function contract uses to
+/// detect that it has been re-entered. Only the type of the argument matters:
+/// it is a closure defined in the wrapper, so every instantiation of a generic
+/// wrapper gets a flag of its own.
+#[inline(never)]
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniContractReentryFlag"]
+pub fn contract_reentry_flag<F>(_: &F) -> *mut bool {
+    todo!()
+}
+
+/// Returns the slot in which the recursion wrapper of a function contract with
+/// a `decreases` clause stores the measure of the outermost call. Like for
+/// [contract_reentry_flag], every instantiation of a generic wrapper gets a
+/// slot of its own.
+#[inline(never)]
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniContractMeasure"]
+pub fn contract_measure<F, D>(_: &F) -> *mut D {
+    todo!()
+}
+
+/// Registers the invariant of a loop annotated with `#[kani::loop_invariant]`. The attribute
+/// adds a call to this function at the beginning of the loop condition.
+///
+/// Kani doesn't evaluate the call in place, it always returns `true`. Instead, the invariant
+/// is used as the contract of the loop when the `loop-contracts` unstable feature is enabled.
+#[inline(never)]
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniLoopInvariant"]
+pub fn loop_invariant<F: Fn() -> bool>(inv: &F) -> bool {
+    inv()
+}
+
+/// Registers the invariant and the measure of a loop annotated with `#[kani::decreases]`. The
+/// attribute adds a call to this function at the beginning of the loop condition, in place of
+/// the call to [loop_invariant].
+///
+/// Like [loop_invariant], the call always returns `true`, and the invariant and the measure are
+/// used as the contract of the loop when the `loop-contracts` unstable feature is enabled.
+#[inline(never)]
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniLoopDecreases"]
+pub fn loop_decreases<F: Fn() -> bool, M: Fn() -> D, D>(inv: &F, measure: &M) -> bool {
+    let _ = loop_measure(measure);
+    inv()
+}
+
+/// Evaluates the measure of a loop annotated with `#[kani::decreases]`.
+#[inline(never)]
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniLoopMeasure"]
+pub fn loop_measure<M: Fn() -> D, D>(measure: &M) -> D {
+    measure()
+}
+
 /// Creates an assertion of the specified condition and message.
 ///
 /// # Example:
@@ -164,6 +394,7 @@ pub fn cover(_cond: bool, _msg: &'static str) {}
 /// Note: This is a safe construct and can only be used with types that implement the `Arbitrary`
 /// trait. The Arbitrary trait is used to build a symbolic value that represents all possible
 /// valid values for type `T`.
+#[rustc_diagnostic_item = "KaniAny"]
 #[inline(always)]
 pub fn any<T: Arbitrary>() -> T {
     T::any()
@@ -186,10 +417,136 @@ pub fn any<T: Arbitrary>() -> T {
 /// Note: This is a safe construct and can only be used with types that implement the `Arbitrary`
 /// trait. The Arbitrary trait is used to build a symbolic value that represents all possible
 /// valid values for type `T`.
+///
+/// Kani also checks that the predicate is satisfiable, like a cover property. If no value
+/// satisfies it, every check that follows holds vacuously, so Kani warns about it in the
+/// verification results.
+#[rustc_diagnostic_item = "KaniAnyWhere"]
 #[inline(always)]
 pub fn any_where<T: Arbitrary, F: FnOnce(&T) -> bool>(f: F) -> T {
     let result = T::any();
-    assume(f(&result));
+    internal::assume_predicate::<F>(f(&result));
+    result
+}
+
+/// Mark `value` as a secret for the constant-time checks, and return it unchanged.
+///
+/// With `--constant-time-checks`, Kani tracks the values computed from secrets, and it reports a
+/// failed check of class `constant_time` at every branch that depends on a secret and at every
+/// memory access whose index or offset depends on one, if the harness reaches it. If `value`
+/// contains pointers, e.g., a `Vec`, only the data it points to is secret, not its length.
+///
+/// Without `--constant-time-checks`, this function has no effect.
+///
+/// # Example:
+///
+/// ```no_run
+/// let key: [u8; 16] = kani::secret(kani::any());
+/// let mut ones = 0;
+/// for byte in key {
+///     // Fails: the branch depends on the secret.
+///     if byte & 1 == 1 {
+///         ones += 1;
+///     }
+/// }
+/// ```
+#[rustc_diagnostic_item = "KaniSecret"]
+#[inline(never)]
+pub fn secret<T>(value: T) -> T {
+    value
+}
+
+/// This creates a symbolic value of the enum type `T` whose variant is one of `variants`.
+///
+/// The derived `Arbitrary` implementation of an enum picks any of its variants. This function
+/// restricts the choice to a subset of variants that is decided by the harness, e.g., by a loop
+/// over the interesting subsets. The fields of the chosen variant are unconstrained.
+///
+/// # Example:
+///
+/// ```no_run
+/// use std::mem::discriminant;
+///
+/// #[derive(kani::Arbitrary)]
+/// enum Command { Read(u8), Write(u8, u8), Reset }
+///
+/// let command: Command = kani::nondet_choice(&[
+///     discriminant(&Command::Read(0)),
+///     discriminant(&Command::Write(0, 0)),
+/// ]);
+/// assert!(!matches!(command, Command::Reset));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `variants` is empty, since no value could be created.
+#[inline(always)]
+pub fn nondet_choice<T: Arbitrary>(variants: &[core::mem::Discriminant<T>]) -> T {
+    assert!(!variants.is_empty(), "`kani::nondet_choice` requires at least one variant");
+    any_where(|value| variants.contains(&core::mem::discriminant(value)))
+}
+
+/// This creates a symbolic *valid* value of type `T` whose size is at most `N`, e.g., a vector
+/// with at most `N` elements or a string with at most `N` bytes.
+///
+/// # Example:
+///
+/// ```rust
+/// let inputA = kani::bounded_any::<Vec<u8>, 4>();
+/// assert!(inputA.len() <= 4);
+/// fn_under_verification(inputA);
+/// ```
+///
+/// Note: This can only be used with types that implement the `BoundedArbitrary` trait.
+#[inline(always)]
+pub fn bounded_any<T: BoundedArbitrary, const N: usize>() -> T {
+    T::bounded_any::<N>()
+}
+
+/// This creates a symbolic array of `N` *valid* values of type `T`.
+///
+/// It is equivalent to `kani::any::<[T; N]>()`, but lets the element type and the
+/// length be given as const-generic arguments.
+///
+/// # Example:
+///
+/// ```rust
+/// let buffer = kani::any_array::<u8, 16>();
+/// fn_under_verification(&buffer);
+/// ```
+#[rustc_diagnostic_item = "KaniAnyArray"]
+#[inline(always)]
+pub fn any_array<T: Arbitrary, const N: usize>() -> [T; N]
+where
+    [(); core::mem::size_of::<[T; N]>()]:,
+{
+    T::any_array()
+}
+
+/// This creates a symbolic array of `N` *valid* values of type `T`, where every element is
+/// constrained to be a value accepted by the predicate.
+///
+/// # Example:
+///
+/// In the snippet below, we are verifying the behavior of the function `fn_under_verification`
+/// for every buffer of 16 bytes that does not contain a zero byte.
+///
+/// ```rust
+/// let buffer = kani::any_array_where::<u8, 16>(|b| *b != 0);
+/// fn_under_verification(&buffer);
+/// ```
+///
+/// The predicate is called once per element, in order, so it may keep state across calls.
+#[rustc_diagnostic_item = "KaniAnyArrayWhere"]
+#[inline(always)]
+pub fn any_array_where<T: Arbitrary, const N: usize>(mut f: impl FnMut(&T) -> bool) -> [T; N]
+where
+    [(); core::mem::size_of::<[T; N]>()]:,
+{
+    let result = T::any_array();
+    for elem in &result {
+        assume(f(elem));
+    }
     result
 }
 
@@ -284,17 +641,219 @@ pub const fn panic(message: &'static str) -> ! {
 #[macro_export]
 macro_rules! cover {
     () => {
-        kani::cover(true, "cover location");
+        $crate::cover(true, "cover location");
     };
     ($cond:expr $(,)?) => {
-        kani::cover($cond, concat!("cover condition: ", stringify!($cond)));
+        $crate::cover($cond, concat!("cover condition: ", stringify!($cond)));
     };
-    ($cond:expr, $msg:literal) => {
-        kani::cover($cond, $msg);
+    ($cond:expr, $msg:literal $(,)?) => {
+        $crate::cover($cond, $msg);
     };
 }
 
+/// Checks a condition and gives the check a name, e.g. `kani::check!(no_overflow, x < 10)`.
+///
+/// The name identifies the check across runs, even when the code around it changes: the
+/// verification results list the status of each named check of the harness, and the report of
+/// `--output-format=json` groups the properties by the name of their check. The same name can be
+/// used in several places, in which case the check fails if any of them fails. Names can be
+/// identifiers or string literals, and must not contain `:`.
+///
+/// Unlike [assert], the condition isn't assumed after the check, so the checks that follow a
+/// failed check are still verified.
+///
+/// # Example:
+///
+/// ```rust
+/// let x: u8 = kani::any();
+/// kani::check!(in_range, x < 200);
+/// kani::check!("parser.no_overflow", x.checked_add(50).is_some(), "x + 50 doesn't overflow");
+/// ```
+#[macro_export]
+macro_rules! check {
+    ($name:ident, $($rest:tt)+) => {
+        $crate::check!(stringify!($name), $($rest)+)
+    };
+    ($name:expr, $cond:expr $(,)?) => {
+        $crate::internal::named_check($cond, concat!($name, ": ", stringify!($cond)))
+    };
+    ($name:expr, $cond:expr, $msg:literal $(,)?) => {
+        $crate::internal::named_check($cond, concat!($name, ": ", $msg))
+    };
+}
+
+/// Asserts a condition like [assert], but reports the check in a property class chosen by the
+/// user, e.g. `kani::assert_class!("protocol", ack <= seq)`.
+///
+/// The class replaces `assertion` in the name of the check (e.g. `check_handshake.protocol.1`),
+/// so the failed checks can be grouped by class in the verification results, and
+/// `--fail-fast-on-class` can stop the verification at the first failure of a class. Classes
+/// must be string literals made of lowercase ASCII letters, digits and `_`, that don't clash
+/// with the classes of Kani's own checks.
+///
+/// # Example:
+///
+/// ```rust
+/// let seq: u32 = kani::any();
+/// let ack: u32 = kani::any_where(|ack| *ack <= seq);
+/// kani::assert_class!("protocol", ack <= seq);
+/// kani::assert_class!("protocol", seq.checked_sub(ack).is_some(), "the window is never negative");
+/// ```
+#[macro_export]
+macro_rules! assert_class {
+    ($class:literal, $cond:expr $(,)?) => {
+        $crate::internal::assert_class(
+            $cond,
+            $class,
+            concat!("assertion failed: ", stringify!($cond)),
+        )
+    };
+    ($class:literal, $cond:expr, $msg:literal $(,)?) => {
+        $crate::internal::assert_class($cond, $class, $msg)
+    };
+}
+
+/// Asserts that the output of a computation doesn't depend on its secret input, i.e., that the
+/// computation doesn't leak the secret, e.g.:
+/// `kani::assert_noninterference!(|key: [u8; 16], msg: [u8; 4]| encrypt(&key, &msg).len())`.
+///
+/// The argument is a closure that takes a secret input and a public input, which must both
+/// implement [Arbitrary]. Kani checks the property by self-composition: it runs the closure twice,
+/// on two independent secrets and the same public input, and asserts that both outputs are
+/// equal. The check is reported in the property class `noninterference`. The secrets are marked
+/// with [secret], so `--constant-time-checks` also applies to the closure.
+///
+/// Only the output of the closure is compared, so the secret may still leak through other
+/// effects, e.g., through global variables that the closure writes.
+///
+/// # Example:
+///
+/// ```rust
+/// // The length of the padding only depends on the length of the message.
+/// kani::assert_noninterference!(|key: u64, len: u8| {
+///     let _ = key;
+///     (16 - len % 16) as usize
+/// });
+/// kani::assert_noninterference!(|key: u64, nonce: u64| nonce.wrapping_add(1), "nonces are public");
+/// ```
+#[macro_export]
+macro_rules! assert_noninterference {
+    ($f:expr $(,)?) => {
+        $crate::assert_noninterference!($f, "the output doesn't depend on the secret input")
+    };
+    ($f:expr, $msg:literal $(,)?) => {{
+        let (first, second) = $crate::internal::self_compose($f);
+        $crate::internal::assert_class(first == second, "noninterference", $msg)
+    }};
+}
+
 // Kani proc macros must be in a separate crate
 pub use kani_macros::*;
 
 pub mod contracts;
+
+/// Asserts that two expressions are equal, and reports the value of both operands when the
+/// assertion fails.
+///
+/// Unlike `assert_eq!`, which only reports that the assertion failed, the counterexample
+/// summary will include the values that each side had in the failing execution:
+///
+/// ```text
+/// Failed Checks: assertion failed: x + 1 == y
+///  File: "src/main.rs", line 10, in check_inc
+///  left: 5
+///  right: 3
+/// ```
+///
+/// Both operands must be sized and implement [PartialEq] with each other.
+///
+/// # Example:
+///
+/// ```rust
+/// let x: u8 = kani::any();
+/// kani::assert_eq!(x.wrapping_add(0), x);
+/// kani::assert_eq!(x & 0, 0, "masking with zero must clear all bits");
+/// ```
+#[cfg(not(feature = "concrete_playback"))]
+#[macro_export]
+macro_rules! assert_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::__assert_cmp!($left, $right, ==, concat!("assertion failed: ", stringify!($left == $right)))
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {{
+        $crate::__assert_cmp!($left, $right, ==, concat!(stringify!($($arg)+)));
+        // Type check the arguments without evaluating them. See the `assert!` override in
+        // Kani's `std` crate.
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    }};
+}
+
+/// Asserts that two expressions are not equal, and reports the value of both operands when
+/// the assertion fails.
+///
+/// See [assert_eq!] for more details.
+///
+/// # Example:
+///
+/// ```rust
+/// let x: u8 = kani::any();
+/// kani::assume(x < u8::MAX);
+/// kani::assert_ne!(x + 1, x);
+/// ```
+#[cfg(not(feature = "concrete_playback"))]
+#[macro_export]
+macro_rules! assert_ne {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::__assert_cmp!($left, $right, !=, concat!("assertion failed: ", stringify!($left != $right)))
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {{
+        $crate::__assert_cmp!($left, $right, !=, concat!(stringify!($($arg)+)));
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    }};
+}
+
+/// Shared implementation of [assert_eq!] and [assert_ne!].
+///
+/// The operands are copied into the `kani_left` and `kani_right` variables, which is how the
+/// driver finds their values in the trace of a failure. The comparison is done on the copies
+/// so that the assignments are not removed by the formula slicing. The copies share any
+/// memory owned by the operands, so they must not be dropped.
+#[cfg(not(feature = "concrete_playback"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert_cmp {
+    ($left:expr, $right:expr, $op:tt, $msg:expr) => {
+        match (&$left, &$right) {
+            (left_val, right_val) => {
+                let kani_left = $crate::untracked_deref(left_val);
+                let kani_right = $crate::untracked_deref(right_val);
+                let cond = kani_left $op kani_right;
+                ::core::mem::forget(kani_left);
+                ::core::mem::forget(kani_right);
+                $crate::assert(cond, $msg);
+            }
+        }
+    };
+}
+
+/// When running concrete playback, fallback to the standard library assertions.
+#[cfg(feature = "concrete_playback")]
+#[macro_export]
+macro_rules! assert_eq {
+    ($($x:tt)*) => {
+        ::core::assert_eq!($($x)*)
+    };
+}
+
+/// When running concrete playback, fallback to the standard library assertions.
+#[cfg(feature = "concrete_playback")]
+#[macro_export]
+macro_rules! assert_ne {
+    ($($x:tt)*) => {
+        ::core::assert_ne!($($x)*)
+    };
+}
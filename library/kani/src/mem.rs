@@ -0,0 +1,74 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module provides predicates about the memory that a raw pointer points to, which are
+//! meant to be used in the preconditions of unsafe functions and in assumptions, e.g.:
+//!
+//! ```rust
+//! #[kani::requires(kani::mem::can_dereference(ptr))]
+//! unsafe fn get(ptr: *const u32) -> u32 {
+//!     *ptr
+//! }
+//! ```
+//!
+//! The predicates only talk about the memory itself: whether it is allocated, and whether the
+//! pointer is within the bounds of its allocation. They don't check that the pointee is a valid
+//! value of type `T`, nor Rust's aliasing rules.
+//!
+//! Kani can't answer these questions outside of verification, e.g., in concrete playback, where
+//! the predicates only check that the pointer isn't null.
+
+use core::mem::{align_of, size_of};
+
+/// Checks that `ptr` can be dereferenced, i.e., that it is non-null, aligned to `T`, and that it
+/// points to `size_of::<T>()` allocated bytes that can be read.
+///
+/// Zero-sized values can be read through any non-null aligned pointer.
+///
+/// # Example:
+///
+/// ```rust
+/// let value = 10u32;
+/// let ptr = &value as *const u32;
+/// assert!(kani::mem::can_dereference(ptr));
+/// assert!(!kani::mem::can_dereference(std::ptr::null::<u32>()));
+/// ```
+pub fn can_dereference<T>(ptr: *const T) -> bool {
+    !ptr.is_null() && is_aligned(ptr) && can_read(ptr)
+}
+
+/// Checks that `ptr` points to `size_of::<T>()` allocated bytes that can be read.
+///
+/// Unlike [can_dereference], this doesn't require `ptr` to be aligned, which is enough for
+/// [std::ptr::read_unaligned].
+pub fn can_read<T>(ptr: *const T) -> bool {
+    let size = size_of::<T>();
+    if size == 0 { !ptr.is_null() } else { read_ok(ptr.cast(), size) }
+}
+
+/// Checks that `ptr` points to `size_of::<T>()` allocated bytes that can be written.
+///
+/// This doesn't require `ptr` to be aligned, which is enough for [std::ptr::write_unaligned].
+/// Use [can_dereference] as well for [std::ptr::write] or to assign through the pointer.
+pub fn can_write<T>(ptr: *mut T) -> bool {
+    let size = size_of::<T>();
+    if size == 0 { !ptr.is_null() } else { write_ok(ptr.cast(), size) }
+}
+
+fn is_aligned<T>(ptr: *const T) -> bool {
+    ptr as usize % align_of::<T>() == 0
+}
+
+/// Kani replaces calls to this function by `__CPROVER_r_ok(ptr, size)`.
+#[rustc_diagnostic_item = "KaniReadOk"]
+#[inline(never)]
+fn read_ok(ptr: *const u8, _size: usize) -> bool {
+    !ptr.is_null()
+}
+
+/// Kani replaces calls to this function by `__CPROVER_w_ok(ptr, size)`.
+#[rustc_diagnostic_item = "KaniWriteOk"]
+#[inline(never)]
+fn write_ok(ptr: *mut u8, _size: usize) -> bool {
+    !ptr.is_null()
+}
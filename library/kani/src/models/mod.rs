@@ -8,8 +8,8 @@
 // Definitions in this module are not meant to be visible to the end user, only the compiler.
 #[allow(dead_code)]
 mod intrinsics {
-    use std::fmt::Debug;
-    use std::mem::size_of;
+    use core::fmt::Debug;
+    use core::mem::size_of;
 
     /// Similar definition to portable SIMD.
     /// We cannot reuse theirs since TRUE and FALSE defs are private.
@@ -124,10 +124,350 @@ pub(super) unsafe fn simd_bitmask<T, U, E, const LANES: usize>(input: T) -> U
     struct Simd<T, const LANES: usize>([T; LANES]);
 }
 
+// Definitions in this module are not meant to be visible to the end user, only the compiler.
+#[allow(dead_code)]
+mod entropy {
+    //! Models of the entropy sources of the operating system. Every byte they produce is
+    //! nondeterministic, so a harness covers any value that the system could have returned.
+    use core::ffi::{c_int, c_void};
+    use core::mem::MaybeUninit;
+
+    /// The maximum number of bytes `getentropy` accepts.
+    const GETENTROPY_MAX: usize = 256;
+
+    fn fill_bytes(dest: &mut [MaybeUninit<u8>]) {
+        for byte in dest {
+            byte.write(crate::any());
+        }
+    }
+
+    /// Model for the function that std uses to seed `RandomState`, e.g. in `HashMap::new`.
+    #[rustc_diagnostic_item = "KaniModelHashmapRandomKeys"]
+    pub(super) fn hashmap_random_keys() -> (u64, u64) {
+        (crate::any(), crate::any())
+    }
+
+    /// Model for `getrandom::getrandom`. The compiler picks `E` to be the error type of the
+    /// crate. The model never fails.
+    #[rustc_diagnostic_item = "KaniModelGetrandom"]
+    pub(super) fn getrandom<E>(dest: &mut [u8]) -> Result<(), E> {
+        for byte in dest {
+            *byte = crate::any();
+        }
+        Ok(())
+    }
+
+    /// Model for `getrandom::getrandom_uninit`.
+    #[rustc_diagnostic_item = "KaniModelGetrandomUninit"]
+    pub(super) fn getrandom_uninit<E>(dest: &mut [MaybeUninit<u8>]) -> Result<&mut [u8], E> {
+        fill_bytes(dest);
+        // SAFETY: Every byte was initialized by `fill_bytes`.
+        Ok(unsafe { &mut *(dest as *mut [MaybeUninit<u8>] as *mut [u8]) })
+    }
+
+    /// Model for the `getrandom` system call. The whole buffer is always filled, so the flags
+    /// have no effect.
+    #[rustc_diagnostic_item = "KaniModelGetrandomSyscall"]
+    pub(super) unsafe fn getrandom_syscall(buf: *mut c_void, buflen: usize, _flags: u32) -> isize {
+        fill_bytes(core::slice::from_raw_parts_mut(buf.cast(), buflen));
+        buflen as isize
+    }
+
+    /// Model for the `getentropy` system call, which fails for requests over 256 bytes.
+    #[rustc_diagnostic_item = "KaniModelGetentropy"]
+    pub(super) unsafe fn getentropy(buf: *mut c_void, buflen: usize) -> c_int {
+        if buflen > GETENTROPY_MAX {
+            return -1;
+        }
+        fill_bytes(core::slice::from_raw_parts_mut(buf.cast(), buflen));
+        0
+    }
+}
+
+// Definitions in this module are not meant to be visible to the end user, only the compiler.
+#[cfg(feature = "std")]
+#[allow(dead_code)]
+mod hash_order {
+    //! Models that make the iteration order of `HashMap` and `HashSet` nondeterministic. The
+    //! compiler only uses them with `--nondet-hash-order`.
+    //!
+    //! The keys of `RandomState` are fixed, and every hash it computes is XORed with a
+    //! nondeterministic seed. The seed is the same for the whole harness, so equal values still
+    //! have equal hashes, but it permutes the buckets of the tables, and thus the order in which
+    //! they are visited. This covers a bounded set of orders, which is much cheaper for the
+    //! solver than inverting SipHash with nondeterministic keys.
+    use core::hash::{BuildHasher, Hash, Hasher};
+    use std::collections::hash_map::RandomState;
+
+    /// The seed of the hash order, picked the first time the harness hashes a value.
+    static mut SEED: Option<u64> = None;
+
+    /// The driver looks for the value returned by this function in the trace of a failure.
+    #[inline(never)]
+    fn hash_order_seed() -> u64 {
+        // SAFETY: The seed is only accessed here, and it never changes once it is set.
+        unsafe { *SEED.get_or_insert_with(crate::any) }
+    }
+
+    /// Model for the function that std uses to seed `RandomState`, e.g. in `HashMap::new`.
+    #[rustc_diagnostic_item = "KaniModelHashmapFixedKeys"]
+    pub(super) fn hashmap_fixed_keys() -> (u64, u64) {
+        (0, 0)
+    }
+
+    /// Model for `<RandomState as BuildHasher>::hash_one`, which `HashMap` and `HashSet` use to
+    /// hash their keys.
+    #[rustc_diagnostic_item = "KaniModelRandomStateHashOne"]
+    // Calling `hash_one` here would call this model again.
+    #[allow(clippy::manual_hash_one)]
+    pub(super) fn hash_one<T: Hash>(state: &RandomState, x: T) -> u64 {
+        let mut hasher = state.build_hasher();
+        x.hash(&mut hasher);
+        hasher.finish() ^ hash_order_seed()
+    }
+}
+
+// Definitions in this module are not meant to be visible to the end user, only the compiler.
+#[allow(dead_code)]
+mod utf8 {
+    //! Models of the unchecked UTF-8 conversions that check the invariant of `str` before they
+    //! convert the bytes. The compiler only uses them with `--utf8-checks`.
+    use crate::internal::check_utf8;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    #[rustc_diagnostic_item = "KaniModelFromUtf8Unchecked"]
+    pub(super) unsafe fn from_utf8_unchecked(v: &[u8]) -> &str {
+        check_utf8(
+            core::str::from_utf8(v).is_ok(),
+            "`str::from_utf8_unchecked` requires the bytes to be valid UTF-8",
+        );
+        // SAFETY: `str` has the same layout as `[u8]`.
+        &*(v as *const [u8] as *const str)
+    }
+
+    #[rustc_diagnostic_item = "KaniModelFromUtf8UncheckedMut"]
+    pub(super) unsafe fn from_utf8_unchecked_mut(v: &mut [u8]) -> &mut str {
+        check_utf8(
+            core::str::from_utf8(v).is_ok(),
+            "`str::from_utf8_unchecked_mut` requires the bytes to be valid UTF-8",
+        );
+        // SAFETY: `str` has the same layout as `[u8]`.
+        &mut *(v as *mut [u8] as *mut str)
+    }
+
+    /// The compiler doesn't replace the calls in the Kani library, so this calls the original
+    /// function.
+    #[rustc_diagnostic_item = "KaniModelStringFromUtf8Unchecked"]
+    pub(super) unsafe fn string_from_utf8_unchecked(bytes: Vec<u8>) -> String {
+        check_utf8(
+            core::str::from_utf8(&bytes).is_ok(),
+            "`String::from_utf8_unchecked` requires the bytes to be valid UTF-8",
+        );
+        String::from_utf8_unchecked(bytes)
+    }
+
+    /// Replaces [crate::internal::assume_utf8] with `--no-utf8-assumptions`.
+    #[rustc_diagnostic_item = "KaniModelSkipUtf8Assumption"]
+    pub(super) fn skip_utf8_assumption(_bytes: &[u8]) {}
+}
+
+// Definitions in this module are not meant to be visible to the end user, only the compiler.
+#[allow(dead_code)]
+mod float {
+    //! Models of the float intrinsics that CBMC doesn't support, as well as of the restrictions
+    //! that `--float-values` puts on the floats generated by Kani.
+    //!
+    //! The models of `fma` compute the exact product and sum with integers, and round the result
+    //! once, like the hardware instruction.
+
+    /// The layout of a binary floating-point format.
+    struct Format {
+        /// The number of bits of the fraction, i.e., of the mantissa without its implicit bit.
+        mant_bits: u32,
+        /// The number of bits of the biased exponent.
+        exp_bits: u32,
+    }
+
+    const F32: Format = Format { mant_bits: 23, exp_bits: 8 };
+    const F64: Format = Format { mant_bits: 52, exp_bits: 11 };
+
+    impl Format {
+        fn bias(&self) -> i32 {
+            (1 << (self.exp_bits - 1)) - 1
+        }
+
+        /// The exponent of the least significant bit of the subnormal numbers.
+        fn min_ulp_exp(&self) -> i32 {
+            1 - self.bias() - self.mant_bits as i32
+        }
+
+        /// Split a finite value into its sign, and an integer mantissa and exponent such that
+        /// the value is `m * 2^e`.
+        fn decode(&self, bits: u64) -> (bool, u128, i32) {
+            let sign = (bits >> (self.mant_bits + self.exp_bits)) & 1 == 1;
+            let exp = ((bits >> self.mant_bits) & ((1 << self.exp_bits) - 1)) as i32;
+            let frac = (bits & ((1 << self.mant_bits) - 1)) as u128;
+            if exp == 0 {
+                (sign, frac, self.min_ulp_exp())
+            } else {
+                (sign, frac | 1 << self.mant_bits, self.min_ulp_exp() + exp - 1)
+            }
+        }
+
+        /// Round `m * 2^e` to the nearest value of the format, ties to even.
+        fn round(&self, sign: bool, m: u128, e: i32) -> u64 {
+            let sign_bit = (sign as u64) << (self.mant_bits + self.exp_bits);
+            let msb = 127 - m.leading_zeros() as i32;
+            let mut ulp_exp = (e + msb - self.mant_bits as i32).max(self.min_ulp_exp());
+            let shift = ulp_exp - e;
+            let (mut mant, round_up) = if shift <= 0 {
+                (m << -shift, false)
+            } else if shift > 127 {
+                (0, false)
+            } else {
+                let rem = m & ((1 << shift) - 1);
+                let half = 1 << (shift - 1);
+                let mant = m >> shift;
+                (mant, rem > half || (rem == half && mant & 1 == 1))
+            };
+            if round_up {
+                mant += 1;
+                if mant == 1 << (self.mant_bits + 1) {
+                    mant >>= 1;
+                    ulp_exp += 1;
+                }
+            }
+            if mant >> self.mant_bits == 0 {
+                // Subnormal or zero.
+                return sign_bit | mant as u64;
+            }
+            let exp = ulp_exp - self.min_ulp_exp() + 1;
+            let max_exp = (1 << self.exp_bits) - 1;
+            if exp >= max_exp {
+                sign_bit | (max_exp as u64) << self.mant_bits
+            } else {
+                let frac = mant as u64 & ((1 << self.mant_bits) - 1);
+                sign_bit | (exp as u64) << self.mant_bits | frac
+            }
+        }
+
+        /// Compute `a * b + c` for finite and non-zero `a` and `b` and finite `c`.
+        fn fma(&self, a: u64, b: u64, c: u64) -> u64 {
+            let (sign_a, m_a, e_a) = self.decode(a);
+            let (sign_b, m_b, e_b) = self.decode(b);
+            let (sign_c, m_c, e_c) = self.decode(c);
+            let (sign, m, e) = normalize(sign_a ^ sign_b, m_a * m_b, e_a + e_b);
+            if m_c == 0 {
+                return self.round(sign, m, e);
+            }
+            let (large, (sign_y, m_y, e_y)) = {
+                let c = normalize(sign_c, m_c, e_c);
+                if (e, m) >= (c.2, c.1) { ((sign, m, e), c) } else { (c, (sign, m, e)) }
+            };
+            let (sign_x, m_x, e_x) = large;
+            // Align the smaller operand. The bits that are shifted out, as well as its lowest
+            // bit, are replaced by a sticky bit: the lowest bit of both operands is then zero
+            // unless the exact result is between two even integers, which is enough to round it.
+            let dist = (e_x - e_y) as u32;
+            let m_y = if dist == 0 {
+                m_y
+            } else if dist >= 126 {
+                1
+            } else {
+                let sticky = m_y & ((1 << (dist + 1)) - 1) != 0;
+                (m_y >> dist) & !1 | sticky as u128
+            };
+            if sign_x == sign_y {
+                self.round(sign_x, m_x + m_y, e_x)
+            } else if m_x == m_y {
+                // The exact result is zero, which is positive when rounding to nearest.
+                0
+            } else {
+                self.round(sign_x, m_x - m_y, e_x)
+            }
+        }
+    }
+
+    /// Shift a non-zero mantissa so that its most significant bit is the 126th one, which leaves
+    /// room for the carry of an addition.
+    fn normalize(sign: bool, m: u128, e: i32) -> (bool, u128, i32) {
+        let shift = m.leading_zeros() as i32 - 2;
+        (sign, m << shift, e - shift)
+    }
+
+    macro_rules! fma_model {
+        ($name: ident, $diag: literal, $ty: ty, $format: ident) => {
+            #[rustc_diagnostic_item = $diag]
+            pub(super) fn $name(a: $ty, b: $ty, c: $ty) -> $ty {
+                if !a.is_finite() || !b.is_finite() || a == 0.0 || b == 0.0 {
+                    // The product is exact, so there is a single rounding.
+                    a * b + c
+                } else if !c.is_finite() {
+                    c
+                } else {
+                    let bits =
+                        $format.fma(a.to_bits() as u64, b.to_bits() as u64, c.to_bits() as u64);
+                    <$ty>::from_bits(bits as _)
+                }
+            }
+        };
+    }
+
+    fma_model!(fmaf32, "KaniModelFmaf32", f32, F32);
+    fma_model!(fmaf64, "KaniModelFmaf64", f64, F64);
+
+    macro_rules! powi_model {
+        ($name: ident, $diag: literal, $ty: ty) => {
+            /// Exponentiation by squaring, which is how the compiler runtime implements `powi`.
+            #[rustc_diagnostic_item = $diag]
+            pub(super) fn $name(a: $ty, n: i32) -> $ty {
+                let mut base = a;
+                let mut exp = n;
+                let mut result = 1.0;
+                // The exponent has 32 bits, so this loop never needs more iterations.
+                for _ in 0..32 {
+                    if exp & 1 != 0 {
+                        result *= base;
+                    }
+                    exp /= 2;
+                    if exp == 0 {
+                        break;
+                    }
+                    base *= base;
+                }
+                if n < 0 { 1.0 / result } else { result }
+            }
+        };
+    }
+
+    powi_model!(powif32, "KaniModelPowif32", f32);
+    powi_model!(powif64, "KaniModelPowif64", f64);
+
+    /// Replaces [crate::internal::assume_float_values] with `--float-values no-nan`.
+    #[rustc_diagnostic_item = "KaniModelAssumeNoNan"]
+    pub(super) fn assume_no_nan(is_nan: bool, _is_infinite: bool, _is_subnormal: bool) {
+        crate::assume(!is_nan);
+    }
+
+    /// Replaces [crate::internal::assume_float_values] with `--float-values finite`.
+    #[rustc_diagnostic_item = "KaniModelAssumeFinite"]
+    pub(super) fn assume_finite(is_nan: bool, is_infinite: bool, _is_subnormal: bool) {
+        crate::assume(!is_nan && !is_infinite);
+    }
+
+    /// Replaces [crate::internal::assume_float_values] with `--float-values normal`.
+    #[rustc_diagnostic_item = "KaniModelAssumeNormal"]
+    pub(super) fn assume_normal(is_nan: bool, is_infinite: bool, is_subnormal: bool) {
+        crate::assume(!is_nan && !is_infinite && !is_subnormal);
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use super::float;
     use super::intrinsics as kani_intrinsic;
-    use std::{fmt::Debug, simd::*};
+    use std::{fmt::Debug, simd::*, vec::Vec};
 
     extern "platform-intrinsic" {
         fn simd_bitmask<T, U>(x: T) -> U;
@@ -211,4 +551,86 @@ fn check_bitmask<T, U, E, const LANES: usize>(mask: T)
             unsafe { simd_bitmask::<T, U>(mask) }
         );
     }
+
+    /// Values with special cases in the float models, and their neighbors.
+    const SPECIAL_F64: [f64; 12] = [
+        0.0,
+        -0.0,
+        1.0,
+        -1.5,
+        f64::MIN_POSITIVE,
+        f64::EPSILON,
+        5e-324,
+        -2.5e-310,
+        f64::MAX,
+        f64::MIN,
+        f64::INFINITY,
+        f64::NAN,
+    ];
+
+    /// A generator of bit patterns, since the tests can't depend on a random number crate.
+    fn bit_patterns(count: usize) -> impl Iterator<Item = u64> {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        (0..count).map(move |_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        })
+    }
+
+    fn assert_same_f64(model: f64, expected: f64, args: impl Debug) {
+        assert!(
+            model.to_bits() == expected.to_bits() || (model.is_nan() && expected.is_nan()),
+            "{args:?}: the model returned {model:e}, expected {expected:e}"
+        );
+    }
+
+    fn assert_same_f32(model: f32, expected: f32, args: impl Debug) {
+        assert!(
+            model.to_bits() == expected.to_bits() || (model.is_nan() && expected.is_nan()),
+            "{args:?}: the model returned {model:e}, expected {expected:e}"
+        );
+    }
+
+    /// Test that the `fma` models round the exact result like `mul_add`, including when the sum
+    /// cancels, overflows, or is subnormal.
+    #[test]
+    fn test_fma() {
+        for a in SPECIAL_F64 {
+            for b in SPECIAL_F64 {
+                for c in SPECIAL_F64 {
+                    assert_same_f64(float::fmaf64(a, b, c), a.mul_add(b, c), (a, b, c));
+                    let (a, b, c) = (a as f32, b as f32, c as f32);
+                    assert_same_f32(float::fmaf32(a, b, c), a.mul_add(b, c), (a, b, c));
+                }
+            }
+        }
+        let bits: Vec<u64> = bit_patterns(3000).collect();
+        for abc in bits.chunks(3) {
+            let [a, b, c] = [abc[0], abc[1], abc[2]].map(f64::from_bits);
+            assert_same_f64(float::fmaf64(a, b, c), a.mul_add(b, c), (a, b, c));
+            // Keep the exponents close, so that the product and the addend overlap.
+            let c = -(a * b) * f64::from_bits(0x3ff0_0000_0000_0000 | (abc[2] & 0xff));
+            assert_same_f64(float::fmaf64(a, b, c), a.mul_add(b, c), (a, b, c));
+            let [a, b, c] = [abc[0], abc[1], abc[2]].map(|x| f32::from_bits(x as u32));
+            assert_same_f32(float::fmaf32(a, b, c), a.mul_add(b, c), (a, b, c));
+            let c = -(a * b) * f32::from_bits(0x3f80_0000 | (abc[2] as u32 & 0xf));
+            assert_same_f32(float::fmaf32(a, b, c), a.mul_add(b, c), (a, b, c));
+        }
+    }
+
+    /// Test that the `powi` models compute the same result as the intrinsic.
+    #[test]
+    fn test_powi() {
+        let exps = [0, 1, -1, 2, 3, -7, 31, 64, -1074, 1023, i32::MAX, i32::MIN];
+        for a in SPECIAL_F64.into_iter().chain(bit_patterns(200).map(f64::from_bits)) {
+            for n in exps {
+                let n = std::hint::black_box(n);
+                assert_same_f64(float::powif64(a, n), a.powi(n), (a, n));
+                let a = a as f32;
+                assert_same_f32(float::powif32(a, n), a.powi(n), (a, n));
+            }
+        }
+    }
 }
@@ -0,0 +1,282 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Symbolic versions of the common `proptest` strategies, for the harnesses generated by
+//! `#[kani::proof_from_proptest]`.
+//!
+//! A proptest strategy samples random values. The strategies of this module produce a symbolic
+//! value that covers every value that the proptest strategy can sample instead, e.g., `0..10u8`
+//! produces a `u8` that is assumed to be in the range, and `prop::collection::vec(s, 0..4)`
+//! produces a vector with a symbolic length of at most 3. The names follow `proptest`, so the
+//! strategy expressions of a `proptest!` block resolve to this module when it's imported with
+//! `use kani::proptest::prelude::*`.
+//!
+//! Only the strategies of this module are supported. Strategies that `proptest` derives from a
+//! regular expression, or from the `proptest::arbitrary::Arbitrary` implementation of a type,
+//! don't have a symbolic version: use [crate::Arbitrary] for the latter.
+
+use crate::Arbitrary;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::{Range, RangeFrom, RangeInclusive};
+
+/// A strategy that produces a symbolic value.
+pub trait Strategy {
+    type Value;
+
+    /// A symbolic value that covers every value the strategy can produce.
+    fn value(&self) -> Self::Value;
+
+    /// Transform the values of the strategy with `fun`.
+    fn prop_map<O, F: Fn(Self::Value) -> O>(self, fun: F) -> Map<Self, F>
+    where
+        Self: Sized,
+    {
+        Map { source: self, fun }
+    }
+
+    /// Only keep the values of the strategy that satisfy `fun`. The values that don't are
+    /// assumed away, so `whence` is only kept for compatibility with `proptest`.
+    fn prop_filter<R, F: Fn(&Self::Value) -> bool>(self, whence: R, fun: F) -> Filter<Self, F>
+    where
+        Self: Sized,
+    {
+        let _ = whence;
+        Filter { source: self, fun }
+    }
+
+    /// Use each value of the strategy to create another strategy, and produce its values.
+    fn prop_flat_map<S: Strategy, F: Fn(Self::Value) -> S>(self, fun: F) -> FlatMap<Self, F>
+    where
+        Self: Sized,
+    {
+        FlatMap { source: self, fun }
+    }
+
+    /// Erase the type of the strategy.
+    fn boxed(self) -> BoxedStrategy<Self::Value>
+    where
+        Self: Sized + 'static,
+    {
+        BoxedStrategy(Box::new(self))
+    }
+}
+
+/// A strategy with an erased type, see [Strategy::boxed].
+pub struct BoxedStrategy<T>(Box<dyn Strategy<Value = T>>);
+
+impl<T> Strategy for BoxedStrategy<T> {
+    type Value = T;
+    fn value(&self) -> T {
+        self.0.value()
+    }
+}
+
+/// See [Strategy::prop_map].
+pub struct Map<S, F> {
+    source: S,
+    fun: F,
+}
+
+impl<S: Strategy, O, F: Fn(S::Value) -> O> Strategy for Map<S, F> {
+    type Value = O;
+    fn value(&self) -> O {
+        (self.fun)(self.source.value())
+    }
+}
+
+/// See [Strategy::prop_filter].
+pub struct Filter<S, F> {
+    source: S,
+    fun: F,
+}
+
+impl<S: Strategy, F: Fn(&S::Value) -> bool> Strategy for Filter<S, F> {
+    type Value = S::Value;
+    fn value(&self) -> S::Value {
+        let value = self.source.value();
+        crate::assume((self.fun)(&value));
+        value
+    }
+}
+
+/// See [Strategy::prop_flat_map].
+pub struct FlatMap<S, F> {
+    source: S,
+    fun: F,
+}
+
+impl<S: Strategy, T: Strategy, F: Fn(S::Value) -> T> Strategy for FlatMap<S, F> {
+    type Value = T::Value;
+    fn value(&self) -> T::Value {
+        (self.fun)(self.source.value()).value()
+    }
+}
+
+/// A strategy that always produces the same value.
+#[derive(Clone, Copy, Debug)]
+pub struct Just<T: Clone>(pub T);
+
+impl<T: Clone> Strategy for Just<T> {
+    type Value = T;
+    fn value(&self) -> T {
+        self.0.clone()
+    }
+}
+
+/// The strategy of [any].
+pub struct Any<T>(PhantomData<T>);
+
+impl<T: Arbitrary> Strategy for Any<T> {
+    type Value = T;
+    fn value(&self) -> T {
+        crate::any()
+    }
+}
+
+/// A strategy that produces any value of `T`.
+pub fn any<T: Arbitrary>() -> Any<T> {
+    Any(PhantomData)
+}
+
+macro_rules! range_strategy {
+    ($($typ:ty),*) => {
+        $(
+            impl Strategy for Range<$typ> {
+                type Value = $typ;
+                fn value(&self) -> $typ {
+                    crate::any_where(|value| self.contains(value))
+                }
+            }
+
+            impl Strategy for RangeInclusive<$typ> {
+                type Value = $typ;
+                fn value(&self) -> $typ {
+                    crate::any_where(|value| self.contains(value))
+                }
+            }
+
+            impl Strategy for RangeFrom<$typ> {
+                type Value = $typ;
+                fn value(&self) -> $typ {
+                    crate::any_where(|value| self.contains(value))
+                }
+            }
+        )*
+    };
+}
+
+range_strategy!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, char);
+
+macro_rules! tuple_strategy {
+    ($($strategy:ident),*) => {
+        #[allow(non_snake_case)]
+        impl<$($strategy: Strategy),*> Strategy for ($($strategy,)*) {
+            type Value = ($($strategy::Value,)*);
+            fn value(&self) -> Self::Value {
+                let ($($strategy,)*) = self;
+                ($($strategy.value(),)*)
+            }
+        }
+    };
+}
+
+tuple_strategy!(A);
+tuple_strategy!(A, B);
+tuple_strategy!(A, B, C);
+tuple_strategy!(A, B, C, D);
+tuple_strategy!(A, B, C, D, E);
+tuple_strategy!(A, B, C, D, E, F);
+tuple_strategy!(A, B, C, D, E, F, G);
+tuple_strategy!(A, B, C, D, E, F, G, H);
+
+impl<S: Strategy, const N: usize> Strategy for [S; N] {
+    type Value = [S::Value; N];
+    fn value(&self) -> Self::Value {
+        core::array::from_fn(|idx| self[idx].value())
+    }
+}
+
+/// Strategies for collections.
+pub mod collection {
+    use super::*;
+
+    /// The bounds of the size of a collection, whose maximum is inclusive.
+    #[derive(Clone, Copy, Debug)]
+    pub struct SizeRange {
+        min: usize,
+        max: usize,
+    }
+
+    impl From<usize> for SizeRange {
+        fn from(size: usize) -> SizeRange {
+            SizeRange { min: size, max: size }
+        }
+    }
+
+    impl From<Range<usize>> for SizeRange {
+        fn from(range: Range<usize>) -> SizeRange {
+            assert!(range.start < range.end, "the size range of a collection must not be empty");
+            SizeRange { min: range.start, max: range.end - 1 }
+        }
+    }
+
+    impl From<RangeInclusive<usize>> for SizeRange {
+        fn from(range: RangeInclusive<usize>) -> SizeRange {
+            SizeRange { min: *range.start(), max: *range.end() }
+        }
+    }
+
+    /// The strategy of [vec].
+    pub struct VecStrategy<S> {
+        element: S,
+        size: SizeRange,
+    }
+
+    impl<S: Strategy> Strategy for VecStrategy<S> {
+        type Value = Vec<S::Value>;
+        fn value(&self) -> Vec<S::Value> {
+            let SizeRange { min, max } = self.size;
+            let len = crate::any_where(|len: &usize| min <= *len && *len <= max);
+            let mut vec = Vec::with_capacity(len);
+            for _ in 0..len {
+                vec.push(self.element.value());
+            }
+            vec
+        }
+    }
+
+    /// A strategy that produces vectors whose length is in `size` and whose elements are
+    /// produced by `element`. Harnesses need an unwinding bound greater than the maximum length.
+    pub fn vec<S: Strategy>(element: S, size: impl Into<SizeRange>) -> VecStrategy<S> {
+        VecStrategy { element, size: size.into() }
+    }
+}
+
+/// Strategies for `Option`.
+pub mod option {
+    use super::*;
+
+    /// The strategy of [of].
+    pub struct OptionStrategy<S>(S);
+
+    impl<S: Strategy> Strategy for OptionStrategy<S> {
+        type Value = Option<S::Value>;
+        fn value(&self) -> Option<S::Value> {
+            if crate::any() { Some(self.0.value()) } else { None }
+        }
+    }
+
+    /// A strategy that produces `None` or a value of `element`.
+    pub fn of<S: Strategy>(element: S) -> OptionStrategy<S> {
+        OptionStrategy(element)
+    }
+}
+
+/// The items of `proptest::prelude` that have a symbolic version.
+pub mod prelude {
+    pub use super::{any, BoxedStrategy, Just, Strategy};
+    /// Re-export of this module under the name that `proptest::prelude` gives to `proptest`.
+    pub use crate::proptest as prop;
+}
@@ -0,0 +1,43 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module provides functions to create non-null pointers into fresh allocations with
+//! symbolic contents. They are useful to set up the internal state of containers that manage
+//! their memory by hand, e.g., a `NonNull<T>` and a length.
+//!
+//! The allocations are never freed by Kani. They are made by the global allocator with the layout
+//! of a `Box`, so the harness can take ownership of them back with [`Box::from_raw`].
+use crate::{Arbitrary, BoundedArbitrary};
+use alloc::boxed::Box;
+use core::ptr::NonNull;
+
+/// Creates a pointer to a fresh allocation that holds a symbolic *valid* value of type `T`.
+///
+/// # Example:
+///
+/// ```rust
+/// let ptr = kani::ptr::any_non_null::<u32>();
+/// let val = unsafe { *ptr.as_ptr() };
+/// unsafe { drop(Box::from_raw(ptr.as_ptr())) };
+/// ```
+pub fn any_non_null<T: Arbitrary>() -> NonNull<T> {
+    NonNull::from(Box::leak(Box::new(T::any())))
+}
+
+/// Creates a pointer to a fresh allocation that holds a slice of at most `MAX_LENGTH` symbolic
+/// *valid* values of type `T`. The length of the slice is also symbolic.
+///
+/// Use [`NonNull::len`] to get the length of the slice and [`NonNull::cast`] to get a pointer to
+/// its first element. Building the slice requires unwinding loops up to `MAX_LENGTH + 1` times.
+///
+/// # Example:
+///
+/// ```rust
+/// let slice = kani::ptr::any_non_null_slice::<u8, 4>();
+/// let (data, len): (NonNull<u8>, usize) = (slice.cast(), slice.len());
+/// assert!(len <= 4);
+/// ```
+pub fn any_non_null_slice<T: Arbitrary, const MAX_LENGTH: usize>() -> NonNull<[T]> {
+    let elems = Box::<[T]>::bounded_any::<MAX_LENGTH>();
+    NonNull::from(Box::leak(elems))
+}
@@ -0,0 +1,83 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for harnesses that check that deserializing a serialized value gives back the value.
+//!
+//! These harnesses usually share the same shape: create a symbolic value, serialize it,
+//! deserialize the bytes and check that the result is equal to the original value. The
+//! [crate::roundtrip!] macro writes these steps, so the harness only names the functions and the
+//! type of the value.
+
+/// The results of the deserialization functions that [crate::roundtrip!] accepts, which may fail
+/// to deserialize the bytes.
+pub trait Decoded<T> {
+    /// The deserialized value, or `None` if deserialization failed.
+    fn into_decoded(self) -> Option<T>;
+}
+
+impl<T> Decoded<T> for Option<T> {
+    fn into_decoded(self) -> Option<T> {
+        self
+    }
+}
+
+impl<T, E> Decoded<T> for Result<T, E> {
+    fn into_decoded(self) -> Option<T> {
+        self.ok()
+    }
+}
+
+/// Checks that deserializing a serialized symbolic value succeeds and gives back the value.
+///
+/// The value is created with [crate::any], or with [crate::bounded_any] if a bound is given
+/// with `= bounded(N)`, e.g., to limit the length of a vector. It's passed by reference to
+/// `serialize`, which returns the bytes, and the bytes are passed by reference to
+/// `deserialize`, which returns either an `Option` or a `Result` of the type of the value. The
+/// type must implement `PartialEq`.
+///
+/// Functions that don't have these signatures, e.g., serde serializers, can be adapted with
+/// closures. Bounded values are built with a loop, so the harness must unwind loops at least
+/// `N + 1` times, plus what the functions need to process `N` elements.
+///
+/// # Example:
+///
+/// ```rust
+/// kani::roundtrip! {
+///     serialize = Header::to_bytes, deserialize = Header::from_bytes;
+///     header: Header
+/// }
+/// kani::roundtrip! {
+///     serialize = |list: &Vec<u16>| encode_list(list), deserialize = decode_list;
+///     list: Vec<u16> = bounded(4)
+/// }
+/// ```
+#[macro_export]
+macro_rules! roundtrip {
+    (
+        serialize = $serialize:expr, deserialize = $deserialize:expr;
+        $value:ident : $ty:ty $(= bounded($bound:expr))? $(,)?
+    ) => {{
+        let $value: $ty = $crate::roundtrip!(@any $ty $(, $bound)?);
+        let bytes = ($serialize)(&$value);
+        let decoded: ::core::option::Option<$ty> =
+            $crate::roundtrip::Decoded::into_decoded(($deserialize)(&bytes));
+        $crate::assert(
+            decoded.is_some(),
+            concat!("deserializing the serialized `", stringify!($value), "` failed"),
+        );
+        $crate::assert(
+            decoded.as_ref() == ::core::option::Option::Some(&$value),
+            concat!(
+                "deserializing the serialized `",
+                stringify!($value),
+                "` gives a different value"
+            ),
+        );
+    }};
+    (@any $ty:ty) => {
+        $crate::any()
+    };
+    (@any $ty:ty, $bound:expr) => {
+        $crate::bounded_any::<$ty, { $bound }>()
+    };
+}
@@ -0,0 +1,55 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for harnesses that check stateful APIs against an arbitrary sequence of calls.
+//!
+//! These harnesses usually share the same shape: pick a number of steps, then in each step pick
+//! one operation, pick its arguments, and apply it to some state before checking an invariant.
+//! The [crate::scenario!] macro writes the loop and the dispatch, so the harness only declares
+//! the operations.
+
+/// Applies a nondeterministic sequence of at most `max_steps` operations.
+///
+/// Each operation has a name, an optional list of arguments and a body. In every step, one of the
+/// operations is chosen, each of its arguments is bound to a value created with [crate::any],
+/// and its body is executed. The bodies can access and mutate any variable in scope, which is
+/// usually the state under test and a model of it. The optional `invariant` block is declared
+/// next to the bound and runs after every step.
+///
+/// The sequence is built with a loop, so the harness must unwind loops at least
+/// `max_steps + 1` times.
+///
+/// # Example:
+///
+/// ```rust
+/// let mut stack = Vec::new();
+/// let mut len = 0;
+/// kani::scenario! {
+///     max_steps = 4, invariant = { assert_eq!(stack.len(), len); };
+///     push(value: u8) => { stack.push(value); len += 1; }
+///     pop => { if stack.pop().is_some() { len -= 1; } }
+///     clear => { stack.clear(); len = 0; }
+/// }
+/// ```
+#[macro_export]
+macro_rules! scenario {
+    (
+        max_steps = $max_steps:expr $(, invariant = $invariant:block)?;
+        $($op:ident $(($($arg:ident : $ty:ty),* $(,)?))? => $body:block)+
+    ) => {{
+        let steps: usize = $crate::any_where(|steps| *steps <= $max_steps);
+        let num_ops = [$(stringify!($op)),+].len();
+        for _ in 0..steps {
+            let choice: usize = $crate::any_where(|choice| *choice < num_ops);
+            let mut _op = 0usize;
+            $(
+                if choice == _op {
+                    $($(let $arg: $ty = $crate::any();)*)?
+                    $body
+                }
+                _op += 1;
+            )+
+            $($invariant)?
+        }
+    }};
+}
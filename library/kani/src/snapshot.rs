@@ -0,0 +1,80 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module provides snapshots of values, which make it easy to check that an operation
+//! doesn't change a value without comparing it field by field, e.g.:
+//!
+//! ```rust
+//! let mut pair: (u32, u64) = kani::any();
+//! let before = kani::snapshot::Snapshot::take(&pair.1);
+//! pair.0 = pair.0.wrapping_add(1);
+//! before.assert_unchanged(&pair.1);
+//! ```
+//!
+//! A [Snapshot] is a copy of the bytes of the value itself, which Kani compares with the current
+//! value as a single bit-vector, without unwinding any loop. It works for any sized type, but it
+//! doesn't follow pointers: the elements of a `Vec` live on the heap, so only changes to its
+//! pointer, length and capacity are detected. Use a [DeepSnapshot] for these types, which clones
+//! the value and compares it with [PartialEq].
+
+use core::intrinsics::raw_eq;
+use core::mem::MaybeUninit;
+
+/// A copy of the bytes of a value at the time the snapshot was taken.
+///
+/// The copy is never used as a value of type `T`, so it is never dropped either.
+pub struct Snapshot<T> {
+    bytes: MaybeUninit<T>,
+}
+
+impl<T> Snapshot<T> {
+    /// Copies the bytes of `value`.
+    pub fn take(value: &T) -> Self {
+        let mut bytes = MaybeUninit::uninit();
+        // SAFETY: The destination is a fresh local of the same type as `value`.
+        unsafe { core::ptr::copy_nonoverlapping(value, bytes.as_mut_ptr(), 1) };
+        Snapshot { bytes }
+    }
+
+    /// Checks that the bytes of `value` are the same as when the snapshot was taken.
+    ///
+    /// Padding bytes are compared as well, so for types with padding, overwriting the value with
+    /// an equal one may still count as a change.
+    pub fn is_unchanged(&self, value: &T) -> bool {
+        // SAFETY: Both sides point to `size_of::<T>()` readable bytes.
+        unsafe { raw_eq(self.bytes.assume_init_ref(), value) }
+    }
+
+    /// Asserts that the bytes of `value` are the same as when the snapshot was taken.
+    pub fn assert_unchanged(&self, value: &T) {
+        crate::assert(self.is_unchanged(value), "value changed since its snapshot was taken");
+    }
+}
+
+/// A clone of a value at the time the snapshot was taken. Unlike a [Snapshot], this compares
+/// the values with [PartialEq], which includes the data that the value owns through pointers.
+pub struct DeepSnapshot<T> {
+    value: T,
+}
+
+impl<T: Clone + PartialEq> DeepSnapshot<T> {
+    /// Clones `value`.
+    pub fn take(value: &T) -> Self {
+        DeepSnapshot { value: value.clone() }
+    }
+
+    /// The value at the time the snapshot was taken.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Checks that `value` is equal to the value when the snapshot was taken.
+    pub fn is_unchanged(&self, value: &T) -> bool {
+        self.value == *value
+    }
+
+    /// Asserts that `value` is equal to the value when the snapshot was taken.
+    pub fn assert_unchanged(&self, value: &T) {
+        crate::assert(self.is_unchanged(value), "value changed since its snapshot was taken");
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module provides readers with symbolic content, which are used to verify streaming
+//! parsers and decoders. Unlike a single symbolic buffer, these readers also pick how the
+//! content is split across reads, so a harness covers every way in which the input can be
+//! fragmented.
+
+use crate::{any, any_where};
+use alloc::vec::Vec;
+use std::io::{self, Read};
+
+/// A reader whose content and chunks are symbolic. See [any_reader].
+#[derive(Clone, Debug)]
+pub struct AnyReader {
+    data: Vec<u8>,
+    pos: usize,
+    max_chunk: usize,
+}
+
+/// Creates a reader of at most `max_total` arbitrary bytes, where each call to `read` returns an
+/// arbitrary number of bytes between one and `max_chunk`, or fewer if the buffer or the
+/// remaining content is smaller. A read returns zero bytes only at the end of the content or
+/// when the buffer is empty, as `io::Read` requires.
+///
+/// Building the content iterates over `max_total`, and each read iterates over `max_chunk`, so
+/// the harness must unwind loops at least `max_total + 1` times.
+///
+/// ```no_run
+/// use std::io::Read;
+///
+/// let mut reader = kani::stream::any_reader(8, 3);
+/// let content = reader.content().to_vec();
+/// let mut decoded = Vec::new();
+/// reader.read_to_end(&mut decoded).unwrap();
+/// assert_eq!(decoded, content);
+/// ```
+pub fn any_reader(max_total: usize, max_chunk: usize) -> AnyReader {
+    assert!(max_chunk > 0, "`max_chunk` must be greater than zero");
+    let len: usize = any_where(|len| *len <= max_total);
+    let data = (0..len).map(|_| any()).collect();
+    AnyReader { data, pos: 0, max_chunk }
+}
+
+impl AnyReader {
+    /// The whole content of the stream, including the bytes that weren't read yet.
+    pub fn content(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The content that wasn't read yet.
+    pub fn remaining(&self) -> &[u8] {
+        &self.data[self.pos..]
+    }
+}
+
+impl Read for AnyReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let limit = buf.len().min(self.max_chunk).min(self.data.len() - self.pos);
+        if limit == 0 {
+            return Ok(0);
+        }
+        let len: usize = any_where(|len| *len >= 1 && *len <= limit);
+        buf[..len].copy_from_slice(&self.data[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
@@ -0,0 +1,464 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for generating arbitrary strings constrained by a regular expression.
+//!
+//! The pattern is compiled into a position (Glushkov) automaton, i.e., an automaton without
+//! epsilon transitions where each state corresponds to one character class of the pattern.
+//! The symbolic string is then constrained to be accepted by this automaton.
+//!
+//! Only a subset of the regular expression syntax is supported, and only ASCII strings are
+//! generated:
+//!  - Literals, `.` (any character except `\n`), and escaped meta-characters (e.g. `\.`).
+//!  - Character classes, such as `[a-z_]` and `[^0-9]`, and the shorthand classes `\d`, `\w`,
+//!    `\s` and their negations `\D`, `\W`, `\S`.
+//!  - Groups `(...)`, alternation `|`, and the quantifiers `*`, `+`, `?`, `{n}`, `{n,}` and
+//!    `{n,m}`.
+//!
+//! The whole string must match the pattern, so the anchors `^` and `$` are only accepted at the
+//! beginning and at the end of the pattern respectively, where they have no effect.
+use crate::{any, any_where, assume};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Generates an arbitrary ASCII string that fully matches the regular expression `pattern` and
+/// whose length is at most `max_len`.
+///
+/// See the [module documentation](self) for the supported regular expression syntax.
+///
+/// # Panics
+///
+/// This function panics if `pattern` is malformed or uses an unsupported construct.
+///
+/// # Example:
+///
+/// ```rust
+/// let id = kani::any_string_matching("[a-z][a-z0-9_]*", 8);
+/// assert!(id.len() <= 8);
+/// assert!(id.starts_with(|c: char| c.is_ascii_lowercase()));
+/// ```
+pub fn any_string_matching(pattern: &str, max_len: usize) -> String {
+    let automaton = Automaton::new(pattern);
+    let len: usize = any_where(|len| *len <= max_len);
+    let mut bytes = Vec::with_capacity(max_len);
+    let mut state = automaton.initial_state();
+    for _ in 0..max_len {
+        if bytes.len() == len {
+            break;
+        }
+        let byte: u8 = any();
+        state = automaton.step(&state, byte);
+        // Prune paths that can no longer match as early as possible.
+        assume(state.iter().any(|active| *active));
+        bytes.push(byte);
+    }
+    assume(automaton.is_accepting(&state));
+    // SAFETY: Every character class only contains ASCII characters, so the string is valid UTF-8.
+    unsafe { String::from_utf8_unchecked(bytes) }
+}
+
+/// A set of ASCII characters, where bit `i` is set if the character `i` is in the set.
+type CharClass = u128;
+
+const NEWLINE: CharClass = 1 << b'\n';
+const DIGITS: CharClass = range(b'0', b'9');
+const WORD: CharClass = DIGITS | range(b'a', b'z') | range(b'A', b'Z') | 1 << b'_';
+const SPACES: CharClass =
+    1 << b' ' | 1 << b'\t' | 1 << b'\n' | 1 << b'\r' | 1 << 0x0b /* \v */ | 1 << 0x0c /* \f */;
+
+/// The class containing all characters from `lo` to `hi` (inclusive).
+const fn range(lo: u8, hi: u8) -> CharClass {
+    let mut class = 0;
+    let mut c = lo;
+    while c <= hi {
+        class |= 1 << c;
+        c += 1;
+    }
+    class
+}
+
+/// The abstract syntax tree of a regular expression after desugaring all quantifiers.
+#[derive(Clone, Debug)]
+enum Regex {
+    /// Matches the empty string.
+    Empty,
+    /// Matches exactly one character from the class.
+    Class(CharClass),
+    Concat(Vec<Regex>),
+    Alt(Vec<Regex>),
+    Star(Box<Regex>),
+}
+
+/// A recursive descent parser for the supported regular expression syntax.
+struct Parser<'a> {
+    pattern: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(pattern: &'a str) -> Regex {
+        let mut parser = Parser { pattern: pattern.as_bytes(), pos: 0 };
+        parser.eat(b'^');
+        let regex = parser.alternation();
+        parser.eat(b'$');
+        if parser.pos < parser.pattern.len() {
+            parser.error("unexpected character");
+        }
+        regex
+    }
+
+    fn error(&self, msg: &str) -> ! {
+        panic!(
+            "Unsupported regular expression `{}` at position {}: {msg}",
+            String::from_utf8_lossy(self.pattern),
+            self.pos
+        )
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.pattern.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> u8 {
+        let Some(c) = self.peek() else { self.error("unexpected end of pattern") };
+        if !c.is_ascii() {
+            self.error("only ASCII characters are supported");
+        }
+        self.pos += 1;
+        c
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, c: u8) {
+        if !self.eat(c) {
+            self.error(&format!("expected `{}`", c as char));
+        }
+    }
+
+    fn alternation(&mut self) -> Regex {
+        let mut branches = vec![self.concatenation()];
+        while self.eat(b'|') {
+            branches.push(self.concatenation());
+        }
+        if branches.len() == 1 { branches.pop().unwrap() } else { Regex::Alt(branches) }
+    }
+
+    fn concatenation(&mut self) -> Regex {
+        let mut items = vec![];
+        while let Some(c) = self.peek() {
+            if c == b'|' || c == b')' || (c == b'$' && self.pos + 1 == self.pattern.len()) {
+                break;
+            }
+            items.push(self.repetition());
+        }
+        Regex::Concat(items)
+    }
+
+    fn repetition(&mut self) -> Regex {
+        let mut regex = self.atom();
+        loop {
+            regex = match self.peek() {
+                Some(b'*') => Regex::Star(Box::new(regex)),
+                Some(b'+') => Regex::Concat(vec![regex.clone(), Regex::Star(Box::new(regex))]),
+                Some(b'?') => Regex::Alt(vec![regex, Regex::Empty]),
+                Some(b'{') => {
+                    self.pos += 1;
+                    let (min, max) = self.bounds();
+                    repeat(regex, min, max)
+                }
+                _ => return regex,
+            };
+            // Skip the quantifier (or the closing `}` of the bounds).
+            self.pos += 1;
+        }
+    }
+
+    /// Parse the bounds of a `{n}`, `{n,}` or `{n,m}` quantifier up to (excluding) the `}`.
+    fn bounds(&mut self) -> (usize, Option<usize>) {
+        let min = self.number();
+        let max = if self.eat(b',') {
+            if self.peek() == Some(b'}') { None } else { Some(self.number()) }
+        } else {
+            Some(min)
+        };
+        if self.peek() != Some(b'}') {
+            self.error("expected `}`");
+        }
+        if max.is_some_and(|max| max < min) {
+            self.error("invalid repetition bounds");
+        }
+        (min, max)
+    }
+
+    fn number(&mut self) -> usize {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        core::str::from_utf8(&self.pattern[start..self.pos])
+            .unwrap()
+            .parse()
+            .unwrap_or_else(|_| self.error("expected a number"))
+    }
+
+    fn atom(&mut self) -> Regex {
+        match self.next() {
+            b'(' => {
+                let regex = self.alternation();
+                self.expect(b')');
+                regex
+            }
+            b'[' => Regex::Class(self.class()),
+            b'.' => Regex::Class(!NEWLINE),
+            b'\\' => Regex::Class(self.escape()),
+            b'*' | b'+' | b'?' | b'{' => self.error("quantifier without a target"),
+            b')' | b'^' | b'$' => self.error("unsupported or unbalanced meta-character"),
+            c => Regex::Class(1 << c),
+        }
+    }
+
+    /// Parse a character class up to (and including) the closing `]`.
+    fn class(&mut self) -> CharClass {
+        let negated = self.eat(b'^');
+        let mut class = 0;
+        let mut first = true;
+        loop {
+            let c = self.next();
+            let lo = match c {
+                b']' if !first => break,
+                b'\\' => {
+                    let escaped = self.escape();
+                    if escaped.count_ones() != 1 {
+                        class |= escaped;
+                        first = false;
+                        continue;
+                    }
+                    escaped.trailing_zeros() as u8
+                }
+                c => c,
+            };
+            first = false;
+            if self.peek() == Some(b'-') && self.pattern.get(self.pos + 1) != Some(&b']') {
+                self.pos += 1;
+                let hi = match self.next() {
+                    b'\\' => {
+                        let escaped = self.escape();
+                        if escaped.count_ones() != 1 {
+                            self.error("invalid range in character class");
+                        }
+                        escaped.trailing_zeros() as u8
+                    }
+                    hi => hi,
+                };
+                if hi < lo {
+                    self.error("invalid range in character class");
+                }
+                class |= range(lo, hi);
+            } else {
+                class |= 1 << lo;
+            }
+        }
+        if negated { !class } else { class }
+    }
+
+    /// Parse the character after a `\`.
+    fn escape(&mut self) -> CharClass {
+        match self.next() {
+            b'd' => DIGITS,
+            b'D' => !DIGITS,
+            b'w' => WORD,
+            b'W' => !WORD,
+            b's' => SPACES,
+            b'S' => !SPACES,
+            b'n' => NEWLINE,
+            b't' => 1 << b'\t',
+            b'r' => 1 << b'\r',
+            c if c.is_ascii_alphanumeric() => self.error("unsupported escape sequence"),
+            c => 1 << c,
+        }
+    }
+}
+
+/// Desugar `regex{min,max}`, where `max == None` means unbounded.
+fn repeat(regex: Regex, min: usize, max: Option<usize>) -> Regex {
+    let mut items = vec![regex.clone(); min];
+    match max {
+        None => items.push(Regex::Star(Box::new(regex))),
+        Some(max) => {
+            // Nest the optional occurrences, i.e. `(x(x)?)?`, to avoid redundant matches.
+            let optional = (min..max).fold(Regex::Empty, |tail, _| {
+                Regex::Alt(vec![Regex::Concat(vec![regex.clone(), tail]), Regex::Empty])
+            });
+            items.push(optional);
+        }
+    }
+    Regex::Concat(items)
+}
+
+/// A position automaton. State `0` is the initial state, and every other state corresponds to
+/// one character class occurrence in the pattern, which has to match the last character read.
+struct Automaton {
+    /// The class of characters that may lead to each state.
+    classes: Vec<CharClass>,
+    /// The states that have a transition to each state.
+    predecessors: Vec<Vec<usize>>,
+    accepting: Vec<bool>,
+}
+
+/// Summary of a sub-expression used to build the automaton.
+struct Positions {
+    nullable: bool,
+    first: Vec<usize>,
+    last: Vec<usize>,
+}
+
+impl Automaton {
+    fn new(pattern: &str) -> Automaton {
+        let regex = Parser::parse(pattern);
+        let mut automaton =
+            Automaton { classes: vec![0], predecessors: vec![vec![]], accepting: vec![] };
+        let positions = automaton.build(&regex);
+        automaton.connect(&[0], &positions.first);
+        automaton.accepting = vec![false; automaton.classes.len()];
+        automaton.accepting[0] = positions.nullable;
+        for state in positions.last {
+            automaton.accepting[state] = true;
+        }
+        automaton
+    }
+
+    fn connect(&mut self, from: &[usize], to: &[usize]) {
+        for target in to {
+            self.predecessors[*target].extend_from_slice(from);
+        }
+    }
+
+    fn build(&mut self, regex: &Regex) -> Positions {
+        match regex {
+            Regex::Empty => Positions { nullable: true, first: vec![], last: vec![] },
+            Regex::Class(class) => {
+                let state = self.classes.len();
+                self.classes.push(*class);
+                self.predecessors.push(vec![]);
+                Positions { nullable: false, first: vec![state], last: vec![state] }
+            }
+            Regex::Concat(items) => {
+                let mut result = Positions { nullable: true, first: vec![], last: vec![] };
+                for item in items {
+                    let next = self.build(item);
+                    self.connect(&result.last, &next.first);
+                    if result.nullable {
+                        result.first.extend_from_slice(&next.first);
+                    }
+                    if next.nullable {
+                        result.last.extend(next.last);
+                    } else {
+                        result.last = next.last;
+                    }
+                    result.nullable &= next.nullable;
+                }
+                result
+            }
+            Regex::Alt(branches) => {
+                let mut result = Positions { nullable: false, first: vec![], last: vec![] };
+                for branch in branches {
+                    let next = self.build(branch);
+                    result.nullable |= next.nullable;
+                    result.first.extend(next.first);
+                    result.last.extend(next.last);
+                }
+                result
+            }
+            Regex::Star(inner) => {
+                let inner = self.build(inner);
+                self.connect(&inner.last, &inner.first);
+                Positions { nullable: true, ..inner }
+            }
+        }
+    }
+
+    fn initial_state(&self) -> Vec<bool> {
+        let mut state = vec![false; self.classes.len()];
+        state[0] = true;
+        state
+    }
+
+    fn step(&self, state: &[bool], byte: u8) -> Vec<bool> {
+        self.classes
+            .iter()
+            .zip(&self.predecessors)
+            .map(|(class, preds)| {
+                byte.is_ascii() && class & (1 << byte) != 0 && preds.iter().any(|pred| state[*pred])
+            })
+            .collect()
+    }
+
+    fn is_accepting(&self, state: &[bool]) -> bool {
+        state.iter().zip(&self.accepting).any(|(active, accepting)| *active && *accepting)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Automaton;
+
+    fn matches(pattern: &str, input: &str) -> bool {
+        let automaton = Automaton::new(pattern);
+        let state =
+            input.bytes().fold(automaton.initial_state(), |state, b| automaton.step(&state, b));
+        automaton.is_accepting(&state)
+    }
+
+    #[test]
+    fn test_literals_and_classes() {
+        assert!(matches("abc", "abc"));
+        assert!(!matches("abc", "ab"));
+        assert!(!matches("abc", "abcd"));
+        assert!(matches("^a.c$", "a-c"));
+        assert!(!matches("a.c", "a\nc"));
+        assert!(matches(r"[a-z_][\w]*", "snake_case1"));
+        assert!(!matches("[a-z]", "A"));
+        assert!(matches("[^0-9]", "x"));
+        assert!(!matches("[^0-9]", "7"));
+        assert!(matches(r"\d\.\d", "1.5"));
+        assert!(matches("[]a]", "]"));
+        assert!(matches("[a-]", "-"));
+    }
+
+    #[test]
+    fn test_quantifiers_and_groups() {
+        assert!(matches("", ""));
+        assert!(matches("a*", ""));
+        assert!(matches("a+b?", "aaa"));
+        assert!(!matches("a+", ""));
+        assert!(matches("(ab|cd)*", "abcdab"));
+        assert!(!matches("(ab|cd)*", "abc"));
+        assert!(matches(r"\d{2,3}", "123"));
+        assert!(!matches(r"\d{2,3}", "1"));
+        assert!(!matches(r"\d{2,3}", "1234"));
+        assert!(matches("x{2}", "xx"));
+        assert!(matches("x{2,}", "xxxxx"));
+        assert!(!matches("x{2,}", "x"));
+        assert!(matches("(a|)b", "b"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unsupported regular expression `(ab`")]
+    fn test_unbalanced_group() {
+        Automaton::new("(ab");
+    }
+
+    #[test]
+    #[should_panic(expected = "quantifier without a target")]
+    fn test_dangling_quantifier() {
+        Automaton::new("*a");
+    }
+}
@@ -0,0 +1,278 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module contains a model of threads to verify concurrent code with a bounded number of
+//! threads, e.g.:
+//!
+//! ```rust
+//! use std::sync::atomic::{AtomicU32, Ordering};
+//! use std::sync::Arc;
+//!
+//! let counter = Arc::new(AtomicU32::new(0));
+//! let other = counter.clone();
+//! let handle = kani::thread::spawn(move || other.fetch_add(1, Ordering::Relaxed));
+//! counter.fetch_add(1, Ordering::Relaxed);
+//! handle.join().unwrap();
+//! assert_eq!(counter.load(Ordering::Relaxed), 2);
+//! ```
+//!
+//! The threads are modeled with CBMC's threads, so Kani checks the assertions for every
+//! interleaving of the steps of the threads. The threads are sequentially consistent, unless a
+//! weaker memory model is chosen with `--memory-model`, and `--race-checks` adds checks for data
+//! races on global variables.
+//!
+//! Kani can't verify the implementation of `std::thread::spawn` and `std::sync::Mutex`, which
+//! rely on the operating system, so `--race-checks` stubs `std::thread::spawn`,
+//! `JoinHandle::join`, `JoinHandle::is_finished` and `Mutex::lock` with the models of this module
+//! in every harness. Without it, harnesses should use [spawn] and [Mutex] instead, e.g., by
+//! importing them with `#[cfg(kani)]`. Threads block by discarding the executions in which they
+//! can't make progress, so a deadlock isn't reported as a failure: use `kani::cover!` to check
+//! that the end of the harness is reachable.
+//!
+//! Outside of verification, e.g., in concrete playback, a thread runs to completion when it is
+//! spawned.
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::any::Any;
+use core::cell::UnsafeCell;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// The result of [JoinHandle::join], which has the same type as `std::thread::Result`.
+///
+/// A panic in a thread is a verification failure, so the result is always `Ok`.
+pub type Result<T> = core::result::Result<T, Box<dyn Any + Send + 'static>>;
+
+/// The state that a thread shares with its [JoinHandle].
+struct Packet<T> {
+    finished: AtomicBool,
+    result: UnsafeCell<Option<T>>,
+}
+
+// SAFETY: The result is only written by the thread before it sets `finished`, and it is only read
+// by the handle after `finished` is set.
+unsafe impl<T: Send> Sync for Packet<T> {}
+
+/// The closure that a new thread runs and where it stores its result.
+struct Start<F, T> {
+    f: F,
+    packet: Arc<Packet<T>>,
+}
+
+/// Spawns a new thread that runs `f`, and returns a handle to wait for its result.
+///
+/// The thread runs concurrently with the thread that spawned it, and Kani explores every
+/// interleaving of their steps.
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let packet =
+        Arc::new(Packet { finished: AtomicBool::new(false), result: UnsafeCell::new(None) });
+    let start = Box::new(Start { f, packet: packet.clone() });
+    crate::internal::spawn_thread(run::<F, T>, Box::into_raw(start).cast());
+    JoinHandle { packet }
+}
+
+/// The entry point of the threads started by [spawn].
+fn run<F: FnOnce() -> T, T>(start: *mut u8) {
+    // SAFETY: `spawn` gives the ownership of a boxed `Start<F, T>` to the thread.
+    let start = unsafe { Box::from_raw(start.cast::<Start<F, T>>()) };
+    let Start { f, packet } = *start;
+    let result = f();
+    // SAFETY: The handle doesn't read the result before `finished` is set.
+    unsafe { *packet.result.get() = Some(result) };
+    packet.finished.store(true, Ordering::Release);
+}
+
+/// A handle to wait for a thread spawned with [spawn].
+pub struct JoinHandle<T> {
+    packet: Arc<Packet<T>>,
+}
+
+impl<T> JoinHandle<T> {
+    /// Waits for the thread to finish and returns its result.
+    ///
+    /// Kani discards the executions in which the thread hasn't finished when this is called.
+    pub fn join(self) -> Result<T> {
+        crate::assume(self.is_finished());
+        // SAFETY: The thread doesn't access the result after `finished` is set.
+        let result = unsafe { (*self.packet.result.get()).take() };
+        Ok(result.unwrap())
+    }
+
+    /// Whether the thread has finished running its closure.
+    pub fn is_finished(&self) -> bool {
+        self.packet.finished.load(Ordering::Acquire)
+    }
+}
+
+/// A mutual exclusion lock for data shared by threads spawned with [spawn].
+///
+/// Unlike `std::sync::Mutex`, the lock is never poisoned, since a panic is a verification
+/// failure, so [Mutex::lock] returns the guard directly.
+pub struct Mutex<T: ?Sized> {
+    locked: AtomicBool,
+    data: UnsafeCell<T>,
+}
+
+// SAFETY: The data is only accessed through a guard, and there is at most one guard at a time.
+unsafe impl<T: ?Sized + Send> Send for Mutex<T> {}
+unsafe impl<T: ?Sized + Send> Sync for Mutex<T> {}
+
+impl<T> Mutex<T> {
+    /// Creates an unlocked mutex that holds `value`.
+    pub const fn new(value: T) -> Self {
+        Mutex { locked: AtomicBool::new(false), data: UnsafeCell::new(value) }
+    }
+
+    /// Consumes the mutex and returns the value it holds.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized> Mutex<T> {
+    /// Waits until the mutex is unlocked and locks it. The mutex is unlocked when the guard is
+    /// dropped.
+    ///
+    /// Kani discards the executions in which the mutex is locked by another thread when this is
+    /// called, so the lock is acquired in a single step.
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        crate::assume(self.acquire());
+        MutexGuard { mutex: self }
+    }
+
+    /// Locks the mutex if it isn't locked, without waiting.
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        // The guard must only be created on success, since dropping it unlocks the mutex.
+        if self.acquire() { Some(MutexGuard { mutex: self }) } else { None }
+    }
+
+    /// Returns a mutable reference to the value, which doesn't need to lock the mutex since the
+    /// mutex is borrowed mutably.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    fn acquire(&self) -> bool {
+        self.locked.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_ok()
+    }
+}
+
+/// Gives access to the value of a locked [Mutex], and unlocks it when it is dropped.
+pub struct MutexGuard<'a, T: ?Sized> {
+    mutex: &'a Mutex<T>,
+}
+
+impl<T: ?Sized> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: The guard has exclusive access to the value while the mutex is locked.
+        unsafe { &*self.mutex.data.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: The guard has exclusive access to the value while the mutex is locked.
+        unsafe { &mut *self.mutex.data.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for MutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.mutex.locked.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(all(feature = "std", target_os = "linux"))]
+pub use std_models::pthread_detach;
+#[cfg(feature = "std")]
+pub use std_models::{std_is_finished, std_join, std_lock, std_spawn};
+
+/// The models that `--race-checks` stubs the threads and mutexes of the standard library with.
+#[cfg(feature = "std")]
+mod std_models {
+    use super::{JoinHandle, Packet};
+    use alloc::boxed::Box;
+    use alloc::sync::Arc;
+    use core::mem;
+    use core::sync::atomic::AtomicUsize;
+    use std::sync::{LockResult, Mutex, MutexGuard, TryLockError};
+
+    /// The state of a `std::thread::JoinHandle` created by [std_spawn], which is never freed.
+    ///
+    /// The standard handle can't be built outside of the standard library, so every word of it
+    /// points to this state instead. The handle holds the id of the native thread and two `Arc`s:
+    /// dropping it decrements `strong`, which takes the place of their strong count and never
+    /// reaches zero, and detaches the thread with [pthread_detach], which does nothing.
+    #[repr(C)]
+    struct HandleState<T> {
+        strong: AtomicUsize,
+        weak: AtomicUsize,
+        packet: Arc<Packet<T>>,
+    }
+
+    fn state<T>(handle: &std::thread::JoinHandle<T>) -> &HandleState<T> {
+        // SAFETY: The handles are created by `std_spawn`, whose first word points to the state.
+        unsafe { &**(handle as *const std::thread::JoinHandle<T>).cast::<*const HandleState<T>>() }
+    }
+
+    /// Model for `std::thread::spawn`, which spawns the thread with [super::spawn].
+    pub fn std_spawn<F, T>(f: F) -> std::thread::JoinHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        type Words<T> = [*const HandleState<T>; 3];
+        assert!(
+            mem::size_of::<std::thread::JoinHandle<T>>() == mem::size_of::<Words<T>>(),
+            "unsupported layout of `std::thread::JoinHandle`"
+        );
+        let state = Box::into_raw(Box::new(HandleState {
+            strong: AtomicUsize::new(usize::MAX / 2),
+            weak: AtomicUsize::new(1),
+            packet: super::spawn(f).packet,
+        }));
+        // SAFETY: The handle has the size of the words, and it's only used by the other models
+        // and by its drop glue, which is harmless as explained in `HandleState`.
+        unsafe { mem::transmute_copy::<Words<T>, _>(&[state; 3]) }
+    }
+
+    /// Model for `std::thread::JoinHandle::join`, which waits for the thread like
+    /// [JoinHandle::join].
+    pub fn std_join<T>(handle: std::thread::JoinHandle<T>) -> std::thread::Result<T> {
+        let packet = state(&handle).packet.clone();
+        mem::forget(handle);
+        JoinHandle { packet }.join()
+    }
+
+    /// Model for `std::thread::JoinHandle::is_finished`.
+    pub fn std_is_finished<T>(handle: &std::thread::JoinHandle<T>) -> bool {
+        JoinHandle { packet: state(handle).packet.clone() }.is_finished()
+    }
+
+    /// Model for `std::sync::Mutex::lock`, which discards the executions in which the mutex is
+    /// locked by another thread like [super::Mutex::lock].
+    pub fn std_lock<T: ?Sized>(mutex: &Mutex<T>) -> LockResult<MutexGuard<'_, T>> {
+        match mutex.try_lock() {
+            Ok(guard) => Ok(guard),
+            Err(TryLockError::Poisoned(error)) => Err(error),
+            Err(TryLockError::WouldBlock) => {
+                crate::assume(false);
+                unreachable!("the mutex is locked")
+            }
+        }
+    }
+
+    /// Model for `pthread_detach`, which the drop glue of the handles created by [std_spawn]
+    /// calls with the address of their state.
+    #[cfg(target_os = "linux")]
+    pub fn pthread_detach(_thread: core::ffi::c_ulong) -> core::ffi::c_int {
+        0
+    }
+}
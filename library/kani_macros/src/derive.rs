@@ -1,6 +1,6 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
-//! This module provides Kani's `derive` macro for `Arbitrary`.
+//! This module provides Kani's `derive` macros for `Arbitrary` and `Invariant`.
 //!
 //! ```
 //! use kani::Arbitrary;
@@ -11,23 +11,51 @@
 //! ```
 use proc_macro2::{Ident, Span, TokenStream};
 use proc_macro_error::abort;
-use quote::{quote, quote_spanned};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::meta::ParseNestedMeta;
 use syn::spanned::Spanned;
 use syn::{
-    parse_macro_input, parse_quote, Data, DataEnum, DeriveInput, Fields, GenericParam, Generics,
-    Index,
+    parse_macro_input, parse_quote, Attribute, Data, DataEnum, DeriveInput, Expr, Field, Fields,
+    GenericParam, Generics, Index, LitStr,
 };
 
 pub fn expand_derive_arbitrary(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let derive_item = parse_macro_input!(item as DeriveInput);
     let item_name = &derive_item.ident;
+    let has_invariant = has_invariant(&derive_item);
 
-    // Add a bound `T: Arbitrary` to every type parameter T.
-    let generics = add_trait_bound(derive_item.generics);
-    // Generate an expression to sum up the heap size of each field.
+    let (generics, mut body) = if item_annotations(&derive_item.attrs).transparent {
+        let field = transparent_field(&derive_item, "Arbitrary");
+        let annotations = field_annotations(field);
+        if annotations.bound.is_some() || annotations.has_constraints() {
+            abort!(field.span(), "Cannot use `bound`, `range` or `assume` annotations on the field of a `transparent` type";
+                note = "the value of the field is created by the `Arbitrary` implementation of its type"
+            )
+        }
+        // Only the type of the field needs to implement `Arbitrary`.
+        let ty = &field.ty;
+        let mut generics = derive_item.generics.clone();
+        generics.make_where_clause().predicates.push(parse_quote!(#ty: kani::Arbitrary));
+        let value = quote_spanned! {field.span()=> <#ty as kani::Arbitrary>::any() };
+        let body = match &field.ident {
+            Some(name) => quote!(Self { #name: #value }),
+            None => quote!(Self(#value)),
+        };
+        (generics, body)
+    } else {
+        // Add a bound `T: Arbitrary` to every type parameter T.
+        (add_trait_bound(derive_item.generics.clone()), fn_any_body(&item_name, &derive_item.data))
+    };
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let body = fn_any_body(&item_name, &derive_item.data);
+    if has_invariant {
+        // Only generate values that satisfy the invariant of the type.
+        body = quote! {
+            let value = { #body };
+            kani::assume(kani::Invariant::is_valid(&value));
+            value
+        };
+    }
     let expanded = quote! {
         // The generated implementation.
         impl #impl_generics kani::Arbitrary for #item_name #ty_generics #where_clause {
@@ -64,7 +92,7 @@ fn add_trait_bound(mut generics: Generics) -> Generics {
 /// ```
 fn fn_any_body(ident: &Ident, data: &Data) -> TokenStream {
     match data {
-        Data::Struct(struct_data) => init_symbolic_item(ident, &struct_data.fields),
+        Data::Struct(struct_data) => init_symbolic_item(&quote!(#ident), &struct_data.fields),
         Data::Enum(enum_data) => fn_any_enum(ident, enum_data),
         Data::Union(_) => {
             abort!(Span::call_site(), "Cannot derive `Arbitrary` for `{}` union", ident;
@@ -79,7 +107,12 @@ fn fn_any_body(ident: &Ident, data: &Data) -> TokenStream {
 /// For named fields, this will generate: `Item { field1: kani::any(), field2: kani::any(), .. }`
 /// For unnamed fields, this will generate: `Item (kani::any(), kani::any(), ..)`
 /// For unit field, generate an empty initialization.
-fn init_symbolic_item(ident: &Ident, fields: &Fields) -> TokenStream {
+/// See [init_symbolic_field] for fields with a `#[kani(bound = N)]` annotation, and
+/// [init_constrained_item] for items with `range` or `assume` annotations.
+fn init_symbolic_item(ident: &TokenStream, fields: &Fields) -> TokenStream {
+    if fields.iter().any(|field| field_annotations(field).has_constraints()) {
+        return init_constrained_item(ident, fields);
+    }
     match fields {
         Fields::Named(ref fields) => {
             // Use the span of each `syn::Field`. This way if one of the field types does not
@@ -87,8 +120,9 @@ fn init_symbolic_item(ident: &Ident, fields: &Fields) -> TokenStream {
             // is. An example is shown in the readme of the parent directory.
             let init = fields.named.iter().map(|field| {
                 let name = &field.ident;
+                let value = init_symbolic_field(field);
                 quote_spanned! {field.span()=>
-                    #name: kani::any()
+                    #name: #value
                 }
             });
             quote! {
@@ -98,11 +132,7 @@ fn init_symbolic_item(ident: &Ident, fields: &Fields) -> TokenStream {
         Fields::Unnamed(ref fields) => {
             // Expands to an expression like
             // Self(kani::any(), kani::any(), ..., kani::any());
-            let init = fields.unnamed.iter().map(|field| {
-                quote_spanned! {field.span()=>
-                    kani::any()
-                }
-            });
+            let init = fields.unnamed.iter().map(init_symbolic_field);
             quote! {
                 #ident(#( #init,)*)
             }
@@ -115,9 +145,223 @@ fn init_symbolic_item(ident: &Ident, fields: &Fields) -> TokenStream {
     }
 }
 
+/// Generate the initialization of an item with fields annotated with `#[kani(range = ..)]` or
+/// `#[kani(assume = "..")]`. Each field is first stored in a local variable, then the value of
+/// each annotated field is constrained with `kani::assume`, and the item is built from the
+/// variables. Like for invariants, the conditions of `assume` annotations refer to the named
+/// fields of the item by their names as references, e.g.:
+/// ```ignore
+/// #[derive(Arbitrary)]
+/// struct Date {
+///     #[kani(range = 1..=12)]
+///     month: u8,
+///     #[kani(range = 1..=31, assume = "*day <= 30 || *month != 4")]
+///     day: u8,
+/// }
+/// ```
+/// will generate the following body for `fn any()`:
+/// ```ignore
+/// fn any() -> Self {
+///     let month: u8 = kani::any();
+///     let day: u8 = kani::any();
+///     {
+///         let (month, day) = (&month, &day);
+///         kani::assume((1..=12).contains(month));
+///         kani::assume((1..=31).contains(day));
+///         kani::assume(*day <= 30 || *month != 4);
+///     }
+///     Date { month, day }
+/// }
+/// ```
+fn init_constrained_item(ident: &TokenStream, fields: &Fields) -> TokenStream {
+    let names: Vec<Ident> = fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| match &field.ident {
+            Some(name) => name.clone(),
+            None => format_ident!("field_{}", idx),
+        })
+        .collect();
+    let mut locals = vec![];
+    let mut conditions = vec![];
+    for (field, name) in fields.iter().zip(&names) {
+        let ty = &field.ty;
+        let value = init_symbolic_field(field);
+        locals.push(quote_spanned! {field.span()=>
+            let #name: #ty = #value;
+        });
+        let annotations = field_annotations(field);
+        if let Some(range) = annotations.range {
+            conditions.push(quote_spanned! {range.span()=> (#range).contains(#name) });
+        }
+        if !annotations.assumptions.is_empty() && field.ident.is_none() {
+            abort!(field.span(), "Cannot use `assume` annotations on unnamed fields";
+                help = "use a `range` annotation, or add the condition to an `invariant` \
+                    annotation of the type"
+            )
+        }
+        conditions.extend(annotations.assumptions.into_iter().map(|cond| quote!(#cond)));
+    }
+    let init = match fields {
+        Fields::Named(_) => quote!(#ident { #(#names,)* }),
+        Fields::Unnamed(_) => quote!(#ident(#(#names,)*)),
+        Fields::Unit => quote!(#ident),
+    };
+    quote! {
+        {
+            #(#locals)*
+            {
+                #[allow(unused_variables)]
+                let (#(#names,)*) = (#(&#names,)*);
+                #(kani::assume(#conditions);)*
+            }
+            #init
+        }
+    }
+}
+
+/// Generate the symbolic value of a field. This is `kani::any()` unless the field has a
+/// `#[kani(bound = N)]` annotation, in which case it is `kani::bounded_any::<_, N>()`.
+fn init_symbolic_field(field: &Field) -> TokenStream {
+    match field_annotations(field).bound {
+        Some(bound) => quote_spanned! {field.span()=>
+            kani::bounded_any::<_, { #bound }>()
+        },
+        None => quote_spanned! {field.span()=>
+            kani::any()
+        },
+    }
+}
+
+/// The `#[kani(..)]` annotations of a field.
+#[derive(Default)]
+struct FieldAnnotations {
+    /// The bound from a `#[kani(bound = N)]` annotation.
+    bound: Option<Expr>,
+    /// The conditions from `#[kani(invariant = "..")]` annotations.
+    invariants: Vec<Expr>,
+    /// The range from a `#[kani(range = ..)]` annotation.
+    range: Option<Expr>,
+    /// The conditions from `#[kani(assume = "..")]` annotations.
+    assumptions: Vec<Expr>,
+}
+
+impl FieldAnnotations {
+    /// Whether the annotations constrain the values that `Arbitrary` generates for the field.
+    fn has_constraints(&self) -> bool {
+        self.range.is_some() || !self.assumptions.is_empty()
+    }
+}
+
+/// Parse the `#[kani(bound = N)]`, `#[kani(range = ..)]`, `#[kani(assume = "..")]` and
+/// `#[kani(invariant = "..")]` annotations of a field.
+fn field_annotations(field: &Field) -> FieldAnnotations {
+    let mut annotations = FieldAnnotations::default();
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("kani")) {
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("bound") {
+                if annotations.bound.is_some() {
+                    return Err(meta.error("duplicated `bound` annotation"));
+                }
+                annotations.bound = Some(meta.value()?.parse::<Expr>()?);
+                Ok(())
+            } else if meta.path.is_ident("range") {
+                if annotations.range.is_some() {
+                    return Err(meta.error("duplicated `range` annotation"));
+                }
+                annotations.range = Some(parse_expr_value(&meta)?);
+                Ok(())
+            } else if meta.path.is_ident("assume") {
+                annotations.assumptions.push(parse_expr_value(&meta)?);
+                Ok(())
+            } else if meta.path.is_ident("invariant") {
+                annotations.invariants.push(parse_expr_value(&meta)?);
+                Ok(())
+            } else {
+                Err(meta.error(
+                    "unsupported annotation, expected `bound = N`, `range = ..`, \
+                    `assume = \"..\"` or `invariant = \"..\"`",
+                ))
+            }
+        });
+        if let Err(err) = result {
+            abort!(err.span(), "Failed to parse `#[kani(..)]` annotation: {}", err);
+        }
+    }
+    annotations
+}
+
+/// The `#[kani(..)]` annotations of a type.
+#[derive(Default)]
+struct ItemAnnotations {
+    /// The conditions from `#[kani(invariant = "..")]` annotations.
+    invariants: Vec<Expr>,
+    /// Whether the type has a `#[kani(transparent)]` annotation, in which case the derived
+    /// implementations delegate to the implementations of the type of its only field.
+    transparent: bool,
+}
+
+/// Parse the `#[kani(invariant = "..")]` and `#[kani(transparent)]` annotations of a type.
+fn item_annotations(attrs: &[Attribute]) -> ItemAnnotations {
+    let mut annotations = ItemAnnotations::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("kani")) {
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("invariant") {
+                annotations.invariants.push(parse_expr_value(&meta)?);
+                Ok(())
+            } else if meta.path.is_ident("transparent") {
+                annotations.transparent = true;
+                Ok(())
+            } else {
+                Err(meta.error(
+                    "unsupported annotation, expected `invariant = \"..\"` or `transparent`",
+                ))
+            }
+        });
+        if let Err(err) = result {
+            abort!(err.span(), "Failed to parse `#[kani(..)]` annotation: {}", err);
+        }
+    }
+    annotations
+}
+
+/// Return the only field of a type with a `#[kani(transparent)]` annotation, which must be a
+/// struct with a single field.
+fn transparent_field<'a>(derive_item: &'a DeriveInput, trait_name: &str) -> &'a Field {
+    match &derive_item.data {
+        Data::Struct(data) if data.fields.len() == 1 => data.fields.iter().next().unwrap(),
+        _ => {
+            let ident = &derive_item.ident;
+            abort!(ident.span(), "Cannot derive `{}` for `{}` with a `transparent` annotation", trait_name, ident;
+                help = "`#[kani(transparent)]` can only be used on structs with a single field"
+            )
+        }
+    }
+}
+
+/// Parse the expression of an `invariant`, `assume` or `range` annotation, which is either a
+/// string that contains the expression, like for other derive macros, or the expression itself.
+fn parse_expr_value(meta: &ParseNestedMeta) -> syn::Result<Expr> {
+    let value = meta.value()?;
+    if value.peek(LitStr) { value.parse::<LitStr>()?.parse() } else { value.parse() }
+}
+
+/// Whether the type or any of its fields has an `invariant` annotation.
+fn has_invariant(derive_item: &DeriveInput) -> bool {
+    let fields: Box<dyn Iterator<Item = &Field>> = match &derive_item.data {
+        Data::Struct(data) => Box::new(data.fields.iter()),
+        Data::Enum(data) => Box::new(data.variants.iter().flat_map(|variant| &variant.fields)),
+        Data::Union(data) => Box::new(data.fields.named.iter()),
+    };
+    !item_annotations(&derive_item.attrs).invariants.is_empty()
+        || fields.into_iter().any(|field| !field_annotations(field).invariants.is_empty())
+}
+
 /// Generate the body of the function `any()` for enums. The cases are:
 /// 1. For zero-variants enumerations, this will encode a `panic!()` statement.
-/// 2. For one or more variants, the code will be something like:
+/// 2. For a single variant, this will simply initialize that variant.
+/// 3. For two or more variants, the code picks a nondeterministic variant index, restricts it
+///    to the number of variants, and initializes the selected variant. E.g.:
 /// ```
 /// # enum Enum{
 /// #    WithoutData,
@@ -127,41 +371,137 @@ fn init_symbolic_item(ident: &Ident, fields: &Fields) -> TokenStream {
 /// #
 /// # impl kani::Arbitrary for Enum {
 /// #     fn any() -> Self {
-///         match kani::any() {
+///         let variant: usize = kani::any();
+///         kani::assume(variant < 3);
+///         match variant {
 ///             0 => Enum::WithoutData,
 ///             1 => Enum::WithUnNamedData(kani::any()),
-///             _ => Enum::WithNamedData {i: kani::any()},
+///             2 => Enum::WithNamedData {i: kani::any()},
+///             _ => unreachable!(),
 ///         }
 /// #    }
 /// # }
 /// ```
 fn fn_any_enum(ident: &Ident, data: &DataEnum) -> TokenStream {
-    if data.variants.is_empty() {
-        let msg = format!(
-            "Cannot create symbolic enum `{ident}`. Enums with zero-variants cannot be instantiated"
-        );
-        quote! {
-            panic!(#msg)
+    match data.variants.len() {
+        0 => {
+            let msg = format!(
+                "Cannot create symbolic enum `{ident}`. Enums with zero-variants cannot be instantiated"
+            );
+            quote! {
+                panic!(#msg)
+            }
         }
-    } else {
-        let arms = data.variants.iter().enumerate().map(|(idx, variant)| {
-            let init = init_symbolic_item(&variant.ident, &variant.fields);
-            if idx + 1 < data.variants.len() {
+        1 => {
+            let variant = &data.variants[0].ident;
+            init_symbolic_item(&quote!(#ident::#variant), &data.variants[0].fields)
+        }
+        num_variants => {
+            let arms = data.variants.iter().enumerate().map(|(idx, variant)| {
+                let variant_ident = &variant.ident;
+                let init = init_symbolic_item(&quote!(#ident::#variant_ident), &variant.fields);
                 let index = Index::from(idx);
                 quote! {
-                    #index => #ident::#init,
+                    #index => #init,
                 }
-            } else {
-                quote! {
-                    _ => #ident::#init,
+            });
+            let num_variants = Index::from(num_variants);
+            quote! {
+                let variant: usize = kani::any();
+                kani::assume(variant < #num_variants);
+                match variant {
+                    #(#arms)*
+                    _ => unreachable!(),
                 }
             }
-        });
+        }
+    }
+}
+
+pub fn expand_derive_invariant(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let derive_item = parse_macro_input!(item as DeriveInput);
+    let item_name = &derive_item.ident;
+    let mut generics = derive_item.generics.clone();
+    if item_annotations(&derive_item.attrs).transparent {
+        // Only the type of the field needs to implement `Invariant`.
+        let ty = &transparent_field(&derive_item, "Invariant").ty;
+        generics.make_where_clause().predicates.push(parse_quote!(#ty: kani::Invariant));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = fn_is_valid_body(item_name, &derive_item);
+    let expanded = quote! {
+        // The generated implementation.
+        impl #impl_generics kani::Invariant for #item_name #ty_generics #where_clause {
+            fn is_valid(&self) -> bool {
+                #body
+            }
+        }
+    };
+    proc_macro::TokenStream::from(expanded)
+}
 
-        quote! {
-            match kani::any() {
-                #(#arms)*
+/// Generate the body of the function `is_valid()`, which is the conjunction of the conditions of
+/// all the `invariant` annotations of the type and of its fields.
+///
+/// The conditions can refer to the value as `self`. For structs with named fields, the fields are
+/// also bound to references with their names, e.g.:
+/// ```ignore
+/// #[derive(Invariant)]
+/// #[kani(invariant = "self.len <= self.cap")]
+/// struct Buffer { #[kani(invariant = "*cap <= 16")] cap: usize, len: usize }
+/// ```
+/// will generate the following body for `fn is_valid()`:
+/// ```ignore
+/// fn is_valid(&self) -> bool {
+///     let Self { cap, len } = self;
+///     true && (self.len <= self.cap) && (*cap <= 16)
+/// }
+/// ```
+///
+/// For types with a `#[kani(transparent)]` annotation, the invariant of the type of their field
+/// is also included, e.g., `kani::Invariant::is_valid(&self.0)`.
+fn fn_is_valid_body(ident: &Ident, derive_item: &DeriveInput) -> TokenStream {
+    let annotations = item_annotations(&derive_item.attrs);
+    let mut invariants = annotations.invariants;
+    if annotations.transparent {
+        let field = transparent_field(derive_item, "Invariant");
+        let member = match &field.ident {
+            Some(name) => quote!(#name),
+            None => Index::from(0).into_token_stream(),
+        };
+        invariants.push(parse_quote!(kani::Invariant::is_valid(&self.#member)));
+    }
+    let mut bindings = quote!();
+    match &derive_item.data {
+        Data::Struct(data) => {
+            for field in &data.fields {
+                invariants.extend(field_annotations(field).invariants);
+            }
+            if let Fields::Named(fields) = &data.fields {
+                let names = fields.named.iter().map(|field| &field.ident);
+                bindings = quote! {
+                    #[allow(unused_variables)]
+                    let Self { #(#names,)* } = self;
+                };
             }
         }
+        Data::Enum(data) => {
+            if let Some(field) = data
+                .variants
+                .iter()
+                .flat_map(|variant| &variant.fields)
+                .find(|field| !field_annotations(field).invariants.is_empty())
+            {
+                abort!(field.span(), "Cannot derive `Invariant` for `{}` from field annotations", ident;
+                    help = "add the invariant of the enum with `#[kani(invariant = \"..\")]` on `{}`", ident
+                )
+            }
+        }
+        Data::Union(_) => {}
+    }
+    quote! {
+        #bindings
+        true #(&& (#invariants))*
     }
 }
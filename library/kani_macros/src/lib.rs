@@ -27,6 +27,8 @@
 ///
 /// If you want to spawn tasks in an async harness, you have to pass a schedule to the `#[kani::proof]` attribute,
 /// e.g. `#[kani::proof(schedule = kani::RoundRobin::default())]`.
+/// Unit schedules such as `kani::Nondeterministic` can be passed directly, e.g.
+/// `#[kani::proof(schedule = kani::Nondeterministic)]`.
 ///
 /// This will wrap the async function in a call to [`block_on_with_spawn`](https://model-checking.github.io/kani/crates/doc/kani/futures/fn.block_on_with_spawn.html) (see its documentation for more information).
 #[proc_macro_error]
@@ -43,10 +45,9 @@ pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// allows users to exercise [negative testing](https://en.wikipedia.org/wiki/Negative_testing)
 /// for Rust unit tests.
 ///
-/// # Limitations
-///
-/// The `#[kani::should_panic]` attribute verifies that there are one or more failed checks related to panics.
-/// At the moment, it's not possible to pin it down to specific panics.
+/// Like `#[should_panic]`, it takes an optional `expected` argument to pin down the panic:
+/// `#[kani::should_panic(expected = "msg")]` only succeeds if every failed check is a panic whose
+/// description contains `msg`, or whose name (e.g. `my_harness.assertion.1`) is `msg`.
 #[proc_macro_attribute]
 pub fn should_panic(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::should_panic(attr, item)
@@ -64,6 +65,9 @@ pub fn unwind(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// The attribute `#[kani::stub(original, replacement)]` can only be used alongside `#[kani::proof]`.
 ///
+/// A trait method of a type is specified by a qualified path, e.g., `<Type as Trait>::method`.
+/// If `Type` inherits the default method of the trait, the method is only replaced for `Type`.
+///
 /// # Arguments
 /// * `original` - The function or method to replace, specified as a path.
 /// * `replacement` - The function or method to use as a replacement, specified as a path.
@@ -72,16 +76,115 @@ pub fn stub(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::stub(attr, item)
 }
 
-/// Select the SAT solver to use with CBMC for this harness
+/// Stub every method of a trait implementation for a proof harness.
+///
+/// The attribute `#[kani::stub_trait_impl(impl Trait for Type, replacement)]` can only be used
+/// alongside `#[kani::proof]`. Each method defined in `impl Trait for Type` is replaced by the
+/// function with the same name inside `replacement`, which must have the same signature as the
+/// method it replaces, including the type of `self`:
+///
+/// ```ignore
+/// struct MockFile;
+/// impl MockFile {
+///     fn read(_file: &mut File, buf: &mut [u8]) -> io::Result<usize> {
+///         Ok(kani::any_where(|len| *len <= buf.len()))
+///     }
+/// }
+///
+/// #[kani::proof]
+/// #[kani::stub_trait_impl(impl Read for File, MockFile)]
+/// fn check_read() { ... }
+/// ```
+///
+/// # Arguments
+/// * `impl Trait for Type` - The trait implementation to replace.
+/// * `replacement` - The module or type that contains the replacement methods, specified as a path.
+#[proc_macro_attribute]
+pub fn stub_trait_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::stub_trait_impl(attr, item)
+}
+
+/// Select the SAT or SMT solver to use with CBMC for this harness
 ///
 /// The attribute `#[kani::solver(arg)]` can only be used alongside `#[kani::proof]`.
 ///
-/// arg - name of solver, e.g. kissat
+/// arg - name of solver, e.g. kissat or z3
 #[proc_macro_attribute]
 pub fn solver(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::solver(attr, item)
 }
 
+/// Stop verifying the harness after the given number of seconds.
+///
+/// The attribute `#[kani::timeout(arg)]` can only be used alongside `#[kani::proof]`, and it
+/// requires `-Z harness-timeout`. It takes precedence over `--harness-timeout`.
+///
+/// arg - the time limit in seconds, which must be greater than 0.
+#[proc_macro_attribute]
+pub fn timeout(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::timeout(attr, item)
+}
+
+/// Select how CBMC explores the paths of this harness
+///
+/// The attribute `#[kani::path_strategy(arg)]` can only be used alongside `#[kani::proof]`.
+///
+/// arg - name of the strategy: `merge` (the default) explores all paths at
+/// once and merges them where they join, while `lifo` and `fifo` explore one
+/// path at a time, depth first and breadth first respectively. Harnesses with
+/// many branches whose paths rarely rejoin can be much faster to verify one
+/// path at a time.
+#[proc_macro_attribute]
+pub fn path_strategy(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::path_strategy(attr, item)
+}
+
+/// Set the default attributes of the harnesses in a module
+///
+/// The attribute `#[kani::defaults(option = value, ...)]` can be applied to an inline module, and
+/// it adds the attribute of each option to the harnesses of the module (and of its inline
+/// submodules) that don't have it already, e.g.
+/// `#[kani::defaults(unwind = 4, solver = cadical)]` adds `#[kani::unwind(4)]` and
+/// `#[kani::solver(cadical)]`. The options are `unwind`, `solver`, `timeout` and `path_strategy`.
+///
+/// A submodule with its own `#[kani::defaults]` inherits the options that it doesn't set. The
+/// attribute can also be used as an inner attribute, i.e., `#![kani::defaults(...)]` at the top
+/// of an inline module, with `#![feature(custom_inner_attributes)]`.
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn defaults(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::defaults(attr, item)
+}
+
+/// Verify the properties of a `proptest!` block as proof harnesses.
+///
+/// Under Kani, each property of the block becomes a `#[kani::proof]` harness with the same name
+/// and attributes (except `#[test]`). Its inputs are produced by the symbolic strategies of
+/// `kani::proptest`, which cover every value that the strategy can sample, and the
+/// `prop_assert!`, `prop_assert_eq!`, `prop_assert_ne!` and `prop_assume!` macros of its body
+/// become `assert!`, `assert_eq!`, `assert_ne!` and `kani::assume`. Outside of Kani, the block is
+/// left unchanged, so the properties still run as tests:
+///
+/// ```ignore
+/// #[cfg_attr(kani, kani::proof_from_proptest)]
+/// proptest! {
+///     #[test]
+///     #[cfg_attr(kani, kani::unwind(5))]
+///     fn check_sort(mut v in prop::collection::vec(any::<u8>(), 0..4)) {
+///         v.sort();
+///         prop_assert!(v.windows(2).all(|w| w[0] <= w[1]));
+///     }
+/// }
+/// ```
+///
+/// Strategies must be built from the ones that `kani::proptest` supports, and the bodies can't
+/// use `?` or return a `TestCaseError`.
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn proof_from_proptest(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::proof_from_proptest(attr, item)
+}
+
 /// Mark an API as unstable. This should only be used inside the Kani sysroot.
 /// See https://model-checking.github.io/kani/rfc/rfcs/0006-unstable-api.html for more details.
 #[doc(hidden)]
@@ -91,12 +194,80 @@ pub fn unstable(attr: TokenStream, item: TokenStream) -> TokenStream {
 }
 
 /// Allow users to auto generate Arbitrary implementations by using `#[derive(Arbitrary)]` macro.
+///
+/// Fields are initialized with `kani::any()`, except for fields annotated with
+/// `#[kani(bound = N)]`, which are initialized with `kani::bounded_any::<_, N>()`. The type of
+/// these fields must implement `kani::BoundedArbitrary`, e.g.:
+///
+/// ```ignore
+/// #[derive(kani::Arbitrary)]
+/// struct Packet {
+///     #[kani(bound = 8)]
+///     payload: Vec<u8>,
+/// }
+/// ```
+///
+/// The values of a field can be restricted with `#[kani(range = ..)]`, which accepts any range
+/// expression, and with `#[kani(assume = "..")]`, whose condition can refer to the named fields
+/// of the struct or variant as references, e.g.:
+///
+/// ```ignore
+/// #[derive(kani::Arbitrary)]
+/// struct Date {
+///     #[kani(range = 1..=12)]
+///     month: u8,
+///     #[kani(range = 1..=31, assume = "*day <= 30 || *month != 4")]
+///     day: u8,
+/// }
+/// ```
+///
+/// If the type or its fields have `#[kani(invariant = "..")]` annotations, the generated values
+/// are assumed to satisfy the invariant, see [`Invariant`][derive@Invariant].
+///
+/// A struct with a single field can be annotated with `#[kani(transparent)]`, in which case its
+/// values are created by the `Arbitrary` implementation of the type of the field, which keeps the
+/// invariants that this implementation establishes. Only the type of the field must implement
+/// `Arbitrary`, rather than every type parameter, e.g.:
+///
+/// ```ignore
+/// #[derive(kani::Arbitrary)]
+/// #[kani(transparent)]
+/// struct Port(NonZeroU16);
+/// ```
 #[proc_macro_error]
-#[proc_macro_derive(Arbitrary)]
+#[proc_macro_derive(Arbitrary, attributes(kani))]
 pub fn derive_arbitrary(item: TokenStream) -> TokenStream {
     derive::expand_derive_arbitrary(item)
 }
 
+/// Allow users to auto generate `Invariant` implementations by using `#[derive(Invariant)]`
+/// macro.
+///
+/// The invariant is the conjunction of the conditions of all `#[kani(invariant = "..")]`
+/// annotations on the type and on its fields. The conditions can refer to the value as `self`,
+/// and, for structs with named fields, to references to the fields by their names, e.g.:
+///
+/// ```ignore
+/// #[derive(kani::Arbitrary, kani::Invariant)]
+/// #[kani(invariant = "self.len <= self.cap")]
+/// struct Buffer {
+///     #[kani(invariant = "*cap <= 16")]
+///     cap: usize,
+///     len: usize,
+/// }
+/// ```
+///
+/// `#[derive(Arbitrary)]` on a type with such annotations only generates values that satisfy the
+/// invariant, so the type must derive both.
+///
+/// With `#[kani(transparent)]` on a struct with a single field, the invariant of the type of the
+/// field is also part of the invariant.
+#[proc_macro_error]
+#[proc_macro_derive(Invariant, attributes(kani))]
+pub fn derive_invariant(item: TokenStream) -> TokenStream {
+    derive::expand_derive_invariant(item)
+}
+
 /// Add a precondition to this function.
 ///
 /// This is part of the function contract API, for more general information see
@@ -127,6 +298,12 @@ pub fn requires(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// the computations must be side effect free, e.g. it cannot perform I/O or use
 /// mutable memory.
 ///
+/// An expression wrapped in `old(..)` is evaluated on entry to the function
+/// instead, e.g. `#[kani::ensures(*counter == old(*counter) + 1)]` relates the
+/// value behind a mutable reference to the value it held before the call. A
+/// wrapped place expression, such as `old(*v)`, is cloned, so it must implement
+/// `Clone`.
+///
 /// Kani requires each function that uses a contract (this attribute or
 /// [`requires`][macro@requires]) to have at least one designated
 /// [`proof_for_contract`][macro@proof_for_contract] harness for checking the
@@ -136,6 +313,23 @@ pub fn ensures(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::ensures(attr, item)
 }
 
+/// Declare the memory this function may write to.
+///
+/// This is part of the function contract API, for more general information see
+/// the [module-level documentation](../kani/contracts/index.html).
+///
+/// The attribute takes a comma separated list of argument names, e.g.
+/// `#[kani::modifies(counter)]`. Each argument must be a mutable reference or
+/// a raw pointer to a sized type. When the contract is checked, writing to any
+/// other memory that outlives the call is an error. When the contract is used
+/// as a stub, the values behind the listed arguments are replaced by
+/// nondeterministic ones, which can then be constrained with
+/// [`ensures`][macro@ensures].
+#[proc_macro_attribute]
+pub fn modifies(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::modifies(attr, item)
+}
+
 /// Designates this function as a harness to check a function contract.
 ///
 /// The argument to this macro is the relative path (e.g. `foo` or
@@ -160,6 +354,12 @@ pub fn proof_for_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// You may use multiple `stub_verified` attributes on a single harness.
 ///
+/// The stub asserts the preconditions of `TARGET`, havocs the memory named in
+/// its `modifies` clause and assumes its postconditions. This is only sound if
+/// the contract holds, so Kani also runs every
+/// [`proof_for_contract`][macro@proof_for_contract] harness of `TARGET`, even
+/// if it was not selected with `--harness`, and warns if there is none.
+///
 /// This is part of the function contract API, for more general information see
 /// the [module-level documentation](../kani/contracts/index.html).
 #[proc_macro_attribute]
@@ -167,6 +367,60 @@ pub fn stub_verified(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::stub_verified(attr, item)
 }
 
+/// Add a loop invariant to a `while` loop.
+///
+/// The invariant must hold before the first iteration of the loop, and every iteration must
+/// preserve it. When loop contracts are enabled (`-Z loop-contracts`), Kani uses the invariant
+/// to abstract the loop instead of unwinding it, so unbounded loops can be verified without
+/// `#[kani::unwind]`. Otherwise, the invariant is ignored.
+///
+/// Since this attribute is applied to a statement, the crate must enable the
+/// `stmt_expr_attributes` and `proc_macro_hygiene` features.
+///
+/// ```ignore
+/// #[kani::loop_invariant(i <= n)]
+/// while i < n {
+///     i += 1;
+/// }
+/// ```
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn loop_invariant(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::loop_invariant(attr, item)
+}
+
+/// Add a termination measure to a `while` loop or to a recursive function.
+///
+/// The attribute takes one or more comma separated integer expressions. Several expressions are
+/// compared lexicographically, like a tuple. The measure must strictly decrease, so with unsigned
+/// integers it proves termination:
+///
+/// - On a loop, the measure is evaluated at the loop head and must decrease in every iteration.
+///   Like [`loop_invariant`][macro@loop_invariant], it is only used when loop contracts are
+///   enabled (`-Z loop-contracts`), and both can be combined on the same loop.
+/// - On a function with a contract, the measure is evaluated on the arguments and must be
+///   smaller at every recursive call than at the outermost call. It is checked when the contract
+///   is verified with [`proof_for_contract`][macro@proof_for_contract].
+///
+/// ```ignore
+/// #[kani::loop_invariant(i <= n)]
+/// #[kani::decreases(n - i)]
+/// while i < n {
+///     i += 1;
+/// }
+///
+/// #[kani::requires(n < 100)]
+/// #[kani::decreases(n)]
+/// fn sum(n: u32) -> u32 {
+///     if n == 0 { 0 } else { n + sum(n - 1) }
+/// }
+/// ```
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn decreases(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::decreases(attr, item)
+}
+
 /// This module implements Kani attributes in a way that only Kani's compiler can understand.
 /// This code should only be activated when pre-building Kani's sysroot.
 #[cfg(kani_sysroot)]
@@ -174,8 +428,23 @@ mod sysroot {
     use proc_macro_error::{abort, abort_call_site};
 
     mod contracts;
-
-    pub use contracts::{ensures, proof_for_contract, requires, stub_verified};
+    mod defaults;
+    mod loop_contracts;
+    mod proptest;
+
+    pub use contracts::{ensures, modifies, proof_for_contract, requires, stub_verified};
+    pub use defaults::defaults;
+    pub use loop_contracts::loop_invariant;
+    pub use proptest::proof_from_proptest;
+
+    /// `decreases` applies both to loops and to functions with a contract.
+    pub fn decreases(attr: TokenStream, item: TokenStream) -> TokenStream {
+        if loop_contracts::is_loop(&item) {
+            loop_contracts::decreases(attr, item)
+        } else {
+            contracts::decreases(attr, item)
+        }
+    }
 
     use super::*;
 
@@ -198,17 +467,87 @@ pub fn $name(attr: TokenStream, item: TokenStream) -> TokenStream {
                 ).into()
             }
         };
-        ($name:ident, no_args) => {
-            pub fn $name(attr: TokenStream, item: TokenStream) -> TokenStream {
-                assert!(attr.is_empty(), "`#[kani::{}]` does not take any arguments currently", stringify!($name));
-                let fn_item = parse_macro_input!(item as ItemFn);
-                let attribute = format_ident!("{}", stringify!($name));
-                quote!(
-                    #[kanitool::#attribute]
-                    #fn_item
-                ).into()
+    }
+
+    /// The arguments of `#[kani::stub_trait_impl(impl Trait for Type, replacement)]`.
+    struct TraitImplStub {
+        trait_path: syn::Path,
+        self_type: syn::Path,
+        replacement: syn::Path,
+    }
+
+    impl Parse for TraitImplStub {
+        fn parse(input: ParseStream) -> syn::Result<Self> {
+            input.parse::<syn::Token![impl]>()?;
+            let trait_path = input.parse()?;
+            input.parse::<syn::Token![for]>()?;
+            let self_type = input.parse()?;
+            input.parse::<syn::Token![,]>()?;
+            let replacement = input.parse()?;
+            let _ = input.parse::<Option<syn::Token![,]>>()?;
+            Ok(TraitImplStub { trait_path, self_type, replacement })
+        }
+    }
+
+    /// Lower the arguments to a list of paths, which is what Kani's compiler expects:
+    /// `#[kanitool::stub_trait_impl(Trait, Type, replacement)]`.
+    pub fn stub_trait_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+        let TraitImplStub { trait_path, self_type, replacement } =
+            parse_macro_input!(attr as TraitImplStub);
+        let fn_item = parse_macro_input!(item as ItemFn);
+        quote!(
+            #[kanitool::stub_trait_impl(#trait_path, #self_type, #replacement)]
+            #fn_item
+        )
+        .into()
+    }
+
+    /// The arguments of `#[kani::stub(original, replacement)]`.
+    struct StubArgs {
+        original: syn::ExprPath,
+        replacement: syn::ExprPath,
+    }
+
+    impl Parse for StubArgs {
+        fn parse(input: ParseStream) -> syn::Result<Self> {
+            let original = input.parse()?;
+            input.parse::<syn::Token![,]>()?;
+            let replacement = input.parse()?;
+            let _ = input.parse::<Option<syn::Token![,]>>()?;
+            Ok(StubArgs { original, replacement })
+        }
+    }
+
+    /// Kani's compiler expects the arguments of `#[kanitool::stub]` to be paths, so a qualified
+    /// path to a trait method, `<Type as Trait>::method`, is lowered to a string literal.
+    /// Any other arguments are passed through, and they are validated by the compiler.
+    pub fn stub(attr: TokenStream, item: TokenStream) -> TokenStream {
+        let args = match syn::parse::<StubArgs>(attr.clone()) {
+            Ok(StubArgs {
+                original: syn::ExprPath { qself: Some(qself), path, .. },
+                replacement,
+            }) => {
+                let to_string =
+                    |tokens: proc_macro2::TokenStream| tokens.to_string().replace(' ', "");
+                let self_ty = &qself.ty;
+                let trait_path = path.segments.iter().take(qself.position);
+                let method = path.segments.iter().skip(qself.position);
+                let original = format!(
+                    "<{} as {}>::{}",
+                    to_string(quote!(#self_ty)),
+                    to_string(quote!(#(#trait_path)::*)),
+                    to_string(quote!(#(#method)::*))
+                );
+                quote!(#original, #replacement)
             }
+            _ => proc_macro2::TokenStream::from(attr),
         };
+        let fn_item = parse_macro_input!(item as ItemFn);
+        quote!(
+            #[kanitool::stub(#args)]
+            #fn_item
+        )
+        .into()
     }
 
     struct ProofOptions {
@@ -309,11 +648,12 @@ pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     }
 
-    kani_attribute!(should_panic, no_args);
+    kani_attribute!(should_panic);
     kani_attribute!(solver);
-    kani_attribute!(stub);
+    kani_attribute!(path_strategy);
     kani_attribute!(unstable);
     kani_attribute!(unwind);
+    kani_attribute!(timeout);
 }
 
 /// This module provides dummy implementations of Kani attributes which cannot be interpreted by
@@ -343,11 +683,19 @@ pub fn proof(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
     no_op!(should_panic);
     no_op!(solver);
+    no_op!(path_strategy);
     no_op!(stub);
+    no_op!(stub_trait_impl);
     no_op!(unstable);
     no_op!(unwind);
+    no_op!(timeout);
     no_op!(requires);
     no_op!(ensures);
+    no_op!(modifies);
     no_op!(proof_for_contract);
     no_op!(stub_verified);
+    no_op!(loop_invariant);
+    no_op!(decreases);
+    no_op!(defaults);
+    no_op!(proof_from_proptest);
 }
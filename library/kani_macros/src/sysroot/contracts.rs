@@ -4,7 +4,7 @@
 //! Implementation of the function contracts code generation.
 //!
 //! The most exciting part is the handling of `requires` and `ensures`, the main
-//! entry point to which is [`requires_ensures_main`]. Most of the code
+//! entry point to which is [`contract_main`]. Most of the code
 //! generation for that is implemented on [`ContractConditionsHandler`] with
 //! [`ContractFunctionState`] steering the code generation. The function state
 //! implements a state machine in order to be able to handle multiple attributes
@@ -120,8 +120,19 @@
 //! "<fn_name>_replace_<fn_hash>"]`.
 //!
 //! The replace function has the same signature as the original function but its
-//! body is replaced by `kani::any()`, which generates a non-deterministic
-//! value.
+//! body is replaced by `kani::internal::contract_result()`, which generates a
+//! non-deterministic value like `kani::any()` and marks where the original
+//! function would have been called.
+//!
+//! ## Modifies
+//!
+//! A `modifies` attribute lists arguments that point to memory the function
+//! may write to. It is handled by the same state machine, but it adds no code
+//! to the check function. Instead the check function is decorated with
+//! `#[kanitool::modifies(<targets>)]`, from which the Kani compiler emits an
+//! assigns clause that CBMC enforces. In the replace function the pointees of
+//! the targets are overwritten with `kani::any()` right before the result is
+//! generated (see [`HavocInjector`]).
 //!
 //! ## Inductive Verification
 //!
@@ -153,22 +164,36 @@
 //!
 //! ```ignored
 //! fn recursion_wrapper_...(fn args ...) {
-//!     static mut REENTRY: bool = false;
+//!     let reentry = kani::contract_reentry_flag(&|| ());
 //!
-//!     if unsafe { REENTRY } {
+//!     if unsafe { *reentry } {
 //!         call_replace(fn args...)
 //!     } else {
-//!         unsafe { reentry = true };
+//!         unsafe { *reentry = true };
 //!         let result = call_check(fn args...);
-//!         unsafe { reentry = false };
+//!         unsafe { *reentry = false };
 //!         result
 //!     }
 //! }
 //! ```
 //!
+//! The tracker is not a `static` in the wrapper, because a `static` is shared
+//! by all instantiations of a generic function. Instead the Kani compiler
+//! creates one tracker for each instantiation of the wrapper, which it tells
+//! apart by the type of the closure we pass to `contract_reentry_flag`. That
+//! way a call to `foo::<u8>` from `foo::<u16>` is checked, rather than
+//! replaced by the contract of an instantiation that is still being verified.
+//!
 //! We register this function as `#[kanitool::checked_with =
 //! "recursion_wrapper_..."]` instead of the check function.
 //!
+//! If the function has a `decreases` clause, the wrapper also evaluates its
+//! measure on the arguments. The first call stores it in a slot that the
+//! compiler creates for each instantiation, like the tracker, via
+//! `kani::contract_measure`, and every re-entry asserts that its measure is
+//! smaller before dispatching to the replacement. The `decreases` attribute
+//! itself leaves the check and replace functions unchanged.
+//!
 //! # Complete example
 //!
 //! ```
@@ -194,8 +219,8 @@
 //!     let divisor_renamed = kani::untracked_deref(&divisor);
 //!     let result = { kani::assume(divisor != 0); { dividend / divisor } };
 //!     kani::assert(result <= dividend_renamed, "result <= dividend");
-//!     std::mem::forget(dividend_renamed);
-//!     std::mem::forget(divisor_renamed);
+//!     core::mem::forget(dividend_renamed);
+//!     core::mem::forget(divisor_renamed);
 //!     result
 //! }
 //!
@@ -206,10 +231,10 @@
 //!     kani::assert(divisor != 0, "divisor != 0");
 //!     let dividend_renamed = kani::untracked_deref(&dividend);
 //!     let divisor_renamed = kani::untracked_deref(&divisor);
-//!     let result = kani::any();
+//!     let result = kani::internal::contract_result();
 //!     kani::assume(result <= dividend_renamed, "result <= dividend");
-//!     std::mem::forget(dividend_renamed);
-//!     std::mem::forget(divisor_renamed);
+//!     core::mem::forget(dividend_renamed);
+//!     core::mem::forget(divisor_renamed);
 //!     result
 //! }
 //!
@@ -217,19 +242,20 @@
 //! #[allow(unused_variables)]
 //! #[kanitool::is_contract_generated(recursion_wrapper)]
 //! fn div_recursion_wrapper_965916(dividend: u32, divisor: u32) -> u32 {
-//!     static mut REENTRY: bool = false;
+//!     let reentry = kani::contract_reentry_flag(&|| ());
 //!
-//!     if unsafe { REENTRY } {
+//!     if unsafe { *reentry } {
 //!         div_replace_965916(dividend, divisor)
 //!     } else {
-//!         unsafe { reentry = true };
+//!         unsafe { *reentry = true };
 //!         let result = div_check_965916(dividend, divisor);
-//!         unsafe { reentry = false };
+//!         unsafe { *reentry = false };
 //!         result
 //!     }
 //! }
 //! ```
 
+use super::loop_contracts::{is_kani_attribute, parse_measure};
 use proc_macro::{Diagnostic, TokenStream};
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use quote::{quote, ToTokens};
@@ -238,8 +264,9 @@
     collections::{HashMap, HashSet},
 };
 use syn::{
-    parse_macro_input, spanned::Spanned, visit::Visit, visit_mut::VisitMut, Attribute, Expr,
-    ItemFn, PredicateType, ReturnType, Signature, TraitBound, TypeParamBound, WhereClause,
+    parse::Parser, parse_macro_input, punctuated::Punctuated, spanned::Spanned, visit::Visit,
+    visit_mut::VisitMut, Attribute, Expr, ExprClosure, ItemFn, PredicateType, ReturnType,
+    Signature, Token, TraitBound, TraitItemFn, TypeParamBound, WhereClause,
 };
 
 /// Create a unique hash for a token stream (basically a [`std::hash::Hash`]
@@ -279,11 +306,19 @@ fn identifier_for_generated_function(related_function: &ItemFn, purpose: &str, h
 }
 
 pub fn requires(attr: TokenStream, item: TokenStream) -> TokenStream {
-    requires_ensures_main(attr, item, true)
+    contract_main(attr, item, ContractConditionsKind::Requires)
 }
 
 pub fn ensures(attr: TokenStream, item: TokenStream) -> TokenStream {
-    requires_ensures_main(attr, item, false)
+    contract_main(attr, item, ContractConditionsKind::Ensures)
+}
+
+pub fn modifies(attr: TokenStream, item: TokenStream) -> TokenStream {
+    contract_main(attr, item, ContractConditionsKind::Modifies)
+}
+
+pub fn decreases(attr: TokenStream, item: TokenStream) -> TokenStream {
+    contract_main(attr, item, ContractConditionsKind::Decreases)
 }
 
 /// Collect all named identifiers used in the argument patterns of a function.
@@ -478,7 +513,15 @@ fn visit_expr_mut(&mut self, i: &mut Expr) {
 fn rename_argument_occurrences(sig: &syn::Signature, attr: &mut Expr) -> HashMap<Ident, Ident> {
     let mut arg_ident_collector = ArgumentIdentCollector::new();
     arg_ident_collector.visit_signature(&sig);
+    rename_idents(arg_ident_collector, attr)
+}
 
+/// Create new names for the collected [`Ident`]s and replace their occurrences
+/// in `attr` with them. See [`rename_argument_occurrences`].
+fn rename_idents(
+    arg_ident_collector: ArgumentIdentCollector,
+    attr: &mut Expr,
+) -> HashMap<Ident, Ident> {
     let mk_new_ident_for = |id: &Ident| Ident::new(&format!("{}_renamed", id), Span::mixed_site());
     let arg_idents = arg_ident_collector
         .0
@@ -494,14 +537,112 @@ fn rename_argument_occurrences(sig: &syn::Signature, attr: &mut Expr) -> HashMap
     arg_idents
 }
 
+/// Replaces every call `old(expr)` in a postcondition with a fresh variable and
+/// collects the wrapped expressions, so that they can be evaluated before the
+/// body of the function runs.
+///
+/// The variable names contain a hash of the postcondition, because the checks
+/// of several `ensures` attributes are nested in one another and each must
+/// refer to its own snapshots.
+struct OldValueExtractor {
+    prefix: String,
+    values: Vec<(Ident, Expr)>,
+}
+
+impl OldValueExtractor {
+    fn new(attr: &Expr) -> Self {
+        use std::hash::Hasher;
+        let mut hasher = std::collections::hash_map::DefaultHasher::default();
+        hash_of_token_stream(&mut hasher, attr.to_token_stream());
+        Self { prefix: format!("old_{:x}", hasher.finish() % 0x1_000_000), values: vec![] }
+    }
+}
+
+impl VisitMut for OldValueExtractor {
+    fn visit_expr_mut(&mut self, i: &mut Expr) {
+        if let Expr::Call(call) = i {
+            if let Expr::Path(func) = call.func.as_ref() {
+                if call.args.len() == 1 && matches_path(&func.path, &["old"]) {
+                    let name = format!("{}_{}", self.prefix, self.values.len());
+                    let ident = Ident::new(&name, Span::mixed_site());
+                    let value = call.args.first().unwrap();
+                    let value = if is_place_expression(value) {
+                        // A place cannot be moved out of (e.g. `old(*v)` for
+                        // `v: &mut Vec<u8>`), so we snapshot a clone of it.
+                        Expr::Verbatim(quote!(::core::clone::Clone::clone(&(#value))))
+                    } else {
+                        value.clone()
+                    };
+                    self.values.push((ident.clone(), value));
+                    *i = Expr::Verbatim(ident.to_token_stream());
+                    return;
+                }
+            }
+        }
+        syn::visit_mut::visit_expr_mut(self, i)
+    }
+}
+
+/// Whether `expr` denotes a memory location, such as `*x`, `x.field` or
+/// `x[i]`, rather than a temporary value.
+fn is_place_expression(expr: &Expr) -> bool {
+    match expr {
+        Expr::Unary(unary) => matches!(unary.op, syn::UnOp::Deref(_)),
+        Expr::Field(_) | Expr::Index(_) | Expr::Path(_) => true,
+        Expr::Paren(paren) => is_place_expression(&paren.expr),
+        _ => false,
+    }
+}
+
+/// Creates the code that evaluates the `old(..)` expressions of a postcondition
+/// on function entry. This runs after the argument copies are made, so the
+/// expressions refer to those copies just like the rest of the postcondition.
+fn make_old_value_snapshots(old_values: &[(Ident, Expr)]) -> TokenStream2 {
+    let (names, exprs): (Vec<_>, Vec<_>) = old_values.iter().cloned().unzip();
+    quote!(#(let #names = #exprs;)*)
+}
+
+/// Prepends the havoc of the `modifies` targets to the
+/// `kani::internal::contract_result()` call that creates the result of a
+/// replace function in place of the call to the contracted function.
+///
+/// There is exactly one such call, generated by the innermost layer of the
+/// replace function, so every `old(..)` value has already been taken and no
+/// postcondition has been assumed yet when it runs. Other `kani::any()` calls,
+/// e.g. in the conditions or in the havoc of another `modifies` clause, are left
+/// untouched.
+struct HavocInjector {
+    havoc: TokenStream2,
+    done: bool,
+}
+
+impl VisitMut for HavocInjector {
+    fn visit_expr_mut(&mut self, i: &mut Expr) {
+        if self.done {
+            return;
+        }
+        if let Expr::Call(call) = i {
+            if let Expr::Path(func) = call.func.as_ref() {
+                if call.args.is_empty()
+                    && matches_path(&func.path, &["kani", "internal", "contract_result"])
+                {
+                    let havoc = &self.havoc;
+                    *i = Expr::Verbatim(quote!({ #havoc #i }));
+                    self.done = true;
+                    return;
+                }
+            }
+        }
+        syn::visit_mut::visit_expr_mut(self, i)
+    }
+}
+
 /// The information needed to generate the bodies of check and replacement
 /// functions that integrate the conditions from this contract attribute.
 struct ContractConditionsHandler<'a> {
     function_state: ContractFunctionState,
     /// Information specific to the type of contract attribute we're expanding.
     condition_type: ContractConditionsType,
-    /// The contents of the attribute.
-    attr: Expr,
     /// Body of the function this attribute was found on.
     annotated_fn: &'a ItemFn,
     /// An unparsed, unmodified copy of `attr`, used in the error messages.
@@ -513,12 +654,36 @@ struct ContractConditionsHandler<'a> {
 /// Information needed for generating check and replace handlers for different
 /// contract attributes.
 enum ContractConditionsType {
-    Requires,
+    Requires {
+        /// The precondition.
+        attr: Expr,
+    },
     Ensures {
+        /// The postcondition, rewritten to refer to the argument copies.
+        attr: Expr,
         /// Translation map from original argument names to names of the copies
         /// we will be emitting.
         argument_names: HashMap<Ident, Ident>,
+        /// The expressions wrapped in `old(..)` by the postcondition, each with
+        /// the name of the variable that holds its value on function entry.
+        old_values: Vec<(Ident, Expr)>,
     },
+    Modifies {
+        /// The arguments pointing to memory the function may write to.
+        targets: Vec<Ident>,
+    },
+    /// The measure is checked by the recursion wrapper, see
+    /// [`make_recursion_wrapper`].
+    Decreases,
+}
+
+/// Which contract attribute we are expanding.
+#[derive(Copy, Clone)]
+enum ContractConditionsKind {
+    Requires,
+    Ensures,
+    Modifies,
+    Decreases,
 }
 
 impl ContractConditionsType {
@@ -526,48 +691,63 @@ impl ContractConditionsType {
     /// function and the contents of the decorating attribute.
     ///
     /// Renames the [`Ident`]s used in `attr` and stores the translation map in
-    /// `argument_names`.
-    fn new_ensures(sig: &Signature, attr: &mut Expr) -> Self {
-        let argument_names = rename_argument_occurrences(sig, attr);
-        ContractConditionsType::Ensures { argument_names }
+    /// `argument_names`. Afterwards replaces every `old(..)` expression in
+    /// `attr` with a fresh variable and stores those in `old_values`.
+    fn new_ensures(sig: &Signature, mut attr: Expr) -> Self {
+        let argument_names = rename_argument_occurrences(sig, &mut attr);
+        let mut old_extractor = OldValueExtractor::new(&attr);
+        old_extractor.visit_expr_mut(&mut attr);
+        ContractConditionsType::Ensures { attr, argument_names, old_values: old_extractor.values }
     }
 }
 
 impl<'a> ContractConditionsHandler<'a> {
-    /// Initialize the handler. Constructs the required
-    /// [`ContractConditionsType`] depending on `is_requires`.
+    /// Initialize the handler. Parses `attr` and constructs the required
+    /// [`ContractConditionsType`] depending on `kind`.
     fn new(
         function_state: ContractFunctionState,
-        is_requires: bool,
-        mut attr: Expr,
+        kind: ContractConditionsKind,
+        attr: TokenStream,
         annotated_fn: &'a ItemFn,
         attr_copy: TokenStream2,
         output: &'a mut TokenStream2,
-    ) -> Self {
-        let condition_type = if is_requires {
-            ContractConditionsType::Requires
-        } else {
-            ContractConditionsType::new_ensures(&annotated_fn.sig, &mut attr)
+    ) -> Result<Self, syn::Error> {
+        let condition_type = match kind {
+            ContractConditionsKind::Requires => {
+                ContractConditionsType::Requires { attr: syn::parse(attr)? }
+            }
+            ContractConditionsKind::Ensures => {
+                ContractConditionsType::new_ensures(&annotated_fn.sig, syn::parse(attr)?)
+            }
+            ContractConditionsKind::Modifies => {
+                let targets = Punctuated::<Ident, Token![,]>::parse_terminated.parse(attr)?;
+                ContractConditionsType::Modifies { targets: targets.into_iter().collect() }
+            }
+            ContractConditionsKind::Decreases => {
+                parse_measure.parse(attr)?;
+                ContractConditionsType::Decreases
+            }
         };
 
-        Self { function_state, condition_type, attr, annotated_fn, attr_copy, output }
+        Ok(Self { function_state, condition_type, annotated_fn, attr_copy, output })
     }
 
     /// Create the body of a check function.
     ///
     /// Wraps the conditions from this attribute around `self.body`.
     fn make_check_body(&self) -> TokenStream2 {
-        let Self { attr, attr_copy, .. } = self;
+        let Self { attr_copy, .. } = self;
         let ItemFn { sig, block, .. } = self.annotated_fn;
         let return_type = return_type_to_type(&sig.output);
 
         match &self.condition_type {
-            ContractConditionsType::Requires => quote!(
+            ContractConditionsType::Requires { attr } => quote!(
                 kani::assume(#attr);
                 #block
             ),
-            ContractConditionsType::Ensures { argument_names } => {
+            ContractConditionsType::Ensures { attr, argument_names, old_values } => {
                 let (arg_copies, copy_clean) = make_unsafe_argument_copies(&argument_names);
+                let old_snapshots = make_old_value_snapshots(old_values);
 
                 // The code that enforces the postconditions and cleans up the shallow
                 // argument copies (with `mem::forget`).
@@ -586,44 +766,78 @@ fn make_check_body(&self) -> TokenStream2 {
 
                 quote!(
                     #arg_copies
+                    #old_snapshots
                     let result : #return_type = #call;
                     #exec_postconditions
                     result
                 )
             }
+            // CBMC checks this clause for us, using the
+            // `kanitool::modifies` attribute on the check function.
+            ContractConditionsType::Modifies { .. } => block.to_token_stream(),
+            ContractConditionsType::Decreases => block.to_token_stream(),
         }
     }
 
     /// Create the body of a stub for this contract.
     ///
     /// Wraps the conditions from this attribute around a prior call. If
-    /// `use_nondet_result` is `true` we will use `kani::any()` to create a
-    /// result, otherwise whatever the `body` of our annotated function was.
+    /// `use_nondet_result` is `true` we will use
+    /// `kani::internal::contract_result()` to create a result, otherwise
+    /// whatever the `body` of our annotated function was.
     ///
     /// `use_nondet_result` will only be true if this is the first time we are
     /// generating a replace function.
     fn make_replace_body(&self, use_nondet_result: bool) -> TokenStream2 {
-        let Self { attr, attr_copy, .. } = self;
+        let Self { attr_copy, .. } = self;
         let ItemFn { sig, block, .. } = self.annotated_fn;
-        let call_to_prior =
-            if use_nondet_result { quote!(kani::any()) } else { block.to_token_stream() };
+        let call_to_prior = if use_nondet_result {
+            quote!(kani::internal::contract_result())
+        } else {
+            block.to_token_stream()
+        };
         let return_type = return_type_to_type(&sig.output);
 
         match &self.condition_type {
-            ContractConditionsType::Requires => quote!(
+            ContractConditionsType::Requires { attr } => quote!(
                 kani::assert(#attr, stringify!(#attr_copy));
                 #call_to_prior
             ),
-            ContractConditionsType::Ensures { argument_names } => {
+            ContractConditionsType::Ensures { attr, argument_names, old_values } => {
                 let (arg_copies, copy_clean) = make_unsafe_argument_copies(&argument_names);
+                let old_snapshots = make_old_value_snapshots(old_values);
                 quote!(
                     #arg_copies
+                    #old_snapshots
                     let result: #return_type = #call_to_prior;
                     kani::assume(#attr);
                     #copy_clean
                     result
                 )
             }
+            ContractConditionsType::Modifies { targets } => {
+                let havoc = quote!(
+                    #(*unsafe {
+                        kani::internal::Pointer::assignable(kani::untracked_deref(&#targets))
+                    } = kani::any();)*
+                );
+                if use_nondet_result {
+                    quote!(
+                        #havoc
+                        kani::internal::contract_result()
+                    )
+                } else {
+                    // The targets have to be havocked before the postconditions
+                    // of the other `ensures` clauses are assumed, but after
+                    // their `old(..)` values are taken. The nondeterministic
+                    // result of the first replace layer marks that point.
+                    let mut body = block.clone();
+                    let mut inject_havoc = HavocInjector { havoc, done: false };
+                    inject_havoc.visit_block_mut(&mut body);
+                    body.to_token_stream()
+                }
+            }
+            ContractConditionsType::Decreases => call_to_prior,
         }
     }
 
@@ -639,6 +853,9 @@ fn emit_check_function(&mut self, check_function_ident: Ident) {
             // important so this happens as the last emitted attribute.
             self.output.extend(quote!(#[kanitool::is_contract_generated(check)]));
         }
+        if let ContractConditionsType::Modifies { targets } = &self.condition_type {
+            self.output.extend(quote!(#[kanitool::modifies(#(#targets),*)]));
+        }
         let body = self.make_check_body();
         let mut sig = self.annotated_fn.sig.clone();
         sig.ident = check_function_ident;
@@ -681,7 +898,7 @@ fn emit_replace_function(&mut self, replace_function_ident: Ident, is_first_emit
     fn emit_common_header(&mut self) {
         if self.function_state.emit_tag_attr() {
             self.output.extend(quote!(
-                #[allow(dead_code, unused_variables)]
+                #[allow(dead_code, unused_variables, unused_braces)]
             ));
         }
         self.output.extend(self.annotated_fn.attrs.iter().flat_map(Attribute::to_token_stream));
@@ -763,20 +980,38 @@ fn make_unsafe_argument_copies(
     let arg_values = renaming_map.keys();
     (
         quote!(#(let #arg_names = kani::untracked_deref(&#arg_values);)*),
-        quote!(#(std::mem::forget(#also_arg_names);)*),
+        quote!(#(core::mem::forget(#also_arg_names);)*),
     )
 }
 
-/// The main meat of handling requires/ensures contracts.
+/// The main meat of handling requires/ensures/modifies contracts.
 ///
 /// See the [module level documentation][self] for a description of how the code
 /// generation works.
-fn requires_ensures_main(attr: TokenStream, item: TokenStream, is_requires: bool) -> TokenStream {
+fn contract_main(
+    attr: TokenStream,
+    item: TokenStream,
+    kind: ContractConditionsKind,
+) -> TokenStream {
     let attr_copy = TokenStream2::from(attr.clone());
-    let attr = parse_macro_input!(attr as Expr);
 
     let mut output = proc_macro2::TokenStream::new();
     let item_stream_clone = item.clone();
+    // The remaining attributes of a closure expression have to be parsed separately.
+    let parse_closure = |input: syn::parse::ParseStream| {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let mut closure: ExprClosure = input.parse()?;
+        closure.attrs.splice(0..0, attrs);
+        Ok(closure)
+    };
+    if let Ok(closure) = parse_closure.parse(item.clone()) {
+        return closure_contract(closure, kind, attr, attr_copy);
+    }
+    if let Ok(declaration) = syn::parse::<TraitItemFn>(item.clone()) {
+        if declaration.default.is_none() {
+            return trait_method_contract(declaration, kind, attr, attr_copy, item_stream_clone);
+        }
+    }
     let item_fn = parse_macro_input!(item as ItemFn);
 
     let function_state = ContractFunctionState::from_attributes(&item_fn.attrs);
@@ -793,14 +1028,21 @@ fn requires_ensures_main(attr: TokenStream, item: TokenStream, is_requires: bool
         return item_fn.into_token_stream().into();
     }
 
-    let mut handler = ContractConditionsHandler::new(
+    let measure = match decreases_measure(kind, &attr, &item_fn.attrs) {
+        Ok(measure) => measure,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    let mut handler = match ContractConditionsHandler::new(
         function_state,
-        is_requires,
+        kind,
         attr,
         &item_fn,
         attr_copy,
         &mut output,
-    );
+    ) {
+        Ok(handler) => handler,
+        Err(e) => return e.into_compile_error().into(),
+    };
 
     match function_state {
         ContractFunctionState::Check => {
@@ -864,37 +1106,20 @@ fn requires_ensures_main(attr: TokenStream, item: TokenStream, is_requires: bool
                 #(#attrs)*
                 #[kanitool::checked_with = #recursion_wrapper_name_str]
                 #[kanitool::replaced_with = #replace_fn_name_str]
-                #vis #sig {
-                    #block
-                }
+                #vis #sig #block
             ));
 
-            let mut wrapper_sig = sig.clone();
-            attach_require_kani_any(&mut wrapper_sig);
-            wrapper_sig.ident = recursion_wrapper_name;
-
-            let args = pats_to_idents(&mut wrapper_sig.inputs).collect::<Vec<_>>();
-            let also_args = args.iter();
             let (call_check, call_replace) = if is_impl_fn {
                 (quote!(Self::#check_fn_name), quote!(Self::#replace_fn_name))
             } else {
                 (quote!(#check_fn_name), quote!(#replace_fn_name))
             };
-
-            handler.output.extend(quote!(
-                #[allow(dead_code, unused_variables)]
-                #[kanitool::is_contract_generated(recursion_wrapper)]
-                #wrapper_sig {
-                    static mut REENTRY: bool = false;
-                    if unsafe { REENTRY } {
-                        #call_replace(#(#args),*)
-                    } else {
-                        unsafe { REENTRY = true };
-                        let result = #call_check(#(#also_args),*);
-                        unsafe { REENTRY = false };
-                        result
-                    }
-                }
+            handler.output.extend(make_recursion_wrapper(
+                sig,
+                recursion_wrapper_name,
+                call_check,
+                call_replace,
+                measure,
             ));
 
             handler.emit_check_function(check_fn_name);
@@ -905,6 +1130,348 @@ fn requires_ensures_main(attr: TokenStream, item: TokenStream, is_requires: bool
     output.into()
 }
 
+/// Expand a contract attribute on a closure expression.
+///
+/// A closure has no path, so it can neither be the target of a
+/// `proof_for_contract` harness nor be replaced with `stub_verified`. Instead
+/// its contract is checked on every call: the precondition is asserted before
+/// the body runs, since the caller is responsible for it, and the postcondition
+/// is asserted once the body returns, e.g.
+///
+/// ```ignore
+/// let double = #[kani::requires(x < 100)] #[kani::ensures(result > x)] |x: u32| x * 2;
+/// ```
+///
+/// becomes
+///
+/// ```ignore
+/// let double = |x: u32| {
+///     let x_renamed = kani::untracked_deref(&x);
+///     let result = { kani::assert(x < 100, "x < 100"); x * 2 };
+///     kani::assert(result > x_renamed, "result > x");
+///     core::mem::forget(x_renamed);
+///     result
+/// };
+/// ```
+///
+/// The remaining contract attributes stay on the closure and wrap its body in
+/// turn.
+fn closure_contract(
+    mut closure: ExprClosure,
+    kind: ContractConditionsKind,
+    attr: TokenStream,
+    attr_copy: TokenStream2,
+) -> TokenStream {
+    let body = &closure.body;
+    let new_body = match kind {
+        ContractConditionsKind::Requires => {
+            let attr: Expr = match syn::parse(attr) {
+                Ok(attr) => attr,
+                Err(e) => return e.into_compile_error().into(),
+            };
+            quote!({
+                kani::assert(#attr, stringify!(#attr_copy));
+                #body
+            })
+        }
+        ContractConditionsKind::Ensures => {
+            let mut attr: Expr = match syn::parse(attr) {
+                Ok(attr) => attr,
+                Err(e) => return e.into_compile_error().into(),
+            };
+            let mut arg_ident_collector = ArgumentIdentCollector::new();
+            closure.inputs.iter().for_each(|input| arg_ident_collector.visit_pat(input));
+            let argument_names = rename_idents(arg_ident_collector, &mut attr);
+            let mut old_extractor = OldValueExtractor::new(&attr);
+            old_extractor.visit_expr_mut(&mut attr);
+            let (arg_copies, copy_clean) = make_unsafe_argument_copies(&argument_names);
+            let old_snapshots = make_old_value_snapshots(&old_extractor.values);
+            let exec_postconditions = quote!(
+                kani::assert(#attr, stringify!(#attr_copy));
+                #copy_clean
+            );
+            let result_type = match &closure.output {
+                ReturnType::Default => quote!(),
+                ReturnType::Type(_, ty) => quote!(: #ty),
+            };
+            let mut call = body.clone();
+            PostconditionInjector(exec_postconditions.clone()).visit_expr_mut(&mut call);
+            quote!({
+                #arg_copies
+                #old_snapshots
+                let result #result_type = #call;
+                #exec_postconditions
+                result
+            })
+        }
+        ContractConditionsKind::Modifies => {
+            return syn::Error::new(
+                Span::call_site(),
+                "`modifies` is not supported on closures, since their contracts are only checked",
+            )
+            .into_compile_error()
+            .into();
+        }
+        ContractConditionsKind::Decreases => {
+            return syn::Error::new(
+                Span::call_site(),
+                "`decreases` is not supported on closures, since they cannot call themselves",
+            )
+            .into_compile_error()
+            .into();
+        }
+    };
+    closure.body = Box::new(Expr::Verbatim(new_body));
+    closure.into_token_stream().into()
+}
+
+/// Expand a contract attribute on the declaration of a trait method, which has no body.
+///
+/// Like for a function, the first contract attribute that is expanded registers check and replace
+/// functions on the declaration. These are emitted as provided methods of the trait whose bodies
+/// call the declared method, e.g.
+///
+/// ```ignore
+/// trait Counter {
+///     #[kani::ensures(result > 0)]
+///     fn next(&mut self) -> u32;
+/// }
+/// ```
+///
+/// becomes
+///
+/// ```ignore
+/// trait Counter {
+///     #[kanitool::checked_with = "next_recursion_wrapper_1a2b3c"]
+///     #[kanitool::replaced_with = "next_replace_1a2b3c"]
+///     fn next(&mut self) -> u32;
+///
+///     #[kanitool::is_contract_generated(recursion_wrapper)]
+///     fn next_recursion_wrapper_1a2b3c(&mut self) -> u32 where u32: kani::Arbitrary {
+///         // Calls `Self::next_check_1a2b3c` the first time and
+///         // `Self::next_replace_1a2b3c` on recursive calls.
+///     }
+///
+///     #[kanitool::is_contract_generated(check)]
+///     fn next_check_1a2b3c(&mut self) -> u32 {
+///         let result: u32 = { Self::next(self) };
+///         kani::assert(result > 0, "result > 0");
+///         result
+///     }
+///
+///     #[kanitool::is_contract_generated(replace)]
+///     fn next_replace_1a2b3c(&mut self) -> u32 where u32: kani::Arbitrary {
+///         let result: u32 = kani::internal::contract_result();
+///         kani::assume(result > 0);
+///         result
+///     }
+/// }
+/// ```
+///
+/// Every implementation of the trait inherits these methods and thereby the contract. The compiler
+/// redirects the calls to the declared method to the recursion wrapper or the replace method,
+/// which resolve to the implementation of the receiver type during monomorphization, except for
+/// the call in the check method. The remaining contract attributes
+/// are copied onto the generated methods and expanded there.
+fn trait_method_contract(
+    declaration: TraitItemFn,
+    kind: ContractConditionsKind,
+    attr: TokenStream,
+    attr_copy: TokenStream2,
+    item_stream_clone: TokenStream,
+) -> TokenStream {
+    if !matches!(
+        ContractFunctionState::from_attributes(&declaration.attrs),
+        ContractFunctionState::Untouched
+    ) {
+        // The check and replace methods were already generated by the first contract attribute.
+        return declaration.into_token_stream().into();
+    }
+    let mut args = vec![];
+    for input in &declaration.sig.inputs {
+        match input {
+            syn::FnArg::Receiver(_) => args.push(Ident::new("self", Span::call_site())),
+            syn::FnArg::Typed(syn::PatType { pat, .. }) => match pat.as_ref() {
+                syn::Pat::Ident(pat) if pat.subpat.is_none() => args.push(pat.ident.clone()),
+                _ => {
+                    return syn::Error::new(
+                        pat.span(),
+                        "contracts on trait methods only support arguments that are plain names",
+                    )
+                    .into_compile_error()
+                    .into();
+                }
+            },
+        }
+    }
+
+    let TraitItemFn { attrs, sig, .. } = &declaration;
+    let method = &sig.ident;
+    let mut provided_sig = sig.clone();
+    if needs_sized_self(sig) {
+        provided_sig.generics.make_where_clause().predicates.push(syn::parse_quote!(Self: Sized));
+    }
+    let item_fn = ItemFn {
+        attrs: attrs.clone(),
+        vis: syn::Visibility::Inherited,
+        sig: provided_sig,
+        block: Box::new(syn::parse_quote!({ Self::#method(#(#args),*) })),
+    };
+
+    let measure = match decreases_measure(kind, &attr, attrs) {
+        Ok(measure) => measure,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    let mut output = TokenStream2::new();
+    let mut handler = match ContractConditionsHandler::new(
+        ContractFunctionState::Untouched,
+        kind,
+        attr,
+        &item_fn,
+        attr_copy,
+        &mut output,
+    ) {
+        Ok(handler) => handler,
+        Err(e) => return e.into_compile_error().into(),
+    };
+
+    let item_hash = short_hash_of_token_stream(&item_stream_clone);
+    let check_fn_name = identifier_for_generated_function(&item_fn, "check", item_hash);
+    let replace_fn_name = identifier_for_generated_function(&item_fn, "replace", item_hash);
+    let recursion_wrapper_name =
+        identifier_for_generated_function(&item_fn, "recursion_wrapper", item_hash);
+    let replace_fn_name_str = syn::LitStr::new(&replace_fn_name.to_string(), Span::call_site());
+    let recursion_wrapper_name_str =
+        syn::LitStr::new(&recursion_wrapper_name.to_string(), Span::call_site());
+    handler.output.extend(quote!(
+        #(#attrs)*
+        #[kanitool::checked_with = #recursion_wrapper_name_str]
+        #[kanitool::replaced_with = #replace_fn_name_str]
+        #sig;
+    ));
+    handler.output.extend(make_recursion_wrapper(
+        &item_fn.sig,
+        recursion_wrapper_name,
+        quote!(Self::#check_fn_name),
+        quote!(Self::#replace_fn_name),
+        measure,
+    ));
+    handler.emit_check_function(check_fn_name);
+    handler.emit_replace_function(replace_fn_name, true);
+    output.into()
+}
+
+/// Whether the provided methods generated for a contract on a trait method need a `Self: Sized`
+/// bound, i.e., whether the method takes or returns `Self` by value.
+fn needs_sized_self(sig: &Signature) -> bool {
+    let is_self =
+        |ty: &syn::Type| matches!(ty, syn::Type::Path(path) if path.path.is_ident("Self"));
+    sig.inputs.iter().any(|input| match input {
+        syn::FnArg::Receiver(receiver) => receiver.reference.is_none() && is_self(&receiver.ty),
+        syn::FnArg::Typed(arg) => is_self(&arg.ty),
+    }) || matches!(&sig.output, ReturnType::Type(_, ty) if is_self(ty))
+}
+
+/// The measure of the `decreases` clause of a function, if it has one. This is
+/// either the attribute that is being expanded, or one of the remaining
+/// attributes of the function, since only the first contract attribute
+/// generates the recursion wrapper that checks the measure.
+fn decreases_measure(
+    kind: ContractConditionsKind,
+    attr: &TokenStream,
+    attrs: &[Attribute],
+) -> syn::Result<Option<TokenStream2>> {
+    if matches!(kind, ContractConditionsKind::Decreases) {
+        return parse_measure.parse(attr.clone()).map(Some);
+    }
+    attrs
+        .iter()
+        .find(|attr| is_kani_attribute(attr, "decreases"))
+        .map(|attr| attr.parse_args_with(parse_measure))
+        .transpose()
+}
+
+/// Create the recursion wrapper, which is registered as the function that
+/// checks the contract. The first call runs the check function, while the
+/// recursive calls made during the check use the replace function instead, so
+/// the contract of a recursive function is verified by induction.
+///
+/// If the function has a `decreases` clause, the wrapper also evaluates the
+/// measure on the arguments of every call. The measure of the first call is
+/// stored, and every recursive call asserts that its measure is smaller, which
+/// proves that the recursion terminates.
+fn make_recursion_wrapper(
+    sig: &Signature,
+    recursion_wrapper_name: Ident,
+    call_check: TokenStream2,
+    call_replace: TokenStream2,
+    measure: Option<TokenStream2>,
+) -> TokenStream2 {
+    let mut wrapper_sig = sig.clone();
+    attach_require_kani_any(&mut wrapper_sig);
+    wrapper_sig.ident = recursion_wrapper_name;
+
+    let args = pats_to_idents(&mut wrapper_sig.inputs).collect::<Vec<_>>();
+    let also_args = args.iter();
+    let Some(measure) = measure else {
+        return quote!(
+            #[allow(dead_code, unused_variables)]
+            #[kanitool::is_contract_generated(recursion_wrapper)]
+            #wrapper_sig {
+                let reentry = kani::contract_reentry_flag(&|| ());
+                if unsafe { *reentry } {
+                    #call_replace(#(#args),*)
+                } else {
+                    unsafe { *reentry = true };
+                    let result = #call_check(#(#also_args),*);
+                    unsafe { *reentry = false };
+                    result
+                }
+            }
+        );
+    };
+
+    // The measure refers to the original argument patterns, so we bind them
+    // to shallow copies of the arguments, like for postconditions.
+    let (pats, copied_args): (Vec<_>, Vec<_>) = sig
+        .inputs
+        .iter()
+        .zip(&args)
+        .filter_map(|(input, arg)| match input {
+            syn::FnArg::Typed(syn::PatType { pat, .. }) => Some((pat, arg)),
+            syn::FnArg::Receiver(_) => None,
+        })
+        .unzip();
+    let mut bindings = ArgumentIdentCollector::new();
+    pats.iter().for_each(|pat| bindings.visit_pat(pat));
+    let bindings = bindings.0.into_iter();
+    let message = format!("the measure must decrease at every recursive call: {measure}");
+    quote!(
+        #[allow(dead_code, unused_variables)]
+        #[kanitool::is_contract_generated(recursion_wrapper)]
+        #wrapper_sig {
+            let reentry = kani::contract_reentry_flag(&|| ());
+            let measure = {
+                #(#[allow(unused_mut)] let #pats = kani::untracked_deref(&#copied_args);)*
+                let measure = #measure;
+                #(core::mem::forget(#bindings);)*
+                measure
+            };
+            let outer_measure = kani::contract_measure(&|| ());
+            if unsafe { *reentry } {
+                kani::assert(measure < unsafe { *outer_measure }, #message);
+                #call_replace(#(#args),*)
+            } else {
+                unsafe { *reentry = true };
+                unsafe { *outer_measure = measure };
+                let result = #call_check(#(#also_args),*);
+                unsafe { *reentry = false };
+                result
+            }
+        }
+    )
+}
+
 /// Convert every use of a pattern in this signature to a simple, fresh, binding-only
 /// argument ([`syn::PatIdent`]) and return the [`Ident`] that was generated.
 fn pats_to_idents<P>(
@@ -0,0 +1,177 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implementation of the `#[kani::defaults]` attribute, which sets the default attributes of the
+//! harnesses in a module.
+
+use proc_macro::TokenStream;
+use proc_macro2::Ident;
+use proc_macro_error::{abort, abort_call_site};
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, Attribute, Expr, ExprLit, Item, ItemMod, Lit, MetaNameValue, Token};
+
+/// The harness attributes that can be given a default value.
+const OPTIONS: [&str; 4] = ["unwind", "solver", "timeout", "path_strategy"];
+
+/// Expand a `#[kani::defaults(option = value, ...)]` annotation on an inline module.
+///
+/// Every harness of the module (and of its inline submodules) that doesn't have the attribute of
+/// an option gets the attribute with the default value, e.g.
+/// ```ignore
+/// #[kani::defaults(unwind = 4, solver = cadical)]
+/// mod proofs {
+///     #[kani::proof]
+///     fn check_a() { ... }
+///
+///     #[kani::proof]
+///     #[kani::unwind(10)]
+///     fn check_b() { ... }
+/// }
+/// ```
+/// is expanded to:
+/// ```ignore
+/// mod proofs {
+///     #[kanitool::unwind(4)]
+///     #[kanitool::solver(cadical)]
+///     #[kani::proof]
+///     fn check_a() { ... }
+///
+///     #[kanitool::solver(cadical)]
+///     #[kani::proof]
+///     #[kani::unwind(10)]
+///     fn check_b() { ... }
+/// }
+/// ```
+///
+/// A submodule with its own `#[kani::defaults]` inherits the options that it doesn't set.
+pub fn defaults(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let options = match syn::parse::Parser::parse(
+        Punctuated::<MetaNameValue, Token![,]>::parse_terminated,
+        attr,
+    ) {
+        Ok(options) => options.into_iter().collect::<Vec<_>>(),
+        Err(_) => abort_call_site!(
+            "`#[kani::defaults]` expects a list of `option = value` pairs";
+            help = "e.g. `#[kani::defaults(unwind = 4, solver = cadical)]`"
+        ),
+    };
+    for option in &options {
+        // Report invalid options even if the module has no harness.
+        let _ = harness_attribute(option);
+    }
+    let Ok(mut module) = syn::parse::<ItemMod>(item) else {
+        abort_call_site!("`#[kani::defaults]` can only be applied to modules")
+    };
+    if module.content.is_none() {
+        abort_call_site!(
+            "`#[kani::defaults]` can only be applied to inline modules";
+            help = "move the attribute to the `mod` block in the module's file"
+        )
+    }
+    apply_defaults(&mut module, &options);
+    quote!(#module).into()
+}
+
+/// Add the default attributes to the harnesses of the module, and pass the defaults on to its
+/// submodules.
+fn apply_defaults(module: &mut ItemMod, options: &[MetaNameValue]) {
+    let Some((_, items)) = module.content.as_mut() else { return };
+    for item in items {
+        match item {
+            Item::Fn(function) if is_harness(&function.attrs) => {
+                let defaults: Vec<Attribute> = options
+                    .iter()
+                    .filter(|option| !has_attribute(&function.attrs, option_name(option)))
+                    .map(harness_attribute)
+                    .collect();
+                function.attrs.splice(0..0, defaults);
+            }
+            Item::Mod(submodule) => {
+                if let Some(attr) =
+                    submodule.attrs.iter_mut().find(|attr| attr_name_is(attr, "defaults"))
+                {
+                    // The submodule's own defaults are expanded later, so add the options that
+                    // it doesn't set to them.
+                    let mut inner = attr
+                        .parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated)
+                        .map(|options| options.into_iter().collect::<Vec<_>>())
+                        .unwrap_or_else(|_| {
+                            abort!(
+                                attr,
+                                "`#[kani::defaults]` expects a list of `option = value` pairs"
+                            )
+                        });
+                    let inherited: Vec<MetaNameValue> = options
+                        .iter()
+                        .filter(|option| {
+                            inner.iter().all(|inner| option_name(inner) != option_name(option))
+                        })
+                        .cloned()
+                        .collect();
+                    inner.extend(inherited);
+                    let path = attr.path().clone();
+                    *attr = parse_quote!(#[#path(#(#inner),*)]);
+                } else {
+                    apply_defaults(submodule, options);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The `kanitool` attribute that sets the option to its value.
+fn harness_attribute(option: &MetaNameValue) -> Attribute {
+    let name = option_name(option);
+    let value = &option.value;
+    match name.to_string().as_str() {
+        "unwind" | "timeout" => match value {
+            Expr::Lit(ExprLit { lit: Lit::Int(value), .. }) => {
+                parse_quote!(#[kanitool::#name(#value)])
+            }
+            _ => abort!(value, "the default `{}` must be an integer", name),
+        },
+        "solver" | "path_strategy" => {
+            let value: Ident = match value {
+                Expr::Path(path) if path.path.get_ident().is_some() => {
+                    path.path.get_ident().unwrap().clone()
+                }
+                Expr::Lit(ExprLit { lit: Lit::Str(value), .. }) => {
+                    value.parse().unwrap_or_else(|_| {
+                        abort!(value, "the default `{}` must be a name, e.g. `cadical`", name)
+                    })
+                }
+                _ => abort!(value, "the default `{}` must be a name, e.g. `cadical`", name),
+            };
+            parse_quote!(#[kanitool::#name(#value)])
+        }
+        _ => abort!(
+            option.path,
+            "`{}` is not a valid option for `#[kani::defaults]`", name;
+            note = "the valid options are: {}", OPTIONS.join(", ")
+        ),
+    }
+}
+
+fn option_name(option: &MetaNameValue) -> &Ident {
+    option
+        .path
+        .get_ident()
+        .unwrap_or_else(|| abort!(option.path, "expected the name of an option, e.g. `unwind`"))
+}
+
+/// Whether the function is a harness, i.e., it has a `proof` or `proof_for_contract` attribute.
+fn is_harness(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| attr_name_is(attr, "proof") || attr_name_is(attr, "proof_for_contract"))
+}
+
+/// Whether there is an attribute for the option, e.g. `#[kani::unwind(..)]`.
+fn has_attribute(attrs: &[Attribute], option: &Ident) -> bool {
+    attrs.iter().any(|attr| attr_name_is(attr, &option.to_string()))
+}
+
+/// Whether the last segment of the path of the attribute is `name`, e.g. `kani::proof`.
+fn attr_name_is(attr: &Attribute, name: &str) -> bool {
+    attr.path().segments.last().is_some_and(|last| last.ident == name)
+}
@@ -0,0 +1,138 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implementation of the loop contracts code generation.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use proc_macro_error::abort_call_site;
+use quote::quote;
+use syn::parse::{ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, ExprWhile, Token};
+
+/// Expand a `#[kani::loop_invariant(inv)]` annotation on a `while` loop.
+///
+/// The invariant is wrapped in a closure and registered with a call to `kani::loop_invariant`
+/// at the beginning of the loop condition. The compiler uses the call to find the loop head,
+/// and attaches the invariant to the back edge of the loop.
+///
+/// ```ignore
+/// #[kani::loop_invariant(i <= 10)]
+/// while i < 10 { i += 1; }
+/// ```
+/// is expanded to:
+/// ```ignore
+/// while kani::loop_invariant(&|| -> bool { i <= 10 }) && (i < 10) { i += 1; }
+/// ```
+///
+/// If the loop also has a `#[kani::decreases]` attribute, both are expanded together, see
+/// [decreases].
+pub fn loop_invariant(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let inv: Expr = match syn::parse(attr) {
+        Ok(inv) => inv,
+        Err(_) => abort_call_site!("`#[kani::loop_invariant]` expects a boolean expression"),
+    };
+    let ExprWhile { mut attrs, label, cond, body, .. } = parse_while(item, "loop_invariant");
+    let expanded = match take_attribute(&mut attrs, "decreases") {
+        Some(decreases) => {
+            let measure = match decreases.parse_args_with(parse_measure) {
+                Ok(measure) => measure,
+                Err(_) => abort_call_site!("`#[kani::decreases]` expects integer expressions"),
+            };
+            quote!(kani::loop_decreases(&|| -> bool { #inv }, &|| #measure))
+        }
+        None => quote!(kani::loop_invariant(&|| -> bool { #inv })),
+    };
+    quote!(
+        #(#attrs)*
+        #label while #expanded && (#cond) #body
+    )
+    .into()
+}
+
+/// Expand a `#[kani::decreases(measure)]` annotation on a `while` loop.
+///
+/// The measure and the invariant of the loop, if it has a `#[kani::loop_invariant]` attribute,
+/// are wrapped in closures and registered with a call to `kani::loop_decreases` at the
+/// beginning of the loop condition. If the loop has no invariant, `true` is used instead. A
+/// measure with several comma separated components is a tuple, compared lexicographically.
+///
+/// ```ignore
+/// #[kani::loop_invariant(i <= 10)]
+/// #[kani::decreases(10 - i)]
+/// while i < 10 { i += 1; }
+/// ```
+/// is expanded to:
+/// ```ignore
+/// while kani::loop_decreases(&|| -> bool { i <= 10 }, &|| 10 - i) && (i < 10) { i += 1; }
+/// ```
+pub fn decreases(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let measure = match parse_measure.parse(attr) {
+        Ok(measure) => measure,
+        Err(_) => abort_call_site!("`#[kani::decreases]` expects integer expressions"),
+    };
+    let ExprWhile { mut attrs, label, cond, body, .. } = parse_while(item, "decreases");
+    let inv = match take_attribute(&mut attrs, "loop_invariant") {
+        Some(invariant) => match invariant.parse_args::<Expr>() {
+            Ok(inv) => quote!(#inv),
+            Err(_) => abort_call_site!("`#[kani::loop_invariant]` expects a boolean expression"),
+        },
+        None => quote!(true),
+    };
+    quote!(
+        #(#attrs)*
+        #label while kani::loop_decreases(&|| -> bool { #inv }, &|| #measure) && (#cond) #body
+    )
+    .into()
+}
+
+/// Whether the item of an attribute is a loop, including its remaining outer attributes.
+pub fn is_loop(item: &TokenStream) -> bool {
+    parse_outer_while.parse(item.clone()).is_ok()
+}
+
+/// Parse a measure with one or more components. Several components are turned into a tuple.
+pub fn parse_measure(input: ParseStream) -> syn::Result<TokenStream2> {
+    let components: Vec<Expr> =
+        Punctuated::<Expr, Token![,]>::parse_terminated(input)?.into_iter().collect();
+    match components.as_slice() {
+        [] => Err(input.error("expected a measure")),
+        [measure] => Ok(quote!(#measure)),
+        _ => Ok(quote!((#(#components),*))),
+    }
+}
+
+fn parse_outer_while(input: ParseStream) -> syn::Result<ExprWhile> {
+    let attrs = input.call(Attribute::parse_outer)?;
+    let mut expr: ExprWhile = input.parse()?;
+    expr.attrs.splice(0..0, attrs);
+    Ok(expr)
+}
+
+fn parse_while(item: TokenStream, attribute: &str) -> ExprWhile {
+    let Ok(expr) = parse_outer_while.parse(item) else {
+        abort_call_site!(
+            "`#[kani::{}]` can only be applied to `while` loops", attribute;
+            help = "rewrite the loop as a `while` loop"
+        )
+    };
+    if matches!(*expr.cond, Expr::Let(_)) {
+        abort_call_site!("`#[kani::{}]` cannot be applied to `while let` loops", attribute)
+    }
+    expr
+}
+
+/// Remove the first attribute named `name` (or `kani::name`) from `attrs`, so it can be expanded
+/// together with the current one.
+fn take_attribute(attrs: &mut Vec<Attribute>, name: &str) -> Option<Attribute> {
+    let position = attrs.iter().position(|attr| is_kani_attribute(attr, name))?;
+    Some(attrs.remove(position))
+}
+
+/// Whether `attr` is the Kani attribute `name`, written either as `name` or as `kani::name`.
+pub fn is_kani_attribute(attr: &Attribute, name: &str) -> bool {
+    let segments: Vec<_> =
+        attr.path().segments.iter().map(|segment| segment.ident.to_string()).collect();
+    segments == [name] || segments == ["kani", name]
+}
@@ -0,0 +1,207 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implementation of the `#[kani::proof_from_proptest]` attribute, which turns the properties of
+//! a `proptest!` block into proof harnesses.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Group, Ident, TokenStream as TokenStream2, TokenTree};
+use proc_macro_error::abort;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, Block, Expr, ItemMacro, Pat, Token, Type};
+
+/// An input of a property: `pattern in strategy`, or `name: Type`, which is a shorthand for
+/// `name in any::<Type>()`.
+struct PropertyInput {
+    pat: Pat,
+    strategy: TokenStream2,
+}
+
+impl Parse for PropertyInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let pat = Pat::parse_single(input)?;
+        if input.parse::<Option<Token![in]>>()?.is_some() {
+            let strategy = input.parse::<Expr>()?;
+            Ok(PropertyInput { pat, strategy: strategy.into_token_stream() })
+        } else {
+            input.parse::<Token![:]>()?;
+            let ty = input.parse::<Type>()?;
+            Ok(PropertyInput { pat, strategy: quote!(any::<#ty>()) })
+        }
+    }
+}
+
+/// A property of a `proptest!` block: `#[test] fn name(inputs) { body }`.
+struct Property {
+    attrs: Vec<Attribute>,
+    name: Ident,
+    inputs: Vec<PropertyInput>,
+    body: Block,
+}
+
+impl Parse for Property {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        input.parse::<Token![fn]>()?;
+        let name = input.parse()?;
+        let content;
+        syn::parenthesized!(content in input);
+        let inputs = content.parse_terminated(PropertyInput::parse, Token![,])?;
+        let body = input.parse()?;
+        Ok(Property { attrs, name, inputs: inputs.into_iter().collect(), body })
+    }
+}
+
+/// The properties of a `proptest!` block. The configuration of the block, if any, is ignored.
+struct Properties(Vec<Property>);
+
+impl Parse for Properties {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.call(Attribute::parse_inner)?;
+        let mut properties = vec![];
+        while !input.is_empty() {
+            properties.push(input.parse()?);
+        }
+        Ok(Properties(properties))
+    }
+}
+
+/// Expand a `#[kani::proof_from_proptest]` annotation on a `proptest!` block.
+///
+/// Each property of the block becomes a harness that produces its inputs with the symbolic
+/// strategies of `kani::proptest`, and whose `prop_assert*!` and `prop_assume!` macros are
+/// replaced by `assert*!` and `kani::assume`, e.g.
+/// ```ignore
+/// #[kani::proof_from_proptest]
+/// proptest! {
+///     #[test]
+///     #[kani::unwind(5)]
+///     fn check_sorted(v in prop::collection::vec(any::<u8>(), 0..4), x: u8) {
+///         prop_assume!(!v.is_empty());
+///         prop_assert!(sort(v).is_sorted());
+///     }
+/// }
+/// ```
+/// is expanded to:
+/// ```ignore
+/// #[kani::proof]
+/// #[kani::unwind(5)]
+/// fn check_sorted() {
+///     let (v, x,) = {
+///         use kani::proptest::prelude::*;
+///         (
+///             kani::proptest::Strategy::value(&(prop::collection::vec(any::<u8>(), 0..4))),
+///             kani::proptest::Strategy::value(&(any::<u8>())),
+///         )
+///     };
+///     {
+///         kani::assume(!v.is_empty());
+///         assert!(sort(v).is_sorted());
+///     }
+/// }
+/// ```
+pub fn proof_from_proptest(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        abort!(TokenStream2::from(attr), "`#[kani::proof_from_proptest]` takes no arguments");
+    }
+    let item = match syn::parse::<ItemMacro>(item) {
+        Ok(item) if item.mac.path.segments.last().is_some_and(|seg| seg.ident == "proptest") => {
+            item
+        }
+        Ok(item) => abort!(
+            item.mac.path,
+            "`#[kani::proof_from_proptest]` can only be used on a `proptest!` block"
+        ),
+        Err(err) => abort!(
+            err.span(),
+            "`#[kani::proof_from_proptest]` can only be used on a `proptest!` block"
+        ),
+    };
+    let properties = match syn::parse2::<Properties>(item.mac.tokens) {
+        Ok(properties) => properties.0,
+        Err(err) => abort!(
+            err.span(),
+            "unsupported `proptest!` property: {}", err;
+            help = "properties must have the form `#[test] fn name(x in strategy, y: Type) {{ .. }}`"
+        ),
+    };
+    let harnesses = properties.into_iter().map(|property| {
+        let Property { attrs, name, inputs, body } = property;
+        let attrs = attrs.into_iter().filter(|attr| !attr.path().is_ident("test"));
+        let pats = inputs.iter().map(|input| &input.pat);
+        let strategies = inputs.iter().map(|input| &input.strategy);
+        let body = replace_prop_macros(body.into_token_stream());
+        quote!(
+            #[kani::proof]
+            #(#attrs)*
+            fn #name() {
+                let (#(#pats,)*) = {
+                    #[allow(unused_imports)]
+                    use kani::proptest::prelude::*;
+                    (#(kani::proptest::Strategy::value(&(#strategies)),)*)
+                };
+                #body
+            }
+        )
+    });
+    quote!(#(#harnesses)*).into()
+}
+
+/// Replace the unqualified `prop_assert!`, `prop_assert_eq!` and `prop_assert_ne!` macros by the
+/// corresponding `assert*!` macros, and `prop_assume!(cond, ..)` by `kani::assume(cond)`.
+fn replace_prop_macros(tokens: TokenStream2) -> TokenStream2 {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut result = vec![];
+    let mut idx = 0;
+    while idx < tokens.len() {
+        let qualified =
+            matches!(result.last(), Some(TokenTree::Punct(punct)) if punct.as_char() == ':');
+        match (&tokens[idx], tokens.get(idx + 1), tokens.get(idx + 2)) {
+            (
+                TokenTree::Ident(ident),
+                Some(TokenTree::Punct(bang)),
+                Some(TokenTree::Group(args)),
+            ) if bang.as_char() == '!' && !qualified => {
+                let replacement = match ident.to_string().as_str() {
+                    "prop_assert" => Some("assert"),
+                    "prop_assert_eq" => Some("assert_eq"),
+                    "prop_assert_ne" => Some("assert_ne"),
+                    "prop_assume" => None,
+                    _ => {
+                        result.push(tokens[idx].clone());
+                        idx += 1;
+                        continue;
+                    }
+                };
+                let args_tokens = replace_prop_macros(args.stream());
+                if let Some(replacement) = replacement {
+                    result.push(TokenTree::Ident(Ident::new(replacement, ident.span())));
+                    result.push(TokenTree::Punct(bang.clone()));
+                    result.push(TokenTree::Group(Group::new(args.delimiter(), args_tokens)));
+                } else {
+                    let cond: TokenStream2 = args_tokens
+                        .into_iter()
+                        .take_while(
+                            |tt| !matches!(tt, TokenTree::Punct(punct) if punct.as_char() == ','),
+                        )
+                        .collect();
+                    result.extend(quote_spanned!(ident.span()=> kani::assume(#cond)));
+                }
+                idx += 3;
+            }
+            (TokenTree::Group(group), _, _) => {
+                let mut new_group =
+                    Group::new(group.delimiter(), replace_prop_macros(group.stream()));
+                new_group.set_span(group.span());
+                result.push(TokenTree::Group(new_group));
+                idx += 1;
+            }
+            (token, _, _) => {
+                result.push(token.clone());
+                idx += 1;
+            }
+        }
+    }
+    result.into_iter().collect()
+}
@@ -114,19 +114,44 @@ fn setup_kani_bundle(kani_dir: &Path, use_local_bundle: Option<OsString>) -> Res
             )?;
     } else {
         let filename = download_filename();
-        println!("[2/5] Downloading Kani release bundle: {}", &filename);
         fail_if_unsupported_target()?;
-        let bundle = base_dir.join(filename);
-        Command::new("curl")
-            .args(["-sSLf", "-o"])
-            .arg(&bundle)
-            .arg(download_url())
-            .run()
-            .context("Failed to download Kani release bundle")?;
+        // If an offline cache is configured, we reuse (or populate) the bundle stored there
+        // instead of downloading a fresh copy into `base_dir` and discarding it afterwards.
+        let cache_dir = bundle_cache_dir();
+        let bundle = cache_dir.as_deref().unwrap_or(base_dir).join(&filename);
+        let checksum = bundle.with_file_name(checksum_filename());
+        if bundle.exists() {
+            println!("[2/5] Using cached Kani release bundle: {}", bundle.display());
+            if !checksum.exists() {
+                // Bundles placed manually in the cache must come with their checksum. Unverified
+                // bundles can still be installed explicitly with `--use-local-bundle`.
+                bail!(
+                    "No checksum found for `{}`: expected `{}`. Add the checksum of the bundle, \
+                    or install it with `cargo kani setup --use-local-bundle <BUNDLE>`.",
+                    bundle.display(),
+                    checksum.display()
+                );
+            }
+            if let Some(mismatch) = checksum_mismatch(&bundle, &checksum)? {
+                // Don't reuse a corrupted bundle in the next setup.
+                std::fs::remove_file(&bundle)?;
+                std::fs::remove_file(&checksum)?;
+                bail!("{mismatch} The cached bundle was removed, try running setup again.");
+            }
+        } else {
+            println!("[2/5] Downloading Kani release bundle: {}", &filename);
+            if let Some(cache_dir) = &cache_dir {
+                std::fs::create_dir_all(cache_dir)?;
+            }
+            download_bundle(&filename, &bundle, &checksum)?;
+        }
 
         Command::new("tar").arg("zxf").arg(&bundle).current_dir(base_dir).run()?;
 
-        std::fs::remove_file(bundle)?;
+        if cache_dir.is_none() {
+            std::fs::remove_file(bundle)?;
+            std::fs::remove_file(checksum)?;
+        }
     }
     Ok(())
 }
@@ -182,13 +207,97 @@ fn download_filename() -> String {
     format!("kani-{VERSION}-{TARGET}.tar.gz")
 }
 
-/// The download URL for this version of Kani
-fn download_url() -> String {
+/// The filename of the SHA-256 checksum published next to the release bundle
+fn checksum_filename() -> String {
+    format!("{}.sha256", download_filename())
+}
+
+/// The download URL for the given release artifact of this version of Kani
+fn download_url(file: &str) -> String {
     let tag: &str = &format!("kani-{VERSION}");
-    let file: &str = &download_filename();
     format!("https://github.com/model-checking/kani/releases/download/{tag}/{file}")
 }
 
+/// The directory used to cache release bundles across setups, if any.
+///
+/// This is set with the `KANI_BUNDLE_CACHE` environment variable, and it is mostly useful for
+/// CI and offline environments: bundles found in this directory are used as is, and downloaded
+/// bundles are kept there for later use.
+fn bundle_cache_dir() -> Option<PathBuf> {
+    env::var_os("KANI_BUNDLE_CACHE").filter(|dir| !dir.is_empty()).map(PathBuf::from)
+}
+
+/// Download the file at `url` into `path`
+fn download(url: &str, path: &Path) -> Result<()> {
+    Command::new("curl").args(["-sSLf", "-o"]).arg(path).arg(url).run()
+}
+
+/// Download the release bundle and its checksum into `bundle` and `checksum`.
+///
+/// The files are downloaded under temporary names and only renamed once the bundle is verified,
+/// so that an interrupted or corrupted download never ends up in the cache.
+fn download_bundle(filename: &str, bundle: &Path, checksum: &Path) -> Result<()> {
+    let partial_bundle = partial_path(bundle);
+    let partial_checksum = partial_path(checksum);
+    let result = (|| {
+        download(&download_url(filename), &partial_bundle)
+            .context("Failed to download Kani release bundle")?;
+        download(&download_url(&checksum_filename()), &partial_checksum)
+            .context("Failed to download Kani release bundle checksum")?;
+        if let Some(mismatch) = checksum_mismatch(&partial_bundle, &partial_checksum)? {
+            bail!("{mismatch} Try running setup again.");
+        }
+        std::fs::rename(&partial_checksum, checksum)?;
+        std::fs::rename(&partial_bundle, bundle)?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&partial_bundle);
+        let _ = std::fs::remove_file(&partial_checksum);
+    }
+    result
+}
+
+/// The temporary path of a file while it's downloaded.
+fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    path.with_file_name(name)
+}
+
+/// Compare the SHA-256 digest of `bundle` with the one recorded in `checksum`, and describe the
+/// mismatch if they differ. Fails if either digest can't be read or computed.
+fn checksum_mismatch(bundle: &Path, checksum: &Path) -> Result<Option<String>> {
+    let expected = std::fs::read_to_string(checksum)
+        .with_context(|| format!("Failed to read checksum file `{}`", checksum.display()))?;
+    let output =
+        Command::new("shasum").args(["-a", "256"]).arg(bundle).output().context(
+            "Failed to compute the Kani release bundle checksum. Is `shasum` installed?",
+        )?;
+    if !output.status.success() {
+        bail!("Failed to compute checksum of `{}`", bundle.display());
+    }
+    let actual = String::from_utf8_lossy(&output.stdout);
+    let Some(expected) = parse_checksum(&expected) else {
+        bail!("Malformed checksum file `{}`", checksum.display())
+    };
+    match parse_checksum(&actual) {
+        Some(actual) if expected.eq_ignore_ascii_case(actual) => Ok(None),
+        actual => Ok(Some(format!(
+            "Checksum mismatch for `{}`: expected `{expected}` but found `{}`.",
+            bundle.display(),
+            actual.unwrap_or("<unknown>")
+        ))),
+    }
+}
+
+/// Extract the digest from a line in the format used by `shasum` / `sha256sum`,
+/// i.e.: `<hex digest>  <file name>`.
+fn parse_checksum(line: &str) -> Option<&str> {
+    let digest = line.split_whitespace().next()?;
+    (digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit())).then_some(digest)
+}
+
 /// Give users a better error message than "404" if we're on an unsupported platform.
 /// This is called just before we try to download the release bundle.
 fn fail_if_unsupported_target() -> Result<()> {
@@ -212,3 +321,24 @@ fn symlink_rust_toolchain(toolchain: &Path, kani_dir: &Path) -> Result<()> {
     std::os::unix::fs::symlink(toolchain, path)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_parse_checksum() {
+        let digest = "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03";
+        assert_eq!(parse_checksum(&format!("{digest}  kani-1.0.tar.gz\n")), Some(digest));
+        assert_eq!(parse_checksum(digest), Some(digest));
+        assert_eq!(parse_checksum(""), None);
+        assert_eq!(parse_checksum("not-a-digest  kani-1.0.tar.gz"), None);
+        assert_eq!(parse_checksum(&digest[1..]), None);
+    }
+
+    #[test]
+    fn check_partial_path() {
+        let bundle = Path::new("/cache/kani-1.0.tar.gz");
+        assert_eq!(partial_path(bundle), Path::new("/cache/kani-1.0.tar.gz.part"));
+    }
+}
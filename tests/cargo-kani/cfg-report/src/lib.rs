@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `cargo kani cfg-report` follows the module files, including the ones only
+//! declared in `cfg(kani)` code, and reports the code that leaks into normal builds.
+
+mod model;
+#[cfg(kani)]
+mod proofs;
+
+pub fn add(a: u8, b: u8) -> u8 {
+    #[cfg(kani)]
+    kani::assume(a < 100);
+    a.wrapping_add(b)
+}
+
+#[cfg(not(kani))]
+pub fn name() -> &'static str {
+    "cfg-report"
+}
+
+#[cfg_attr(kani, kani::proof)]
+fn check_add() {
+    assert_eq!(add(1, 2), 3);
+}
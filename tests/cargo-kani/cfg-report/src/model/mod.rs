@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+pub struct Model {
+    pub value: u8,
+}
+
+#[cfg(any(kani, feature = "verify"))]
+impl kani::Arbitrary for Model {
+    fn any() -> Self {
+        Model { value: kani::any() }
+    }
+}
+
+#[cfg(any(kani, test))]
+pub fn check_model(model: &Model) -> bool {
+    model.value < 100
+}
@@ -0,0 +1,10 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+mod helpers;
+
+#[kani::proof]
+fn check_add_any() {
+    let a: u8 = helpers::small();
+    assert!(crate::add(a, 1) > a);
+}
@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+pub fn small() -> u8 {
+    let value: u8 = kani::any();
+    kani::assume(value < 100);
+    value
+}
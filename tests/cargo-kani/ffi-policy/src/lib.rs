@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that undefined foreign functions are modeled according to the FFI policy file.
+
+extern "C" {
+    fn read_sensor(status: *mut i32) -> u32;
+    fn fill_buffer(buffer: *mut [u8; 4], len: usize) -> i32;
+}
+
+#[kani::proof]
+fn check_havoc() {
+    let mut status = 0;
+    let value = unsafe { read_sensor(&mut status) };
+    kani::cover!(value == 42);
+    kani::cover!(status == -1);
+}
+
+#[kani::proof]
+fn check_zeroed() {
+    let mut buffer = [1u8; 4];
+    let ret = unsafe { fill_buffer(&mut buffer, buffer.len()) };
+    assert_eq!(ret, 0);
+    assert_eq!(buffer, [0; 4]);
+}
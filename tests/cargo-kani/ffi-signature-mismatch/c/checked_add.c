@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#include <stdint.h>
+
+uint32_t checked_add(uint32_t a, uint32_t b, uint32_t *result)
+{
+    if (a > UINT32_MAX - b) {
+        return 1;
+    }
+    *result = a + b;
+    return 0;
+}
+
+uint64_t double_it(uint64_t a)
+{
+    return a * 2;
+}
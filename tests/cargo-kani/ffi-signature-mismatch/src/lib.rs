@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that Kani rejects Rust declarations of foreign functions that don't match their
+//! definition in the C files given with `--c-lib`.
+
+extern "C" {
+    // The C function also takes the pointer to the result.
+    fn checked_add(a: u32, b: u32) -> u32;
+    // The C function takes and returns a `uint64_t`.
+    fn double_it(a: u32) -> u32;
+}
+
+#[kani::proof]
+fn check_ffi() {
+    let a: u32 = kani::any();
+    kani::assume(a < 100);
+    unsafe {
+        assert_eq!(checked_add(a, 1), 0);
+        assert_eq!(double_it(a), a * 2);
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that fixed-capacity containers of no_std crates can be generated.
+#![no_std]
+
+use arrayvec::{ArrayString, ArrayVec};
+use kani::fixed_capacity::{any_collection, any_str};
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_heapless_vec() {
+    let vec: heapless::Vec<u8, 4> = any_collection::<_, _, 4>();
+    assert!(vec.len() <= 4);
+    kani::cover!(vec.is_full());
+    kani::cover!(vec.is_empty());
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_heapless_string() {
+    let name: heapless::String<3> = any_str::<_, 3>();
+    assert!(name.len() <= 3);
+    kani::cover!(name.chars().count() == 1 && name.len() == 3);
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_array_vec() {
+    let mut vec: ArrayVec<(u8, bool), 3> = any_collection::<_, _, 3>();
+    let len = vec.len();
+    if vec.try_push(kani::any()).is_ok() {
+        assert!(len < 3);
+    }
+    kani::cover!(len == 3);
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_array_string() {
+    let text: ArrayString<4> = any_str::<_, 4>();
+    assert!(core::str::from_utf8(text.as_bytes()).is_ok());
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_bound_exceeds_capacity() {
+    let _vec: ArrayVec<u8, 2> = any_collection::<_, _, 3>();
+}
@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that the Kani library can be used by a `no_std` crate that only depends on `core` and
+//! `alloc`.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+pub fn sum(values: &[u8]) -> u32 {
+    values.iter().map(|value| *value as u32).sum()
+}
+
+#[cfg(kani)]
+mod verification {
+    use super::*;
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn check_sum() {
+        let values: Vec<u8> = kani::bounded_any::<_, 4>();
+        assert!(sum(&values) <= 4 * u8::MAX as u32);
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `#[kani::proof_from_proptest]` verifies the properties of a `proptest!` block
+//! with symbolic inputs, and that it reports the counterexamples that random sampling misses.
+
+use proptest::prelude::*;
+
+pub fn clamp_percent(value: u32) -> u8 {
+    if value > 100 { 100 } else { value as u8 }
+}
+
+pub fn checked_average(values: &[u8]) -> Option<u8> {
+    if values.is_empty() {
+        return None;
+    }
+    let sum: u32 = values.iter().map(|v| *v as u32).sum();
+    Some((sum / values.len() as u32) as u8)
+}
+
+/// Wrong for exactly one input, which random sampling is unlikely to find.
+pub fn parse_flag(byte: u8) -> bool {
+    byte != 0 && byte != 0xa7
+}
+
+#[cfg_attr(kani, kani::proof_from_proptest)]
+proptest! {
+    #[test]
+    fn check_clamp(value in 0..1000u32) {
+        prop_assert!(clamp_percent(value) <= 100);
+    }
+
+    #[test]
+    #[cfg_attr(kani, kani::unwind(5))]
+    fn check_average(values in prop::collection::vec(any::<u8>(), 0..4)) {
+        prop_assume!(!values.is_empty());
+        let average = checked_average(&values).unwrap();
+        prop_assert!(values.iter().any(|v| *v >= average));
+    }
+
+    #[test]
+    fn check_flag(byte: u8) {
+        prop_assume!(byte != 0);
+        prop_assert!(parse_flag(byte));
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `--test-modules` verifies the harnesses of `#[cfg(test)]` modules, which share the
+//! helpers of the unit tests, and that the unit tests themselves aren't verified.
+
+pub const MAX_RETRIES: u8 = 5;
+
+pub struct Config {
+    retries: u8,
+}
+
+impl Config {
+    pub fn new(retries: u8) -> Config {
+        Config { retries: retries.min(MAX_RETRIES) }
+    }
+
+    pub fn retries(&self) -> u8 {
+        self.retries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_config(retries: u8) -> Config {
+        Config::new(retries)
+    }
+
+    #[test]
+    fn default_retries() {
+        assert_eq!(sample_config(3).retries(), 3);
+    }
+
+    #[cfg(kani)]
+    #[kani::proof]
+    fn check_retries() {
+        assert!(sample_config(kani::any()).retries() <= MAX_RETRIES);
+    }
+}
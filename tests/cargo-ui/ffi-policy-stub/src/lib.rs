@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that Kani fails to compile a harness that reaches a foreign function that the FFI policy
+//! requires to be stubbed.
+
+extern "C" {
+    fn shutdown(code: i32) -> !;
+}
+
+#[kani::proof]
+fn check_not_stubbed() {
+    if kani::any() {
+        unsafe { shutdown(1) }
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z abstractions --use-abstractions
+
+//! Check that `--use-abstractions` stubs `Vec::new` and the hashing of `HashMap` with the models
+//! of `kani::abstractions`, that the containers still behave as documented, and that only the
+//! models that replaced a call are reported.
+
+use std::collections::HashMap;
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_vec() {
+    let mut vec = Vec::new();
+    for i in 0..4u8 {
+        vec.push(i);
+    }
+    assert_eq!(vec.len(), 4);
+    assert!(vec.capacity() >= kani::abstractions::VEC_CAPACITY);
+    assert_eq!(vec.iter().sum::<u8>(), 6);
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+fn check_hash_map() {
+    let key: u32 = kani::any();
+    kani::assume(key != 7);
+    let mut map = HashMap::new();
+    map.insert(key, 'a');
+    map.insert(7, 'b');
+    assert_eq!(map.get(&key), Some(&'a'));
+    assert_eq!(map.get(&7), Some(&'b'));
+    assert_eq!(map.len(), 2);
+}
@@ -0,0 +1,12 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that each element of an array built by `kani::any_array` can take every value,
+//! independently of the other elements.
+
+#[kani::proof]
+fn check_any_array() {
+    let arr = kani::any_array::<bool, 3>();
+    kani::cover!(arr == [true, false, true]);
+    kani::cover!(arr == [false, true, false]);
+}
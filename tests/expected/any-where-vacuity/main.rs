@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that Kani warns about harnesses that are vacuous because the predicate of
+//! `kani::any_where` is unsatisfiable, and that it doesn't warn about satisfiable predicates.
+
+#[kani::proof]
+fn check_vacuous() {
+    let x: u8 = kani::any_where(|x| *x > 10 && *x < 5);
+    assert!(x == 42);
+}
+
+#[kani::proof]
+fn check_satisfiable() {
+    let x: u8 = kani::any_where(|x| *x > 10);
+    assert!(x > 5);
+}
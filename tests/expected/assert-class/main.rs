@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --fail-fast-on-class protocol
+
+//! Check that `kani::assert_class!` reports the assertion in the given property class, and that
+//! the failures are grouped by class.
+
+#[kani::proof]
+fn check_protocol() {
+    let state: u8 = kani::any();
+    kani::assert_class!("protocol", state != 3, "the state machine never enters state 3");
+    assert!(state != 5);
+}
+
+#[kani::proof]
+fn check_default_message() {
+    let version: u8 = kani::any();
+    kani::assume(version < 2);
+    kani::assert_class!("protocol", version < 2);
+}
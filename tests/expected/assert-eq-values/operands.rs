@@ -0,0 +1,38 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::assert_eq!` and `kani::assert_ne!` report the value of their operands when
+//! the assertion fails.
+
+#[derive(PartialEq)]
+struct Point {
+    x: u8,
+    y: u8,
+}
+
+#[kani::proof]
+fn check_eq_scalars() {
+    let x: u8 = kani::any();
+    kani::assume(x == 7);
+    kani::assert_eq!(x + 1, 9);
+}
+
+#[kani::proof]
+fn check_ne_with_message() {
+    let x: u32 = kani::any();
+    kani::assume(x == 3);
+    kani::assert_ne!(x, 3, "x must not be {}", 3);
+}
+
+#[kani::proof]
+fn check_eq_structs() {
+    let point = Point { x: 1, y: 2 };
+    kani::assert_eq!(point, Point { x: 1, y: 3 });
+}
+
+#[kani::proof]
+fn check_eq_success() {
+    let x: u16 = kani::any();
+    kani::assert_eq!(x, x);
+    kani::assert_ne!(x.wrapping_add(1), x);
+}
@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// compile-flags: --edition 2018
+// kani-flags: -Z async-lib
+
+//! Check that the nondeterministic schedule explores the interleavings of the tasks: some of them
+//! lose an update and some don't. Also check that the schedule can be given as a type.
+
+use std::sync::{
+    atomic::{AtomicI64, Ordering},
+    Arc,
+};
+
+/// Increments the counter with a separate load and store, which may lose an update if another
+/// task runs in between.
+async fn increment(x: Arc<AtomicI64>) {
+    let value = x.load(Ordering::Relaxed);
+    kani::yield_now().await;
+    x.store(value + 1, Ordering::Relaxed);
+}
+
+#[kani::proof(schedule = kani::Nondeterministic)]
+#[kani::unwind(6)]
+async fn check_nondeterministic() {
+    let x = Arc::new(AtomicI64::new(0));
+    let handle = kani::spawn(increment(x.clone()));
+    increment(x.clone()).await;
+    handle.await;
+    kani::cover!(x.load(Ordering::Relaxed) == 2, "no lost update");
+    assert_eq!(x.load(Ordering::Relaxed), 2, "lost update");
+}
@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the bounds declared with `kani::bounds!` constrain the harness and are reported in
+//! the verification summary.
+
+use std::time::Duration;
+
+#[kani::proof]
+fn check_duration_add() {
+    let secs: u64 = kani::any();
+    let nanos: u32 = kani::any();
+    kani::bounds!(secs in 0..=3600, nanos in ..1_000_000_000);
+    let duration = Duration::new(secs, nanos);
+    assert!(duration.checked_add(duration).is_some());
+}
+
+#[kani::proof]
+fn check_vacuous_bound() {
+    let x: u8 = kani::any();
+    kani::assume(x > 10);
+    kani::bounds!(x in 0..10);
+    assert!(x < 10);
+}
@@ -0,0 +1,43 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z const-fn-checks --const-fn-checks
+
+//! Check that `--const-fn-checks` verifies that the `const fn`s of the crate don't panic when they
+//! are called at runtime with arbitrary arguments.
+
+/// Cannot panic.
+const fn midpoint(a: u8, b: u8) -> u8 {
+    a / 2 + b / 2 + (a % 2 + b % 2) / 2
+}
+
+/// Overflows for large inputs, which const evaluation of the uses below never triggers.
+const fn area(width: u16, height: u16) -> u16 {
+    width * height
+}
+
+struct Celsius(i32);
+
+impl Celsius {
+    /// Cannot panic.
+    const fn is_freezing(&self) -> bool {
+        self.0 <= 0
+    }
+
+    /// Divides by zero for `0`.
+    const fn scale(factor: u8) -> u8 {
+        100 / factor
+    }
+}
+
+const fn first<T: Copy>(values: [T; 2]) -> T {
+    values[0]
+}
+
+const DEFAULT_AREA: u16 = area(10, 20);
+const FIRST: u8 = first([midpoint(1, 3), Celsius::scale(4)]);
+
+#[kani::proof]
+fn check_constants() {
+    assert!(DEFAULT_AREA == 200);
+    assert!(FIRST == 2);
+}
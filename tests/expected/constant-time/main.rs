@@ -0,0 +1,51 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --constant-time-checks -Z constant-time-checks
+//! Check that `--constant-time-checks` reports the branches and the memory accesses that depend
+//! on a value marked with `kani::secret`, and only those.
+
+const SBOX: [u8; 4] = [3, 0, 2, 1];
+
+fn ct_eq(a: &[u8; 4], b: &[u8; 4]) -> u8 {
+    let mut diff = 0;
+    for i in 0..4 {
+        diff |= a[i] ^ b[i];
+    }
+    diff
+}
+
+fn early_exit_eq(a: &[u8; 4], b: &[u8; 4]) -> bool {
+    for i in 0..4 {
+        if a[i] != b[i] {
+            return false;
+        }
+    }
+    true
+}
+
+fn substitute(key: u8) -> u8 {
+    SBOX[(key & 3) as usize]
+}
+
+#[kani::proof]
+fn check_constant_time() {
+    let key = kani::secret(kani::any::<[u8; 4]>());
+    let input: [u8; 4] = kani::any();
+    // Branching on public values is fine.
+    if input[0] != 0 {
+        let _ = ct_eq(&key, &input);
+    }
+}
+
+#[kani::proof]
+fn check_secret_branch() {
+    let key = kani::secret(kani::any::<[u8; 4]>());
+    let input: [u8; 4] = kani::any();
+    let _ = early_exit_eq(&key, &input);
+}
+
+#[kani::proof]
+fn check_secret_index() {
+    let key: u8 = kani::secret(kani::any());
+    let _ = substitute(key);
+}
@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// This test checks that Kani reports a double free at the Rust code that frees
+// the memory the second time, along with the type that owns it and the place
+// where it was allocated.
+
+#[kani::proof]
+fn check_double_free() {
+    let raw = Box::into_raw(Box::new(10u32));
+    let first = unsafe { Box::from_raw(raw) };
+    let second = unsafe { Box::from_raw(raw) };
+    drop(first);
+    drop(second);
+}
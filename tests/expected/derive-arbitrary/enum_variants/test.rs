@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that a derived `Arbitrary` enum can take every one of its variants,
+//! including enums with a single variant and enums with explicit discriminants.
+
+#[derive(kani::Arbitrary)]
+enum Single {
+    Only(u8),
+}
+
+#[derive(kani::Arbitrary)]
+enum Sparse {
+    Low = 1,
+    Mid = 50,
+    High = 200,
+}
+
+#[kani::proof]
+fn check_single() {
+    let Single::Only(val) = kani::any();
+    kani::cover!(val == 0);
+    kani::cover!(val == u8::MAX);
+}
+
+#[kani::proof]
+fn check_sparse() {
+    let sparse: Sparse = kani::any();
+    kani::cover!(matches!(sparse, Sparse::Low));
+    kani::cover!(matches!(sparse, Sparse::Mid));
+    kani::cover!(matches!(sparse, Sparse::High));
+    let disc = sparse as u8;
+    assert!(disc == 1 || disc == 50 || disc == 200);
+}
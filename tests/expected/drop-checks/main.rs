@@ -0,0 +1,46 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z drop-checks --drop-checks
+
+//! Check that `--drop-checks` verifies the `Drop` implementations of the crate with an arbitrary
+//! value, and that it skips the ones it cannot build a value for.
+
+#[derive(kani::Arbitrary)]
+struct Counter {
+    count: u8,
+}
+
+impl Drop for Counter {
+    fn drop(&mut self) {
+        self.count = self.count.saturating_sub(1);
+    }
+}
+
+#[derive(kani::Arbitrary)]
+struct Divisor {
+    value: u32,
+}
+
+impl Drop for Divisor {
+    fn drop(&mut self) {
+        assert!(100 / self.value > 0, "the divisor is too big");
+    }
+}
+
+struct NotArbitrary;
+
+impl Drop for NotArbitrary {
+    fn drop(&mut self) {}
+}
+
+struct Generic<T>(T);
+
+impl<T> Drop for Generic<T> {
+    fn drop(&mut self) {}
+}
+
+#[kani::proof]
+fn check_counter() {
+    let counter = Counter { count: 0 };
+    drop(counter);
+}
@@ -0,0 +1,36 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that Kani models the entropy sources of the operating system, and that the values
+//! they produce are nondeterministic.
+
+use std::collections::HashMap;
+use std::ffi::{c_int, c_void};
+
+extern "C" {
+    fn getrandom(buf: *mut c_void, buflen: usize, flags: u32) -> isize;
+    fn getentropy(buf: *mut c_void, buflen: usize) -> c_int;
+}
+
+#[kani::proof]
+fn check_hashmap() {
+    let mut map = HashMap::new();
+    map.insert(1u8, 'a');
+    assert_eq!(map.get(&1), Some(&'a'));
+}
+
+#[kani::proof]
+fn check_getrandom() {
+    let mut buf = [0u8; 4];
+    let len = unsafe { getrandom(buf.as_mut_ptr().cast(), buf.len(), 0) };
+    assert_eq!(len, 4);
+    kani::cover!(buf == [1, 2, 3, 4], "buffer may hold any value");
+}
+
+#[kani::proof]
+fn check_getentropy() {
+    let mut buf = [0u8; 300];
+    assert_eq!(unsafe { getentropy(buf.as_mut_ptr().cast(), buf.len()) }, -1);
+    assert_eq!(unsafe { getentropy(buf.as_mut_ptr().cast(), 8) }, 0);
+    kani::cover!(buf[..8] != [0; 8], "buffer may be filled");
+}
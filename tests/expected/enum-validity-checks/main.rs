@@ -0,0 +1,54 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --enum-validity-checks -Z enum-validity-checks
+//! Check that the values of enum types created by transmutes, raw pointer reads and union reads
+//! must have a valid discriminant and a valid payload for their variant.
+
+#[derive(Clone, Copy)]
+#[repr(u8)]
+enum Level {
+    Low = 1,
+    High = 4,
+}
+
+#[derive(Clone, Copy)]
+enum Shape {
+    Dot,
+    Circle(bool),
+    Square(char),
+}
+
+union Bits {
+    byte: u8,
+    level: Level,
+}
+
+#[kani::proof]
+fn check_transmute_discriminant() {
+    let byte: u8 = kani::any();
+    let level: Level = unsafe { std::mem::transmute(byte) };
+    assert!(matches!(level, Level::Low | Level::High));
+}
+
+#[kani::proof]
+fn check_transmute_niche() {
+    let byte: u8 = kani::any();
+    kani::assume(byte <= 2);
+    // Every value from 0 to 2 is valid for an `Option<bool>`.
+    let value: Option<bool> = unsafe { std::mem::transmute(byte) };
+    assert!(value != Some(true) || byte == 1);
+}
+
+#[kani::proof]
+fn check_raw_read_payload() {
+    let bytes: [u32; 2] = kani::any();
+    let shape = unsafe { std::ptr::read(&bytes as *const [u32; 2] as *const Shape) };
+    let _ = matches!(shape, Shape::Dot);
+}
+
+#[kani::proof]
+fn check_union_read() {
+    let bits = Bits { byte: kani::any() };
+    let level = unsafe { bits.level };
+    assert!(matches!(level, Level::Low | Level::High));
+}
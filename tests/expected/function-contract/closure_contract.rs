@@ -0,0 +1,44 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that the contract of a closure is checked whenever the closure is called.
+
+#![feature(stmt_expr_attributes, proc_macro_hygiene)]
+
+fn apply<F: Fn(u32) -> u32>(f: F, x: u32) -> u32 {
+    f(x)
+}
+
+#[kani::proof]
+fn check_closure() {
+    let double = #[kani::requires(x < 100)]
+    #[kani::ensures(result > x)]
+    |x: u32| -> u32 {
+        if x == 0 {
+            return 1;
+        }
+        x * 2
+    };
+    let x: u32 = kani::any();
+    kani::assume(x < 100);
+    assert!(apply(double, x) > 0);
+}
+
+#[kani::proof]
+fn check_closure_precondition() {
+    let decrement = #[kani::requires(x > 0)]
+    |x: u32| x - 1;
+    apply(decrement, kani::any());
+}
+
+#[kani::proof]
+fn check_closure_postcondition() {
+    let mut total = 0;
+    let mut add = #[kani::ensures(result > old(total))]
+    |y: u32| {
+        total += y;
+        total
+    };
+    add(kani::any());
+}
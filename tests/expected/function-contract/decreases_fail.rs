@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a recursive call that doesn't decrease the measure is reported.
+
+#[kani::requires(n <= 10)]
+#[kani::ensures(result <= 10)]
+#[kani::decreases(n)]
+fn countdown(n: u8) -> u8 {
+    if n == 0 { 0 } else { countdown(n) }
+}
+
+#[kani::proof_for_contract(countdown)]
+fn non_decreasing_harness() {
+    let _ = countdown(kani::any());
+}
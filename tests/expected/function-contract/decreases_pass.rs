@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that the measure of a recursive function is checked to decrease at the
+//! recursive call.
+
+type T = u8;
+
+#[kani::requires(x != 0 && y != 0)]
+#[kani::ensures(result != 0 && x % result == 0 && y % result == 0)]
+#[kani::decreases(x.min(y))]
+fn gcd(x: T, y: T) -> T {
+    let mut max = x;
+    let mut min = y;
+    if min > max {
+        let val = max;
+        max = min;
+        min = val;
+    }
+
+    let res = max % min;
+    if res == 0 { min } else { gcd(min, res) }
+}
+
+#[kani::proof_for_contract(gcd)]
+fn decreasing_harness() {
+    let _ = gcd(kani::any(), kani::any());
+}
@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that the contract of a generic function is checked for every
+//! instantiation used by the harness, and that the failure names the
+//! instantiation that violates it.
+
+trait Double: Copy + PartialOrd {
+    fn double(self) -> Self;
+}
+
+impl Double for u8 {
+    fn double(self) -> Self {
+        self.saturating_mul(2)
+    }
+}
+
+impl Double for i8 {
+    fn double(self) -> Self {
+        self.saturating_mul(2)
+    }
+}
+
+#[kani::ensures(result >= x)]
+fn double<T: Double>(x: T) -> T {
+    x.double()
+}
+
+#[kani::proof_for_contract(double)]
+fn double_harness() {
+    double::<u8>(kani::any());
+    double::<i8>(kani::any());
+}
@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check contracts on methods of a generic type that take `&self` and
+//! `&mut self`, and that have their own generic parameters and where-clauses.
+
+struct Bounded<T> {
+    items: Vec<T>,
+    capacity: usize,
+}
+
+impl<T: Copy> Bounded<T> {
+    #[kani::requires(self.items.len() < self.capacity)]
+    #[kani::ensures(self.items.len() == old(self.items.len()) + 1)]
+    fn push(&mut self, item: T) {
+        self.items.push(item)
+    }
+
+    #[kani::ensures(result <= self.items.len())]
+    fn count_where<F>(&self, predicate: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.items.iter().filter(|item| predicate(item)).count()
+    }
+}
+
+#[kani::proof_for_contract(Bounded::push)]
+fn check_push() {
+    let mut bounded = Bounded { items: vec![kani::any::<u8>()], capacity: 2 };
+    bounded.push(kani::any());
+}
+
+#[kani::proof_for_contract(Bounded::count_where)]
+#[kani::unwind(3)]
+fn check_count_where() {
+    let bounded = Bounded { items: vec![kani::any::<u8>(), kani::any()], capacity: 2 };
+    let threshold: u8 = kani::any();
+    bounded.count_where(|item| *item > threshold);
+}
@@ -0,0 +1,46 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a generic function can be replaced by its contract, and that the
+//! preconditions are checked separately for every instantiation.
+
+trait Halve: Copy + PartialOrd + kani::Arbitrary {
+    fn halve(self) -> Self;
+    fn is_zero(self) -> bool;
+}
+
+impl Halve for u8 {
+    fn halve(self) -> Self {
+        self / 2
+    }
+    fn is_zero(self) -> bool {
+        self == 0
+    }
+}
+
+impl Halve for u32 {
+    fn halve(self) -> Self {
+        self / 2
+    }
+    fn is_zero(self) -> bool {
+        self == 0
+    }
+}
+
+#[kani::requires(!x.is_zero())]
+#[kani::ensures(result < x)]
+fn halve<T: Halve>(x: T) -> T {
+    x.halve()
+}
+
+#[kani::proof]
+#[kani::stub_verified(halve)]
+fn use_halve() {
+    let small: u8 = kani::any();
+    kani::assume(small != 0);
+    assert!(halve(small) < small, "halve u8");
+    let large: u32 = kani::any();
+    kani::assume(large != 0);
+    assert!(halve(large) < large, "halve u32");
+}
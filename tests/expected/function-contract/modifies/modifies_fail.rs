@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that writing to memory that is not listed in the `modifies` clause
+//! is reported.
+
+#[kani::modifies(first)]
+fn swap(first: &mut u32, second: &mut u32) {
+    let tmp = *first;
+    *first = *second;
+    *second = tmp;
+}
+
+#[kani::proof_for_contract(swap)]
+fn swap_harness() {
+    let mut first = kani::any();
+    let mut second = kani::any();
+    swap(&mut first, &mut second);
+}
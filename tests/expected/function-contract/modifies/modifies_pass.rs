@@ -0,0 +1,57 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a function which only writes to the memory listed in its
+//! `modifies` clause passes, and that a replaced call havocs that memory. The
+//! last harness replaces two functions with a `modifies` clause, each of which
+//! has to havoc its own targets.
+
+#[kani::requires(*counter < 100)]
+#[kani::ensures(*counter == old(*counter) + 1)]
+#[kani::modifies(counter)]
+fn increment(counter: &mut u32) {
+    *counter += 1;
+}
+
+#[kani::proof_for_contract(increment)]
+fn increment_harness() {
+    let mut counter = kani::any();
+    increment(&mut counter);
+}
+
+#[kani::ensures(*flag)]
+#[kani::modifies(flag)]
+fn raise(flag: &mut bool) {
+    *flag = true;
+}
+
+#[kani::proof_for_contract(raise)]
+fn raise_harness() {
+    let mut flag = kani::any();
+    raise(&mut flag);
+}
+
+#[kani::proof]
+#[kani::stub_verified(increment)]
+fn use_increment() {
+    let mut counter = 7;
+    let unrelated = 3;
+    increment(&mut counter);
+    assert_eq!(counter, 8);
+    assert_eq!(unrelated, 3);
+}
+
+#[kani::proof]
+#[kani::stub_verified(increment)]
+#[kani::stub_verified(raise)]
+fn use_increment_and_raise() {
+    let mut counter = 7;
+    let mut flag = false;
+    let unrelated = 3;
+    increment(&mut counter);
+    raise(&mut flag);
+    assert_eq!(counter, 8);
+    assert!(flag);
+    assert_eq!(unrelated, 3);
+}
@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `old(..)` can snapshot places whose type is not `Copy`, such as
+//! the vector behind a mutable reference or a field of `self`.
+
+#[kani::ensures(v[..v.len() - 1] == old(*v)[..])]
+fn push(v: &mut Vec<u8>, x: u8) {
+    v.push(x);
+}
+
+struct Stack {
+    items: Vec<u8>,
+}
+
+impl Stack {
+    #[kani::ensures(self.items[1..] == old(self.items)[..])]
+    fn push_front(&mut self, x: u8) {
+        self.items.insert(0, x);
+    }
+}
+
+#[kani::proof_for_contract(push)]
+fn push_harness() {
+    let mut v = vec![kani::any()];
+    push(&mut v, kani::any());
+}
+
+#[kani::proof_for_contract(Stack::push_front)]
+fn push_front_harness() {
+    let mut stack = Stack { items: vec![kani::any()] };
+    stack.push_front(kani::any());
+}
@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a postcondition with `old(..)` fails if the function does not
+//! preserve the relation to the value on entry.
+
+#[kani::ensures(*x == old(*x).wrapping_add(1))]
+fn bad_increment(x: &mut u8) {
+    *x = x.saturating_add(1);
+}
+
+#[kani::proof_for_contract(bad_increment)]
+fn bad_increment_harness() {
+    let mut x = kani::any();
+    bad_increment(&mut x);
+}
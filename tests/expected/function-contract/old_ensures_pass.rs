@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `old(..)` in a postcondition refers to the values on entry.
+
+#[kani::requires(*counter < u32::MAX)]
+#[kani::ensures(*counter == old(*counter) + 1)]
+fn increment(counter: &mut u32) {
+    *counter += 1;
+}
+
+#[kani::ensures(v.len() == old(v.len()) + 1)]
+#[kani::ensures(v[old(v.len())] == x)]
+fn push(v: &mut Vec<u8>, x: u8) {
+    v.push(x);
+}
+
+#[kani::proof_for_contract(increment)]
+fn increment_harness() {
+    let mut counter = kani::any();
+    increment(&mut counter);
+}
+
+#[kani::proof_for_contract(push)]
+fn push_harness() {
+    let mut v = vec![kani::any()];
+    push(&mut v, kani::any());
+}
@@ -0,0 +1,26 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a precondition that uses `kani::mem::can_dereference` rules out invalid pointers
+//! in the harness of a contract, and is checked where the contract replaces the function.
+
+#[kani::requires(kani::mem::can_dereference(ptr))]
+unsafe fn get(ptr: *const u32) -> u32 {
+    *ptr
+}
+
+#[kani::proof_for_contract(get)]
+fn get_harness() {
+    let value: u32 = kani::any();
+    let ptr = if kani::any() { &value as *const u32 } else { std::ptr::null() };
+    let _ = unsafe { get(ptr) };
+}
+
+#[kani::proof]
+#[kani::stub_verified(get)]
+fn get_dangling_harness() {
+    let ptr = Box::into_raw(Box::new(1u32));
+    drop(unsafe { Box::from_raw(ptr) });
+    let _ = unsafe { get(ptr) };
+}
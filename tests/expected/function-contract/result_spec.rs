@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that the `matches_*!` macros can describe `Result` and `Option`
+//! return values that are not `Copy` without moving `result`.
+
+#[kani::ensures(
+    kani::matches_ok!(result, v if v.len() == n as usize)
+        || kani::matches_err!(result, e if *e == n)
+)]
+#[kani::ensures(kani::iff!(n <= 4, kani::matches_ok!(result)))]
+fn zeros(n: u8) -> Result<Vec<u8>, u8> {
+    if n <= 4 { Ok(vec![0; n as usize]) } else { Err(n) }
+}
+
+#[kani::ensures(kani::implies!(divisor != 0 => kani::matches_some!(result, q if *q <= dividend)))]
+fn checked_div(dividend: u32, divisor: u32) -> Option<u32> {
+    dividend.checked_div(divisor)
+}
+
+#[kani::proof_for_contract(zeros)]
+#[kani::unwind(6)]
+fn zeros_harness() {
+    let _ = zeros(kani::any());
+}
+
+#[kani::proof_for_contract(checked_div)]
+fn checked_div_harness() {
+    let _ = checked_div(kani::any(), kani::any());
+}
@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts --harness use_div
+
+//! Check that selecting a harness which uses a verified stub also runs the
+//! harness that checks the contract of the stub.
+
+#[kani::requires(divisor != 0)]
+#[kani::ensures(result <= dividend)]
+fn div(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+#[kani::proof_for_contract(div)]
+fn check_div() {
+    div(kani::any(), kani::any());
+}
+
+#[kani::proof]
+#[kani::stub_verified(div)]
+fn use_div() {
+    assert!(div(9, 1) <= 9);
+}
+
+#[kani::proof]
+fn unrelated() {
+    assert!(div(9, 1) == 9);
+}
@@ -0,0 +1,59 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a contract on the declaration of a trait method is inherited by every
+//! implementation of the trait.
+
+trait Counter {
+    #[kani::requires(step > 0)]
+    #[kani::ensures(result > old(self.value()))]
+    fn advance(&mut self, step: u32) -> u32;
+
+    fn value(&self) -> u32;
+}
+
+struct Forward(u32);
+
+impl Counter for Forward {
+    fn advance(&mut self, step: u32) -> u32 {
+        self.0 = self.0.saturating_add(step);
+        self.0
+    }
+
+    fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+struct Stuck(u32);
+
+impl Counter for Stuck {
+    fn advance(&mut self, _step: u32) -> u32 {
+        self.0
+    }
+
+    fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+#[kani::proof_for_contract(Counter::advance)]
+fn check_forward() {
+    let mut counter = Forward(kani::any_where(|value| *value < u32::MAX));
+    counter.advance(kani::any());
+}
+
+#[kani::proof_for_contract(Counter::advance)]
+fn check_stuck() {
+    let mut counter = Stuck(kani::any());
+    counter.advance(kani::any());
+}
+
+#[kani::proof]
+#[kani::stub_verified(Counter::advance)]
+fn use_contract() {
+    let mut counter = Stuck(0);
+    let counter: &mut dyn Counter = &mut counter;
+    assert!(counter.advance(1) > 0, "the contract guarantees progress");
+}
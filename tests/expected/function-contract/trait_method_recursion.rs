@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that the contract of a recursive implementation of a trait method is
+//! verified by induction: the recursive call is replaced by the contract.
+
+trait Triangle {
+    #[kani::requires(n <= 1000)]
+    #[kani::ensures(result <= n * (n + 1) / 2)]
+    fn triangle(&self, n: u64) -> u64;
+}
+
+struct Recursive;
+
+impl Triangle for Recursive {
+    fn triangle(&self, n: u64) -> u64 {
+        if n == 0 { 0 } else { n + self.triangle(n - 1) }
+    }
+}
+
+#[kani::proof_for_contract(Triangle::triangle)]
+fn check_triangle() {
+    Recursive.triangle(kani::any());
+}
@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that Kani reports harnesses that cannot be distinguished by their name.
+
+macro_rules! gen_harness {
+    () => {
+        const _: () = {
+            #[kani::proof]
+            fn check() {}
+        };
+    };
+}
+
+gen_harness!();
+gen_harness!();
+
+#[kani::proof]
+fn check_unique() {}
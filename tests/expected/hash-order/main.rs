@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z hash-order --nondet-hash-order
+
+//! Check that `--nondet-hash-order` finds code that depends on the iteration order of a
+//! `HashSet`, and that code that sorts the elements first is verified.
+
+use std::collections::HashSet;
+
+fn ids() -> HashSet<u8> {
+    let mut set = HashSet::new();
+    set.insert(1);
+    set.insert(2);
+    set
+}
+
+#[kani::proof]
+fn check_first_id() {
+    let first = ids().into_iter().next().unwrap();
+    assert_eq!(first, 1, "the first id is the smallest");
+}
+
+#[kani::proof]
+fn check_sorted_first_id() {
+    let mut ids: Vec<_> = ids().into_iter().collect();
+    ids.sort();
+    assert_eq!(ids[0], 1, "the first id is the smallest");
+    assert!(ids.contains(&2));
+}
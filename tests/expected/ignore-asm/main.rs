@@ -0,0 +1,42 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z ignore-asm --ignore-asm
+//
+//! This tests that `--ignore-asm` replaces the outputs of inline assembly
+//! blocks with nondeterministic values, and that Kani reports which blocks were
+//! replaced.
+
+use std::arch::asm;
+
+/// Read the time-stamp counter, whose value can't be predicted.
+fn rdtsc() -> u64 {
+    let low: u32;
+    let high: u32;
+    unsafe {
+        asm!("rdtsc", out("eax") low, out("edx") high, options(nomem, nostack));
+    }
+    ((high as u64) << 32) | low as u64
+}
+
+fn add_one(x: u32) -> u32 {
+    let mut y = x;
+    unsafe {
+        asm!("add {0:e}, 1", inout(reg) y);
+    }
+    y
+}
+
+#[kani::proof]
+fn check_rdtsc() {
+    let time = rdtsc();
+    kani::cover!(time == 0);
+    kani::cover!(time == u64::MAX);
+}
+
+#[kani::proof]
+fn check_inout() {
+    // The output is nondeterministic, so this fails even though the assembly
+    // is correct.
+    assert_eq!(add_one(1), 2);
+}
@@ -0,0 +1,40 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `kani::implies!` only evaluates its conclusion when the premises hold, that
+//! `kani::iff!` compares both sides, and that both can be used in contracts.
+
+#[kani::requires(kani::implies!(!v.is_empty() => v[0] < 100))]
+#[kani::ensures(kani::iff!(result, v.is_empty()))]
+fn starts_small(v: &[u8]) -> bool {
+    v.first().map_or(true, |first| *first < 100)
+}
+
+#[kani::proof_for_contract(starts_small)]
+fn check_contract() {
+    let v: [u8; 2] = kani::any();
+    let len: usize = kani::any_where(|len| *len <= 2);
+    starts_small(&v[..len]);
+}
+
+#[kani::proof]
+fn check_short_circuit() {
+    let v: [u8; 4] = kani::any();
+    let idx: usize = kani::any();
+    kani::assume(kani::implies!(idx < v.len() => v[idx] == 1));
+    assert!(kani::implies!(idx < v.len(), v[idx] > 0));
+}
+
+#[kani::proof]
+fn check_iff() {
+    let x: u8 = kani::any();
+    assert!(kani::iff!(x % 2 == 0, x & 1 == 0));
+}
+
+#[kani::proof]
+fn check_implies_fail() {
+    let x: u8 = kani::any();
+    let y: u8 = kani::any();
+    assert!(kani::implies!(x > 0, x < 10 => y > 0));
+}
@@ -0,0 +1,26 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// kani-flags: -Z loop-contracts
+
+//! Check that the invariant of a loop is attached to all of its back edges, here the one of
+//! `continue` and the one at the end of the body.
+
+#![feature(stmt_expr_attributes)]
+#![feature(proc_macro_hygiene)]
+
+#[kani::proof]
+fn continue_while_loop_harness() {
+    let mut x: u8 = kani::any_where(|i| *i >= 2);
+
+    #[kani::loop_invariant(x >= 2)]
+    while x > 2 {
+        if x % 2 == 0 {
+            x = x - 1;
+            continue;
+        }
+        x = x - 1;
+    }
+
+    assert!(x == 2);
+}
@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// kani-flags: -Z loop-contracts
+
+//! Check that a loop with an invariant and a decreasing measure can be verified without
+//! unwinding it.
+
+#![feature(stmt_expr_attributes)]
+#![feature(proc_macro_hygiene)]
+
+#[kani::proof]
+fn decreases_while_loop_harness() {
+    let mut x: u8 = kani::any_where(|i| *i >= 2);
+    let mut y: u8 = kani::any();
+
+    #[kani::loop_invariant(x >= 2)]
+    #[kani::decreases(x, y)]
+    while x > 2 {
+        if y > 0 {
+            y = y - 1;
+        } else {
+            x = x - 1;
+            y = kani::any();
+        }
+    }
+
+    assert!(x == 2);
+}
@@ -0,0 +1,49 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --lossy-cast-checks -Z lossy-cast-checks
+//! Check that `--lossy-cast-checks` reports the integer casts that change the value on some
+//! path, and that intentional casts can be allowed.
+
+fn checksum(bytes: &[u8; 4]) -> u8 {
+    let mut sum: u32 = 0;
+    for byte in bytes {
+        sum += *byte as u32;
+    }
+    sum as u8
+}
+
+fn to_index(offset: i32) -> usize {
+    offset as usize
+}
+
+#[cfg_attr(kani, kanitool::allow_lossy_cast)]
+fn low_byte(value: u32) -> u8 {
+    value as u8
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_truncation() {
+    let bytes: [u8; 4] = kani::any();
+    let _ = checksum(&bytes);
+}
+
+#[kani::proof]
+fn check_sign_change() {
+    let _ = to_index(kani::any());
+}
+
+#[kani::proof]
+fn check_fits() {
+    let len: u64 = kani::any();
+    kani::assume(len <= u16::MAX as u64);
+    let short = len as u16;
+    assert_eq!(short as u64, len);
+}
+
+#[kani::proof]
+fn check_allowed() {
+    let _ = low_byte(kani::any());
+    #[cfg_attr(kani, kanitool::allow_lossy_cast)]
+    let _wrapped = kani::any::<i64>() as i8;
+}
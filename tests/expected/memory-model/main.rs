@@ -0,0 +1,39 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --memory-model tso -Z memory-model
+//! Check the store buffering litmus test under total store order: each thread writes to one
+//! location and reads the other. With relaxed atomics, both writes can be delayed after the reads,
+//! so both threads can read 0. Sequentially consistent atomics forbid this outcome.
+
+use kani::thread::spawn;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static X_RELAXED: AtomicU32 = AtomicU32::new(0);
+static Y_RELAXED: AtomicU32 = AtomicU32::new(0);
+
+static X_SEQCST: AtomicU32 = AtomicU32::new(0);
+static Y_SEQCST: AtomicU32 = AtomicU32::new(0);
+
+#[kani::proof]
+fn check_store_buffering_relaxed() {
+    let handle = spawn(|| {
+        X_RELAXED.store(1, Ordering::Relaxed);
+        Y_RELAXED.load(Ordering::Relaxed)
+    });
+    Y_RELAXED.store(1, Ordering::Relaxed);
+    let x = X_RELAXED.load(Ordering::Relaxed);
+    let y = handle.join().unwrap();
+    kani::cover!(x == 0 && y == 0, "both threads read 0");
+}
+
+#[kani::proof]
+fn check_store_buffering_seqcst() {
+    let handle = spawn(|| {
+        X_SEQCST.store(1, Ordering::SeqCst);
+        Y_SEQCST.load(Ordering::SeqCst)
+    });
+    Y_SEQCST.store(1, Ordering::SeqCst);
+    let x = X_SEQCST.load(Ordering::SeqCst);
+    let y = handle.join().unwrap();
+    assert!(x == 1 || y == 1, "a thread reads the write of the other");
+}
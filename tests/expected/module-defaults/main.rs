@@ -0,0 +1,39 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `#[kani::defaults]` sets the attributes of the harnesses of a module, unless the
+//! harness or a submodule overrides them.
+
+fn sum(n: u8) -> u32 {
+    let mut total = 0;
+    for i in 0..n {
+        total += i as u32;
+    }
+    total
+}
+
+#[kani::defaults(unwind = 4, solver = "cadical")]
+mod proofs {
+    use super::sum;
+
+    #[kani::proof]
+    fn check_default_unwind() {
+        assert_eq!(sum(3), 3);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(2)]
+    fn check_harness_unwind() {
+        assert_eq!(sum(3), 3);
+    }
+
+    #[kani::defaults(unwind = 1)]
+    mod nested {
+        use super::sum;
+
+        #[kani::proof]
+        fn check_nested_unwind() {
+            assert_eq!(sum(3), 3);
+        }
+    }
+}
@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the results of `kani::check!` are listed by the name of the check, and that a
+//! failed check doesn't stop the checks that follow it.
+
+fn checked_double(x: u8) -> u8 {
+    kani::check!("double.no_overflow", x < 128, "x * 2 doesn't overflow");
+    x.wrapping_mul(2)
+}
+
+#[kani::proof]
+fn check_named() {
+    let x: u8 = kani::any();
+    kani::check!(in_range, x < 200);
+    let y = checked_double(x / 2);
+    let z = checked_double(x);
+    kani::check!(even, y % 2 == 0 && z % 2 == 0);
+}
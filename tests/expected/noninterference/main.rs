@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `kani::assert_noninterference!` fails if and only if the output of the closure
+//! depends on its secret input.
+
+struct Account {
+    pin: u16,
+    balance: u32,
+}
+
+fn masked_balance(account: &Account) -> u32 {
+    account.balance & !0xff
+}
+
+fn check_pin(account: &Account, guess: u16) -> bool {
+    account.pin == guess
+}
+
+#[kani::proof]
+fn check_public_output() {
+    kani::assert_noninterference!(|pin: u16, balance: u32| {
+        masked_balance(&Account { pin, balance })
+    });
+}
+
+#[kani::proof]
+fn check_leaky_output() {
+    kani::assert_noninterference!(
+        |pin: u16, guess: u16| check_pin(&Account { pin, balance: 0 }, guess),
+        "the result of a guess doesn't reveal the pin"
+    );
+}
@@ -0,0 +1,37 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z pointer-provenance-checks --check pointer-provenance
+
+//! Check that `--check pointer-provenance` reports pointer arithmetic that leaves the allocation
+//! of the pointer and integers that are cast to pointers, but not in-bounds arithmetic.
+
+#[kani::proof]
+fn check_offset_in_bounds() {
+    let array = [1u8, 2, 3, 4];
+    let index: usize = kani::any();
+    kani::assume(index <= array.len());
+    // One past the end of the array is still in bounds.
+    let _end = unsafe { array.as_ptr().add(index) };
+}
+
+#[kani::proof]
+fn check_offset_out_of_bounds() {
+    let array = [1u16, 2, 3];
+    let _ptr = unsafe { array.as_ptr().add(4) };
+}
+
+#[kani::proof]
+fn check_wrapping_offset() {
+    let array = [1u16, 2, 3];
+    // Wrapping arithmetic may leave the allocation, as long as the pointer isn't dereferenced.
+    let ptr = array.as_ptr().wrapping_add(10).wrapping_sub(10);
+    assert_eq!(unsafe { *ptr }, 1);
+}
+
+#[kani::proof]
+fn check_int_to_ptr() {
+    let value = 42u32;
+    let addr = &value as *const u32 as usize;
+    let ptr = addr as *const u32;
+    let _ = ptr;
+}
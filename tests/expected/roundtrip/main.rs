@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::roundtrip!` reports the values that don't round-trip through a codec.
+
+/// Saturates values that don't fit in a byte, so they don't round-trip.
+fn encode(value: &u16) -> [u8; 1] {
+    [(*value).min(u8::MAX as u16) as u8]
+}
+
+fn decode(bytes: &[u8; 1]) -> Option<u16> {
+    Some(bytes[0] as u16)
+}
+
+/// Rejects the zero byte, so zero fails to deserialize.
+fn decode_nonzero(bytes: &[u8; 1]) -> Result<u8, ()> {
+    if bytes[0] == 0 { Err(()) } else { Ok(bytes[0]) }
+}
+
+#[kani::proof]
+fn check_saturating_codec() {
+    kani::roundtrip! {
+        serialize = encode, deserialize = decode;
+        value: u16
+    }
+}
+
+#[kani::proof]
+fn check_nonzero_codec() {
+    kani::roundtrip! {
+        serialize = |byte: &u8| [*byte], deserialize = decode_nonzero;
+        byte: u8
+    }
+}
@@ -0,0 +1,14 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that a snapshot reports a value that changed.
+
+#[kani::proof]
+fn check_changed() {
+    let mut pair: (u32, u32) = kani::any();
+    let before = kani::snapshot::Snapshot::take(&pair);
+    if pair.0 > 10 {
+        pair.1 = pair.1.wrapping_add(1);
+    }
+    before.assert_unchanged(&pair);
+}
@@ -0,0 +1,26 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stack-depth --max-stack-depth 4
+
+//! Check that `--max-stack-depth` flags call chains that are deeper than the given bound,
+//! while shallower ones are verified successfully.
+
+fn depth(n: u8) -> u8 {
+    if n == 0 { 0 } else { 1 + depth(n - 1) }
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_shallow() {
+    // Harness + 3 calls to `depth`.
+    assert_eq!(depth(2), 2);
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_deep() {
+    let n: u8 = kani::any();
+    kani::assume(n < 4);
+    assert_eq!(depth(n), n);
+}
@@ -0,0 +1,43 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z race-checks --race-checks
+
+//! Check that `--race-checks` stubs `std::thread::spawn` and `std::sync::Mutex` with the models
+//! of `kani::thread`, so that Kani explores the interleavings of the threads.
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[kani::proof]
+fn check_mutex() {
+    let counter = Arc::new(Mutex::new(0));
+    let other = counter.clone();
+    let handle = thread::spawn(move || *other.lock().unwrap() += 1);
+    *counter.lock().unwrap() += 1;
+    handle.join().unwrap();
+    assert_eq!(*counter.lock().unwrap(), 2);
+}
+
+/// The lock is released between the read and the write, so both threads can read 0.
+#[kani::proof]
+fn check_lost_update() {
+    let counter = Arc::new(Mutex::new(0));
+    let other = counter.clone();
+    let handle = thread::spawn(move || {
+        let value = *other.lock().unwrap();
+        *other.lock().unwrap() = value + 1;
+    });
+    let value = *counter.lock().unwrap();
+    *counter.lock().unwrap() = value + 1;
+    handle.join().unwrap();
+    assert!(*counter.lock().unwrap() == 2, "lost update");
+}
+
+#[kani::proof]
+fn check_detached() {
+    let x: u8 = kani::any();
+    let handle = thread::spawn(move || x / 2);
+    if handle.is_finished() {
+        assert_eq!(handle.join().unwrap(), x / 2);
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness check_usage --enable-unstable --enable-stubbing
+//
+//! This tests that Kani reports the call sites that each stub redirected, and
+//! warns about the stubs that didn't replace any call.
+
+fn magic_number() -> u32 {
+    13
+}
+
+fn unused() -> u32 {
+    0
+}
+
+fn double_magic() -> u32 {
+    magic_number() * 2
+}
+
+fn mock_magic_number() -> u32 {
+    42
+}
+
+fn mock_unused() -> u32 {
+    1
+}
+
+#[kani::proof]
+#[kani::stub(magic_number, mock_magic_number)]
+#[kani::stub(unused, mock_unused)]
+fn check_usage() {
+    assert_eq!(magic_number(), 42);
+    assert_eq!(double_magic(), 84);
+}
@@ -0,0 +1,40 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that Kani explores the interleavings of the threads spawned with
+//! `kani::thread::spawn`, and that joins and mutexes synchronize them.
+
+use kani::thread::{spawn, Mutex};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+/// The increments aren't atomic, so both threads can read 0 before either of them writes.
+#[kani::proof]
+fn check_lost_update() {
+    let counter = Arc::new(AtomicU32::new(0));
+    let other = counter.clone();
+    let handle = spawn(move || {
+        let value = other.load(Ordering::SeqCst);
+        other.store(value + 1, Ordering::SeqCst);
+    });
+    let value = counter.load(Ordering::SeqCst);
+    counter.store(value + 1, Ordering::SeqCst);
+    handle.join().unwrap();
+    assert!(counter.load(Ordering::SeqCst) == 2, "lost update");
+}
+
+#[kani::proof]
+fn check_mutex() {
+    let counter = Arc::new(Mutex::new(0));
+    let other = counter.clone();
+    let handle = spawn(move || *other.lock() += 1);
+    *counter.lock() += 1;
+    handle.join().unwrap();
+    assert_eq!(*counter.lock(), 2);
+}
+
+#[kani::proof]
+fn check_join_result() {
+    let x: u8 = kani::any();
+    let handle = spawn(move || x as u16 * 2);
+    assert_eq!(handle.join().unwrap(), x as u16 * 2);
+}
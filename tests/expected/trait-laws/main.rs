@@ -0,0 +1,51 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z trait-laws --trait-laws
+
+//! Check that `--trait-laws` verifies the laws of the `PartialEq`, `Eq`, `Ord` and `Hash`
+//! implementations of the crate.
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+/// Derived implementations obey the laws.
+#[derive(kani::Arbitrary, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Version {
+    major: u8,
+    minor: u8,
+}
+
+/// Compares case-insensitively, but hashes the raw byte.
+#[derive(kani::Arbitrary, Eq)]
+struct Letter(u8);
+
+impl PartialEq for Letter {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_ascii_lowercase() == other.0.to_ascii_lowercase()
+    }
+}
+
+impl Hash for Letter {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+/// Orders by distance to 100, which makes `cmp` disagree with `eq`.
+#[derive(kani::Arbitrary, PartialEq, Eq)]
+struct Distance(u8);
+
+impl PartialOrd for Distance {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Distance {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.abs_diff(100).cmp(&other.0.abs_diff(100))
+    }
+}
+
+#[derive(PartialEq)]
+struct NotArbitrary;
@@ -0,0 +1,46 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z uninit-checks --check uninit
+
+//! Check that `--check uninit` reports the reads of memory that was never initialized, on the
+//! stack and on the heap, and not the reads of memory that was initialized.
+
+use std::alloc::{alloc, dealloc, Layout};
+use std::mem::MaybeUninit;
+
+#[kani::proof]
+fn check_assume_init_uninit() {
+    let x: MaybeUninit<u32> = MaybeUninit::uninit();
+    let _v = unsafe { x.assume_init() };
+}
+
+#[kani::proof]
+fn check_assume_init_written() {
+    let mut x: MaybeUninit<u32> = MaybeUninit::uninit();
+    x.write(kani::any());
+    let v = unsafe { x.assume_init() };
+    let y: MaybeUninit<u8> = MaybeUninit::new(v as u8);
+    assert_eq!(unsafe { y.assume_init() }, v as u8);
+}
+
+#[kani::proof]
+fn check_heap_uninit() {
+    let layout = Layout::new::<[u8; 4]>();
+    unsafe {
+        let ptr = alloc(layout);
+        *ptr = 1;
+        let _v = *ptr.add(2);
+        dealloc(ptr, layout);
+    }
+}
+
+#[kani::proof]
+fn check_heap_written() {
+    let layout = Layout::new::<u16>();
+    unsafe {
+        let ptr = alloc(layout) as *mut u16;
+        ptr.write(10);
+        assert_eq!(*ptr, 10);
+        dealloc(ptr as *mut u8, layout);
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that Kani reports reads of union fields that cover bytes that were never initialized.
+
+union Value {
+    byte: u8,
+    word: u32,
+}
+
+/// Use the value that is read, so the read isn't removed as dead code.
+fn consume<T>(_value: T) {}
+
+#[kani::proof]
+fn check_read_larger_field() {
+    let value = Value { byte: kani::any() };
+    consume(unsafe { value.word });
+}
+
+#[kani::proof]
+fn check_read_smaller_field() {
+    let value = Value { word: kani::any() };
+    consume(unsafe { value.byte });
+}
+
+#[kani::proof]
+fn check_write_larger_field() {
+    let mut value = Value { byte: kani::any() };
+    if kani::any() {
+        value.word = kani::any();
+    }
+    consume(unsafe { value.word });
+}
@@ -0,0 +1,42 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z utf8-checks --utf8-checks --no-utf8-assumptions
+
+//! Check that `--utf8-checks` checks the bytes given to the unchecked conversions of the local
+//! crate, and that `--no-utf8-assumptions` lets the generators build invalid strings.
+
+fn as_text(bytes: &[u8]) -> &str {
+    // SAFETY: The callers only pass ASCII bytes. Or do they?
+    unsafe { std::str::from_utf8_unchecked(bytes) }
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_ascii() {
+    let bytes: [u8; 2] = kani::any();
+    kani::assume(bytes.is_ascii());
+    assert_eq!(as_text(&bytes).len(), 2);
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_any_bytes() {
+    let bytes: [u8; 2] = kani::any();
+    let _ = as_text(&bytes);
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_string() {
+    let bytes = vec![b'o', b'k'];
+    // SAFETY: Both bytes are ASCII.
+    let text = unsafe { String::from_utf8_unchecked(bytes) };
+    assert_eq!(text, "ok");
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_bounded_string() {
+    let text: String = kani::bounded_any::<_, 2>();
+    assert!(std::str::from_utf8(text.as_bytes()).is_ok(), "the string is valid UTF-8");
+}
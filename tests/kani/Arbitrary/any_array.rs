@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the predicate given to `kani::any_array_where` holds for every element of the
+//! array. See `tests/expected/any-array` for the values that `kani::any_array` can build.
+
+#[kani::proof]
+#[kani::unwind(17)]
+fn check_any_array_where_non_zero() {
+    let buffer = kani::any_array_where::<u8, 16>(|b| *b != 0);
+    for b in buffer {
+        assert_ne!(b, 0);
+    }
+    kani::cover!(buffer[15] == u8::MAX);
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_any_array_where_sorted() {
+    let mut prev = 0;
+    let sorted = kani::any_array_where::<u32, 4>(|elem| {
+        let ordered = *elem >= prev;
+        prev = *elem;
+        ordered
+    });
+    assert!(sorted.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+#[kani::proof]
+fn check_any_array_empty() {
+    let empty = kani::any_array_where::<char, 0>(|_| false);
+    assert!(empty.is_empty());
+}
@@ -0,0 +1,73 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the `BoundedArbitrary` implementations respect the bound, and that derived
+//! `Arbitrary` implementations honor `#[kani(bound = N)]` field annotations.
+
+use std::collections::{BTreeMap, HashSet, VecDeque};
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_vec() {
+    let vec: Vec<u16> = kani::bounded_any::<_, 4>();
+    assert!(vec.len() <= 4);
+    kani::cover!(vec.is_empty());
+    kani::cover!(vec.len() == 4);
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_vec_deque() {
+    let deque = kani::bounded_any::<VecDeque<bool>, 4>();
+    assert!(deque.len() <= 4);
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_string() {
+    let string = kani::bounded_any::<String, 3>();
+    assert!(string.len() <= 3);
+    assert!(string.chars().count() <= 3);
+    kani::cover!(string == "€");
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_maps() {
+    let map = kani::bounded_any::<BTreeMap<u8, char>, 3>();
+    assert!(map.len() <= 3);
+    let set = kani::bounded_any::<HashSet<u8>, 2>();
+    assert!(set.len() <= 2);
+}
+
+#[derive(kani::Arbitrary)]
+struct Packet {
+    header: u8,
+    #[kani(bound = 4)]
+    payload: Vec<u8>,
+    #[kani(bound = Self::NAME_LEN)]
+    name: Option<String>,
+}
+
+impl Packet {
+    const NAME_LEN: usize = 2;
+}
+
+#[derive(kani::Arbitrary)]
+enum Message {
+    Empty,
+    Data(#[kani(bound = 2)] Box<[u32]>),
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_derive_bound() {
+    let packet: Packet = kani::any();
+    assert!(packet.payload.len() <= 4);
+    assert!(packet.name.map_or(true, |name| name.len() <= Packet::NAME_LEN));
+    kani::cover!(packet.header == 0 && packet.payload.len() == 4);
+    match kani::any::<Message>() {
+        Message::Empty => {}
+        Message::Data(data) => assert!(data.len() <= 2),
+    }
+}
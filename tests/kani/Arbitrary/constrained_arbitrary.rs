@@ -0,0 +1,56 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that derived `Arbitrary` implementations honor `#[kani(range = ..)]` and
+//! `#[kani(assume = "..")]` field annotations.
+
+#[derive(kani::Arbitrary)]
+struct Date {
+    #[kani(range = 1..=12)]
+    month: u8,
+    #[kani(range = 1..=31, assume = "*day <= 30 || *month != 4")]
+    day: u8,
+    year: u16,
+}
+
+#[derive(kani::Arbitrary)]
+struct Percent(#[kani(range = 0..=100)] u8);
+
+#[derive(kani::Arbitrary)]
+enum Sensor {
+    Offline,
+    Reading(#[kani(range = -40..125)] i8),
+    Battery {
+        #[kani(bound = 2)]
+        cells: Vec<u8>,
+        #[kani(assume = *level as usize <= 10 * cells.len())]
+        level: u8,
+    },
+}
+
+#[kani::proof]
+fn check_struct_range() {
+    let date: Date = kani::any();
+    assert!(date.month >= 1 && date.month <= 12);
+    assert!(date.day >= 1 && date.day <= 31);
+    assert!(date.month != 4 || date.day != 31);
+    kani::cover!(date.month == 12 && date.day == 31);
+    let percent: Percent = kani::any();
+    assert!(percent.0 <= 100);
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+fn check_enum_range() {
+    match kani::any() {
+        Sensor::Offline => {}
+        Sensor::Reading(temperature) => {
+            assert!(temperature >= -40 && temperature < 125);
+            kani::cover!(temperature == -40);
+        }
+        Sensor::Battery { cells, level } => {
+            assert!(level as usize <= 10 * cells.len());
+            kani::cover!(level == 20);
+        }
+    }
+}
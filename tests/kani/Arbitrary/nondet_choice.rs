@@ -0,0 +1,40 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check that `kani::nondet_choice` only creates the selected variants of an enum, and that it can
+// create each of them.
+
+use std::mem::discriminant;
+
+#[derive(kani::Arbitrary, PartialEq)]
+enum Command {
+    Read(u8),
+    Write(u8, u8),
+    Reset,
+}
+
+#[kani::proof]
+fn check_subset() {
+    let command: Command =
+        kani::nondet_choice(&[discriminant(&Command::Read(0)), discriminant(&Command::Reset)]);
+    assert!(!matches!(command, Command::Write(..)));
+    kani::cover!(matches!(command, Command::Read(_)));
+    kani::cover!(command == Command::Reset);
+}
+
+#[kani::proof]
+fn check_runtime_subset() {
+    let variants = [discriminant(&Command::Write(0, 0)), discriminant(&Command::Reset)];
+    let count: usize = kani::any_where(|count| *count > 0 && *count <= variants.len());
+    let command: Command = kani::nondet_choice(&variants[..count]);
+    if count == 1 {
+        assert!(matches!(command, Command::Write(..)));
+    }
+    assert!(!matches!(command, Command::Read(_)));
+}
+
+#[kani::proof]
+#[kani::should_panic]
+fn check_empty() {
+    let _: Command = kani::nondet_choice(&[]);
+}
@@ -0,0 +1,50 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the derived `Arbitrary` and `Invariant` implementations of a type with a
+//! `#[kani(transparent)]` annotation delegate to the implementations of its field.
+
+use std::marker::PhantomData;
+use std::num::NonZeroU16;
+
+#[derive(kani::Arbitrary)]
+#[kani(transparent)]
+struct Port(NonZeroU16);
+
+/// The type parameter doesn't implement `Arbitrary`, only the type of the field does.
+#[derive(kani::Arbitrary)]
+#[kani(transparent)]
+struct Marker<T> {
+    #[allow(dead_code)]
+    marker: PhantomData<T>,
+}
+
+struct NotArbitrary;
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+#[kani(invariant = "self.hi >= self.lo")]
+struct Range {
+    lo: u8,
+    hi: u8,
+}
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+#[kani(transparent, invariant = "self.0.hi != 255")]
+struct Bounded(Range);
+
+#[kani::proof]
+fn check_transparent_arbitrary() {
+    let port: Port = kani::any();
+    assert!(port.0.get() != 0);
+    let _marker: Marker<NotArbitrary> = kani::any();
+}
+
+#[kani::proof]
+fn check_transparent_invariant() {
+    let mut bounded: Bounded = kani::any();
+    assert!(bounded.0.lo <= bounded.0.hi);
+    assert!(bounded.0.hi != 255);
+    kani::assert_invariant(&bounded);
+    bounded.0.lo = bounded.0.hi + 1;
+    assert!(!kani::Invariant::is_valid(&bounded));
+}
@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z array-theory --array-theory
+
+//! Check that harnesses over large buffers are verified with `--array-theory`, which encodes the
+//! buffers with the array theory of the solver instead of one variable per element.
+
+#[kani::proof]
+fn check_large_buffer() {
+    let mut buffer = [0u8; 4096];
+    let idx: usize = kani::any();
+    kani::assume(idx < buffer.len());
+    let value: u8 = kani::any();
+    buffer[idx] = value;
+    assert_eq!(buffer[idx], value);
+    let other: usize = kani::any();
+    kani::assume(other < buffer.len() && other != idx);
+    assert_eq!(buffer[other], 0);
+}
@@ -46,3 +46,17 @@ fn round_robin_schedule_manual() {
     );
     assert_eq!(x.load(Ordering::Relaxed), 2);
 }
+
+#[kani::proof(schedule = kani::Nondeterministic)]
+#[kani::unwind(4)]
+async fn nondeterministic_schedule() {
+    let x = Arc::new(AtomicI64::new(0));
+    let x2 = x.clone();
+    let handle = kani::spawn(async move {
+        x2.fetch_add(1, Ordering::Relaxed);
+    });
+    kani::yield_now().await;
+    x.fetch_add(1, Ordering::Relaxed);
+    handle.await;
+    assert_eq!(x.load(Ordering::Relaxed), 2);
+}
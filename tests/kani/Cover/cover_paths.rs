@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::cover!` can be used through a renamed `kani` crate and that it accepts a
+//! trailing comma after its message.
+
+extern crate kani as verifier;
+
+#[kani::proof]
+fn check_renamed_crate() {
+    let x: u8 = verifier::any();
+    verifier::cover!();
+    verifier::cover!(x > 10);
+    verifier::cover!(x == 0, "x can be zero",);
+}
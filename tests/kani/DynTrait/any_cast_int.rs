@@ -1,9 +1,7 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-// kani-flags: --no-restrict-vtable
-// Tracking issue for the need for this flag:
-// https://github.com/model-checking/kani/issues/802
+// kani-flags: --enable-unstable --restrict-vtable
 
 use std::any::Any;
 
@@ -0,0 +1,85 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// kani-flags: --enable-unstable --restrict-vtable
+
+//! Check that downcasting trait objects with auto traits finds the concrete type, also when the
+//! targets of virtual calls are restricted. The downcasts call `type_id` through a trait object
+//! without the auto traits, which shares the vtable of the original trait object.
+
+use std::any::{Any, TypeId};
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+struct ParseError {
+    position: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "parse error at {}", self.position)
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug)]
+struct IoError;
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "I/O error")
+    }
+}
+
+impl Error for IoError {}
+
+fn parse(input: u8) -> Result<u8, Box<dyn Error + Send + Sync>> {
+    match input {
+        0 => Err(Box::new(IoError)),
+        1..=9 => Ok(input),
+        _ => Err(Box::new(ParseError { position: input as usize })),
+    }
+}
+
+#[kani::proof]
+fn check_error_downcast() {
+    let input: u8 = kani::any();
+    match parse(input) {
+        Ok(value) => assert!(value > 0 && value < 10),
+        Err(err) => {
+            assert_eq!(err.is::<IoError>(), input == 0);
+            assert_eq!(err.is::<ParseError>(), input >= 10);
+            if let Some(parse_err) = err.downcast_ref::<ParseError>() {
+                assert_eq!(parse_err.position, input as usize);
+            }
+            if input >= 10 {
+                let parse_err = err.downcast::<ParseError>().unwrap();
+                assert_eq!(*parse_err, ParseError { position: input as usize });
+            }
+        }
+    }
+}
+
+#[kani::proof]
+fn check_any_downcast() {
+    let value: Box<dyn Any + Send> = if kani::any() { Box::new(10u32) } else { Box::new('a') };
+    let type_id = (*value).type_id();
+    assert!(type_id == TypeId::of::<u32>() || type_id == TypeId::of::<char>());
+    assert_ne!(type_id, TypeId::of::<i32>());
+    match value.downcast::<u32>() {
+        Ok(int) => assert_eq!(*int, 10),
+        Err(value) => assert_eq!(*value.downcast::<char>().unwrap(), 'a'),
+    }
+}
+
+#[kani::proof]
+fn check_downcast_mut() {
+    let mut value: Box<dyn Any + Send + Sync> = Box::new(5u8);
+    if let Some(int) = value.downcast_mut::<u8>() {
+        *int += 1;
+    }
+    assert!(value.downcast_mut::<u16>().is_none());
+    assert_eq!(value.downcast_ref::<u8>(), Some(&6));
+}
@@ -0,0 +1,53 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check the generation of fixed-capacity containers with a minimal container, which only
+//! implements the standard traits that the functions rely on.
+extern crate kani;
+
+use kani::fixed_capacity::{any_collection, any_str};
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+struct Buffer<const CAP: usize> {
+    len: usize,
+    bytes: [u8; CAP],
+}
+
+impl<const CAP: usize> FromIterator<u8> for Buffer<CAP> {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut buffer = Buffer { len: 0, bytes: [0; CAP] };
+        for byte in iter {
+            assert!(buffer.len < CAP, "buffer is full");
+            buffer.bytes[buffer.len] = byte;
+            buffer.len += 1;
+        }
+        buffer
+    }
+}
+
+impl<const CAP: usize> FromStr for Buffer<CAP> {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        if s.len() > CAP {
+            return Err(());
+        }
+        Ok(s.bytes().collect())
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_collection() {
+    let buffer: Buffer<3> = any_collection::<_, _, 3>();
+    assert!(buffer.len <= 3);
+    assert!(buffer.bytes[buffer.len..].iter().all(|byte| *byte == 0));
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_str() {
+    let buffer: Buffer<4> = any_str::<_, 4>();
+    assert!(std::str::from_utf8(&buffer.bytes[..buffer.len]).is_ok());
+}
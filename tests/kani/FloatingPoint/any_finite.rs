@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks the generators of floats that exclude special values.
+
+#[kani::proof]
+fn check_finite() {
+    let x = kani::any_finite_f64();
+    assert!(x - x == 0.0);
+    let y = kani::any_finite_f32();
+    assert!(y.is_finite());
+    kani::cover!(y.is_subnormal());
+}
+
+#[kani::proof]
+fn check_normal() {
+    let x = kani::any_normal_f64();
+    assert!(x.is_normal() || x == 0.0);
+    let y = kani::any_normal_f32();
+    assert!(!y.is_subnormal() && !y.is_nan());
+}
@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z float-values --float-values normal
+
+//! Checks that `--float-values` restricts the floats created by `kani::any`,
+//! including the fields of types that derive `Arbitrary`.
+
+#[derive(kani::Arbitrary)]
+struct Point {
+    x: f32,
+    y: f64,
+}
+
+#[kani::proof]
+fn check_any() {
+    let x: f64 = kani::any();
+    assert!(x.is_finite());
+    assert!(x.is_normal() || x == 0.0);
+}
+
+#[kani::proof]
+fn check_derive() {
+    let point: Point = kani::any();
+    assert!(point.x.is_finite() && !point.x.is_subnormal());
+    assert!(point.y.is_finite() && !point.y.is_subnormal());
+}
+
+#[kani::proof]
+fn check_zero() {
+    let x: f32 = kani::any();
+    kani::cover!(x == 0.0);
+}
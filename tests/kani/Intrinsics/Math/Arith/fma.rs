@@ -0,0 +1,38 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Checks that `fmaf32` and `fmaf64` round the result of the multiplication and
+// the addition only once.
+#![feature(core_intrinsics)]
+use std::intrinsics::{fmaf32, fmaf64};
+
+#[kani::proof]
+fn test_single_rounding_f64() {
+    // 0.1 isn't exact, and the error is lost when the product is rounded.
+    assert!(0.1f64 * 10.0 - 1.0 == 0.0);
+    let result = unsafe { fmaf64(0.1, 10.0, -1.0) };
+    assert!(result == 5.551115123125783e-17);
+}
+
+#[kani::proof]
+fn test_single_rounding_f32() {
+    let result = unsafe { fmaf32(0.1, 10.0, -1.0) };
+    assert!(result == 1.4901161e-8);
+}
+
+#[kani::proof]
+fn test_mul_add_identity() {
+    let x = kani::any_finite_f32();
+    let y = kani::any_finite_f32();
+    assert!(x.mul_add(1.0, y) == x + y);
+    assert!(x.mul_add(y, 0.0) == x * y);
+}
+
+#[kani::proof]
+fn test_special_values() {
+    let x: f64 = kani::any();
+    kani::assume(!x.is_nan());
+    assert!(unsafe { fmaf64(x, 0.0, f64::NAN) }.is_nan());
+    // The product isn't rounded, so it doesn't overflow before the addition.
+    assert!(unsafe { fmaf64(f64::MAX, 2.0, f64::NEG_INFINITY) } == f64::NEG_INFINITY);
+}
@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Checks that `powif32` and `powif64` raise a float to an integer power.
+#![feature(core_intrinsics)]
+use std::intrinsics::{powif32, powif64};
+
+#[kani::proof]
+fn test_powers_of_two() {
+    assert!(unsafe { powif64(2.0, 10) } == 1024.0);
+    assert!(unsafe { powif64(2.0, -2) } == 0.25);
+    assert!(unsafe { powif32(2.0, 0) } == 1.0);
+    assert!(unsafe { powif32(2.0, 128) } == f32::INFINITY);
+}
+
+#[kani::proof]
+fn test_square() {
+    let x: f32 = kani::any();
+    let square = x.powi(2);
+    assert!(square == x * x || x.is_nan());
+}
+
+#[kani::proof]
+fn test_any_exponent() {
+    let n: i32 = kani::any();
+    let result = 1.0f64.powi(n);
+    assert!(result == 1.0);
+}
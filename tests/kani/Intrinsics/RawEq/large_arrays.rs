@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Check that comparing buffers of a known size doesn't require any unwinding, since the bytes
+// are compared as a single bit vector.
+#![feature(core_intrinsics)]
+use std::intrinsics::compare_bytes;
+
+#[kani::proof]
+#[kani::unwind(1)]
+fn check_array_eq() {
+    let a: [u8; 256] = kani::any();
+    let mut b = a;
+    assert!(a == b);
+    let idx: usize = kani::any_where(|idx| *idx < b.len());
+    b[idx] = b[idx].wrapping_add(1);
+    assert!(a != b);
+}
+
+#[kani::proof]
+#[kani::unwind(1)]
+fn check_compare_bytes_order() {
+    let a: [u8; 16] = kani::any();
+    let b: [u8; 16] = kani::any();
+    let cmp = unsafe { compare_bytes(a.as_ptr(), b.as_ptr(), 16) };
+    // The bytes are ordered like the big-endian integers they represent.
+    let expected = u128::from_be_bytes(a).cmp(&u128::from_be_bytes(b));
+    assert_eq!(cmp.cmp(&0), expected);
+}
+
+/// Buffers that are too large for a single bit vector are compared byte by byte, which has to be
+/// unwound.
+#[kani::proof]
+#[kani::unwind(302)]
+fn check_array_eq_above_threshold() {
+    let a: [u8; 300] = kani::any();
+    let mut b = a;
+    assert!(a == b);
+    b[299] = b[299].wrapping_add(1);
+    assert!(a != b);
+}
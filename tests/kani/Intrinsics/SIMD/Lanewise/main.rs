@@ -0,0 +1,78 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks the SIMD intrinsics that apply an operation to each lane of their arguments.
+#![feature(repr_simd, platform_intrinsics)]
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct i8x4(i8, i8, i8, i8);
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct f32x4(f32, f32, f32, f32);
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct i32x4(i32, i32, i32, i32);
+
+extern "platform-intrinsic" {
+    fn simd_neg<T>(x: T) -> T;
+    fn simd_cast<T, U>(x: T) -> U;
+    fn simd_select<M, T>(mask: M, a: T, b: T) -> T;
+    fn simd_select_bitmask<M, T>(mask: M, a: T, b: T) -> T;
+    fn simd_saturating_add<T>(x: T, y: T) -> T;
+    fn simd_saturating_sub<T>(x: T, y: T) -> T;
+    fn simd_fabs<T>(x: T) -> T;
+    fn simd_floor<T>(x: T) -> T;
+    fn simd_fmin<T>(x: T, y: T) -> T;
+}
+
+#[kani::proof]
+fn check_neg() {
+    let x: i8 = kani::any();
+    let v = unsafe { simd_neg(i8x4(x, 0, -1, i8::MIN)) };
+    assert!(v == i8x4(x.wrapping_neg(), 0, 1, i8::MIN));
+}
+
+#[kani::proof]
+fn check_cast() {
+    let x: i32 = kani::any();
+    let narrow: i8x4 = unsafe { simd_cast(i32x4(x, 300, -1, 127)) };
+    assert!(narrow == i8x4(x as i8, 300i32 as i8, -1, 127));
+    let float: f32x4 = unsafe { simd_cast(i32x4(x, 1, -2, 0)) };
+    assert!(float.0 == x as f32 && float.2 == -2.0);
+}
+
+#[kani::proof]
+fn check_select() {
+    let a = i8x4(kani::any(), kani::any(), kani::any(), kani::any());
+    let b = i8x4(kani::any(), kani::any(), kani::any(), kani::any());
+    let v = unsafe { simd_select(i8x4(-1, 0, -1, 0), a, b) };
+    assert!(v == i8x4(a.0, b.1, a.2, b.3));
+    let v = unsafe { simd_select_bitmask(0b1100u8, a, b) };
+    assert!(v == i8x4(b.0, b.1, a.2, a.3));
+}
+
+#[kani::proof]
+fn check_saturating() {
+    let x: i8 = kani::any();
+    let y: i8 = kani::any();
+    let v = unsafe { simd_saturating_add(i8x4(x, 100, -100, 0), i8x4(y, 100, -100, 0)) };
+    assert!(v == i8x4(x.saturating_add(y), i8::MAX, i8::MIN, 0));
+    let v = unsafe { simd_saturating_sub(i8x4(x, -100, 0, 0), i8x4(y, 100, 0, 0)) };
+    assert!(v == i8x4(x.saturating_sub(y), i8::MIN, 0, 0));
+}
+
+#[kani::proof]
+fn check_float_ops() {
+    let v = unsafe { simd_fabs(f32x4(-1.5, 2.0, -0.0, f32::NEG_INFINITY)) };
+    assert!(v == f32x4(1.5, 2.0, 0.0, f32::INFINITY));
+    let v = unsafe { simd_floor(f32x4(-1.5, 2.5, 0.1, 3.0)) };
+    assert!(v == f32x4(-2.0, 2.0, 0.0, 3.0));
+    let v = unsafe { simd_fmin(f32x4(1.0, f32::NAN, 3.0, -1.0), f32x4(2.0, 1.0, f32::NAN, -2.0)) };
+    assert!(v == f32x4(1.0, 1.0, 3.0, -2.0));
+}
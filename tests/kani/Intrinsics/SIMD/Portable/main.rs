@@ -0,0 +1,46 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that code written with the portable SIMD types of `core::simd` can be verified.
+#![feature(portable_simd)]
+use std::simd::prelude::*;
+use std::simd::ToBitMask;
+
+/// Count the bytes of `haystack` that are equal to `needle`, 16 bytes at a time.
+fn count(haystack: &[u8; 32], needle: u8) -> u32 {
+    let needles = u8x16::splat(needle);
+    haystack
+        .chunks_exact(16)
+        .map(|chunk| u8x16::from_slice(chunk).simd_eq(needles).to_bitmask().count_ones())
+        .sum()
+}
+
+#[kani::proof]
+fn check_count() {
+    let haystack: [u8; 32] = kani::any();
+    let needle: u8 = kani::any();
+    let expected = haystack.iter().filter(|byte| **byte == needle).count() as u32;
+    assert_eq!(count(&haystack, needle), expected);
+}
+
+#[kani::proof]
+fn check_lanewise() {
+    let a = i32x4::from_array(kani::any());
+    let b = i32x4::from_array(kani::any());
+    let max = a.simd_max(b);
+    for i in 0..4 {
+        assert!(max[i] == a[i].max(b[i]));
+    }
+    let clamped = a.simd_clamp(i32x4::splat(-10), i32x4::splat(10));
+    assert!(clamped.reduce_max() <= 10 && clamped.reduce_min() >= -10);
+    let sum = a.saturating_add(b);
+    assert!(sum[0] == a[0].saturating_add(b[0]));
+}
+
+#[kani::proof]
+fn check_cast() {
+    let bytes = u8x4::from_array(kani::any());
+    let wide: u32x4 = bytes.cast();
+    assert!(wide.reduce_sum() <= 4 * 255);
+    assert!((-bytes.cast::<i16>())[0] == -(bytes[0] as i16));
+}
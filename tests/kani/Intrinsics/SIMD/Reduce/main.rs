@@ -0,0 +1,77 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks the SIMD intrinsics that reduce the lanes of a vector to a single value.
+#![feature(repr_simd, platform_intrinsics)]
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+pub struct u8x4(u8, u8, u8, u8);
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+pub struct i32x4(i32, i32, i32, i32);
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+pub struct f64x2(f64, f64);
+
+extern "platform-intrinsic" {
+    fn simd_reduce_add_ordered<T, U>(x: T, acc: U) -> U;
+    fn simd_reduce_add_unordered<T, U>(x: T) -> U;
+    fn simd_reduce_mul_unordered<T, U>(x: T) -> U;
+    fn simd_reduce_and<T, U>(x: T) -> U;
+    fn simd_reduce_or<T, U>(x: T) -> U;
+    fn simd_reduce_xor<T, U>(x: T) -> U;
+    fn simd_reduce_min<T, U>(x: T) -> U;
+    fn simd_reduce_max<T, U>(x: T) -> U;
+    fn simd_reduce_all<T>(x: T) -> bool;
+    fn simd_reduce_any<T>(x: T) -> bool;
+}
+
+#[kani::proof]
+fn check_arith() {
+    let x: u8 = kani::any();
+    let v = u8x4(x, 200, 100, 1);
+    let sum: u8 = unsafe { simd_reduce_add_unordered(v) };
+    assert!(sum == x.wrapping_add(200).wrapping_add(100).wrapping_add(1));
+    let sum: u8 = unsafe { simd_reduce_add_ordered(v, 5) };
+    assert!(sum == x.wrapping_add(50));
+    let product: i32 = unsafe { simd_reduce_mul_unordered(i32x4(2, -3, 4, 1)) };
+    assert!(product == -24);
+    let sum: f64 = unsafe { simd_reduce_add_ordered(f64x2(0.5, 0.25), 1.0) };
+    assert!(sum == 1.75);
+}
+
+#[kani::proof]
+fn check_bitwise() {
+    let v = u8x4(0b1100, 0b1010, 0b1111, 0b1000);
+    assert!(unsafe { simd_reduce_and::<_, u8>(v) } == 0b1000);
+    assert!(unsafe { simd_reduce_or::<_, u8>(v) } == 0b1111);
+    assert!(unsafe { simd_reduce_xor::<_, u8>(v) } == 0b0001);
+}
+
+#[kani::proof]
+fn check_min_max() {
+    let x: i32 = kani::any();
+    let v = i32x4(x, -5, 7, 0);
+    let min: i32 = unsafe { simd_reduce_min(v) };
+    let max: i32 = unsafe { simd_reduce_max(v) };
+    assert!(min == x.min(-5));
+    assert!(max == x.max(7));
+    let max: f64 = unsafe { simd_reduce_max(f64x2(f64::NAN, -1.0)) };
+    assert!(max == -1.0);
+}
+
+#[kani::proof]
+fn check_masks() {
+    let lane: i32 = kani::any();
+    kani::assume(lane == 0 || lane == -1);
+    let all = unsafe { simd_reduce_all(i32x4(-1, -1, lane, -1)) };
+    let any = unsafe { simd_reduce_any(i32x4(0, lane, 0, 0)) };
+    assert!(all == (lane == -1));
+    assert!(any == (lane == -1));
+}
@@ -0,0 +1,66 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that the SSE and AVX intrinsics of `std::arch` can be verified, including
+//! `_mm_shuffle_epi8` and `_mm256_shuffle_epi8`, which call LLVM intrinsics.
+#![cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+use std::mem::transmute;
+
+/// Find the first byte of `block` that is equal to `needle`, like `memchr`.
+#[target_feature(enable = "sse2")]
+unsafe fn find(block: [u8; 16], needle: u8) -> Option<usize> {
+    let block = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+    let mask = _mm_movemask_epi8(_mm_cmpeq_epi8(block, _mm_set1_epi8(needle as i8)));
+    if mask == 0 { None } else { Some(mask.trailing_zeros() as usize) }
+}
+
+#[kani::proof]
+fn check_find() {
+    let block: [u8; 16] = kani::any();
+    let needle: u8 = kani::any();
+    let expected = block.iter().position(|byte| *byte == needle);
+    assert_eq!(unsafe { find(block, needle) }, expected);
+}
+
+#[kani::proof]
+fn check_arith() {
+    let a: [i16; 8] = kani::any();
+    let b: [i16; 8] = kani::any();
+    let sum: [i16; 8] = unsafe {
+        transmute(_mm_adds_epi16(transmute::<_, __m128i>(a), transmute::<_, __m128i>(b)))
+    };
+    for i in 0..8 {
+        assert!(sum[i] == a[i].saturating_add(b[i]));
+    }
+}
+
+#[kani::proof]
+fn check_shuffle() {
+    let bytes: [u8; 16] = kani::any();
+    let indices: [u8; 16] = kani::any();
+    let shuffled: [u8; 16] = unsafe {
+        transmute(_mm_shuffle_epi8(
+            transmute::<_, __m128i>(bytes),
+            transmute::<_, __m128i>(indices),
+        ))
+    };
+    for i in 0..16 {
+        let expected = if indices[i] & 0x80 != 0 { 0 } else { bytes[indices[i] as usize & 0xf] };
+        assert!(shuffled[i] == expected);
+    }
+}
+
+#[kani::proof]
+fn check_shuffle_avx2() {
+    let bytes: [u8; 32] = kani::any();
+    let shuffled: [u8; 32] = unsafe {
+        // Reverse the bytes of each 128-bit lane.
+        let indices = _mm256_setr_epi8(
+            15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8, 7,
+            6, 5, 4, 3, 2, 1, 0,
+        );
+        transmute(_mm256_shuffle_epi8(transmute::<_, __m256i>(bytes), indices))
+    };
+    assert!(shuffled[0] == bytes[15] && shuffled[16] == bytes[31]);
+}
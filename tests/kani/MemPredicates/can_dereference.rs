@@ -0,0 +1,54 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check the pointer predicates of `kani::mem` on valid, null, dangling, and out-of-bounds
+//! pointers.
+
+extern crate kani;
+use kani::mem::{can_dereference, can_read, can_write};
+
+#[kani::proof]
+fn check_valid() {
+    let mut value: u32 = kani::any();
+    let ptr = &mut value as *mut u32;
+    assert!(can_dereference(ptr));
+    assert!(can_read(ptr));
+    assert!(can_write(ptr));
+}
+
+#[kani::proof]
+fn check_null() {
+    assert!(!can_dereference(std::ptr::null::<u64>()));
+    assert!(!can_read(std::ptr::null::<u64>()));
+    assert!(!can_write(std::ptr::null_mut::<u64>()));
+}
+
+#[kani::proof]
+fn check_out_of_bounds() {
+    let array = [0u16; 4];
+    let idx: usize = kani::any();
+    kani::assume(idx <= 4);
+    let ptr = array.as_ptr().wrapping_add(idx);
+    assert_eq!(can_dereference(ptr), idx < 4);
+}
+
+#[kani::proof]
+fn check_unaligned() {
+    let array = [0u32; 2];
+    let ptr = (array.as_ptr() as *const u8).wrapping_add(1) as *const u32;
+    assert!(can_read(ptr));
+    assert!(!can_dereference(ptr));
+}
+
+#[kani::proof]
+fn check_dangling() {
+    let ptr = Box::into_raw(Box::new(10u8));
+    assert!(can_dereference(ptr));
+    drop(unsafe { Box::from_raw(ptr) });
+    assert!(!can_dereference(ptr));
+}
+
+#[kani::proof]
+fn check_zero_sized() {
+    assert!(can_dereference(std::ptr::NonNull::<()>::dangling().as_ptr()));
+}
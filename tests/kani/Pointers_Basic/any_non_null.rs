@@ -0,0 +1,57 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the pointers created by `kani::ptr` point to valid, owned allocations that a
+//! hand-rolled container can use.
+
+use std::ptr::NonNull;
+
+/// A minimal stack that owns a boxed slice through a raw pointer.
+struct RawStack {
+    data: NonNull<u16>,
+    capacity: usize,
+    len: usize,
+}
+
+impl RawStack {
+    fn pop(&mut self) -> Option<u16> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            Some(unsafe { *self.data.as_ptr().add(self.len) })
+        }
+    }
+}
+
+impl Drop for RawStack {
+    fn drop(&mut self) {
+        let slice = NonNull::slice_from_raw_parts(self.data, self.capacity);
+        unsafe { drop(Box::from_raw(slice.as_ptr())) };
+    }
+}
+
+#[kani::proof]
+fn check_any_non_null() {
+    let ptr = kani::ptr::any_non_null::<char>();
+    let c = unsafe { *ptr.as_ptr() };
+    assert!(c <= char::MAX);
+    unsafe { *ptr.as_ptr() = 'a' };
+    let owned = unsafe { Box::from_raw(ptr.as_ptr()) };
+    assert_eq!(*owned, 'a');
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_raw_stack() {
+    let slice = kani::ptr::any_non_null_slice::<u16, 3>();
+    let capacity = slice.len();
+    assert!(capacity <= 3);
+    let len = kani::any_where(|len| *len <= capacity);
+    let mut stack = RawStack { data: slice.cast(), capacity, len };
+    if len > 0 {
+        assert!(stack.pop().is_some());
+    } else {
+        assert!(stack.pop().is_none());
+    }
+}
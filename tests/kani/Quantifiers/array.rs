@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that quantified conditions over arrays don't need to unwind any loop.
+
+fn is_small(value: u8) -> bool {
+    value < 10
+}
+
+#[kani::proof]
+#[kani::unwind(1)]
+fn check_forall() {
+    let buf: [u8; 100] = kani::any();
+    kani::assume(kani::forall!(|i in (0, 100)| is_small(buf[i])));
+    let idx: usize = kani::any_where(|idx| *idx < 100);
+    assert!(buf[idx] < 10);
+}
+
+#[kani::proof]
+#[kani::unwind(1)]
+fn check_exists() {
+    let buf: [u8; 100] = kani::any();
+    let idx: usize = kani::any_where(|idx| *idx < 100);
+    kani::assume(buf[idx] == 0);
+    assert!(kani::exists!(|i in (0, 100)| buf[i] == 0));
+    assert!(kani::forall!(|i in (10, 10)| buf[i] == 1));
+}
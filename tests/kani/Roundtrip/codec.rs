@@ -0,0 +1,60 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::roundtrip!` proves that encoding and decoding a value gives it back.
+
+#[derive(Debug, PartialEq, kani::Arbitrary)]
+struct Header {
+    kind: u8,
+    len: u16,
+    urgent: bool,
+}
+
+impl Header {
+    fn to_bytes(&self) -> [u8; 4] {
+        let len = self.len.to_le_bytes();
+        [self.kind, len[0], len[1], self.urgent as u8]
+    }
+
+    fn from_bytes(bytes: &[u8; 4]) -> Option<Header> {
+        let urgent = match bytes[3] {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+        Some(Header { kind: bytes[0], len: u16::from_le_bytes([bytes[1], bytes[2]]), urgent })
+    }
+}
+
+fn encode_list(list: &[u16]) -> Vec<u8> {
+    let mut bytes = vec![list.len() as u8];
+    for value in list {
+        bytes.extend_from_slice(&value.to_be_bytes());
+    }
+    bytes
+}
+
+fn decode_list(bytes: &[u8]) -> Result<Vec<u16>, &'static str> {
+    let (len, rest) = bytes.split_first().ok_or("empty")?;
+    if rest.len() != 2 * *len as usize {
+        return Err("wrong length");
+    }
+    Ok(rest.chunks(2).map(|chunk| u16::from_be_bytes([chunk[0], chunk[1]])).collect())
+}
+
+#[kani::proof]
+fn check_header_roundtrip() {
+    kani::roundtrip! {
+        serialize = Header::to_bytes, deserialize = Header::from_bytes;
+        header: Header
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_list_roundtrip() {
+    kani::roundtrip! {
+        serialize = |list: &Vec<u16>| encode_list(list), deserialize = decode_list;
+        list: Vec<u16> = bounded(2),
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::scenario!` applies arbitrary sequences of operations to a state.
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_stack_len() {
+    let mut stack = Vec::new();
+    let mut len = 0;
+    kani::scenario! {
+        max_steps = 3, invariant = { assert_eq!(stack.len(), len); };
+        push(value: u8) => { stack.push(value); len += 1; }
+        pop => {
+            if stack.pop().is_some() {
+                len -= 1;
+            }
+        }
+        clear => {
+            stack.clear();
+            len = 0;
+        }
+    }
+    assert!(len <= 3);
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_all_ops_reachable() {
+    let mut total: u32 = 0;
+    let mut pairs = 0;
+    kani::scenario! {
+        max_steps = 2;
+        add(x: u8) => { total += x as u32; }
+        add_pair(x: u8, y: u8,) => {
+            total += x as u32 + y as u32;
+            pairs += 1;
+        }
+    }
+    kani::cover!(pairs == 2);
+    assert!(total <= 2 * 2 * u8::MAX as u32);
+}
@@ -0,0 +1,49 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that snapshots detect that a value is unchanged.
+extern crate kani;
+
+use kani::snapshot::{DeepSnapshot, Snapshot};
+
+#[derive(Clone, PartialEq, kani::Arbitrary)]
+struct Account {
+    id: u64,
+    balance: u64,
+    frozen: bool,
+}
+
+fn deposit(accounts: &mut [Account; 2], idx: usize, amount: u64) {
+    if !accounts[idx].frozen {
+        accounts[idx].balance = accounts[idx].balance.saturating_add(amount);
+    }
+}
+
+#[kani::proof]
+fn check_other_account_unchanged() {
+    let mut accounts: [Account; 2] = kani::any();
+    let other = Snapshot::take(&accounts[1]);
+    deposit(&mut accounts, 0, kani::any());
+    other.assert_unchanged(&accounts[1]);
+}
+
+#[kani::proof]
+fn check_frozen_account_unchanged() {
+    let mut accounts: [Account; 2] = kani::any();
+    kani::assume(accounts[0].frozen);
+    let before = Snapshot::take(&accounts);
+    deposit(&mut accounts, 0, kani::any());
+    assert!(before.is_unchanged(&accounts));
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+fn check_deep_snapshot() {
+    let mut values = vec![kani::any::<u8>(), kani::any()];
+    let before = DeepSnapshot::take(&values);
+    values.reserve(10);
+    before.assert_unchanged(&values);
+    values[1] = values[1].wrapping_add(1);
+    assert!(!before.is_unchanged(&values));
+    assert_eq!(before.value()[0], values[0]);
+}
@@ -0,0 +1,48 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --default-unwind 8
+
+//! Check that a streaming decoder of length-prefixed messages handles every fragmentation of
+//! its input, using a reader with symbolic chunks.
+extern crate kani;
+
+use kani::stream::any_reader;
+use std::io::{self, Read};
+
+/// Decode a message made of a length byte followed by that many bytes.
+fn decode<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut len = [0u8; 1];
+    reader.read_exact(&mut len)?;
+    let mut message = vec![0u8; len[0] as usize];
+    reader.read_exact(&mut message)?;
+    Ok(message)
+}
+
+#[kani::proof]
+fn check_decode_fragmented() {
+    let mut reader = any_reader(4, 2);
+    let content = reader.content().to_vec();
+    match decode(&mut reader) {
+        Ok(message) => {
+            assert_eq!(message.len(), content[0] as usize);
+            assert_eq!(message, content[1..=message.len()]);
+        }
+        Err(err) => {
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+            assert!(content.is_empty() || content.len() <= content[0] as usize);
+        }
+    }
+}
+
+#[kani::proof]
+fn check_chunk_sizes() {
+    let mut reader = any_reader(6, 3);
+    let total = reader.content().len();
+    let mut buf = [0u8; 4];
+    let read = reader.read(&mut buf).unwrap();
+    assert!(read <= 3);
+    assert_eq!(read == 0, total == 0);
+    assert_eq!(reader.remaining().len(), total - read);
+    kani::cover!(read == 2);
+    assert_eq!(reader.read(&mut []).unwrap(), 0);
+}
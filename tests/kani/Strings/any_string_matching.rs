@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that strings generated by `kani::any_string_matching` satisfy the pattern.
+
+#[kani::proof]
+fn check_identifier() {
+    let id = kani::any_string_matching("[a-z_][a-z0-9_]*", 4);
+    assert!(id.len() <= 4);
+    assert!(!id.is_empty());
+    assert!(!id.starts_with(|c: char| c.is_ascii_digit()));
+    assert!(id.bytes().all(|c| c == b'_' || c.is_ascii_lowercase() || c.is_ascii_digit()));
+}
+
+#[kani::proof]
+fn check_version() {
+    let version = kani::any_string_matching(r"\d{1,2}\.\d", 4);
+    let (major, minor) = version.split_once('.').unwrap();
+    assert!(major.parse::<u8>().unwrap() < 100);
+    assert!(minor.len() == 1);
+    kani::cover!(version == "10.2");
+}
+
+#[kani::proof]
+fn check_alternation() {
+    let flag = kani::any_string_matching("--(on|off)", 5);
+    assert!(flag == "--on" || flag == "--off");
+}
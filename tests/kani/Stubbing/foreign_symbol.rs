@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! Check that a foreign function can be stubbed by its symbol name, which
+//! replaces every declaration of the function.
+
+mod sensor {
+    extern "C" {
+        pub fn read_sensor(channel: u32) -> i32;
+    }
+}
+
+mod legacy {
+    extern "C" {
+        #[link_name = "read_sensor"]
+        pub fn read_channel(channel: u32) -> i32;
+    }
+}
+
+fn read_sensor_model(channel: u32) -> i32 {
+    kani::assume(channel < 4);
+    channel as i32 * 10
+}
+
+#[kani::proof]
+#[kani::stub(read_sensor, read_sensor_model)]
+fn check_all_declarations() {
+    let channel: u32 = kani::any();
+    let value = unsafe { sensor::read_sensor(channel) };
+    assert_eq!(value % 10, 0);
+    assert_eq!(unsafe { legacy::read_channel(2) }, 20);
+}
+
+#[kani::proof]
+#[kani::stub(read_sensor, read_sensor_model)]
+fn check_function_pointer() {
+    let read: unsafe extern "C" fn(u32) -> i32 = sensor::read_sensor;
+    assert_eq!(unsafe { read(1) }, 10);
+}
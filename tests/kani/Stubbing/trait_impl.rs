@@ -0,0 +1,70 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness check -Z stubbing
+//
+//! This tests stubbing all the methods of a trait implementation at once.
+
+trait Sensor {
+    fn read(&self) -> u32;
+    fn calibrate(&mut self, offset: u32);
+    fn describe(&self) -> &'static str {
+        "sensor"
+    }
+}
+
+struct Hardware {
+    offset: u32,
+}
+
+impl Sensor for Hardware {
+    fn read(&self) -> u32 {
+        unimplemented!("talks to the device")
+    }
+
+    fn calibrate(&mut self, _offset: u32) {
+        unimplemented!("talks to the device")
+    }
+}
+
+struct Other;
+
+impl Sensor for Other {
+    fn read(&self) -> u32 {
+        7
+    }
+
+    fn calibrate(&mut self, _offset: u32) {}
+}
+
+/// The replacement methods take the original `Self` type.
+struct MockHardware;
+
+impl MockHardware {
+    fn read(hw: &Hardware) -> u32 {
+        let value: u32 = kani::any();
+        kani::assume(value < 100);
+        value + hw.offset
+    }
+
+    fn calibrate(hw: &mut Hardware, offset: u32) {
+        hw.offset = offset;
+    }
+}
+
+fn measure(sensor: &dyn Sensor) -> u32 {
+    sensor.read()
+}
+
+#[kani::proof]
+#[kani::stub_trait_impl(impl Sensor for Hardware, MockHardware)]
+fn check_hardware() {
+    let mut hw = Hardware { offset: 0 };
+    hw.calibrate(1000);
+    let value = hw.read();
+    assert!(value >= 1000 && value < 1100);
+    assert!(measure(&hw) >= 1000);
+    // Default methods and other implementations of the trait are left untouched.
+    assert_eq!(hw.describe(), "sensor");
+    assert_eq!(Other.read(), 7);
+}
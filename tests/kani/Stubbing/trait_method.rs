@@ -0,0 +1,65 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness check -Z stubbing
+//
+//! This tests stubbing a trait method of a type with a qualified path, both when the
+//! implementation defines the method and when it inherits the default method of the trait.
+
+trait Sensor {
+    fn read(&self) -> u32;
+    fn average(&self) -> u32 {
+        (self.read() + self.read()) / 2
+    }
+}
+
+struct Hardware;
+
+impl Sensor for Hardware {
+    fn read(&self) -> u32 {
+        unimplemented!("talks to the device")
+    }
+}
+
+struct Other;
+
+impl Sensor for Other {
+    fn read(&self) -> u32 {
+        10
+    }
+}
+
+fn read_stub(_hw: &Hardware) -> u32 {
+    kani::any_where(|value| *value < 100)
+}
+
+fn average_stub(_hw: &Hardware) -> u32 {
+    42
+}
+
+fn generic_average<S: Sensor>(sensor: &S) -> u32 {
+    sensor.average()
+}
+
+fn dyn_average(sensor: &dyn Sensor) -> u32 {
+    sensor.average()
+}
+
+#[kani::proof]
+#[kani::stub(<Hardware as Sensor>::read, read_stub)]
+fn check_impl_method() {
+    assert!(Hardware.read() < 100);
+    assert!(dyn_average(&Hardware) < 100);
+    assert_eq!(Other.read(), 10);
+}
+
+#[kani::proof]
+#[kani::stub(<Hardware as Sensor>::average, average_stub)]
+fn check_default_method() {
+    assert_eq!(Hardware.average(), 42);
+    assert_eq!(generic_average(&Hardware), 42);
+    assert_eq!(dyn_average(&Hardware), 42);
+    // The default method is left untouched for the other implementations.
+    assert_eq!(Other.average(), 10);
+    assert_eq!(dyn_average(&Other), 10);
+}
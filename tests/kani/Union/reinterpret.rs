@@ -0,0 +1,54 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that reading a union field other than the one that was written reinterprets the bits
+//! of the written field.
+
+#[repr(C)]
+union Bits {
+    float: f32,
+    int: u32,
+}
+
+/// Like the unions of C libraries, e.g., `in6_addr`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+union Addr {
+    bytes: [u8; 4],
+    words: [u16; 2],
+    word: u32,
+}
+
+#[kani::proof]
+fn check_float_bits() {
+    let float: f32 = kani::any();
+    kani::assume(float.is_finite());
+    let bits = Bits { float };
+    assert_eq!(unsafe { bits.int }, float.to_bits());
+}
+
+#[kani::proof]
+fn check_byte_order() {
+    let word: u32 = kani::any();
+    let addr = Addr { word };
+    let bytes = unsafe { addr.bytes };
+    assert_eq!(bytes, word.to_ne_bytes());
+    let words = unsafe { addr.words };
+    assert_eq!(words[0].to_ne_bytes(), [bytes[0], bytes[1]]);
+}
+
+#[kani::proof]
+fn check_write_field() {
+    let mut addr = Addr { word: 0 };
+    let byte: u8 = kani::any();
+    unsafe { addr.bytes[0] = byte };
+    // The bytes of `word` that `bytes[0]` doesn't overlap are still initialized.
+    assert_eq!(unsafe { addr.word }.to_ne_bytes(), [byte, 0, 0, 0]);
+}
+
+#[kani::proof]
+fn check_copy() {
+    let addr = Addr { bytes: kani::any() };
+    let copy = addr;
+    assert_eq!(unsafe { copy.word }, u32::from_ne_bytes(unsafe { addr.bytes }));
+}
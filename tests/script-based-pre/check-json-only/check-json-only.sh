@@ -0,0 +1,18 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that the standard output is only the JSON report with `--output-format json`, even when
+# the harnesses have stubs and failures that Kani reports on.
+
+set -eu
+
+KANI_OUTPUT=`kani stubs.rs --output-format json --enable-unstable --enable-stubbing \
+    --suggest-assumptions -Z suggest-assumptions 2>/dev/null || true`
+
+if echo "${KANI_OUTPUT}" | python3 -c "import json, sys; json.load(sys.stdin)"; then
+    echo "success: \`--output-format json\` only printed the JSON report"
+else
+    echo "error: \`--output-format json\` printed something else than the JSON report"
+    exit 1
+fi
@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::ptr;
+
+struct Node {
+    value: u8,
+    next: *mut Node,
+}
+
+fn new_node(value: u8) -> *mut Node {
+    Box::into_raw(Box::new(Node { value, next: ptr::null_mut() }))
+}
+
+/// Links two heap nodes into a cycle, and fails on the cycle.
+#[kani::proof]
+fn check_cycle() {
+    let first = new_node(kani::any());
+    let second = new_node(kani::any());
+    unsafe {
+        (*first).next = second;
+        (*second).next = first;
+        assert!((*(*first).next).next.is_null(), "{}", (*second).value);
+    }
+}
+
+/// A harness that succeeds, for which no heap graph is generated.
+#[kani::proof]
+fn check_no_cycle() {
+    let first = new_node(0);
+    unsafe {
+        assert!((*first).next.is_null());
+    }
+}
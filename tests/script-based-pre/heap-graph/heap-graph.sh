@@ -0,0 +1,24 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that `--heap-graph` draws the heap objects (`dynamic_object$N`) that CBMC allocates for
+# `Box`, and the cycle between them, but only for the harness that fails.
+
+set -eu
+
+OUT_DIR=heap-graph-out
+rm -rf ${OUT_DIR}
+
+kani cycle.rs --target-dir ${OUT_DIR} --heap-graph -Z heap-graph || true
+
+# The number of each object depends on the allocations of CBMC, so only the shape is checked.
+cat ${OUT_DIR}/report-*check_cycle*/heap-graph.dot | sed -E 's/dynamic_object\$[0-9]+/dynamic_object$N/g'
+
+if ls ${OUT_DIR}/report-*check_no_cycle*/heap-graph.dot 2>/dev/null; then
+    echo "error: a heap graph was generated for a successful harness"
+else
+    echo "success: no heap graph for the successful harness"
+fi
+
+rm -rf ${OUT_DIR}
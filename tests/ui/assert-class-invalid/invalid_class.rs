@@ -0,0 +1,12 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --only-codegen
+
+//! Check that Kani rejects property classes that are not valid or that are used by Kani itself.
+
+#[kani::proof]
+fn check_invalid_classes() {
+    let x: u8 = kani::any();
+    kani::assert_class!("My Class", x > 0);
+    kani::assert_class!("cover", x > 1);
+}
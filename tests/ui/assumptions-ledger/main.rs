@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --output-format json --no-overflow-checks --enable-unstable --enable-stubbing
+//! Check that the JSON report lists the assumptions of each harness.
+
+fn read_sensor() -> u32 {
+    unimplemented!()
+}
+
+fn mock_read_sensor() -> u32 {
+    kani::any()
+}
+
+#[kani::proof]
+#[kani::stub(read_sensor, mock_read_sensor)]
+fn check_sensor() {
+    let value = read_sensor();
+    kani::assume(value < 1000);
+    assert!(value * 2 < 2000);
+}
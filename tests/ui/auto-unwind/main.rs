@@ -0,0 +1,36 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --auto-unwind --auto-unwind-max 16 -Z auto-unwind
+//! Check that `--auto-unwind` finds the smallest sufficient bound of each harness, and that it
+//! reports when no bound up to `--auto-unwind-max` is enough.
+
+fn sum(values: &[u8]) -> u32 {
+    let mut total = 0;
+    for value in values {
+        total += *value as u32;
+    }
+    total
+}
+
+#[kani::proof]
+fn check_sum() {
+    let values: [u8; 5] = kani::any();
+    assert!(sum(&values) <= 5 * 255);
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+fn check_sum_from_attribute() {
+    let values: [u8; 9] = kani::any();
+    assert!(sum(&values) <= 9 * 255);
+}
+
+#[kani::proof]
+fn check_unbounded() {
+    let limit: u32 = kani::any();
+    let mut count = 0;
+    while count < limit {
+        count += 1;
+    }
+    assert_eq!(count, limit);
+}
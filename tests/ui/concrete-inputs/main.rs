@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z concrete-inputs --concrete divisor=0 --concrete strict=true --concrete len=3
+//! Check that `--concrete` fixes the inputs of the harnesses that it names, and that the other
+//! inputs stay symbolic.
+
+fn checked_div(dividend: u32, divisor: u32, strict: bool) -> Option<u32> {
+    if strict && divisor == 0 { None } else { Some(dividend / divisor) }
+}
+
+#[kani::proof]
+fn check_div() {
+    let dividend: u32 = kani::any();
+    let divisor: u32 = kani::any();
+    let strict: bool = kani::any();
+    // The division by zero is unreachable because `strict` is fixed to `true`.
+    assert!(checked_div(dividend, divisor, strict).is_none());
+}
+
+#[kani::proof]
+fn check_symbolic() {
+    let value: u8 = kani::any();
+    kani::cover!(value == 7);
+}
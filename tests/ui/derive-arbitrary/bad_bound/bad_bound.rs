@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that Kani rejects field annotations other than `#[kani(bound = N)]`.
+
+extern crate kani;
+
+#[derive(kani::Arbitrary)]
+struct Buffer {
+    #[kani(max_len = 4)]
+    data: Vec<u8>,
+}
+
+#[kani::proof]
+fn check_buffer() {
+    let buffer: Buffer = kani::any();
+    assert!(buffer.data.len() <= 4);
+}
@@ -0,0 +1,14 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `transparent` annotations can only be used on structs with a single field.
+
+#[derive(kani::Arbitrary)]
+#[kani(transparent)]
+enum Wrapper {
+    Value(u8),
+}
+
+#[kani::proof]
+fn dead_harness() {
+    panic!("This shouldn't compile");
+}
@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `assume` annotations can't be used on unnamed fields, which they can't refer to.
+
+#[derive(kani::Arbitrary)]
+struct Percent(#[kani(assume = "true")] u8);
+
+#[kani::proof]
+fn dead_harness() {
+    panic!("This shouldn't compile");
+}
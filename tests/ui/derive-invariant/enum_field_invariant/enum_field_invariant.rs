@@ -0,0 +1,14 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the invariant of an enum can't be declared on the fields of its variants.
+
+#[derive(kani::Invariant)]
+enum Reading {
+    Celsius(#[kani(invariant = "*self >= -273")] i32),
+    Unknown,
+}
+
+#[kani::proof]
+fn dead_harness() {
+    panic!("This shouldn't compile");
+}
@@ -0,0 +1,37 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the derived `Arbitrary` implementation of a type with `invariant` annotations only
+//! generates values that satisfy the invariant, and that the invariant can be asserted.
+
+#[derive(kani::Arbitrary, kani::Invariant)]
+#[kani(invariant = "self.len <= self.cap")]
+struct Buffer {
+    #[kani(invariant = "*cap <= 16")]
+    cap: usize,
+    len: usize,
+}
+
+impl Buffer {
+    fn push(&mut self) {
+        self.len += 1;
+    }
+}
+
+#[kani::proof]
+fn check_buffer_invariant() {
+    let mut buffer: Buffer = kani::any();
+    assert!(buffer.len <= buffer.cap);
+    assert!(buffer.cap <= 16);
+    kani::cover!(buffer.len < buffer.cap);
+    if buffer.len < buffer.cap {
+        buffer.push();
+        kani::assert_invariant(&buffer);
+    }
+}
+
+#[kani::proof]
+fn check_broken_invariant() {
+    let mut buffer: Buffer = kani::any();
+    buffer.push();
+    kani::assert_invariant(&buffer);
+}
@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --harness check --exclude-harness check_slow --exclude-harness second::
+//! Ensure that `--exclude-harness` removes harnesses from the selection.
+
+mod first {
+    #[kani::proof]
+    fn check_foo() {
+        assert!(1 == 1);
+    }
+
+    /// A harness that will fail verification if it is run.
+    #[kani::proof]
+    fn check_slow() {
+        assert!(1 == 2);
+    }
+}
+
+mod second {
+    /// A harness that will fail verification if it is run.
+    #[kani::proof]
+    fn check_foo() {
+        assert!(2 == 3);
+    }
+}
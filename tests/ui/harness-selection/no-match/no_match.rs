@@ -0,0 +1,9 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --harness-pattern ^check_.*_missing$
+//! Ensure that Kani fails if the harness selection is empty.
+
+#[kani::proof]
+fn check_foo() {
+    assert!(1 == 1);
+}
@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --harness-pattern ^first::check_ --harness-pattern ::verify_.*_ok$
+//! Ensure that harnesses can be selected with regular expressions.
+
+mod first {
+    #[kani::proof]
+    fn check_foo() {
+        assert!(1 == 1);
+    }
+
+    #[kani::proof]
+    fn check_bar() {
+        assert!(2 == 2);
+    }
+
+    /// A harness that will fail verification if it is run.
+    #[kani::proof]
+    fn ignore_first() {
+        assert!(1 == 2);
+    }
+}
+
+mod second {
+    #[kani::proof]
+    fn verify_foo_ok() {
+        assert!(3 == 3);
+    }
+
+    /// A harness that will fail verification if it is run.
+    #[kani::proof]
+    fn verify_foo_fail() {
+        assert!(3 == 4);
+    }
+
+    /// A harness that will fail verification if it is run.
+    #[kani::proof]
+    fn check_foo() {
+        assert!(3 == 5);
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --only-codegen
+//! Check that Kani warns about symbolic values that are large enough to make verification
+//! intractable, including the ones created by derived `Arbitrary` implementations, and that it
+//! doesn't warn about small ones.
+
+#[derive(kani::Arbitrary)]
+struct Page {
+    header: u32,
+    data: Box<[u8; 2048]>,
+}
+
+#[kani::proof]
+fn check_buffer() {
+    let buffer: [u8; 4096] = kani::any();
+    let small: [u8; 16] = kani::any();
+    assert!(buffer.len() > small.len());
+}
+
+#[kani::proof]
+fn check_page() {
+    let page: Page = kani::any();
+    assert_eq!(page.data.len(), 2048);
+}
@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that a harness can be verified one path at a time.
+
+#[kani::proof]
+#[kani::path_strategy(lifo)]
+fn check() {
+    let x: u8 = kani::any();
+    let y = if x < 10 {
+        x * 2
+    } else if x < 100 {
+        x - 10
+    } else {
+        x / 2
+    };
+    assert!(y < 128);
+}
@@ -0,0 +1,13 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --path-strategy fifo
+
+//! Checks that `--path-strategy` overrides the `path_strategy` attribute.
+
+#[kani::proof]
+#[kani::path_strategy(merge)]
+fn check() {
+    let x: bool = kani::any();
+    let y = if x { 1 } else { 2 };
+    assert!(y > 0);
+}
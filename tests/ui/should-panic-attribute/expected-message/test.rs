@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// compile-flags: -Copt-level=1
+//! Checks that verification passes when `#[kani::should_panic(expected = ..)]`
+//! is used and all failures encountered are the expected panic, either by
+//! message or by property name.
+
+fn divide(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        panic!("division by zero: {a} / 0");
+    }
+    a / b
+}
+
+#[kani::proof]
+#[kani::should_panic(expected = "division by zero")]
+fn check_message() {
+    divide(kani::any(), 0);
+}
+
+#[kani::proof]
+#[kani::should_panic(expected = "check_name.assertion.1")]
+fn check_name() {
+    let x: u8 = kani::any();
+    assert!(x > 200);
+}
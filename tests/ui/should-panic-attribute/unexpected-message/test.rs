@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// compile-flags: -Copt-level=1
+//! Checks that verification fails when `#[kani::should_panic(expected = ..)]`
+//! is used and some of the panics encountered aren't the expected one.
+
+#[kani::proof]
+#[kani::should_panic(expected = "panicked on the `if` branch!")]
+fn check() {
+    if kani::any() {
+        panic!("panicked on the `if` branch!");
+    } else {
+        panic!("panicked on the `else` branch!");
+    }
+}
@@ -0,0 +1,12 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that Kani errors out before verifying any harness if the program of an SMT solver
+//! command is not found
+
+#[kani::proof]
+#[kani::solver(smt2 = "non_existing_smt_solver --incremental")]
+fn check() {}
+
+#[kani::proof]
+fn check_default() {}
@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness check_mismatch -Z stubbing
+//
+//! Check that Kani rejects a stub of a default trait method whose signature doesn't match the
+//! method for the type.
+
+trait Sensor {
+    fn average(&self) -> u32 {
+        0
+    }
+}
+
+struct Hardware;
+
+impl Sensor for Hardware {}
+
+fn average_stub(_hw: &Hardware) -> u64 {
+    42
+}
+
+#[kani::proof]
+#[kani::stub(<Hardware as Sensor>::average, average_stub)]
+fn check_mismatch() {
+    assert_eq!(Hardware.average(), 42);
+}
@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! Check that Kani reports an error when the type doesn't implement the trait to be stubbed.
+
+trait Counter {
+    fn get(&self) -> u32;
+}
+
+struct Unrelated;
+
+mod mock {
+    pub fn get(_counter: &super::Unrelated) -> u32 {
+        0
+    }
+}
+
+#[kani::proof]
+#[kani::stub_trait_impl(impl Counter for Unrelated, mock)]
+fn check_missing_impl() {
+    assert_eq!(mock::get(&Unrelated), 0);
+}
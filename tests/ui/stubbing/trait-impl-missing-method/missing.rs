@@ -0,0 +1,37 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! Check that Kani reports the methods of a trait implementation that have no replacement.
+
+trait Counter {
+    fn get(&self) -> u32;
+    fn increment(&mut self);
+}
+
+struct Real(u32);
+
+impl Counter for Real {
+    fn get(&self) -> u32 {
+        self.0
+    }
+
+    fn increment(&mut self) {
+        self.0 += 1;
+    }
+}
+
+mod mock {
+    pub fn get(_counter: &super::Real) -> u32 {
+        0
+    }
+}
+
+#[kani::proof]
+#[kani::stub_trait_impl(impl Counter for Real, mock)]
+fn check_missing_method() {
+    let mut counter = Real(0);
+    counter.increment();
+    assert_eq!(counter.get(), 0);
+}
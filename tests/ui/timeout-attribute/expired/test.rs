@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z harness-timeout
+//! Check that a harness that doesn't finish within its `#[kani::timeout]` is reported as TIMEOUT,
+//! and that it makes Kani fail without affecting the other harnesses.
+
+#[kani::proof]
+#[kani::timeout(1)]
+fn check_distributive() {
+    // Non-linear arithmetic on 64 bits is very hard for SAT solvers.
+    let a: u64 = kani::any();
+    let b: u64 = kani::any();
+    let c: u64 = kani::any();
+    assert_eq!(
+        a.wrapping_mul(b.wrapping_add(c)),
+        a.wrapping_mul(b).wrapping_add(a.wrapping_mul(c))
+    );
+}
+
+#[kani::proof]
+#[kani::timeout(600)]
+fn check_fast() {
+    let x: u8 = kani::any();
+    assert!(x / 2 <= x);
+}
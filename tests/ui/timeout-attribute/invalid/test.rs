@@ -0,0 +1,7 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z harness-timeout
+
+#[kani::proof]
+#[kani::timeout(0)]
+fn check() {}
@@ -5,12 +5,16 @@
 //! We use Rust here just to aid in making the "script" more robust.
 //!
 //! Run with `cargo run -p build-kani -- release` and this will produce
-//! (e.g.) `kani-1.0-x86_64-unknown-linux-gnu.tar.gz`.
+//! (e.g.) `kani-1.0-x86_64-unknown-linux-gnu.tar.gz` and its checksum file
+//! `kani-1.0-x86_64-unknown-linux-gnu.tar.gz.sha256`.
 
 mod parser;
 mod sysroot;
 
-use crate::sysroot::{build_bin, build_lib, kani_playback_lib, kani_sysroot_lib};
+use crate::sysroot::{
+    build_bin, build_lib, build_target_lib, kani_playback_lib, kani_sysroot_lib,
+    kani_targets_sysroot,
+};
 use anyhow::{bail, Result};
 use clap::Parser;
 use std::{ffi::OsString, path::Path, process::Command};
@@ -19,7 +23,11 @@ fn main() -> Result<()> {
     let args = parser::ArgParser::parse();
 
     match args.subcommand {
-        parser::Commands::BuildDev(build_parser) => build_lib(&build_bin(&build_parser.args)?),
+        parser::Commands::BuildDev(build_parser) => {
+            let bin_folder = build_bin(&build_parser.args)?;
+            build_lib(&bin_folder)?;
+            build_parser.targets.iter().try_for_each(|target| build_target_lib(&bin_folder, target))
+        }
         parser::Commands::Bundle(bundle_parser) => {
             let version_string = bundle_parser.version;
             let kani_string = format!("kani-{version_string}");
@@ -38,6 +46,7 @@ fn main() -> Result<()> {
             // cbmc-viewer isn't bundled, it's pip install'd on first-time setup
 
             create_release_bundle(dir, &bundle_name)?;
+            create_bundle_checksum(&bundle_name)?;
 
             std::fs::remove_dir_all(dir)?;
 
@@ -96,6 +105,9 @@ fn bundle_kani(dir: &Path) -> Result<()> {
     // 4. Pre-compiled library files
     cp_dir(&kani_sysroot_lib(), dir)?;
     cp_dir(&kani_playback_lib().parent().unwrap(), dir)?;
+    if kani_targets_sysroot().exists() {
+        cp_dir(&kani_targets_sysroot(), dir)?;
+    }
 
     // 5. Record the exact toolchain we use
     std::fs::write(dir.join("rust-toolchain-version"), env!("RUSTUP_TOOLCHAIN"))?;
@@ -151,6 +163,17 @@ fn create_release_bundle(dir: &Path, bundle: &str) -> Result<()> {
     Command::new("tar").args(["zcf", bundle]).arg(dir).run()
 }
 
+/// Record the SHA-256 digest of `bundle` in `<bundle>.sha256`, which is published alongside
+/// the bundle so `cargo kani setup` can verify what it downloads.
+fn create_bundle_checksum(bundle: &str) -> Result<()> {
+    let output = Command::new("shasum").args(["-a", "256", bundle]).output()?;
+    if !output.status.success() {
+        bail!("Failed to compute checksum of {bundle}");
+    }
+    std::fs::write(format!("{bundle}.sha256"), output.stdout)?;
+    Ok(())
+}
+
 /// Helper trait to fallibly run commands
 trait AutoRun {
     fn run(&mut self) -> Result<()>;
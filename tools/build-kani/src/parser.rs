@@ -14,6 +14,10 @@ pub struct ArgParser {
 
 #[derive(Args, Debug, Eq, PartialEq)]
 pub struct BuildDevParser {
+    /// Also build Kani's libraries for the given target, e.g., `thumbv7em-none-eabihf`, so
+    /// crates can be verified with `--target`. Only `core` and `alloc` are built for it.
+    #[clap(long = "target", value_name = "TRIPLE")]
+    pub targets: Vec<String>,
     /// Arguments to be passed down to cargo when building cargo binaries.
     #[clap(value_name = "ARG", allow_hyphen_values = true)]
     pub args: Vec<String>,
@@ -9,7 +9,8 @@
 //! Rustc expects the sysroot to have a specific folder layout:
 //! `{SYSROOT}/rustlib/<target-triplet>/lib/<libraries>`
 //!
-//! Note: We don't cross-compile. Target is the same as the host.
+//! Note: The folders above are built for the host. The libraries for other targets, which are
+//! built on demand, are kept in a sysroot of their own: `targets/<target-triplet>/lib/`.
 
 use crate::{cp, AutoRun};
 use anyhow::{bail, format_err, Result};
@@ -55,6 +56,11 @@ pub fn kani_playback_lib() -> PathBuf {
     path_buf!(kani_sysroot(), "playback/lib")
 }
 
+/// Returns the path to the folder with the sysroots of the targets other than the host.
+pub fn kani_targets_sysroot() -> PathBuf {
+    path_buf!(kani_sysroot(), "targets")
+}
+
 /// Returns the path to where Kani's pre-compiled binaries are stored.
 fn kani_sysroot_bin() -> PathBuf {
     path_buf!(kani_sysroot(), "bin")
@@ -75,7 +81,38 @@ fn build_verification_lib(compiler_path: &Path) -> Result<()> {
     let extra_args =
         ["-Z", "build-std=panic_abort,std,test", "--config", "profile.dev.panic=\"abort\""];
     let compiler_args = ["--kani-compiler", "-Cllvm-args=--ignore-global-asm --build-std"];
-    build_kani_lib(compiler_path, &kani_sysroot_lib(), &extra_args, &compiler_args)
+    build_kani_lib(
+        compiler_path,
+        &kani_sysroot_lib(),
+        env!("TARGET"),
+        &["std", "kani", "kani_macros"],
+        &extra_args,
+        &compiler_args,
+    )
+}
+
+/// Build the `targets/<target>/lib/` folder used to verify crates for `target` instead of the
+/// host. Most of these targets don't have a standard library, so this only includes `core`,
+/// `alloc` and the Kani library without its `std` feature.
+pub fn build_target_lib(bin_folder: &Path, target: &str) -> Result<()> {
+    let compiler_path = bin_folder.join("kani-compiler");
+    let extra_args = [
+        "--no-default-features",
+        "-Z",
+        "build-std=core,alloc",
+        "--config",
+        "profile.dev.panic=\"abort\"",
+    ];
+    let compiler_args = ["--kani-compiler", "-Cllvm-args=--ignore-global-asm --build-std"];
+    let output_path = path_buf!(kani_targets_sysroot(), target, "lib");
+    build_kani_lib(
+        &compiler_path,
+        &output_path,
+        target,
+        &["kani", "kani_macros"],
+        &extra_args,
+        &compiler_args,
+    )
 }
 
 /// Build the `lib-playback/` folder that will be used during counter example playback.
@@ -83,26 +120,28 @@ fn build_verification_lib(compiler_path: &Path) -> Result<()> {
 fn build_playback_lib(compiler_path: &Path) -> Result<()> {
     let extra_args =
         ["--features=std/concrete_playback,kani/concrete_playback", "-Z", "build-std=std,test"];
-    build_kani_lib(compiler_path, &kani_playback_lib(), &extra_args, &[])
+    build_kani_lib(
+        compiler_path,
+        &kani_playback_lib(),
+        env!("TARGET"),
+        &["std", "kani", "kani_macros"],
+        &extra_args,
+        &[],
+    )
 }
 
 fn build_kani_lib(
     compiler_path: &Path,
     output_path: &Path,
+    target: &str,
+    packages: &[&str],
     extra_cargo_args: &[&str],
     extra_rustc_args: &[&str],
 ) -> Result<()> {
     // Run cargo build with -Z build-std
-    let target = env!("TARGET");
     let target_dir = env!("KANI_BUILD_LIBS");
     let args = [
         "build",
-        "-p",
-        "std",
-        "-p",
-        "kani",
-        "-p",
-        "kani_macros",
         "-Z",
         "unstable-options",
         "--target-dir",
@@ -130,6 +169,7 @@ fn build_kani_lib(
         .env("CARGO_ENCODED_RUSTFLAGS", rustc_args.join("\x1f"))
         .env("RUSTC", compiler_path)
         .args(args)
+        .args(packages.iter().flat_map(|package| ["-p", package]))
         .args(extra_cargo_args)
         .stdout(Stdio::piped())
         .spawn()